@@ -200,6 +200,12 @@ impl BidiContext {
         self.reorder_nsm = reorder;
     }
 
+    /// Returns the embedding level of the paragraph that was resolved
+    /// by the most recent call to `resolve_paragraph`
+    pub fn base_level(&self) -> Level {
+        self.base_level
+    }
+
     /// Produces a sequence of `BidiRun` structs that represent runs of
     /// text and their direction (and level) across the entire paragraph.
     pub fn runs<'a>(&'a self) -> impl Iterator<Item = BidiRun> + 'a {
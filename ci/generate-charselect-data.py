@@ -0,0 +1,99 @@
+#!/usr/bin/env python3
+"""
+Generates wezterm-gui/src/overlay/charselect/data.rs, the built-in
+character sets offered by the CharSelect overlay.
+The emoji are taken from termwiz/data/emoji-data.txt and the names
+are taken from the unicode database that ships with python.
+Run this from the top of the repo.
+"""
+import unicodedata
+
+EMOJI_DATA = "termwiz/data/emoji-data.txt"
+OUTPUT = "wezterm-gui/src/overlay/charselect/data.rs"
+
+# name, ranges of codepoints
+BLOCKS = [
+    ("ARROWS", [(0x2190, 0x21FF), (0x27F0, 0x27FF), (0x2900, 0x297F)]),
+    ("MATH", [(0x2200, 0x22FF), (0x00B1, 0x00B1), (0x00D7, 0x00D7), (0x00F7, 0x00F7)]),
+    ("GREEK", [(0x0391, 0x03A9), (0x03B1, 0x03C9)]),
+    ("CURRENCY", [(0x0024, 0x0024), (0x00A2, 0x00A5), (0x20A0, 0x20C0)]),
+    ("BOX_DRAWING", [(0x2500, 0x259F)]),
+    ("PUNCTUATION", [(0x2010, 0x205E), (0x00A1, 0x00A1), (0x00A7, 0x00A7),
+                     (0x00AB, 0x00AB), (0x00B6, 0x00B7), (0x00BB, 0x00BB), (0x00BF, 0x00BF)]),
+]
+
+
+def parse_emoji_data():
+    props = {}
+    with open(EMOJI_DATA) as f:
+        for line in f:
+            line = line.split("#", 1)[0].strip()
+            if not line:
+                continue
+            cps, prop = [x.strip() for x in line.split(";")]
+            if ".." in cps:
+                start, end = [int(x, 16) for x in cps.split("..")]
+            else:
+                start = end = int(cps, 16)
+            for cp in range(start, end + 1):
+                props.setdefault(cp, set()).add(prop)
+    return props
+
+
+def name_of(cp):
+    try:
+        return unicodedata.name(chr(cp)).lower()
+    except ValueError:
+        return None
+
+
+def rust_str(text):
+    return '"' + "".join("\\u{%x}" % ord(c) if ord(c) > 0x7e or c in '"\\' else c
+                         for c in text) + '"'
+
+
+def entry(text, name, skin_tones=False):
+    return "    CharEntry { text: %s, name: %s, skin_tones: %s },\n" % (
+        rust_str(text), rust_str(name), "true" if skin_tones else "false")
+
+
+def main():
+    props = parse_emoji_data()
+    out = [
+        "// DO NOT EDIT THIS FILE. IT WAS AUTOMATICALLY GENERATED BY:\n",
+        "//\n",
+        "//   ci/generate-charselect-data.py\n",
+        "//\n",
+        "// Unicode version: %s\n\n" % unicodedata.unidata_version,
+        "use super::CharEntry;\n\n",
+        "pub const EMOJI: &[CharEntry] = &[\n",
+    ]
+    for cp in sorted(props):
+        p = props[cp]
+        if "Emoji" not in p or "Emoji_Component" in p or cp < 0x80:
+            continue
+        name = name_of(cp)
+        if name is None:
+            continue
+        text = chr(cp)
+        if "Emoji_Presentation" not in p:
+            # Request the emoji presentation
+            text += "️"
+        out.append(entry(text, name, "Emoji_Modifier_Base" in p))
+    out.append("];\n")
+
+    for label, ranges in BLOCKS:
+        out.append("\npub const %s: &[CharEntry] = &[\n" % label)
+        for start, end in ranges:
+            for cp in range(start, end + 1):
+                name = name_of(cp)
+                if name is None:
+                    continue
+                out.append(entry(chr(cp), name))
+        out.append("];\n")
+
+    with open(OUTPUT, "w") as f:
+        f.write("".join(out))
+
+
+main()
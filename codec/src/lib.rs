@@ -12,7 +12,11 @@
 #![cfg_attr(feature = "cargo-clippy", allow(clippy::range_plus_one))]
 
 use anyhow::{bail, Context as _, Error};
+use config::keyassignment::{PaneDirection, RotationDirection};
+use config::MuxCompression;
 use mux::client::{ClientId, ClientInfo};
+use mux::domain::ConnectionStats;
+use mux::history::{CommandHistoryEntry, CommandHistoryQuery};
 use mux::pane::PaneId;
 use mux::renderable::{RenderableDimensions, StableCursorPosition};
 use mux::tab::{PaneNode, SerdeUrl, SplitRequest, TabId};
@@ -26,6 +30,7 @@ use std::convert::TryInto;
 use std::io::Cursor;
 use std::ops::Range;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use termwiz::hyperlink::Hyperlink;
 use termwiz::image::{ImageData, TextureCoordinate};
@@ -273,49 +278,136 @@ pub struct DecodedPdu {
     pub pdu: Pdu,
 }
 
-/// If the serialized size is larger than this, then we'll consider compressing it
-const COMPRESS_THRESH: usize = 32;
+struct Serialized {
+    data: Vec<u8>,
+    is_compressed: bool,
+    uncompressed_len: usize,
+}
 
-fn serialize<T: serde::Serialize>(t: &T) -> Result<(Vec<u8>, bool), Error> {
+fn serialize<T: serde::Serialize>(
+    t: &T,
+    compression: &MuxCompression,
+) -> Result<Serialized, Error> {
     let mut uncompressed = Vec::new();
     let mut encode = varbincode::Serializer::new(&mut uncompressed);
     t.serialize(&mut encode)?;
-
-    if uncompressed.len() <= COMPRESS_THRESH {
-        return Ok((uncompressed, false));
+    let uncompressed_len = uncompressed.len();
+
+    if !compression.enabled || uncompressed_len <= compression.threshold {
+        return Ok(Serialized {
+            data: uncompressed,
+            is_compressed: false,
+            uncompressed_len,
+        });
     }
     // It's a little heavy; let's try compressing it
-    let mut compressed = Vec::new();
-    let mut compress = zstd::Encoder::new(&mut compressed, zstd::DEFAULT_COMPRESSION_LEVEL)?;
-    let mut encode = varbincode::Serializer::new(&mut compress);
-    t.serialize(&mut encode)?;
-    drop(encode);
-    compress.finish()?;
+    let compressed = zstd::encode_all(uncompressed.as_slice(), compression.level)?;
 
     log::debug!(
         "serialized+compress len {} vs {}",
         compressed.len(),
-        uncompressed.len()
+        uncompressed_len
     );
 
-    if compressed.len() < uncompressed.len() {
-        Ok((compressed, true))
+    if compressed.len() < uncompressed_len {
+        Ok(Serialized {
+            data: compressed,
+            is_compressed: true,
+            uncompressed_len,
+        })
     } else {
-        Ok((uncompressed, false))
+        Ok(Serialized {
+            data: uncompressed,
+            is_compressed: false,
+            uncompressed_len,
+        })
+    }
+}
+
+/// Counts the bytes that are read through it
+struct CountingReader<R> {
+    inner: R,
+    count: u64,
+}
+
+impl<R: std::io::Read> std::io::Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count += n as u64;
+        Ok(n)
     }
 }
 
+/// Deserializes a PDU, returning it along with its uncompressed size
 fn deserialize<T: serde::de::DeserializeOwned, R: std::io::Read>(
-    mut r: R,
+    r: R,
     is_compressed: bool,
-) -> Result<T, Error> {
+) -> Result<(T, u64), Error> {
     if is_compressed {
-        let mut decompress = zstd::Decoder::new(r)?;
+        let mut decompress = CountingReader {
+            inner: zstd::Decoder::new(r)?,
+            count: 0,
+        };
         let mut decode = varbincode::Deserializer::new(&mut decompress);
-        serde::Deserialize::deserialize(&mut decode).map_err(Into::into)
+        let t = serde::Deserialize::deserialize(&mut decode)?;
+        Ok((t, decompress.count))
     } else {
+        let mut r = CountingReader { inner: r, count: 0 };
         let mut decode = varbincode::Deserializer::new(&mut r);
-        serde::Deserialize::deserialize(&mut decode).map_err(Into::into)
+        let t = serde::Deserialize::deserialize(&mut decode)?;
+        Ok((t, r.count))
+    }
+}
+
+/// Counts the PDUs that are encoded and decoded on a connection,
+/// and their sizes before and after compression
+#[derive(Debug, Default)]
+pub struct CodecStats {
+    pdus_sent: AtomicU64,
+    compressed_pdus_sent: AtomicU64,
+    bytes_sent: AtomicU64,
+    uncompressed_bytes_sent: AtomicU64,
+    pdus_received: AtomicU64,
+    compressed_pdus_received: AtomicU64,
+    bytes_received: AtomicU64,
+    uncompressed_bytes_received: AtomicU64,
+}
+
+impl CodecStats {
+    fn record_sent(&self, serialized: &Serialized) {
+        self.pdus_sent.fetch_add(1, Ordering::Relaxed);
+        if serialized.is_compressed {
+            self.compressed_pdus_sent.fetch_add(1, Ordering::Relaxed);
+        }
+        self.bytes_sent
+            .fetch_add(serialized.data.len() as u64, Ordering::Relaxed);
+        self.uncompressed_bytes_sent
+            .fetch_add(serialized.uncompressed_len as u64, Ordering::Relaxed);
+    }
+
+    fn record_received(&self, decoded: &Decoded, uncompressed_len: u64) {
+        self.pdus_received.fetch_add(1, Ordering::Relaxed);
+        if decoded.is_compressed {
+            self.compressed_pdus_received
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        self.bytes_received
+            .fetch_add(decoded.data.len() as u64, Ordering::Relaxed);
+        self.uncompressed_bytes_received
+            .fetch_add(uncompressed_len, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> ConnectionStats {
+        ConnectionStats {
+            pdus_sent: self.pdus_sent.load(Ordering::Relaxed),
+            compressed_pdus_sent: self.compressed_pdus_sent.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            uncompressed_bytes_sent: self.uncompressed_bytes_sent.load(Ordering::Relaxed),
+            pdus_received: self.pdus_received.load(Ordering::Relaxed),
+            compressed_pdus_received: self.compressed_pdus_received.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            uncompressed_bytes_received: self.uncompressed_bytes_received.load(Ordering::Relaxed),
+        }
     }
 }
 
@@ -335,7 +427,7 @@ macro_rules! pdu {
                     Pdu::Invalid{..} => bail!("attempted to serialize Pdu::Invalid"),
                     $(
                         Pdu::$name(s) => {
-                            let (data, is_compressed) = serialize(s)?;
+                            let Serialized { data, is_compressed, .. } = serialize(s, &MuxCompression::default())?;
                             let encoded_size = encode_raw($vers, serial, &data, is_compressed, w)?;
                             metrics::histogram!("pdu.size", encoded_size as f64, "pdu" => stringify!($name));
                             metrics::histogram!("pdu.size.rate", encoded_size as f64, "pdu" => stringify!($name));
@@ -346,12 +438,27 @@ macro_rules! pdu {
             }
 
             pub async fn encode_async<W: Unpin + AsyncWriteExt>(&self, w: &mut W, serial: u64) -> Result<(), Error> {
+                self.encode_async_with(w, serial, &MuxCompression::default(), None).await
+            }
+
+            /// Encodes the PDU, compressing it according to `compression`,
+            /// and counting it in `stats`
+            pub async fn encode_async_with<W: Unpin + AsyncWriteExt>(
+                &self,
+                w: &mut W,
+                serial: u64,
+                compression: &MuxCompression,
+                stats: Option<&CodecStats>,
+            ) -> Result<(), Error> {
                 match self {
                     Pdu::Invalid{..} => bail!("attempted to serialize Pdu::Invalid"),
                     $(
                         Pdu::$name(s) => {
-                            let (data, is_compressed) = serialize(s)?;
-                            let encoded_size = encode_raw_async($vers, serial, &data, is_compressed, w).await?;
+                            let serialized = serialize(s, compression)?;
+                            if let Some(stats) = stats {
+                                stats.record_sent(&serialized);
+                            }
+                            let encoded_size = encode_raw_async($vers, serial, &serialized.data, serialized.is_compressed, w).await?;
                             metrics::histogram!("pdu.size", encoded_size as f64, "pdu" => stringify!($name));
                             metrics::histogram!("pdu.size.rate", encoded_size as f64, "pdu" => stringify!($name));
                             Ok(())
@@ -369,7 +476,7 @@ macro_rules! pdu {
                             metrics::histogram!("pdu.size.rate", decoded.data.len() as f64, "pdu" => stringify!($name));
                             Ok(DecodedPdu {
                                 serial: decoded.serial,
-                                pdu: Pdu::$name(deserialize(decoded.data.as_slice(), decoded.is_compressed)?)
+                                pdu: Pdu::$name(deserialize(decoded.data.as_slice(), decoded.is_compressed)?.0)
                             })
                         }
                     ,)*
@@ -388,20 +495,40 @@ macro_rules! pdu {
                 where R: std::marker::Unpin,
                       R: AsyncRead,
                       R: std::fmt::Debug
+            {
+                Self::decode_async_with(r, max_serial, None).await
+            }
+
+            /// Decodes a PDU, counting it in `stats`
+            pub async fn decode_async_with<R>(
+                r: &mut R,
+                max_serial: Option<u64>,
+                stats: Option<&CodecStats>,
+            ) -> Result<DecodedPdu, Error>
+                where R: std::marker::Unpin,
+                      R: AsyncRead,
+                      R: std::fmt::Debug
             {
                 let decoded = decode_raw_async(r, max_serial).await.context("decoding a PDU")?;
                 match decoded.ident {
                     $(
                         $vers => {
                             metrics::histogram!("pdu.size", decoded.data.len() as f64, "pdu" => stringify!($name));
+                            let (pdu, uncompressed_len) = deserialize(decoded.data.as_slice(), decoded.is_compressed)?;
+                            if let Some(stats) = stats {
+                                stats.record_received(&decoded, uncompressed_len);
+                            }
                             Ok(DecodedPdu {
                                 serial: decoded.serial,
-                                pdu: Pdu::$name(deserialize(decoded.data.as_slice(), decoded.is_compressed)?)
+                                pdu: Pdu::$name(pdu)
                             })
                         }
                     ,)*
                     _ => {
                         metrics::histogram!("pdu.size", decoded.data.len() as f64, "pdu" => "??");
+                        if let Some(stats) = stats {
+                            stats.record_received(&decoded, decoded.data.len() as u64);
+                        }
                         Ok(DecodedPdu {
                             serial: decoded.serial,
                             pdu: Pdu::Invalid{ident:decoded.ident}
@@ -416,7 +543,7 @@ macro_rules! pdu {
 /// The overall version of the codec.
 /// This must be bumped when backwards incompatible changes
 /// are made to the types and protocol.
-pub const CODEC_VERSION: usize = 25;
+pub const CODEC_VERSION: usize = 43;
 
 // Defines the Pdu enum.
 // Each struct has an explicit identifying number.
@@ -464,6 +591,32 @@ pdu! {
     GetImageCellResponse: 47,
     MovePaneToNewTab: 48,
     MovePaneToNewTabResponse: 49,
+    ReadFileChunk: 50,
+    ReadFileChunkResponse: 51,
+    WriteFileChunk: 52,
+    SubscribeEvents: 53,
+    MuxEvent: 54,
+    GetCommandHistory: 55,
+    GetCommandHistoryResponse: 56,
+    AttachDomainGroup: 57,
+    DetachDomainGroup: 58,
+    ActivatePaneDirection: 59,
+    RotatePanes: 60,
+    ApplyWorkspaceLayout: 61,
+    ExportScrollback: 62,
+    ExportScrollbackResponse: 63,
+    ResumePanes: 64,
+    ResumePanesResponse: 65,
+    RenameWorkspace: 66,
+    MergeWorkspace: 67,
+    DeleteWorkspace: 68,
+    SwapPanes: 69,
+    ReadDir: 70,
+    ReadDirResponse: 71,
+    SetCompression: 72,
+    PerformActions: 73,
+    SplitPaneWithLayout: 74,
+    SplitPaneWithLayoutResponse: 75,
 }
 
 impl Pdu {
@@ -603,6 +756,24 @@ pub struct SplitPane {
     pub move_pane_id: Option<PaneId>,
 }
 
+/// Applies nested splits to a pane.  `splits` is the JSON encoded
+/// list of `config::SplitLayout` that is accepted by
+/// `wezterm cli split-pane --layout`.
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct SplitPaneWithLayout {
+    pub pane_id: PaneId,
+    pub splits: String,
+}
+
+/// The panes that were created, in the order that they were created.
+/// If one of the splits failed, `error` describes why, and `pane_ids`
+/// holds the panes that were created before it.
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct SplitPaneWithLayoutResponse {
+    pub pane_ids: Vec<PaneId>,
+    pub error: Option<String>,
+}
+
 #[derive(Deserialize, Serialize, PartialEq, Debug)]
 pub struct MovePaneToNewTab {
     pub pane_id: PaneId,
@@ -616,6 +787,191 @@ pub struct MovePaneToNewTabResponse {
     pub window_id: WindowId,
 }
 
+/// Reads a portion of a file from the filesystem that hosts
+/// the specified pane.
+/// Relative paths are resolved against the working directory of the pane.
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ReadFileChunk {
+    pub pane_id: PaneId,
+    pub path: String,
+    pub offset: u64,
+    pub max_bytes: usize,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ReadFileChunkResponse {
+    /// An empty data vec indicates the end of the file
+    pub data: Vec<u8>,
+    pub total_size: Option<u64>,
+}
+
+/// Lists the entries of a directory on the filesystem of a domain.
+/// Relative paths are resolved against the home directory of the user.
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ReadDir {
+    pub domain: config::keyassignment::SpawnTabDomain,
+    /// The pane that resolves `CurrentPaneDomain`
+    pub pane_id: Option<PaneId>,
+    pub path: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ReadDirResponse {
+    pub entries: Vec<mux::domain::DirEntry>,
+}
+
+/// Writes a portion of a file to the filesystem that hosts
+/// the specified pane.  Writing at offset 0 creates or truncates the file.
+/// Relative paths are resolved against the working directory of the pane.
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct WriteFileChunk {
+    pub pane_id: PaneId,
+    pub path: String,
+    pub offset: u64,
+    pub data: Vec<u8>,
+    /// The overall size of the file being transferred, if known.
+    /// Used for progress reporting.
+    pub total_size: Option<u64>,
+}
+
+/// The kinds of event that a client can ask to be told about
+/// using `SubscribeEvents`
+#[derive(Deserialize, Serialize, PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum MuxEventKind {
+    PaneOutput,
+    PaneFocused,
+    PaneResized,
+    PaneAdded,
+    PaneRemoved,
+}
+
+/// Asks the server to send a `MuxEvent` to this client each time that
+/// one of the listed kinds of event occurs.
+/// Replaces any earlier subscription made by the client.
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct SubscribeEvents {
+    pub events: Vec<MuxEventKind>,
+}
+
+/// Sent unilaterally to clients that have subscribed to `kind`
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone)]
+pub struct MuxEvent {
+    pub kind: MuxEventKind,
+    pub pane_id: PaneId,
+    /// The new size of the pane, for `PaneResized`
+    pub size: Option<TerminalSize>,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct GetCommandHistory {
+    pub query: CommandHistoryQuery,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct GetCommandHistoryResponse {
+    pub entries: Vec<CommandHistoryEntry>,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ActivatePaneDirection {
+    pub pane_id: PaneId,
+    pub direction: PaneDirection,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct RotatePanes {
+    pub pane_id: PaneId,
+    pub direction: RotationDirection,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct AttachDomainGroup {
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct DetachDomainGroup {
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ApplyWorkspaceLayout {
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ExportScrollback {
+    pub pane_id: PaneId,
+    pub format: mux::export::ExportFormat,
+    pub start_line: Option<isize>,
+    pub end_line: Option<isize>,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ExportScrollbackResponse {
+    pub text: String,
+}
+
+/// Sent by a client that has reconnected to the server, so that the
+/// panes that it already knows about are brought up to date by sending
+/// only what changed since the state that it last received, rather
+/// than their full contents.
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ResumePanes {
+    pub panes: Vec<ResumePane>,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ResumePane {
+    pub pane_id: PaneId,
+    /// The seqno of the last GetPaneRenderChangesResponse that the
+    /// client applied for this pane
+    pub seqno: SequenceNo,
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct ResumePanesResponse {
+    /// The panes that were resumed; the others are synchronized
+    /// in full, as they would be for a new connection
+    pub resumed: Vec<PaneId>,
+}
+
+/// Renames a workspace, failing if the new name is already in use
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct RenameWorkspace {
+    pub old_workspace: String,
+    pub new_workspace: String,
+}
+
+/// Moves the windows of one workspace into another
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct MergeWorkspace {
+    pub from_workspace: String,
+    pub into_workspace: String,
+}
+
+/// Closes all of the windows in a workspace
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct DeleteWorkspace {
+    pub workspace: String,
+}
+
+/// Exchanges the positions of two panes, which may be in different tabs
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct SwapPanes {
+    pub pane_id: PaneId,
+    pub other_pane_id: PaneId,
+}
+
+/// Applies actions to the terminal of a pane, as though they had been
+/// output by the program running in it.  The actions are sent as the
+/// escape sequences that encode them.
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct PerformActions {
+    pub pane_id: PaneId,
+    pub actions: String,
+}
+
 #[derive(Deserialize, Serialize, PartialEq, Debug)]
 pub struct SpawnV2 {
     pub domain: config::keyassignment::SpawnTabDomain,
@@ -655,6 +1011,9 @@ pub struct WriteToPane {
 pub struct SendPaste {
     pub pane_id: PaneId,
     pub data: String,
+    /// Whether the server should emit the `pre-paste` event before
+    /// pasting.  The GUI has already done so for pastes that it makes.
+    pub emit_pre_paste: bool,
 }
 
 #[derive(Deserialize, Serialize, PartialEq, Debug)]
@@ -740,6 +1099,26 @@ pub struct SetClientId {
     pub client_id: ClientId,
 }
 
+/// Sent by the client to select how the server compresses the
+/// PDUs that it sends to that client
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
+pub struct SetCompression {
+    pub enabled: bool,
+    pub threshold: usize,
+    pub level: i32,
+}
+
+/// The zstd compression levels that may be selected by `SetCompression`
+pub const COMPRESSION_LEVELS: std::ops::RangeInclusive<i32> = 1..=22;
+
+impl SetCompression {
+    /// Returns the requested level, clamped to `COMPRESSION_LEVELS`
+    pub fn clamped_level(&self) -> i32 {
+        self.level
+            .clamp(*COMPRESSION_LEVELS.start(), *COMPRESSION_LEVELS.end())
+    }
+}
+
 #[derive(Deserialize, Serialize, PartialEq, Debug)]
 pub struct SetFocusedPane {
     pub pane_id: PaneId,
@@ -1122,4 +1501,74 @@ mod test {
             Pdu::decode(encoded.as_slice()).unwrap()
         );
     }
+
+    #[test]
+    fn test_compression_settings() {
+        let pdu = Pdu::WriteToPane(WriteToPane {
+            pane_id: 1,
+            data: vec![b'a'; 4096],
+        });
+        let encode = |compression: &MuxCompression, stats: &CodecStats| {
+            let mut encoded = Vec::new();
+            smol::block_on(pdu.encode_async_with(&mut encoded, 1, compression, Some(stats)))
+                .unwrap();
+            encoded
+        };
+
+        let stats = CodecStats::default();
+        let compressed = encode(&MuxCompression::default(), &stats);
+        let uncompressed = encode(
+            &MuxCompression {
+                enabled: false,
+                ..MuxCompression::default()
+            },
+            &stats,
+        );
+        let below_threshold = encode(
+            &MuxCompression {
+                threshold: 8192,
+                ..MuxCompression::default()
+            },
+            &stats,
+        );
+        assert!(compressed.len() < 100);
+        assert!(uncompressed.len() > 4096);
+        assert_eq!(uncompressed, below_threshold);
+
+        let sent = stats.snapshot();
+        assert_eq!(sent.pdus_sent, 3);
+        assert_eq!(sent.compressed_pdus_sent, 1);
+        assert!(sent.bytes_sent < sent.uncompressed_bytes_sent);
+
+        let stats = CodecStats::default();
+        for encoded in [&compressed, &uncompressed] {
+            let decoded = smol::block_on(Pdu::decode_async_with(
+                &mut encoded.as_slice(),
+                None,
+                Some(&stats),
+            ))
+            .unwrap();
+            assert_eq!(decoded.pdu, pdu);
+        }
+        let received = stats.snapshot();
+        assert_eq!(received.pdus_received, 2);
+        assert_eq!(received.compressed_pdus_received, 1);
+        assert!(received.bytes_received < received.uncompressed_bytes_received);
+        assert_eq!(
+            received.uncompressed_bytes_received,
+            2 * sent.uncompressed_bytes_sent / 3
+        );
+    }
+
+    #[test]
+    fn test_compression_level_clamped() {
+        let request = |level| SetCompression {
+            enabled: true,
+            threshold: 32,
+            level,
+        };
+        assert_eq!(request(3).clamped_level(), 3);
+        assert_eq!(request(-5).clamped_level(), 1);
+        assert_eq!(request(i32::MAX).clamped_level(), 22);
+    }
 }
@@ -76,3 +76,119 @@ impl Default for AudibleBell {
         Self::SystemBeep
     }
 }
+
+/// An action to take when the bell rings in a pane
+#[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub enum BellAction {
+    /// Perform the system beep or alert sound
+    SystemBeep,
+    /// Flash the specified target, using the fade settings
+    /// from the `visual_bell` configuration
+    VisualBell(VisualBellTarget),
+    /// Play the sound file at the specified path
+    PlaySound(String),
+    /// Show a toast notification
+    Toast,
+    /// Emit the named event, passing the window and pane
+    EmitEvent(String),
+}
+
+/// Maps a bell from panes that match the `domain` and `program`
+/// criteria to a list of actions.
+/// An empty list of actions silences the bell.
+#[derive(Debug, Clone, Default, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct BellRule {
+    /// Only match panes that belong to the named domain
+    #[dynamic(default)]
+    pub domain: Option<String>,
+    /// Only match panes whose foreground process has this
+    /// executable name, eg: "vim"
+    #[dynamic(default)]
+    pub program: Option<String>,
+    #[dynamic(default)]
+    pub actions: Vec<BellAction>,
+}
+
+impl BellRule {
+    /// Returns true if the rule applies to a pane in `domain`
+    /// whose foreground process is `program`.
+    /// `program` may be a full path; only its final component is compared.
+    pub fn matches(&self, domain: &str, program: Option<&str>) -> bool {
+        if let Some(want) = &self.domain {
+            if want != domain {
+                return false;
+            }
+        }
+        if let Some(want) = &self.program {
+            if !program_matches(want, program) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Returns true if the executable `program` is named `want`.
+/// `program` may be a full path; only its final component is compared,
+/// and a `.exe` suffix is ignored.
+pub(crate) fn program_matches(want: &str, program: Option<&str>) -> bool {
+    let base = program.map(|program| {
+        program
+            .rsplit(|c| c == '/' || c == '\\')
+            .next()
+            .unwrap_or(program)
+    });
+    match base {
+        Some(base) => base == want || base.strip_suffix(".exe") == Some(want),
+        None => false,
+    }
+}
+
+/// Returns the actions of the first rule that matches, if any
+pub fn find_bell_rule_actions<'a>(
+    rules: &'a [BellRule],
+    domain: &str,
+    program: Option<&str>,
+) -> Option<&'a [BellAction]> {
+    rules
+        .iter()
+        .find(|rule| rule.matches(domain, program))
+        .map(|rule| rule.actions.as_slice())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rule(domain: Option<&str>, program: Option<&str>, actions: Vec<BellAction>) -> BellRule {
+        BellRule {
+            domain: domain.map(|s| s.to_string()),
+            program: program.map(|s| s.to_string()),
+            actions,
+        }
+    }
+
+    #[test]
+    fn bell_rule_matching() {
+        let rules = vec![
+            rule(Some("local"), Some("vim"), vec![]),
+            rule(None, Some("make"), vec![BellAction::Toast]),
+            rule(Some("SSH:host"), None, vec![BellAction::SystemBeep]),
+        ];
+
+        assert_eq!(
+            find_bell_rule_actions(&rules, "local", Some("/usr/bin/vim")),
+            Some(&[][..])
+        );
+        assert_eq!(
+            find_bell_rule_actions(&rules, "SSH:host", Some("vim")),
+            Some(&[BellAction::SystemBeep][..])
+        );
+        assert_eq!(
+            find_bell_rule_actions(&rules, "local", Some("C:\\bin\\make.exe")),
+            Some(&[BellAction::Toast][..])
+        );
+        assert_eq!(find_bell_rule_actions(&rules, "local", None), None);
+        assert_eq!(find_bell_rule_actions(&rules, "local", Some("bash")), None);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::*;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+
+/// Controls what happens when a program running in a domain
+/// asks to set or read the clipboard using OSC 52
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub enum ClipboardPolicy {
+    /// The clipboard is set or read
+    Allow,
+    /// The request is ignored
+    Deny,
+    /// The user is asked whether the clipboard should be set or read
+    Prompt,
+    /// The text is added to the clipboard history, from where it can
+    /// be pasted using `PasteFromHistory`, but the system clipboard
+    /// is left unchanged.  Reads are answered with the most recent
+    /// entry of the clipboard history.
+    LocalBuffer,
+}
+
+impl Default for ClipboardPolicy {
+    fn default() -> Self {
+        Self::Allow
+    }
+}
+
+impl Config {
+    /// Returns the clipboard_policy that is configured for the
+    /// named unix, ssh or tls domain
+    fn configured_clipboard_policy(&self, domain_name: &str) -> Option<ClipboardPolicy> {
+        if let Some(unix) = self.unix_domains.iter().find(|d| d.name == domain_name) {
+            unix.clipboard_policy
+        } else if let Some(ssh) = self.ssh_domains.iter().find(|d| d.name == domain_name) {
+            ssh.clipboard_policy
+        } else if let Some(tls) = self.tls_clients.iter().find(|d| d.name == domain_name) {
+            tls.clipboard_policy
+        } else {
+            None
+        }
+    }
+
+    /// Returns the policy that applies when a program in the named
+    /// domain sets the clipboard.  Programs are allowed to set the
+    /// clipboard unless the domain configures a clipboard_policy.
+    pub fn clipboard_policy_for_domain(&self, domain_name: &str) -> ClipboardPolicy {
+        self.configured_clipboard_policy(domain_name)
+            .unwrap_or(ClipboardPolicy::Allow)
+    }
+
+    /// Returns the policy that applies when a program in the named
+    /// domain reads the clipboard.  Programs are denied access to
+    /// the clipboard unless the domain configures a clipboard_policy.
+    pub fn clipboard_read_policy_for_domain(&self, domain_name: &str) -> ClipboardPolicy {
+        self.configured_clipboard_policy(domain_name)
+            .unwrap_or(ClipboardPolicy::Deny)
+    }
+}
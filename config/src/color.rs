@@ -329,6 +329,18 @@ pub struct TabBarStyle {
     pub new_tab: String,
     #[dynamic(default = "default_new_tab")]
     pub new_tab_hover: String,
+    /// Appended to the default tab title when a pane in the tab
+    /// has output that hasn't been seen
+    #[dynamic(default = "default_unseen_output_badge")]
+    pub unseen_output_badge: String,
+    /// Appended to the default tab title, followed by the count,
+    /// when the bell rang in a pane in the tab that wasn't focused
+    #[dynamic(default = "default_unseen_bell_badge")]
+    pub unseen_bell_badge: String,
+    /// Appended to the default tab title when the active pane is in
+    /// a domain whose clipboard_policy isn't `Allow`
+    #[dynamic(default = "default_clipboard_restricted_badge")]
+    pub clipboard_restricted_badge: String,
 }
 
 impl Default for TabBarStyle {
@@ -336,6 +348,9 @@ impl Default for TabBarStyle {
         Self {
             new_tab: default_new_tab(),
             new_tab_hover: default_new_tab(),
+            unseen_output_badge: default_unseen_output_badge(),
+            unseen_bell_badge: default_unseen_bell_badge(),
+            clipboard_restricted_badge: default_clipboard_restricted_badge(),
         }
     }
 }
@@ -344,6 +359,18 @@ fn default_new_tab() -> String {
     " + ".to_string()
 }
 
+fn default_unseen_output_badge() -> String {
+    " \u{25cf}".to_string()
+}
+
+fn default_unseen_bell_badge() -> String {
+    " \u{1f514}".to_string()
+}
+
+fn default_clipboard_restricted_badge() -> String {
+    " \u{1f4cb}".to_string()
+}
+
 #[derive(Debug, Clone, FromDynamic, ToDynamic)]
 pub struct WindowFrameConfig {
     #[dynamic(default = "default_inactive_titlebar_bg")]
@@ -432,6 +459,14 @@ fn default_button_bg() -> RgbColor {
     RgbColor::new_8bpc(0x33, 0x33, 0x33)
 }
 
+/// The color schemes to use for the light and dark appearances
+/// of the windowing environment
+#[derive(Debug, Default, Clone, Eq, PartialEq, FromDynamic, ToDynamic)]
+pub struct ColorSchemeForAppearance {
+    pub light: Option<String>,
+    pub dark: Option<String>,
+}
+
 #[derive(Debug, Default, Clone, Eq, PartialEq, FromDynamic, ToDynamic)]
 pub struct ColorSchemeMetaData {
     pub name: Option<String>,
@@ -451,7 +486,7 @@ pub struct ColorSchemeFile {
 }
 impl_lua_conversion_dynamic!(ColorSchemeFile);
 
-fn dynamic_to_toml(value: Value) -> anyhow::Result<toml::Value> {
+pub(crate) fn dynamic_to_toml(value: Value) -> anyhow::Result<toml::Value> {
     Ok(match value {
         Value::Null => anyhow::bail!("cannot map Null to toml"),
         Value::Bool(b) => toml::Value::Boolean(b),
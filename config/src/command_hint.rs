@@ -0,0 +1,38 @@
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+
+/// Matches a line of the output of a failed command that warrants
+/// a hint, such as the "command not found" message of a shell
+#[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct CommandHintRule {
+    /// Only applies if the foreground process of the pane, which is
+    /// the shell once the command has finished, has this name; eg: `bash`.
+    /// Applies to any shell if not set.
+    #[dynamic(default)]
+    pub shell: Option<String>,
+    /// The regex that is matched against each line of the output.
+    /// Its capture groups are passed to the `command-hint` event.
+    pub regex: String,
+}
+
+impl CommandHintRule {
+    fn new(shell: &str, regex: &str) -> Self {
+        Self {
+            shell: Some(shell.to_string()),
+            regex: regex.to_string(),
+        }
+    }
+}
+
+/// Rules that match the "command not found" messages of common shells;
+/// the first capture group is the name of the command
+pub fn default_command_hint_rules() -> Vec<CommandHintRule> {
+    vec![
+        CommandHintRule::new("bash", r"^bash: (?:line \d+: )?(\S+): command not found"),
+        CommandHintRule::new("zsh", r"^zsh: command not found: (\S+)"),
+        CommandHintRule::new("fish", r"^fish: Unknown command:? '?([^'\s]+)'?"),
+        CommandHintRule::new("dash", r"^(?:dash|sh): \d+: (\S+): not found"),
+        CommandHintRule::new("sh", r"^(?:dash|sh): \d+: (\S+): not found"),
+        CommandHintRule::new("pwsh", r"The term '([^']+)' is not recognized"),
+        CommandHintRule::new("powershell", r"The term '([^']+)' is not recognized"),
+    ]
+}
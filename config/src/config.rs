@@ -1,8 +1,10 @@
 use crate::background::{BackgroundLayer, Gradient};
-use crate::bell::{AudibleBell, EasingFunction, VisualBell};
+use crate::bell::{AudibleBell, BellRule, EasingFunction, VisualBell};
 use crate::color::{
-    ColorSchemeFile, HsbTransform, Palette, SrgbaTuple, TabBarStyle, WindowFrameConfig,
+    ColorSchemeFile, ColorSchemeForAppearance, HsbTransform, Palette, SrgbaTuple, TabBarStyle,
+    WindowFrameConfig,
 };
+use crate::command_hint::{default_command_hint_rules, CommandHintRule};
 use crate::daemon::DaemonOptions;
 use crate::exec_domain::ExecDomain;
 use crate::font::{
@@ -11,12 +13,15 @@ use crate::font::{
 };
 use crate::frontend::FrontEndSelection;
 use crate::keyassignment::{
-    KeyAssignment, KeyTable, KeyTableEntry, KeyTables, MouseEventTrigger, SpawnCommand,
+    CharSelectSet, ClipboardPasteSource, KeyAssignment, KeyTable, KeyTableEntry, KeyTables,
+    MouseEventTrigger, MouseTable, SpawnCommand,
 };
-use crate::keys::{Key, LeaderKey, Mouse};
+use crate::keys::{ComposeSequence, Key, KeyNoAction, KeyRemap, LeaderKey, Mouse};
 use crate::lua::make_lua_context;
 use crate::ssh::{SshBackend, SshDomain};
+use crate::status::StatusSegment;
 use crate::tls::{TlsDomainClient, TlsDomainServer};
+use crate::tray::TrayMenuEntry;
 use crate::units::Dimension;
 use crate::unix::UnixDomain;
 use crate::wsl::WslDomain;
@@ -120,10 +125,39 @@ pub struct Config {
     #[dynamic(default)]
     pub color_schemes: HashMap<String, Palette>,
 
+    /// Use a different color scheme depending on whether the windowing
+    /// environment is in light or dark mode.  This takes precedence
+    /// over color_scheme.
+    pub color_scheme_for_appearance: Option<ColorSchemeForAppearance>,
+
+    /// The name of the color scheme that is in effect, taking
+    /// color_scheme_for_appearance into account
+    #[dynamic(default)]
+    pub resolved_color_scheme: Option<String>,
+
+    /// How long to crossfade between the old and new colors when
+    /// the appearance changes the color scheme. 0 disables the
+    /// transition.
+    #[dynamic(default)]
+    pub color_scheme_transition_duration_ms: u64,
+    #[dynamic(default)]
+    pub color_scheme_transition_function: EasingFunction,
+
     /// How many lines of scrollback you want to retain
     #[dynamic(default = "default_scrollback_lines")]
     pub scrollback_lines: usize,
 
+    /// Whether to rewrap the lines of the primary screen, including
+    /// the scrollback, to fit the new width when a pane is resized
+    #[dynamic(default = "default_true")]
+    pub reflow_on_resize: bool,
+
+    /// How many lines of alternate screen content to retain after
+    /// they scroll off or are cleared from the alternate screen.
+    /// Zero disables the alternate screen history.
+    #[dynamic(default)]
+    pub alternate_screen_scrollback_lines: usize,
+
     /// If no `prog` is specified on the command line, use this
     /// instead of running the user's shell.
     /// For example, to have `wezterm` always run `top` by default,
@@ -174,6 +208,22 @@ pub struct Config {
     #[dynamic(default = "default_hyperlink_rules")]
     pub hyperlink_rules: Vec<hyperlink::Rule>,
 
+    /// When hovering over an explicit hyperlink, show its target
+    /// in the bottom left corner of the window
+    #[dynamic(default)]
+    pub hyperlink_hover_preview: bool,
+
+    /// When the hovered hyperlink, or the active quick select match,
+    /// refers to a local image file, show a thumbnail of it
+    #[dynamic(default)]
+    pub image_preview: bool,
+    /// The largest width and height of the image preview, in pixels
+    #[dynamic(default = "default_image_preview_max_size")]
+    pub image_preview_max_size: u32,
+    /// Image files larger than this number of bytes are not previewed
+    #[dynamic(default = "default_image_preview_max_file_size")]
+    pub image_preview_max_file_size: u64,
+
     /// What to set the TERM variable to
     #[dynamic(default = "default_term")]
     pub term: String,
@@ -237,6 +287,11 @@ pub struct Config {
     #[dynamic(default = "default_harfbuzz_features")]
     pub harfbuzz_features: Vec<String>,
 
+    /// Whether ligatures are split so that the cursor and selection
+    /// can be drawn over the individual characters that they cover
+    #[dynamic(default)]
+    pub ligature_splitting: LigatureSplitting,
+
     #[dynamic(default)]
     pub front_end: FrontEndSelection,
 
@@ -265,6 +320,21 @@ pub struct Config {
     #[dynamic(default)]
     pub tls_clients: Vec<TlsDomainClient>,
 
+    /// Named groups of client domains that can be attached
+    /// and detached as a unit
+    #[dynamic(default)]
+    pub domain_groups: Vec<DomainGroup>,
+
+    /// Named layouts of windows, tabs and panes that can be
+    /// applied to a workspace
+    #[dynamic(default)]
+    pub workspace_layouts: Vec<WorkspaceLayout>,
+
+    /// Named window sizes, font scales and positions that can be
+    /// applied using window:apply_preset
+    #[dynamic(default)]
+    pub window_size_presets: Vec<WindowSizePreset>,
+
     /// Constrains the rate at which the multiplexer client will
     /// speculatively fetch line data.
     /// This helps to avoid saturating the link between the client
@@ -273,6 +343,25 @@ pub struct Config {
     #[dynamic(default = "default_ratelimit_line_prefetches_per_second")]
     pub ratelimit_mux_line_prefetches_per_second: u32,
 
+    /// Constrains the rate at which output from a pane is processed,
+    /// in bytes per second.  0 means that there is no limit.
+    /// This can be overridden for a pane using `pane:set_output_rate`.
+    #[dynamic(default)]
+    pub ratelimit_output_bytes_per_second: u32,
+
+    /// When a pane produces output faster than it can be processed,
+    /// pause between batches of output so that input and rendering
+    /// remain responsive.
+    #[dynamic(default)]
+    pub adaptive_output_ratelimit: bool,
+
+    /// How often, in milliseconds, to sample the CPU and memory usage
+    /// of the processes running in each pane.  0 disables sampling.
+    /// The samples are available via `pane:get_resource_usage` and
+    /// `wezterm cli list --format json`.
+    #[dynamic(default)]
+    pub pane_resource_usage_sample_interval: u64,
+
     /// The buffer size used by parse_buffered_data in the mux module.
     /// This should not be too large, otherwise the processing cost
     /// of applying a batch of actions to the terminal will be too
@@ -283,6 +372,30 @@ pub struct Config {
     #[dynamic(default = "default_mux_env_remove")]
     pub mux_env_remove: Vec<String>,
 
+    /// When non-zero, the multiplexer server hibernates panes that
+    /// have produced no output and received no input for this many
+    /// seconds: their scrollback is compressed until it is next
+    /// viewed.  0 disables hibernation.
+    #[dynamic(default)]
+    pub mux_pane_hibernate_after_seconds: u64,
+
+    /// When true, the gui periodically records its windows, tabs and
+    /// panes in a journal, and offers to restore them at the next
+    /// launch if it didn't shut down cleanly
+    #[dynamic(default)]
+    pub enable_crash_recovery: bool,
+
+    /// How many lines of the scrollback of each pane are recorded
+    /// in the crash recovery journal
+    #[dynamic(default = "default_crash_recovery_scrollback_lines")]
+    pub crash_recovery_scrollback_lines: usize,
+
+    /// The names of the programs that are respawned when restoring
+    /// a session from the crash recovery journal.  Other panes are
+    /// restored with the default program.
+    #[dynamic(default)]
+    pub crash_recovery_restartable_commands: Vec<String>,
+
     #[dynamic(default)]
     pub keys: Vec<Key>,
     #[dynamic(default)]
@@ -298,6 +411,28 @@ pub struct Config {
     pub disable_default_key_bindings: bool,
     pub leader: Option<LeaderKey>,
 
+    /// Pressing this key starts one of the `compose_sequences`
+    #[dynamic(default)]
+    pub compose_key: Option<KeyNoAction>,
+    #[dynamic(default)]
+    pub compose_sequences: Vec<ComposeSequence>,
+
+    /// Remaps physical keys, such as CapsLock to LeftControl,
+    /// before key assignments are matched
+    #[dynamic(default)]
+    pub key_remap: Vec<KeyRemap>,
+
+    /// When a key table or the leader key is active, show the keys
+    /// that can be pressed and what they do
+    #[dynamic(default)]
+    pub show_key_hints: bool,
+
+    /// When true, the title of each pane is shown in a border above it,
+    /// and clicking the title activates the pane.  A row is reserved
+    /// at the top of the window for the titles of the topmost panes.
+    #[dynamic(default)]
+    pub pane_border_titles: bool,
+
     #[dynamic(default)]
     pub disable_default_quick_select_patterns: bool,
     #[dynamic(default)]
@@ -308,8 +443,20 @@ pub struct Config {
     #[dynamic(default)]
     pub mouse_bindings: Vec<Mouse>,
     #[dynamic(default)]
+    pub mouse_tables: HashMap<String, Vec<Mouse>>,
+    #[dynamic(default)]
     pub disable_default_mouse_bindings: bool,
 
+    /// Whether gestures on a touchscreen, or a trackpad on macOS,
+    /// scroll, zoom, switch tabs and perform `touch_long_press_action`
+    #[dynamic(default)]
+    pub enable_touch_gestures: bool,
+
+    /// The action that is performed when a finger is held in
+    /// place on a touchscreen
+    #[dynamic(default = "default_touch_long_press_action")]
+    pub touch_long_press_action: KeyAssignment,
+
     #[dynamic(default)]
     pub daemon_options: DaemonOptions,
 
@@ -354,6 +501,21 @@ pub struct Config {
     #[dynamic(default = "default_tab_max_width")]
     pub tab_max_width: usize,
 
+    /// The height of the tab bar.  When not set, the fancy tab bar
+    /// is 1.75 times the height of the window_frame font and the
+    /// retro tab bar is one cell tall.  Cells are measured using
+    /// the font that is used to render the tab bar.
+    #[dynamic(try_from = "crate::units::OptPixelUnit", default)]
+    pub tab_bar_height: Option<Dimension>,
+
+    /// The space above and below the titles of the tabs in the
+    /// fancy tab bar.  It is reduced if the tabs would not otherwise
+    /// fit in the height of the tab bar.  When neither this nor
+    /// tab_bar_height is set, the fancy tab bar keeps its original
+    /// layout.
+    #[dynamic(try_from = "crate::units::OptPixelUnit", default)]
+    pub tab_bar_vertical_padding: Option<Dimension>,
+
     /// If true, hide the tab bar if the window only has a single tab.
     #[dynamic(default)]
     pub hide_tab_bar_if_only_one_tab: bool,
@@ -364,6 +526,28 @@ pub struct Config {
     #[dynamic(try_from = "crate::units::PixelUnit", default = "default_half_cell")]
     pub min_scroll_bar_height: Dimension,
 
+    /// When true, the scroll bar shows marks for the shell prompts,
+    /// the lines that match `scrollbar_error_patterns`, search matches
+    /// and any marks added using `pane:set_scrollbar_marks`
+    #[dynamic(default)]
+    pub show_scrollbar_marks: bool,
+
+    /// Regular expressions; lines that match any of them are marked
+    /// in the scroll bar
+    #[dynamic(default)]
+    pub scrollbar_error_patterns: Vec<String>,
+
+    /// When true, a miniature overview of the scrollback of the
+    /// active pane is shown on the right hand side of the window
+    #[dynamic(default)]
+    pub enable_scrollback_minimap: bool,
+
+    #[dynamic(
+        try_from = "crate::units::PixelUnit",
+        default = "default_scrollback_minimap_width"
+    )]
+    pub scrollback_minimap_width: Dimension,
+
     /// If false, do not try to use a Wayland protocol connection
     /// when starting the gui frontend, and instead use X11.
     /// This option is only considered on X11/Wayland systems and
@@ -388,6 +572,11 @@ pub struct Config {
     #[dynamic(default)]
     pub window_padding: WindowPadding,
 
+    /// Rounds the corners of the window with this radius.
+    /// When not set, the corners are left to the desktop environment.
+    #[dynamic(default)]
+    pub window_corner_radius: Option<Dimension>,
+
     /// Specifies the path to a background image attachment file.
     /// The file can be any image format that the rust `image`
     /// crate is able to identify and load.
@@ -397,6 +586,17 @@ pub struct Config {
     /// The image will be scaled to fit the window.
     #[dynamic(default)]
     pub window_background_image: Option<PathBuf>,
+
+    /// The path to a GLSL fragment shader that is applied to each
+    /// rendered frame
+    #[dynamic(default)]
+    pub post_processing_shader: Option<PathBuf>,
+
+    /// The toml file that the settings editor writes to.
+    /// Defaults to `managed-settings.toml` in the config directory.
+    #[dynamic(default)]
+    pub managed_settings_file: Option<PathBuf>,
+
     #[dynamic(default)]
     pub window_background_gradient: Option<Gradient>,
     #[dynamic(default)]
@@ -452,9 +652,22 @@ pub struct Config {
     #[dynamic(default = "default_inactive_pane_hsb")]
     pub inactive_pane_hsb: HsbTransform,
 
+    /// How long panes take to fade to and from inactive_pane_hsb
+    /// when the active pane changes. 0 disables the fade.
+    #[dynamic(default)]
+    pub inactive_pane_fade_duration_ms: u64,
+    #[dynamic(default)]
+    pub inactive_pane_fade_function: EasingFunction,
+
     #[dynamic(default = "default_one_point_oh")]
     pub text_background_opacity: f32,
 
+    /// The radius, in pixels, of the blur that is applied to the
+    /// window background layers behind each pane.  0 disables it.
+    /// This can be overridden for a pane using `pane:set_background_blur`.
+    #[dynamic(default)]
+    pub pane_background_blur: u16,
+
     /// Specifies how often a blinking cursor transitions between visible
     /// and invisible, expressed in milliseconds.
     /// Setting this to 0 disables blinking.
@@ -560,27 +773,100 @@ pub struct Config {
     #[dynamic(default)]
     pub window_close_confirmation: WindowCloseConfirmation,
 
+    /// What to do when `wezterm start` finds that a GUI instance
+    /// is already running
+    #[dynamic(default)]
+    pub existing_gui_instance: ExistingGuiInstance,
+
+    /// Show an icon in the system tray / menu bar
+    #[dynamic(default)]
+    pub enable_tray_icon: bool,
+
+    /// When the tray icon is enabled, minimizing a window hides it
+    /// until it is restored from the tray
+    #[dynamic(default)]
+    pub minimize_to_tray: bool,
+
+    /// When the tray icon is enabled, closing a window hides it
+    /// rather than closing it
+    #[dynamic(default)]
+    pub close_to_tray: bool,
+
+    /// Additional entries to show at the top of the tray menu
+    #[dynamic(default)]
+    pub tray_menu_items: Vec<TrayMenuEntry>,
+
     #[dynamic(default)]
     pub native_macos_fullscreen_mode: bool,
 
+    /// Whether `wezterm://run` URLs, such as those opened by a
+    /// Shortcuts action, are permitted to run commands
+    #[dynamic(default)]
+    pub macos_allow_url_commands: bool,
+
     #[dynamic(default = "default_word_boundary")]
     pub selection_word_boundary: String,
 
+    /// Replace selection_word_boundary for panes whose foreground
+    /// process matches the rule
+    #[dynamic(default)]
+    pub selection_word_boundary_rules: Vec<SelectionWordBoundaryRule>,
+
     #[dynamic(default = "default_enq_answerback")]
     pub enq_answerback: String,
 
+    /// Additional capabilities, or replacement values for the built-in
+    /// capabilities, that are reported in response to XTGETTCAP queries
+    #[dynamic(default)]
+    pub term_capabilities: HashMap<String, String>,
+
+    /// Which of the XTWINOPS (`CSI t`) window operations the
+    /// applications running in the terminal may perform
+    #[dynamic(default = "default_allowed_window_operations")]
+    pub allowed_window_operations: Vec<WindowOperationKind>,
+
     #[dynamic(default = "default_true")]
     pub adjust_window_size_when_changing_font_size: bool,
 
+    /// How long, in milliseconds, to take when transitioning between
+    /// font scales.  0 (the default) applies the new scale immediately.
+    #[dynamic(default)]
+    pub font_scale_animation_duration: u64,
+    #[dynamic(default = "linear_ease")]
+    pub font_scale_animation_ease: EasingFunction,
+
+    /// How long, in milliseconds, to take when transitioning between
+    /// pane layouts.  0 (the default) resizes the panes immediately.
+    #[dynamic(default)]
+    pub pane_resize_animation_duration: u64,
+    #[dynamic(default = "linear_ease")]
+    pub pane_resize_animation_ease: EasingFunction,
+
     #[dynamic(default)]
     pub use_resize_increments: bool,
 
+    /// While the window is being resized, show its size in
+    /// columns and rows in the middle of the window
+    #[dynamic(default)]
+    pub show_resize_badge: bool,
+
     #[dynamic(default = "default_alternate_buffer_wheel_scroll_speed")]
     pub alternate_buffer_wheel_scroll_speed: u8,
 
+    /// Translate the horizontal mouse wheel into Arrow Left/Right
+    /// keys in the alternate screen
+    #[dynamic(default)]
+    pub alternate_buffer_horizontal_wheel_arrow_keys: bool,
+
     #[dynamic(default = "default_status_update_interval")]
     pub status_update_interval: u64,
 
+    /// Named areas of the status, each of which is updated
+    /// independently via `window:set_status`.  The built-in
+    /// `left`, `center` and `right` segments are always available.
+    #[dynamic(default)]
+    pub status_segments: Vec<StatusSegment>,
+
     #[dynamic(default)]
     pub experimental_pixel_positioning: bool,
 
@@ -614,27 +900,108 @@ pub struct Config {
     #[dynamic(default)]
     pub pane_focus_follows_mouse: bool,
 
+    /// How long the mouse has to rest over a pane before
+    /// pane_focus_follows_mouse activates it
+    #[dynamic(default)]
+    pub pane_focus_follows_mouse_delay_ms: u64,
+
+    /// When pane_focus_follows_mouse is enabled, also raise
+    /// and focus the window when the mouse moves over it
+    #[dynamic(default)]
+    pub pane_focus_follows_mouse_autoraise: bool,
+
     #[dynamic(default = "default_true")]
     pub unzoom_on_switch_pane: bool,
 
     #[dynamic(default = "default_max_fps")]
     pub max_fps: u8,
 
+    /// How rendered frames are presented to the display
+    #[dynamic(default)]
+    pub present_mode: PresentMode,
+
+    /// When true, the GPU is flushed right after painting a frame that
+    /// shows the response of a pane to a key press, rather than letting
+    /// the driver queue it
+    #[dynamic(default)]
+    pub low_latency_input: bool,
+
+    /// The initial width and height, in pixels, of the texture atlas
+    /// that holds the rasterized glyphs of a window.
+    /// It is rounded up to a power of two.
+    #[dynamic(default = "default_glyph_atlas_size")]
+    pub glyph_atlas_size: usize,
+
+    /// What to do when the glyph atlas fills up
+    #[dynamic(default)]
+    pub glyph_atlas_growth: GlyphAtlasGrowth,
+
     #[dynamic(default)]
     pub visual_bell: VisualBell,
 
     #[dynamic(default)]
     pub audible_bell: AudibleBell,
 
+    #[dynamic(default)]
+    pub bell_rules: Vec<BellRule>,
+
+    #[dynamic(default)]
+    pub bell_rate_limit_ms: u64,
+
     #[dynamic(default)]
     pub canonicalize_pasted_newlines: Option<NewlineCanon>,
 
+    /// When true, the `clipboard-changed` event is passed the
+    /// new content of the clipboard
+    #[dynamic(default)]
+    pub clipboard_changed_event_include_content: bool,
+
+    #[dynamic(default = "default_clipboard_history_size")]
+    pub clipboard_history_size: usize,
+
+    /// The maximum number of bytes of the selected text that are
+    /// passed to the `selection-changed` event
+    #[dynamic(default = "default_selection_changed_event_max_text_len")]
+    pub selection_changed_event_max_text_len: usize,
+
+    #[dynamic(default)]
+    pub clipboard_history_persist: bool,
+
+    /// The passphrase from which the key that encrypts the
+    /// persisted clipboard history is derived
+    #[dynamic(default)]
+    pub clipboard_history_passphrase: Option<String>,
+
+    /// Additional groups of text that can be chosen from
+    /// the CharSelect overlay
+    #[dynamic(default)]
+    pub char_select_custom_sets: Vec<CharSelectSet>,
+
+    /// How many commands reported by shell integration are
+    /// retained for `wezterm cli history`
+    #[dynamic(default = "default_command_history_size")]
+    pub command_history_size: usize,
+
+    /// When a command reported by shell integration fails and its
+    /// output matches one of the `command_hint_rules`, emit the
+    /// `command-hint` event and show the suggestion that it returns
+    #[dynamic(default)]
+    pub enable_command_hints: bool,
+
+    #[dynamic(default = "default_command_hint_rules")]
+    pub command_hint_rules: Vec<CommandHintRule>,
+
     #[dynamic(default = "default_unicode_version")]
     pub unicode_version: u8,
 
     #[dynamic(default)]
     pub treat_east_asian_ambiguous_width_as_wide: bool,
 
+    /// Overrides the width and the default presentation of ranges
+    /// of codepoints, in preference to the unicode tables
+    #[dynamic(default)]
+    pub unicode_width_overrides: Vec<termwiz::cell::WidthOverride>,
+
     #[dynamic(default = "default_true")]
     pub allow_download_protocols: bool,
 
@@ -647,6 +1014,11 @@ pub struct Config {
     #[dynamic(default)]
     pub default_workspace: Option<String>,
 
+    /// Maps the name of a workspace to a table of configuration
+    /// overrides that are applied to the windows in that workspace
+    #[dynamic(default)]
+    pub workspace_overrides: HashMap<String, wezterm_dynamic::Value>,
+
     #[dynamic(default)]
     pub xcursor_theme: Option<String>,
 
@@ -883,6 +1255,7 @@ impl Config {
                 (key, mods),
                 KeyTableEntry {
                     action: k.action.clone(),
+                    description: k.description.clone(),
                 },
             );
         }
@@ -899,6 +1272,7 @@ impl Config {
                     (key, mods),
                     KeyTableEntry {
                         action: k.action.clone(),
+                        description: k.description.clone(),
                     },
                 );
             }
@@ -918,6 +1292,20 @@ impl Config {
         map
     }
 
+    pub fn mouse_tables(&self) -> HashMap<String, MouseTable> {
+        let mut tables = HashMap::new();
+
+        for (name, bindings) in &self.mouse_tables {
+            let mut table = MouseTable::default();
+            for m in bindings {
+                table.insert((m.event.clone(), m.mods), m.action.clone());
+            }
+            tables.insert(name.to_string(), table);
+        }
+
+        tables
+    }
+
     /// In some cases we need to compute expanded values based
     /// on those provided by the user.  This is where we do that.
     pub fn compute_extra_defaults(&self, config_path: Option<&Path>) -> Self {
@@ -937,6 +1325,18 @@ impl Config {
                     cfg.window_background_image.replace(config_dir.join(path));
                 }
             }
+
+            if let Some(path) = &self.post_processing_shader {
+                if !path.is_absolute() {
+                    cfg.post_processing_shader.replace(config_dir.join(path));
+                }
+            }
+
+            if let Some(path) = &self.managed_settings_file {
+                if !path.is_absolute() {
+                    cfg.managed_settings_file.replace(config_dir.join(path));
+                }
+            }
         }
 
         // Add some reasonable default font rules
@@ -989,22 +1389,9 @@ impl Config {
         cfg.load_color_schemes(&cfg.compute_color_scheme_dirs())
             .ok();
 
-        cfg.resolved_palette = cfg.colors.as_ref().cloned().unwrap_or(Default::default());
-        // Color scheme overrides any manually specified palette
-        if let Some(scheme) = cfg.color_scheme.as_ref() {
-            match cfg.resolve_color_scheme() {
-                None => {
-                    log::error!(
-                        "Your configuration specifies color_scheme=\"{}\" \
-                        but that scheme was not found",
-                        scheme
-                    );
-                }
-                Some(p) => {
-                    cfg.resolved_palette = p.clone();
-                }
-            }
-        }
+        cfg.resolve_palette();
+        cfg.apply_managed_settings();
+        cfg.apply_domain_group_credentials();
 
         if let Some(bg) = BackgroundLayer::with_legacy(self) {
             cfg.background.insert(0, bg);
@@ -1085,8 +1472,43 @@ impl Config {
         Ok(())
     }
 
+    /// Computes resolved_color_scheme and resolved_palette from
+    /// the colors, color_scheme and color_scheme_for_appearance
+    /// options, taking the current appearance into account.
+    pub(crate) fn resolve_palette(&mut self) {
+        let is_dark = crate::appearance_is_dark();
+        self.resolved_color_scheme = self
+            .color_scheme_for_appearance
+            .as_ref()
+            .and_then(|schemes| {
+                if is_dark {
+                    schemes.dark.clone()
+                } else {
+                    schemes.light.clone()
+                }
+            })
+            .or_else(|| self.color_scheme.clone());
+
+        self.resolved_palette = self.colors.as_ref().cloned().unwrap_or(Default::default());
+        // Color scheme overrides any manually specified palette
+        if let Some(scheme) = self.resolved_color_scheme.as_ref() {
+            match self.resolve_color_scheme() {
+                None => {
+                    log::error!(
+                        "Your configuration specifies color scheme \"{}\" \
+                        but that scheme was not found",
+                        scheme
+                    );
+                }
+                Some(p) => {
+                    self.resolved_palette = p.clone();
+                }
+            }
+        }
+    }
+
     pub fn resolve_color_scheme(&self) -> Option<&Palette> {
-        let scheme_name = self.color_scheme.as_ref()?;
+        let scheme_name = self.resolved_color_scheme.as_ref()?;
 
         if let Some(palette) = self.color_schemes.get(scheme_name) {
             Some(palette)
@@ -1244,6 +1666,10 @@ fn default_scrollback_lines() -> usize {
     3500
 }
 
+fn default_crash_recovery_scrollback_lines() -> usize {
+    200
+}
+
 fn default_initial_rows() -> u16 {
     24
 }
@@ -1270,6 +1696,14 @@ fn default_harfbuzz_features() -> Vec<String> {
         .collect()
 }
 
+fn default_image_preview_max_size() -> u32 {
+    256
+}
+
+fn default_image_preview_max_file_size() -> u64 {
+    16 * 1024 * 1024
+}
+
 fn default_term() -> String {
     "xterm-256color".into()
 }
@@ -1298,10 +1732,26 @@ pub fn default_write_timeout() -> Duration {
     Duration::from_secs(60)
 }
 
+fn default_clipboard_history_size() -> usize {
+    50
+}
+
+fn default_selection_changed_event_max_text_len() -> usize {
+    16 * 1024
+}
+
+fn default_command_history_size() -> usize {
+    1000
+}
+
 pub fn default_local_echo_threshold_ms() -> Option<u64> {
     Some(100)
 }
 
+fn default_touch_long_press_action() -> KeyAssignment {
+    KeyAssignment::PasteFrom(ClipboardPasteSource::Clipboard)
+}
+
 fn default_bypass_mouse_reporting_modifiers() -> Modifiers {
     Modifiers::SHIFT
 }
@@ -1327,6 +1777,10 @@ fn default_anim_fps() -> u8 {
     10
 }
 
+fn default_glyph_atlas_size() -> usize {
+    128
+}
+
 fn default_max_fps() -> u8 {
     60
 }
@@ -1364,6 +1818,15 @@ fn default_word_boundary() -> String {
     " \t\n{[}]()\"'`".to_string()
 }
 
+fn default_allowed_window_operations() -> Vec<WindowOperationKind> {
+    vec![
+        WindowOperationKind::ReportSize,
+        WindowOperationKind::ReportPosition,
+        WindowOperationKind::ReportState,
+        WindowOperationKind::ReportTitle,
+    ]
+}
+
 fn default_enq_answerback() -> String {
     "".to_string()
 }
@@ -1392,6 +1855,26 @@ fn default_inactive_pane_hsb() -> HsbTransform {
     }
 }
 
+/// Uses a different set of word boundary characters for panes whose
+/// foreground process has the executable name `program`, eg: "vim"
+#[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct SelectionWordBoundaryRule {
+    pub program: String,
+    pub word_boundary: String,
+}
+
+/// Returns the word boundary of the first rule that matches the
+/// foreground process `program`, if any
+pub fn find_selection_word_boundary<'a>(
+    rules: &'a [SelectionWordBoundaryRule],
+    program: Option<&str>,
+) -> Option<&'a str> {
+    rules
+        .iter()
+        .find(|rule| crate::bell::program_matches(&rule.program, program))
+        .map(|rule| rule.word_boundary.as_str())
+}
+
 #[derive(FromDynamic, ToDynamic, Clone, Copy, Debug)]
 pub enum DefaultCursorStyle {
     BlinkingBlock,
@@ -1436,6 +1919,10 @@ const fn default_half_cell() -> Dimension {
     Dimension::Cells(0.5)
 }
 
+const fn default_scrollback_minimap_width() -> Dimension {
+    Dimension::Cells(6.0)
+}
+
 #[derive(FromDynamic, ToDynamic, Clone, Copy, Debug)]
 pub struct WindowPadding {
     #[dynamic(try_from = "crate::units::PixelUnit", default = "default_one_cell")]
@@ -1468,6 +1955,20 @@ pub enum NewlineCanon {
     CarriageReturnAndLineFeed,
 }
 
+#[derive(FromDynamic, ToDynamic, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowOperationKind {
+    ReportSize,
+    ReportPosition,
+    ReportState,
+    ReportTitle,
+    Resize,
+    Move,
+    Iconify,
+    RaiseLower,
+    Maximize,
+    FullScreen,
+}
+
 #[derive(FromDynamic, ToDynamic, Clone, Copy, Debug)]
 pub enum WindowCloseConfirmation {
     AlwaysPrompt,
@@ -1482,6 +1983,25 @@ impl Default for WindowCloseConfirmation {
     }
 }
 
+#[derive(FromDynamic, ToDynamic, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExistingGuiInstance {
+    /// Ask the running instance to spawn the program in a new window,
+    /// and exit
+    SpawnWindow,
+    /// Start a new GUI process that uses the mux of the running
+    /// instance, via its local socket
+    Attach,
+    /// Start a new GUI process that is independent of the running
+    /// instance, as though `--always-new-process` had been used
+    NewProcess,
+}
+
+impl Default for ExistingGuiInstance {
+    fn default() -> Self {
+        Self::SpawnWindow
+    }
+}
+
 struct PathPossibility {
     path: PathBuf,
     is_required: bool,
@@ -1562,6 +2082,41 @@ impl DroppedFileQuoting {
     }
 }
 
+#[derive(Debug, FromDynamic, ToDynamic, Clone, Copy, PartialEq, Eq)]
+pub enum PresentMode {
+    /// Wait for the vertical blank before presenting a frame
+    VSync,
+    /// Present frames without waiting for the vertical blank;
+    /// when a compositor is running, it shows the most recent frame
+    /// at the next vertical blank.  Frames are paced to max_fps.
+    Mailbox,
+    /// Like Mailbox, but frames are not paced to max_fps.
+    /// Without a compositor, frames may tear.
+    Immediate,
+}
+
+impl Default for PresentMode {
+    fn default() -> Self {
+        PresentMode::Mailbox
+    }
+}
+
+#[derive(Debug, FromDynamic, ToDynamic, Clone, Copy, PartialEq, Eq)]
+pub enum GlyphAtlasGrowth {
+    /// Clear the atlas and rasterize the glyphs of the frame again,
+    /// growing it only if they still don't fit
+    ClearThenGrow,
+    /// Grow the atlas, keeping the glyphs that were already rasterized.
+    /// This uses more GPU memory, but avoids rasterizing glyphs again.
+    Grow,
+}
+
+impl Default for GlyphAtlasGrowth {
+    fn default() -> Self {
+        GlyphAtlasGrowth::ClearThenGrow
+    }
+}
+
 #[derive(Debug, FromDynamic, ToDynamic, Clone, Copy, PartialEq, Eq)]
 pub enum ImePreeditRendering {
     /// IME preedit is rendered by WezTerm itself
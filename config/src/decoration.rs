@@ -0,0 +1,90 @@
+use crate::{Dimension, RgbaColor};
+use luahelper::impl_lua_conversion_dynamic;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+
+/// What the coordinates of a decoration layer are relative to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub enum DecorationAnchor {
+    /// The top left corner of the window
+    Window,
+    /// The top left corner of the pane with this id.
+    /// The layer is hidden while that pane isn't visible.
+    Pane(usize),
+}
+
+impl Default for DecorationAnchor {
+    fn default() -> Self {
+        Self::Window
+    }
+}
+
+fn default_line_width() -> Dimension {
+    Dimension::Pixels(1.)
+}
+
+/// A primitive that is drawn in a decoration layer.
+/// Positions and sizes are relative to the anchor of the layer;
+/// percentages are of the width or height of the anchor.
+#[derive(Debug, Clone, PartialEq, FromDynamic, ToDynamic)]
+pub enum DecorationItem {
+    Rect {
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        x: Dimension,
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        y: Dimension,
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        width: Dimension,
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        height: Dimension,
+        color: RgbaColor,
+    },
+    Line {
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        x1: Dimension,
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        y1: Dimension,
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        x2: Dimension,
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        y2: Dimension,
+        #[dynamic(try_from = "crate::units::PixelUnit", default = "default_line_width")]
+        width: Dimension,
+        color: RgbaColor,
+    },
+    Text {
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        x: Dimension,
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        y: Dimension,
+        text: String,
+        /// Defaults to the foreground color of the palette
+        #[dynamic(default)]
+        color: Option<RgbaColor>,
+        /// Defaults to transparent
+        #[dynamic(default)]
+        background: Option<RgbaColor>,
+    },
+    /// An image file, scaled to fit within the given size
+    Icon {
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        x: Dimension,
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        y: Dimension,
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        width: Dimension,
+        #[dynamic(try_from = "crate::units::PixelUnit")]
+        height: Dimension,
+        path: String,
+    },
+}
+
+/// A named set of primitives that is drawn over the panes of
+/// a window, set via `window:set_decorations`
+#[derive(Debug, Clone, Default, PartialEq, FromDynamic, ToDynamic)]
+pub struct DecorationLayer {
+    #[dynamic(default)]
+    pub anchor: DecorationAnchor,
+    #[dynamic(default)]
+    pub items: Vec<DecorationItem>,
+}
+impl_lua_conversion_dynamic!(DecorationLayer);
@@ -0,0 +1,107 @@
+use crate::*;
+use std::collections::HashMap;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+
+/// A named set of multiplexer domains that are attached and
+/// detached together
+#[derive(Default, Debug, Clone, FromDynamic, ToDynamic)]
+pub struct DomainGroup {
+    /// The name of this group.  It is used by the lua and cli
+    /// functions that attach and detach the group.
+    pub name: String,
+
+    /// The names of the unix, ssh and tls domains in this group
+    #[dynamic(default)]
+    pub domains: Vec<String>,
+
+    /// The local workspace that receives the windows from the
+    /// default workspace of each of the remote servers in the group
+    pub default_workspace: Option<String>,
+
+    /// If true, attach the group at startup
+    #[dynamic(default)]
+    pub connect_automatically: bool,
+
+    /// The username to use for ssh domains in the group that
+    /// don't specify their own
+    pub ssh_username: Option<String>,
+
+    /// ssh options that apply to each of the ssh domains in the
+    /// group.  Options set on the domain itself take precedence.
+    #[dynamic(default)]
+    pub ssh_option: HashMap<String, String>,
+
+    /// The client certificate credentials to use for tls domains
+    /// in the group that don't specify their own
+    pub pem_private_key: Option<PathBuf>,
+    pub pem_cert: Option<PathBuf>,
+    pub pem_ca: Option<PathBuf>,
+    #[dynamic(default)]
+    pub pem_root_certs: Vec<PathBuf>,
+}
+
+impl DomainGroup {
+    pub fn contains(&self, domain_name: &str) -> bool {
+        self.domains.iter().any(|name| name == domain_name)
+    }
+
+    fn apply_to_ssh_domain(&self, ssh: &mut SshDomain) {
+        if ssh.username.is_none() {
+            ssh.username = self.ssh_username.clone();
+        }
+        for (key, value) in &self.ssh_option {
+            ssh.ssh_option
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+    }
+
+    fn apply_to_tls_client(&self, tls: &mut TlsDomainClient) {
+        if tls.pem_private_key.is_none() {
+            tls.pem_private_key = self.pem_private_key.clone();
+        }
+        if tls.pem_cert.is_none() {
+            tls.pem_cert = self.pem_cert.clone();
+        }
+        if tls.pem_ca.is_none() {
+            tls.pem_ca = self.pem_ca.clone();
+        }
+        if tls.pem_root_certs.is_empty() {
+            tls.pem_root_certs = self.pem_root_certs.clone();
+        }
+    }
+}
+
+impl Config {
+    /// Returns the group that contains the named domain
+    pub fn domain_group_for_domain(&self, domain_name: &str) -> Option<&DomainGroup> {
+        self.domain_groups
+            .iter()
+            .find(|group| group.contains(domain_name))
+    }
+
+    pub fn domain_group_by_name(&self, name: &str) -> Option<&DomainGroup> {
+        self.domain_groups.iter().find(|group| group.name == name)
+    }
+
+    /// Propagates the credentials from each group to its member domains
+    pub(crate) fn apply_domain_group_credentials(&mut self) {
+        for group in &self.domain_groups {
+            for name in &group.domains {
+                if let Some(ssh) = self.ssh_domains.iter_mut().find(|ssh| &ssh.name == name) {
+                    group.apply_to_ssh_domain(ssh);
+                } else if let Some(tls) = self.tls_clients.iter_mut().find(|tls| &tls.name == name)
+                {
+                    group.apply_to_tls_client(tls);
+                } else if !self.unix_domains.iter().any(|unix| &unix.name == name) {
+                    log::warn!(
+                        "domain group `{}` refers to `{}`, which is not a \
+                         unix, ssh or tls domain",
+                        group.name,
+                        name
+                    );
+                }
+            }
+        }
+    }
+}
@@ -218,6 +218,26 @@ impl FontWeight {
     }
 }
 
+/// Controls whether ligatures are broken apart so that the cursor
+/// and the selection can be drawn over the individual characters
+/// that make up the ligature
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub enum LigatureSplitting {
+    /// Ligatures are always rendered whole
+    Never,
+    /// Ligatures are split at the edges of the cursor
+    Cursor,
+    /// Ligatures are split at the edges of the cursor and
+    /// of the selection
+    CursorAndSelection,
+}
+
+impl Default for LigatureSplitting {
+    fn default() -> Self {
+        Self::Never
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FromDynamic, ToDynamic)]
 pub enum FreeTypeLoadTarget {
     /// This corresponds to the default hinting algorithm, optimized
@@ -355,6 +375,12 @@ pub struct FontAttributes {
     pub scale: Option<NotNan<f64>>,
     #[dynamic(default)]
     pub assume_emoji_presentation: Option<bool>,
+    /// Multiplies the strength of synthesized bold; 0 disables it
+    #[dynamic(default)]
+    pub synthetic_bold_strength: Option<NotNan<f64>>,
+    /// The horizontal skew applied to synthesize italics; 0 disables it
+    #[dynamic(default)]
+    pub synthetic_italic_slant: Option<NotNan<f64>>,
 }
 impl_lua_conversion_dynamic!(FontAttributes);
 
@@ -383,6 +409,8 @@ impl FontAttributes {
             freetype_load_flags: None,
             scale: None,
             assume_emoji_presentation: None,
+            synthetic_bold_strength: None,
+            synthetic_italic_slant: None,
         }
     }
 
@@ -400,6 +428,8 @@ impl FontAttributes {
             freetype_load_flags: None,
             scale: None,
             assume_emoji_presentation: None,
+            synthetic_bold_strength: None,
+            synthetic_italic_slant: None,
         }
     }
 }
@@ -419,6 +449,8 @@ impl Default for FontAttributes {
             freetype_load_flags: None,
             scale: None,
             assume_emoji_presentation: None,
+            synthetic_bold_strength: None,
+            synthetic_italic_slant: None,
         }
     }
 }
@@ -102,7 +102,11 @@ pub enum SelectionMode {
     Line,
     SemanticZone,
     Block,
+    /// Selects the word, then on each subsequent use expands to the
+    /// enclosing path or URL, quoted string, bracketed group and line
+    Smart,
 }
+impl_lua_conversion_dynamic!(SelectionMode);
 
 #[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
 pub enum Pattern {
@@ -218,7 +222,7 @@ impl std::fmt::Display for SpawnCommand {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, FromDynamic, ToDynamic)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, FromDynamic, ToDynamic)]
 pub enum PaneDirection {
     Up,
     Down,
@@ -240,11 +244,13 @@ impl Default for ScrollbackEraseMode {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, FromDynamic, ToDynamic)]
+#[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
 pub enum ClipboardCopyDestination {
     Clipboard,
     PrimarySelection,
     ClipboardAndPrimarySelection,
+    /// A named register, rather than a system clipboard
+    Register(String),
 }
 impl_lua_conversion_dynamic!(ClipboardCopyDestination);
 
@@ -254,10 +260,12 @@ impl Default for ClipboardCopyDestination {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, FromDynamic, ToDynamic)]
+#[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
 pub enum ClipboardPasteSource {
     Clipboard,
     PrimarySelection,
+    /// A named register, rather than a system clipboard
+    Register(String),
 }
 
 impl Default for ClipboardPasteSource {
@@ -288,6 +296,70 @@ pub struct PaneSelectArguments {
     pub mode: PaneSelectMode,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub enum DiffPanesLayout {
+    /// The lines of both panes are interleaved, with the
+    /// removed and added lines marked, like `diff -u`
+    Unified,
+    /// The panes are shown next to each other, like `diff -y`
+    SideBySide,
+}
+
+impl Default for DiffPanesLayout {
+    fn default() -> Self {
+        Self::SideBySide
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct DiffPanesArguments {
+    #[dynamic(default)]
+    pub layout: DiffPanesLayout,
+
+    /// Compare the scrollback of the panes as well as
+    /// their visible content
+    #[dynamic(default)]
+    pub scrollback: bool,
+
+    /// Overrides the main quick_select_alphabet config when
+    /// choosing the pane to compare with the active pane
+    #[dynamic(default)]
+    pub alphabet: String,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct CharSelectArguments {
+    /// The name of the group to show initially, such as "Emoji"
+    /// or the name of one of the char_select_custom_sets.
+    /// The recently used group is shown if it isn't empty.
+    #[dynamic(default)]
+    pub group: Option<String>,
+    /// Copy the chosen text to the clipboard rather than sending
+    /// it to the pane
+    #[dynamic(default)]
+    pub copy_on_select: bool,
+    #[dynamic(default)]
+    pub copy_to: ClipboardCopyDestination,
+}
+
+/// A named group of text, configured via `char_select_custom_sets`,
+/// that is offered by the CharSelect overlay
+#[derive(Default, Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct CharSelectSet {
+    pub name: String,
+    pub entries: Vec<CharSelectEntry>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct CharSelectEntry {
+    /// The text to insert; this may be several codepoints long
+    pub text: String,
+    /// A description that is shown alongside the text and
+    /// matched by the filter
+    #[dynamic(default)]
+    pub name: String,
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
 pub struct QuickSelectArguments {
     /// Overrides the main quick_select_alphabet config
@@ -301,6 +373,10 @@ pub struct QuickSelectArguments {
     /// Label to use in place of "copy" when `action` is set
     #[dynamic(default)]
     pub label: String,
+    /// Label the hyperlinks in the viewport, rather than matches
+    /// for the patterns, and open the selected link
+    #[dynamic(default)]
+    pub hyperlinks: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
@@ -335,9 +411,15 @@ pub enum KeyAssignment {
     MoveTab(usize),
     ScrollByPage(NotNan<f64>),
     ScrollByLine(isize),
+    ScrollByColumn(isize),
     ScrollToPrompt(isize),
     ScrollToTop,
     ScrollToBottom,
+    ToggleOutputFold,
+    ToggleOutputFoldAtMouseCursor,
+    FoldAllOutput,
+    UnfoldAllOutput,
+    ToggleLineWrap,
     ShowTabNavigator,
     ShowDebugOverlay,
     HideApplication,
@@ -348,6 +430,7 @@ pub enum KeyAssignment {
     SplitVertical(SpawnCommand),
     ShowLauncher,
     ShowLauncherArgs(LauncherActionArgs),
+    ShowSettingsEditor,
     ClearScrollback(ScrollbackEraseMode),
     Search(Pattern),
     ActivateCopyMode,
@@ -363,7 +446,10 @@ pub enum KeyAssignment {
     AdjustPaneSize(PaneDirection, usize),
     ActivatePaneDirection(PaneDirection),
     ActivatePaneByIndex(usize),
+    SwapActivePaneWithIndex(usize),
     TogglePaneZoomState,
+    TogglePaneReadOnly,
+    ToggleStackedLayout,
     CloseCurrentPane {
         confirm: bool,
     },
@@ -399,9 +485,47 @@ pub enum KeyAssignment {
     RotatePanes(RotationDirection),
     SplitPane(SplitPane),
     PaneSelect(PaneSelectArguments),
+    DiffPanes(DiffPanesArguments),
+    ToggleBellMute,
+    PasteFromHistory,
+    ShowRegisters,
+    ShowKeyCheatsheet,
+    CharSelect(CharSelectArguments),
+    StartMacroRecording(String),
+    StopMacroRecording,
+    PlayMacro(PlayMacro),
 }
 impl_lua_conversion_dynamic!(KeyAssignment);
 
+/// An input event that was captured while recording a macro
+#[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub enum MacroEvent {
+    /// A key press, in the same form as the `SendKey` assignment
+    Key(KeyNoAction),
+    /// Text produced by an input method or by composing a dead key
+    Text(String),
+}
+impl_lua_conversion_dynamic!(MacroEvent);
+
+impl MacroEvent {
+    /// Returns the assignment that sends this event to a pane
+    pub fn to_key_assignment(&self) -> KeyAssignment {
+        match self {
+            Self::Key(key) => KeyAssignment::SendKey(key.clone()),
+            Self::Text(text) => KeyAssignment::SendString(text.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct PlayMacro {
+    pub name: String,
+    /// How long to wait before sending each event; 0 sends
+    /// all of the events at once
+    #[dynamic(default)]
+    pub delay_milliseconds: u64,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
 pub struct SplitPane {
     pub direction: PaneDirection,
@@ -425,7 +549,7 @@ impl Default for SplitSize {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, FromDynamic, ToDynamic)]
 pub enum RotationDirection {
     Clockwise,
     CounterClockwise,
@@ -439,6 +563,7 @@ pub enum CopyModeAssignment {
     MoveToScrollbackTop,
     MoveToScrollbackBottom,
     SetSelectionMode(Option<SelectionMode>),
+    ToggleRectangularSelection,
     MoveToStartOfLineContent,
     MoveToEndOfLineContent,
     MoveToStartOfLine,
@@ -462,9 +587,12 @@ pub enum CopyModeAssignment {
     ClearPattern,
     EditPattern,
     AcceptPattern,
+    MoveToMouseCursor,
+    SelectToMouseCursor(SelectionMode),
 }
 
 pub type KeyTable = HashMap<(KeyCode, Modifiers), KeyTableEntry>;
+pub type MouseTable = HashMap<(MouseEventTrigger, Modifiers), KeyAssignment>;
 
 #[derive(Debug, Clone, Default)]
 pub struct KeyTables {
@@ -475,4 +603,5 @@ pub struct KeyTables {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct KeyTableEntry {
     pub action: KeyAssignment,
+    pub description: Option<String>,
 }
@@ -123,6 +123,27 @@ pub struct Key {
     #[dynamic(flatten)]
     pub key: KeyNoAction,
     pub action: KeyAssignment,
+    /// Describes the action in the key hints that are shown
+    /// when `show_key_hints` is enabled
+    #[dynamic(default)]
+    pub description: Option<String>,
+}
+
+/// A sequence of keys that produces `result` when they are pressed
+/// after the `compose_key`
+#[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct ComposeSequence {
+    /// The text produced by each of the keys, such as `"e"` or `"'"`
+    pub keys: Vec<String>,
+    pub result: String,
+}
+
+/// Makes the physical key `from` behave as though it were `to`,
+/// before any key assignments are matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct KeyRemap {
+    pub from: PhysKeyCode,
+    pub to: PhysKeyCode,
 }
 
 #[derive(Debug, Clone, FromDynamic, ToDynamic)]
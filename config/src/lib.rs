@@ -21,39 +21,59 @@ use wezterm_dynamic::{FromDynamic, FromDynamicOptions, ToDynamic, UnknownFieldAc
 
 mod background;
 mod bell;
+mod clipboard_policy;
 mod color;
+mod command_hint;
 mod config;
 mod daemon;
+mod decoration;
+mod domain_group;
 mod exec_domain;
 mod font;
 mod frontend;
 pub mod keyassignment;
 mod keys;
 pub mod lua;
+mod managed_settings;
+mod mux_compression;
 mod scheme_data;
 mod ssh;
+mod status;
 mod terminal;
 mod tls;
+mod tray;
 mod units;
 mod unix;
 mod version;
+mod window_preset;
+mod workspace_layout;
 mod wsl;
 
 pub use crate::config::*;
 pub use background::*;
 pub use bell::*;
+pub use clipboard_policy::*;
 pub use color::*;
+pub use command_hint::*;
 pub use daemon::*;
+pub use decoration::*;
+pub use domain_group::*;
 pub use exec_domain::*;
 pub use font::*;
 pub use frontend::*;
 pub use keys::*;
+pub use managed_settings::*;
+pub use mux_compression::*;
 pub use ssh::*;
+pub use status::*;
 pub use terminal::*;
 pub use tls::*;
+pub use tray::*;
 pub use units::*;
 pub use unix::*;
 pub use version::*;
+pub use window_preset::*;
+pub use workspace_layout::*;
 pub use wsl::*;
 
 type ErrorCallback = fn(&str);
@@ -61,10 +81,12 @@ type ErrorCallback = fn(&str);
 lazy_static! {
     pub static ref HOME_DIR: PathBuf = dirs_next::home_dir().expect("can't find HOME dir");
     pub static ref CONFIG_DIR: PathBuf = xdg_config_home();
+    pub static ref DATA_DIR: PathBuf = xdg_data_home();
     pub static ref RUNTIME_DIR: PathBuf = compute_runtime_dir().unwrap();
     static ref CONFIG: Configuration = Configuration::new();
     static ref CONFIG_FILE_OVERRIDE: Mutex<Option<PathBuf>> = Mutex::new(None);
     static ref CONFIG_SKIP: AtomicBool = AtomicBool::new(false);
+    static ref APPEARANCE_IS_DARK: AtomicBool = AtomicBool::new(false);
     static ref CONFIG_OVERRIDES: Mutex<Vec<(String, String)>> = Mutex::new(vec![]);
     static ref SHOW_ERROR: Mutex<Option<ErrorCallback>> =
         Mutex::new(Some(|e| log::error!("{}", e)));
@@ -334,6 +356,13 @@ fn xdg_config_home() -> PathBuf {
     }
 }
 
+fn xdg_data_home() -> PathBuf {
+    match std::env::var_os("XDG_DATA_HOME").map(|s| PathBuf::from(s).join("wezterm")) {
+        Some(p) => p,
+        None => HOME_DIR.join(".local").join("share").join("wezterm"),
+    }
+}
+
 pub fn set_config_file_override(path: &Path) {
     CONFIG_FILE_OVERRIDE
         .lock()
@@ -385,6 +414,21 @@ pub fn reload() {
     CONFIG.reload();
 }
 
+/// Records whether the windowing environment is using a dark appearance,
+/// which selects the color scheme from `color_scheme_for_appearance`.
+/// If the appearance changed, the palette of the current configuration
+/// is re-resolved and subscribers are notified, without re-evaluating
+/// the config file.
+pub fn set_appearance_is_dark(is_dark: bool) {
+    if APPEARANCE_IS_DARK.swap(is_dark, Ordering::Relaxed) != is_dark {
+        CONFIG.appearance_changed();
+    }
+}
+
+pub fn appearance_is_dark() -> bool {
+    APPEARANCE_IS_DARK.load(Ordering::Relaxed)
+}
+
 /// If there was an error loading the preferred configuration,
 /// return it, otherwise return the current configuration
 pub fn configuration_result() -> Result<ConfigHandle, Error> {
@@ -530,9 +574,16 @@ impl ConfigInner {
 
         match config {
             Ok(config) => {
+                // Reload when the post processing shader is edited
+                if let Some(path) = &config.post_processing_shader {
+                    watch_paths.push(path.clone());
+                }
+                // and when the settings editor saves its changes
+                watch_paths.push(config.managed_settings_path());
                 self.config = Arc::new(config);
                 self.error.take();
                 self.generation += 1;
+                self.apply_width_overrides();
 
                 // If we loaded a user config, publish this latest version of
                 // the lua state to the LUA_PIPE.  This allows a subsequent
@@ -562,6 +613,22 @@ impl ConfigInner {
         }
     }
 
+    /// Re-resolve the palette of the current configuration
+    /// after the appearance of the windowing environment changed
+    fn appearance_changed(&mut self) {
+        if self.config.color_scheme_for_appearance.is_none() {
+            return;
+        }
+        let mut config = (*self.config).clone();
+        config.resolve_palette();
+        if config.resolved_color_scheme == self.config.resolved_color_scheme {
+            return;
+        }
+        self.config = Arc::new(config);
+        self.generation += 1;
+        self.notify();
+    }
+
     /// Discard the current configuration and any recorded
     /// error message; replace them with the default
     /// configuration
@@ -569,12 +636,20 @@ impl ConfigInner {
         self.config = Arc::new(Config::default_config());
         self.error.take();
         self.generation += 1;
+        self.apply_width_overrides();
     }
 
     fn use_this_config(&mut self, cfg: Config) {
         self.config = Arc::new(cfg);
         self.error.take();
         self.generation += 1;
+        self.apply_width_overrides();
+    }
+
+    /// The width of text is computed in many places that don't have
+    /// access to the config, so the overrides are applied globally
+    fn apply_width_overrides(&self) {
+        termwiz::cell::set_width_overrides(self.config.unicode_width_overrides.clone());
     }
 
     fn overridden(&mut self, overrides: &wezterm_dynamic::Value) -> Result<ConfigHandle, Error> {
@@ -670,6 +745,11 @@ impl Configuration {
         inner.reload();
     }
 
+    fn appearance_changed(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.appearance_changed();
+    }
+
     /// Returns a copy of any captured error message.
     /// The error message is not cleared.
     pub fn get_error(&self) -> Option<String> {
@@ -308,6 +308,10 @@ struct LuaFontAttributes {
     pub scale: Option<NotNan<f64>>,
     #[dynamic(default)]
     pub assume_emoji_presentation: Option<bool>,
+    #[dynamic(default)]
+    pub synthetic_bold_strength: Option<NotNan<f64>>,
+    #[dynamic(default)]
+    pub synthetic_italic_slant: Option<NotNan<f64>>,
 }
 impl<'lua> FromLua<'lua> for LuaFontAttributes {
     fn from_lua(value: Value<'lua>, _lua: &'lua Lua) -> Result<Self, mlua::Error> {
@@ -397,6 +401,8 @@ fn font<'lua>(
             },
             scale: attrs.scale,
             assume_emoji_presentation: attrs.assume_emoji_presentation,
+            synthetic_bold_strength: attrs.synthetic_bold_strength,
+            synthetic_italic_slant: attrs.synthetic_italic_slant,
         }));
 
     Ok(text_style)
@@ -448,6 +454,8 @@ fn font_with_fallback<'lua>(
                 },
                 scale: attrs.scale,
                 assume_emoji_presentation: attrs.assume_emoji_presentation,
+                synthetic_bold_strength: attrs.synthetic_bold_strength,
+                synthetic_italic_slant: attrs.synthetic_italic_slant,
             }));
     }
 
@@ -0,0 +1,103 @@
+use crate::*;
+use anyhow::Context;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+
+/// The subset of an ssh domain that can be edited from the
+/// settings editor
+#[derive(Default, Debug, Clone, FromDynamic, ToDynamic)]
+pub struct ManagedSshDomain {
+    pub name: String,
+    pub remote_address: String,
+    pub username: Option<String>,
+}
+
+/// Settings that are edited at runtime by the settings editor overlay
+/// rather than by hand.  They are persisted to a toml file that sits
+/// alongside the lua config, and are merged into the configuration
+/// each time that it is loaded.
+#[derive(Default, Debug, Clone, FromDynamic, ToDynamic)]
+pub struct ManagedSettings {
+    /// Overrides the `default_prog` from the lua config
+    pub default_prog: Option<Vec<String>>,
+    /// Appended to the `launch_menu` from the lua config
+    #[dynamic(default)]
+    pub launch_menu: Vec<SpawnCommand>,
+    /// Added to `ssh_domains`, unless the lua config already
+    /// defines a domain with the same name
+    #[dynamic(default)]
+    pub ssh_domains: Vec<ManagedSshDomain>,
+}
+
+impl ManagedSettings {
+    /// Load the settings from the specified path.
+    /// A missing file is treated as empty settings.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err).with_context(|| format!("reading {}", path.display()));
+            }
+        };
+        let value: toml::Value =
+            toml::from_str(&text).with_context(|| format!("parsing {}", path.display()))?;
+        Self::from_dynamic(&toml_to_dynamic(&value), Default::default())
+            .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let value = crate::color::dynamic_to_toml(self.to_dynamic())?;
+        let text = format!(
+            "# This file is managed by the wezterm settings editor.\n\
+             # Changes made to it by hand may be overwritten.\n\n{}",
+            toml::to_string_pretty(&value)?
+        );
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating {}", parent.display()))?;
+        }
+        std::fs::write(path, text).with_context(|| format!("writing {}", path.display()))
+    }
+}
+
+impl Config {
+    /// Returns the path to the file that holds the managed settings
+    pub fn managed_settings_path(&self) -> PathBuf {
+        self.managed_settings_file
+            .clone()
+            .unwrap_or_else(|| CONFIG_DIR.join("managed-settings.toml"))
+    }
+
+    /// Merges the managed settings into this configuration
+    pub(crate) fn apply_managed_settings(&mut self) {
+        let settings = match ManagedSettings::load(&self.managed_settings_path()) {
+            Ok(settings) => settings,
+            Err(err) => {
+                log::error!("{:#}", err);
+                return;
+            }
+        };
+
+        if let Some(prog) = settings.default_prog {
+            self.default_prog.replace(prog);
+        }
+        self.launch_menu.extend(settings.launch_menu);
+
+        for managed in settings.ssh_domains {
+            if self.ssh_domains.iter().any(|ssh| ssh.name == managed.name) {
+                log::warn!(
+                    "ssh domain `{}` from the managed settings is shadowed \
+                     by the ssh domain of the same name in the config file",
+                    managed.name
+                );
+                continue;
+            }
+            // Round trip through the dynamic representation so that the
+            // defaults for the remaining fields are applied
+            match SshDomain::from_dynamic(&managed.to_dynamic(), Default::default()) {
+                Ok(ssh) => self.ssh_domains.push(ssh),
+                Err(err) => log::error!("ssh domain `{}`: {}", managed.name, err),
+            }
+        }
+    }
+}
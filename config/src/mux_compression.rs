@@ -0,0 +1,40 @@
+use crate::default_true;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+
+/// Controls the compression of the PDUs that are exchanged with the
+/// server of a unix, ssh or tls domain.  The client applies it to the
+/// PDUs that it sends, and asks the server to apply it to the PDUs
+/// that it sends back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct MuxCompression {
+    #[dynamic(default = "default_true")]
+    pub enabled: bool,
+    /// Only PDUs whose serialized size is larger than this many
+    /// bytes are compressed
+    #[dynamic(default = "default_compression_threshold")]
+    pub threshold: usize,
+    /// The zstd compression level.  Higher levels make the PDUs
+    /// smaller, at the cost of more CPU time, which is worthwhile
+    /// on slow links.
+    #[dynamic(default = "default_compression_level")]
+    pub level: i32,
+}
+
+impl Default for MuxCompression {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold: default_compression_threshold(),
+            level: default_compression_level(),
+        }
+    }
+}
+
+fn default_compression_threshold() -> usize {
+    32
+}
+
+fn default_compression_level() -> i32 {
+    // zstd's default level
+    3
+}
@@ -91,6 +91,15 @@ pub struct SshDomain {
 
     #[dynamic(default)]
     pub assume_shell: Shell,
+
+    /// Whether programs in this domain may set or read the local
+    /// clipboard
+    #[dynamic(default)]
+    pub clipboard_policy: Option<ClipboardPolicy>,
+
+    /// How the PDUs exchanged with the server are compressed
+    #[dynamic(default)]
+    pub mux_compression: MuxCompression,
 }
 
 #[derive(Clone, Debug)]
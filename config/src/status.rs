@@ -0,0 +1,49 @@
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+
+/// Where a status segment is shown in the tab bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub enum StatusPosition {
+    /// Before the tabs
+    Left,
+    /// Centered in the space between the tabs and the right status
+    Center,
+    /// Aligned to the right edge of the tab bar
+    Right,
+}
+
+/// A named area of the status that is updated independently of
+/// the others, via `window:set_status`
+#[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct StatusSegment {
+    pub name: String,
+    pub position: StatusPosition,
+    /// If set, the `update-status-<name>` event is emitted at this
+    /// interval, in milliseconds, so that the segment can be
+    /// refreshed without rebuilding the rest of the status
+    #[dynamic(default)]
+    pub update_interval: Option<u64>,
+}
+
+impl StatusSegment {
+    /// The segments that are available without any configuration;
+    /// one for each position, named after it
+    pub fn builtin() -> Vec<StatusSegment> {
+        [
+            (StatusPosition::Left, "left"),
+            (StatusPosition::Center, "center"),
+            (StatusPosition::Right, "right"),
+        ]
+        .iter()
+        .map(|&(position, name)| StatusSegment {
+            name: name.to_string(),
+            position,
+            update_interval: None,
+        })
+        .collect()
+    }
+
+    /// The name of the event that is emitted to update this segment
+    pub fn event_name(&self) -> String {
+        format!("update-status-{}", self.name)
+    }
+}
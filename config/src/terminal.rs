@@ -1,6 +1,6 @@
 //! Bridge our gui config into the terminal crate configuration
 
-use crate::{configuration, ConfigHandle, NewlineCanon};
+use crate::{configuration, ConfigHandle, NewlineCanon, WindowOperationKind};
 use std::sync::Mutex;
 use termwiz::cell::UnicodeVersion;
 use wezterm_term::color::ColorPalette;
@@ -45,6 +45,14 @@ impl wezterm_term::TerminalConfiguration for TermConfig {
         self.configuration().scrollback_lines
     }
 
+    fn alternate_screen_scrollback_size(&self) -> usize {
+        self.configuration().alternate_screen_scrollback_lines
+    }
+
+    fn reflow_on_resize(&self) -> bool {
+        self.configuration().reflow_on_resize
+    }
+
     fn enable_csi_u_key_encoding(&self) -> bool {
         self.configuration().enable_csi_u_key_encoding
     }
@@ -59,6 +67,11 @@ impl wezterm_term::TerminalConfiguration for TermConfig {
         self.configuration().alternate_buffer_wheel_scroll_speed
     }
 
+    fn alternate_buffer_horizontal_wheel_arrow_keys(&self) -> bool {
+        self.configuration()
+            .alternate_buffer_horizontal_wheel_arrow_keys
+    }
+
     fn enq_answerback(&self) -> String {
         configuration().enq_answerback.clone()
     }
@@ -104,4 +117,27 @@ impl wezterm_term::TerminalConfiguration for TermConfig {
             hint: config.bidi_direction,
         }
     }
+
+    fn term_capability(&self, name: &str) -> Option<String> {
+        self.configuration().term_capabilities.get(name).cloned()
+    }
+
+    fn allow_window_operation(&self, kind: wezterm_term::config::WindowOperationKind) -> bool {
+        use wezterm_term::config::WindowOperationKind as Kind;
+        let kind = match kind {
+            Kind::ReportSize => WindowOperationKind::ReportSize,
+            Kind::ReportPosition => WindowOperationKind::ReportPosition,
+            Kind::ReportState => WindowOperationKind::ReportState,
+            Kind::ReportTitle => WindowOperationKind::ReportTitle,
+            Kind::Resize => WindowOperationKind::Resize,
+            Kind::Move => WindowOperationKind::Move,
+            Kind::Iconify => WindowOperationKind::Iconify,
+            Kind::RaiseLower => WindowOperationKind::RaiseLower,
+            Kind::Maximize => WindowOperationKind::Maximize,
+            Kind::FullScreen => WindowOperationKind::FullScreen,
+        };
+        self.configuration()
+            .allowed_window_operations
+            .contains(&kind)
+    }
 }
@@ -23,6 +23,12 @@ pub struct TlsDomainServer {
     /// to the trust store.
     #[dynamic(default)]
     pub pem_root_certs: Vec<PathBuf>,
+
+    /// If set, the address:port combination on which to serve a web
+    /// dashboard for administering the mux server.  It uses the same
+    /// certificates as the TLS domain, and requires the browser to
+    /// present a client certificate that would be accepted by it.
+    pub web_dashboard_bind_address: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, FromDynamic, ToDynamic)]
@@ -64,6 +70,14 @@ pub struct TlsDomainClient {
     #[dynamic(default)]
     pub accept_invalid_hostnames: bool,
 
+    /// If true, and the certificate presented by the server can't be
+    /// verified, ask whether to trust it the first time that it is
+    /// seen, and remember its fingerprint.  Subsequent connections
+    /// accept only that certificate until it is revoked with
+    /// `wezterm cli tls trust revoke`.
+    #[dynamic(default)]
+    pub trust_on_first_use: bool,
+
     /// the hostname string that we expect to match against the common name
     /// field in the certificate presented by the server.  This defaults to
     /// the hostname portion of the `remote_address` configuration and you
@@ -85,6 +99,15 @@ pub struct TlsDomainClient {
 
     /// The path to the wezterm binary on the remote host
     pub remote_wezterm_path: Option<String>,
+
+    /// Whether programs in this domain may set or read the local
+    /// clipboard
+    #[dynamic(default)]
+    pub clipboard_policy: Option<ClipboardPolicy>,
+
+    /// How the PDUs exchanged with the server are compressed
+    #[dynamic(default)]
+    pub mux_compression: MuxCompression,
 }
 
 impl TlsDomainClient {
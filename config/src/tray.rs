@@ -0,0 +1,16 @@
+use crate::keyassignment::KeyAssignment;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+
+/// An entry in the menu of the system tray icon
+#[derive(Debug, Clone, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct TrayMenuEntry {
+    #[dynamic(default)]
+    pub label: String,
+    /// The action to perform when the entry is selected.
+    /// An entry without an action is shown disabled.
+    #[dynamic(default)]
+    pub action: Option<KeyAssignment>,
+    /// Show a separator line rather than a selectable entry
+    #[dynamic(default)]
+    pub separator: bool,
+}
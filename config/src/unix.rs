@@ -53,6 +53,15 @@ pub struct UnixDomain {
     /// Don't use default_local_echo_threshold_ms() here to
     /// disable the predictive echo for Unix domains by default.
     pub local_echo_threshold_ms: Option<u64>,
+
+    /// Whether programs in this domain may set or read the local
+    /// clipboard
+    #[dynamic(default)]
+    pub clipboard_policy: Option<ClipboardPolicy>,
+
+    /// How the PDUs exchanged with the server are compressed
+    #[dynamic(default)]
+    pub mux_compression: MuxCompression,
 }
 
 impl Default for UnixDomain {
@@ -68,6 +77,8 @@ impl Default for UnixDomain {
             write_timeout: default_write_timeout(),
             local_echo_threshold_ms: None,
             proxy_command: None,
+            clipboard_policy: None,
+            mux_compression: MuxCompression::default(),
         }
     }
 }
@@ -0,0 +1,71 @@
+use crate::*;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+
+/// The position of the top left corner of a window, in screen coordinates
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct WindowPresetPosition {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// A named window size, font scale and position, applied using
+/// `window:apply_preset`
+#[derive(Default, Debug, Clone, PartialEq, FromDynamic, ToDynamic)]
+pub struct WindowSizePreset {
+    pub name: String,
+    /// The size of the terminal area in cells
+    pub cols: Option<usize>,
+    pub rows: Option<usize>,
+    /// The size of the inside of the window in pixels, which fixes
+    /// the aspect ratio of the window.  Used when cols and rows are
+    /// not specified.
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+    /// The font scale, relative to font_size.
+    /// If omitted, the current font scale of the window is retained.
+    pub font_scale: Option<f64>,
+    pub position: Option<WindowPresetPosition>,
+}
+
+/// The size that a `WindowSizePreset` resizes the window to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowPresetSize {
+    Cells { cols: usize, rows: usize },
+    Pixels { width: usize, height: usize },
+}
+
+impl WindowSizePreset {
+    /// Validates the preset and returns the size that it specifies
+    pub fn size(&self) -> anyhow::Result<WindowPresetSize> {
+        if let Some(font_scale) = self.font_scale {
+            if !font_scale.is_finite() || font_scale <= 0.0 {
+                anyhow::bail!(
+                    "window size preset `{}` has an invalid font_scale {}",
+                    self.name,
+                    font_scale
+                );
+            }
+        }
+        match (self.cols, self.rows, self.width, self.height) {
+            (Some(cols), Some(rows), None, None) if cols > 0 && rows > 0 => {
+                Ok(WindowPresetSize::Cells { cols, rows })
+            }
+            (None, None, Some(width), Some(height)) if width > 0 && height > 0 => {
+                Ok(WindowPresetSize::Pixels { width, height })
+            }
+            _ => anyhow::bail!(
+                "window size preset `{}` must specify either non-zero cols and rows \
+                 or non-zero width and height",
+                self.name
+            ),
+        }
+    }
+}
+
+impl Config {
+    pub fn window_size_preset_by_name(&self, name: &str) -> Option<&WindowSizePreset> {
+        self.window_size_presets
+            .iter()
+            .find(|preset| preset.name == name)
+    }
+}
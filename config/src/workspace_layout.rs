@@ -0,0 +1,115 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+
+/// Where a split pane is placed relative to the pane that it
+/// was split from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, FromDynamic, ToDynamic)]
+pub enum LayoutSplitDirection {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl Default for LayoutSplitDirection {
+    fn default() -> Self {
+        Self::Right
+    }
+}
+
+/// Describes a pane in a workspace layout, along with the panes
+/// that are split off from it.  The serde representation is the
+/// same as the lua representation, and is used by
+/// `wezterm cli split-pane --layout`.
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize, FromDynamic, ToDynamic)]
+#[serde(default)]
+pub struct PaneLayout {
+    /// The command to run.  If omitted, the default program for
+    /// the domain is used.
+    pub args: Option<Vec<String>>,
+    /// The working directory for the command.  A leading `~` is
+    /// expanded to the home directory.  If omitted, splits use the
+    /// directory of the pane that they are split from.
+    pub cwd: Option<String>,
+    #[dynamic(default)]
+    pub set_environment_variables: HashMap<String, String>,
+    /// The name of the domain to spawn into.  If omitted, tabs use
+    /// the default domain, and splits use the domain of the pane
+    /// that they are split from.
+    pub domain: Option<String>,
+    /// The panes that are split off from this pane, in order
+    #[dynamic(default)]
+    pub splits: Vec<SplitLayout>,
+}
+
+impl PaneLayout {
+    pub fn resolved_cwd(&self) -> Option<String> {
+        let cwd = self.cwd.as_ref()?;
+        if cwd == "~" {
+            Some(HOME_DIR.display().to_string())
+        } else if let Some(rest) = cwd.strip_prefix("~/") {
+            Some(HOME_DIR.join(rest).display().to_string())
+        } else {
+            Some(cwd.clone())
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize, FromDynamic, ToDynamic)]
+pub struct SplitLayout {
+    #[dynamic(default)]
+    #[serde(default)]
+    pub direction: LayoutSplitDirection,
+    /// Values less than 1.0 are a fraction of the pane being split,
+    /// other values are a number of cells
+    #[dynamic(default = "default_split_size")]
+    #[serde(default = "default_split_size")]
+    pub size: f32,
+    #[dynamic(flatten)]
+    #[serde(flatten)]
+    pub pane: PaneLayout,
+}
+
+fn default_split_size() -> f32 {
+    0.5
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize, FromDynamic, ToDynamic)]
+#[serde(default)]
+pub struct WindowLayout {
+    /// The root pane of each tab in the window
+    #[dynamic(default)]
+    pub tabs: Vec<PaneLayout>,
+}
+
+/// A named, declarative description of the windows, tabs and panes
+/// of a workspace
+#[derive(Default, Debug, Clone, PartialEq, Deserialize, Serialize, FromDynamic, ToDynamic)]
+#[serde(default)]
+pub struct WorkspaceLayout {
+    pub name: String,
+    /// The workspace to create the windows in.
+    /// Defaults to the name of the layout.
+    pub workspace: Option<String>,
+    /// If true, apply this layout when the gui starts
+    #[dynamic(default)]
+    pub apply_at_startup: bool,
+    #[dynamic(default)]
+    pub windows: Vec<WindowLayout>,
+}
+
+impl WorkspaceLayout {
+    pub fn workspace_name(&self) -> &str {
+        self.workspace.as_deref().unwrap_or(&self.name)
+    }
+}
+
+impl Config {
+    pub fn workspace_layout_by_name(&self, name: &str) -> Option<&WorkspaceLayout> {
+        self.workspace_layouts
+            .iter()
+            .find(|layout| layout.name == name)
+    }
+}
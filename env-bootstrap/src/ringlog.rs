@@ -159,6 +159,7 @@ impl log::Log for Logger {
 
     fn log(&self, record: &Record) {
         RINGS.lock().unwrap().log(record);
+        logging::capture::capture_record(record);
         if self.filter.matches(record) {
             let ts = Local::now().format("%H:%M:%S%.3f").to_string();
             let level = record.level().as_str();
@@ -277,6 +278,7 @@ fn setup_pretty() -> (LevelFilter, Logger) {
 pub fn setup_logger() {
     let (max_level, logger) = setup_pretty();
     if log::set_boxed_logger(Box::new(logger)).is_ok() {
-        log::set_max_level(max_level);
+        // Captures made by `wezterm.log_capture` may raise this
+        logging::capture::set_base_max_level(max_level);
     }
 }
@@ -0,0 +1,54 @@
+#![cfg(all(not(target_os = "macos"), not(windows)))]
+//! See <https://upower.freedesktop.org/docs/>
+
+use futures_util::stream::{select, StreamExt};
+use zbus::dbus_proxy;
+use zvariant::OwnedObjectPath;
+
+#[dbus_proxy(
+    interface = "org.freedesktop.UPower",
+    default_service = "org.freedesktop.UPower",
+    default_path = "/org/freedesktop/UPower"
+)]
+trait UPower {
+    #[dbus_proxy(signal)]
+    fn device_added(&self, device: OwnedObjectPath) -> zbus::Result<()>;
+
+    #[dbus_proxy(signal)]
+    fn device_removed(&self, device: OwnedObjectPath) -> zbus::Result<()>;
+}
+
+/// The composite device whose properties summarize all of the batteries,
+/// and which therefore changes whenever any of them does
+const DISPLAY_DEVICE: &str = "/org/freedesktop/UPower/devices/DisplayDevice";
+
+async fn watch_upower() -> zbus::Result<()> {
+    let connection = zbus::ConnectionBuilder::system()?.build().await?;
+
+    let upower = UPowerProxy::new(&connection).await?;
+    let display_device = zbus::fdo::PropertiesProxy::builder(&connection)
+        .destination("org.freedesktop.UPower")?
+        .path(DISPLAY_DEVICE)?
+        .build()
+        .await?;
+
+    let added = upower.receive_device_added().await?.map(|_| ());
+    let removed = upower.receive_device_removed().await?.map(|_| ());
+    let changed = display_device
+        .receive_properties_changed()
+        .await?
+        .map(|_| ());
+
+    let mut events = select(added, select(removed, changed));
+    while events.next().await.is_some() {
+        crate::power::power_status_changed();
+    }
+    Ok(())
+}
+
+/// Reports changes to the power status until the connection to
+/// the system bus is lost
+pub fn watch_power_status() -> anyhow::Result<()> {
+    async_std::task::block_on(watch_upower())?;
+    Ok(())
+}
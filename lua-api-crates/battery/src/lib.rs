@@ -3,13 +3,18 @@ use config::lua::mlua::{self, Lua};
 use luahelper::impl_lua_conversion_dynamic;
 use wezterm_dynamic::{FromDynamic, ToDynamic};
 
+mod dbus;
+mod macos;
+mod power;
+mod windows;
+
 pub fn register(lua: &Lua) -> anyhow::Result<()> {
     let wezterm_mod = get_or_create_module(lua, "wezterm")?;
     wezterm_mod.set("battery_info", lua.create_function(battery_info)?)?;
     Ok(())
 }
 
-#[derive(FromDynamic, ToDynamic, Debug)]
+#[derive(FromDynamic, ToDynamic, Debug, Clone)]
 struct BatteryInfo {
     state_of_charge: f32,
     vendor: String,
@@ -18,39 +23,15 @@ struct BatteryInfo {
     serial: String,
     time_to_full: Option<f32>,
     time_to_empty: Option<f32>,
+    /// Watts flowing into the battery when it is charging,
+    /// or out of it (as a negative number) when it is discharging
+    charge_rate: f32,
+    /// Seconds until the battery is full when it is charging,
+    /// or empty when it is discharging
+    time_remaining: Option<f32>,
 }
 impl_lua_conversion_dynamic!(BatteryInfo);
 
 fn battery_info<'lua>(_: &'lua Lua, _: ()) -> mlua::Result<Vec<BatteryInfo>> {
-    use starship_battery::{Manager, State};
-    let manager = Manager::new().map_err(|e| mlua::Error::external(e))?;
-    let mut result = vec![];
-    for b in manager.batteries().map_err(|e| mlua::Error::external(e))? {
-        let bat = b.map_err(|e| mlua::Error::external(e))?;
-        result.push(BatteryInfo {
-            state_of_charge: bat.state_of_charge().value,
-            vendor: opt_string(bat.vendor()),
-            model: opt_string(bat.model()),
-            serial: opt_string(bat.serial_number()),
-            state: match bat.state() {
-                State::Charging => "Charging",
-                State::Discharging => "Discharging",
-                State::Empty => "Empty",
-                State::Full => "Full",
-                State::Unknown | _ => "Unknown",
-            }
-            .to_string(),
-            time_to_full: bat.time_to_full().map(|q| q.value),
-            time_to_empty: bat.time_to_empty().map(|q| q.value),
-        })
-    }
-    Ok(result)
-}
-
-fn opt_string(s: Option<&str>) -> String {
-    match s {
-        Some(s) => s,
-        None => "unknown",
-    }
-    .to_string()
+    power::battery_info().map_err(|e| mlua::Error::external(format!("{:#}", e)))
 }
@@ -0,0 +1,33 @@
+#![cfg(target_os = "macos")]
+
+use core_foundation::base::TCFType;
+use core_foundation::runloop::{
+    kCFRunLoopDefaultMode, CFRunLoop, CFRunLoopSource, CFRunLoopSourceRef,
+};
+use std::os::raw::c_void;
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    fn IOPSNotificationCreateRunLoopSource(
+        callback: extern "C" fn(*mut c_void),
+        context: *mut c_void,
+    ) -> CFRunLoopSourceRef;
+}
+
+extern "C" fn power_sources_changed(_context: *mut c_void) {
+    crate::power::power_status_changed();
+}
+
+/// Runs a run loop on the current thread that receives the
+/// notifications that IOKit sends when a power source changes
+pub fn watch_power_status() -> anyhow::Result<()> {
+    let source =
+        unsafe { IOPSNotificationCreateRunLoopSource(power_sources_changed, std::ptr::null_mut()) };
+    if source.is_null() {
+        anyhow::bail!("IOPSNotificationCreateRunLoopSource failed");
+    }
+    let source = unsafe { CFRunLoopSource::wrap_under_create_rule(source) };
+    CFRunLoop::get_current().add_source(&source, unsafe { kCFRunLoopDefaultMode });
+    CFRunLoop::run_current();
+    Ok(())
+}
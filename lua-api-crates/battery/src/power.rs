@@ -0,0 +1,123 @@
+//! Keeps the information about the batteries up to date by listening
+//! for the notifications that the system sends when the power status
+//! changes, rather than querying the batteries each time that
+//! `wezterm.battery_info()` is called, which is typically once per
+//! second from the `update-right-status` event.
+//! The notifications come from UPower via dbus on unix systems, from
+//! WM_POWERBROADCAST on Windows and from IOKit on macOS.  If they are
+//! not available, the batteries are queried on each call.
+use crate::BatteryInfo;
+use std::sync::{Mutex, Once};
+
+#[cfg(all(not(target_os = "macos"), not(windows)))]
+use crate::dbus as backend;
+#[cfg(target_os = "macos")]
+use crate::macos as backend;
+#[cfg(windows)]
+use crate::windows as backend;
+
+#[derive(Default)]
+struct PowerStatus {
+    /// The batteries as of the most recent query, or None if the
+    /// power status has changed since then
+    batteries: Option<Vec<BatteryInfo>>,
+    /// Whether changes to the power status are being watched
+    watching: bool,
+}
+
+lazy_static::lazy_static! {
+    static ref STATUS: Mutex<PowerStatus> = Mutex::new(PowerStatus::default());
+}
+
+static START_WATCHER: Once = Once::new();
+
+/// Called by the backend when the system reports that the
+/// power status changed
+pub fn power_status_changed() {
+    STATUS.lock().unwrap().batteries.take();
+}
+
+fn start_watcher() {
+    STATUS.lock().unwrap().watching = true;
+    let res = std::thread::Builder::new()
+        .name("power-status".to_string())
+        .spawn(|| {
+            if let Err(err) = backend::watch_power_status() {
+                log::warn!(
+                    "Unable to watch the power status; \
+                     batteries will be queried each time that \
+                     battery_info is called: {:#}",
+                    err
+                );
+            }
+            let mut status = STATUS.lock().unwrap();
+            status.watching = false;
+            status.batteries.take();
+        });
+    if let Err(err) = res {
+        log::error!("failed to spawn power-status thread: {:#}", err);
+        STATUS.lock().unwrap().watching = false;
+    }
+}
+
+fn query_batteries() -> anyhow::Result<Vec<BatteryInfo>> {
+    use starship_battery::{Manager, State};
+    let manager = Manager::new()?;
+    let mut result = vec![];
+    for b in manager.batteries()? {
+        let bat = b?;
+        let rate = bat.energy_rate().value;
+        result.push(BatteryInfo {
+            state_of_charge: bat.state_of_charge().value,
+            vendor: opt_string(bat.vendor()),
+            model: opt_string(bat.model()),
+            serial: opt_string(bat.serial_number()),
+            state: match bat.state() {
+                State::Charging => "Charging",
+                State::Discharging => "Discharging",
+                State::Empty => "Empty",
+                State::Full => "Full",
+                State::Unknown | _ => "Unknown",
+            }
+            .to_string(),
+            time_to_full: bat.time_to_full().map(|q| q.value),
+            time_to_empty: bat.time_to_empty().map(|q| q.value),
+            charge_rate: match bat.state() {
+                State::Discharging => -rate,
+                _ => rate,
+            },
+            time_remaining: match bat.state() {
+                State::Charging => bat.time_to_full().map(|q| q.value),
+                State::Discharging => bat.time_to_empty().map(|q| q.value),
+                _ => None,
+            },
+        })
+    }
+    Ok(result)
+}
+
+fn opt_string(s: Option<&str>) -> String {
+    match s {
+        Some(s) => s,
+        None => "unknown",
+    }
+    .to_string()
+}
+
+/// Returns the batteries as of the most recent change to the
+/// power status
+pub fn battery_info() -> anyhow::Result<Vec<BatteryInfo>> {
+    START_WATCHER.call_once(start_watcher);
+
+    // Hold the lock while querying, so that a change that is reported
+    // meanwhile discards the result rather than being lost
+    let mut status = STATUS.lock().unwrap();
+    if let Some(batteries) = &status.batteries {
+        return Ok(batteries.clone());
+    }
+    let batteries = query_batteries()?;
+    if status.watching {
+        status.batteries.replace(batteries.clone());
+    }
+    Ok(batteries)
+}
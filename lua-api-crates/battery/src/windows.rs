@@ -0,0 +1,83 @@
+#![cfg(windows)]
+
+use std::ffi::OsStr;
+use std::os::windows::ffi::OsStrExt;
+use std::ptr::null_mut;
+use winapi::shared::minwindef::{LPARAM, LRESULT, TRUE, UINT, WPARAM};
+use winapi::shared::windef::HWND;
+use winapi::um::libloaderapi::GetModuleHandleW;
+use winapi::um::winuser::{
+    CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+    TranslateMessage, MSG, PBT_APMPOWERSTATUSCHANGE, PBT_APMRESUMEAUTOMATIC, WM_POWERBROADCAST,
+    WNDCLASSW, WS_OVERLAPPED,
+};
+
+fn wide_string(s: &str) -> Vec<u16> {
+    OsStr::new(s).encode_wide().chain(Some(0)).collect()
+}
+
+unsafe extern "system" fn wnd_proc(
+    hwnd: HWND,
+    msg: UINT,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if msg == WM_POWERBROADCAST
+        && (wparam == PBT_APMPOWERSTATUSCHANGE || wparam == PBT_APMRESUMEAUTOMATIC)
+    {
+        crate::power::power_status_changed();
+        return TRUE as LRESULT;
+    }
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
+
+/// Creates a hidden window on the current thread and dispatches its
+/// messages, reporting the WM_POWERBROADCAST messages that it receives.
+/// A top level window is used rather than a message-only window,
+/// because broadcast messages are not sent to message-only windows.
+pub fn watch_power_status() -> anyhow::Result<()> {
+    let class_name = wide_string("WezTermPowerStatus");
+    unsafe {
+        let instance = GetModuleHandleW(null_mut());
+        let class = WNDCLASSW {
+            style: 0,
+            lpfnWndProc: Some(wnd_proc),
+            cbClsExtra: 0,
+            cbWndExtra: 0,
+            hInstance: instance,
+            hIcon: null_mut(),
+            hCursor: null_mut(),
+            hbrBackground: null_mut(),
+            lpszMenuName: null_mut(),
+            lpszClassName: class_name.as_ptr(),
+        };
+        if RegisterClassW(&class) == 0 {
+            anyhow::bail!("RegisterClassW: {}", std::io::Error::last_os_error());
+        }
+
+        let hwnd = CreateWindowExW(
+            0,
+            class_name.as_ptr(),
+            class_name.as_ptr(),
+            WS_OVERLAPPED,
+            0,
+            0,
+            0,
+            0,
+            null_mut(),
+            null_mut(),
+            instance,
+            null_mut(),
+        );
+        if hwnd.is_null() {
+            anyhow::bail!("CreateWindowExW: {}", std::io::Error::last_os_error());
+        }
+
+        let mut msg: MSG = std::mem::zeroed();
+        while GetMessageW(&mut msg, null_mut(), 0, 0) > 0 {
+            TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    }
+    Ok(())
+}
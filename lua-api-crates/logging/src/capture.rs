@@ -0,0 +1,181 @@
+//! Allows lua code to subscribe to log records using `wezterm.log_capture`.
+//! The logger that is set up by env-bootstrap passes every record to
+//! `capture_record`, which queues it for each matching capture until
+//! the script collects it.
+use config::lua::mlua::{self, Lua, MetaMethod, UserData, UserDataMethods};
+use log::{LevelFilter, Record};
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The maximum number of records that are held for a capture;
+/// older records are discarded when more arrive
+const MAX_RECORDS: usize = 1024;
+
+lazy_static::lazy_static! {
+    static ref CAPTURES: Mutex<Captures> = Mutex::new(Captures::default());
+}
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, PartialEq)]
+struct CapturedRecord {
+    /// Seconds since the unix epoch
+    time: f64,
+    level: log::Level,
+    target: String,
+    message: String,
+}
+
+struct Capture {
+    level: LevelFilter,
+    filter: String,
+    records: VecDeque<CapturedRecord>,
+}
+
+impl Capture {
+    fn matches(&self, record: &Record) -> bool {
+        record.level() <= self.level && record.target().starts_with(&self.filter)
+    }
+}
+
+#[derive(Default)]
+struct Captures {
+    /// The max level that the logger was configured with
+    base_level: Option<LevelFilter>,
+    captures: HashMap<usize, Capture>,
+}
+
+impl Captures {
+    /// Ensures that records at the levels that are being
+    /// captured are passed to the logger
+    fn update_max_level(&self) {
+        let base_level = match self.base_level {
+            Some(level) => level,
+            // The logger isn't ours, so leave it alone
+            None => return,
+        };
+        let level = self
+            .captures
+            .values()
+            .map(|c| c.level)
+            .fold(base_level, LevelFilter::max);
+        log::set_max_level(level);
+    }
+}
+
+/// Records the max level that the logger was set up with,
+/// so that it can be restored when captures are removed
+pub fn set_base_max_level(level: LevelFilter) {
+    let mut captures = CAPTURES.lock().unwrap();
+    captures.base_level.replace(level);
+    captures.update_max_level();
+}
+
+/// Queues `record` for each capture that matches it
+pub fn capture_record(record: &Record) {
+    let mut captures = CAPTURES.lock().unwrap();
+    if captures.captures.is_empty() {
+        return;
+    }
+    let mut captured = None;
+    for capture in captures.captures.values_mut() {
+        if !capture.matches(record) {
+            continue;
+        }
+        let captured = captured
+            .get_or_insert_with(|| CapturedRecord {
+                time: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs_f64())
+                    .unwrap_or(0.),
+                level: record.level(),
+                target: record.target().to_string(),
+                message: record.args().to_string(),
+            })
+            .clone();
+        if capture.records.len() == MAX_RECORDS {
+            capture.records.pop_front();
+        }
+        capture.records.push_back(captured);
+    }
+}
+
+/// A subscription to log records, returned by `wezterm.log_capture`
+pub struct LogCapture {
+    id: usize,
+}
+
+impl LogCapture {
+    fn new(level: LevelFilter, filter: String) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let mut captures = CAPTURES.lock().unwrap();
+        captures.captures.insert(
+            id,
+            Capture {
+                level,
+                filter,
+                records: VecDeque::new(),
+            },
+        );
+        captures.update_max_level();
+        Self { id }
+    }
+
+    fn take_records(&self) -> Vec<CapturedRecord> {
+        CAPTURES
+            .lock()
+            .unwrap()
+            .captures
+            .get_mut(&self.id)
+            .map(|capture| capture.records.drain(..).collect())
+            .unwrap_or_default()
+    }
+
+    fn close(&self) {
+        let mut captures = CAPTURES.lock().unwrap();
+        captures.captures.remove(&self.id);
+        captures.update_max_level();
+    }
+}
+
+impl Drop for LogCapture {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl UserData for LogCapture {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, _: ()| {
+            Ok(format!("LogCapture({})", this.id))
+        });
+        methods.add_method("records", |lua, this, _: ()| {
+            let records = lua.create_table()?;
+            for (idx, record) in this.take_records().into_iter().enumerate() {
+                let tbl = lua.create_table()?;
+                tbl.set("time", record.time)?;
+                tbl.set("level", record.level.as_str())?;
+                tbl.set("target", record.target)?;
+                tbl.set("message", record.message)?;
+                records.set(idx + 1, tbl)?;
+            }
+            Ok(records)
+        });
+        methods.add_method("close", |_, this, _: ()| {
+            this.close();
+            Ok(())
+        });
+    }
+}
+
+pub fn log_capture(_: &Lua, (level, filter): (String, Option<String>)) -> mlua::Result<LogCapture> {
+    let level = LevelFilter::from_str(&level).map_err(|_| {
+        mlua::Error::external(format!(
+            "{} is not a valid log level; use one of error, warn, info, debug or trace",
+            level
+        ))
+    })?;
+    Ok(LogCapture::new(level, filter.unwrap_or_default()))
+}
@@ -2,6 +2,8 @@ use config::lua::get_or_create_module;
 use config::lua::mlua::{Lua, Value, Variadic};
 use luahelper::ValuePrinter;
 
+pub mod capture;
+
 pub fn register(lua: &Lua) -> anyhow::Result<()> {
     let wezterm_mod = get_or_create_module(lua, "wezterm")?;
 
@@ -30,6 +32,8 @@ pub fn register(lua: &Lua) -> anyhow::Result<()> {
         })?,
     )?;
 
+    wezterm_mod.set("log_capture", lua.create_function(capture::log_capture)?)?;
+
     lua.globals().set(
         "print",
         lua.create_function(|_, args: Variadic<Value>| {
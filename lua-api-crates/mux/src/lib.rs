@@ -1,24 +1,72 @@
 use config::keyassignment::SpawnTabDomain;
 use config::lua::mlua::{self, Lua, UserData, UserDataMethods, Value as LuaValue};
 use config::lua::{get_or_create_module, get_or_create_sub_module};
+use config::SplitLayout;
 use luahelper::impl_lua_conversion_dynamic;
-use mux::domain::SplitSource;
+use mux::domain::{Domain, DomainId, SplitSource};
+use mux::history::CommandHistoryQuery;
 use mux::pane::{Pane, PaneId};
-use mux::tab::{SplitDirection, SplitRequest, SplitSize, Tab, TabId};
+use mux::tab::{SplitDirection, SplitLayoutSpec, SplitRequest, SplitSize, Tab, TabId};
 use mux::window::{Window, WindowId};
 use mux::Mux;
 use portable_pty::CommandBuilder;
 use std::cell::{Ref, RefMut};
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::sync::Arc;
+use virtual_pane::{virtual_pane_domain, VirtualPane, VirtualPaneSpec};
 use wezterm_dynamic::{FromDynamic, ToDynamic};
 use wezterm_term::TerminalSize;
 
+mod virtual_pane;
+
 fn get_mux() -> mlua::Result<Rc<Mux>> {
     Mux::get()
         .ok_or_else(|| mlua::Error::external("cannot get Mux: not running on the mux thread?"))
 }
 
+/// Emits the `pre-paste` event for text that is about to be pasted
+/// into the specified pane.
+/// Returns the text that should be pasted, which may have been
+/// replaced by the event handler, or None if the handler returned
+/// `false` to cancel the paste.
+/// Panes that are not known to the mux, such as overlays, don't
+/// have the event emitted for them.
+pub fn apply_pre_paste_hook(pane_id: PaneId, text: String) -> Option<String> {
+    if Mux::get().and_then(|mux| mux.get_pane(pane_id)).is_none() {
+        return Some(text);
+    }
+
+    let result = config::run_immediate_with_lua_config(|lua| {
+        let lua = match lua {
+            Some(lua) => lua,
+            None => return Ok(None),
+        };
+        let v = config::lua::emit_sync_callback(
+            &*lua,
+            ("pre-paste".to_string(), (MuxPane(pane_id), text.clone())),
+        )?;
+        match v {
+            LuaValue::Nil | LuaValue::Boolean(true) => Ok(None),
+            LuaValue::Boolean(false) => Ok(Some(None)),
+            LuaValue::String(s) => Ok(Some(Some(s.to_str()?.to_string()))),
+            _ => anyhow::bail!("pre-paste: expected a string, boolean or nil return value"),
+        }
+    });
+
+    match result {
+        Ok(None) => Some(text),
+        Ok(Some(replacement)) => replacement,
+        Err(err) => {
+            log::error!(
+                "Error while running pre-paste hook: {:#}, pasting the original text",
+                err
+            );
+            Some(text)
+        }
+    }
+}
+
 pub fn register(lua: &Lua) -> anyhow::Result<()> {
     let mux_mod = get_or_create_sub_module(lua, "mux")?;
 
@@ -84,11 +132,89 @@ pub fn register(lua: &Lua) -> anyhow::Result<()> {
         })?,
     )?;
 
+    mux_mod.set(
+        "get_domain",
+        lua.create_function(|_, name: Option<String>| {
+            let mux = get_mux()?;
+            let domain = match name {
+                Some(name) => mux.get_domain_by_name(&name).ok_or_else(|| {
+                    mlua::Error::external(format!("domain {} not found in mux", name))
+                })?,
+                None => mux.default_domain(),
+            };
+            Ok(MuxDomain(domain.domain_id()))
+        })?,
+    )?;
+
     mux_mod.set(
         "spawn_window",
         lua.create_async_function(|_, spawn: SpawnWindow| async move { spawn.spawn().await })?,
     )?;
 
+    mux_mod.set(
+        "get_command_history",
+        lua.create_function(|_, query: Option<CommandHistoryQuery>| {
+            let mux = get_mux()?;
+            Ok(mux.query_command_history(&query.unwrap_or_default()))
+        })?,
+    )?;
+
+    mux_mod.set(
+        "attach_domain_group",
+        lua.create_async_function(|_, name: String| async move {
+            let mux = get_mux()?;
+            mux.attach_domain_group(&name)
+                .await
+                .map_err(|e| mlua::Error::external(format!("{:#}", e)))
+        })?,
+    )?;
+
+    mux_mod.set(
+        "apply_workspace_layout",
+        lua.create_async_function(|_, name: String| async move {
+            let mux = get_mux()?;
+            mux.apply_workspace_layout(&name)
+                .await
+                .map_err(|e| mlua::Error::external(format!("{:#}", e)))
+        })?,
+    )?;
+
+    mux_mod.set(
+        "detach_domain_group",
+        lua.create_function(|_, name: String| {
+            let mux = get_mux()?;
+            mux.detach_domain_group(&name)
+                .map_err(|e| mlua::Error::external(format!("{:#}", e)))
+        })?,
+    )?;
+
+    mux_mod.set(
+        "rename_workspace",
+        lua.create_function(|_, (old_workspace, new_workspace): (String, String)| {
+            let mux = get_mux()?;
+            mux.rename_workspace(&old_workspace, &new_workspace)
+                .map_err(|e| mlua::Error::external(format!("{:#}", e)))
+        })?,
+    )?;
+
+    mux_mod.set(
+        "merge_workspace",
+        lua.create_function(|_, (from_workspace, into_workspace): (String, String)| {
+            let mux = get_mux()?;
+            mux.merge_workspace(&from_workspace, &into_workspace)
+                .map_err(|e| mlua::Error::external(format!("{:#}", e)))
+        })?,
+    )?;
+
+    mux_mod.set(
+        "delete_workspace",
+        lua.create_function(|_, workspace: String| {
+            let mux = get_mux()?;
+            mux.delete_workspace(&workspace)
+                .map_err(|e| mlua::Error::external(format!("{:#}", e)))
+        })?,
+    )?;
+
     mux_mod.set(
         "all_windows",
         lua.create_function(|_, _: ()| {
@@ -204,6 +330,48 @@ impl SplitPane {
     }
 }
 
+#[derive(Debug, Default, FromDynamic, ToDynamic)]
+struct SplitVirtualPane {
+    #[dynamic(flatten)]
+    spec: VirtualPaneSpec,
+    #[dynamic(default)]
+    direction: HandySplitDirection,
+    #[dynamic(default)]
+    top_level: bool,
+    #[dynamic(default = "default_split_size")]
+    size: f32,
+}
+impl_lua_conversion_dynamic!(SplitVirtualPane);
+
+/// The command that the virtual pane domain interprets as
+/// the name of the event of the pane
+fn virtual_pane_command(spec: &VirtualPaneSpec) -> CommandBuilderFrag {
+    CommandBuilderFrag {
+        args: Some(vec![spec.event.clone()]),
+        ..Default::default()
+    }
+}
+
+impl SplitVirtualPane {
+    async fn run(self, pane: MuxPane) -> mlua::Result<MuxPane> {
+        self.spec
+            .validate()
+            .map_err(|e| mlua::Error::external(format!("{:#}", e)))?;
+        let mux = get_mux()?;
+        let split = SplitPane {
+            cmd_builder: virtual_pane_command(&self.spec),
+            domain: SpawnTabDomain::DomainId(virtual_pane_domain(&mux).domain_id()),
+            direction: self.direction,
+            top_level: self.top_level,
+            size: self.size,
+        };
+        let pane = split.run(pane).await?;
+        VirtualPane::configure(pane.0, &self.spec)
+            .map_err(|e| mlua::Error::external(format!("{:#}", e)))?;
+        Ok(pane)
+    }
+}
+
 #[derive(Debug, FromDynamic, ToDynamic)]
 struct SpawnWindow {
     #[dynamic(default = "spawn_tab_default_domain")]
@@ -261,6 +429,8 @@ pub struct MuxWindow(pub WindowId);
 pub struct MuxTab(pub TabId);
 #[derive(Clone, Copy, Debug)]
 pub struct MuxPane(pub PaneId);
+#[derive(Clone, Copy, Debug)]
+pub struct MuxDomain(pub DomainId);
 
 impl MuxWindow {
     fn resolve<'a>(&self, mux: &'a Rc<Mux>) -> mlua::Result<Ref<'a, Window>> {
@@ -363,6 +533,22 @@ impl UserData for MuxWindow {
         methods.add_async_method("spawn_tab", |_, this, spawn: SpawnTab| async move {
             spawn.spawn(this).await
         });
+        methods.add_async_method(
+            "spawn_virtual_tab",
+            |_, this, spec: VirtualPaneSpec| async move {
+                spec.validate()
+                    .map_err(|e| mlua::Error::external(format!("{:#}", e)))?;
+                let mux = get_mux()?;
+                let spawn = SpawnTab {
+                    domain: SpawnTabDomain::DomainId(virtual_pane_domain(&mux).domain_id()),
+                    cmd_builder: virtual_pane_command(&spec),
+                };
+                let (tab, pane, window) = spawn.spawn(this).await?;
+                VirtualPane::configure(pane.0, &spec)
+                    .map_err(|e| mlua::Error::external(format!("{:#}", e)))?;
+                Ok((tab, pane, window))
+            },
+        );
         methods.add_method("get_title", |_, this, _: ()| {
             let mux = get_mux()?;
             let window = this.resolve(&mux)?;
@@ -424,6 +610,25 @@ impl UserData for MuxPane {
             let args = args.unwrap_or_default();
             args.run(this).await
         });
+        methods.add_async_method(
+            "split_virtual",
+            |_, this, args: SplitVirtualPane| async move { args.run(this).await },
+        );
+        methods.add_async_method(
+            "split_with_layout",
+            |_, this, splits: LuaValue| async move {
+                let splits: Vec<SplitLayout> = luahelper::from_lua_value_dynamic(splits)?;
+                let mux = get_mux()?;
+                let mut created = vec![];
+                match mux.apply_pane_splits(this.0, &splits, &mut created).await {
+                    Ok(()) => Ok(created.into_iter().map(MuxPane).collect::<Vec<_>>()),
+                    Err(err) => Err(mlua::Error::external(format!(
+                        "{:#} (panes {:?} were created before the error)",
+                        err, created
+                    ))),
+                }
+            },
+        );
         methods.add_method("send_paste", |_, this, text: String| {
             let mux = get_mux()?;
             let pane = this.resolve(&mux)?;
@@ -451,6 +656,11 @@ impl UserData for MuxPane {
                 .resolve_pane_id(this.0)
                 .map(|(_domain_id, _window_id, tab_id)| MuxTab(tab_id)))
         });
+        methods.add_method("swap_with", |_, this, other: MuxPane| {
+            let mux = get_mux()?;
+            mux.swap_panes(this.0, other.0)
+                .map_err(|e| mlua::Error::external(format!("{:#}", e)))
+        });
     }
 }
 
@@ -469,6 +679,8 @@ struct MuxPaneInfo {
     pub is_active: bool,
     /// true if this pane is zoomed
     pub is_zoomed: bool,
+    /// true if the tab is stacked and this pane is collapsed to its title
+    pub is_collapsed: bool,
     /// The offset from the top left corner of the containing tab to the top
     /// left corner of this pane, in cells.
     pub left: usize,
@@ -515,6 +727,18 @@ impl UserData for MuxTab {
             let tab = this.resolve(&mux)?;
             Ok(tab.set_title(&title))
         });
+        methods.add_method("get_layout", |lua, this, _: ()| {
+            let mux = get_mux()?;
+            let tab = this.resolve(&mux)?;
+            luahelper::dynamic_to_lua_value(lua, tab.get_layout().to_dynamic())
+        });
+        methods.add_method("set_layout", |_, this, spec: LuaValue| {
+            let spec: SplitLayoutSpec = luahelper::from_lua_value_dynamic(spec)?;
+            let mux = get_mux()?;
+            let tab = this.resolve(&mux)?;
+            tab.set_layout(&spec)
+                .map_err(|err| mlua::Error::external(format!("{:#}", err)))
+        });
         methods.add_method("panes", |_, this, _: ()| {
             let mux = get_mux()?;
             let tab = this.resolve(&mux)?;
@@ -534,6 +758,7 @@ impl UserData for MuxTab {
                     index: pos.index,
                     is_active: pos.is_active,
                     is_zoomed: pos.is_zoomed,
+                    is_collapsed: pos.is_collapsed,
                     left: pos.left,
                     top: pos.top,
                     width: pos.width,
@@ -555,3 +780,39 @@ impl UserData for MuxTab {
         });
     }
 }
+
+impl MuxDomain {
+    fn resolve(&self, mux: &Rc<Mux>) -> mlua::Result<Arc<dyn Domain>> {
+        mux.get_domain(self.0)
+            .ok_or_else(|| mlua::Error::external(format!("domain id {} not found in mux", self.0)))
+    }
+}
+
+impl UserData for MuxDomain {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_meta_method(mlua::MetaMethod::ToString, |_, this, _: ()| {
+            Ok(format!("MuxDomain(domain_id:{}, pid:{})", this.0, unsafe {
+                libc::getpid()
+            }))
+        });
+        methods.add_method("domain_id", |_, this, _: ()| Ok(this.0));
+        methods.add_method("name", |_, this, _: ()| {
+            let mux = get_mux()?;
+            let domain = this.resolve(&mux)?;
+            Ok(domain.domain_name().to_string())
+        });
+        methods.add_method("connection_stats", |_, this, _: ()| {
+            let mux = get_mux()?;
+            let domain = this.resolve(&mux)?;
+            Ok(domain.connection_stats())
+        });
+        methods.add_async_method("read_dir", |_, this, path: String| async move {
+            let mux = get_mux()?;
+            let domain = this.resolve(&mux)?;
+            domain
+                .read_dir(&path)
+                .await
+                .map_err(|e| mlua::Error::external(format!("{:#}", e)))
+        });
+    }
+}
@@ -0,0 +1,450 @@
+//! Virtual panes display lines that are produced by a lua event
+//! handler, rather than the output of a program running in a pty.
+//! The handler is called with the pane and a table describing the
+//! reason for the call: when the pane is first shown, when it is
+//! resized, when it receives input and, optionally, periodically.
+//! Whenever the handler returns a list of lines, they replace the
+//! content of the pane.
+use crate::MuxPane;
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use config::lua::mlua::Value as LuaValue;
+use luahelper::impl_lua_conversion_dynamic;
+use mux::domain::{alloc_domain_id, Domain, DomainId, DomainState};
+use mux::pane::{alloc_pane_id, CloseReason, Pane, PaneId};
+use mux::renderable::*;
+use mux::window::WindowId;
+use mux::{Mux, MuxNotification};
+use portable_pty::CommandBuilder;
+use rangeset::RangeSet;
+use std::cell::{RefCell, RefMut};
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+use termwiz::surface::SequenceNo;
+use url::Url;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+use wezterm_term::color::ColorPalette;
+use wezterm_term::input::MouseButton;
+use wezterm_term::{
+    KeyCode, KeyModifiers, Line, MouseEvent, MouseEventKind, StableRowIndex, TerminalSize,
+};
+
+/// Describes a virtual pane that is to be spawned
+#[derive(Debug, Default, Clone, FromDynamic, ToDynamic)]
+pub struct VirtualPaneSpec {
+    /// The name of the event that produces the content of the pane
+    pub event: String,
+    pub title: Option<String>,
+    /// If set, the event is emitted at this interval, in seconds
+    pub refresh_interval: Option<f64>,
+}
+impl_lua_conversion_dynamic!(VirtualPaneSpec);
+
+impl VirtualPaneSpec {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(interval) = self.refresh_interval {
+            if !interval.is_finite() || interval <= 0.0 {
+                bail!("refresh_interval must be a positive number of seconds");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The second parameter that is passed to the event handler
+#[derive(Debug, Default, Clone, FromDynamic, ToDynamic)]
+struct VirtualPaneEvent {
+    /// One of "render", "resize", "refresh", "key", "mouse" or "paste"
+    kind: String,
+    cols: usize,
+    rows: usize,
+    key: Option<String>,
+    mods: Option<String>,
+    button: Option<String>,
+    x: Option<usize>,
+    y: Option<i64>,
+    text: Option<String>,
+}
+impl_lua_conversion_dynamic!(VirtualPaneEvent);
+
+fn mods_to_string(mods: KeyModifiers) -> String {
+    let mut names = vec![];
+    for (value, label) in [
+        (KeyModifiers::SHIFT, "SHIFT"),
+        (KeyModifiers::ALT, "ALT"),
+        (KeyModifiers::CTRL, "CTRL"),
+        (KeyModifiers::SUPER, "SUPER"),
+    ] {
+        if mods.contains(value) {
+            names.push(label);
+        }
+    }
+    if names.is_empty() {
+        "NONE".to_string()
+    } else {
+        names.join("|")
+    }
+}
+
+fn key_to_string(key: KeyCode) -> String {
+    match key {
+        KeyCode::Char(c) => c.to_string(),
+        key => format!("{:?}", key),
+    }
+}
+
+fn button_to_string(button: MouseButton) -> Option<&'static str> {
+    Some(match button {
+        MouseButton::Left => "Left",
+        MouseButton::Middle => "Middle",
+        MouseButton::Right => "Right",
+        MouseButton::WheelUp(_) => "WheelUp",
+        MouseButton::WheelDown(_) => "WheelDown",
+        MouseButton::WheelLeft(_) => "WheelLeft",
+        MouseButton::WheelRight(_) => "WheelRight",
+        MouseButton::None => return None,
+    })
+}
+
+pub struct VirtualPaneDomain {
+    domain_id: DomainId,
+}
+
+/// Returns the domain that hosts the virtual panes, creating it
+/// when it is first needed
+pub fn virtual_pane_domain(mux: &Rc<Mux>) -> Arc<dyn Domain> {
+    if let Some(domain) = mux
+        .iter_domains()
+        .into_iter()
+        .find(|domain| domain.downcast_ref::<VirtualPaneDomain>().is_some())
+    {
+        return domain;
+    }
+    let domain: Arc<dyn Domain> = Arc::new(VirtualPaneDomain {
+        domain_id: alloc_domain_id(),
+    });
+    mux.add_domain(&domain);
+    domain
+}
+
+#[async_trait(?Send)]
+impl Domain for VirtualPaneDomain {
+    /// The first argument of `command` is the name of the event
+    async fn spawn_pane(
+        &self,
+        size: TerminalSize,
+        command: Option<CommandBuilder>,
+        _command_dir: Option<String>,
+    ) -> anyhow::Result<Rc<dyn Pane>> {
+        let event = command
+            .as_ref()
+            .and_then(|cmd| cmd.get_argv().first())
+            .map(|arg| arg.to_string_lossy().to_string())
+            .ok_or_else(|| anyhow!("a virtual pane requires the name of its event"))?;
+        let pane: Rc<dyn Pane> = Rc::new(VirtualPane::new(self.domain_id, size, event));
+        let mux = Mux::get().unwrap();
+        mux.add_pane(&pane)?;
+
+        // Produce the initial content once the pane has been
+        // placed in its tab and configured by our caller
+        let pane_id = pane.pane_id();
+        promise::spawn::spawn(async move {
+            if let Some(pane) = VirtualPane::get(pane_id) {
+                pane.downcast_ref::<VirtualPane>()
+                    .unwrap()
+                    .emit("render", Default::default());
+            }
+        })
+        .detach();
+
+        Ok(pane)
+    }
+
+    fn spawnable(&self) -> bool {
+        false
+    }
+
+    fn domain_id(&self) -> DomainId {
+        self.domain_id
+    }
+
+    fn domain_name(&self) -> &str {
+        "VirtualPaneDomain"
+    }
+
+    async fn attach(&self, _window_id: Option<WindowId>) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn detach(&self) -> anyhow::Result<()> {
+        bail!("detach not implemented for VirtualPaneDomain");
+    }
+
+    fn state(&self) -> DomainState {
+        DomainState::Attached
+    }
+}
+
+pub struct VirtualPane {
+    pane_id: PaneId,
+    domain_id: DomainId,
+    event: String,
+    title: RefCell<String>,
+    size: RefCell<TerminalSize>,
+    terminal: RefCell<wezterm_term::Terminal>,
+    writer: RefCell<std::io::Sink>,
+    dead: RefCell<bool>,
+}
+
+impl VirtualPane {
+    fn new(domain_id: DomainId, size: TerminalSize, event: String) -> Self {
+        let terminal = wezterm_term::Terminal::new(
+            size,
+            Arc::new(config::TermConfig::new()),
+            "WezTerm",
+            config::wezterm_version(),
+            Box::new(std::io::sink()),
+        );
+        Self {
+            pane_id: alloc_pane_id(),
+            domain_id,
+            title: RefCell::new(event.clone()),
+            event,
+            size: RefCell::new(size),
+            terminal: RefCell::new(terminal),
+            writer: RefCell::new(std::io::sink()),
+            dead: RefCell::new(false),
+        }
+    }
+
+    /// Returns the pane if it is a live virtual pane
+    fn get(pane_id: PaneId) -> Option<Rc<dyn Pane>> {
+        let pane = Mux::get()?.get_pane(pane_id)?;
+        if pane.is_dead() || pane.downcast_ref::<VirtualPane>().is_none() {
+            return None;
+        }
+        Some(pane)
+    }
+
+    /// Applies the title and refresh interval of `spec`, which must
+    /// have been validated, to the virtual pane `pane_id`
+    pub fn configure(pane_id: PaneId, spec: &VirtualPaneSpec) -> anyhow::Result<()> {
+        let pane = Self::get(pane_id).ok_or_else(|| anyhow!("pane {} is not virtual", pane_id))?;
+        let virtual_pane = pane.downcast_ref::<VirtualPane>().unwrap();
+        if let Some(title) = &spec.title {
+            *virtual_pane.title.borrow_mut() = title.clone();
+        }
+        if let Some(interval) = spec.refresh_interval {
+            let interval = Duration::from_secs_f64(interval);
+            promise::spawn::spawn(async move {
+                loop {
+                    smol::Timer::after(interval).await;
+                    match Self::get(pane_id) {
+                        Some(pane) => pane
+                            .downcast_ref::<VirtualPane>()
+                            .unwrap()
+                            .emit("refresh", Default::default()),
+                        None => break,
+                    }
+                }
+            })
+            .detach();
+        }
+        Ok(())
+    }
+
+    /// Emits the event of the pane, and shows the lines that
+    /// the handler returns, if any
+    fn emit(&self, kind: &str, event: VirtualPaneEvent) {
+        let size = *self.size.borrow();
+        let event = VirtualPaneEvent {
+            kind: kind.to_string(),
+            cols: size.cols,
+            rows: size.rows,
+            ..event
+        };
+        let pane_id = self.pane_id;
+        let name = self.event.clone();
+
+        let result = config::run_immediate_with_lua_config(|lua| {
+            let lua = match lua {
+                Some(lua) => lua,
+                None => return Ok(None),
+            };
+            let v =
+                config::lua::emit_sync_callback(&*lua, (name.clone(), (MuxPane(pane_id), event)))?;
+            let lines = match v {
+                LuaValue::Nil => None,
+                LuaValue::Table(lines) => Some(
+                    lines
+                        .sequence_values::<String>()
+                        .collect::<Result<Vec<_>, _>>()?,
+                ),
+                _ => bail!("expected a list of lines or nil"),
+            };
+            Ok(lines)
+        });
+
+        match result {
+            Ok(Some(lines)) => self.set_lines(&lines),
+            Ok(None) => {}
+            Err(err) => log::error!("virtual pane event `{}`: {:#}", self.event, err),
+        }
+    }
+
+    /// Replaces the content of the pane with `lines`.  The lines
+    /// may contain escape sequences, such as those produced by
+    /// `wezterm.format`, but don't wrap; lines that don't fit are
+    /// truncated.
+    fn set_lines(&self, lines: &[String]) {
+        let rows = self.size.borrow().rows;
+        // Disable auto-wrap, hide the cursor and clear the screen
+        let mut output = String::from("\x1b[?7l\x1b[?25l\x1b[H\x1b[2J");
+        for (idx, line) in lines.iter().take(rows).enumerate() {
+            if idx > 0 {
+                output.push_str("\r\n");
+            }
+            output.push_str(line);
+            output.push_str("\x1b[0m");
+        }
+        self.terminal.borrow_mut().advance_bytes(output);
+        if let Some(mux) = Mux::get() {
+            mux.notify(MuxNotification::PaneOutput(self.pane_id));
+        }
+    }
+}
+
+impl Pane for VirtualPane {
+    fn pane_id(&self) -> PaneId {
+        self.pane_id
+    }
+
+    fn get_cursor_position(&self) -> StableCursorPosition {
+        terminal_get_cursor_position(&mut self.terminal.borrow_mut())
+    }
+
+    fn get_current_seqno(&self) -> SequenceNo {
+        self.terminal.borrow().current_seqno()
+    }
+
+    fn get_changed_since(
+        &self,
+        lines: Range<StableRowIndex>,
+        seqno: SequenceNo,
+    ) -> RangeSet<StableRowIndex> {
+        terminal_get_dirty_lines(&mut self.terminal.borrow_mut(), lines, seqno)
+    }
+
+    fn get_lines(&self, lines: Range<StableRowIndex>) -> (StableRowIndex, Vec<Line>) {
+        terminal_get_lines(&mut self.terminal.borrow_mut(), lines)
+    }
+
+    fn get_dimensions(&self) -> RenderableDimensions {
+        terminal_get_dimensions(&mut self.terminal.borrow_mut())
+    }
+
+    fn get_title(&self) -> String {
+        self.title.borrow().clone()
+    }
+
+    fn can_close_without_prompting(&self, _reason: CloseReason) -> bool {
+        true
+    }
+
+    fn send_paste(&self, text: &str) -> anyhow::Result<()> {
+        self.emit(
+            "paste",
+            VirtualPaneEvent {
+                text: Some(text.to_string()),
+                ..Default::default()
+            },
+        );
+        Ok(())
+    }
+
+    fn reader(&self) -> anyhow::Result<Option<Box<dyn std::io::Read + Send>>> {
+        Ok(None)
+    }
+
+    fn writer(&self) -> RefMut<dyn std::io::Write> {
+        self.writer.borrow_mut()
+    }
+
+    fn resize(&self, size: TerminalSize) -> anyhow::Result<()> {
+        if *self.size.borrow() == size {
+            return Ok(());
+        }
+        *self.size.borrow_mut() = size;
+        self.terminal.borrow_mut().resize(size);
+        self.emit("resize", Default::default());
+        Ok(())
+    }
+
+    fn key_down(&self, key: KeyCode, mods: KeyModifiers) -> anyhow::Result<()> {
+        if key.is_modifier() {
+            return Ok(());
+        }
+        self.emit(
+            "key",
+            VirtualPaneEvent {
+                key: Some(key_to_string(key)),
+                mods: Some(mods_to_string(mods)),
+                ..Default::default()
+            },
+        );
+        Ok(())
+    }
+
+    fn key_up(&self, _key: KeyCode, _mods: KeyModifiers) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn mouse_event(&self, event: MouseEvent) -> anyhow::Result<()> {
+        if event.kind != MouseEventKind::Press {
+            return Ok(());
+        }
+        if let Some(button) = button_to_string(event.button) {
+            self.emit(
+                "mouse",
+                VirtualPaneEvent {
+                    button: Some(button.to_string()),
+                    mods: Some(mods_to_string(event.modifiers)),
+                    x: Some(event.x),
+                    y: Some(event.y),
+                    ..Default::default()
+                },
+            );
+        }
+        Ok(())
+    }
+
+    fn kill(&self) {
+        *self.dead.borrow_mut() = true;
+    }
+
+    fn is_dead(&self) -> bool {
+        *self.dead.borrow()
+    }
+
+    fn palette(&self) -> ColorPalette {
+        self.terminal.borrow().palette()
+    }
+
+    fn domain_id(&self) -> DomainId {
+        self.domain_id
+    }
+
+    fn is_mouse_grabbed(&self) -> bool {
+        true
+    }
+
+    fn is_alt_screen_active(&self) -> bool {
+        false
+    }
+
+    fn get_current_working_dir(&self) -> Option<Url> {
+        None
+    }
+}
@@ -1,6 +1,7 @@
 use bstr::BString;
 use config::lua::get_or_create_module;
-use config::lua::mlua::{self, Lua};
+use config::lua::mlua::{self, Lua, RegistryKey};
+use smol::io::{AsyncRead, AsyncReadExt};
 
 pub fn register(lua: &Lua) -> anyhow::Result<()> {
     let wezterm_mod = get_or_create_module(lua, "wezterm")?;
@@ -13,6 +14,10 @@ pub fn register(lua: &Lua) -> anyhow::Result<()> {
         "background_child_process",
         lua.create_async_function(background_child_process)?,
     )?;
+    wezterm_mod.set(
+        "run_child_process_streaming",
+        lua.create_function(run_child_process_streaming)?,
+    )?;
     Ok(())
 }
 
@@ -73,3 +78,150 @@ async fn background_child_process<'lua>(_: &'lua Lua, args: Vec<String>) -> mlua
 
     Ok(())
 }
+
+/// A piece of output from a process started by
+/// `run_child_process_streaming`
+enum StreamEvent {
+    Stdout(Vec<u8>),
+    Stderr(Vec<u8>),
+}
+
+/// The lua functions that receive the output of a process started
+/// by `run_child_process_streaming`.  They are held in the registry
+/// of the lua state that started the process; if the configuration
+/// is reloaded in the meantime, they can no longer be called.
+struct StreamCallbacks {
+    on_stdout: Option<RegistryKey>,
+    on_stderr: Option<RegistryKey>,
+    on_exit: Option<RegistryKey>,
+}
+
+impl StreamCallbacks {
+    fn from_table<'lua>(lua: &'lua Lua, callbacks: mlua::Table<'lua>) -> mlua::Result<Self> {
+        let key = |name: &str| -> mlua::Result<Option<RegistryKey>> {
+            match callbacks.get::<_, Option<mlua::Function>>(name)? {
+                Some(func) => Ok(Some(lua.create_registry_value(func)?)),
+                None => Ok(None),
+            }
+        };
+        Ok(Self {
+            on_stdout: key("on_stdout")?,
+            on_stderr: key("on_stderr")?,
+            on_exit: key("on_exit")?,
+        })
+    }
+}
+
+/// Calls the callback identified by `key` with the most recently
+/// loaded lua configuration
+async fn call_callback<A>(key: &Option<RegistryKey>, args: A) -> anyhow::Result<()>
+where
+    A: for<'lua> mlua::ToLuaMulti<'lua>,
+{
+    let key = match key {
+        Some(key) => key,
+        None => return Ok(()),
+    };
+    config::with_lua_config_on_main_thread(move |lua| async move {
+        if let Some(lua) = lua {
+            let func: mlua::Function = lua.registry_value(key)?;
+            func.call_async::<_, ()>(args).await?;
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// Forwards the output of `reader` to `tx` as it arrives
+async fn read_stream<R: AsyncRead + Unpin>(
+    mut reader: R,
+    tx: smol::channel::Sender<StreamEvent>,
+    wrap: fn(Vec<u8>) -> StreamEvent,
+) {
+    let mut buf = vec![0u8; 8192];
+    loop {
+        match reader.read(&mut buf).await {
+            Ok(0) => break,
+            Ok(n) => {
+                if tx.send(wrap(buf[..n].to_vec())).await.is_err() {
+                    break;
+                }
+            }
+            Err(err) => {
+                log::error!("run_child_process_streaming: reading output: {:#}", err);
+                break;
+            }
+        }
+    }
+}
+
+async fn stream_child_output(
+    mut child: smol::process::Child,
+    callbacks: StreamCallbacks,
+) -> anyhow::Result<()> {
+    let (tx, rx) = smol::channel::unbounded();
+
+    // The output is read on the background executor, and only the
+    // callbacks themselves run on the main thread
+    if let Some(stdout) = child.stdout.take() {
+        smol::spawn(read_stream(stdout, tx.clone(), StreamEvent::Stdout)).detach();
+    }
+    if let Some(stderr) = child.stderr.take() {
+        smol::spawn(read_stream(stderr, tx.clone(), StreamEvent::Stderr)).detach();
+    }
+    drop(tx);
+
+    while let Ok(event) = rx.recv().await {
+        let result = match event {
+            StreamEvent::Stdout(data) => call_callback(&callbacks.on_stdout, BString::from(data)),
+            StreamEvent::Stderr(data) => call_callback(&callbacks.on_stderr, BString::from(data)),
+        }
+        .await;
+        if let Err(err) = result {
+            log::error!("run_child_process_streaming: output callback: {:#}", err);
+        }
+    }
+
+    let status = child.status().await?;
+    call_callback(&callbacks.on_exit, (status.success(), status.code())).await
+}
+
+fn run_child_process_streaming<'lua>(
+    lua: &'lua Lua,
+    (args, callbacks): (Vec<String>, mlua::Table<'lua>),
+) -> mlua::Result<()> {
+    if args.is_empty() {
+        return Err(mlua::Error::external(
+            "run_child_process_streaming: the argument list must not be empty",
+        ));
+    }
+    let callbacks = StreamCallbacks::from_table(lua, callbacks)?;
+
+    let mut cmd = smol::process::Command::new(&args[0]);
+
+    if args.len() > 1 {
+        cmd.args(&args[1..]);
+    }
+
+    #[cfg(windows)]
+    {
+        use smol::process::windows::CommandExt;
+        cmd.creation_flags(winapi::um::winbase::CREATE_NO_WINDOW);
+    }
+
+    let child = cmd
+        .stdin(smol::process::Stdio::null())
+        .stdout(smol::process::Stdio::piped())
+        .stderr(smol::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| mlua::Error::external(e))?;
+
+    promise::spawn::spawn(async move {
+        if let Err(err) = stream_child_output(child, callbacks).await {
+            log::error!("run_child_process_streaming: {:#}", err);
+        }
+    })
+    .detach();
+
+    Ok(())
+}
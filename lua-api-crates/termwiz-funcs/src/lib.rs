@@ -2,12 +2,16 @@ use config::lua::get_or_create_module;
 use config::lua::mlua::{self, Lua, ToLua};
 use luahelper::impl_lua_conversion_dynamic;
 use termwiz::caps::{Capabilities, ColorLevel, ProbeHints};
-use termwiz::cell::{grapheme_column_width, unicode_column_width, AttributeChange, CellAttributes};
+use termwiz::cell::{
+    grapheme_column_width, set_width_overrides, unicode_column_width, width_overrides,
+    AttributeChange, CellAttributes, WidthOverride,
+};
 use termwiz::color::{AnsiColor, ColorAttribute, ColorSpec, RgbColor};
 use termwiz::input::Modifiers;
 use termwiz::render::terminfo::TerminfoRenderer;
 use termwiz::surface::change::Change;
 use unicode_segmentation::UnicodeSegmentation;
+use wezterm_bidi::{BidiContext, Direction, ParagraphDirectionHint};
 use wezterm_dynamic::{FromDynamic, ToDynamic};
 
 pub fn register(lua: &Lua) -> anyhow::Result<()> {
@@ -18,6 +22,20 @@ pub fn register(lua: &Lua) -> anyhow::Result<()> {
         "column_width",
         lua.create_function(|_, s: String| Ok(unicode_column_width(&s, None)))?,
     )?;
+    wezterm_mod.set(
+        "get_unicode_width_overrides",
+        lua.create_function(|lua, _: ()| {
+            luahelper::dynamic_to_lua_value(lua, width_overrides().to_dynamic())
+        })?,
+    )?;
+    wezterm_mod.set(
+        "set_unicode_width_overrides",
+        lua.create_function(|_, overrides: mlua::Value| {
+            let overrides: Vec<WidthOverride> = luahelper::from_lua_value_dynamic(overrides)?;
+            set_width_overrides(overrides);
+            Ok(())
+        })?,
+    )?;
 
     wezterm_mod.set(
         "pad_right",
@@ -40,6 +58,24 @@ pub fn register(lua: &Lua) -> anyhow::Result<()> {
         "truncate_left",
         lua.create_function(|_, (s, max_width): (String, usize)| Ok(truncate_left(&s, max_width)))?,
     )?;
+
+    wezterm_mod.set(
+        "truncate_visual",
+        lua.create_function(
+            |_, (s, max_width, options): (String, usize, Option<VisualOptions>)| {
+                Ok(truncate_visual(&s, max_width, &options.unwrap_or_default()))
+            },
+        )?,
+    )?;
+
+    wezterm_mod.set(
+        "pad_visual",
+        lua.create_function(
+            |_, (s, width, options): (String, usize, Option<VisualOptions>)| {
+                Ok(pad_visual(s, width, &options.unwrap_or_default()))
+            },
+        )?,
+    )?;
     wezterm_mod.set("permute_any_mods", lua.create_function(permute_any_mods)?)?;
     wezterm_mod.set(
         "permute_any_or_no_mods",
@@ -97,24 +133,85 @@ impl Into<ColorSpec> for FormatColor {
 pub enum FormatItem {
     Foreground(FormatColor),
     Background(FormatColor),
+    UnderlineColor(FormatColor),
     Attribute(AttributeChange),
     ResetAttributes,
     Text(String),
 }
 impl_lua_conversion_dynamic!(FormatItem);
 
-impl Into<Change> for FormatItem {
-    fn into(self) -> Change {
-        match self {
-            Self::Attribute(change) => change.into(),
-            Self::Text(t) => t.into(),
-            Self::Foreground(c) => AttributeChange::Foreground(c.to_attr()).into(),
-            Self::Background(c) => AttributeChange::Background(c.to_attr()).into(),
-            Self::ResetAttributes => Change::AllAttributes(CellAttributes::default()),
+/// Applies `change` to `attr`
+fn apply_attribute_change(attr: &mut CellAttributes, change: &AttributeChange) {
+    match change {
+        AttributeChange::Intensity(value) => {
+            attr.set_intensity(*value);
+        }
+        AttributeChange::Underline(value) => {
+            attr.set_underline(*value);
+        }
+        AttributeChange::Italic(value) => {
+            attr.set_italic(*value);
+        }
+        AttributeChange::Blink(value) => {
+            attr.set_blink(*value);
+        }
+        AttributeChange::Reverse(value) => {
+            attr.set_reverse(*value);
+        }
+        AttributeChange::StrikeThrough(value) => {
+            attr.set_strikethrough(*value);
+        }
+        AttributeChange::Invisible(value) => {
+            attr.set_invisible(*value);
+        }
+        AttributeChange::Foreground(value) => {
+            attr.set_foreground(*value);
+        }
+        AttributeChange::Background(value) => {
+            attr.set_background(*value);
+        }
+        AttributeChange::Hyperlink(value) => {
+            attr.set_hyperlink(value.clone());
         }
     }
 }
 
+/// Converts `items` into changes.
+/// There is no `AttributeChange` for the underline color, so the
+/// attributes are tracked in order to express it as a change to
+/// all of the attributes.
+fn items_to_changes(items: Vec<FormatItem>) -> Vec<Change> {
+    let mut attr = CellAttributes::default();
+    items
+        .into_iter()
+        .map(|item| match item {
+            FormatItem::Attribute(change) => {
+                apply_attribute_change(&mut attr, &change);
+                change.into()
+            }
+            FormatItem::Text(t) => t.into(),
+            FormatItem::Foreground(c) => {
+                let change = AttributeChange::Foreground(c.to_attr());
+                apply_attribute_change(&mut attr, &change);
+                change.into()
+            }
+            FormatItem::Background(c) => {
+                let change = AttributeChange::Background(c.to_attr());
+                apply_attribute_change(&mut attr, &change);
+                change.into()
+            }
+            FormatItem::UnderlineColor(c) => {
+                attr.set_underline_color(c.to_attr());
+                Change::AllAttributes(attr.clone())
+            }
+            FormatItem::ResetAttributes => {
+                attr = CellAttributes::default();
+                Change::AllAttributes(attr.clone())
+            }
+        })
+        .collect()
+}
+
 struct FormatTarget {
     target: Vec<u8>,
 }
@@ -135,7 +232,7 @@ impl termwiz::render::RenderTty for FormatTarget {
 }
 
 pub fn format_as_escapes(items: Vec<FormatItem>) -> anyhow::Result<String> {
-    let mut changes: Vec<Change> = items.into_iter().map(Into::into).collect();
+    let mut changes = items_to_changes(items);
     changes.push(Change::AllAttributes(CellAttributes::default()).into());
     let mut renderer = new_wezterm_terminfo_renderer();
     let mut target = FormatTarget { target: vec![] };
@@ -197,6 +294,146 @@ pub fn truncate_right(s: &str, max_width: usize) -> String {
     result
 }
 
+/// An edge of a paragraph, in its reading direction
+#[derive(Debug, FromDynamic, ToDynamic, Clone, Copy, PartialEq, Eq)]
+pub enum VisualEdge {
+    Start,
+    End,
+}
+
+impl Default for VisualEdge {
+    fn default() -> Self {
+        Self::End
+    }
+}
+
+#[derive(Debug, Default, FromDynamic, ToDynamic, Clone, PartialEq)]
+pub struct VisualOptions {
+    /// The edge that is truncated or padded
+    #[dynamic(default)]
+    pub edge: VisualEdge,
+    /// Text to place at the truncated edge
+    #[dynamic(default)]
+    pub ellipsis: String,
+    /// The paragraph direction used to reorder the text for display.
+    /// If omitted, the `bidi_enabled` and `bidi_direction` configuration
+    /// is used, which is how the tab bar and status area are rendered.
+    pub direction: Option<ParagraphDirectionHint>,
+}
+impl_lua_conversion_dynamic!(VisualOptions);
+
+impl VisualOptions {
+    fn direction(&self) -> Option<ParagraphDirectionHint> {
+        if self.direction.is_some() {
+            return self.direction;
+        }
+        let config = config::configuration();
+        if config.bidi_enabled {
+            Some(config.bidi_direction)
+        } else {
+            None
+        }
+    }
+}
+
+/// Truncates `s` so that it occupies no more than `max_width` cells
+/// when displayed.  Whole graphemes are removed from the edge of the
+/// displayed text that is selected by `options.edge`; with bidi
+/// enabled, that is the edge after the text has been reordered, so
+/// that the portion of an RTL run that remains is the portion that
+/// was visible next to the rest of the text.  The remaining graphemes
+/// are returned in their original logical order.
+pub fn truncate_visual(s: &str, max_width: usize, options: &VisualOptions) -> String {
+    let graphemes: Vec<&str> = s.graphemes(true).collect();
+    let widths: Vec<usize> = graphemes
+        .iter()
+        .map(|g| grapheme_column_width(g, None))
+        .collect();
+    if widths.iter().sum::<usize>() <= max_width {
+        return s.to_string();
+    }
+
+    let mut ellipsis = options.ellipsis.as_str();
+    let mut budget = match max_width.checked_sub(unicode_column_width(ellipsis, None)) {
+        Some(budget) => budget,
+        None => {
+            ellipsis = "";
+            max_width
+        }
+    };
+
+    // The graphemes in the order that they are displayed, left to right
+    let mut visual = vec![];
+    let mut rtl = false;
+    match options.direction() {
+        Some(hint) => {
+            let mut paragraph = vec![];
+            let mut grapheme_idx = vec![];
+            for (idx, g) in graphemes.iter().enumerate() {
+                for c in g.chars() {
+                    paragraph.push(c);
+                    grapheme_idx.push(idx);
+                }
+            }
+            let mut context = BidiContext::new();
+            context.set_reorder_non_spacing_marks(true);
+            context.resolve_paragraph(&paragraph, hint);
+            rtl = context.base_level().direction() == Direction::RightToLeft;
+            let (_levels, reordered) = context.reorder_line(0..paragraph.len());
+            for cp_idx in reordered {
+                let idx = grapheme_idx[cp_idx];
+                if !visual.contains(&idx) {
+                    visual.push(idx);
+                }
+            }
+        }
+        None => visual.extend(0..graphemes.len()),
+    }
+
+    // Keep graphemes starting from the edge opposite to the one
+    // being truncated.  Graphemes that were removed by the bidi
+    // algorithm, such as explicit direction controls, have no
+    // width and are always kept.
+    let keep_from_left = rtl == (options.edge == VisualEdge::Start);
+    if !keep_from_left {
+        visual.reverse();
+    }
+    let mut keep = vec![true; graphemes.len()];
+    let mut truncated = false;
+    for idx in visual {
+        if truncated || widths[idx] > budget {
+            truncated = true;
+            keep[idx] = false;
+        } else {
+            budget -= widths[idx];
+        }
+    }
+
+    let text: String = graphemes
+        .iter()
+        .zip(keep)
+        .filter_map(|(g, keep)| keep.then(|| *g))
+        .collect();
+    match options.edge {
+        VisualEdge::End => format!("{}{}", text, ellipsis),
+        VisualEdge::Start => format!("{}{}", ellipsis, text),
+    }
+}
+
+/// Pads `s` with spaces at the edge selected by `options.edge` so that
+/// it occupies at least `width` cells when displayed.  The padding is
+/// placed at the logical start or end of the text, which the bidi
+/// algorithm displays at the corresponding edge of the paragraph.
+pub fn pad_visual(mut result: String, width: usize, options: &VisualOptions) -> String {
+    let len = unicode_column_width(&result, None);
+    let padding = " ".repeat(width.saturating_sub(len));
+    match options.edge {
+        VisualEdge::End => result.push_str(&padding),
+        VisualEdge::Start => result.insert_str(0, &padding),
+    }
+    result
+}
+
 fn permute_mods<'lua>(
     lua: &'lua Lua,
     item: mlua::Table,
@@ -252,7 +489,8 @@ lazy_static::lazy_static! {
                 .colorterm(None)
                 .colorterm_bce(None)
                 .term_program(Some("WezTerm".into()))
-                .term_program_version(Some(config::wezterm_version().into())),
+                .term_program_version(Some(config::wezterm_version().into()))
+                .styled_underlines(Some(true)),
         )
         .expect("cannot fail to make internal Capabilities")
     };
@@ -2,6 +2,9 @@ use chrono::prelude::*;
 use config::lua::mlua::{self, Lua, MetaMethod, UserData, UserDataMethods};
 use config::lua::{get_or_create_module, get_or_create_sub_module};
 
+mod timer;
+pub use timer::*;
+
 pub fn register(lua: &Lua) -> anyhow::Result<()> {
     let time_mod = get_or_create_sub_module(lua, "time")?;
 
@@ -30,6 +33,14 @@ pub fn register(lua: &Lua) -> anyhow::Result<()> {
         })?,
     )?;
 
+    time_mod.set(
+        "call_every",
+        lua.create_function(|lua, (interval, func): (f64, mlua::Function)| {
+            let interval = interval_from_seconds(interval)?;
+            schedule_every(lua, interval, func, || Some(()))
+        })?,
+    )?;
+
     // For backwards compatibility
     let wezterm_mod = get_or_create_module(lua, "wezterm")?;
     wezterm_mod.set("strftime", lua.create_function(strftime)?)?;
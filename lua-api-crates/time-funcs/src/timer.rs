@@ -0,0 +1,125 @@
+//! Timers that repeatedly call a lua function, as created by
+//! `wezterm.time.call_every` and `window:call_every`.
+//! The function is held in the registry of the lua state that created
+//! the timer; once the configuration is reloaded that state is replaced
+//! and the timer stops, so a timer set up by the configuration file is
+//! recreated rather than duplicated by each reload.
+use config::lua::mlua::{self, Lua, RegistryKey, ToLuaMulti, UserData, UserDataMethods};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The handle to a timer that is returned to lua.
+/// Dropping the handle does not cancel the timer.
+#[derive(Clone, Debug)]
+pub struct Timer {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Timer {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl UserData for Timer {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("cancel", |_, this, _: ()| {
+            this.cancel();
+            Ok(())
+        });
+        methods.add_method("is_cancelled", |_, this, _: ()| Ok(this.is_cancelled()));
+    }
+}
+
+/// Converts an interval expressed in seconds, as passed from lua
+pub fn interval_from_seconds(seconds: f64) -> mlua::Result<Duration> {
+    if !seconds.is_finite() || seconds <= 0. {
+        return Err(mlua::Error::external(format!(
+            "timer interval must be a positive number of seconds, but got {seconds}"
+        )));
+    }
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+/// Calls `func` every `interval` until the timer is cancelled, the
+/// configuration is reloaded or `make_args` returns None.
+/// `make_args` is called on the main thread before each call to
+/// produce the arguments that are passed to `func`.
+/// An error raised by `func` is logged and doesn't stop the timer.
+pub fn schedule_every<F, A>(
+    lua: &Lua,
+    interval: Duration,
+    func: mlua::Function,
+    make_args: F,
+) -> mlua::Result<Timer>
+where
+    F: Fn() -> Option<A> + Clone + Send + 'static,
+    A: for<'lua> ToLuaMulti<'lua> + 'static,
+{
+    let timer = Timer {
+        cancelled: Arc::new(AtomicBool::new(false)),
+    };
+    let func = Arc::new(lua.create_registry_value(func)?);
+    let handle = timer.clone();
+
+    // The timer may be created while the configuration is being loaded,
+    // on a background thread or before the main thread is ready to
+    // run futures, so it counts down on a background thread instead.
+    smol::spawn(async move {
+        loop {
+            smol::Timer::after(interval).await;
+            if handle.is_cancelled() {
+                break;
+            }
+            if !promise::spawn::can_spawn_into_main_thread() {
+                continue;
+            }
+            let func = Arc::clone(&func);
+            let make_args = make_args.clone();
+            let tick = promise::spawn::spawn_into_main_thread(async move {
+                promise::spawn::spawn(call_timer(func, make_args)).await
+            });
+            match tick.await {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(err) => log::error!("timer callback: {:#}", err),
+            }
+        }
+        handle.cancel();
+    })
+    .detach();
+
+    Ok(timer)
+}
+
+/// Calls the function of the timer once.
+/// Returns false if the timer should stop.
+async fn call_timer<F, A>(func: Arc<RegistryKey>, make_args: F) -> anyhow::Result<bool>
+where
+    F: Fn() -> Option<A>,
+    A: for<'lua> ToLuaMulti<'lua>,
+{
+    config::with_lua_config_on_main_thread(move |lua| async move {
+        let lua = match lua {
+            Some(lua) => lua,
+            None => return Ok(false),
+        };
+        if !lua.owns_registry_value(&func) {
+            // The configuration was reloaded
+            return Ok(false);
+        }
+        let args = match make_args() {
+            Some(args) => args,
+            None => return Ok(false),
+        };
+        let func: mlua::Function = lua.registry_value(&func)?;
+        func.call_async::<_, ()>(args).await?;
+        Ok(true)
+    })
+    .await
+}
@@ -35,6 +35,70 @@ pub struct Screens {
 }
 impl_lua_conversion_dynamic!(Screens);
 
+#[derive(Debug, Clone, Default, FromDynamic, ToDynamic)]
+pub struct FileFilter {
+    pub name: String,
+    #[dynamic(default)]
+    pub patterns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, FromDynamic, ToDynamic)]
+pub struct PickFileArgs {
+    #[dynamic(default = "default_pick_file_title")]
+    pub title: String,
+    pub accept_label: Option<String>,
+    #[dynamic(default)]
+    pub multiple: bool,
+    #[dynamic(default)]
+    pub directory: bool,
+    #[dynamic(default)]
+    pub save: bool,
+    pub current_name: Option<String>,
+    pub current_folder: Option<String>,
+    #[dynamic(default)]
+    pub filters: Vec<FileFilter>,
+}
+impl_lua_conversion_dynamic!(PickFileArgs);
+
+fn default_pick_file_title() -> String {
+    "Choose a file".to_string()
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+async fn pick_file(args: PickFileArgs) -> anyhow::Result<Option<Vec<String>>> {
+    use window::os::xdg_desktop_portal;
+
+    let options = xdg_desktop_portal::FilePickerOptions {
+        title: args.title,
+        accept_label: args.accept_label,
+        multiple: args.multiple,
+        directory: args.directory,
+        save: args.save,
+        current_name: args.current_name,
+        current_folder: args.current_folder.map(Into::into),
+        filters: args
+            .filters
+            .into_iter()
+            .map(|f| xdg_desktop_portal::FileFilter {
+                name: f.name,
+                patterns: f.patterns,
+            })
+            .collect(),
+    };
+
+    Ok(xdg_desktop_portal::pick_file(options).await?.map(|paths| {
+        paths
+            .into_iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect()
+    }))
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+async fn pick_file(_args: PickFileArgs) -> anyhow::Result<Option<Vec<String>>> {
+    anyhow::bail!("wezterm.gui.pick_file is only supported on X11 and Wayland");
+}
+
 impl From<window::screen::ScreenInfo> for ScreenInfo {
     fn from(info: window::screen::ScreenInfo) -> Self {
         Self {
@@ -85,6 +149,25 @@ pub fn register(lua: &Lua) -> anyhow::Result<()> {
         })?,
     )?;
 
+    window_mod.set(
+        "pick_file",
+        lua.create_async_function(|lua, args: Option<PickFileArgs>| async move {
+            let args = args.unwrap_or_default();
+            let multiple = args.multiple;
+            let paths = pick_file(args)
+                .await
+                .map_err(|err| mlua::Error::external(format!("{err:#}")))?;
+            match paths {
+                None => Ok(mlua::Value::Nil),
+                Some(paths) if multiple => Ok(mlua::Value::Table(lua.create_sequence_from(paths)?)),
+                Some(paths) => match paths.into_iter().next() {
+                    Some(path) => Ok(mlua::Value::String(lua.create_string(&path)?)),
+                    None => Ok(mlua::Value::Nil),
+                },
+            }
+        })?,
+    )?;
+
     window_mod.set(
         "get_appearance",
         lua.create_function(|_, _: ()| {
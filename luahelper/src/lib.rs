@@ -295,7 +295,11 @@ impl<'lua> std::fmt::Debug for ValuePrinterHelper<'lua> {
                 self.visited
                     .borrow_mut()
                     .insert(self.value.to_pointer() as usize);
-                if let Ok(true) = t.contains_key(1) {
+                // Tables that also have keys beyond their sequence
+                // are printed as maps so that those keys are shown
+                let is_list = matches!(t.contains_key(1), Ok(true))
+                    && t.clone().pairs::<LuaValue, LuaValue>().count() == t.raw_len() as usize;
+                if is_list {
                     // Treat as list
                     let mut list = fmt.debug_list();
                     for (idx, value) in t.clone().sequence_values().enumerate() {
@@ -15,11 +15,14 @@ use async_trait::async_trait;
 use config::keyassignment::{SpawnCommand, SpawnTabDomain};
 use config::{configuration, ExecDomain, ValueOrFunc, WslDomain};
 use downcast_rs::{impl_downcast, Downcast};
+use luahelper::impl_lua_conversion_dynamic;
 use portable_pty::{native_pty_system, CommandBuilder, PtySystem};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::ffi::OsString;
 use std::path::PathBuf;
 use std::rc::Rc;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
 use wezterm_term::TerminalSize;
 
 static DOMAIN_ID: ::std::sync::atomic::AtomicUsize = ::std::sync::atomic::AtomicUsize::new(0);
@@ -44,6 +47,94 @@ pub enum SplitSource {
     MovePane(PaneId),
 }
 
+/// A portion of a file, as returned by `Domain::read_file_chunk`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileChunk {
+    pub data: Vec<u8>,
+    /// The overall size of the file, if known
+    pub total_size: Option<u64>,
+}
+
+/// An entry of a directory, as returned by `Domain::read_dir`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromDynamic, ToDynamic)]
+pub struct DirEntry {
+    pub name: String,
+    /// true if the entry is a directory, or a symlink to one
+    pub is_dir: bool,
+    /// The size of the file, if known
+    pub size: Option<u64>,
+}
+impl_lua_conversion_dynamic!(DirEntry);
+
+/// Counts the PDUs exchanged with the server of a client domain, as
+/// returned by `Domain::connection_stats`.  The byte counts are the
+/// sizes of the serialized PDUs, excluding the frame headers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct ConnectionStats {
+    pub pdus_sent: u64,
+    pub compressed_pdus_sent: u64,
+    /// The number of bytes written to the connection
+    pub bytes_sent: u64,
+    /// The number of bytes that would have been written
+    /// without compression
+    pub uncompressed_bytes_sent: u64,
+    pub pdus_received: u64,
+    pub compressed_pdus_received: u64,
+    pub bytes_received: u64,
+    pub uncompressed_bytes_received: u64,
+}
+impl_lua_conversion_dynamic!(ConnectionStats);
+
+/// Splits a partially typed path into its directory, including the
+/// trailing slash, and the start of the name of an entry within it
+pub fn split_partial_path(partial: &str) -> (&str, &str) {
+    match partial.rfind('/') {
+        Some(idx) => (&partial[..=idx], &partial[idx + 1..]),
+        None => ("", partial),
+    }
+}
+
+/// Returns the paths that complete `partial`, given the `entries` of
+/// the directory that it is in.  Directories are suffixed with a slash
+/// so that completion can continue into them.  Hidden entries are only
+/// included once the name being completed starts with a dot.
+pub fn complete_path(partial: &str, entries: &[DirEntry], dirs_only: bool) -> Vec<String> {
+    let (dir, prefix) = split_partial_path(partial);
+    entries
+        .iter()
+        .filter(|entry| {
+            entry.name.starts_with(prefix)
+                && (!dirs_only || entry.is_dir)
+                && (prefix.starts_with('.') || !entry.name.starts_with('.'))
+        })
+        .map(|entry| {
+            format!(
+                "{}{}{}",
+                dir,
+                entry.name,
+                if entry.is_dir { "/" } else { "" }
+            )
+        })
+        .collect()
+}
+
+/// Resolves a path that is relative to the current working directory
+/// of the specified pane into a path on the local filesystem
+fn resolve_local_path(pane_id: PaneId, path: &str) -> PathBuf {
+    let path = PathBuf::from(path);
+    if path.is_absolute() {
+        return path;
+    }
+    let cwd = Mux::get()
+        .and_then(|mux| mux.get_pane(pane_id))
+        .and_then(|pane| pane.get_current_working_dir())
+        .and_then(|url| url.to_file_path().ok());
+    match cwd {
+        Some(cwd) => cwd.join(path),
+        None => path,
+    }
+}
+
 #[async_trait(?Send)]
 pub trait Domain: Downcast {
     /// Spawn a new command within this domain
@@ -163,10 +254,83 @@ pub trait Domain: Downcast {
     /// Indicates the state of the domain
     fn state(&self) -> DomainState;
 
+    /// Reads up to `max_bytes` from the file at `path`, starting at
+    /// `offset`, from the filesystem that hosts the specified pane.
+    /// Relative paths are resolved against the working directory of
+    /// the pane.  An empty chunk indicates the end of the file.
+    async fn read_file_chunk(
+        &self,
+        pane_id: PaneId,
+        path: &str,
+        offset: u64,
+        max_bytes: usize,
+    ) -> anyhow::Result<FileChunk> {
+        let path = resolve_local_path(pane_id, path);
+        smol::unblock(move || -> anyhow::Result<FileChunk> {
+            use std::io::{Read, Seek, SeekFrom};
+            let mut file = std::fs::File::open(&path)
+                .with_context(|| format!("opening {} for read", path.display()))?;
+            let total_size = file.metadata().ok().map(|m| m.len());
+            file.seek(SeekFrom::Start(offset))?;
+            let mut data = vec![];
+            file.take(max_bytes as u64).read_to_end(&mut data)?;
+            Ok(FileChunk { data, total_size })
+        })
+        .await
+    }
+
+    /// Writes `data` to the file at `path`, starting at `offset`, on the
+    /// filesystem that hosts the specified pane.
+    /// Writing at offset 0 creates or truncates the file.
+    /// `total_size` is the overall size of the file being transferred,
+    /// if known, which allows the domain to tell when the transfer
+    /// is complete.
+    /// Relative paths are resolved against the working directory of
+    /// the pane.
+    async fn write_file_chunk(
+        &self,
+        pane_id: PaneId,
+        path: &str,
+        offset: u64,
+        data: Vec<u8>,
+        _total_size: Option<u64>,
+    ) -> anyhow::Result<()> {
+        let path = resolve_local_path(pane_id, path);
+        smol::unblock(move || -> anyhow::Result<()> {
+            use std::io::{Seek, SeekFrom, Write};
+            let mut file = if offset == 0 {
+                std::fs::File::create(&path)
+            } else {
+                std::fs::OpenOptions::new().write(true).open(&path)
+            }
+            .with_context(|| format!("opening {} for write", path.display()))?;
+            file.seek(SeekFrom::Start(offset))?;
+            file.write_all(&data)?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Lists the entries of the directory at `path` on the filesystem
+    /// of the domain, sorted by name.  Relative paths are resolved
+    /// against the home directory of the user.
+    async fn read_dir(&self, _path: &str) -> anyhow::Result<Vec<DirEntry>> {
+        anyhow::bail!(
+            "listing directories is not supported by the {} domain",
+            self.domain_name()
+        );
+    }
+
     /// Called to advise the domain that a local window is closing.
     /// This allows the domain the opportunity to eg: detach/hide
     /// its tabs/panes rather than actually killing them off
     fn local_window_is_closing(&self, _window_id: WindowId) {}
+
+    /// Returns the traffic statistics of the connection to the
+    /// server of the domain, if it has one
+    fn connection_stats(&self) -> Option<ConnectionStats> {
+        None
+    }
 }
 impl_downcast!(Domain);
 
@@ -501,4 +665,71 @@ impl Domain for LocalDomain {
     fn state(&self) -> DomainState {
         DomainState::Attached
     }
+
+    async fn read_dir(&self, path: &str) -> anyhow::Result<Vec<DirEntry>> {
+        let path = config::HOME_DIR.join(path);
+        smol::unblock(move || -> anyhow::Result<Vec<DirEntry>> {
+            let mut entries = vec![];
+            for entry in std::fs::read_dir(&path)
+                .with_context(|| format!("reading directory {}", path.display()))?
+            {
+                let entry = entry?;
+                // Follow symlinks, so that links to directories
+                // can be completed into
+                let metadata = std::fs::metadata(entry.path()).ok();
+                entries.push(DirEntry {
+                    name: entry.file_name().to_string_lossy().into_owned(),
+                    is_dir: metadata.as_ref().map_or(false, |m| m.is_dir()),
+                    size: metadata.map(|m| m.len()),
+                });
+            }
+            entries.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok(entries)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(name: &str, is_dir: bool) -> DirEntry {
+        DirEntry {
+            name: name.to_string(),
+            is_dir,
+            size: None,
+        }
+    }
+
+    #[test]
+    fn split() {
+        assert_eq!(split_partial_path("/usr/lo"), ("/usr/", "lo"));
+        assert_eq!(split_partial_path("/usr/"), ("/usr/", ""));
+        assert_eq!(split_partial_path("src"), ("", "src"));
+    }
+
+    #[test]
+    fn completion() {
+        let entries = [
+            entry(".config", true),
+            entry("lib", true),
+            entry("lib64", true),
+            entry("libc.so", false),
+            entry("local", true),
+        ];
+        assert_eq!(
+            complete_path("/usr/li", &entries, false),
+            vec!["/usr/lib/", "/usr/lib64/", "/usr/libc.so"]
+        );
+        assert_eq!(
+            complete_path("/usr/li", &entries, true),
+            vec!["/usr/lib/", "/usr/lib64/"]
+        );
+        assert_eq!(
+            complete_path("", &entries, true),
+            vec!["lib/", "lib64/", "local/"]
+        );
+        assert_eq!(complete_path(".c", &entries, true), vec![".config/"]);
+    }
 }
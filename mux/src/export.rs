@@ -0,0 +1,338 @@
+//! Converts the lines of a pane into a form that can be saved
+//! outside of wezterm: plain text, text with escape sequences,
+//! or a standalone HTML document.
+use crate::pane::Pane;
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use termwiz::cell::{CellAttributes, Intensity, Underline};
+use termwiz::color::ColorAttribute;
+use termwiz::surface::{Change, Line};
+use termwiz_funcs::new_wezterm_terminfo_renderer;
+use wezterm_term::color::ColorPalette;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ExportFormat {
+    /// Plain text, with trailing whitespace trimmed from each line
+    Text,
+    /// Text with escape sequences that reproduce the colors
+    /// and attributes of the cells
+    Ansi,
+    /// A standalone HTML document
+    Html,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "text" => Ok(Self::Text),
+            "ansi" => Ok(Self::Ansi),
+            "html" => Ok(Self::Html),
+            _ => Err(anyhow!(
+                "unknown export format `{}`; expected one of \"text\", \"ansi\" or \"html\"",
+                s
+            )),
+        }
+    }
+}
+
+/// Exports the lines of the pane in the specified format.
+/// `start_line` and `end_line` are inclusive, and are relative
+/// to the top of the screen: `0` is the first line of the screen
+/// and negative numbers refer to lines in the scrollback.
+/// By default, the whole scrollback and the screen are exported.
+pub fn export_scrollback(
+    pane: &dyn Pane,
+    format: ExportFormat,
+    start_line: Option<isize>,
+    end_line: Option<isize>,
+) -> anyhow::Result<String> {
+    let dims = pane.get_dimensions();
+    let bottom_row = dims.physical_top + dims.viewport_rows as isize;
+    let top_row = match start_line {
+        Some(start) => (dims.physical_top + start).max(dims.scrollback_top),
+        None => dims.scrollback_top,
+    };
+    let end_row = match end_line {
+        Some(end) => (dims.physical_top + end + 1).min(bottom_row),
+        None => bottom_row,
+    };
+    let (_first_row, lines) = pane.get_lines(top_row..end_row.max(top_row));
+
+    match format {
+        ExportFormat::Text => Ok(lines_to_text(&lines)),
+        ExportFormat::Ansi => lines_to_escapes(lines),
+        ExportFormat::Html => Ok(lines_to_html(&lines, &pane.palette(), &pane.get_title())),
+    }
+}
+
+/// Renders the lines as plain text, with the trailing whitespace
+/// of each line removed; blank lines are preserved
+fn lines_to_text(lines: &[Line]) -> String {
+    let mut text = String::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if idx > 0 {
+            text.push('\n');
+        }
+        let start = text.len();
+        for (_, cell) in line.visible_cells() {
+            text.push_str(cell.str());
+        }
+        let trimmed = start + text[start..].trim_end().len();
+        text.truncate(trimmed);
+    }
+    text
+}
+
+/// Renders the lines as text with the escape sequences that
+/// reproduce their colors and attributes
+pub fn lines_to_escapes(lines: Vec<Line>) -> anyhow::Result<String> {
+    let mut changes = vec![];
+    let mut attr = CellAttributes::blank();
+    for line in lines {
+        changes.append(&mut line.changes(&attr));
+        changes.push(Change::Text("\r\n".to_string()));
+        if let Some(a) = line.cells().last().map(|cell| cell.attrs()) {
+            attr = a.clone();
+        }
+    }
+    changes.push(Change::AllAttributes(CellAttributes::blank()));
+    let mut renderer = new_wezterm_terminfo_renderer();
+
+    struct Target {
+        target: Vec<u8>,
+    }
+
+    impl std::io::Write for Target {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            std::io::Write::write(&mut self.target, buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl termwiz::render::RenderTty for Target {
+        fn get_size_in_cells(&mut self) -> termwiz::Result<(usize, usize)> {
+            Ok((80, 24))
+        }
+    }
+
+    let mut target = Target { target: vec![] };
+    renderer.render_to(&changes, &mut target)?;
+    Ok(String::from_utf8(target.target)?)
+}
+
+fn escape_html(s: &str, out: &mut String) {
+    for c in s.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Returns the CSS for `color`, or None for the default color.
+/// The 16 ANSI colors refer to the variables that are defined
+/// by `lines_to_html` so that the palette can be adjusted in
+/// the exported document.
+fn css_color(color: ColorAttribute, palette: &ColorPalette) -> Option<String> {
+    match color {
+        ColorAttribute::Default => None,
+        ColorAttribute::PaletteIndex(idx) if idx < 16 => Some(format!("var(--color{})", idx)),
+        ColorAttribute::PaletteIndex(idx) => Some(palette.colors.0[idx as usize].to_rgb_string()),
+        ColorAttribute::TrueColorWithPaletteFallback(rgb, _)
+        | ColorAttribute::TrueColorWithDefaultFallback(rgb) => {
+            Some(rgb.to_tuple_rgba().to_rgb_string())
+        }
+    }
+}
+
+fn css_style(attrs: &CellAttributes, palette: &ColorPalette) -> String {
+    let mut fg = css_color(attrs.foreground(), palette);
+    let mut bg = css_color(attrs.background(), palette);
+    if attrs.reverse() {
+        let reversed_fg = Some(bg.unwrap_or_else(|| "var(--bg)".to_string()));
+        bg = Some(fg.unwrap_or_else(|| "var(--fg)".to_string()));
+        fg = reversed_fg;
+    }
+    if attrs.invisible() {
+        fg = Some("transparent".to_string());
+    }
+
+    let mut style = String::new();
+    if let Some(fg) = fg {
+        write!(style, "color:{};", fg).ok();
+    }
+    if let Some(bg) = bg {
+        write!(style, "background-color:{};", bg).ok();
+    }
+    match attrs.intensity() {
+        Intensity::Normal => {}
+        Intensity::Bold => style.push_str("font-weight:bold;"),
+        Intensity::Half => style.push_str("opacity:0.5;"),
+    }
+    if attrs.italic() {
+        style.push_str("font-style:italic;");
+    }
+
+    let mut decorations = vec![];
+    if attrs.underline() != Underline::None {
+        decorations.push("underline");
+    }
+    if attrs.strikethrough() {
+        decorations.push("line-through");
+    }
+    if attrs.overline() {
+        decorations.push("overline");
+    }
+    if !decorations.is_empty() {
+        write!(style, "text-decoration:{};", decorations.join(" ")).ok();
+    }
+
+    style
+}
+
+/// Renders the lines as a standalone HTML document, using the
+/// colors from `palette`
+pub fn lines_to_html(lines: &[Line], palette: &ColorPalette, title: &str) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>");
+    escape_html(title, &mut html);
+    html.push_str("</title>\n<style>\n:root {\n");
+    writeln!(html, "  --fg: {};", palette.foreground.to_rgb_string()).ok();
+    writeln!(html, "  --bg: {};", palette.background.to_rgb_string()).ok();
+    for (idx, color) in palette.colors.0.iter().take(16).enumerate() {
+        writeln!(html, "  --color{}: {};", idx, color.to_rgb_string()).ok();
+    }
+    html.push_str(
+        "}\nbody {\n  color: var(--fg);\n  background-color: var(--bg);\n}\n\
+         pre {\n  font-family: monospace;\n}\n</style>\n</head>\n<body>\n<pre>",
+    );
+
+    for line in lines {
+        // Skip trailing blank cells, but not those that have been
+        // colored, as they are visible in the terminal
+        let cells: Vec<_> = line.visible_cells().map(|(_, cell)| cell).collect();
+        let len = cells
+            .iter()
+            .rposition(|cell| {
+                let attrs = cell.attrs();
+                cell.str() != " "
+                    || attrs.background() != ColorAttribute::Default
+                    || attrs.reverse()
+            })
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+
+        // Each run of cells with the same style and hyperlink
+        // is placed in a single element
+        let mut current: Option<(String, Option<String>)> = None;
+        for cell in &cells[..len] {
+            let attrs = cell.attrs();
+            let run = (
+                css_style(attrs, palette),
+                attrs.hyperlink().map(|link| link.uri().to_string()),
+            );
+            if current.as_ref() != Some(&run) {
+                if let Some(prior) = current.take() {
+                    close_run(&prior, &mut html);
+                }
+                if let Some(uri) = &run.1 {
+                    html.push_str("<a href=\"");
+                    escape_html(uri, &mut html);
+                    html.push_str("\">");
+                }
+                if !run.0.is_empty() {
+                    write!(html, "<span style=\"{}\">", run.0).ok();
+                }
+                current = Some(run);
+            }
+            escape_html(cell.str(), &mut html);
+        }
+        if let Some(prior) = current.take() {
+            close_run(&prior, &mut html);
+        }
+        html.push('\n');
+    }
+
+    html.push_str("</pre>\n</body>\n</html>\n");
+    html
+}
+
+fn close_run((style, uri): &(String, Option<String>), html: &mut String) {
+    if !style.is_empty() {
+        html.push_str("</span>");
+    }
+    if uri.is_some() {
+        html.push_str("</a>");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use termwiz::color::AnsiColor;
+    use termwiz::surface::SEQ_ZERO;
+
+    #[test]
+    fn text_keeps_blank_lines() {
+        let attrs = CellAttributes::default();
+        let lines: Vec<Line> = ["one  ", "", "   ", "two", ""]
+            .iter()
+            .map(|text| Line::from_text(text, &attrs, SEQ_ZERO, None))
+            .collect();
+        assert_eq!(lines_to_text(&lines), "one\n\n\ntwo\n");
+    }
+
+    #[test]
+    fn html_runs() {
+        let palette = ColorPalette::default();
+        let mut red = CellAttributes::default();
+        red.set_foreground(AnsiColor::Maroon)
+            .set_intensity(Intensity::Bold);
+
+        let mut line = Line::from_text("<ls> ok   ", &CellAttributes::default(), SEQ_ZERO, None);
+        line.overlay_text_with_attribute(5, "ok", red, SEQ_ZERO);
+
+        let html = lines_to_html(&[line], &palette, "a & b");
+        assert!(html.contains("<title>a &amp; b</title>"));
+        assert!(html.contains(&format!(
+            "--color1: {};",
+            palette.colors.0[1].to_rgb_string()
+        )));
+        assert!(html.contains(
+            "<pre>&lt;ls&gt; <span style=\"color:var(--color1);font-weight:bold;\">ok</span>\n</pre>"
+        ));
+    }
+
+    #[test]
+    fn reverse_video() {
+        let palette = ColorPalette::default();
+        let mut attrs = CellAttributes::default();
+        attrs.set_reverse(true);
+        assert_eq!(
+            css_style(&attrs, &palette),
+            "color:var(--bg);background-color:var(--fg);"
+        );
+
+        attrs.set_background(ColorAttribute::PaletteIndex(200));
+        assert_eq!(
+            css_style(&attrs, &palette),
+            format!(
+                "color:{};background-color:var(--fg);",
+                palette.colors.0[200].to_rgb_string()
+            )
+        );
+    }
+
+    #[test]
+    fn parse_format() {
+        assert_eq!("html".parse::<ExportFormat>().unwrap(), ExportFormat::Html);
+        assert!("pdf".parse::<ExportFormat>().is_err());
+    }
+}
@@ -0,0 +1,49 @@
+//! Hibernates idle panes to reduce the memory used by a multiplexer
+//! server that hosts a large number of them.  The scrollback of a pane
+//! that has produced no output and received no input for longer than
+//! `mux_pane_hibernate_after_seconds` is compressed, and it is restored
+//! when it is next viewed; see `Pane::hibernate_if_idle`.
+use crate::{Mux, MuxNotification};
+use config::configuration;
+use std::time::Duration;
+
+/// How often the panes are checked for idleness
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Hibernates the panes that have been idle for longer than the
+/// configured threshold, and returns the number of panes that
+/// were hibernated
+pub fn hibernate_idle_panes() -> usize {
+    let after = configuration().mux_pane_hibernate_after_seconds;
+    if after == 0 {
+        return 0;
+    }
+    let mux = match Mux::get() {
+        Some(mux) => mux,
+        None => return 0,
+    };
+    let idle = Duration::from_secs(after);
+    let mut count = 0;
+    for pane in mux.iter_panes() {
+        if pane.hibernate_if_idle(idle) {
+            count += 1;
+            mux.notify(MuxNotification::PaneHibernated(pane.pane_id()));
+        }
+    }
+    if count > 0 {
+        log::debug!("hibernated {} idle panes", count);
+    }
+    count
+}
+
+/// Starts periodically hibernating idle panes.
+/// This must be called on the main thread.
+pub fn start_hibernation_task() {
+    promise::spawn::spawn(async {
+        loop {
+            smol::Timer::after(CHECK_INTERVAL).await;
+            hibernate_idle_panes();
+        }
+    })
+    .detach();
+}
@@ -0,0 +1,143 @@
+//! Aggregates the commands that are run in all panes, as reported
+//! by shell integration (OSC 133), so that they can be queried by
+//! `wezterm cli history` and from lua.
+use crate::pane::PaneId;
+use luahelper::impl_lua_conversion_dynamic;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromDynamic, ToDynamic)]
+pub struct CommandHistoryEntry {
+    pub pane_id: PaneId,
+    /// The name of the domain that the pane belongs to
+    pub domain: String,
+    /// The working directory of the pane when the command started,
+    /// as reported via OSC 7
+    pub cwd: Option<String>,
+    pub command: String,
+    /// When the command started, in seconds since the unix epoch
+    pub started: i64,
+    /// The exit status, if the command has finished and the
+    /// shell reported it
+    pub exit_status: Option<i32>,
+}
+impl_lua_conversion_dynamic!(CommandHistoryEntry);
+
+/// Selects entries from the history
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, FromDynamic, ToDynamic)]
+pub struct CommandHistoryQuery {
+    /// Only return commands that were run in this pane
+    #[dynamic(default)]
+    pub pane_id: Option<PaneId>,
+    /// Only return commands that contain this text
+    #[dynamic(default)]
+    pub search: Option<String>,
+    /// Return at most this many entries
+    #[dynamic(default)]
+    pub limit: Option<usize>,
+}
+impl_lua_conversion_dynamic!(CommandHistoryQuery);
+
+/// The most recently started command is held at the front
+#[derive(Debug, Default)]
+pub struct CommandHistory {
+    entries: VecDeque<CommandHistoryEntry>,
+}
+
+impl CommandHistory {
+    pub fn record_start(&mut self, entry: CommandHistoryEntry, max_entries: usize) {
+        self.entries.push_front(entry);
+        self.entries.truncate(max_entries);
+    }
+
+    /// Records the exit status of the most recent command in the pane
+    pub fn record_finish(&mut self, pane_id: PaneId, status: i32) {
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.pane_id == pane_id)
+        {
+            if entry.exit_status.is_none() {
+                entry.exit_status.replace(status);
+            }
+        }
+    }
+
+    /// Returns the matching entries, most recent first
+    pub fn query(&self, query: &CommandHistoryQuery) -> Vec<CommandHistoryEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| query.pane_id.map_or(true, |id| entry.pane_id == id))
+            .filter(|entry| {
+                query
+                    .search
+                    .as_ref()
+                    .map_or(true, |text| entry.command.contains(text.as_str()))
+            })
+            .take(query.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(pane_id: PaneId, command: &str) -> CommandHistoryEntry {
+        CommandHistoryEntry {
+            pane_id,
+            domain: "local".to_string(),
+            cwd: None,
+            command: command.to_string(),
+            started: 0,
+            exit_status: None,
+        }
+    }
+
+    fn commands(entries: Vec<CommandHistoryEntry>) -> Vec<String> {
+        entries.into_iter().map(|entry| entry.command).collect()
+    }
+
+    #[test]
+    fn query() {
+        let mut history = CommandHistory::default();
+        history.record_start(entry(1, "ls"), 3);
+        history.record_start(entry(2, "cargo build"), 3);
+        history.record_start(entry(1, "cargo test"), 3);
+        history.record_start(entry(2, "git status"), 3);
+
+        assert_eq!(
+            commands(history.query(&CommandHistoryQuery::default())),
+            vec!["git status", "cargo test", "cargo build"]
+        );
+        assert_eq!(
+            commands(history.query(&CommandHistoryQuery {
+                pane_id: Some(2),
+                ..Default::default()
+            })),
+            vec!["git status", "cargo build"]
+        );
+        assert_eq!(
+            commands(history.query(&CommandHistoryQuery {
+                search: Some("cargo".to_string()),
+                limit: Some(1),
+                ..Default::default()
+            })),
+            vec!["cargo test"]
+        );
+    }
+
+    #[test]
+    fn finish_updates_latest_in_pane() {
+        let mut history = CommandHistory::default();
+        history.record_start(entry(1, "false"), 10);
+        history.record_start(entry(2, "sleep 10"), 10);
+        history.record_finish(1, 1);
+
+        let entries = history.query(&CommandHistoryQuery::default());
+        assert_eq!(entries[0].exit_status, None);
+        assert_eq!(entries[1].exit_status, Some(1));
+    }
+}
@@ -0,0 +1,451 @@
+//! Records the windows, tabs and panes of the gui in a journal on
+//! disk when `enable_crash_recovery` is set, so that a session that
+//! ends abnormally can be restored at the next launch.
+//! The journal is a `WorkspaceLayout` for each workspace, along with
+//! the tail of the scrollback of each pane.  It is replaced atomically
+//! each time that it is written, and is removed when the gui exits
+//! cleanly, so a journal that outlives the process that wrote it
+//! indicates that the process terminated abnormally.
+use crate::domain::LocalDomain;
+use crate::layout::plan_layout;
+use crate::pane::{Pane, PaneId};
+use crate::tab::{PaneEntry, PaneNode, SplitDirection};
+use crate::Mux;
+use anyhow::Context;
+use config::{
+    configuration, ConfigHandle, LayoutSplitDirection, PaneLayout, SplitLayout, WindowLayout,
+    WorkspaceLayout,
+};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
+use termwiz::escape::parser::Parser;
+
+/// How often the journal is brought up to date
+const JOURNAL_INTERVAL: Duration = Duration::from_secs(10);
+
+lazy_static::lazy_static! {
+    static ref START_TIME: u64 = process_start_time(std::process::id()).unwrap_or(0);
+}
+
+/// Returns the start time of the process `pid`, in the system
+/// dependent units of `LocalProcessInfo::start_time`, or None
+/// if there is no such process
+fn process_start_time(pid: u32) -> Option<u64> {
+    procinfo::LocalProcessInfo::with_root_pid(pid).map(|proc| proc.start_time)
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalWorkspace {
+    pub layout: WorkspaceLayout,
+    /// The tail of the scrollback of each pane, in the order
+    /// in which the panes are created by `plan_layout`
+    pub scrollback: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionJournal {
+    /// The process that wrote the journal
+    pub pid: u32,
+    /// The start time of the process that wrote the journal, which
+    /// tells it apart from a later process that reused its pid.
+    /// 0 if it is not known.
+    #[serde(default)]
+    pub start_time: u64,
+    pub workspaces: Vec<JournalWorkspace>,
+}
+
+pub fn journal_path() -> PathBuf {
+    config::DATA_DIR.join("session-journal.json")
+}
+
+impl SessionJournal {
+    /// Writes the journal to a temporary file and then renames it
+    /// into place, so that the journal on disk is always complete
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = journal_path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let temp = path.with_extension("json.tmp");
+        let mut file = std::fs::File::create(&temp)?;
+        file.write_all(&serde_json::to_vec(self)?)?;
+        file.sync_all()?;
+        std::fs::rename(&temp, &path)?;
+        Ok(())
+    }
+
+    pub fn load() -> anyhow::Result<Option<Self>> {
+        let data = match std::fs::read(journal_path()) {
+            Ok(data) => data,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Some(serde_json::from_slice(&data)?))
+    }
+
+    /// Returns the journal if it was left behind by a process that
+    /// is no longer running, which means that it didn't shut down
+    /// cleanly
+    pub fn load_abandoned() -> anyhow::Result<Option<Self>> {
+        Ok(Self::load()?.filter(|journal| {
+            if journal.pid == std::process::id() {
+                return false;
+            }
+            match process_start_time(journal.pid) {
+                None => true,
+                // The pid was reused by an unrelated process
+                Some(start_time) => journal.start_time != 0 && start_time != journal.start_time,
+            }
+        }))
+    }
+
+    /// Checks that the journal is consistent, as it may have been
+    /// truncated or damaged on disk
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for workspace in &self.workspaces {
+            let panes = plan_layout(&workspace.layout).len();
+            anyhow::ensure!(
+                panes == workspace.scrollback.len(),
+                "workspace {:?} has {} panes but scrollback for {}",
+                workspace.layout.name,
+                panes,
+                workspace.scrollback.len()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Removes the journal if it was written by this process
+pub fn discard_journal() {
+    if let Ok(Some(journal)) = SessionJournal::load() {
+        if journal.pid == std::process::id() {
+            if let Err(err) = std::fs::remove_file(journal_path()) {
+                log::error!("failed to remove session journal: {:#}", err);
+            }
+        }
+    }
+}
+
+/// Converts the split tree of a tab into a `PaneLayout`, with
+/// `leaf` producing the layout of each pane.  Panes for which
+/// `leaf` returns None are left out.
+/// Returns the layout along with the ids of the panes that it
+/// describes, in the order in which they are created from it.
+pub fn pane_node_to_layout(
+    node: PaneNode,
+    leaf: &mut impl FnMut(&PaneEntry) -> Option<PaneLayout>,
+) -> Option<(PaneLayout, Vec<PaneId>)> {
+    match node {
+        PaneNode::Empty => None,
+        PaneNode::Leaf(entry) => leaf(&entry).map(|layout| (layout, vec![entry.pane_id])),
+        PaneNode::Split { left, right, node } => {
+            let first = pane_node_to_layout(*left, leaf);
+            let second = pane_node_to_layout(*right, leaf);
+            match (first, second) {
+                (Some((mut layout, mut panes)), Some((second, second_panes))) => {
+                    // Splitting the first pane off from the second
+                    // before applying the rest of its own splits
+                    // reproduces the original arrangement
+                    let (direction, first_size, second_size) = match node.direction {
+                        SplitDirection::Horizontal => (
+                            LayoutSplitDirection::Right,
+                            node.first.cols,
+                            node.second.cols,
+                        ),
+                        SplitDirection::Vertical => (
+                            LayoutSplitDirection::Bottom,
+                            node.first.rows,
+                            node.second.rows,
+                        ),
+                    };
+                    layout.splits.insert(
+                        0,
+                        SplitLayout {
+                            direction,
+                            size: second_size as f32 / (first_size + second_size + 1) as f32,
+                            pane: second,
+                        },
+                    );
+                    panes.splice(1..1, second_panes);
+                    Some((layout, panes))
+                }
+                (Some(first), None) => Some(first),
+                (None, second) => second,
+            }
+        }
+    }
+}
+
+fn scrollback_tail(pane: &Rc<dyn Pane>, lines: usize) -> String {
+    if lines == 0 {
+        return String::new();
+    }
+    let dims = pane.get_dimensions();
+    let end = dims.physical_top + dims.viewport_rows as isize;
+    let start = end.saturating_sub(lines as isize).max(dims.scrollback_top);
+    let (_first, lines) = pane.get_lines(start..end);
+    let mut text: Vec<String> = lines
+        .iter()
+        .map(|line| line.as_str().trim_end().to_string())
+        .collect();
+    while text.last().map(|line| line.is_empty()).unwrap_or(false) {
+        text.pop();
+    }
+    text.join("\n")
+}
+
+impl Mux {
+    fn journal_pane_layout(&self, entry: &PaneEntry, config: &ConfigHandle) -> Option<PaneLayout> {
+        let pane = self.get_pane(entry.pane_id)?;
+        // Panes in other domains, such as multiplexer domains, outlive
+        // the gui, and are reattached rather than restored
+        let domain = self.get_domain(pane.domain_id())?;
+        domain.downcast_ref::<LocalDomain>()?;
+
+        let process = pane.get_foreground_process_info();
+        let cwd = entry
+            .working_dir
+            .as_ref()
+            .and_then(|url| url.url.to_file_path().ok())
+            .or_else(|| process.as_ref().map(|proc| proc.cwd.clone()))
+            .filter(|cwd| !cwd.as_os_str().is_empty())
+            .map(|cwd| cwd.to_string_lossy().to_string());
+        let args = process
+            .filter(|proc| {
+                config
+                    .crash_recovery_restartable_commands
+                    .iter()
+                    .any(|name| *name == proc.name)
+            })
+            .map(|proc| proc.argv);
+        let domain = if domain.domain_id() == self.default_domain().domain_id() {
+            None
+        } else {
+            Some(domain.domain_name().to_string())
+        };
+
+        Some(PaneLayout {
+            args,
+            cwd,
+            domain,
+            ..Default::default()
+        })
+    }
+
+    /// Describes the local panes of each workspace in a journal
+    pub fn snapshot_session(&self) -> SessionJournal {
+        let config = configuration();
+        let mut workspaces = vec![];
+        for workspace in self.iter_workspaces() {
+            let mut layout = WorkspaceLayout {
+                name: workspace.clone(),
+                workspace: Some(workspace.clone()),
+                ..Default::default()
+            };
+            let mut panes = vec![];
+            for window_id in self.iter_windows_in_workspace(&workspace) {
+                let tabs: Vec<_> = match self.get_window(window_id) {
+                    Some(window) => window.iter().cloned().collect(),
+                    None => continue,
+                };
+                let mut window = WindowLayout::default();
+                for tab in tabs {
+                    if let Some((tab_layout, tab_panes)) =
+                        pane_node_to_layout(tab.codec_pane_tree(), &mut |entry| {
+                            self.journal_pane_layout(entry, &config)
+                        })
+                    {
+                        window.tabs.push(tab_layout);
+                        panes.extend(tab_panes);
+                    }
+                }
+                if !window.tabs.is_empty() {
+                    layout.windows.push(window);
+                }
+            }
+            if layout.windows.is_empty() {
+                continue;
+            }
+            let scrollback = panes
+                .into_iter()
+                .map(|pane_id| {
+                    self.get_pane(pane_id)
+                        .map(|pane| scrollback_tail(&pane, config.crash_recovery_scrollback_lines))
+                        .unwrap_or_default()
+                })
+                .collect();
+            workspaces.push(JournalWorkspace { layout, scrollback });
+        }
+        SessionJournal {
+            pid: std::process::id(),
+            start_time: *START_TIME,
+            workspaces,
+        }
+    }
+
+    /// Recreates the windows, tabs and panes that are recorded in
+    /// `journal`, and shows the recorded scrollback in each pane
+    pub async fn restore_session(&self, journal: &SessionJournal) -> anyhow::Result<()> {
+        journal
+            .validate()
+            .context("the session journal is damaged")?;
+        for workspace in &journal.workspaces {
+            let (name, panes) = self.apply_layout(&workspace.layout).await?;
+            for (pane_id, text) in panes.iter().zip(workspace.scrollback.iter()) {
+                if text.is_empty() {
+                    continue;
+                }
+                if let Some(pane) = self.get_pane(*pane_id) {
+                    let text = format!("{}\r\n", text.replace('\n', "\r\n"));
+                    pane.perform_actions(Parser::new().parse_as_vec(text.as_bytes()));
+                }
+            }
+            if self.is_active_workspace_empty() {
+                self.set_active_workspace(&name);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Starts periodically bringing the journal up to date.
+/// This must be called on the main thread.
+pub fn start_journal_task() {
+    promise::spawn::spawn(async {
+        let mut last = None;
+        loop {
+            smol::Timer::after(JOURNAL_INTERVAL).await;
+            if !configuration().enable_crash_recovery {
+                if last.take().is_some() {
+                    discard_journal();
+                }
+                continue;
+            }
+            let mux = match Mux::get() {
+                Some(mux) => mux,
+                None => continue,
+            };
+            let journal = mux.snapshot_session();
+            if last.as_ref() == Some(&journal) {
+                continue;
+            }
+            if let Err(err) = journal.save() {
+                log::error!("failed to write session journal: {:#}", err);
+            }
+            last = Some(journal);
+        }
+    })
+    .detach();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::layout::plan_pane_splits;
+    use crate::tab::SplitDirectionAndSize;
+    use wezterm_term::TerminalSize;
+
+    fn leaf(pane_id: PaneId) -> PaneNode {
+        PaneNode::Leaf(PaneEntry {
+            window_id: 0,
+            tab_id: 0,
+            pane_id,
+            title: String::new(),
+            size: TerminalSize::default(),
+            working_dir: None,
+            is_active_pane: false,
+            is_zoomed_pane: false,
+            workspace: String::new(),
+            resource_usage: None,
+        })
+    }
+
+    fn split(direction: SplitDirection, left: PaneNode, right: PaneNode) -> PaneNode {
+        let size = |cols, rows| TerminalSize {
+            cols,
+            rows,
+            ..Default::default()
+        };
+        PaneNode::Split {
+            left: Box::new(left),
+            right: Box::new(right),
+            node: SplitDirectionAndSize {
+                direction,
+                first: size(59, 24),
+                second: size(20, 5),
+            },
+        }
+    }
+
+    #[test]
+    fn tree_to_layout() {
+        // 1 | 2 on top, with 3 below both of them, and 4 below 2
+        let tree = split(
+            SplitDirection::Vertical,
+            split(
+                SplitDirection::Horizontal,
+                leaf(1),
+                split(SplitDirection::Vertical, leaf(2), leaf(4)),
+            ),
+            leaf(3),
+        );
+        let (layout, panes) = pane_node_to_layout(tree, &mut |entry| {
+            Some(PaneLayout {
+                args: Some(vec![entry.pane_id.to_string()]),
+                ..Default::default()
+            })
+        })
+        .unwrap();
+
+        assert_eq!(layout.splits.len(), 2);
+        assert_eq!(layout.splits[0].direction, LayoutSplitDirection::Bottom);
+        assert_eq!(layout.splits[0].size, 5. / 30.);
+        assert_eq!(layout.splits[1].direction, LayoutSplitDirection::Right);
+        assert_eq!(layout.splits[1].size, 20. / 80.);
+
+        // The panes are listed in the order that the layout creates them
+        let created: Vec<PaneId> = std::iter::once(&layout)
+            .chain(plan_pane_splits(&layout).iter().map(|step| match step {
+                crate::layout::LayoutStep::SplitPane { split, .. } => &split.pane,
+                _ => unreachable!(),
+            }))
+            .map(|pane| pane.args.as_ref().unwrap()[0].parse().unwrap())
+            .collect();
+        assert_eq!(created, panes);
+        assert_eq!(panes, vec![1, 3, 2, 4]);
+
+        // Panes that are left out don't leave a gap in the layout
+        let (layout, panes) = pane_node_to_layout(
+            split(SplitDirection::Horizontal, leaf(1), leaf(2)),
+            &mut |entry| (entry.pane_id == 2).then(PaneLayout::default),
+        )
+        .unwrap();
+        assert!(layout.splits.is_empty());
+        assert_eq!(panes, vec![2]);
+    }
+
+    #[test]
+    fn validate_scrollback() {
+        let mut journal = SessionJournal {
+            pid: 1,
+            start_time: 0,
+            workspaces: vec![JournalWorkspace {
+                layout: WorkspaceLayout {
+                    windows: vec![WindowLayout {
+                        tabs: vec![PaneLayout::default(), PaneLayout::default()],
+                    }],
+                    ..Default::default()
+                },
+                scrollback: vec![String::new(), String::new()],
+            }],
+        };
+        assert!(journal.validate().is_ok());
+
+        journal.workspaces[0].scrollback.pop();
+        assert!(journal.validate().is_err());
+    }
+}
@@ -0,0 +1,409 @@
+//! Applies a `WorkspaceLayout` from the configuration.
+//! The layout is first flattened into a plan: a sequence of steps in
+//! which each step creates a single pane, and refers to the window or
+//! pane that it builds upon by the index of the step that created it.
+//! Executing the steps in order means that the same layout always
+//! produces the same arrangement of windows, tabs and panes.
+use crate::domain::SplitSource;
+use crate::pane::PaneId;
+use crate::tab::{SplitDirection, SplitRequest, SplitSize};
+use crate::window::WindowId;
+use crate::Mux;
+use anyhow::{anyhow, Context};
+use config::keyassignment::SpawnTabDomain;
+use config::{configuration, LayoutSplitDirection, PaneLayout, SplitLayout, WorkspaceLayout};
+use portable_pty::CommandBuilder;
+use wezterm_term::TerminalSize;
+
+#[derive(Debug, PartialEq)]
+pub enum LayoutStep<'a> {
+    /// Create a new window whose first tab has this root pane
+    SpawnWindow { pane: &'a PaneLayout },
+    /// Add a tab to the window created by step `window`
+    SpawnTab { window: usize, pane: &'a PaneLayout },
+    /// Split the pane created by step `pane`
+    SplitPane { pane: usize, split: &'a SplitLayout },
+}
+
+/// Appends the steps that split `pane`, the pane created by step
+/// `pane`, to `steps`.  `first_step` is the index of the step at the
+/// start of `steps`.
+fn plan_splits<'a>(
+    pane: usize,
+    layout: &'a PaneLayout,
+    first_step: usize,
+    steps: &mut Vec<LayoutStep<'a>>,
+) {
+    for split in &layout.splits {
+        let step = first_step + steps.len();
+        steps.push(LayoutStep::SplitPane { pane, split });
+        plan_splits(step, &split.pane, first_step, steps);
+    }
+}
+
+pub fn plan_layout(layout: &WorkspaceLayout) -> Vec<LayoutStep> {
+    let mut steps = vec![];
+    for window in &layout.windows {
+        let mut window_step = None;
+        for tab in &window.tabs {
+            let step = steps.len();
+            match window_step {
+                None => {
+                    steps.push(LayoutStep::SpawnWindow { pane: tab });
+                    window_step = Some(step);
+                }
+                Some(window) => steps.push(LayoutStep::SpawnTab { window, pane: tab }),
+            }
+            plan_splits(step, tab, 0, &mut steps);
+        }
+    }
+    steps
+}
+
+/// Plans the splits of an existing pane, which is treated as though
+/// it were created by step 0.  The returned steps are therefore
+/// numbered from 1, and are all `LayoutStep::SplitPane`.
+pub fn plan_pane_splits(pane: &PaneLayout) -> Vec<LayoutStep> {
+    let mut steps = vec![];
+    plan_splits(0, pane, 1, &mut steps);
+    steps
+}
+
+/// Parses a list of splits in the JSON form that is accepted by
+/// `wezterm cli split-pane --layout`
+pub fn splits_from_json(json: &str) -> anyhow::Result<Vec<SplitLayout>> {
+    serde_json::from_str(json).context("parsing layout")
+}
+
+fn command_for_pane(pane: &PaneLayout) -> (Option<CommandBuilder>, Option<String>) {
+    let cwd = pane.resolved_cwd();
+    match &pane.args {
+        Some(args) => {
+            let mut builder = CommandBuilder::from_argv(args.iter().map(Into::into).collect());
+            for (k, v) in &pane.set_environment_variables {
+                builder.env(k, v);
+            }
+            if let Some(cwd) = &cwd {
+                builder.cwd(cwd);
+            }
+            (Some(builder), cwd)
+        }
+        None => (None, cwd),
+    }
+}
+
+fn split_request(split: &SplitLayout) -> SplitRequest {
+    SplitRequest {
+        direction: match split.direction {
+            LayoutSplitDirection::Left | LayoutSplitDirection::Right => SplitDirection::Horizontal,
+            LayoutSplitDirection::Top | LayoutSplitDirection::Bottom => SplitDirection::Vertical,
+        },
+        target_is_second: match split.direction {
+            LayoutSplitDirection::Top | LayoutSplitDirection::Left => false,
+            LayoutSplitDirection::Bottom | LayoutSplitDirection::Right => true,
+        },
+        top_level: false,
+        size: if split.size <= 0.0 {
+            SplitSize::Percent(50)
+        } else if split.size < 1.0 {
+            SplitSize::Percent((split.size * 100.).floor() as u8)
+        } else {
+            SplitSize::Cells(split.size as usize)
+        },
+    }
+}
+
+impl Mux {
+    async fn spawn_layout_tab(
+        &self,
+        window_id: Option<WindowId>,
+        pane: &PaneLayout,
+        size: TerminalSize,
+        workspace: &str,
+    ) -> anyhow::Result<(PaneId, WindowId)> {
+        let (command, cwd) = command_for_pane(pane);
+        let domain = match &pane.domain {
+            Some(name) => SpawnTabDomain::DomainName(name.clone()),
+            None => SpawnTabDomain::DefaultDomain,
+        };
+        let (_tab, pane, window_id) = self
+            .spawn_tab_or_window(
+                window_id,
+                domain,
+                command,
+                cwd,
+                size,
+                None,
+                workspace.to_string(),
+            )
+            .await?;
+        Ok((pane.pane_id(), window_id))
+    }
+
+    async fn split_layout_pane(
+        &self,
+        parent: PaneId,
+        split: &SplitLayout,
+    ) -> anyhow::Result<PaneId> {
+        let (command, command_dir) = command_for_pane(&split.pane);
+        let domain = match &split.pane.domain {
+            Some(name) => SpawnTabDomain::DomainName(name.clone()),
+            None => SpawnTabDomain::CurrentPaneDomain,
+        };
+        let (pane, _size) = self
+            .split_pane(
+                parent,
+                split_request(split),
+                SplitSource::Spawn {
+                    command,
+                    command_dir,
+                },
+                domain,
+            )
+            .await?;
+        Ok(pane.pane_id())
+    }
+
+    /// Splits `pane_id` as described by `splits`, making the splits in
+    /// the order given by `plan_pane_splits`.  The id of each pane is
+    /// pushed to `created` as soon as it is created, so that the caller
+    /// knows which panes exist even if a later split fails.
+    pub async fn apply_pane_splits(
+        &self,
+        pane_id: PaneId,
+        splits: &[SplitLayout],
+        created: &mut Vec<PaneId>,
+    ) -> anyhow::Result<()> {
+        let root = PaneLayout {
+            splits: splits.to_vec(),
+            ..Default::default()
+        };
+        // The pane created by each step; step 0 is the pane being split
+        let mut panes = vec![pane_id];
+        for step in plan_pane_splits(&root) {
+            let (parent, split) = match step {
+                LayoutStep::SplitPane { pane, split } => (panes[pane], split),
+                _ => unreachable!("plan_pane_splits only splits panes"),
+            };
+            let pane_id = self
+                .split_layout_pane(parent, split)
+                .await
+                .with_context(|| format!("splitting pane {}", parent))?;
+            panes.push(pane_id);
+            created.push(pane_id);
+        }
+        Ok(())
+    }
+
+    /// Creates the windows, tabs and panes described by the named
+    /// layout, and returns the name of the workspace that contains them
+    pub async fn apply_workspace_layout(&self, name: &str) -> anyhow::Result<String> {
+        let config = configuration();
+        let layout = config
+            .workspace_layout_by_name(name)
+            .ok_or_else(|| anyhow!("no workspace layout named `{}`", name))?;
+        let (workspace, _panes) = self.apply_layout(layout).await?;
+        Ok(workspace)
+    }
+
+    /// Creates the windows, tabs and panes described by `layout`.
+    /// Returns the name of the workspace that contains them, along
+    /// with the panes that were created by each of the steps of
+    /// `plan_layout`, in order.
+    pub async fn apply_layout(
+        &self,
+        layout: &WorkspaceLayout,
+    ) -> anyhow::Result<(String, Vec<PaneId>)> {
+        let config = configuration();
+        let workspace = layout.workspace_name().to_string();
+        let size = config.initial_size(0);
+
+        // The pane and window that were created by each step
+        let mut created: Vec<(PaneId, WindowId)> = vec![];
+        let mut root_panes = vec![];
+
+        for step in plan_layout(layout) {
+            let (pane_id, window_id) = match step {
+                LayoutStep::SpawnWindow { pane } => {
+                    let created = self.spawn_layout_tab(None, pane, size, &workspace).await?;
+                    root_panes.push(created.0);
+                    created
+                }
+                LayoutStep::SpawnTab { window, pane } => {
+                    let window_id = Some(created[window].1);
+                    let created = self
+                        .spawn_layout_tab(window_id, pane, size, &workspace)
+                        .await?;
+                    root_panes.push(created.0);
+                    created
+                }
+                LayoutStep::SplitPane { pane, split } => {
+                    let (parent, window_id) = created[pane];
+                    (self.split_layout_pane(parent, split).await?, window_id)
+                }
+            };
+            created.push((pane_id, window_id));
+        }
+
+        // Focus the root pane of each tab, and the first tab of
+        // each window, as though the layout had been built by hand
+        for pane_id in root_panes {
+            if let (Some(pane), Some((_domain, _window_id, tab_id))) =
+                (self.get_pane(pane_id), self.resolve_pane_id(pane_id))
+            {
+                if let Some(tab) = self.get_tab(tab_id) {
+                    tab.set_active_pane(&pane);
+                }
+            }
+        }
+        let mut windows: Vec<WindowId> = created.iter().map(|(_, window_id)| *window_id).collect();
+        windows.dedup();
+        for window_id in windows {
+            if let Some(mut window) = self.get_window_mut(window_id) {
+                window.set_active_without_saving(0);
+            }
+        }
+
+        Ok((
+            workspace,
+            created.into_iter().map(|(pane_id, _)| pane_id).collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use config::WindowLayout;
+
+    fn pane(args: &str, splits: Vec<SplitLayout>) -> PaneLayout {
+        PaneLayout {
+            args: Some(vec![args.to_string()]),
+            splits,
+            ..Default::default()
+        }
+    }
+
+    fn split(direction: LayoutSplitDirection, pane: PaneLayout) -> SplitLayout {
+        SplitLayout {
+            direction,
+            size: 0.5,
+            pane,
+        }
+    }
+
+    #[test]
+    fn plan_is_depth_first() {
+        let layout = WorkspaceLayout {
+            name: "dev".to_string(),
+            windows: vec![
+                WindowLayout {
+                    tabs: vec![
+                        pane(
+                            "editor",
+                            vec![
+                                split(
+                                    LayoutSplitDirection::Right,
+                                    pane(
+                                        "build",
+                                        vec![split(
+                                            LayoutSplitDirection::Bottom,
+                                            pane("test", vec![]),
+                                        )],
+                                    ),
+                                ),
+                                split(LayoutSplitDirection::Bottom, pane("shell", vec![])),
+                            ],
+                        ),
+                        pane("logs", vec![]),
+                    ],
+                },
+                WindowLayout { tabs: vec![] },
+                WindowLayout {
+                    tabs: vec![pane("top", vec![])],
+                },
+            ],
+            ..Default::default()
+        };
+
+        let win0 = &layout.windows[0];
+        let editor = &win0.tabs[0];
+        let build = &editor.splits[0];
+        assert_eq!(
+            plan_layout(&layout),
+            vec![
+                LayoutStep::SpawnWindow { pane: editor },
+                LayoutStep::SplitPane {
+                    pane: 0,
+                    split: build
+                },
+                LayoutStep::SplitPane {
+                    pane: 1,
+                    split: &build.pane.splits[0]
+                },
+                LayoutStep::SplitPane {
+                    pane: 0,
+                    split: &editor.splits[1]
+                },
+                LayoutStep::SpawnTab {
+                    window: 0,
+                    pane: &win0.tabs[1]
+                },
+                LayoutStep::SpawnWindow {
+                    pane: &layout.windows[2].tabs[0]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_pane_splits_numbers_from_one() {
+        let root = pane(
+            "editor",
+            vec![
+                split(
+                    LayoutSplitDirection::Right,
+                    pane(
+                        "build",
+                        vec![split(LayoutSplitDirection::Bottom, pane("test", vec![]))],
+                    ),
+                ),
+                split(LayoutSplitDirection::Bottom, pane("shell", vec![])),
+            ],
+        );
+        let build = &root.splits[0];
+        assert_eq!(
+            plan_pane_splits(&root),
+            vec![
+                LayoutStep::SplitPane {
+                    pane: 0,
+                    split: build
+                },
+                LayoutStep::SplitPane {
+                    pane: 1,
+                    split: &build.pane.splits[0]
+                },
+                LayoutStep::SplitPane {
+                    pane: 0,
+                    split: &root.splits[1]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn split_sizes() {
+        let mut layout = split(LayoutSplitDirection::Left, PaneLayout::default());
+        let request = split_request(&layout);
+        assert_eq!(request.direction, SplitDirection::Horizontal);
+        assert!(!request.target_is_second);
+        assert_eq!(request.size, SplitSize::Percent(50));
+
+        layout.direction = LayoutSplitDirection::Bottom;
+        layout.size = 12.0;
+        let request = split_request(&layout);
+        assert_eq!(request.direction, SplitDirection::Vertical);
+        assert!(request.target_is_second);
+        assert_eq!(request.size, SplitSize::Cells(12));
+    }
+}
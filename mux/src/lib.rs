@@ -1,11 +1,13 @@
 use crate::client::{ClientId, ClientInfo};
+use crate::history::{CommandHistory, CommandHistoryEntry, CommandHistoryQuery};
+use crate::outputrate::OutputRate;
 use crate::pane::{Pane, PaneId};
 use crate::tab::{SplitRequest, Tab, TabId};
 use crate::window::{Window, WindowId};
 use anyhow::{anyhow, Context, Error};
 use config::keyassignment::SpawnTabDomain;
 use config::{configuration, ExitBehavior};
-use domain::{Domain, DomainId, DomainState, SplitSource};
+use domain::{Domain, DomainId, DomainState, FileChunk, SplitSource};
 use filedescriptor::{socketpair, AsRawSocketDescriptor, FileDescriptor};
 #[cfg(unix)]
 use libc::{SOL_SOCKET, SO_RCVBUF, SO_SNDBUF};
@@ -13,8 +15,9 @@ use log::error;
 use metrics::histogram;
 use percent_encoding::percent_decode_str;
 use portable_pty::{CommandBuilder, ExitStatus, PtySize};
+use ratelim::RateLimiter;
 use std::cell::{Ref, RefCell, RefMut};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::convert::TryInto;
 use std::io::{Read, Write};
 use std::rc::Rc;
@@ -33,9 +36,16 @@ pub mod activity;
 pub mod client;
 pub mod connui;
 pub mod domain;
+pub mod export;
+pub mod hibernate;
+pub mod history;
+pub mod journal;
+pub mod layout;
 pub mod localpane;
+pub mod outputrate;
 pub mod pane;
 pub mod renderable;
+pub mod resourceusage;
 pub mod ssh;
 pub mod tab;
 pub mod termwiztermtab;
@@ -53,11 +63,23 @@ pub enum MuxNotification {
     PaneOutput(PaneId),
     PaneAdded(PaneId),
     PaneRemoved(PaneId),
+    PaneFocused(PaneId),
+    /// The scrollback of the pane was compressed because it was idle,
+    /// so any state that is cached for rendering it can be dropped
+    PaneHibernated(PaneId),
+    PaneResized {
+        pane_id: PaneId,
+        size: TerminalSize,
+    },
     WindowCreated(WindowId),
     WindowRemoved(WindowId),
     WindowInvalidated(WindowId),
     WindowWorkspaceChanged(WindowId),
     ActiveWorkspaceChanged(Arc<ClientId>),
+    /// A window was assigned a workspace that no other window had
+    WorkspaceCreated(String),
+    /// The last window in a workspace was removed or moved elsewhere
+    WorkspaceRemoved(String),
     Alert {
         pane_id: PaneId,
         alert: wezterm_term::Alert,
@@ -72,6 +94,28 @@ pub enum MuxNotification {
         name: Option<String>,
         data: Arc<Vec<u8>>,
     },
+    FileTransferProgress {
+        pane_id: PaneId,
+        path: String,
+        direction: FileTransferDirection,
+        transferred: u64,
+        total_size: Option<u64>,
+    },
+    /// An ssh domain needs the user to answer a prompt while
+    /// connecting the pane
+    SshPrompt {
+        pane_id: PaneId,
+        domain_name: String,
+        prompt: ssh::SshPrompt,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileTransferDirection {
+    /// Copying from the client into the pane's filesystem
+    Upload,
+    /// Copying from the pane's filesystem to the client
+    Download,
 }
 
 static SUB_ID: AtomicUsize = AtomicUsize::new(0);
@@ -88,10 +132,31 @@ pub struct Mux {
     clients: RefCell<HashMap<ClientId, ClientInfo>>,
     identity: RefCell<Option<Arc<ClientId>>>,
     num_panes_by_workspace: RefCell<HashMap<String, usize>>,
+    /// The workspaces that have been announced to subscribers
+    workspaces: RefCell<BTreeSet<String>>,
+    command_history: RefCell<CommandHistory>,
 }
 
 const BUFSIZE: usize = 1024 * 1024;
 
+/// Emits the lua event named `event`, passing it the name of a workspace
+fn emit_workspace_event(event: &'static str, workspace: String) {
+    promise::spawn::spawn(async move {
+        if let Err(err) = config::with_lua_config_on_main_thread(move |lua| async move {
+            if let Some(lua) = lua {
+                let args = lua.pack_multi(workspace)?;
+                config::lua::emit_event(&lua, (event.to_string(), args)).await?;
+            }
+            anyhow::Ok(())
+        })
+        .await
+        {
+            log::error!("while processing {} event: {:#}", event, err);
+        }
+    })
+    .detach();
+}
+
 /// This function bounces parsed actions over to the main thread to feed to
 /// the pty in the mux.
 /// It blocks until the mux has finished consuming the data, which provides
@@ -139,6 +204,7 @@ fn parse_buffered_data(pane_id: PaneId, dead: &Arc<AtomicBool>, mut rx: FileDesc
                 break;
             }
             Ok(size) => {
+                let start = Instant::now();
                 parser.parse(&buf[0..size], |action| {
                     let mut flush = false;
                     match &action {
@@ -174,6 +240,16 @@ fn parse_buffered_data(pane_id: PaneId, dead: &Arc<AtomicBool>, mut rx: FileDesc
                     send_actions_to_mux(pane_id, dead, std::mem::take(&mut actions));
                 }
 
+                // If the buffer was filled then the pane is producing output
+                // faster than we can process it; in adaptive mode, give the
+                // mux thread as long again to process input and render
+                // before we apply the next batch
+                if size == buf.len() && OutputRate::for_pane(pane_id).adaptive {
+                    let elapsed = start.elapsed();
+                    histogram!("parse_buffered_data.adaptive.latency", elapsed);
+                    thread::sleep(elapsed);
+                }
+
                 buf.resize(configuration().mux_output_parser_buffer_size, 0);
             }
         }
@@ -198,6 +274,39 @@ fn set_socket_buffer(fd: &mut FileDescriptor, option: i32, size: usize) -> anyho
     }
 }
 
+/// Writes `data` to the parser, waiting as needed so that the output
+/// of the pane is processed no faster than its output rate.
+/// `limiter` holds the limiter for the current rate, if any.
+fn write_rate_limited(
+    pane_id: PaneId,
+    tx: &mut FileDescriptor,
+    mut data: &[u8],
+    limiter: &mut Option<(u32, RateLimiter)>,
+) -> std::io::Result<()> {
+    while !data.is_empty() {
+        let rate = OutputRate::for_pane(pane_id).bytes_per_second;
+        if rate == 0 {
+            limiter.take();
+            return tx.write_all(data);
+        }
+        if !matches!(limiter, Some((current, _)) if *current == rate) {
+            limiter.replace((rate, RateLimiter::new(move |_| rate)));
+        }
+        let (_, lim) = limiter.as_mut().unwrap();
+        match lim.admit_check(data.len() as u32) {
+            Ok(amount) => {
+                tx.write_all(&data[..amount as usize])?;
+                data = &data[amount as usize..];
+            }
+            Err(delay) => {
+                histogram!("read_from_pane_pty.ratelimit.latency", delay);
+                thread::sleep(delay);
+            }
+        }
+    }
+    Ok(())
+}
+
 /// This function is run in a separate thread; its purpose is to perform
 /// blocking reads from the pty (non-blocking reads are not portable to
 /// all platforms and pty/tty types), parse the escape sequences and
@@ -222,6 +331,8 @@ fn read_from_pane_pty(pane_id: PaneId, banner: Option<String>, mut reader: Box<d
         tx.write_all(banner.as_bytes()).ok();
     }
 
+    let mut limiter = None;
+
     while !dead.load(Ordering::Relaxed) {
         match reader.read(&mut buf) {
             Ok(size) if size == 0 => {
@@ -234,7 +345,7 @@ fn read_from_pane_pty(pane_id: PaneId, banner: Option<String>, mut reader: Box<d
             }
             Ok(size) => {
                 histogram!("read_from_pane_pty.bytes.rate", size as f64);
-                if let Err(err) = tx.write_all(&buf[..size]) {
+                if let Err(err) = write_rate_limited(pane_id, &mut tx, &buf[..size], &mut limiter) {
                     error!(
                         "read_pty failed to write to parser: pane {} {:?}",
                         pane_id, err
@@ -265,6 +376,7 @@ fn read_from_pane_pty(pane_id: PaneId, banner: Option<String>, mut reader: Box<d
         }
     }
 
+    crate::outputrate::unregister_pane(pane_id);
     dead.store(true, Ordering::Relaxed);
 }
 
@@ -344,6 +456,8 @@ impl Mux {
             clients: RefCell::new(HashMap::new()),
             identity: RefCell::new(None),
             num_panes_by_workspace: RefCell::new(HashMap::new()),
+            workspaces: RefCell::new(BTreeSet::new()),
+            command_history: RefCell::new(CommandHistory::default()),
         }
     }
 
@@ -395,6 +509,7 @@ impl Mux {
         if let Some(pane) = self.get_pane(pane_id) {
             pane.focus_changed(true);
         }
+        self.notify(MuxNotification::PaneFocused(pane_id));
     }
 
     pub fn register_client(&self, client_id: Arc<ClientId>) {
@@ -403,6 +518,46 @@ impl Mux {
             .insert((*client_id).clone(), ClientInfo::new(client_id));
     }
 
+    /// Records that shell integration reported the start of a command
+    /// in the pane
+    pub fn record_command_started(&self, pane_id: PaneId, command: String) {
+        let max_entries = configuration().command_history_size;
+        if max_entries == 0 {
+            return;
+        }
+        let pane = match self.get_pane(pane_id) {
+            Some(pane) => pane,
+            None => return,
+        };
+        let domain = self
+            .get_domain(pane.domain_id())
+            .map(|domain| domain.domain_name().to_string())
+            .unwrap_or_default();
+        let entry = CommandHistoryEntry {
+            pane_id,
+            domain,
+            cwd: pane.get_current_working_dir().map(|url| url.to_string()),
+            command,
+            started: chrono::Utc::now().timestamp(),
+            exit_status: None,
+        };
+        self.command_history
+            .borrow_mut()
+            .record_start(entry, max_entries);
+    }
+
+    /// Records the exit status reported by shell integration for the
+    /// most recent command in the pane
+    pub fn record_command_finished(&self, pane_id: PaneId, status: i32) {
+        self.command_history
+            .borrow_mut()
+            .record_finish(pane_id, status);
+    }
+
+    pub fn query_command_history(&self, query: &CommandHistoryQuery) -> Vec<CommandHistoryEntry> {
+        self.command_history.borrow().query(query)
+    }
+
     pub fn iter_clients(&self) -> Vec<ClientInfo> {
         self.clients
             .borrow()
@@ -425,6 +580,74 @@ impl Mux {
         names
     }
 
+    fn check_workspace_exists(&self, workspace: &str) -> anyhow::Result<()> {
+        if self.iter_workspaces().iter().any(|w| w == workspace) {
+            Ok(())
+        } else {
+            anyhow::bail!("workspace {} does not exist", workspace)
+        }
+    }
+
+    /// Moves the windows in workspace `from` to workspace `into`, and
+    /// makes `into` active for the clients for which `from` was active
+    fn move_workspace(&self, from: &str, into: &str) {
+        for window_id in self.iter_windows_in_workspace(from) {
+            if let Some(mut window) = self.get_window_mut(window_id) {
+                window.set_workspace(into);
+            }
+        }
+        let clients: Vec<Arc<ClientId>> = self
+            .clients
+            .borrow()
+            .values()
+            .filter(|info| {
+                info.active_workspace
+                    .as_deref()
+                    .unwrap_or(DEFAULT_WORKSPACE)
+                    == from
+            })
+            .map(|info| Arc::clone(&info.client_id))
+            .collect();
+        for ident in clients {
+            self.set_active_workspace_for_client(&ident, into);
+        }
+        self.recompute_pane_count();
+    }
+
+    /// Renames a workspace.
+    /// Fails if there is already a workspace named `new_workspace`;
+    /// use `merge_workspace` to combine two workspaces.
+    pub fn rename_workspace(&self, old_workspace: &str, new_workspace: &str) -> anyhow::Result<()> {
+        self.check_workspace_exists(old_workspace)?;
+        if old_workspace == new_workspace {
+            return Ok(());
+        }
+        if self.iter_workspaces().iter().any(|w| w == new_workspace) {
+            anyhow::bail!("workspace {} already exists", new_workspace);
+        }
+        self.move_workspace(old_workspace, new_workspace);
+        Ok(())
+    }
+
+    /// Moves all of the windows in workspace `from` into workspace
+    /// `into`, which need not already exist
+    pub fn merge_workspace(&self, from: &str, into: &str) -> anyhow::Result<()> {
+        self.check_workspace_exists(from)?;
+        if from != into {
+            self.move_workspace(from, into);
+        }
+        Ok(())
+    }
+
+    /// Closes all of the windows in a workspace, killing their panes
+    pub fn delete_workspace(&self, workspace: &str) -> anyhow::Result<()> {
+        self.check_workspace_exists(workspace)?;
+        for window_id in self.iter_windows_in_workspace(workspace) {
+            self.kill_window(window_id);
+        }
+        Ok(())
+    }
+
     /// Generate a new unique workspace name
     pub fn generate_workspace_name(&self) -> String {
         let used = self.iter_workspaces();
@@ -508,8 +731,43 @@ impl Mux {
     }
 
     pub fn notify(&self, notification: MuxNotification) {
-        let mut subscribers = self.subscribers.borrow_mut();
-        subscribers.retain(|_, notify| notify(notification.clone()));
+        let windows_changed = matches!(
+            notification,
+            MuxNotification::WindowCreated(_)
+                | MuxNotification::WindowRemoved(_)
+                | MuxNotification::WindowWorkspaceChanged(_)
+        );
+        {
+            let mut subscribers = self.subscribers.borrow_mut();
+            subscribers.retain(|_, notify| notify(notification.clone()));
+        }
+        if windows_changed {
+            // The window may still be borrowed by our caller, so defer
+            // examining the workspaces until it has been released
+            promise::spawn::spawn_into_main_thread(async {
+                if let Some(mux) = Mux::get() {
+                    mux.reconcile_workspaces();
+                }
+            })
+            .detach();
+        }
+    }
+
+    /// Updates the set of known workspaces to match the workspaces of
+    /// the windows, and announces those that were created or removed
+    /// to subscribers and to the `workspace-created` and
+    /// `workspace-removed` events
+    fn reconcile_workspaces(&self) {
+        let current: BTreeSet<String> = self.iter_workspaces().into_iter().collect();
+        let prior = std::mem::replace(&mut *self.workspaces.borrow_mut(), current.clone());
+        for workspace in current.difference(&prior) {
+            self.notify(MuxNotification::WorkspaceCreated(workspace.clone()));
+            emit_workspace_event("workspace-created", workspace.clone());
+        }
+        for workspace in prior.difference(&current) {
+            self.notify(MuxNotification::WorkspaceRemoved(workspace.clone()));
+            emit_workspace_event("workspace-removed", workspace.clone());
+        }
     }
 
     pub fn default_domain(&self) -> Arc<dyn Domain> {
@@ -544,6 +802,63 @@ impl Mux {
             .insert(domain.domain_name().to_string(), Arc::clone(domain));
     }
 
+    /// Resolves the members of the named `domain_groups` entry
+    pub fn resolve_domain_group(&self, name: &str) -> anyhow::Result<Vec<Arc<dyn Domain>>> {
+        let config = configuration();
+        let group = config
+            .domain_group_by_name(name)
+            .ok_or_else(|| anyhow!("no domain group named `{}`", name))?;
+        group
+            .domains
+            .iter()
+            .map(|domain_name| {
+                self.get_domain_by_name(domain_name).ok_or_else(|| {
+                    anyhow!("domain group `{}`: no domain named `{}`", name, domain_name)
+                })
+            })
+            .collect()
+    }
+
+    /// Attaches all of the domains in the named group.
+    /// The domains connect simultaneously; a failure to attach one
+    /// of them doesn't prevent the others from attaching, but is
+    /// reported once they have all finished.
+    pub async fn attach_domain_group(&self, name: &str) -> anyhow::Result<()> {
+        let tasks: Vec<_> = self
+            .resolve_domain_group(name)?
+            .into_iter()
+            .map(|domain| {
+                promise::spawn::spawn(async move {
+                    domain
+                        .attach(None)
+                        .await
+                        .with_context(|| format!("attaching domain `{}`", domain.domain_name()))
+                })
+            })
+            .collect();
+
+        let mut result = Ok(());
+        for task in tasks {
+            if let Err(err) = task.await {
+                log::error!("domain group `{}`: {:#}", name, err);
+                if result.is_ok() {
+                    result = Err(err);
+                }
+            }
+        }
+        result
+    }
+
+    /// Detaches all of the attached domains in the named group
+    pub fn detach_domain_group(&self, name: &str) -> anyhow::Result<()> {
+        for domain in self.resolve_domain_group(name)? {
+            if domain.state() == DomainState::Attached {
+                domain.detach()?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn set_mux(mux: &Rc<Mux>) {
         MUX.with(|m| {
             *m.borrow_mut() = Some(Rc::clone(mux));
@@ -591,6 +906,7 @@ impl Mux {
         let pane_id = pane.pane_id();
         if let Some(reader) = pane.reader()? {
             let banner = self.banner.borrow().clone();
+            crate::outputrate::register_pane(pane_id);
             thread::spawn(move || read_from_pane_pty(pane_id, banner, reader));
         }
         self.recompute_pane_count();
@@ -894,8 +1210,16 @@ impl Mux {
                 let (pane_domain_id, _window_id, _tab_id) = self
                     .resolve_pane_id(pane_id)
                     .ok_or_else(|| anyhow!("pane_id {} invalid", pane_id))?;
-                self.get_domain(pane_domain_id)
-                    .expect("resolve_pane_id to give valid domain_id")
+                let domain = self
+                    .get_domain(pane_domain_id)
+                    .expect("resolve_pane_id to give valid domain_id");
+                // Panes such as virtual panes live in domains that
+                // can't spawn programs
+                if domain.spawnable() {
+                    domain
+                } else {
+                    self.default_domain()
+                }
             }
             SpawnTabDomain::DomainId(domain_id) => self
                 .get_domain(*domain_id)
@@ -938,6 +1262,62 @@ impl Mux {
         })
     }
 
+    fn resolve_pane_domain(&self, pane_id: PaneId) -> anyhow::Result<Arc<dyn Domain>> {
+        let pane = self
+            .get_pane(pane_id)
+            .ok_or_else(|| anyhow!("pane_id {} invalid", pane_id))?;
+        self.get_domain(pane.domain_id())
+            .ok_or_else(|| anyhow!("domain for pane {} not found", pane_id))
+    }
+
+    /// Reads a chunk of a file from the filesystem that hosts the pane.
+    /// See `Domain::read_file_chunk` for more details.
+    pub async fn read_file_chunk(
+        &self,
+        pane_id: PaneId,
+        path: &str,
+        offset: u64,
+        max_bytes: usize,
+    ) -> anyhow::Result<FileChunk> {
+        let domain = self.resolve_pane_domain(pane_id)?;
+        let chunk = domain
+            .read_file_chunk(pane_id, path, offset, max_bytes)
+            .await?;
+        self.notify(MuxNotification::FileTransferProgress {
+            pane_id,
+            path: path.to_string(),
+            direction: FileTransferDirection::Download,
+            transferred: offset + chunk.data.len() as u64,
+            total_size: chunk.total_size,
+        });
+        Ok(chunk)
+    }
+
+    /// Writes a chunk of a file to the filesystem that hosts the pane.
+    /// See `Domain::write_file_chunk` for more details.
+    pub async fn write_file_chunk(
+        &self,
+        pane_id: PaneId,
+        path: &str,
+        offset: u64,
+        data: Vec<u8>,
+        total_size: Option<u64>,
+    ) -> anyhow::Result<()> {
+        let domain = self.resolve_pane_domain(pane_id)?;
+        let len = data.len() as u64;
+        domain
+            .write_file_chunk(pane_id, path, offset, data, total_size)
+            .await?;
+        self.notify(MuxNotification::FileTransferProgress {
+            pane_id,
+            path: path.to_string(),
+            direction: FileTransferDirection::Upload,
+            transferred: offset + len,
+            total_size,
+        });
+        Ok(())
+    }
+
     pub async fn split_pane(
         &self,
         // TODO: disambiguate with TabId
@@ -994,6 +1374,59 @@ impl Mux {
         Ok((pane, size))
     }
 
+    /// Exchanges the positions of two panes, which may be in different
+    /// tabs or windows.  Each pane takes the size of the position that
+    /// it is moved into; the layouts of the tabs are unchanged.
+    pub fn swap_panes(&self, pane_id: PaneId, other_pane_id: PaneId) -> anyhow::Result<()> {
+        if pane_id == other_pane_id {
+            return Ok(());
+        }
+        let pane = self
+            .get_pane(pane_id)
+            .ok_or_else(|| anyhow!("pane {} not found", pane_id))?;
+        let other = self
+            .get_pane(other_pane_id)
+            .ok_or_else(|| anyhow!("pane {} not found", other_pane_id))?;
+        if pane.domain_id() != other.domain_id() {
+            anyhow::bail!(
+                "panes {} and {} belong to different domains",
+                pane_id,
+                other_pane_id
+            );
+        }
+        let (_domain, window_id, tab_id) = self
+            .resolve_pane_id(pane_id)
+            .ok_or_else(|| anyhow!("pane {} is not in a tab", pane_id))?;
+        let (_domain, other_window_id, other_tab_id) = self
+            .resolve_pane_id(other_pane_id)
+            .ok_or_else(|| anyhow!("pane {} is not in a tab", other_pane_id))?;
+        let tab = self
+            .get_tab(tab_id)
+            .ok_or_else(|| anyhow!("Invalid tab id {}", tab_id))?;
+
+        if tab_id == other_tab_id {
+            tab.swap_panes(pane_id, other_pane_id)
+                .ok_or_else(|| anyhow!("failed to swap panes in tab {}", tab_id))?;
+        } else {
+            let other_tab = self
+                .get_tab(other_tab_id)
+                .ok_or_else(|| anyhow!("Invalid tab id {}", other_tab_id))?;
+            tab.replace_pane(pane_id, &other)
+                .ok_or_else(|| anyhow!("pane {} wasn't in its containing tab!?", pane_id))?;
+            other_tab
+                .replace_pane(other_pane_id, &pane)
+                .ok_or_else(|| anyhow!("pane {} wasn't in its containing tab!?", other_pane_id))?;
+        }
+
+        pane.panes_swapped(&*other);
+
+        self.notify(MuxNotification::WindowInvalidated(window_id));
+        if other_window_id != window_id {
+            self.notify(MuxNotification::WindowInvalidated(other_window_id));
+        }
+        Ok(())
+    }
+
     pub async fn move_pane_to_new_tab(
         &self,
         pane_id: PaneId,
@@ -1,5 +1,5 @@
 use crate::domain::DomainId;
-use crate::pane::{CloseReason, Pane, PaneId, Pattern, SearchResult};
+use crate::pane::{CloseReason, Pane, PaneId, Pattern, SearchResult, WorkingDirAccuracy};
 use crate::renderable::*;
 use crate::tmux::{TmuxDomain, TmuxDomainState};
 use crate::{Domain, Mux, MuxNotification};
@@ -26,6 +26,7 @@ use wezterm_term::color::ColorPalette;
 use wezterm_term::{
     Alert, AlertHandler, CellAttributes, Clipboard, DownloadHandler, KeyCode, KeyModifiers,
     MouseEvent, SemanticZone, StableRowIndex, Terminal, TerminalConfiguration, TerminalSize,
+    TerminalStateReport,
 };
 
 #[derive(Debug)]
@@ -58,6 +59,13 @@ pub struct LocalPane {
     tmux_domain: RefCell<Option<Arc<TmuxDomainState>>>,
     proc_list: RefCell<Option<CachedProcInfo>>,
     command_description: String,
+    read_only: RefCell<bool>,
+    /// Swallows the writes made while the pane is read-only
+    discard: RefCell<std::io::Sink>,
+    /// When the pane last produced output or received input
+    last_activity: RefCell<Instant>,
+    /// The compressed scrollback of the pane while it is hibernated
+    hibernated: RefCell<Option<Vec<u8>>>,
 }
 
 #[async_trait(?Send)]
@@ -78,6 +86,10 @@ impl Pane for LocalPane {
         self.terminal.borrow().get_keyboard_encoding()
     }
 
+    fn get_terminal_state(&self) -> Option<TerminalStateReport> {
+        Some(self.terminal.borrow().terminal_state_report())
+    }
+
     fn get_current_seqno(&self) -> SequenceNo {
         self.terminal.borrow().current_seqno()
     }
@@ -91,6 +103,12 @@ impl Pane for LocalPane {
     }
 
     fn get_lines(&self, lines: Range<StableRowIndex>) -> (StableRowIndex, Vec<Line>) {
+        if self.hibernated.borrow().is_some() {
+            let first_live_row = self.terminal.borrow().screen().phys_to_stable_row_index(0);
+            if lines.start < first_live_row {
+                self.wake();
+            }
+        }
         let (first, mut lines) = terminal_get_lines(&mut self.terminal.borrow_mut(), lines);
 
         if self.tmux_domain.borrow().is_some() {
@@ -250,15 +268,24 @@ impl Pane for LocalPane {
     }
 
     fn perform_actions(&self, actions: Vec<termwiz::escape::Action>) {
+        self.note_activity();
         self.terminal.borrow_mut().perform_actions(actions)
     }
 
     fn mouse_event(&self, event: MouseEvent) -> Result<(), Error> {
+        self.note_activity();
+        if self.is_read_only() {
+            return Ok(());
+        }
         Mux::get().unwrap().record_input_for_current_identity();
         self.terminal.borrow_mut().mouse_event(event)
     }
 
     fn key_down(&self, key: KeyCode, mods: KeyModifiers) -> Result<(), Error> {
+        self.note_activity();
+        if self.is_read_only() {
+            return Ok(());
+        }
         Mux::get().unwrap().record_input_for_current_identity();
         if self.tmux_domain.borrow().is_some() {
             log::error!("key: {:?}", key);
@@ -272,11 +299,28 @@ impl Pane for LocalPane {
     }
 
     fn key_up(&self, key: KeyCode, mods: KeyModifiers) -> Result<(), Error> {
+        if self.is_read_only() {
+            return Ok(());
+        }
         Mux::get().unwrap().record_input_for_current_identity();
         self.terminal.borrow_mut().key_up(key, mods)
     }
 
+    fn set_read_only(&self, read_only: bool) {
+        *self.read_only.borrow_mut() = read_only;
+        if let Some(mux) = Mux::get() {
+            mux.notify(MuxNotification::PaneOutput(self.pane_id));
+        }
+    }
+
+    fn is_read_only(&self) -> bool {
+        *self.read_only.borrow()
+    }
+
     fn resize(&self, size: TerminalSize) -> Result<(), Error> {
+        // The hibernated lines need to be rewrapped along with the others
+        self.wake();
+        let prior = self.get_dimensions();
         self.pty.borrow_mut().resize(PtySize {
             rows: size.rows.try_into()?,
             cols: size.cols.try_into()?,
@@ -284,10 +328,21 @@ impl Pane for LocalPane {
             pixel_height: size.pixel_height.try_into()?,
         })?;
         self.terminal.borrow_mut().resize(size);
+        if prior.cols != size.cols || prior.viewport_rows != size.rows {
+            if let Some(mux) = Mux::get() {
+                mux.notify(MuxNotification::PaneResized {
+                    pane_id: self.pane_id,
+                    size,
+                });
+            }
+        }
         Ok(())
     }
 
     fn writer(&self) -> RefMut<dyn std::io::Write> {
+        if self.is_read_only() {
+            return self.discard.borrow_mut();
+        }
         Mux::get().unwrap().record_input_for_current_identity();
         self.pty.borrow_mut()
     }
@@ -297,6 +352,10 @@ impl Pane for LocalPane {
     }
 
     fn send_paste(&self, text: &str) -> Result<(), Error> {
+        self.note_activity();
+        if self.is_read_only() {
+            return Ok(());
+        }
         Mux::get().unwrap().record_input_for_current_identity();
         if self.tmux_domain.borrow().is_some() {
             Ok(())
@@ -330,6 +389,7 @@ impl Pane for LocalPane {
     }
 
     fn erase_scrollback(&self, erase_mode: ScrollbackEraseMode) {
+        self.hibernated.borrow_mut().take();
         match erase_mode {
             ScrollbackEraseMode::ScrollbackOnly => {
                 self.terminal.borrow_mut().erase_scrollback();
@@ -348,8 +408,14 @@ impl Pane for LocalPane {
         self.terminal.borrow().has_unseen_output()
     }
 
+    fn unseen_bell_count(&self) -> usize {
+        self.terminal.borrow().unseen_bell_count()
+    }
+
     fn is_mouse_grabbed(&self) -> bool {
-        if self.tmux_domain.borrow().is_some() {
+        // Let the gui select and scroll rather than reporting the
+        // mouse to the application, which wouldn't receive it
+        if self.tmux_domain.borrow().is_some() || self.is_read_only() {
             false
         } else {
             self.terminal.borrow().is_mouse_grabbed()
@@ -372,6 +438,16 @@ impl Pane for LocalPane {
             .or_else(|| self.divine_current_working_dir())
     }
 
+    fn get_current_working_dir_accuracy(&self) -> Option<WorkingDirAccuracy> {
+        if self.terminal.borrow().get_current_dir().is_some() {
+            Some(WorkingDirAccuracy::Reported)
+        } else if self.divine_current_working_dir().is_some() {
+            Some(WorkingDirAccuracy::Inferred)
+        } else {
+            None
+        }
+    }
+
     fn get_foreground_process_info(&self) -> Option<LocalProcessInfo> {
         #[cfg(unix)]
         if let Some(pid) = self.pty.borrow().process_group_leader() {
@@ -466,12 +542,42 @@ impl Pane for LocalPane {
         }
     }
 
+    fn hibernate_if_idle(&self, idle: Duration) -> bool {
+        if self.hibernated.borrow().is_some() || self.last_activity.borrow().elapsed() < idle {
+            return false;
+        }
+        let lines = self.terminal.borrow_mut().take_scrollback();
+        if lines.is_empty() {
+            return false;
+        }
+        match compress_lines(&lines) {
+            Ok(compressed) => {
+                log::debug!(
+                    "pane {} hibernated {} lines of scrollback into {} bytes",
+                    self.pane_id,
+                    lines.len(),
+                    compressed.len()
+                );
+                self.hibernated.borrow_mut().replace(compressed);
+                self.proc_list.borrow_mut().take();
+                true
+            }
+            Err(err) => {
+                log::error!("pane {}: unable to hibernate: {:#}", self.pane_id, err);
+                self.terminal.borrow_mut().restore_scrollback(lines);
+                false
+            }
+        }
+    }
+
     fn get_semantic_zones(&self) -> anyhow::Result<Vec<SemanticZone>> {
+        self.wake();
         let mut term = self.terminal.borrow_mut();
         term.get_semantic_zones()
     }
 
     async fn search(&self, mut pattern: Pattern) -> anyhow::Result<Vec<SearchResult>> {
+        self.wake();
         let term = self.terminal.borrow();
         let screen = term.screen();
 
@@ -750,6 +856,12 @@ impl AlertHandler for LocalPaneNotifHandler {
                         }
                     }
                 }
+                Alert::CommandStarted { command } => {
+                    mux.record_command_started(self.pane_id, command.clone());
+                }
+                Alert::CommandFinished { status } => {
+                    mux.record_command_finished(self.pane_id, *status);
+                }
                 _ => {}
             }
 
@@ -761,6 +873,22 @@ impl AlertHandler for LocalPaneNotifHandler {
     }
 }
 
+fn compress_lines(lines: &[Line]) -> anyhow::Result<Vec<u8>> {
+    let mut compressed = Vec::new();
+    let mut compress = zstd::Encoder::new(&mut compressed, zstd::DEFAULT_COMPRESSION_LEVEL)?;
+    let mut encode = varbincode::Serializer::new(&mut compress);
+    serde::Serialize::serialize(lines, &mut encode)?;
+    drop(encode);
+    compress.finish()?;
+    Ok(compressed)
+}
+
+fn decompress_lines(compressed: &[u8]) -> anyhow::Result<Vec<Line>> {
+    let mut decompress = zstd::Decoder::new(compressed)?;
+    let mut decode = varbincode::Deserializer::new(&mut decompress);
+    serde::Deserialize::deserialize(&mut decode).map_err(Into::into)
+}
+
 /// This is a little gross; on some systems, our pipe reader will continue
 /// to be blocked in read even after the child process has died.
 /// We need to wake up and notice that the child terminated in order
@@ -804,6 +932,9 @@ impl LocalPane {
         command_description: String,
     ) -> Self {
         let (process, signaller, pid) = split_child(process);
+        if let Some(pid) = pid {
+            crate::resourceusage::register_pane(pane_id, pid);
+        }
 
         terminal.set_device_control_handler(Box::new(LocalPaneDCSHandler {
             pane_id,
@@ -824,9 +955,45 @@ impl LocalPane {
             tmux_domain: RefCell::new(None),
             proc_list: RefCell::new(None),
             command_description,
+            read_only: RefCell::new(false),
+            discard: RefCell::new(std::io::sink()),
+            last_activity: RefCell::new(Instant::now()),
+            hibernated: RefCell::new(None),
         }
     }
 
+    /// Records that the pane is in use, so that it isn't hibernated
+    fn note_activity(&self) {
+        *self.last_activity.borrow_mut() = Instant::now();
+    }
+
+    /// Restores the scrollback that was compressed when the pane
+    /// was hibernated
+    fn wake(&self) {
+        let compressed = match self.hibernated.borrow_mut().take() {
+            Some(compressed) => compressed,
+            None => return,
+        };
+        self.note_activity();
+        let lines = match decompress_lines(&compressed) {
+            Ok(lines) => lines,
+            Err(err) => {
+                log::error!(
+                    "pane {}: unable to restore hibernated scrollback: {:#}",
+                    self.pane_id,
+                    err
+                );
+                vec![]
+            }
+        };
+        log::debug!(
+            "pane {} restored {} lines of hibernated scrollback",
+            self.pane_id,
+            lines.len()
+        );
+        self.terminal.borrow_mut().restore_scrollback(lines);
+    }
+
     fn divine_current_working_dir(&self) -> Option<Url> {
         #[cfg(unix)]
         if let Some(pid) = self.pty.borrow().process_group_leader() {
@@ -913,6 +1080,7 @@ impl LocalPane {
 
 impl Drop for LocalPane {
     fn drop(&mut self) {
+        crate::resourceusage::unregister_pane(self.pane_id);
         // Avoid lingering zombies if we can, but don't block forever.
         // <https://github.com/wez/wezterm/issues/558>
         if let ProcessState::Running { signaller, .. } = &mut *self.process.borrow_mut() {
@@ -0,0 +1,66 @@
+//! Controls the rate at which the output of a pane is processed.
+//! The defaults are taken from the `ratelimit_output_bytes_per_second`
+//! and `adaptive_output_ratelimit` configuration options, and can be
+//! overridden for an individual pane at runtime.
+use crate::pane::PaneId;
+use config::{configuration, ConfigHandle};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+lazy_static::lazy_static! {
+    /// The panes whose output is read by `read_from_pane_pty`,
+    /// along with the override for each, if any
+    static ref PANES: Mutex<HashMap<PaneId, Option<OutputRate>>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputRate {
+    /// The maximum number of bytes that are processed per second,
+    /// or 0 if there is no limit
+    pub bytes_per_second: u32,
+    /// When set, yield to the mux thread between batches of output
+    /// while the pane is producing more output than can be processed
+    pub adaptive: bool,
+}
+
+impl OutputRate {
+    pub fn from_config(config: &ConfigHandle) -> Self {
+        Self {
+            bytes_per_second: config.ratelimit_output_bytes_per_second,
+            adaptive: config.adaptive_output_ratelimit,
+        }
+    }
+
+    /// Returns the rate that applies to the output of the pane
+    pub fn for_pane(pane_id: PaneId) -> Self {
+        match PANES.lock().unwrap().get(&pane_id) {
+            Some(Some(rate)) => *rate,
+            _ => Self::from_config(&configuration()),
+        }
+    }
+}
+
+pub(crate) fn register_pane(pane_id: PaneId) {
+    PANES.lock().unwrap().insert(pane_id, None);
+}
+
+pub(crate) fn unregister_pane(pane_id: PaneId) {
+    PANES.lock().unwrap().remove(&pane_id);
+}
+
+/// Overrides the rate for the pane, or restores the rate from the
+/// configuration if `rate` is None.
+/// Fails if the output of the pane is not processed by this process,
+/// for example, because it is in a multiplexer domain.
+pub fn set_output_rate(pane_id: PaneId, rate: Option<OutputRate>) -> anyhow::Result<()> {
+    match PANES.lock().unwrap().get_mut(&pane_id) {
+        Some(entry) => {
+            *entry = rate;
+            Ok(())
+        }
+        None => anyhow::bail!(
+            "the output of pane {} is not processed by this process",
+            pane_id
+        ),
+    }
+}
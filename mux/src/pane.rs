@@ -2,7 +2,7 @@ use crate::domain::DomainId;
 use crate::renderable::*;
 use crate::Mux;
 use async_trait::async_trait;
-use config::keyassignment::{KeyAssignment, ScrollbackEraseMode};
+use config::keyassignment::{KeyAssignment, RotationDirection, ScrollbackEraseMode};
 use downcast_rs::{impl_downcast, Downcast};
 use rangeset::RangeSet;
 use serde::{Deserialize, Serialize};
@@ -10,6 +10,7 @@ use std::cell::RefMut;
 use std::collections::HashMap;
 use std::ops::Range;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use termwiz::hyperlink::Rule;
 use termwiz::input::KeyboardEncoding;
 use termwiz::surface::{Line, SequenceNo};
@@ -17,7 +18,7 @@ use url::Url;
 use wezterm_term::color::ColorPalette;
 use wezterm_term::{
     Clipboard, DownloadHandler, KeyCode, KeyModifiers, MouseEvent, SemanticZone, StableRowIndex,
-    TerminalConfiguration, TerminalSize,
+    TerminalConfiguration, TerminalSize, TerminalStateReport,
 };
 
 static PANE_ID: ::std::sync::atomic::AtomicUsize = ::std::sync::atomic::AtomicUsize::new(0);
@@ -85,6 +86,16 @@ pub enum CloseReason {
     Pane,
 }
 
+/// Describes how the current working directory of a pane was determined
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WorkingDirAccuracy {
+    /// The shell reported it via OSC 7
+    Reported,
+    /// The shell didn't report it, so it was inferred from the
+    /// working directory of the foreground process
+    Inferred,
+}
+
 const PASTE_CHUNK_SIZE: usize = 1024;
 
 struct Paste {
@@ -356,6 +367,20 @@ pub trait Pane: Downcast {
     /// Called as a hint that the pane is being resized as part of
     /// a zoom-to-fill-all-the-tab-space operation.
     fn set_zoomed(&self, _zoomed: bool) {}
+    /// Called on the active pane after the panes in its tab have been
+    /// rotated, so that a remote mux can rotate its copy of the tab.
+    fn panes_rotated(&self, _direction: RotationDirection) {}
+    /// Called after the pane has exchanged positions with `other`, so
+    /// that a remote mux can swap its copies of the panes.
+    fn panes_swapped(&self, _other: &dyn Pane) {}
+    /// Blocks, or unblocks, all input to the pane.  While the pane is
+    /// read-only, key presses, mouse events, pastes and writes are
+    /// discarded, but its output continues to be shown and can be
+    /// scrolled and selected.
+    fn set_read_only(&self, _read_only: bool) {}
+    fn is_read_only(&self) -> bool {
+        false
+    }
     fn key_down(&self, key: KeyCode, mods: KeyModifiers) -> anyhow::Result<()>;
     fn key_up(&self, key: KeyCode, mods: KeyModifiers) -> anyhow::Result<()>;
     fn perform_assignment(&self, _assignment: &KeyAssignment) -> bool {
@@ -372,12 +397,27 @@ pub trait Pane: Downcast {
         KeyboardEncoding::Xterm
     }
 
+    /// Returns the modes, margins, tab stops and other state of the
+    /// terminal, if this pane has a local terminal model
+    fn get_terminal_state(&self) -> Option<TerminalStateReport> {
+        None
+    }
+
     fn copy_user_vars(&self) -> HashMap<String, String> {
         HashMap::new()
     }
 
     fn erase_scrollback(&self, _erase_mode: ScrollbackEraseMode) {}
 
+    /// Compresses the scrollback of the pane if it has produced no
+    /// output and received no input for at least `idle`, so that it
+    /// uses less memory while it isn't in use.  The scrollback is
+    /// restored when it is next needed.
+    /// Returns true if the pane was hibernated.
+    fn hibernate_if_idle(&self, _idle: Duration) -> bool {
+        false
+    }
+
     /// Called to advise on whether this tab has focus
     fn focus_changed(&self, _focused: bool) {}
 
@@ -389,6 +429,12 @@ pub trait Pane: Downcast {
         false
     }
 
+    /// Returns the number of times that the bell has rung
+    /// since the pane lost focus
+    fn unseen_bell_count(&self) -> usize {
+        0
+    }
+
     /// Certain panes are OK to be closed with impunity (no prompts)
     fn can_close_without_prompting(&self, _reason: CloseReason) -> bool {
         false
@@ -421,6 +467,11 @@ pub trait Pane: Downcast {
     }
 
     fn get_current_working_dir(&self) -> Option<Url>;
+    /// Returns how the result of `get_current_working_dir` was
+    /// determined, or None if that isn't known
+    fn get_current_working_dir_accuracy(&self) -> Option<WorkingDirAccuracy> {
+        None
+    }
     fn get_foreground_process_name(&self) -> Option<String> {
         None
     }
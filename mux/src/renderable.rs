@@ -90,12 +90,15 @@ pub fn terminal_get_lines(
 /// Implements Pane::get_dimensions for Terminal
 pub fn terminal_get_dimensions(term: &mut Terminal) -> RenderableDimensions {
     let screen = term.screen();
+    // Hibernated scrollback is included so that scrolling back
+    // into it will request its lines and wake the pane
+    let hibernated = screen.hibernated_rows();
     RenderableDimensions {
         cols: screen.physical_cols,
         viewport_rows: screen.physical_rows,
-        scrollback_rows: screen.scrollback_rows(),
+        scrollback_rows: screen.scrollback_rows() + hibernated,
         physical_top: screen.visible_row_to_stable_row(0),
-        scrollback_top: screen.phys_to_stable_row_index(0),
+        scrollback_top: screen.phys_to_stable_row_index(0) - hibernated as StableRowIndex,
         dpi: screen.dpi,
     }
 }
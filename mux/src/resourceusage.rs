@@ -0,0 +1,239 @@
+//! Samples the CPU and memory usage of the processes that are running
+//! in each local pane, so that runaway jobs can be found.
+//! Sampling is enabled by the `pane_resource_usage_sample_interval`
+//! configuration option and is performed by a background thread that
+//! is only running while sampling is enabled; the most recent sample for
+//! a pane is returned by `get_resource_usage`.
+use crate::pane::PaneId;
+use config::configuration;
+use luahelper::impl_lua_conversion_dynamic;
+use procinfo::LocalProcessInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+
+/// Guards against an interval so short that sampling would
+/// itself be a significant load
+const MIN_INTERVAL: Duration = Duration::from_millis(250);
+
+lazy_static::lazy_static! {
+    /// The panes whose processes are sampled, keyed by pane id
+    static ref PANES: Mutex<HashMap<PaneId, PaneSampler>> = Mutex::new(HashMap::new());
+}
+
+static SAMPLER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// The resources used by the process tree of a pane.
+/// This type is used directly by the codec, take care to bump
+/// the codec version if you change this
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, FromDynamic, ToDynamic)]
+pub struct PaneResourceUsage {
+    /// The CPU usage of the processes over the period since the
+    /// previous sample, as a percentage of a single CPU core; a process
+    /// that keeps several cores busy uses more than 100 percent
+    pub cpu_percent: f64,
+    /// The sum of the resident memory of the processes, in bytes
+    pub memory_bytes: u64,
+    /// The number of processes in the tree
+    pub process_count: usize,
+}
+impl_lua_conversion_dynamic!(PaneResourceUsage);
+
+struct PaneSampler {
+    /// The process that was spawned into the pane
+    pid: u32,
+    /// The CPU time of each process as of the previous sample
+    cpu_times: HashMap<u32, Duration>,
+    sampled_at: Option<Instant>,
+    usage: Option<PaneResourceUsage>,
+}
+
+impl PaneSampler {
+    fn new(pid: u32) -> Self {
+        Self {
+            pid,
+            cpu_times: HashMap::new(),
+            sampled_at: None,
+            usage: None,
+        }
+    }
+
+    /// Records a sample of the CPU time of each process in the tree
+    /// and their total memory usage.  The CPU usage is computed from
+    /// the CPU time that the processes consumed since the previous
+    /// sample, so no usage is available until a second sample has
+    /// been recorded.
+    fn record(&mut self, cpu_times: HashMap<u32, Duration>, memory_bytes: u64, now: Instant) {
+        if let Some(sampled_at) = self.sampled_at {
+            let elapsed = now.saturating_duration_since(sampled_at);
+            if !elapsed.is_zero() {
+                // Processes that started since the previous sample
+                // count all of their CPU time
+                let consumed: Duration = cpu_times
+                    .iter()
+                    .map(|(pid, time)| {
+                        time.saturating_sub(self.cpu_times.get(pid).copied().unwrap_or_default())
+                    })
+                    .sum();
+                self.usage.replace(PaneResourceUsage {
+                    cpu_percent: consumed.as_secs_f64() * 100. / elapsed.as_secs_f64(),
+                    memory_bytes,
+                    process_count: cpu_times.len(),
+                });
+            }
+        }
+        self.cpu_times = cpu_times;
+        self.sampled_at.replace(now);
+    }
+
+    fn reset(&mut self) {
+        self.cpu_times.clear();
+        self.sampled_at.take();
+        self.usage.take();
+    }
+}
+
+fn sample_process_tree(pid: u32) -> (HashMap<u32, Duration>, u64) {
+    let mut cpu_times = HashMap::new();
+    let mut memory_bytes = 0;
+    if let Some(root) = LocalProcessInfo::with_root_pid(pid) {
+        for pid in root.flatten_to_pids() {
+            if let Some(usage) = LocalProcessInfo::resource_usage(pid) {
+                cpu_times.insert(pid, usage.cpu_time);
+                memory_bytes += usage.resident_bytes;
+            }
+        }
+    }
+    (cpu_times, memory_bytes)
+}
+
+fn sampler_thread() {
+    loop {
+        let interval = configuration().pane_resource_usage_sample_interval;
+        if interval == 0 {
+            for sampler in PANES.lock().unwrap().values_mut() {
+                sampler.reset();
+            }
+            SAMPLER_RUNNING.store(false, Ordering::SeqCst);
+            // Sampling may have been re-enabled after the configuration
+            // was checked, in which case no other thread was started
+            if configuration().pane_resource_usage_sample_interval == 0
+                || SAMPLER_RUNNING.swap(true, Ordering::SeqCst)
+            {
+                return;
+            }
+            continue;
+        }
+
+        // Don't hold the lock while examining the processes
+        let pids: Vec<(PaneId, u32)> = PANES
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(pane_id, sampler)| (*pane_id, sampler.pid))
+            .collect();
+        for (pane_id, pid) in pids {
+            let (cpu_times, memory_bytes) = sample_process_tree(pid);
+            let now = Instant::now();
+            if let Some(sampler) = PANES.lock().unwrap().get_mut(&pane_id) {
+                if cpu_times.is_empty() {
+                    // The process has exited
+                    sampler.reset();
+                } else {
+                    sampler.record(cpu_times, memory_bytes, now);
+                }
+            }
+        }
+
+        std::thread::sleep(Duration::from_millis(interval).max(MIN_INTERVAL));
+    }
+}
+
+/// Starts the sampler thread if sampling is enabled and it isn't
+/// already running.  The thread stops once sampling is disabled, and
+/// is started again by the next pane that is registered or queried.
+fn start_sampler_if_enabled() {
+    if configuration().pane_resource_usage_sample_interval == 0
+        || SAMPLER_RUNNING.swap(true, Ordering::SeqCst)
+    {
+        return;
+    }
+    if let Err(err) = std::thread::Builder::new()
+        .name("pane-resource-usage".to_string())
+        .spawn(sampler_thread)
+    {
+        log::error!("failed to start pane resource usage sampler: {:#}", err);
+        SAMPLER_RUNNING.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Starts sampling the process tree rooted at `pid` for the pane
+pub(crate) fn register_pane(pane_id: PaneId, pid: u32) {
+    PANES.lock().unwrap().insert(pane_id, PaneSampler::new(pid));
+    start_sampler_if_enabled();
+}
+
+pub(crate) fn unregister_pane(pane_id: PaneId) {
+    PANES.lock().unwrap().remove(&pane_id);
+}
+
+/// Returns the most recent sample of the resources used by the
+/// processes in the pane.
+/// Returns None if sampling is disabled, if the processes of the pane
+/// are not running in this process, for example, because it is in
+/// a multiplexer domain, or if they have not yet been sampled.
+pub fn get_resource_usage(pane_id: PaneId) -> Option<PaneResourceUsage> {
+    start_sampler_if_enabled();
+    PANES
+        .lock()
+        .unwrap()
+        .get(&pane_id)
+        .and_then(|sampler| sampler.usage)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cpu_percent() {
+        let start = Instant::now();
+        let secs = Duration::from_secs;
+        let mut sampler = PaneSampler::new(1);
+
+        sampler.record(HashMap::from([(1, secs(10))]), 100, start);
+        assert_eq!(sampler.usage, None);
+
+        // Process 2 started since the first sample
+        sampler.record(
+            HashMap::from([(1, secs(11)), (2, secs(1))]),
+            300,
+            start + secs(4),
+        );
+        assert_eq!(
+            sampler.usage,
+            Some(PaneResourceUsage {
+                cpu_percent: 50.,
+                memory_bytes: 300,
+                process_count: 2,
+            })
+        );
+
+        // Process 1 exited, and process 2 kept two cores busy
+        sampler.record(HashMap::from([(2, secs(5))]), 200, start + secs(6));
+        assert_eq!(
+            sampler.usage,
+            Some(PaneResourceUsage {
+                cpu_percent: 200.,
+                memory_bytes: 200,
+                process_count: 1,
+            })
+        );
+
+        sampler.reset();
+        assert_eq!(sampler.usage, None);
+    }
+}
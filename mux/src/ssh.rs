@@ -1,15 +1,16 @@
 use crate::connui::ConnectionUI;
-use crate::domain::{alloc_domain_id, Domain, DomainId, DomainState};
+use crate::domain::{alloc_domain_id, DirEntry, Domain, DomainId, DomainState, FileChunk};
 use crate::localpane::LocalPane;
 use crate::pane::{alloc_pane_id, Pane, PaneId};
-use crate::Mux;
+use crate::{Mux, MuxNotification};
 use anyhow::{anyhow, bail, Context};
 use async_trait::async_trait;
 use config::{Shell, SshBackend, SshDomain};
 use filedescriptor::{poll, pollfd, socketpair, AsRawSocketDescriptor, FileDescriptor, POLLIN};
+use luahelper::impl_lua_conversion_dynamic;
 use portable_pty::cmdbuilder::CommandBuilder;
 use portable_pty::{ChildKiller, ExitStatus, MasterPty, PtySize};
-use smol::channel::{bounded, Receiver as AsyncReceiver};
+use smol::channel::{bounded, Receiver as AsyncReceiver, Sender as AsyncSender};
 use std::cell::RefCell;
 use std::collections::{HashMap, VecDeque};
 use std::io::{BufWriter, Read, Write};
@@ -23,14 +24,30 @@ use termwiz::lineedit::*;
 use termwiz::render::terminfo::TerminfoRenderer;
 use termwiz::surface::Change;
 use termwiz::terminal::{ScreenSize, Terminal, TerminalWaker};
-use wezterm_ssh::{ConfigMap, Session, SessionEvent, SshChildProcess, SshPty};
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+use wezterm_ssh::{
+    AuthenticationPrompt, ConfigMap, File, HostVerificationEvent, OpenFileType, OpenOptions,
+    Session, SessionEvent, Sftp, SshChildProcess, SshPty, WriteMode,
+};
 use wezterm_term::TerminalSize;
 
+/// A LineEditorHost that obscures the input when `echo` is false,
+/// for use when prompting for passwords
 #[derive(Default)]
-struct PasswordPromptHost {
+pub struct PasswordPromptHost {
     history: BasicHistory,
     echo: bool,
 }
+
+impl PasswordPromptHost {
+    pub fn new(echo: bool) -> Self {
+        Self {
+            history: BasicHistory::default(),
+            echo,
+        }
+    }
+}
+
 impl LineEditorHost for PasswordPromptHost {
     fn history(&mut self) -> &mut dyn History {
         &mut self.history
@@ -124,6 +141,95 @@ pub fn ssh_connect_with_ui(
     })
 }
 
+/// A prompt that the GUI shows in an overlay over the pane, in place
+/// of the text prompt in the pane, while an ssh domain is connecting
+#[derive(Debug, Clone)]
+pub enum SshPrompt {
+    /// Asks whether to trust a host key that is not yet known
+    HostVerify {
+        message: String,
+        reply: AsyncSender<bool>,
+    },
+    /// Asks for the responses to keyboard-interactive authentication
+    Authenticate {
+        username: String,
+        instructions: String,
+        prompts: Vec<AuthenticationPrompt>,
+        /// Is sent None if the user cancelled the prompt
+        reply: AsyncSender<Option<Vec<String>>>,
+    },
+}
+
+/// Asks the GUI to show the prompt over the pane, and waits for the answer.
+/// Returns None if there is no GUI that can show the prompt, which is
+/// detected by every subscriber having dropped the reply sender, in which
+/// case the caller should prompt in the pane instead.
+fn prompt_via_gui<T: Send + 'static>(
+    pane_id: PaneId,
+    domain_name: &str,
+    make_prompt: impl FnOnce(AsyncSender<T>) -> SshPrompt,
+) -> Option<T> {
+    let (reply, answer) = bounded(1);
+    let prompt = make_prompt(reply);
+    let domain_name = domain_name.to_string();
+    promise::spawn::spawn_into_main_thread(async move {
+        if let Some(mux) = Mux::get() {
+            mux.notify(MuxNotification::SshPrompt {
+                pane_id,
+                domain_name,
+                prompt,
+            });
+        }
+    })
+    .detach();
+    smol::block_on(answer.recv()).ok()
+}
+
+/// The information passed to the `ssh-host-verify` event
+#[derive(Debug, Clone, FromDynamic, ToDynamic)]
+struct HostVerifyInfo {
+    domain_name: String,
+    hostname: String,
+    port: u16,
+    fingerprint: String,
+    message: String,
+}
+impl_lua_conversion_dynamic!(HostVerifyInfo);
+
+/// Emits the `ssh-host-verify` event so that the configuration can
+/// decide whether to trust the host without prompting the user.
+/// Returns None if the user should be prompted.
+fn host_verify_hook(domain_name: &str, verify: &HostVerificationEvent) -> Option<bool> {
+    let info = HostVerifyInfo {
+        domain_name: domain_name.to_string(),
+        hostname: verify.hostname.clone(),
+        port: verify.port,
+        fingerprint: verify.fingerprint.clone(),
+        message: verify.message.clone(),
+    };
+    let result = smol::block_on(promise::spawn::spawn_into_main_thread(async move {
+        config::run_immediate_with_lua_config(|lua| {
+            let lua = match lua {
+                Some(lua) => lua,
+                None => return Ok(None),
+            };
+            let v = config::lua::emit_sync_callback(&*lua, ("ssh-host-verify".to_string(), info))?;
+            match v {
+                mlua::Value::Nil => Ok(None),
+                mlua::Value::Boolean(v) => Ok(Some(v)),
+                _ => bail!("ssh-host-verify: expected a boolean or nil return value"),
+            }
+        })
+    }));
+    match result {
+        Ok(trust) => trust,
+        Err(err) => {
+            log::error!("Error while running ssh-host-verify hook: {:#}", err);
+            None
+        }
+    }
+}
+
 /// Represents a connection to remote host via ssh.
 /// The domain is created with the ssh config prior to making the
 /// connection.  The connection is established by the first spawn()
@@ -132,8 +238,17 @@ pub fn ssh_connect_with_ui(
 /// pane, we play some tricks with wrapped versions of the pty, child
 /// and the reader and writer instances so that we can inject the
 /// interactive setup.  The bulk of that is driven by `connect_ssh_session`.
+/// An upload that is in progress via `write_file_chunk`
+struct Upload {
+    file: File,
+    /// The offset at which the next chunk is expected
+    next_offset: u64,
+}
+
 pub struct RemoteSshDomain {
     session: RefCell<Option<Session>>,
+    /// Uploads that are in progress, keyed by remote path
+    uploads: RefCell<HashMap<String, Upload>>,
     dom: SshDomain,
     id: DomainId,
     name: String,
@@ -188,6 +303,7 @@ impl RemoteSshDomain {
             id,
             name: dom.name.clone(),
             session: RefCell::new(None),
+            uploads: RefCell::new(HashMap::new()),
             dom: dom.clone(),
         })
     }
@@ -196,6 +312,32 @@ impl RemoteSshDomain {
         ssh_domain_to_ssh_config(&self.dom)
     }
 
+    fn sftp(&self) -> anyhow::Result<Sftp> {
+        let session = self.session.borrow();
+        let session = session
+            .as_ref()
+            .ok_or_else(|| anyhow!("ssh domain {} is not connected", self.name))?;
+        Ok(session.sftp())
+    }
+
+    /// Resolves a path that is relative to the current working directory
+    /// of the specified pane into a path on the remote host.
+    /// Paths that cannot be resolved are left relative, which sftp will
+    /// treat as relative to the home directory of the remote user.
+    fn resolve_remote_path(&self, pane_id: PaneId, path: &str) -> String {
+        if path.starts_with('/') {
+            return path.to_string();
+        }
+        let cwd = Mux::get()
+            .and_then(|mux| mux.get_pane(pane_id))
+            .and_then(|pane| pane.get_current_working_dir())
+            .map(|url| url.path().to_string());
+        match cwd {
+            Some(cwd) => format!("{}/{}", cwd.trim_end_matches('/'), path),
+            None => path.to_string(),
+        }
+    }
+
     fn build_command(
         &self,
         pane_id: PaneId,
@@ -279,6 +421,8 @@ impl RemoteSshDomain {
 
 /// Carry out the authentication process and create the initial pty.
 fn connect_ssh_session(
+    pane_id: PaneId,
+    domain_name: String,
     session: Session,
     events: smol::channel::Receiver<SessionEvent>,
     mut stdin_read: FileDescriptor,
@@ -458,10 +602,27 @@ fn connect_ssh_session(
                 }
             }
             SessionEvent::HostVerify(verify) => {
+                if let Some(ok) = host_verify_hook(&domain_name, &verify) {
+                    log::info!(
+                        "ssh-host-verify hook {} {}:{}",
+                        if ok { "trusted" } else { "rejected" },
+                        verify.hostname,
+                        verify.port
+                    );
+                    smol::block_on(verify.answer(ok)).context("send verify response")?;
+                    continue;
+                }
+                let message = verify.message.clone();
+                if let Some(ok) = prompt_via_gui(pane_id, &domain_name, |reply| {
+                    SshPrompt::HostVerify { message, reply }
+                }) {
+                    smol::block_on(verify.answer(ok)).context("send verify response")?;
+                    continue;
+                }
+
                 shim.output_line(&verify.message)?;
                 let mut editor = LineEditor::new(&mut shim);
-                let mut host = PasswordPromptHost::default();
-                host.echo = true;
+                let mut host = PasswordPromptHost::new(true);
                 editor.set_prompt("Enter [y/n]> ");
                 let ok = if let Some(line) = editor.read_line(&mut host)? {
                     match line.as_ref() {
@@ -474,6 +635,23 @@ fn connect_ssh_session(
                 smol::block_on(verify.answer(ok)).context("send verify response")?;
             }
             SessionEvent::Authenticate(auth) => {
+                let username = auth.username.clone();
+                let instructions = auth.instructions.clone();
+                let prompts = auth.prompts.clone();
+                match prompt_via_gui(pane_id, &domain_name, |reply| SshPrompt::Authenticate {
+                    username,
+                    instructions,
+                    prompts,
+                    reply,
+                }) {
+                    Some(Some(answers)) => {
+                        smol::block_on(auth.answer(answers))?;
+                        continue;
+                    }
+                    Some(None) => anyhow::bail!("Authentication was cancelled"),
+                    None => {}
+                }
+
                 if !auth.username.is_empty() {
                     shim.output_line(&format!("Authentication for {}", auth.username))?;
                 }
@@ -488,9 +666,8 @@ fn connect_ssh_session(
                         shim.output_line(line)?;
                     }
                     let mut editor = LineEditor::new(&mut shim);
-                    let mut host = PasswordPromptHost::default();
+                    let mut host = PasswordPromptHost::new(prompt.echo);
                     editor.set_prompt(editor_prompt);
-                    host.echo = prompt.echo;
                     if let Some(line) = editor.read_line(&mut host)? {
                         answers.push(line);
                     } else {
@@ -642,8 +819,11 @@ impl Domain for RemoteSshDomain {
             // to perform the blocking (from its perspective) terminal
             // UI to carry out any authentication.
             let mut stdout_write = BufWriter::new(stdout_write);
+            let domain_name = self.name.clone();
             std::thread::spawn(move || {
                 if let Err(err) = connect_ssh_session(
+                    pane_id,
+                    domain_name,
                     session,
                     events,
                     stdin_read,
@@ -689,6 +869,116 @@ impl Domain for RemoteSshDomain {
         Ok(pane)
     }
 
+    async fn read_file_chunk(
+        &self,
+        pane_id: PaneId,
+        path: &str,
+        offset: u64,
+        max_bytes: usize,
+    ) -> anyhow::Result<FileChunk> {
+        use smol::io::AsyncReadExt;
+
+        let path = self.resolve_remote_path(pane_id, path);
+        let sftp = self.sftp()?;
+        let file = sftp
+            .open(path.as_str())
+            .await
+            .with_context(|| format!("opening {} for read", path))?;
+        let total_size = file.metadata().await.ok().and_then(|m| m.size);
+        file.seek(offset).await?;
+        let mut data = vec![];
+        file.take(max_bytes as u64).read_to_end(&mut data).await?;
+        Ok(FileChunk { data, total_size })
+    }
+
+    async fn read_dir(&self, path: &str) -> anyhow::Result<Vec<DirEntry>> {
+        let sftp = self.sftp()?;
+        let listing = sftp
+            .read_dir(path)
+            .await
+            .with_context(|| format!("reading directory {}", path))?;
+        let mut entries = vec![];
+        for (entry_path, metadata) in listing {
+            let name = entry_path.as_str().rsplit('/').next().unwrap_or("");
+            if name.is_empty() || name == "." || name == ".." {
+                continue;
+            }
+            let is_dir = if metadata.is_symlink() {
+                sftp.metadata(entry_path.as_str())
+                    .await
+                    .map_or(false, |m| m.is_dir())
+            } else {
+                metadata.is_dir()
+            };
+            entries.push(DirEntry {
+                name: name.to_string(),
+                is_dir,
+                size: metadata.size,
+            });
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    async fn write_file_chunk(
+        &self,
+        pane_id: PaneId,
+        path: &str,
+        offset: u64,
+        data: Vec<u8>,
+        total_size: Option<u64>,
+    ) -> anyhow::Result<()> {
+        use smol::io::AsyncWriteExt;
+
+        let path = self.resolve_remote_path(pane_id, path);
+
+        // Continue with the handle left open by the previous chunk of
+        // this transfer, if any.  A chunk at offset 0 starts a new transfer.
+        let upload = self.uploads.borrow_mut().remove(&path);
+        let mut file = match upload {
+            Some(upload) if offset != 0 && upload.next_offset == offset => upload.file,
+            upload => {
+                if let Some(mut upload) = upload {
+                    upload.file.close().await.ok();
+                }
+                let sftp = self.sftp()?;
+                let file = if offset == 0 {
+                    sftp.create(path.as_str()).await
+                } else {
+                    sftp.open_with_mode(
+                        path.as_str(),
+                        OpenOptions {
+                            read: false,
+                            write: Some(WriteMode::Write),
+                            mode: 0o644,
+                            ty: OpenFileType::File,
+                        },
+                    )
+                    .await
+                }
+                .with_context(|| format!("opening {} for write", path))?;
+                file.seek(offset).await?;
+                file
+            }
+        };
+        file.write_all(&data).await?;
+
+        let next_offset = offset + data.len() as u64;
+        match total_size {
+            // Keep the handle for the next chunk until the
+            // transfer is complete
+            Some(total_size) if !data.is_empty() && next_offset < total_size => {
+                self.uploads
+                    .borrow_mut()
+                    .insert(path, Upload { file, next_offset });
+            }
+            _ => {
+                file.close().await?;
+            }
+        }
+        Ok(())
+    }
+
     fn domain_id(&self) -> DomainId {
         self.id
     }
@@ -1,15 +1,18 @@
 use crate::domain::DomainId;
 use crate::pane::*;
-use crate::{Mux, WindowId};
+use crate::resourceusage::{get_resource_usage, PaneResourceUsage};
+use crate::{Mux, MuxNotification, WindowId};
 use bintree::PathBranch;
 use config::configuration;
 use config::keyassignment::PaneDirection;
 use rangeset::range_intersection;
 use serde::{Deserialize, Serialize};
-use std::cell::{RefCell, RefMut};
+use std::cell::{Cell, RefCell, RefMut};
 use std::convert::TryInto;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 use url::Url;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
 use wezterm_term::TerminalSize;
 
 pub type Tree = bintree::Tree<Rc<dyn Pane>, SplitDirectionAndSize>;
@@ -25,7 +28,11 @@ pub struct Tab {
     size: RefCell<TerminalSize>,
     active: RefCell<usize>,
     zoomed: RefCell<Option<Rc<dyn Pane>>>,
+    /// true if the panes are stacked, with only the active pane expanded
+    stacked: Cell<bool>,
     title: RefCell<String>,
+    /// Incremented to cancel an in-progress layout animation
+    layout_animation: Cell<usize>,
 }
 
 #[derive(Clone)]
@@ -36,6 +43,9 @@ pub struct PositionedPane {
     pub is_active: bool,
     /// true if this pane is zoomed
     pub is_zoomed: bool,
+    /// true if the tab is stacked and this pane is collapsed to a
+    /// single row, in which its title is shown
+    pub is_collapsed: bool,
     /// The offset from the top left corner of the containing tab to the top
     /// left corner of this pane, in cells.
     pub left: usize,
@@ -57,6 +67,7 @@ impl std::fmt::Debug for PositionedPane {
         fmt.debug_struct("PositionedPane")
             .field("index", &self.index)
             .field("is_active", &self.is_active)
+            .field("is_collapsed", &self.is_collapsed)
             .field("left", &self.left)
             .field("top", &self.top)
             .field("width", &self.width)
@@ -179,6 +190,23 @@ pub struct PositionedSplit {
     pub size: usize,
 }
 
+/// Describes the relative sizes of the splits in a tab.
+/// The shape follows the split tree of the tab: `first` and `second`
+/// describe the splits nested within the left/top and right/bottom
+/// halves of this split respectively.
+#[derive(Debug, Clone, Default, PartialEq, FromDynamic, ToDynamic)]
+pub struct SplitLayoutSpec {
+    /// The proportion of the space that is given to the first half of
+    /// the split, in the range 0.0 to 1.0.  If omitted, the split keeps
+    /// its current size.
+    #[dynamic(default)]
+    pub ratio: Option<f32>,
+    #[dynamic(default)]
+    pub first: Option<Box<SplitLayoutSpec>>,
+    #[dynamic(default)]
+    pub second: Option<Box<SplitLayoutSpec>>,
+}
+
 fn is_pane(pane: &Rc<dyn Pane>, other: &Option<&Rc<dyn Pane>>) -> bool {
     if let Some(other) = other {
         other.pane_id() == pane.pane_id()
@@ -226,6 +254,7 @@ fn pane_tree(
                 },
                 working_dir: working_dir.map(Into::into),
                 workspace: workspace.to_string(),
+                resource_usage: get_resource_usage(pane.pane_id()),
             })
         }
     }
@@ -444,6 +473,199 @@ fn apply_sizes_from_splits(tree: &Tree, size: &TerminalSize) {
     }
 }
 
+/// Returns the proportion of the space within a split that
+/// is occupied by its first half
+fn split_ratio(node: &SplitDirectionAndSize) -> f32 {
+    let (first, second) = match node.direction {
+        SplitDirection::Horizontal => (node.first.cols, node.second.cols),
+        SplitDirection::Vertical => (node.first.rows, node.second.rows),
+    };
+    if first + second == 0 {
+        0.5
+    } else {
+        first as f32 / (first + second) as f32
+    }
+}
+
+fn collect_split_ratios(tree: &Tree, ratios: &mut Vec<f32>) {
+    if let Tree::Node {
+        left,
+        right,
+        data: Some(data),
+    } = tree
+    {
+        ratios.push(split_ratio(data));
+        collect_split_ratios(left, ratios);
+        collect_split_ratios(right, ratios);
+    }
+}
+
+/// Lays out the splits in `tree` to fill `size`, taking the ratio of
+/// each split, in topological order, from `ratios`.  Splits for which
+/// there is no ratio keep their current proportions.
+/// Each half of a split is kept large enough to hold the panes within it.
+/// `prior` is the size that was previously allocated to `tree`; panes
+/// are only resized if their size has changed.
+fn apply_split_ratios(
+    tree: &mut Tree,
+    size: &TerminalSize,
+    prior: &TerminalSize,
+    cell_dimensions: &TerminalSize,
+    ratios: &mut dyn Iterator<Item = f32>,
+    resize_panes: bool,
+) {
+    match tree {
+        Tree::Empty | Tree::Node { data: None, .. } => {}
+        Tree::Leaf(pane) => {
+            if resize_panes && size != prior {
+                pane.resize(*size).ok();
+            }
+        }
+        Tree::Node {
+            left,
+            right,
+            data: Some(data),
+        } => {
+            let ratio = ratios
+                .next()
+                .unwrap_or_else(|| split_ratio(data))
+                .max(0.)
+                .min(1.);
+            let (min_first_x, min_first_y) = compute_min_size(left);
+            let (min_second_x, min_second_y) = compute_min_size(right);
+            let prior_first = data.first;
+            let prior_second = data.second;
+
+            let mut first = *size;
+            let mut second = *size;
+            match data.direction {
+                SplitDirection::Horizontal => {
+                    let avail = size.cols.saturating_sub(1);
+                    first.cols = ((avail as f32 * ratio).round() as usize)
+                        .min(avail.saturating_sub(min_second_x))
+                        .max(min_first_x);
+                    second.cols = avail.saturating_sub(first.cols);
+                }
+                SplitDirection::Vertical => {
+                    let avail = size.rows.saturating_sub(1);
+                    first.rows = ((avail as f32 * ratio).round() as usize)
+                        .min(avail.saturating_sub(min_second_y))
+                        .max(min_first_y);
+                    second.rows = avail.saturating_sub(first.rows);
+                }
+            }
+            first.pixel_width = first.cols * cell_dimensions.pixel_width;
+            first.pixel_height = first.rows * cell_dimensions.pixel_height;
+            second.pixel_width = second.cols * cell_dimensions.pixel_width;
+            second.pixel_height = second.rows * cell_dimensions.pixel_height;
+            data.first = first;
+            data.second = second;
+
+            apply_split_ratios(
+                left,
+                &first,
+                &prior_first,
+                cell_dimensions,
+                ratios,
+                resize_panes,
+            );
+            apply_split_ratios(
+                right,
+                &second,
+                &prior_second,
+                cell_dimensions,
+                ratios,
+                resize_panes,
+            );
+        }
+    }
+}
+
+/// Computes the ratios that give as much space as possible to the pane
+/// with index `pane_index`, leaving splits that don't contain that pane
+/// unchanged.  Returns true if `tree` contains the pane.
+fn ratios_favoring_pane(
+    tree: &Tree,
+    pane_index: usize,
+    leaf_index: &mut usize,
+    ratios: &mut Vec<f32>,
+) -> bool {
+    match tree {
+        Tree::Empty | Tree::Node { data: None, .. } => false,
+        Tree::Leaf(_) => {
+            let found = *leaf_index == pane_index;
+            *leaf_index += 1;
+            found
+        }
+        Tree::Node {
+            left,
+            right,
+            data: Some(data),
+        } => {
+            let slot = ratios.len();
+            ratios.push(split_ratio(data));
+            if ratios_favoring_pane(left, pane_index, leaf_index, ratios) {
+                ratios[slot] = 1.0;
+                ratios_favoring_pane(right, pane_index, leaf_index, ratios);
+                true
+            } else if ratios_favoring_pane(right, pane_index, leaf_index, ratios) {
+                ratios[slot] = 0.0;
+                true
+            } else {
+                false
+            }
+        }
+    }
+}
+
+fn ratios_from_spec(
+    tree: &Tree,
+    spec: Option<&SplitLayoutSpec>,
+    ratios: &mut Vec<f32>,
+) -> anyhow::Result<()> {
+    match tree {
+        Tree::Empty | Tree::Node { data: None, .. } => Ok(()),
+        Tree::Leaf(pane) => match spec {
+            Some(spec) if spec != &SplitLayoutSpec::default() => anyhow::bail!(
+                "layout describes a split where the tab has pane {}",
+                pane.pane_id()
+            ),
+            _ => Ok(()),
+        },
+        Tree::Node {
+            left,
+            right,
+            data: Some(data),
+        } => {
+            let ratio = match spec.and_then(|spec| spec.ratio) {
+                Some(ratio) if !(0.0..=1.0).contains(&ratio) => {
+                    anyhow::bail!("split ratio {} is not in the range 0.0 to 1.0", ratio)
+                }
+                Some(ratio) => ratio,
+                None => split_ratio(data),
+            };
+            ratios.push(ratio);
+            ratios_from_spec(left, spec.and_then(|spec| spec.first.as_deref()), ratios)?;
+            ratios_from_spec(right, spec.and_then(|spec| spec.second.as_deref()), ratios)
+        }
+    }
+}
+
+fn spec_from_tree(tree: &Tree) -> Option<Box<SplitLayoutSpec>> {
+    match tree {
+        Tree::Node {
+            left,
+            right,
+            data: Some(data),
+        } => Some(Box::new(SplitLayoutSpec {
+            ratio: Some(split_ratio(data)),
+            first: spec_from_tree(left),
+            second: spec_from_tree(right),
+        })),
+        _ => None,
+    }
+}
+
 fn cell_dimensions(size: &TerminalSize) -> TerminalSize {
     TerminalSize {
         rows: 1,
@@ -462,7 +684,9 @@ impl Tab {
             size: RefCell::new(*size),
             active: RefCell::new(0),
             zoomed: RefCell::new(None),
+            stacked: Cell::new(false),
             title: RefCell::new(String::new()),
+            layout_animation: Cell::new(0),
         }
     }
 
@@ -611,8 +835,11 @@ impl Tab {
                 pane.set_zoomed(false);
             }
 
-            let mut root = self.pane.borrow_mut();
-            apply_sizes_from_splits(root.as_mut().unwrap(), &size);
+            {
+                let mut root = self.pane.borrow_mut();
+                apply_sizes_from_splits(root.as_mut().unwrap(), &size);
+            }
+            self.apply_stacked_size();
         } else {
             // We weren't zoomed, but now we want to zoom.
             // Locate the active pane
@@ -624,6 +851,62 @@ impl Tab {
         }
     }
 
+    pub fn is_stacked(&self) -> bool {
+        self.stacked.get()
+    }
+
+    /// Switches between the regular split layout and the stacked
+    /// layout, in which the panes are arranged from top to bottom and
+    /// only the active pane is expanded; the others are collapsed to
+    /// a single row that shows their title.
+    /// The split layout is retained while the panes are stacked.
+    pub fn set_stacked(&self, stacked: bool) {
+        if self.stacked.get() == stacked {
+            return;
+        }
+        self.cancel_layout_animation();
+        self.stacked.set(stacked);
+        if stacked {
+            self.apply_stacked_size();
+        } else if self.zoomed.borrow().is_none() {
+            let size = *self.size.borrow();
+            let mut root = self.pane.borrow_mut();
+            apply_sizes_from_splits(root.as_mut().unwrap(), &size);
+        }
+    }
+
+    pub fn toggle_stacked(&self) {
+        self.set_stacked(!self.is_stacked());
+    }
+
+    /// Returns the size of the active pane when the panes are stacked:
+    /// the size of the tab, less a row for each of the other panes
+    fn stacked_pane_size(&self, num_panes: usize) -> TerminalSize {
+        let size = *self.size.borrow();
+        let dims = cell_dimensions(&size);
+        let rows = size.rows.saturating_sub(num_panes.saturating_sub(1)).max(1);
+        TerminalSize {
+            rows,
+            cols: size.cols,
+            pixel_width: size.pixel_width,
+            pixel_height: rows * dims.pixel_height,
+            dpi: size.dpi,
+        }
+    }
+
+    /// Resizes the active pane to fill the space that is left by the
+    /// collapsed panes, if the panes are stacked.
+    /// The collapsed panes retain their size in the split layout.
+    fn apply_stacked_size(&self) {
+        if !self.stacked.get() || self.zoomed.borrow().is_some() {
+            return;
+        }
+        let size = self.stacked_pane_size(self.count_panes());
+        if let Some(pane) = self.get_active_pane() {
+            pane.resize(size).ok();
+        }
+    }
+
     pub fn contains_pane(&self, pane: PaneId) -> bool {
         fn contains(tree: &Tree, pane: PaneId) -> bool {
             match tree {
@@ -680,6 +963,8 @@ impl Tab {
                 }
             }
         }
+        drop(root);
+        self.apply_stacked_size();
     }
 
     pub fn rotate_clockwise(&self) {
@@ -712,6 +997,8 @@ impl Tab {
                 }
             }
         }
+        drop(root);
+        self.apply_stacked_size();
     }
 
     fn iter_panes_impl(&self, respect_zoom_state: bool) -> Vec<PositionedPane> {
@@ -724,6 +1011,7 @@ impl Tab {
                     index: 0,
                     is_active: true,
                     is_zoomed: true,
+                    is_collapsed: false,
                     left: 0,
                     top: 0,
                     width: size.cols.into(),
@@ -769,6 +1057,7 @@ impl Tab {
                     index,
                     is_active: index == active_idx,
                     is_zoomed: false,
+                    is_collapsed: false,
                     left,
                     top,
                     width: dims.cols as _,
@@ -788,12 +1077,36 @@ impl Tab {
             }
         }
 
+        if self.stacked.get() {
+            // Arrange the panes from top to bottom, giving the
+            // active pane all of the rows that the others don't need
+            let size = *self.size.borrow();
+            let expanded = self.stacked_pane_size(panes.len());
+            let cell_height = cell_dimensions(&size).pixel_height;
+            let mut top = 0;
+            for pos in &mut panes {
+                pos.left = 0;
+                pos.top = top;
+                pos.width = size.cols;
+                pos.pixel_width = size.pixel_width;
+                pos.is_collapsed = !pos.is_active;
+                if pos.is_active {
+                    pos.height = expanded.rows;
+                    pos.pixel_height = expanded.pixel_height;
+                } else {
+                    pos.height = 1;
+                    pos.pixel_height = cell_height;
+                }
+                top += pos.height;
+            }
+        }
+
         panes
     }
 
     pub fn iter_splits(&self) -> Vec<PositionedSplit> {
         let mut dividers = vec![];
-        if self.zoomed.borrow().is_some() {
+        if self.zoomed.borrow().is_some() || self.stacked.get() {
             return dividers;
         }
 
@@ -910,6 +1223,7 @@ impl Tab {
 
         // And finally restore the zoom, if appropriate
         self.set_zoomed(was_zoomed);
+        self.apply_stacked_size();
     }
 
     fn apply_pane_size(&self, pane_size: TerminalSize, cursor: &mut Cursor) {
@@ -953,7 +1267,7 @@ impl Tab {
     /// the GUI to use stale size information for the window it spawns
     /// to attach this tab.
     pub fn rebuild_splits_sizes_from_contained_panes(&self) {
-        if self.zoomed.borrow().is_some() {
+        if self.zoomed.borrow().is_some() || self.stacked.get() {
             return;
         }
 
@@ -1002,7 +1316,8 @@ impl Tab {
     /// The adjusted size is propogated downwards to contained children and
     /// their panes are resized accordingly.
     pub fn resize_split_by(&self, split_index: usize, delta: isize) {
-        if self.zoomed.borrow().is_some() {
+        self.cancel_layout_animation();
+        if self.zoomed.borrow().is_some() || self.stacked.get() {
             return;
         }
 
@@ -1119,7 +1434,7 @@ impl Tab {
     /// Adjusts the size of the active pane in the specified direction
     /// by the specified amount.
     pub fn adjust_pane_size(&self, direction: PaneDirection, amount: usize) {
-        if self.zoomed.borrow().is_some() {
+        if self.zoomed.borrow().is_some() || self.stacked.get() {
             return;
         }
         let active_index = *self.active.borrow();
@@ -1181,6 +1496,225 @@ impl Tab {
         }
     }
 
+    /// Returns the proportion of the space that is given to the first
+    /// half of each split, in the topological order of the splits
+    /// returned by iter_splits().
+    pub fn split_ratios(&self) -> Vec<f32> {
+        let mut ratios = vec![];
+        if let Some(root) = self.pane.borrow().as_ref() {
+            collect_split_ratios(root, &mut ratios);
+        }
+        ratios
+    }
+
+    /// Re-lays out the splits using the provided ratios, which are in
+    /// the same order as those returned by split_ratios(), and resizes
+    /// the panes to match.
+    pub fn set_split_ratios(&self, ratios: &[f32]) {
+        let was_zoomed = self.zoomed.borrow().is_some();
+        self.set_zoomed(false);
+        self.apply_split_ratios(ratios, true);
+        self.set_zoomed(was_zoomed);
+        self.apply_stacked_size();
+    }
+
+    /// Adjusts the geometry of the splits to match `ratios`.
+    /// The panes are only resized if `resize_panes` is true; the
+    /// frames of an animation skip that so that the PTYs are resized
+    /// just once, by resize_panes_to_splits() at the end.
+    fn apply_split_ratios(&self, ratios: &[f32], resize_panes: bool) {
+        let size = *self.size.borrow();
+        let dims = self.cell_dimensions();
+        let mut root = self.pane.borrow_mut();
+        if let Some(root) = root.as_mut() {
+            apply_split_ratios(
+                root,
+                &size,
+                &size,
+                &dims,
+                &mut ratios.iter().copied(),
+                resize_panes,
+            );
+        }
+    }
+
+    /// Resizes each pane to match the current geometry of the splits
+    fn resize_panes_to_splits(&self) {
+        if self.zoomed.borrow().is_some() {
+            return;
+        }
+        let size = *self.size.borrow();
+        if let Some(root) = self.pane.borrow().as_ref() {
+            apply_sizes_from_splits(root, &size);
+        }
+        self.apply_stacked_size();
+    }
+
+    /// Returns a SplitLayoutSpec that describes the current layout
+    pub fn get_layout(&self) -> SplitLayoutSpec {
+        self.pane
+            .borrow()
+            .as_ref()
+            .and_then(spec_from_tree)
+            .map(|spec| *spec)
+            .unwrap_or_default()
+    }
+
+    /// Changes the sizes of the splits to match `spec`.
+    /// The transition is animated if `pane_resize_animation_duration`
+    /// is configured.
+    pub fn set_layout(self: &Rc<Self>, spec: &SplitLayoutSpec) -> anyhow::Result<()> {
+        let mut ratios = vec![];
+        if let Some(root) = self.pane.borrow().as_ref() {
+            ratios_from_spec(root, Some(spec), &mut ratios)?;
+        }
+        self.animate_split_ratios(self.split_ratios(), ratios, None);
+        Ok(())
+    }
+
+    /// Calls `func` to change the sizes of the panes in the tab, and then
+    /// animates the transition from the prior layout to the new one.
+    pub fn animate_layout_change<F: FnOnce(&Tab)>(self: &Rc<Self>, func: F) {
+        let from = self.split_ratios();
+        func(self);
+        if configuration().pane_resize_animation_duration == 0
+            || self.zoomed.borrow().is_some()
+            || self.stacked.get()
+        {
+            self.cancel_layout_animation();
+            return;
+        }
+        let to = self.split_ratios();
+        if from != to && from.len() == to.len() {
+            self.set_split_ratios(&from);
+            self.animate_split_ratios(from, to, None);
+        }
+    }
+
+    /// Like toggle_zoom(), but animates the active pane growing to fill
+    /// the tab, or shrinking back to its place in the layout.
+    pub fn toggle_zoom_animated(self: &Rc<Self>) {
+        if configuration().pane_resize_animation_duration == 0 || self.stacked.get() {
+            self.cancel_layout_animation();
+            self.toggle_zoom();
+            return;
+        }
+
+        let layout = self.split_ratios();
+        let mut maximized = vec![];
+        if let Some(root) = self.pane.borrow().as_ref() {
+            ratios_favoring_pane(root, self.get_active_idx(), &mut 0, &mut maximized);
+        }
+
+        let zoomed = self.zoomed.borrow_mut().take();
+        if let Some(pane) = zoomed {
+            // Un-zoom into the layout in which the pane is as large as
+            // possible, then let the other panes grow back into place
+            pane.set_zoomed(false);
+            self.set_split_ratios(&maximized);
+            let dpi = self.size.borrow().dpi;
+            if let Some(pos) = self
+                .iter_panes()
+                .into_iter()
+                .find(|pos| pos.pane.pane_id() == pane.pane_id())
+            {
+                pane.resize(TerminalSize {
+                    rows: pos.height,
+                    cols: pos.width,
+                    pixel_width: pos.pixel_width,
+                    pixel_height: pos.pixel_height,
+                    dpi,
+                })
+                .ok();
+            }
+            self.animate_split_ratios(maximized, layout, None);
+        } else {
+            // Grow the pane to fill the tab, then restore the layout
+            // of the other panes before zooming it
+            self.animate_split_ratios(
+                layout.clone(),
+                maximized,
+                Some(Box::new(move |tab: &Tab| {
+                    tab.set_split_ratios(&layout);
+                    tab.set_zoomed(true);
+                })),
+            );
+        }
+    }
+
+    fn cancel_layout_animation(&self) {
+        self.layout_animation
+            .set(self.layout_animation.get().wrapping_add(1));
+    }
+
+    /// Transitions the splits from the `from` ratios to the `to` ratios
+    /// over `pane_resize_animation_duration`.  The panes are resized
+    /// once the animation completes, or if it is abandoned because
+    /// another layout change is made while it is running, or because
+    /// panes are added or removed.
+    fn animate_split_ratios(
+        self: &Rc<Self>,
+        from: Vec<f32>,
+        to: Vec<f32>,
+        on_complete: Option<Box<dyn FnOnce(&Tab)>>,
+    ) {
+        self.cancel_layout_animation();
+        let generation = self.layout_animation.get();
+
+        let config = configuration();
+        let duration = Duration::from_millis(config.pane_resize_animation_duration);
+        if duration.as_millis() == 0
+            || from.len() != to.len()
+            || self.zoomed.borrow().is_some()
+            || self.stacked.get()
+        {
+            self.set_split_ratios(&to);
+            if let Some(on_complete) = on_complete {
+                on_complete(self);
+            }
+            return;
+        }
+        let easing = config.pane_resize_animation_ease;
+        let frame_interval = Duration::from_millis(1000 / config.animation_fps.max(1) as u64);
+
+        let tab = Rc::clone(self);
+        let start = Instant::now();
+        promise::spawn::spawn(async move {
+            loop {
+                if tab.layout_animation.get() != generation || tab.split_ratios().len() != to.len()
+                {
+                    tab.resize_panes_to_splits();
+                    return;
+                }
+                let progress = start.elapsed().as_secs_f32() / duration.as_secs_f32();
+                if progress >= 1.0 {
+                    tab.apply_split_ratios(&to, false);
+                    match on_complete {
+                        Some(on_complete) => on_complete(&tab),
+                        None => tab.resize_panes_to_splits(),
+                    }
+                    return;
+                }
+                let position = easing.evaluate_at_position(progress);
+                let ratios: Vec<f32> = from
+                    .iter()
+                    .zip(to.iter())
+                    .map(|(from, to)| from + (to - from) * position)
+                    .collect();
+                tab.apply_split_ratios(&ratios, false);
+                // The panes aren't resized, so nothing else
+                // would cause the frame to be painted
+                if let Some(mux) = Mux::get() {
+                    if let Some(window_id) = mux.window_containing_tab(tab.tab_id()) {
+                        mux.notify(MuxNotification::WindowInvalidated(window_id));
+                    }
+                }
+                smol::Timer::after(frame_interval).await;
+            }
+        })
+        .detach();
+    }
+
     /// Activate an adjacent pane in the specified direction.
     /// In cases where there are multiple adjacent panes in the
     /// intended direction, we take the pane that has the largest
@@ -1222,6 +1756,20 @@ impl Tab {
             return;
         }
 
+        if self.stacked.get() {
+            // The stacked panes are in topological order from top to bottom
+            match direction {
+                PaneDirection::Up | PaneDirection::Left if active.index > 0 => {
+                    self.set_active_idx(active.index - 1);
+                }
+                PaneDirection::Down | PaneDirection::Right if active.index + 1 < panes.len() => {
+                    self.set_active_idx(active.index + 1);
+                }
+                _ => {}
+            }
+            return;
+        }
+
         let mut best = None;
 
         /// Compute the edge intersection size between two touching panes
@@ -1407,6 +1955,10 @@ impl Tab {
             *self.active.borrow_mut() = active_idx;
         }
 
+        if !dead_panes.is_empty() {
+            self.apply_stacked_size();
+        }
+
         if !dead_panes.is_empty() && kill {
             let to_kill: Vec<_> = dead_panes.iter().map(|p| p.pane_id()).collect();
             promise::spawn::spawn_into_main_thread(async move {
@@ -1467,6 +2019,7 @@ impl Tab {
         {
             let prior = self.get_active_pane();
             *self.active.borrow_mut() = item.index;
+            self.apply_stacked_size();
             self.advise_focus_change(prior);
         }
     }
@@ -1493,6 +2046,7 @@ impl Tab {
     pub fn set_active_idx(&self, pane_index: usize) {
         let prior = self.get_active_pane();
         *self.active.borrow_mut() = pane_index;
+        self.apply_stacked_size();
         self.advise_focus_change(prior);
     }
 
@@ -1556,12 +2110,79 @@ impl Tab {
             let size = *self.size.borrow();
             apply_sizes_from_splits(root.as_mut().unwrap(), &size);
         }
+        self.apply_stacked_size();
 
         // And update focus
         self.advise_focus_change(Some(pane));
         None
     }
 
+    /// Exchanges the positions of the panes `pane_id` and `other_pane_id`
+    /// in this tab.  Each pane takes the size of the position that it is
+    /// moved into, and the active position is unchanged.
+    pub fn swap_panes(&self, pane_id: PaneId, other_pane_id: PaneId) -> Option<()> {
+        let panes = self.iter_panes_ignoring_zoom();
+        let find = |id: PaneId| {
+            panes
+                .iter()
+                .find(|p| p.pane.pane_id() == id)
+                .map(|p| Rc::clone(&p.pane))
+        };
+        let pane = find(pane_id)?;
+        let other = find(other_pane_id)?;
+        self.replace_panes(&[(pane_id, other), (other_pane_id, pane)]);
+        Some(())
+    }
+
+    /// Puts `pane`, which is taken from another tab, in the position of
+    /// the pane `pane_id`, and returns the pane that was replaced
+    pub fn replace_pane(&self, pane_id: PaneId, pane: &Rc<dyn Pane>) -> Option<Rc<dyn Pane>> {
+        let old = self
+            .iter_panes_ignoring_zoom()
+            .into_iter()
+            .find(|p| p.pane.pane_id() == pane_id)?
+            .pane;
+        self.replace_panes(&[(pane_id, Rc::clone(pane))]);
+        Some(old)
+    }
+
+    /// Replaces the panes whose ids are in `replacements` with the
+    /// corresponding panes, preserving the split layout
+    fn replace_panes(&self, replacements: &[(PaneId, Rc<dyn Pane>)]) {
+        if self.pane.borrow().is_none() {
+            return;
+        }
+        self.set_zoomed(false);
+        let prior = self.get_active_pane();
+
+        {
+            let mut root = self.pane.borrow_mut();
+            let mut cursor = root.take().unwrap().cursor();
+
+            loop {
+                if let Some(leaf) = cursor.leaf_mut() {
+                    let pane_id = leaf.pane_id();
+                    if let Some((_, pane)) = replacements.iter().find(|(id, _)| *id == pane_id) {
+                        *leaf = Rc::clone(pane);
+                    }
+                }
+
+                match cursor.preorder_next() {
+                    Ok(c) => cursor = c,
+                    Err(c) => {
+                        root.replace(c.tree());
+                        // Advise the panes of their new sizes
+                        let size = *self.size.borrow();
+                        apply_sizes_from_splits(root.as_mut().unwrap(), &size);
+                        break;
+                    }
+                }
+            }
+        }
+        self.apply_stacked_size();
+        self.advise_focus_change(prior);
+    }
+
     /// Computes the size of the pane that would result if the specified
     /// pane was split in a particular direction.
     /// The intent is to call this prior to spawning the new pane so that
@@ -1740,6 +2361,8 @@ impl Tab {
                         };
 
                         *self.active.borrow_mut() = pane_index;
+                        drop(root);
+                        self.apply_stacked_size();
                         return Ok(pane_index);
                     }
                     Err(cursor) => cursor,
@@ -1786,6 +2409,8 @@ impl Tab {
             }
         }
 
+        self.apply_stacked_size();
+
         log::debug!("split info after split: {:#?}", self.iter_splits());
         log::debug!("pane info after split: {:#?}", self.iter_panes());
 
@@ -1856,6 +2481,7 @@ pub struct PaneEntry {
     pub is_active_pane: bool,
     pub is_zoomed_pane: bool,
     pub workspace: String,
+    pub resource_usage: Option<PaneResourceUsage>,
 }
 
 #[derive(Deserialize, Clone, Serialize, PartialEq, Debug)]
@@ -2190,4 +2816,191 @@ mod test {
         assert_eq!(400, panes[2].pixel_width);
         assert_eq!(600, panes[2].pixel_height);
     }
+
+    #[test]
+    fn swap_panes() {
+        let size = TerminalSize {
+            rows: 24,
+            cols: 80,
+            pixel_width: 800,
+            pixel_height: 600,
+            dpi: 96,
+        };
+
+        let tab = Tab::new(&size);
+        tab.assign_pane(&FakePane::new(1, size));
+        let request = SplitRequest {
+            direction: SplitDirection::Horizontal,
+            ..Default::default()
+        };
+        let horz_size = tab.compute_split_size(0, request).unwrap();
+        tab.split_and_insert(0, request, FakePane::new(2, horz_size.second))
+            .unwrap();
+        tab.set_split_ratios(&[0.25]);
+
+        // The panes exchange positions, and take the sizes of them
+        tab.swap_panes(1, 2).unwrap();
+        let panes = tab.iter_panes();
+        assert_eq!(2, panes[0].pane.pane_id());
+        assert_eq!(20, panes[0].width);
+        assert_eq!(1, panes[1].pane.pane_id());
+        assert_eq!(59, panes[1].width);
+        assert!(tab.swap_panes(1, 3).is_none());
+
+        // A pane can take the position of a pane in another tab
+        let other_tab = Tab::new(&size);
+        let pane_3 = FakePane::new(3, size);
+        other_tab.assign_pane(&pane_3);
+        let pane_1 = tab.replace_pane(1, &pane_3).unwrap();
+        assert_eq!(1, pane_1.pane_id());
+        other_tab.replace_pane(3, &pane_1).unwrap();
+        let panes = tab.iter_panes();
+        assert_eq!(3, panes[1].pane.pane_id());
+        assert_eq!(59, panes[1].width);
+        let panes = other_tab.iter_panes();
+        assert_eq!(1, panes[0].pane.pane_id());
+        assert_eq!(80, panes[0].width);
+        assert!(tab.replace_pane(1, &pane_1).is_none());
+    }
+
+    #[test]
+    fn split_ratios() {
+        let size = TerminalSize {
+            rows: 24,
+            cols: 80,
+            pixel_width: 800,
+            pixel_height: 600,
+            dpi: 96,
+        };
+
+        let tab = Rc::new(Tab::new(&size));
+        tab.assign_pane(&FakePane::new(1, size));
+        assert_eq!(tab.get_layout(), SplitLayoutSpec::default());
+
+        let request = SplitRequest {
+            direction: SplitDirection::Horizontal,
+            ..Default::default()
+        };
+        let horz_size = tab.compute_split_size(0, request).unwrap();
+        tab.split_and_insert(0, request, FakePane::new(2, horz_size.second))
+            .unwrap();
+        assert_eq!(tab.split_ratios(), vec![39. / 79.]);
+
+        tab.set_split_ratios(&[0.25]);
+        let panes = tab.iter_panes();
+        assert_eq!(20, panes[0].width);
+        assert_eq!(200, panes[0].pixel_width);
+        assert_eq!(21, panes[1].left);
+        assert_eq!(59, panes[1].width);
+        assert_eq!(tab.split_ratios(), vec![20. / 79.]);
+
+        // The second pane is given as much space as possible
+        let mut maximized = vec![];
+        ratios_favoring_pane(
+            tab.pane.borrow().as_ref().unwrap(),
+            1,
+            &mut 0,
+            &mut maximized,
+        );
+        assert_eq!(maximized, vec![0.]);
+        tab.set_split_ratios(&maximized);
+        let panes = tab.iter_panes();
+        assert_eq!(1, panes[0].width);
+        assert_eq!(78, panes[1].width);
+
+        tab.set_layout(&SplitLayoutSpec {
+            ratio: Some(0.5),
+            ..Default::default()
+        })
+        .unwrap();
+        let panes = tab.iter_panes();
+        assert_eq!(40, panes[0].width);
+        assert_eq!(39, panes[1].width);
+        assert_eq!(
+            tab.get_layout(),
+            SplitLayoutSpec {
+                ratio: Some(40. / 79.),
+                ..Default::default()
+            }
+        );
+
+        assert!(tab
+            .set_layout(&SplitLayoutSpec {
+                ratio: Some(1.5),
+                ..Default::default()
+            })
+            .is_err());
+        assert!(tab
+            .set_layout(&SplitLayoutSpec {
+                ratio: None,
+                first: Some(Box::new(SplitLayoutSpec {
+                    ratio: Some(0.5),
+                    ..Default::default()
+                })),
+                second: None,
+            })
+            .is_err());
+        assert_eq!(40, tab.iter_panes()[0].width);
+    }
+
+    #[test]
+    fn stacked_layout() {
+        let size = TerminalSize {
+            rows: 24,
+            cols: 80,
+            pixel_width: 800,
+            pixel_height: 600,
+            dpi: 96,
+        };
+        let pane_rows =
+            |pane: &Rc<dyn Pane>| pane.downcast_ref::<FakePane>().unwrap().size.borrow().rows;
+
+        let tab = Tab::new(&size);
+        tab.assign_pane(&FakePane::new(1, size));
+        let request = SplitRequest::default();
+        let horz_size = tab.compute_split_size(0, request).unwrap();
+        tab.split_and_insert(0, request, FakePane::new(2, horz_size.second))
+            .unwrap();
+        let request = SplitRequest {
+            direction: SplitDirection::Vertical,
+            ..Default::default()
+        };
+        let vert_size = tab.compute_split_size(1, request).unwrap();
+        tab.split_and_insert(1, request, FakePane::new(3, vert_size.second))
+            .unwrap();
+        assert_eq!(2, tab.get_active_idx());
+
+        tab.set_stacked(true);
+        assert!(tab.iter_splits().is_empty());
+        let panes = tab.iter_panes();
+        let layout: Vec<_> = panes
+            .iter()
+            .map(|p| (p.top, p.height, p.width, p.is_collapsed))
+            .collect();
+        assert_eq!(
+            layout,
+            vec![(0, 1, 80, true), (1, 1, 80, true), (2, 22, 80, false)]
+        );
+        assert_eq!(550, panes[2].pixel_height);
+        assert_eq!(22, pane_rows(&panes[2].pane));
+
+        tab.activate_pane_direction(PaneDirection::Up);
+        let panes = tab.iter_panes();
+        let layout: Vec<_> = panes
+            .iter()
+            .map(|p| (p.top, p.height, p.is_collapsed))
+            .collect();
+        assert_eq!(layout, vec![(0, 1, true), (1, 22, false), (23, 1, true)]);
+        assert_eq!(22, pane_rows(&panes[1].pane));
+
+        // The split layout is restored
+        tab.set_stacked(false);
+        assert_eq!(2, tab.iter_splits().len());
+        let panes = tab.iter_panes();
+        assert!(panes.iter().all(|p| !p.is_collapsed));
+        assert_eq!(39, panes[0].width);
+        assert_eq!(24, pane_rows(&panes[0].pane));
+        assert_eq!(11, pane_rows(&panes[1].pane));
+        assert_eq!(12, pane_rows(&panes[2].pane));
+    }
 }
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::time::Duration;
 use wezterm_dynamic::{FromDynamic, ToDynamic};
 
 mod linux;
@@ -57,6 +58,17 @@ pub struct LocalProcessInfo {
 }
 luahelper::impl_lua_conversion_dynamic!(LocalProcessInfo);
 
+/// The resources used by a single process, at the time that they
+/// were sampled
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct LocalProcessResourceUsage {
+    /// The CPU time that the process has consumed so far, in both
+    /// user and kernel mode
+    pub cpu_time: Duration,
+    /// The size of the resident set of the process, in bytes
+    pub resident_bytes: u64,
+}
+
 impl LocalProcessInfo {
     /// Walk this sub-tree of processes and return a unique set
     /// of executable base names. eg: `foo/bar` and `woot/bar`
@@ -77,6 +89,15 @@ impl LocalProcessInfo {
         names
     }
 
+    /// Walk this sub-tree of processes and return the pids that it contains
+    pub fn flatten_to_pids(&self) -> Vec<u32> {
+        let mut pids = vec![self.pid];
+        for proc in self.children.values() {
+            pids.append(&mut proc.flatten_to_pids());
+        }
+        pids
+    }
+
     #[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
     pub fn with_root_pid(_pid: u32) -> Option<Self> {
         None
@@ -91,4 +112,9 @@ impl LocalProcessInfo {
     pub fn executable_path(_pid: u32) -> Option<PathBuf> {
         None
     }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
+    pub fn resource_usage(_pid: u32) -> Option<LocalProcessResourceUsage> {
+        None
+    }
 }
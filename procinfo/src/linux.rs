@@ -28,6 +28,28 @@ impl LocalProcessInfo {
         std::fs::read_link(format!("/proc/{}/exe", pid)).ok()
     }
 
+    pub fn resource_usage(pid: u32) -> Option<LocalProcessResourceUsage> {
+        let data = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+        let (_pid_name, fields) = data.rsplit_once(')')?;
+        let fields = fields.split_whitespace().collect::<Vec<_>>();
+        // The fields are numbered from the state, which is the third
+        // field described by proc(5)
+        let utime: u64 = fields.get(11)?.parse().ok()?;
+        let stime: u64 = fields.get(12)?.parse().ok()?;
+        let rss: u64 = fields.get(21)?.parse().ok()?;
+
+        let ticks_per_second = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if ticks_per_second <= 0 || page_size <= 0 {
+            return None;
+        }
+
+        Some(LocalProcessResourceUsage {
+            cpu_time: Duration::from_secs_f64((utime + stime) as f64 / ticks_per_second as f64),
+            resident_bytes: rss * page_size as u64,
+        })
+    }
+
     pub fn with_root_pid(pid: u32) -> Option<Self> {
         use libc::pid_t;
 
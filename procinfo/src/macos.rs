@@ -65,6 +65,37 @@ impl LocalProcessInfo {
         Some(OsString::from_vec(buffer).into())
     }
 
+    pub fn resource_usage(pid: u32) -> Option<LocalProcessResourceUsage> {
+        let mut info: libc::proc_taskinfo = unsafe { std::mem::zeroed() };
+        let size = std::mem::size_of_val(&info) as libc::c_int;
+        let ret = unsafe {
+            libc::proc_pidinfo(
+                pid as _,
+                libc::PROC_PIDTASKINFO,
+                0,
+                &mut info as *mut _ as *mut _,
+                size,
+            )
+        };
+        if ret != size {
+            return None;
+        }
+
+        // The times are measured in mach absolute time units, which
+        // are not nanoseconds on all hardware
+        let mut timebase = libc::mach_timebase_info { numer: 0, denom: 0 };
+        if unsafe { libc::mach_timebase_info(&mut timebase) } != 0 || timebase.denom == 0 {
+            return None;
+        }
+        let ticks = info.pti_total_user + info.pti_total_system;
+        let nanos = ticks as u128 * timebase.numer as u128 / timebase.denom as u128;
+
+        Some(LocalProcessResourceUsage {
+            cpu_time: Duration::from_nanos(nanos as u64),
+            resident_bytes: info.pti_resident_size,
+        })
+    }
+
     pub fn with_root_pid(pid: u32) -> Option<Self> {
         /// Enumerate all current process identifiers
         fn all_pids() -> Vec<libc::pid_t> {
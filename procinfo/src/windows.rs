@@ -15,6 +15,7 @@ use winapi::shared::ntdef::{FALSE, NT_SUCCESS};
 use winapi::um::handleapi::CloseHandle;
 use winapi::um::memoryapi::ReadProcessMemory;
 use winapi::um::processthreadsapi::{GetCurrentProcessId, GetProcessTimes, OpenProcess};
+use winapi::um::psapi::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
 use winapi::um::shellapi::CommandLineToArgvW;
 use winapi::um::tlhelp32::*;
 use winapi::um::winbase::{LocalFree, QueryFullProcessImageNameW};
@@ -314,6 +315,44 @@ impl ProcHandle {
 
         Some((start.dwHighDateTime as u64) << 32 | start.dwLowDateTime as u64)
     }
+
+    /// Retrieves the CPU time and working set size of the process
+    fn resource_usage(&self) -> Option<LocalProcessResourceUsage> {
+        const fn empty() -> FILETIME {
+            FILETIME {
+                dwLowDateTime: 0,
+                dwHighDateTime: 0,
+            }
+        }
+        fn to_u64(time: &FILETIME) -> u64 {
+            (time.dwHighDateTime as u64) << 32 | time.dwLowDateTime as u64
+        }
+
+        let mut start = empty();
+        let mut exit = empty();
+        let mut kernel = empty();
+        let mut user = empty();
+
+        let res =
+            unsafe { GetProcessTimes(self.proc, &mut start, &mut exit, &mut kernel, &mut user) };
+        if res == 0 {
+            return None;
+        }
+
+        let mut counters: PROCESS_MEMORY_COUNTERS = unsafe { std::mem::zeroed() };
+        let size = std::mem::size_of_val(&counters) as DWORD;
+        counters.cb = size;
+        let res = unsafe { GetProcessMemoryInfo(self.proc, &mut counters, size) };
+        if res == 0 {
+            return None;
+        }
+
+        // The times are measured in 100ns intervals
+        Some(LocalProcessResourceUsage {
+            cpu_time: Duration::from_nanos((to_u64(&kernel) + to_u64(&user)) * 100),
+            resident_bytes: counters.WorkingSetSize as u64,
+        })
+    }
 }
 
 /// Parse a command line string into an argv array
@@ -356,6 +395,11 @@ impl LocalProcessInfo {
         proc.executable()
     }
 
+    pub fn resource_usage(pid: u32) -> Option<LocalProcessResourceUsage> {
+        let proc = ProcHandle::new(pid)?;
+        proc.resource_usage()
+    }
+
     pub fn with_root_pid(pid: u32) -> Option<Self> {
         log::trace!("LocalProcessInfo::with_root_pid({}), getting snapshot", pid);
         let procs = Snapshot::entries();
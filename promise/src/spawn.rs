@@ -2,6 +2,7 @@ use anyhow::{anyhow, Result};
 use async_executor::Executor;
 use flume::{bounded, unbounded, Receiver, TryRecvError};
 use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::task::{Poll, Waker};
 
@@ -19,6 +20,8 @@ lazy_static::lazy_static! {
     static ref SCOPED_EXECUTOR: Mutex<Option<Arc<Executor<'static>>>> = Mutex::new(None);
 }
 
+static SCHEDULERS_CONFIGURED: AtomicBool = AtomicBool::new(false);
+
 fn schedule_runnable(runnable: Runnable, high_pri: bool) {
     let func = if high_pri {
         ON_MAIN_THREAD.lock()
@@ -39,6 +42,14 @@ fn schedule_runnable(runnable: Runnable, high_pri: bool) {
 pub fn set_schedulers(main: ScheduleFunc, low_pri: ScheduleFunc) {
     *ON_MAIN_THREAD.lock().unwrap() = Box::new(main);
     *ON_MAIN_THREAD_LOW_PRI.lock().unwrap() = Box::new(low_pri);
+    SCHEDULERS_CONFIGURED.store(true, Ordering::Release);
+}
+
+/// Returns true if futures can be spawned into the main thread, either
+/// because `set_schedulers` has been called or because a `ScopedExecutor`
+/// is alive.  Otherwise, spawning into the main thread panics.
+pub fn can_spawn_into_main_thread() -> bool {
+    SCHEDULERS_CONFIGURED.load(Ordering::Acquire) || get_scoped().is_some()
 }
 
 /// Spawn a new thread to execute the provided function.
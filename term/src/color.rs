@@ -85,6 +85,38 @@ impl ColorPalette {
             | ColorAttribute::TrueColorWithDefaultFallback(color) => color.into(),
         }
     }
+
+    /// Returns a palette whose colors are `fraction` of the way
+    /// from the colors of `self` to the colors of `other`
+    pub fn interpolate(&self, other: &Self, fraction: f32) -> Self {
+        fn lerp(a: SrgbaTuple, b: SrgbaTuple, fraction: f32) -> SrgbaTuple {
+            SrgbaTuple(
+                a.0 + (b.0 - a.0) * fraction,
+                a.1 + (b.1 - a.1) * fraction,
+                a.2 + (b.2 - a.2) * fraction,
+                a.3 + (b.3 - a.3) * fraction,
+            )
+        }
+
+        Self {
+            colors: self
+                .colors
+                .0
+                .iter()
+                .zip(other.colors.0.iter())
+                .map(|(&a, &b)| lerp(a, b, fraction))
+                .collect(),
+            foreground: lerp(self.foreground, other.foreground, fraction),
+            background: lerp(self.background, other.background, fraction),
+            cursor_fg: lerp(self.cursor_fg, other.cursor_fg, fraction),
+            cursor_bg: lerp(self.cursor_bg, other.cursor_bg, fraction),
+            cursor_border: lerp(self.cursor_border, other.cursor_border, fraction),
+            selection_fg: lerp(self.selection_fg, other.selection_fg, fraction),
+            selection_bg: lerp(self.selection_bg, other.selection_bg, fraction),
+            scrollbar_thumb: lerp(self.scrollbar_thumb, other.scrollbar_thumb, fraction),
+            split: lerp(self.split, other.split, fraction),
+        }
+    }
 }
 
 lazy_static::lazy_static! {
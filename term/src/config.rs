@@ -1,8 +1,78 @@
 use crate::color::ColorPalette;
 use termwiz::cell::UnicodeVersion;
+use termwiz::escape::csi::Window;
 use termwiz::surface::{Line, SequenceNo};
 use wezterm_bidi::ParagraphDirectionHint;
 
+/// The groups of XTWINOPS (`CSI t`) window operations that can be
+/// allowed or denied by the embedding application
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowOperationKind {
+    /// Report the size of the text area, cells, window or screen
+    ReportSize,
+    /// Report the position of the window or its text area
+    ReportPosition,
+    /// Report whether the window is iconified
+    ReportState,
+    /// Report the window title or icon label
+    ReportTitle,
+    Resize,
+    Move,
+    Iconify,
+    RaiseLower,
+    Maximize,
+    FullScreen,
+}
+
+impl WindowOperationKind {
+    /// Returns the group of the window operation, or None if it
+    /// isn't subject to being allowed or denied
+    pub fn for_csi(window: &Window) -> Option<Self> {
+        match window {
+            Window::ReportTextAreaSizeCells
+            | Window::ReportTextAreaSizePixels
+            | Window::ReportCellSizePixels
+            | Window::ReportWindowSizePixels
+            | Window::ReportScreenSizePixels
+            | Window::ReportScreenSizeCells => Some(Self::ReportSize),
+            Window::ReportWindowPosition | Window::ReportTextAreaPosition => {
+                Some(Self::ReportPosition)
+            }
+            Window::ReportWindowState => Some(Self::ReportState),
+            Window::ReportWindowTitle | Window::ReportIconLabel => Some(Self::ReportTitle),
+            Window::ResizeWindowCells { .. } | Window::ResizeWindowPixels { .. } => {
+                Some(Self::Resize)
+            }
+            Window::MoveWindow { .. } => Some(Self::Move),
+            Window::Iconify | Window::DeIconify => Some(Self::Iconify),
+            Window::RaiseWindow | Window::LowerWindow => Some(Self::RaiseLower),
+            Window::MaximizeWindow
+            | Window::MaximizeWindowVertically
+            | Window::MaximizeWindowHorizontally
+            | Window::RestoreMaximizedWindow => Some(Self::Maximize),
+            Window::ChangeToFullScreenMode
+            | Window::UndoFullScreenMode
+            | Window::ToggleFullScreen => Some(Self::FullScreen),
+            Window::RefreshWindow
+            | Window::ReportCellSizePixelsResponse { .. }
+            | Window::PushIconAndWindowTitle
+            | Window::PushIconTitle
+            | Window::PushWindowTitle
+            | Window::PopIconAndWindowTitle
+            | Window::PopIconTitle
+            | Window::PopWindowTitle
+            | Window::ChecksumRectangularArea { .. } => None,
+        }
+    }
+
+    pub fn is_report(self) -> bool {
+        matches!(
+            self,
+            Self::ReportSize | Self::ReportPosition | Self::ReportState | Self::ReportTitle
+        )
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum NewlineCanon {
     None,
@@ -146,6 +216,23 @@ pub trait TerminalConfiguration: std::fmt::Debug {
         3500
     }
 
+    /// Returns the number of rows of alternate screen content to
+    /// retain after it has scrolled off the top of, or been cleared
+    /// from, the alternate screen, so that full screen applications
+    /// such as pagers can be reviewed in copy mode.
+    /// Zero disables this history.
+    fn alternate_screen_scrollback_size(&self) -> usize {
+        0
+    }
+
+    /// Returns true if the lines of the primary screen, including
+    /// the scrollback, should be rewrapped to fit the new width when
+    /// the terminal is resized.  When false, lines are truncated
+    /// when the terminal is made narrower.
+    fn reflow_on_resize(&self) -> bool {
+        true
+    }
+
     /// Return true if the embedding application wants to use CSI-u encoding
     /// for keys that would otherwise be ambiguous.
     /// <http://www.leonerd.org.uk/hacks/fixterms/>
@@ -167,6 +254,13 @@ pub trait TerminalConfiguration: std::fmt::Debug {
         3
     }
 
+    /// Whether the horizontal mouse wheel generates Arrow Left/Right
+    /// key events in the alternate screen, as the vertical wheel
+    /// does with Arrow Up/Down
+    fn alternate_buffer_horizontal_wheel_arrow_keys(&self) -> bool {
+        false
+    }
+
     fn enq_answerback(&self) -> String {
         "".to_string()
     }
@@ -205,6 +299,20 @@ pub trait TerminalConfiguration: std::fmt::Debug {
             hint: ParagraphDirectionHint::LeftToRight,
         }
     }
+
+    /// Returns the value to report for the named capability in
+    /// response to an XTGETTCAP query, overriding the value from
+    /// the built-in terminfo database.
+    fn term_capability(&self, _name: &str) -> Option<String> {
+        None
+    }
+
+    /// Returns true if the application may perform window operations
+    /// of the specified kind.  By default, the application may ask
+    /// for reports but may not manipulate the window.
+    fn allow_window_operation(&self, kind: WindowOperationKind) -> bool {
+        kind.is_report()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
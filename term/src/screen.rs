@@ -31,10 +31,15 @@ pub struct Screen {
     /// config so we can access Maximum number of lines of scrollback
     config: Arc<dyn TerminalConfiguration>,
 
-    /// Whether scrollback is allowed; this is another way of saying
-    /// that we're the primary rather than the alternate screen.
+    /// Whether this is the primary rather than the alternate screen.
+    /// The alternate screen only has scrollback if the configuration
+    /// asks to retain a history of alternate screen content.
     allow_scrollback: bool,
 
+    /// The number of lines of scrollback that were removed by
+    /// `take_scrollback` and are yet to be restored
+    hibernated_rows: usize,
+
     pub(crate) keyboard_stack: Vec<KeyboardEncoding>,
 
     /// Physical, visible height of the screen (not including scrollback)
@@ -48,7 +53,7 @@ fn scrollback_size(config: &Arc<dyn TerminalConfiguration>, allow_scrollback: bo
     if allow_scrollback {
         config.scrollback_size()
     } else {
-        0
+        config.alternate_screen_scrollback_size()
     }
 }
 
@@ -81,6 +86,7 @@ impl Screen {
             physical_rows,
             physical_cols,
             stable_row_index_offset: 0,
+            hibernated_rows: 0,
             dpi: size.dpi,
             keyboard_stack: vec![],
         }
@@ -209,7 +215,7 @@ impl Screen {
             // We only do this for the primary, and not for the alternate
             // screen (hence the check for allow_scrollback), to avoid
             // conflicting screen updates with full screen apps.
-            if self.allow_scrollback {
+            if self.allow_scrollback && self.config.reflow_on_resize() {
                 self.rewrap_lines(physical_cols, physical_rows, cursor.x, cursor_phys, seqno)
             } else {
                 for line in &mut self.lines {
@@ -700,6 +706,37 @@ impl Screen {
         }
     }
 
+    /// Moves the visible lines into the scrollback, leaving blank
+    /// lines in their place.  This is used to retain the content of
+    /// the alternate screen when it is cleared.  Nothing is saved if
+    /// the screen has no scrollback or is entirely blank.
+    pub fn save_visible_to_scrollback(
+        &mut self,
+        seqno: SequenceNo,
+        blank_attr: CellAttributes,
+        bidi_mode: BidiMode,
+    ) {
+        if self.scrollback_size() == 0 {
+            return;
+        }
+        let first_row = self.phys_row(0);
+        if self
+            .lines
+            .range(first_row..)
+            .all(|line| line.is_whitespace())
+        {
+            return;
+        }
+        let rows = self.physical_rows;
+        self.scroll_up(
+            &(0..rows as VisibleRowIndex),
+            rows,
+            seqno,
+            blank_attr,
+            bidi_mode,
+        );
+    }
+
     pub fn erase_scrollback(&mut self) {
         let len = self.lines.len();
         let to_clear = len - self.physical_rows;
@@ -707,6 +744,49 @@ impl Screen {
             self.lines.pop_front();
             self.stable_row_index_offset += 1;
         }
+        self.hibernated_rows = 0;
+    }
+
+    /// Removes the lines of scrollback so that they can be held in a
+    /// more compact form while the terminal is idle.  The stable row
+    /// indices of the remaining lines are unchanged.
+    /// The lines are returned to the screen by `restore_scrollback`.
+    pub fn take_scrollback(&mut self) -> Vec<Line> {
+        let count = self.lines.len() - self.physical_rows;
+        self.stable_row_index_offset += count;
+        self.hibernated_rows += count;
+        let lines = self.lines.drain(0..count).collect();
+        self.lines.shrink_to_fit();
+        lines
+    }
+
+    /// Returns the number of lines of scrollback that were removed
+    /// by `take_scrollback` and are yet to be restored
+    pub fn hibernated_rows(&self) -> usize {
+        self.hibernated_rows
+    }
+
+    /// Returns the lines that were removed by `take_scrollback` to the
+    /// top of the scrollback.  The lines are discarded if the scrollback
+    /// was erased in the meantime, and the oldest lines are discarded
+    /// if the scrollback has since grown beyond its capacity.
+    /// If fewer lines are returned than were taken, the missing lines
+    /// are treated as the oldest ones.
+    pub fn restore_scrollback(&mut self, lines: Vec<Line>) {
+        if self.hibernated_rows == 0 {
+            return;
+        }
+        let lines: Vec<Line> = lines.into_iter().take(self.hibernated_rows).collect();
+        self.hibernated_rows = 0;
+        self.stable_row_index_offset -= lines.len();
+        for line in lines.into_iter().rev() {
+            self.lines.push_front(line);
+        }
+        let max_allowed = self.physical_rows + self.scrollback_size();
+        while self.lines.len() > max_allowed {
+            self.lines.pop_front();
+            self.stable_row_index_offset += 1;
+        }
     }
 
     /// ```text
@@ -59,6 +59,72 @@ pub enum Alert {
     /// When something bumps the seqno in the terminal model and
     /// the terminal is not focused
     OutputSinceFocusLost,
+    /// The shell integration marked the start of the output of
+    /// a command (OSC 133;C); `command` is the text that was entered
+    /// at the prompt
+    CommandStarted {
+        command: String,
+    },
+    /// The shell integration reported the exit status of the
+    /// command (OSC 133;D)
+    CommandFinished {
+        status: i32,
+    },
+    /// The application requested a window operation (XTWINOPS)
+    /// that can only be carried out by the GUI
+    WindowOperation(WindowOperation),
+    /// The application asked for the contents of the clipboard
+    /// using OSC 52.  The GUI decides whether to answer, based on
+    /// the clipboard policy of the domain.
+    ClipboardQuery(ClipboardSelection),
+}
+
+/// A window operation requested by the application using XTWINOPS
+/// (`CSI t`).  Reports are answered by writing the same escape
+/// sequences as xterm to the pane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+pub enum WindowOperation {
+    Iconify,
+    DeIconify,
+    /// Move the top left of the window to the specified position,
+    /// in pixels
+    Move {
+        x: i64,
+        y: i64,
+    },
+    /// Resize the text area to the specified size in pixels.
+    /// A missing dimension is left unchanged, while zero selects
+    /// the size of the screen.
+    ResizePixels {
+        width: Option<i64>,
+        height: Option<i64>,
+    },
+    /// Resize the text area to the specified size in cells.
+    /// A missing or zero dimension is left unchanged.
+    ResizeCells {
+        width: Option<i64>,
+        height: Option<i64>,
+    },
+    Raise,
+    Lower,
+    Maximize,
+    RestoreMaximized,
+    FullScreen,
+    UndoFullScreen,
+    ToggleFullScreen,
+    /// Report `CSI 1 t` if the window is open, `CSI 2 t` if iconified
+    ReportState,
+    /// Report the position of the window as `CSI 3 ; x ; y t`
+    ReportWindowPosition,
+    /// Report the position of the text area as `CSI 3 ; x ; y t`
+    ReportTextAreaPosition,
+    /// Report the size of the window as `CSI 4 ; height ; width t`
+    ReportWindowSizePixels,
+    /// Report the size of the screen as `CSI 5 ; height ; width t`
+    ReportScreenSizePixels,
+    /// Report the size of the screen as `CSI 9 ; rows ; cols t`
+    ReportScreenSizeCells,
 }
 
 pub trait AlertHandler {
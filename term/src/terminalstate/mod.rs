@@ -3,7 +3,7 @@
 #![cfg_attr(feature = "cargo-clippy", allow(clippy::range_plus_one))]
 use super::*;
 use crate::color::{ColorPalette, RgbColor};
-use crate::config::{BidiMode, NewlineCanon};
+use crate::config::{BidiMode, NewlineCanon, WindowOperationKind};
 use log::debug;
 use num_traits::ToPrimitive;
 use std::collections::HashMap;
@@ -29,9 +29,12 @@ mod keyboard;
 mod kitty;
 mod mouse;
 pub(crate) mod performer;
+mod report;
 mod sixel;
 use crate::terminalstate::image::*;
 use crate::terminalstate::kitty::*;
+use crate::terminalstate::mouse::LocatorState;
+pub use crate::terminalstate::report::TerminalStateReport;
 
 lazy_static::lazy_static! {
     static ref DB: Database = {
@@ -315,6 +318,10 @@ pub struct TerminalState {
     button_event_mouse: bool,
     current_mouse_buttons: Vec<MouseButton>,
     last_mouse_move: Option<MouseEvent>,
+    /// The most recent position of the mouse, which is reported
+    /// by the locator
+    mouse_position: Option<MouseEvent>,
+    locator: LocatorState,
     cursor_visible: bool,
 
     keyboard_encoding: KeyboardEncoding,
@@ -373,6 +380,8 @@ pub struct TerminalState {
 
     lost_focus_seqno: SequenceNo,
     focused: bool,
+    /// The number of times the bell rang since the terminal lost focus
+    unseen_bell_count: usize,
 
     /// True if lines should be marked as bidi-enabled, and thus
     /// have the renderer apply the bidi algorithm.
@@ -518,6 +527,8 @@ impl TerminalState {
             button_event_mouse: false,
             mouse_tracking: false,
             last_mouse_move: None,
+            mouse_position: None,
+            locator: LocatorState::default(),
             cursor_visible: true,
             g0_charset: CharSet::Ascii,
             g1_charset: CharSet::DecLineDrawing,
@@ -550,6 +561,7 @@ impl TerminalState {
             accumulating_title: None,
             lost_focus_seqno: seqno,
             focused: true,
+            unseen_bell_count: 0,
             bidi_enabled: None,
             bidi_hint: None,
         }
@@ -688,12 +700,28 @@ impl TerminalState {
         self.screen_mut().erase_scrollback();
     }
 
+    /// Removes the scrollback of the primary screen so that it can be
+    /// held in a more compact form while the terminal is idle.
+    /// See `Screen::take_scrollback`.
+    pub fn take_scrollback(&mut self) -> Vec<Line> {
+        self.screen.screen.take_scrollback()
+    }
+
+    /// Returns the scrollback that was removed by `take_scrollback`
+    /// to the primary screen
+    pub fn restore_scrollback(&mut self, lines: Vec<Line>) {
+        self.screen.screen.restore_scrollback(lines)
+    }
+
     /// Returns true if the associated application has enabled any of the
     /// supported mouse reporting modes.
     /// This is useful for the hosting GUI application to decide how best
     /// to dispatch mouse events to the terminal.
     pub fn is_mouse_grabbed(&self) -> bool {
-        self.mouse_tracking || self.button_event_mouse || self.any_event_mouse
+        self.mouse_tracking
+            || self.button_event_mouse
+            || self.any_event_mouse
+            || self.locator.reports_buttons()
     }
 
     pub fn is_alt_screen_active(&self) -> bool {
@@ -733,7 +761,9 @@ impl TerminalState {
             self.writer.flush().ok();
         }
         self.focused = focused;
-        if !focused {
+        if focused {
+            self.unseen_bell_count = 0;
+        } else {
             self.lost_focus_seqno = self.seqno;
         }
     }
@@ -744,6 +774,12 @@ impl TerminalState {
         !self.focused && self.seqno > self.lost_focus_seqno
     }
 
+    /// Returns the number of times that the bell has rung since
+    /// the terminal lost focus
+    pub fn unseen_bell_count(&self) -> usize {
+        self.unseen_bell_count
+    }
+
     pub(crate) fn trigger_unseen_output_notif(&mut self) {
         if self.has_unseen_output() {
             if let Some(handler) = self.alert_handler.as_mut() {
@@ -1088,6 +1124,26 @@ impl TerminalState {
         });
     }
 
+    /// Returns the value that is reported for the named capability
+    /// by XTGETTCAP.  Capabilities defined by the configuration take
+    /// precedence over those of the built-in terminfo database, which
+    /// is compiled from the same source as the shipped wezterm terminfo.
+    fn tcap_value(&self, name: &str) -> Option<Vec<u8>> {
+        if let Some(value) = self.config.term_capability(name) {
+            return Some(value.into_bytes());
+        }
+        match name {
+            "TN" | "name" => Some(self.term_program.as_bytes().to_vec()),
+            "Co" | "colors" => Some(b"256".to_vec()),
+            "RGB" => Some(b"8/8/8".to_vec()),
+            _ => DB.raw(name).map(|value| match value {
+                Value::True => b"1".to_vec(),
+                Value::Number(n) => n.to_string().into_bytes(),
+                Value::String(s) => s.to_vec(),
+            }),
+        }
+    }
+
     /// <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h4-Device-Control-functions:DCS-plus-q-Pt-ST.F95>
     /// XTGETTCAP
     fn xt_get_tcap(&mut self, names: Vec<String>) {
@@ -1097,48 +1153,17 @@ impl TerminalState {
             res.push_str("\x1bP");
 
             let encoded_name = hex::encode_upper(&name);
-            match name.as_str() {
-                "TN" | "name" => {
-                    res.push_str("1+r");
-                    res.push_str(&encoded_name);
-                    res.push('=');
-
-                    let encoded_val = hex::encode_upper(&self.term_program);
-                    res.push_str(&encoded_val);
-                }
-
-                "Co" | "colors" => {
+            match self.tcap_value(name) {
+                Some(value) => {
                     res.push_str("1+r");
                     res.push_str(&encoded_name);
                     res.push('=');
-                    let encoded_val = hex::encode_upper("256");
-                    res.push_str(&encoded_val);
+                    res.push_str(&hex::encode_upper(&value));
                 }
-
-                "RGB" => {
-                    res.push_str("1+r");
+                None => {
+                    log::trace!("xt_get_tcap: unknown name {}", name);
+                    res.push_str("0+r");
                     res.push_str(&encoded_name);
-                    res.push('=');
-                    let encoded_val = hex::encode_upper("8/8/8");
-                    res.push_str(&encoded_val);
-                }
-
-                _ => {
-                    if let Some(value) = DB.raw(name) {
-                        res.push_str("1+r");
-                        res.push_str(&encoded_name);
-                        res.push('=');
-                        let value = match value {
-                            Value::True => hex::encode_upper("1"),
-                            Value::Number(n) => hex::encode_upper(&n.to_string()),
-                            Value::String(s) => hex::encode_upper(s),
-                        };
-                        res.push_str(&value);
-                    } else {
-                        log::trace!("xt_get_tcap: unknown name {}", name);
-                        res.push_str("0+r");
-                        res.push_str(&encoded_name);
-                    }
                 }
             }
             res.push_str("\x1b\\");
@@ -1186,6 +1211,7 @@ impl TerminalState {
                 ident.push_str(";6"); // Selective erase
                 ident.push_str(";18"); // windowing extensions
                 ident.push_str(";22"); // ANSI color, vt525
+                ident.push_str(";29"); // ANSI text locator
                 ident.push('c');
 
                 self.writer.write(ident.as_bytes()).ok();
@@ -1822,7 +1848,20 @@ impl TerminalState {
         checksum
     }
 
+    fn request_window_operation(&mut self, op: WindowOperation) {
+        if let Some(handler) = self.alert_handler.as_mut() {
+            handler.alert(Alert::WindowOperation(op));
+        }
+    }
+
     fn perform_csi_window(&mut self, window: Window) {
+        if let Some(kind) = WindowOperationKind::for_csi(&window) {
+            if !self.config.allow_window_operation(kind) {
+                log::debug!("window operation {:?} is not allowed", window);
+                return;
+            }
+        }
+
         match window {
             Window::ReportTextAreaSizeCells => {
                 let screen = self.screen();
@@ -1865,6 +1904,16 @@ impl TerminalState {
                 self.writer.flush().ok();
             }
 
+            Window::ReportIconLabel => {
+                write!(
+                    self.writer,
+                    "{}",
+                    OperatingSystemCommand::SetIconNameSun(self.get_title().to_string())
+                )
+                .ok();
+                self.writer.flush().ok();
+            }
+
             Window::ChecksumRectangularArea {
                 request_id,
                 top,
@@ -1882,11 +1931,60 @@ impl TerminalState {
                 write!(self.writer, "\x1bP{}!~{:04x}\x1b\\", request_id, checksum).ok();
                 self.writer.flush().ok();
             }
-            Window::ResizeWindowCells { .. } => {
-                // We don't allow the application to change the window size; that's
-                // up to the user!
+            Window::ResizeWindowCells { width, height } => {
+                self.request_window_operation(WindowOperation::ResizeCells { width, height })
+            }
+            Window::ResizeWindowPixels { width, height } => {
+                self.request_window_operation(WindowOperation::ResizePixels { width, height })
+            }
+            Window::MoveWindow { x, y } => {
+                self.request_window_operation(WindowOperation::Move { x, y })
+            }
+            Window::Iconify => self.request_window_operation(WindowOperation::Iconify),
+            Window::DeIconify => self.request_window_operation(WindowOperation::DeIconify),
+            Window::RaiseWindow => self.request_window_operation(WindowOperation::Raise),
+            Window::LowerWindow => self.request_window_operation(WindowOperation::Lower),
+            Window::MaximizeWindow
+            | Window::MaximizeWindowVertically
+            | Window::MaximizeWindowHorizontally => {
+                // We can't maximize in a single direction, so do
+                // the next best thing
+                self.request_window_operation(WindowOperation::Maximize)
+            }
+            Window::RestoreMaximizedWindow => {
+                self.request_window_operation(WindowOperation::RestoreMaximized)
+            }
+            Window::ChangeToFullScreenMode => {
+                self.request_window_operation(WindowOperation::FullScreen)
+            }
+            Window::UndoFullScreenMode => {
+                self.request_window_operation(WindowOperation::UndoFullScreen)
+            }
+            Window::ToggleFullScreen => {
+                self.request_window_operation(WindowOperation::ToggleFullScreen)
+            }
+            Window::ReportWindowState => {
+                self.request_window_operation(WindowOperation::ReportState)
+            }
+            Window::ReportWindowPosition => {
+                self.request_window_operation(WindowOperation::ReportWindowPosition)
+            }
+            Window::ReportTextAreaPosition => {
+                self.request_window_operation(WindowOperation::ReportTextAreaPosition)
+            }
+            Window::ReportWindowSizePixels => {
+                self.request_window_operation(WindowOperation::ReportWindowSizePixels)
+            }
+            Window::ReportScreenSizePixels => {
+                self.request_window_operation(WindowOperation::ReportScreenSizePixels)
+            }
+            Window::ReportScreenSizeCells => {
+                self.request_window_operation(WindowOperation::ReportScreenSizeCells)
+            }
+            Window::RefreshWindow => {
+                // We redraw whenever the content changes, so
+                // there is nothing to refresh
             }
-            Window::Iconify | Window::DeIconify => {}
             Window::PopIconAndWindowTitle
             | Window::PopWindowTitle
             | Window::PopIconTitle
@@ -1913,7 +2011,14 @@ impl TerminalState {
                 self.perform_csi_edit(Edit::EraseInLine(EraseInLine::EraseToStartOfLine));
                 0..cy
             }
-            EraseInDisplay::EraseDisplay => 0..rows,
+            EraseInDisplay::EraseDisplay => {
+                if self.screen.is_alt_screen_active() {
+                    let bidi_mode = self.get_bidi_mode();
+                    self.screen_mut()
+                        .save_visible_to_scrollback(seqno, pen.clone(), bidi_mode);
+                }
+                0..rows
+            }
             EraseInDisplay::EraseScrollback => {
                 self.screen_mut().erase_scrollback();
                 return;
@@ -2529,6 +2634,55 @@ impl TerminalState {
         Ok(zones)
     }
 
+    /// Returns the text of the most recent input zone, searching upwards
+    /// from the cursor.  This is the command that was entered at the
+    /// prompt when the shell marks the start of its output.
+    fn text_of_last_input_zone(&mut self) -> String {
+        // Bound the search so that a missing marker can't cause us
+        // to walk the entire scrollback
+        const MAX_ROWS: usize = 64;
+
+        let cursor_y = self.cursor.y;
+        let screen = self.screen_mut();
+        let last_phys = screen.phys_row(cursor_y);
+        let first_phys = last_phys.saturating_sub(MAX_ROWS);
+
+        let mut rows: Vec<(String, bool)> = vec![];
+        for phys in (first_phys..=last_phys).rev() {
+            let line = screen.line_mut(phys);
+            let mut text = String::new();
+            let mut starts_zone = false;
+            for (_, cell) in line.visible_cells() {
+                if cell.attrs().semantic_type() == SemanticType::Input {
+                    text.push_str(cell.str());
+                } else if text.is_empty() {
+                    starts_zone = true;
+                }
+            }
+            if text.is_empty() {
+                if rows.is_empty() {
+                    continue;
+                }
+                break;
+            }
+            rows.push((text, line.last_cell_was_wrapped()));
+            if starts_zone {
+                break;
+            }
+        }
+
+        let mut command = String::new();
+        for (text, wrapped) in rows.into_iter().rev() {
+            if wrapped {
+                command.push_str(&text);
+            } else {
+                command.push_str(text.trim_end());
+                command.push('\n');
+            }
+        }
+        command.trim().to_string()
+    }
+
     #[inline]
     pub fn get_reverse_video(&self) -> bool {
         self.reverse_video_mode
@@ -2,6 +2,43 @@ use crate::input::*;
 use crate::terminalstate::MouseEncoding;
 use crate::TerminalState;
 use anyhow::bail;
+use termwiz::escape::csi::{
+    Locator, LocatorButtons, LocatorEvent, LocatorEventSelection, LocatorReportingMode,
+    LocatorUnits,
+};
+use termwiz::escape::CSI;
+
+/// The rectangle that the DEC locator must leave before its
+/// position is reported, in the units of the locator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LocatorFilter {
+    top: i64,
+    left: i64,
+    bottom: i64,
+    right: i64,
+}
+
+/// The state of the DEC locator, which is an alternative to the
+/// mouse reporting modes that reports the position of the mouse in
+/// cells or pixels when the application requests it, or when the
+/// selected button events occur
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LocatorState {
+    enabled: bool,
+    one_shot: bool,
+    pixels: bool,
+    report_button_down: bool,
+    report_button_up: bool,
+    filter: Option<LocatorFilter>,
+}
+
+impl LocatorState {
+    /// Returns true if the locator reports button events, which
+    /// means that the mouse is grabbed by the application
+    pub(crate) fn reports_buttons(&self) -> bool {
+        self.enabled && (self.report_button_down || self.report_button_up)
+    }
+}
 
 impl TerminalState {
     /// Encode a coordinate value using X10 encoding.
@@ -31,6 +68,8 @@ impl TerminalState {
             MouseButton::Right => 2,
             MouseButton::WheelUp(_) => 64,
             MouseButton::WheelDown(_) => 65,
+            MouseButton::WheelLeft(_) => 66,
+            MouseButton::WheelRight(_) => 67,
         };
 
         if event.modifiers.contains(KeyModifiers::SHIFT) {
@@ -85,12 +124,21 @@ impl TerminalState {
             )?;
             self.writer.flush()?;
         } else if self.screen.is_alt_screen_active() {
+            if matches!(
+                event.button,
+                MouseButton::WheelLeft(_) | MouseButton::WheelRight(_)
+            ) && !self.config.alternate_buffer_horizontal_wheel_arrow_keys()
+            {
+                return Ok(());
+            }
             // Send cursor keys instead (equivalent to xterm's alternateScroll mode)
             for _ in 0..self.config.alternate_buffer_wheel_scroll_speed() {
                 self.key_down(
                     match event.button {
                         MouseButton::WheelDown(_) => KeyCode::DownArrow,
                         MouseButton::WheelUp(_) => KeyCode::UpArrow,
+                        MouseButton::WheelLeft(_) => KeyCode::LeftArrow,
+                        MouseButton::WheelRight(_) => KeyCode::RightArrow,
                         _ => bail!("unexpected mouse event"),
                     },
                     KeyModifiers::default(),
@@ -255,6 +303,179 @@ impl TerminalState {
         Ok(())
     }
 
+    /// Returns the position of the mouse in the units of the locator,
+    /// as a one-based (row, column) pair.
+    /// The mouse can be above the top of the terminal, for example,
+    /// while dragging, so the row is clamped to the first row.
+    fn locator_position(&self, event: &MouseEvent) -> (i64, i64) {
+        let y = event.y.max(0);
+        if self.locator.pixels {
+            let height = self.screen.physical_rows as usize;
+            let width = self.screen.physical_cols as usize;
+            let y_pixel_offset = if event.y < 0 {
+                0
+            } else {
+                event.y_pixel_offset.max(0)
+            };
+            (
+                (y as usize * (self.pixel_height / height)) as i64 + y_pixel_offset as i64 + 1,
+                (event.x * (self.pixel_width / width)) as i64
+                    + event.x_pixel_offset.max(0) as i64
+                    + 1,
+            )
+        } else {
+            (y + 1, event.x as i64 + 1)
+        }
+    }
+
+    fn locator_buttons(&self) -> LocatorButtons {
+        let mut buttons = LocatorButtons::NONE;
+        for button in &self.current_mouse_buttons {
+            buttons |= match button {
+                MouseButton::Left => LocatorButtons::LEFT,
+                MouseButton::Middle => LocatorButtons::MIDDLE,
+                MouseButton::Right => LocatorButtons::RIGHT,
+                _ => LocatorButtons::NONE,
+            };
+        }
+        buttons
+    }
+
+    /// Sends a DECLRP locator report.  Any report cancels the filter
+    /// rectangle, and turns off a one-shot locator.
+    fn locator_report(&mut self, event: LocatorEvent) -> anyhow::Result<()> {
+        let report = match self.mouse_position {
+            Some(position) => {
+                let (row, column) = self.locator_position(&position);
+                Locator::Report {
+                    event,
+                    buttons: self.locator_buttons(),
+                    row,
+                    column,
+                    page: 1,
+                }
+            }
+            None => Locator::Unavailable,
+        };
+        write!(self.writer, "{}", CSI::Locator(report))?;
+        self.writer.flush()?;
+
+        self.locator.filter.take();
+        if self.locator.one_shot {
+            self.locator.enabled = false;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn perform_csi_locator(&mut self, locator: Locator) -> anyhow::Result<()> {
+        match locator {
+            Locator::EnableReporting { mode, units } => {
+                self.locator.enabled = mode != LocatorReportingMode::Off;
+                self.locator.one_shot = mode == LocatorReportingMode::OneShot;
+                self.locator.pixels = units == LocatorUnits::DevicePixels;
+                self.locator.filter.take();
+            }
+            Locator::SelectEvents(events) => {
+                for event in events {
+                    match event {
+                        LocatorEventSelection::ExplicitOnly => {
+                            self.locator.report_button_down = false;
+                            self.locator.report_button_up = false;
+                        }
+                        LocatorEventSelection::ButtonDown => self.locator.report_button_down = true,
+                        LocatorEventSelection::NoButtonDown => {
+                            self.locator.report_button_down = false
+                        }
+                        LocatorEventSelection::ButtonUp => self.locator.report_button_up = true,
+                        LocatorEventSelection::NoButtonUp => self.locator.report_button_up = false,
+                    }
+                }
+            }
+            Locator::FilterRectangle {
+                top,
+                left,
+                bottom,
+                right,
+            } => {
+                if !self.locator.enabled {
+                    return Ok(());
+                }
+                let (row, column) = match self.mouse_position {
+                    Some(position) => self.locator_position(&position),
+                    None => return self.locator_report(LocatorEvent::OutsideFilterRectangle),
+                };
+                let filter = LocatorFilter {
+                    top: top.unwrap_or(row),
+                    left: left.unwrap_or(column),
+                    bottom: bottom.unwrap_or(row),
+                    right: right.unwrap_or(column),
+                };
+                if row < filter.top
+                    || row > filter.bottom
+                    || column < filter.left
+                    || column > filter.right
+                {
+                    return self.locator_report(LocatorEvent::OutsideFilterRectangle);
+                }
+                self.locator.filter.replace(filter);
+            }
+            Locator::RequestPosition => {
+                if self.locator.enabled {
+                    return self.locator_report(LocatorEvent::Request);
+                }
+                write!(self.writer, "{}", CSI::Locator(Locator::Unavailable))?;
+                self.writer.flush()?;
+            }
+            Locator::Report { .. } | Locator::Unavailable => {
+                log::error!("locator report sent by app? {:?}", locator);
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles a mouse event while the locator is enabled, in place
+    /// of the mouse reporting modes
+    fn locator_mouse_event(&mut self, event: MouseEvent) -> anyhow::Result<()> {
+        let locator_event = match (event.kind, event.button) {
+            (MouseEventKind::Press, MouseButton::Left) => LocatorEvent::LeftButtonDown,
+            (MouseEventKind::Release, MouseButton::Left) => LocatorEvent::LeftButtonUp,
+            (MouseEventKind::Press, MouseButton::Middle) => LocatorEvent::MiddleButtonDown,
+            (MouseEventKind::Release, MouseButton::Middle) => LocatorEvent::MiddleButtonUp,
+            (MouseEventKind::Press, MouseButton::Right) => LocatorEvent::RightButtonDown,
+            (MouseEventKind::Release, MouseButton::Right) => LocatorEvent::RightButtonUp,
+            (MouseEventKind::Move, _) => {
+                if let Some(filter) = self.locator.filter {
+                    let (row, column) = self.locator_position(&event);
+                    if row < filter.top
+                        || row > filter.bottom
+                        || column < filter.left
+                        || column > filter.right
+                    {
+                        return self.locator_report(LocatorEvent::OutsideFilterRectangle);
+                    }
+                }
+                return Ok(());
+            }
+            _ => return self.mouse_wheel(event),
+        };
+
+        let report = match event.kind {
+            MouseEventKind::Press => {
+                self.current_mouse_buttons.retain(|&b| b != event.button);
+                self.current_mouse_buttons.push(event.button);
+                self.locator.report_button_down
+            }
+            _ => {
+                self.current_mouse_buttons.retain(|&b| b != event.button);
+                self.locator.report_button_up
+            }
+        };
+        if report {
+            self.locator_report(locator_event)?;
+        }
+        Ok(())
+    }
+
     /// Informs the terminal of a mouse event.
     /// If mouse reporting has been activated, the mouse event will be encoded
     /// appropriately and written to the associated writer.
@@ -267,6 +488,11 @@ impl TerminalState {
         // make sure that we clamp this and handle it nicely at the model layer.
         event.y = event.y.min(self.screen().physical_rows as i64 - 1);
         event.x = event.x.min(self.screen().physical_cols - 1);
+        self.mouse_position.replace(event);
+
+        if self.locator.enabled {
+            return self.locator_mouse_event(event);
+        }
 
         match event {
             MouseEvent {
@@ -278,6 +504,16 @@ impl TerminalState {
                 kind: MouseEventKind::Press,
                 button: MouseButton::WheelDown(_),
                 ..
+            }
+            | MouseEvent {
+                kind: MouseEventKind::Press,
+                button: MouseButton::WheelLeft(_),
+                ..
+            }
+            | MouseEvent {
+                kind: MouseEventKind::Press,
+                button: MouseButton::WheelRight(_),
+                ..
             } => self.mouse_wheel(event),
             MouseEvent {
                 kind: MouseEventKind::Press,
@@ -1,4 +1,5 @@
 use crate::terminal::Alert;
+use crate::terminalstate::report::{cursor_style_param, sgr_params};
 use crate::terminalstate::{
     default_color_map, CharSet, MouseEncoding, TabStop, UnicodeVersionStackEntry,
 };
@@ -261,6 +262,24 @@ impl<'a> Performer<'a> {
                                 .ok();
                                 self.writer.flush().ok();
                             }
+                            &[b'm'] => {
+                                // SGR - the current graphic rendition
+                                let params = sgr_params(&self.pen);
+                                write!(self.writer, "{}1$r{}m{}", DCS, params, ST).ok();
+                                self.writer.flush().ok();
+                            }
+                            &[b' ', b'q'] => {
+                                // DECSCUSR - cursor style
+                                let style = cursor_style_param(self.cursor.shape);
+                                write!(self.writer, "{}1$r{} q{}", DCS, style, ST).ok();
+                                self.writer.flush().ok();
+                            }
+                            &[b't'] => {
+                                // DECSLPP - lines per page
+                                let rows = self.screen().physical_rows;
+                                write!(self.writer, "{}1$r{}t{}", DCS, rows, ST).ok();
+                                self.writer.flush().ok();
+                            }
                             _ => {
                                 log::warn!("unhandled DECRQSS {:?}", s);
                                 // Reply that the request is invalid
@@ -365,6 +384,9 @@ impl<'a> Performer<'a> {
             ControlCode::IND => self.c1_index(),
             ControlCode::NEL => self.c1_nel(),
             ControlCode::Bell => {
+                if !self.focused {
+                    self.unseen_bell_count += 1;
+                }
                 if let Some(handler) = self.alert_handler.as_mut() {
                     handler.alert(Alert::Bell);
                 } else {
@@ -481,6 +503,11 @@ impl<'a> Performer<'a> {
                     self.writer.flush().ok();
                 }
             }
+            CSI::Locator(locator) => {
+                if let Err(err) = self.state.perform_csi_locator(locator) {
+                    log::error!("failed to write locator report: {:#}", err);
+                }
+            }
             CSI::Keyboard(Keyboard::ReportKittyState(_)) => {
                 // This is a response to QueryKittySupport and it is invalid for us
                 // to receive it. Just ignore it.
@@ -607,6 +634,7 @@ impl<'a> Performer<'a> {
                 self.any_event_mouse = false;
                 self.button_event_mouse = false;
                 self.current_mouse_buttons.clear();
+                self.locator = Default::default();
                 self.cursor_visible = true;
                 self.g0_charset = CharSet::Ascii;
                 self.g1_charset = CharSet::DecLineDrawing;
@@ -681,7 +709,11 @@ impl<'a> Performer<'a> {
                 let selection = selection_to_selection(selection);
                 self.set_clipboard_contents(selection, None).ok();
             }
-            OperatingSystemCommand::QuerySelection(_) => {}
+            OperatingSystemCommand::QuerySelection(selection) => {
+                if let Some(handler) = self.alert_handler.as_mut() {
+                    handler.alert(Alert::ClipboardQuery(selection_to_selection(selection)));
+                }
+            }
             OperatingSystemCommand::SetSelection(selection, selection_data) => {
                 let selection = selection_to_selection(selection);
                 match self.set_clipboard_contents(selection, Some(selection_data)) {
@@ -787,11 +819,23 @@ impl<'a> Performer<'a> {
                 FinalTermSemanticPrompt::MarkEndOfInputAndStartOfOutput { .. },
             ) => {
                 self.pen.set_semantic_type(SemanticType::Output);
+                if self.alert_handler.is_some() {
+                    let command = self.text_of_last_input_zone();
+                    if !command.is_empty() {
+                        if let Some(handler) = self.alert_handler.as_mut() {
+                            handler.alert(Alert::CommandStarted { command });
+                        }
+                    }
+                }
             }
 
             OperatingSystemCommand::FinalTermSemanticPrompt(
-                FinalTermSemanticPrompt::CommandStatus { .. },
-            ) => {}
+                FinalTermSemanticPrompt::CommandStatus { status, .. },
+            ) => {
+                if let Some(handler) = self.alert_handler.as_mut() {
+                    handler.alert(Alert::CommandFinished { status });
+                }
+            }
 
             OperatingSystemCommand::SystemNotification(message) => {
                 if let Some(handler) = self.alert_handler.as_mut() {
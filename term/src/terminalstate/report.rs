@@ -0,0 +1,175 @@
+//! Reports the state of the terminal; both to the application,
+//! via DECRQSS, and as a whole, for debugging and for tools that
+//! snapshot and restore the terminal state.
+use super::*;
+use termwiz::cell::{Blink, CellAttributes, Intensity, Underline};
+use termwiz::color::{ColorAttribute, ColorSpec};
+use termwiz::escape::csi::Sgr;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+
+/// A snapshot of the modes and other state of a terminal that
+/// isn't otherwise visible in its content
+#[derive(Debug, Clone, PartialEq, Eq, Default, FromDynamic, ToDynamic)]
+pub struct TerminalStateReport {
+    /// The cursor position, relative to the top left of the screen.
+    /// 0-based.
+    pub cursor_x: usize,
+    pub cursor_y: i64,
+    /// The DECSCUSR parameter for the cursor shape
+    pub cursor_style: u8,
+    pub cursor_visible: bool,
+    /// The SGR parameters that describe the current pen
+    pub sgr: String,
+
+    /// The scroll region, as 0-based inclusive row and column indices
+    pub top_margin: i64,
+    pub bottom_margin: i64,
+    pub left_margin: usize,
+    pub right_margin: usize,
+
+    /// The 0-based columns that have tab stops
+    pub tab_stops: Vec<usize>,
+
+    /// The designated G0 and G1 character sets, and which of
+    /// them is currently invoked
+    pub g0_charset: String,
+    pub g1_charset: String,
+    pub shift_out: bool,
+
+    pub alt_screen_active: bool,
+    pub auto_wrap: bool,
+    pub reverse_wraparound: bool,
+    pub origin_mode: bool,
+    pub insert_mode: bool,
+    pub newline_mode: bool,
+    pub reverse_video: bool,
+    pub left_and_right_margin_mode: bool,
+    pub application_cursor_keys: bool,
+    pub application_keypad: bool,
+    pub bracketed_paste: bool,
+    pub focus_tracking: bool,
+    pub mouse_tracking: bool,
+    pub button_event_mouse: bool,
+    pub any_event_mouse: bool,
+    pub mouse_encoding: String,
+    pub keyboard_encoding: String,
+}
+
+fn color_spec(color: ColorAttribute) -> ColorSpec {
+    match color {
+        ColorAttribute::TrueColorWithPaletteFallback(color, _)
+        | ColorAttribute::TrueColorWithDefaultFallback(color) => ColorSpec::TrueColor(color),
+        ColorAttribute::PaletteIndex(idx) => ColorSpec::PaletteIndex(idx),
+        ColorAttribute::Default => ColorSpec::Default,
+    }
+}
+
+/// Returns the SGR parameters that select `attrs` after a reset,
+/// in the form used by DECRPSS; eg: `0;1;38:2::255:0:0`
+pub(crate) fn sgr_params(attrs: &CellAttributes) -> String {
+    let mut sgrs = vec![Sgr::Reset];
+    if attrs.intensity() != Intensity::Normal {
+        sgrs.push(Sgr::Intensity(attrs.intensity()));
+    }
+    if attrs.italic() {
+        sgrs.push(Sgr::Italic(true));
+    }
+    if attrs.underline() != Underline::None {
+        sgrs.push(Sgr::Underline(attrs.underline()));
+    }
+    if attrs.blink() != Blink::None {
+        sgrs.push(Sgr::Blink(attrs.blink()));
+    }
+    if attrs.reverse() {
+        sgrs.push(Sgr::Inverse(true));
+    }
+    if attrs.invisible() {
+        sgrs.push(Sgr::Invisible(true));
+    }
+    if attrs.strikethrough() {
+        sgrs.push(Sgr::StrikeThrough(true));
+    }
+    if attrs.overline() {
+        sgrs.push(Sgr::Overline(true));
+    }
+    for (color, sgr) in [
+        (attrs.foreground(), Sgr::Foreground as fn(ColorSpec) -> Sgr),
+        (attrs.background(), Sgr::Background),
+        (attrs.underline_color(), Sgr::UnderlineColor),
+    ] {
+        if color != ColorAttribute::Default {
+            sgrs.push(sgr(color_spec(color)));
+        }
+    }
+
+    sgrs.iter()
+        .map(|sgr| sgr.to_string().trim_end_matches('m').to_string())
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Returns the DECSCUSR parameter that selects `shape`
+pub(crate) fn cursor_style_param(shape: CursorShape) -> u8 {
+    match shape {
+        CursorShape::Default => 0,
+        CursorShape::BlinkingBlock => 1,
+        CursorShape::SteadyBlock => 2,
+        CursorShape::BlinkingUnderline => 3,
+        CursorShape::SteadyUnderline => 4,
+        CursorShape::BlinkingBar => 5,
+        CursorShape::SteadyBar => 6,
+    }
+}
+
+fn charset_name(charset: CharSet) -> String {
+    match charset {
+        CharSet::Ascii => "Ascii",
+        CharSet::Uk => "Uk",
+        CharSet::DecLineDrawing => "DecLineDrawing",
+    }
+    .to_string()
+}
+
+impl TerminalState {
+    /// Returns a snapshot of the modes and other state of the terminal
+    pub fn terminal_state_report(&self) -> TerminalStateReport {
+        TerminalStateReport {
+            cursor_x: self.cursor.x,
+            cursor_y: self.cursor.y,
+            cursor_style: cursor_style_param(self.cursor.shape),
+            cursor_visible: self.cursor_visible,
+            sgr: sgr_params(&self.pen),
+            top_margin: self.top_and_bottom_margins.start,
+            bottom_margin: self.top_and_bottom_margins.end - 1,
+            left_margin: self.left_and_right_margins.start,
+            right_margin: self.left_and_right_margins.end.saturating_sub(1),
+            tab_stops: self
+                .tabs
+                .tabs
+                .iter()
+                .enumerate()
+                .filter_map(|(col, &stop)| if stop { Some(col) } else { None })
+                .collect(),
+            g0_charset: charset_name(self.g0_charset),
+            g1_charset: charset_name(self.g1_charset),
+            shift_out: self.shift_out,
+            alt_screen_active: self.screen.is_alt_screen_active(),
+            auto_wrap: self.dec_auto_wrap,
+            reverse_wraparound: self.reverse_wraparound_mode,
+            origin_mode: self.dec_origin_mode,
+            insert_mode: self.insert,
+            newline_mode: self.newline_mode,
+            reverse_video: self.reverse_video_mode,
+            left_and_right_margin_mode: self.left_and_right_margin_mode,
+            application_cursor_keys: self.application_cursor_keys,
+            application_keypad: self.application_keypad,
+            bracketed_paste: self.bracketed_paste,
+            focus_tracking: self.focus_tracking,
+            mouse_tracking: self.mouse_tracking,
+            button_event_mouse: self.button_event_mouse,
+            any_event_mouse: self.any_event_mouse,
+            mouse_encoding: format!("{:?}", self.mouse_encoding),
+            keyboard_encoding: format!("{:?}", self.keyboard_encoding),
+        }
+    }
+}
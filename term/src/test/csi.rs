@@ -222,3 +222,26 @@ fn test_ed_erase_scrollback() {
     term.print("b");
     assert_all_contents(&term, file!(), line!(), &["111", "222", "ab "]);
 }
+
+#[test]
+fn test_terminal_state_report() {
+    let mut term = TestTerm::new(5, 10, 0);
+    term.set_scroll_region(1, 3);
+    term.print("\x1b[1;4;38:2::255:0:0m\x1b[3 q\x1b(0");
+
+    let report = term.terminal_state_report();
+    assert_eq!(report.top_margin, 1);
+    assert_eq!(report.bottom_margin, 3);
+    assert_eq!(report.left_margin, 0);
+    assert_eq!(report.right_margin, 9);
+    assert_eq!(report.sgr, "0;1;4;38:2::255:0:0");
+    assert_eq!(report.cursor_style, 3);
+    assert_eq!(report.tab_stops, vec![0, 8]);
+    assert_eq!(report.g0_charset, "DecLineDrawing");
+    assert!(report.auto_wrap);
+
+    term.print("\x1b[0m\x1b(B");
+    let report = term.terminal_state_report();
+    assert_eq!(report.sgr, "0");
+    assert_eq!(report.g0_charset, "Ascii");
+}
@@ -46,12 +46,22 @@ struct TestTerm {
 #[derive(Debug)]
 struct TestTermConfig {
     scrollback: usize,
+    reflow_on_resize: bool,
+    alt_scrollback: usize,
 }
 impl TerminalConfiguration for TestTermConfig {
     fn scrollback_size(&self) -> usize {
         self.scrollback
     }
 
+    fn alternate_screen_scrollback_size(&self) -> usize {
+        self.alt_scrollback
+    }
+
+    fn reflow_on_resize(&self) -> bool {
+        self.reflow_on_resize
+    }
+
     fn color_palette(&self) -> ColorPalette {
         ColorPalette::default()
     }
@@ -59,6 +69,18 @@ impl TerminalConfiguration for TestTermConfig {
 
 impl TestTerm {
     fn new(height: usize, width: usize, scrollback: usize) -> Self {
+        Self::with_config(
+            height,
+            width,
+            TestTermConfig {
+                scrollback,
+                reflow_on_resize: true,
+                alt_scrollback: 0,
+            },
+        )
+    }
+
+    fn with_config(height: usize, width: usize, config: TestTermConfig) -> Self {
         let _ = env_logger::Builder::new()
             .is_test(true)
             .filter_level(log::LevelFilter::Trace)
@@ -72,7 +94,7 @@ impl TestTerm {
                 pixel_height: height * 16,
                 dpi: 0,
             },
-            Arc::new(TestTermConfig { scrollback }),
+            Arc::new(config),
             "WezTerm",
             "O_o",
             Box::new(Vec::new()),
@@ -358,6 +380,100 @@ fn test_semantic_1539() {
     );
 }
 
+#[test]
+fn test_command_alerts() {
+    use termwiz::escape::osc::FinalTermSemanticPrompt;
+
+    struct CollectAlerts(Arc<std::sync::Mutex<Vec<Alert>>>);
+    impl AlertHandler for CollectAlerts {
+        fn alert(&mut self, alert: Alert) {
+            self.0.lock().unwrap().push(alert);
+        }
+    }
+
+    let alerts = Arc::new(std::sync::Mutex::new(vec![]));
+    let mut term = TestTerm::new(5, 10, 0);
+    term.set_notification_handler(Box::new(CollectAlerts(Arc::clone(&alerts))));
+    term.print(format!(
+        "{}$ {}echo hello\r\nworld{}\r\nhi\r\n{}",
+        OperatingSystemCommand::FinalTermSemanticPrompt(
+            FinalTermSemanticPrompt::FreshLineAndStartPrompt {
+                aid: None,
+                cl: None
+            }
+        ),
+        OperatingSystemCommand::FinalTermSemanticPrompt(
+            FinalTermSemanticPrompt::MarkEndOfPromptAndStartOfInputUntilNextMarker
+        ),
+        OperatingSystemCommand::FinalTermSemanticPrompt(
+            FinalTermSemanticPrompt::MarkEndOfInputAndStartOfOutput { aid: None }
+        ),
+        OperatingSystemCommand::FinalTermSemanticPrompt(FinalTermSemanticPrompt::CommandStatus {
+            status: 1,
+            aid: None
+        }),
+    ));
+
+    assert_eq!(
+        *alerts.lock().unwrap(),
+        vec![
+            Alert::CommandStarted {
+                command: "echo hello\nworld".to_string()
+            },
+            Alert::CommandFinished { status: 1 },
+        ]
+    );
+}
+
+#[test]
+fn test_window_operation_alerts() {
+    struct CollectAlerts(Arc<std::sync::Mutex<Vec<Alert>>>);
+    impl AlertHandler for CollectAlerts {
+        fn alert(&mut self, alert: Alert) {
+            self.0.lock().unwrap().push(alert);
+        }
+    }
+
+    let alerts = Arc::new(std::sync::Mutex::new(vec![]));
+    let mut term = TestTerm::new(5, 10, 0);
+    term.set_notification_handler(Box::new(CollectAlerts(Arc::clone(&alerts))));
+    // Iconify and resize are denied by default; the reports are
+    // allowed and need the gui to answer them
+    term.print("\x1b[2t\x1b[8;10;20t\x1b[13t\x1b[15t\x1b[11t");
+
+    assert_eq!(
+        *alerts.lock().unwrap(),
+        vec![
+            Alert::WindowOperation(WindowOperation::ReportWindowPosition),
+            Alert::WindowOperation(WindowOperation::ReportScreenSizePixels),
+            Alert::WindowOperation(WindowOperation::ReportState),
+        ]
+    );
+}
+
+#[test]
+fn test_clipboard_query_alert() {
+    struct CollectAlerts(Arc<std::sync::Mutex<Vec<Alert>>>);
+    impl AlertHandler for CollectAlerts {
+        fn alert(&mut self, alert: Alert) {
+            self.0.lock().unwrap().push(alert);
+        }
+    }
+
+    let alerts = Arc::new(std::sync::Mutex::new(vec![]));
+    let mut term = TestTerm::new(5, 10, 0);
+    term.set_notification_handler(Box::new(CollectAlerts(Arc::clone(&alerts))));
+    term.print("\x1b]52;c;?\x1b\\\x1b]52;p;?\x07");
+
+    assert_eq!(
+        *alerts.lock().unwrap(),
+        vec![
+            Alert::ClipboardQuery(ClipboardSelection::Clipboard),
+            Alert::ClipboardQuery(ClipboardSelection::PrimarySelection),
+        ]
+    );
+}
+
 #[test]
 fn test_semantic() {
     use termwiz::escape::osc::FinalTermSemanticPrompt;
@@ -981,6 +1097,51 @@ fn test_resize_wrap() {
     );
 }
 
+/// Test that wrapped lines in the scrollback are rewrapped along
+/// with the visible lines
+#[test]
+fn test_resize_wrap_scrollback() {
+    let mut term = TestTerm::new(2, 4, 4);
+    term.print("2222aa\r\n333\r\nb");
+    assert_all_contents(&term, file!(), line!(), &["2222", "aa  ", "333 ", "b   "]);
+    term.resize(TerminalSize {
+        rows: 2,
+        cols: 6,
+        ..Default::default()
+    });
+    assert_all_contents(&term, file!(), line!(), &["2222aa", "333 ", "b   "]);
+    term.resize(TerminalSize {
+        rows: 2,
+        cols: 3,
+        ..Default::default()
+    });
+    assert_all_contents(&term, file!(), line!(), &["222", "2aa", "333", "b"]);
+}
+
+/// When reflow is disabled, narrowing the terminal truncates
+/// the lines rather than rewrapping them
+#[test]
+fn test_resize_no_reflow() {
+    const LINES: usize = 4;
+    let mut term = TestTerm::with_config(
+        LINES,
+        4,
+        TestTermConfig {
+            scrollback: 0,
+            reflow_on_resize: false,
+            alt_scrollback: 0,
+        },
+    );
+    term.print("111\r\n2222aa\r\n");
+    assert_visible_contents(&term, file!(), line!(), &["111 ", "2222", "aa  ", "    "]);
+    term.resize(TerminalSize {
+        rows: LINES,
+        cols: 2,
+        ..Default::default()
+    });
+    assert_visible_contents(&term, file!(), line!(), &["11", "22", "aa", "  "]);
+}
+
 #[test]
 fn test_resize_wrap_issue_971() {
     const LINES: usize = 4;
@@ -1238,3 +1399,74 @@ fn test_hyperlinks() {
         Compare::TEXT | Compare::ATTRS,
     );
 }
+
+#[test]
+fn test_unseen_bell_count() {
+    let mut term = TestTerm::new(3, 10, 0);
+    term.print("\x07");
+    assert_eq!(term.unseen_bell_count(), 0);
+
+    term.focus_changed(false);
+    term.print("\x07hello\x07");
+    assert_eq!(term.unseen_bell_count(), 2);
+    assert!(term.has_unseen_output());
+
+    term.focus_changed(true);
+    assert_eq!(term.unseen_bell_count(), 0);
+    assert!(!term.has_unseen_output());
+}
+
+/// With alternate screen scrollback enabled, content that scrolls off
+/// the top of the alternate screen, or is cleared from it, is retained
+#[test]
+fn test_alt_screen_scrollback() {
+    let mut term = TestTerm::with_config(
+        3,
+        4,
+        TestTermConfig {
+            scrollback: 0,
+            reflow_on_resize: true,
+            alt_scrollback: 10,
+        },
+    );
+    term.print("\x1b[?1049h");
+    term.print("aaa\r\nbbb\r\nccc\r\nddd");
+    assert_all_contents(&term, file!(), line!(), &["aaa ", "bbb ", "ccc ", "ddd "]);
+
+    term.print("\x1b[2J");
+    let frame = ["aaa ", "bbb ", "ccc ", "ddd ", "    ", "    ", "    "];
+    assert_all_contents(&term, file!(), line!(), &frame);
+
+    // Clearing a blank screen doesn't save an empty frame
+    term.print("\x1b[2J");
+    assert_all_contents(&term, file!(), line!(), &frame);
+
+    // The primary screen is unaffected
+    term.print("\x1b[?1049l");
+    assert_all_contents(&term, file!(), line!(), &["    ", "    ", "    "]);
+}
+
+#[test]
+fn test_take_scrollback() {
+    let mut term = TestTerm::new(2, 1, 4);
+    term.print("1\n2\n3\n4\n");
+    let lines = term.take_scrollback();
+    assert_eq!(lines.len(), 3);
+    assert_all_contents(&term, file!(), line!(), &["4", " "]);
+    assert_eq!(term.screen().visible_row_to_stable_row(0), 3);
+    assert_eq!(term.screen().hibernated_rows(), 3);
+
+    // Output that arrives in the meantime is retained, and the
+    // oldest lines are discarded if there is no longer room for them
+    term.print("5\n6\n");
+    term.restore_scrollback(lines);
+    assert_all_contents(&term, file!(), line!(), &["2", "3", "4", "5", "6", " "]);
+    assert_eq!(term.screen().visible_row_to_stable_row(0), 5);
+    assert_eq!(term.screen().hibernated_rows(), 0);
+
+    // Lines that were erased while hibernated are not restored
+    let lines = term.take_scrollback();
+    term.print("\x1b[3J");
+    term.restore_scrollback(lines);
+    assert_all_contents(&term, file!(), line!(), &["6", " "]);
+}
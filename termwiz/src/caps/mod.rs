@@ -107,6 +107,10 @@ builder! {
 
         /// Whether mouse support is present and should be used
         mouse_reporting: Option<bool>,
+
+        /// Whether curly, dotted and dashed underlines and the
+        /// underline color (SGR 58) are supported
+        styled_underlines: Option<bool>,
     }
 }
 
@@ -154,6 +158,7 @@ pub struct Capabilities {
     terminfo_db: Option<terminfo::Database>,
     bracketed_paste: bool,
     mouse_reporting: bool,
+    styled_underlines: bool,
 }
 
 impl Capabilities {
@@ -279,6 +284,16 @@ impl Capabilities {
             }
         });
 
+        // The Smulx extended capability is the terminfo convention for
+        // describing support for the styled underline escapes.
+        let styled_underlines = hints.styled_underlines.unwrap_or_else(|| {
+            let has_smulx = terminfo_db
+                .as_ref()
+                .map(|db| db.raw("Smulx").is_some())
+                .unwrap_or(false);
+            has_smulx || hints.term_program.as_ref().map(String::as_ref) == Some("WezTerm")
+        });
+
         let bracketed_paste = hints.bracketed_paste.unwrap_or(true);
         let mouse_reporting = hints.mouse_reporting.unwrap_or(true);
 
@@ -291,6 +306,7 @@ impl Capabilities {
             terminfo_db,
             bracketed_paste,
             mouse_reporting,
+            styled_underlines,
         })
     }
 
@@ -336,6 +352,12 @@ impl Capabilities {
     pub fn mouse_reporting(&self) -> bool {
         self.mouse_reporting
     }
+
+    /// Whether curly, dotted and dashed underlines and the
+    /// underline color are supported
+    pub fn styled_underlines(&self) -> bool {
+        self.styled_underlines
+    }
 }
 
 #[cfg(test)]
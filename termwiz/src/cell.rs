@@ -7,7 +7,8 @@ use crate::widechar_width::WcWidth;
 #[cfg(feature = "use_serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::mem;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
 use wezterm_dynamic::{FromDynamic, ToDynamic};
 
 #[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
@@ -857,6 +858,93 @@ pub const LATEST_UNICODE_VERSION: UnicodeVersion = UnicodeVersion {
     ambiguous_are_wide: false,
 };
 
+/// Overrides the width and/or the default presentation of a range
+/// of codepoints
+#[derive(Clone, Debug, Eq, PartialEq, FromDynamic, ToDynamic)]
+pub struct WidthOverride {
+    /// The first codepoint in the range
+    pub first: u32,
+    /// The last codepoint in the range; if omitted, the range
+    /// holds only `first`
+    #[dynamic(default)]
+    pub last: Option<u32>,
+    /// The number of cells occupied by a grapheme that contains
+    /// any of the codepoints
+    #[dynamic(default)]
+    pub width: Option<u8>,
+    /// The presentation of the codepoints when it is not explicitly
+    /// selected by a variation selector
+    #[dynamic(default)]
+    pub presentation: Option<Presentation>,
+}
+
+impl WidthOverride {
+    fn contains(&self, c: char) -> bool {
+        let c = c as u32;
+        c >= self.first && c <= self.last.unwrap_or(self.first)
+    }
+
+    /// Returns the overridden width of the grapheme `s`, which is the
+    /// largest of the widths that override the codepoints that it contains.
+    /// Cells are either 1 or 2 columns wide, so the width is clamped to
+    /// that range.
+    fn width_of(overrides: &[Self], s: &str) -> Option<usize> {
+        s.chars()
+            .filter_map(|c| {
+                overrides
+                    .iter()
+                    .find(|o| o.width.is_some() && o.contains(c))
+                    .and_then(|o| o.width)
+            })
+            .max()
+            .map(|width| (width as usize).max(1).min(2))
+    }
+
+    /// Returns the overridden presentation of `c`
+    fn presentation_of(overrides: &[Self], c: char) -> Option<Presentation> {
+        overrides
+            .iter()
+            .find(|o| o.presentation.is_some() && o.contains(c))
+            .and_then(|o| o.presentation)
+    }
+}
+
+/// Avoids taking the lock in the common case that there are no overrides
+static HAS_WIDTH_OVERRIDES: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    static ref WIDTH_OVERRIDES: RwLock<Vec<WidthOverride>> = RwLock::new(vec![]);
+}
+
+/// Sets the overrides that apply to the width and presentation of
+/// codepoints throughout the process.
+/// Where the ranges overlap, the override that is listed first
+/// takes precedence.
+pub fn set_width_overrides(overrides: Vec<WidthOverride>) {
+    let mut current = WIDTH_OVERRIDES.write().unwrap();
+    HAS_WIDTH_OVERRIDES.store(!overrides.is_empty(), Ordering::Relaxed);
+    *current = overrides;
+}
+
+/// Returns the overrides that were set by `set_width_overrides`
+pub fn width_overrides() -> Vec<WidthOverride> {
+    WIDTH_OVERRIDES.read().unwrap().clone()
+}
+
+fn overridden_width(s: &str) -> Option<usize> {
+    if !HAS_WIDTH_OVERRIDES.load(Ordering::Relaxed) {
+        return None;
+    }
+    WidthOverride::width_of(&WIDTH_OVERRIDES.read().unwrap(), s)
+}
+
+pub(crate) fn overridden_presentation(c: char) -> Option<Presentation> {
+    if !HAS_WIDTH_OVERRIDES.load(Ordering::Relaxed) {
+        return None;
+    }
+    WidthOverride::presentation_of(&WIDTH_OVERRIDES.read().unwrap(), c)
+}
+
 /// Returns the number of cells visually occupied by a sequence
 /// of graphemes.
 /// Calls through to `grapheme_column_width` for each grapheme
@@ -898,7 +986,14 @@ pub fn unicode_column_width(s: &str, version: Option<UnicodeVersion>) -> usize {
 /// The terminal emulator can then pass the unicode version through to
 /// the Cell that is used to hold a grapheme, and that per-Cell version
 /// can then be used to calculate width.
+///
+/// Widths that are set by `set_width_overrides` take precedence over
+/// all of the above.
 pub fn grapheme_column_width(s: &str, version: Option<UnicodeVersion>) -> usize {
+    if let Some(width) = overridden_width(s) {
+        return width;
+    }
+
     let version = version.unwrap_or(LATEST_UNICODE_VERSION);
     let ambiguous_are_wide = version.ambiguous_are_wide;
     let version = version.version;
@@ -959,6 +1054,53 @@ mod test {
     use super::*;
     use unicode_segmentation::UnicodeSegmentation;
 
+    #[test]
+    fn width_overrides() {
+        // The overrides are examined directly, rather than being set
+        // process-wide, which would affect tests running concurrently
+        let overrides = [
+            WidthOverride {
+                first: 0xa000,
+                last: Some(0xa010),
+                width: Some(1),
+                presentation: None,
+            },
+            WidthOverride {
+                first: 0xa008,
+                last: None,
+                width: Some(2),
+                presentation: None,
+            },
+            WidthOverride {
+                first: 0x2602,
+                last: None,
+                width: None,
+                presentation: Some(Presentation::Emoji),
+            },
+            WidthOverride {
+                first: 0xfe0f,
+                last: None,
+                width: Some(4),
+                presentation: None,
+            },
+        ];
+        assert_eq!(WidthOverride::width_of(&overrides, "\u{a000}"), Some(1));
+        // The first matching override takes precedence
+        assert_eq!(WidthOverride::width_of(&overrides, "\u{a008}"), Some(1));
+        assert_eq!(WidthOverride::width_of(&overrides, "\u{a011}"), None);
+        assert_eq!(WidthOverride::width_of(&overrides, "\u{2602}"), None);
+        // The widest codepoint wins, and the width is clamped
+        assert_eq!(
+            WidthOverride::width_of(&overrides, "\u{a000}\u{fe0f}"),
+            Some(2)
+        );
+        assert_eq!(
+            WidthOverride::presentation_of(&overrides, '\u{2602}'),
+            Some(Presentation::Emoji)
+        );
+        assert_eq!(WidthOverride::presentation_of(&overrides, '\u{a000}'), None);
+    }
+
     #[test]
     fn teeny_string() {
         let s = TeenyString::from_char('a');
@@ -1,6 +1,7 @@
 use crate::cell::{Cell, CellAttributes};
 use crate::emoji::Presentation;
 use std::borrow::Cow;
+use std::ops::Range;
 use wezterm_bidi::{BidiContext, Direction, ParagraphDirectionHint};
 
 /// A `CellCluster` is another representation of a Line.
@@ -39,6 +40,67 @@ impl CellCluster {
         }
     }
 
+    /// Split the cluster so that the cells either side of each of the
+    /// cell indices in `boundaries` are placed in separate clusters.
+    /// Since each cluster is shaped independently, this prevents
+    /// ligatures from being formed across those boundaries.
+    pub fn split_at_cells(self, boundaries: &[usize]) -> Vec<CellCluster> {
+        let mut split_bytes = vec![];
+        let mut prior_cell = self.first_cell_idx;
+        for (byte_idx, _) in self.text.char_indices() {
+            let cell_idx = self.byte_to_cell_idx(byte_idx);
+            if cell_idx != prior_cell && boundaries.contains(&cell_idx) {
+                split_bytes.push(byte_idx);
+            }
+            prior_cell = cell_idx;
+        }
+        if split_bytes.is_empty() {
+            return vec![self];
+        }
+
+        let mut clusters = vec![];
+        let mut start = 0;
+        for end in split_bytes {
+            clusters.push(self.sub_cluster(start..end));
+            start = end;
+        }
+        clusters.push(self.sub_cluster(start..self.text.len()));
+        clusters
+    }
+
+    /// Returns a cluster comprised of the text in the specified
+    /// byte range, which must start and end on cell boundaries
+    fn sub_cluster(&self, range: Range<usize>) -> CellCluster {
+        let mut width = 0;
+        let mut prior_cell = None;
+        for (byte_idx, _) in self.text[range.clone()].char_indices() {
+            let cell_idx = self.byte_to_cell_idx(range.start + byte_idx);
+            if prior_cell != Some(cell_idx) {
+                width += self.byte_to_cell_width(range.start + byte_idx) as usize;
+                prior_cell = Some(cell_idx);
+            }
+        }
+
+        CellCluster {
+            attrs: self.attrs.clone(),
+            text: self.text[range.clone()].to_string(),
+            width,
+            presentation: self.presentation,
+            direction: self.direction,
+            byte_to_cell_idx: if self.byte_to_cell_idx.is_empty() {
+                vec![]
+            } else {
+                self.byte_to_cell_idx[range.clone()].to_vec()
+            },
+            byte_to_cell_width: if self.byte_to_cell_width.is_empty() {
+                vec![]
+            } else {
+                self.byte_to_cell_width[range.clone()].to_vec()
+            },
+            first_cell_idx: self.byte_to_cell_idx(range.start),
+        }
+    }
+
     /// Compute the list of CellClusters from a set of visible cells.
     /// The input is typically the result of calling `Line::visible_cells()`.
     pub fn make_cluster<'a>(
@@ -289,3 +351,41 @@ impl CellCluster {
         self.text.push_str(text);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::surface::{Line, SEQ_ZERO};
+
+    #[test]
+    fn split_at_cells() {
+        let line = Line::from_text("a->b", &CellAttributes::default(), SEQ_ZERO, None);
+        let clusters = line.cluster(None);
+        assert_eq!(clusters.len(), 1);
+
+        let split = clusters[0].clone().split_at_cells(&[2, 3]);
+        let texts: Vec<_> = split
+            .iter()
+            .map(|c| (c.text.as_str(), c.first_cell_idx, c.width))
+            .collect();
+        assert_eq!(texts, vec![("a-", 0, 2), (">", 2, 1), ("b", 3, 1)]);
+
+        // Boundaries at the edges of the cluster have no effect
+        assert_eq!(clusters[0].clone().split_at_cells(&[0, 4]).len(), 1);
+    }
+
+    #[test]
+    fn split_wide_cells() {
+        let line = Line::from_text("x\u{4e2d}=>", &CellAttributes::default(), SEQ_ZERO, None);
+        let clusters = line.cluster(None);
+        assert_eq!(clusters.len(), 1);
+
+        let split = clusters[0].clone().split_at_cells(&[2, 3]);
+        assert_eq!(split.len(), 2);
+        assert_eq!(split[0].text, "x\u{4e2d}");
+        assert_eq!(split[0].width, 3);
+        assert_eq!(split[1].text, "=>");
+        assert_eq!(split[1].first_cell_idx, 3);
+        assert_eq!(split[1].byte_to_cell_idx(1), 4);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::emoji_variation::VARIATION_MAP;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, FromDynamic, ToDynamic)]
 pub enum Presentation {
     Text,
     Emoji,
@@ -27,7 +28,9 @@ impl Presentation {
     }
 
     pub fn for_char(c: char) -> Self {
-        if crate::emoji_presentation::EMOJI_PRESENTATION.contains_u32(c as u32) {
+        if let Some(presentation) = crate::cell::overridden_presentation(c) {
+            presentation
+        } else if crate::emoji_presentation::EMOJI_PRESENTATION.contains_u32(c as u32) {
             Self::Emoji
         } else {
             Self::Text
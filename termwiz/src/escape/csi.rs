@@ -30,6 +30,9 @@ pub enum CSI {
 
     Keyboard(Keyboard),
 
+    /// The DEC locator, which reports the position of the mouse
+    Locator(Locator),
+
     /// ECMA-48 SCP
     SelectCharacterPath(CharacterPath, i64),
 
@@ -128,6 +131,7 @@ impl Display for CSI {
             CSI::Keyboard(Keyboard::PopKittyState(n)) => write!(f, "<{}u", *n)?,
             CSI::Keyboard(Keyboard::QueryKittySupport) => write!(f, "?u")?,
             CSI::Keyboard(Keyboard::ReportKittyState(flags)) => write!(f, "?{}u", flags.bits())?,
+            CSI::Locator(locator) => locator.fmt(f)?,
             CSI::SelectCharacterPath(path, n) => {
                 let a = match path {
                     CharacterPath::ImplementationDefault => 0,
@@ -656,6 +660,147 @@ impl Display for MouseReport {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum LocatorReportingMode {
+    Off = 0,
+    On = 1,
+    /// Reports a single event and then turns off
+    OneShot = 2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum LocatorUnits {
+    /// Character cells
+    Default = 0,
+    DevicePixels = 1,
+    Cells = 2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum LocatorEventSelection {
+    /// Only report the position when it is explicitly requested
+    ExplicitOnly = 0,
+    ButtonDown = 1,
+    NoButtonDown = 2,
+    ButtonUp = 3,
+    NoButtonUp = 4,
+}
+
+/// The event that caused a locator report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromPrimitive, ToPrimitive)]
+pub enum LocatorEvent {
+    /// The position was requested by DECRQLP
+    Request = 1,
+    LeftButtonDown = 2,
+    LeftButtonUp = 3,
+    MiddleButtonDown = 4,
+    MiddleButtonUp = 5,
+    RightButtonDown = 6,
+    RightButtonUp = 7,
+    Button4Down = 8,
+    Button4Up = 9,
+    /// The locator left the filter rectangle
+    OutsideFilterRectangle = 10,
+}
+
+bitflags::bitflags! {
+pub struct LocatorButtons: u8 {
+    const NONE = 0;
+    const RIGHT = 1;
+    const MIDDLE = 2;
+    const LEFT = 4;
+    const BUTTON4 = 8;
+}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Locator {
+    /// DECELR - Enable Locator Reporting
+    EnableReporting {
+        mode: LocatorReportingMode,
+        units: LocatorUnits,
+    },
+    /// DECSLE - Select Locator Events
+    SelectEvents(Vec<LocatorEventSelection>),
+    /// DECEFR - Enable Filter Rectangle.
+    /// The position is reported once the locator leaves the rectangle.
+    /// Omitted edges default to the current position of the locator.
+    FilterRectangle {
+        top: Option<i64>,
+        left: Option<i64>,
+        bottom: Option<i64>,
+        right: Option<i64>,
+    },
+    /// DECRQLP - Request Locator Position
+    RequestPosition,
+    /// DECLRP - Locator Report, sent in response to DECRQLP
+    /// or when one of the selected events occurs
+    Report {
+        event: LocatorEvent,
+        buttons: LocatorButtons,
+        row: i64,
+        column: i64,
+        page: i64,
+    },
+    /// DECLRP when the locator is unavailable
+    Unavailable,
+}
+
+impl Display for Locator {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), FmtError> {
+        match self {
+            Locator::EnableReporting { mode, units } => write!(
+                f,
+                "{};{}'z",
+                mode.to_u8().ok_or_else(|| FmtError)?,
+                units.to_u8().ok_or_else(|| FmtError)?
+            ),
+            Locator::SelectEvents(events) => {
+                for (idx, event) in events.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ";")?;
+                    }
+                    write!(f, "{}", event.to_u8().ok_or_else(|| FmtError)?)?;
+                }
+                write!(f, "'{{")
+            }
+            Locator::FilterRectangle {
+                top,
+                left,
+                bottom,
+                right,
+            } => {
+                for (idx, edge) in [top, left, bottom, right].iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ";")?;
+                    }
+                    if let Some(edge) = edge {
+                        write!(f, "{}", edge)?;
+                    }
+                }
+                write!(f, "'w")
+            }
+            Locator::RequestPosition => write!(f, "'|"),
+            Locator::Report {
+                event,
+                buttons,
+                row,
+                column,
+                page,
+            } => write!(
+                f,
+                "{};{};{};{};{}&w",
+                event.to_u8().ok_or_else(|| FmtError)?,
+                buttons.bits(),
+                row,
+                column,
+                page
+            ),
+            Locator::Unavailable => write!(f, "0&w"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum XtermKeyModifierResource {
     Keyboard,
@@ -1652,6 +1797,11 @@ impl<'a> CSIParser<'a> {
             ('k', [.., CsiParam::P(b' ')]) => self.select_character_path(params),
             ('q', [.., CsiParam::P(b' ')]) => self.cursor_style(params),
             ('y', [.., CsiParam::P(b'*')]) => self.checksum_area(params),
+            ('z', [.., CsiParam::P(b'\'')])
+            | ('{', [.., CsiParam::P(b'\'')])
+            | ('w', [.., CsiParam::P(b'\'')])
+            | ('|', [.., CsiParam::P(b'\'')])
+            | ('w', [.., CsiParam::P(b'&')]) => self.locator(params).map(CSI::Locator),
 
             ('c', [CsiParam::P(b'='), ..]) => self
                 .req_tertiary_device_attributes(params)
@@ -1889,6 +2039,48 @@ impl<'a> CSIParser<'a> {
         }))
     }
 
+    fn locator(&mut self, params: &'a [CsiParam]) -> Result<Locator, ()> {
+        let cracked = Cracked::parse(&params[..params.len() - 1])?;
+        match self.control {
+            'z' => Ok(Locator::EnableReporting {
+                mode: FromPrimitive::from_i64(cracked.opt_int(0).unwrap_or(0)).ok_or(())?,
+                units: FromPrimitive::from_i64(cracked.opt_int(1).unwrap_or(0)).ok_or(())?,
+            }),
+            '{' => {
+                let mut events = vec![];
+                for idx in 0..cracked.len().max(1) {
+                    events.push(
+                        FromPrimitive::from_i64(cracked.opt_int(idx).unwrap_or(0)).ok_or(())?,
+                    );
+                }
+                Ok(Locator::SelectEvents(events))
+            }
+            'w' if params.last() == Some(&CsiParam::P(b'\'')) => Ok(Locator::FilterRectangle {
+                top: cracked.opt_int(0),
+                left: cracked.opt_int(1),
+                bottom: cracked.opt_int(2),
+                right: cracked.opt_int(3),
+            }),
+            '|' => match cracked.opt_int(0).unwrap_or(0) {
+                0 | 1 => Ok(Locator::RequestPosition),
+                _ => Err(()),
+            },
+            'w' => match cracked.opt_int(0).unwrap_or(0) {
+                0 => Ok(Locator::Unavailable),
+                event => Ok(Locator::Report {
+                    event: FromPrimitive::from_i64(event).ok_or(())?,
+                    buttons: LocatorButtons::from_bits_truncate(
+                        cracked.opt_int(1).unwrap_or(0).try_into().map_err(|_| ())?,
+                    ),
+                    row: cracked.int(2)?,
+                    column: cracked.int(3)?,
+                    page: cracked.opt_int(4).unwrap_or(1),
+                }),
+            },
+            _ => Err(()),
+        }
+    }
+
     fn dsr(&mut self, params: &'a [CsiParam]) -> Result<CSI, ()> {
         match params {
             [CsiParam::Integer(5)] => {
@@ -2908,6 +3100,99 @@ mod test {
         );
     }
 
+    #[test]
+    fn locator() {
+        let res: Vec<_> = CSI::parse(
+            &[
+                CsiParam::Integer(1),
+                CsiParam::P(b';'),
+                CsiParam::Integer(1),
+                CsiParam::P(b'\''),
+            ],
+            false,
+            'z',
+        )
+        .collect();
+        assert_eq!(encode(&res), "\x1b[1;1'z");
+        assert_eq!(
+            res,
+            vec![CSI::Locator(Locator::EnableReporting {
+                mode: LocatorReportingMode::On,
+                units: LocatorUnits::DevicePixels,
+            })]
+        );
+
+        let res: Vec<_> = CSI::parse(
+            &[
+                CsiParam::Integer(1),
+                CsiParam::P(b';'),
+                CsiParam::Integer(3),
+                CsiParam::P(b'\''),
+            ],
+            false,
+            '{',
+        )
+        .collect();
+        assert_eq!(encode(&res), "\x1b[1;3'{");
+        assert_eq!(
+            res,
+            vec![CSI::Locator(Locator::SelectEvents(vec![
+                LocatorEventSelection::ButtonDown,
+                LocatorEventSelection::ButtonUp,
+            ]))]
+        );
+
+        let res: Vec<_> = CSI::parse(&[CsiParam::P(b'\'')], false, '|').collect();
+        assert_eq!(encode(&res), "\x1b['|");
+        assert_eq!(res, vec![CSI::Locator(Locator::RequestPosition)]);
+
+        let res: Vec<_> = CSI::parse(
+            &[CsiParam::P(b';'), CsiParam::Integer(5), CsiParam::P(b'\'')],
+            false,
+            'w',
+        )
+        .collect();
+        assert_eq!(encode(&res), "\x1b[;5;;'w");
+        assert_eq!(
+            res,
+            vec![CSI::Locator(Locator::FilterRectangle {
+                top: None,
+                left: Some(5),
+                bottom: None,
+                right: None,
+            })]
+        );
+
+        let res: Vec<_> = CSI::parse(
+            &[
+                CsiParam::Integer(2),
+                CsiParam::P(b';'),
+                CsiParam::Integer(4),
+                CsiParam::P(b';'),
+                CsiParam::Integer(10),
+                CsiParam::P(b';'),
+                CsiParam::Integer(20),
+                CsiParam::P(b';'),
+                CsiParam::Integer(1),
+                CsiParam::P(b'&'),
+            ],
+            false,
+            'w',
+        )
+        .collect();
+        assert_eq!(encode(&res), "\x1b[2;4;10;20;1&w");
+        assert_eq!(
+            res,
+            vec![CSI::Locator(Locator::Report {
+                event: LocatorEvent::LeftButtonDown,
+                buttons: LocatorButtons::LEFT,
+                row: 10,
+                column: 20,
+                page: 1,
+            })]
+        );
+    }
+
     #[test]
     fn soft_reset() {
         let res: Vec<_> = CSI::parse(&[CsiParam::P(b'!')], false, 'p').collect();
@@ -65,11 +65,13 @@ impl TerminfoRenderer {
         if let Some(attr) = self.pending_attr.take() {
             let mut current_foreground = self.current_attr.foreground();
             let mut current_background = self.current_attr.background();
+            let mut current_underline_color = self.current_attr.underline_color();
 
             if !attr.attribute_bits_equal(&self.current_attr) {
                 // Updating the attribute bits also resets the colors.
                 current_foreground = ColorAttribute::Default;
                 current_background = ColorAttribute::Default;
+                current_underline_color = ColorAttribute::Default;
 
                 // The SetAttributes capability can only handle single underline and slow blink.
                 if let Some(sgr) = self.get_capability::<cap::SetAttributes>() {
@@ -107,8 +109,19 @@ impl TerminfoRenderer {
                     }
                 }
 
-                if attr.underline() == Underline::Double {
-                    attr_on!(Sgr::Underline(Underline::Double));
+                match attr.underline() {
+                    Underline::None | Underline::Single => {}
+                    Underline::Double => {
+                        attr_on!(Sgr::Underline(Underline::Double));
+                    }
+                    style if self.caps.styled_underlines() => {
+                        attr_on!(Sgr::Underline(style));
+                    }
+                    _ => {
+                        // Fall back to a plain underline where the
+                        // terminal cannot render the requested style
+                        attr_on!(Sgr::Underline(Underline::Single));
+                    }
                 }
 
                 if attr.blink() == Blink::Rapid {
@@ -198,6 +211,20 @@ impl TerminfoRenderer {
                 }
             }
 
+            if self.caps.styled_underlines() && attr.underline_color() != current_underline_color {
+                let spec = match (has_true_color, attr.underline_color()) {
+                    (true, ColorAttribute::TrueColorWithPaletteFallback(tc, _))
+                    | (true, ColorAttribute::TrueColorWithDefaultFallback(tc)) => {
+                        ColorSpec::TrueColor(tc)
+                    }
+                    (false, ColorAttribute::TrueColorWithDefaultFallback(_))
+                    | (_, ColorAttribute::Default) => ColorSpec::Default,
+                    (false, ColorAttribute::TrueColorWithPaletteFallback(_, idx))
+                    | (_, ColorAttribute::PaletteIndex(idx)) => ColorSpec::PaletteIndex(idx),
+                };
+                write!(out, "{}", CSI::Sgr(Sgr::UnderlineColor(spec)))?;
+            }
+
             if self.caps.hyperlinks() {
                 if let Some(link) = attr.hyperlink() {
                     let osc = OperatingSystemCommand::SetHyperlink(Some((**link).clone()));
@@ -1064,6 +1091,61 @@ mod test {
         );
     }
 
+    #[test]
+    fn styled_underline() {
+        let caps = Capabilities::new_with_hints(
+            ProbeHints::default()
+                .color_level(Some(ColorLevel::TrueColor))
+                .styled_underlines(Some(true)),
+        )
+        .unwrap();
+        let mut out = FakeTerm::new(caps);
+        let mut attr = CellAttributes::default();
+        attr.set_underline(Underline::Curly)
+            .set_underline_color(AnsiColor::Red);
+        out.render(&[Change::AllAttributes(attr), Change::Text("a".into())])
+            .unwrap();
+
+        let result = out.parse();
+        assert_eq!(
+            result,
+            vec![
+                Action::CSI(CSI::Sgr(Sgr::Reset)),
+                Action::CSI(CSI::Sgr(Sgr::Underline(Underline::Curly))),
+                Action::CSI(CSI::Sgr(Sgr::UnderlineColor(ColorSpec::PaletteIndex(
+                    AnsiColor::Red as u8
+                )))),
+                Action::Print('a'),
+            ]
+        );
+    }
+
+    #[test]
+    fn styled_underline_fallback() {
+        let caps = Capabilities::new_with_hints(
+            ProbeHints::default()
+                .color_level(Some(ColorLevel::TrueColor))
+                .styled_underlines(Some(false)),
+        )
+        .unwrap();
+        let mut out = FakeTerm::new(caps);
+        let mut attr = CellAttributes::default();
+        attr.set_underline(Underline::Curly)
+            .set_underline_color(AnsiColor::Red);
+        out.render(&[Change::AllAttributes(attr), Change::Text("a".into())])
+            .unwrap();
+
+        let result = out.parse();
+        assert_eq!(
+            result,
+            vec![
+                Action::CSI(CSI::Sgr(Sgr::Reset)),
+                Action::CSI(CSI::Sgr(Sgr::Underline(Underline::Single))),
+                Action::Print('a'),
+            ]
+        );
+    }
+
     #[test]
     fn red_bold_text() {
         let mut out = FakeTerm::new(xterm_terminfo());
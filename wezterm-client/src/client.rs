@@ -1,11 +1,12 @@
 use crate::domain::{ClientDomain, ClientDomainConfig};
 use crate::pane::ClientPane;
+use crate::tlstrust::{self, PresentedCert, TrustStore};
 use crate::UnixStream;
 use anyhow::{anyhow, bail, Context};
 use async_ossl::AsyncSslStream;
 use async_trait::async_trait;
 use codec::*;
-use config::{configuration, SshDomain, TlsDomainClient, UnixDomain, UnixTarget};
+use config::{configuration, MuxCompression, SshDomain, TlsDomainClient, UnixDomain, UnixTarget};
 use filedescriptor::FileDescriptor;
 use futures::FutureExt;
 use mux::client::ClientId;
@@ -27,6 +28,7 @@ use std::net::TcpStream;
 #[cfg(unix)]
 use std::os::unix::process::CommandExt;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 use thiserror::Error;
@@ -44,6 +46,7 @@ enum ReaderMessage {
         pdu: Pdu,
         promise: Sender<anyhow::Result<Pdu>>,
     },
+    AddEventSubscriber(Sender<MuxEvent>),
     Readable,
 }
 
@@ -52,6 +55,8 @@ pub struct Client {
     sender: Sender<ReaderMessage>,
     local_domain_id: Option<DomainId>,
     client_id: ClientId,
+    compression: MuxCompression,
+    stats: Arc<CodecStats>,
     pub is_reconnectable: bool,
     pub is_local: bool,
 }
@@ -210,6 +215,7 @@ fn process_unilateral(
                 let local_window_id = client_domain
                     .remote_to_local_window_id(window_id)
                     .ok_or_else(|| anyhow!("no local window for remote window id {}", window_id))?;
+                let workspace = client_domain.local_workspace(&workspace);
                 if let Some(mut window) = mux.get_window_mut(local_window_id) {
                     window.set_workspace(&workspace);
                 }
@@ -244,16 +250,24 @@ fn client_thread(
     reconnectable: &mut Reconnectable,
     local_domain_id: Option<DomainId>,
     rx: &mut Receiver<ReaderMessage>,
+    stats: &CodecStats,
 ) -> anyhow::Result<()> {
-    block_on(client_thread_async(reconnectable, local_domain_id, rx))
+    block_on(client_thread_async(
+        reconnectable,
+        local_domain_id,
+        rx,
+        stats,
+    ))
 }
 
 async fn client_thread_async(
     reconnectable: &mut Reconnectable,
     local_domain_id: Option<DomainId>,
     rx: &mut Receiver<ReaderMessage>,
+    stats: &CodecStats,
 ) -> anyhow::Result<()> {
     let mut next_serial = 1u64;
+    let compression = reconnectable.config.mux_compression();
 
     struct Promises {
         map: HashMap<u64, Sender<anyhow::Result<Pdu>>>,
@@ -277,6 +291,8 @@ async fn client_thread_async(
         map: HashMap::new(),
     };
 
+    let mut event_subscribers: Vec<Sender<MuxEvent>> = vec![];
+
     let mut stream = reconnectable.take_stream().unwrap();
 
     loop {
@@ -291,16 +307,22 @@ async fn client_thread_async(
                 next_serial += 1;
                 promises.map.insert(serial, promise);
 
-                pdu.encode_async(&mut stream, serial)
+                pdu.encode_async_with(&mut stream, serial, &compression, Some(stats))
                     .await
                     .context("encoding a PDU to send to the server")?;
                 stream.flush().await.context("flushing PDU to server")?;
             }
+            Ok(ReaderMessage::AddEventSubscriber(subscriber)) => {
+                event_subscribers.push(subscriber);
+            }
             Ok(ReaderMessage::Readable) => {
-                match Pdu::decode_async(&mut stream, Some(next_serial)).await {
+                match Pdu::decode_async_with(&mut stream, Some(next_serial), Some(stats)).await {
                     Ok(decoded) => {
                         log::trace!("decoded serial {}", decoded.serial);
-                        if decoded.serial == 0 {
+                        if let (0, Pdu::MuxEvent(event)) = (decoded.serial, &decoded.pdu) {
+                            event_subscribers
+                                .retain(|subscriber| subscriber.try_send(event.clone()).is_ok());
+                        } else if decoded.serial == 0 {
                             process_unilateral(local_domain_id, decoded)
                                 .context("processing unilateral PDU from server")
                                 .map_err(|e| {
@@ -884,6 +906,22 @@ impl Reconnectable {
             }
         }
 
+        let trusted = if tls_client.trust_on_first_use {
+            TrustStore::load()?
+                .get(self.config.name())
+                .map(|cert| cert.fingerprint.clone())
+        } else {
+            None
+        };
+        let capture = if tls_client.trust_on_first_use {
+            Some(tlstrust::verify_with_trusted_fingerprint(
+                &mut connector,
+                trusted.clone(),
+            ))
+        } else {
+            None
+        };
+
         let connector = connector.build();
         let connector = connector
             .configure()?
@@ -896,34 +934,96 @@ impl Reconnectable {
         stream.set_write_timeout(Some(tls_client.write_timeout))?;
         stream.set_read_timeout(Some(tls_client.read_timeout))?;
 
-        let stream = Box::new(Async::new(AsyncSslStream::new(
-            connector
-                .connect(
-                    tls_client
-                        .expected_cn
-                        .as_ref()
-                        .map(String::as_str)
-                        .unwrap_or(remote_host_name),
-                    stream,
-                )
-                .with_context(|| {
+        let result = connector.connect(
+            tls_client
+                .expected_cn
+                .as_ref()
+                .map(String::as_str)
+                .unwrap_or(remote_host_name),
+            stream,
+        );
+        let stream = match result {
+            Ok(stream) => stream,
+            Err(err) => {
+                let rejected = capture
+                    .and_then(|capture| capture.presented())
+                    .filter(|cert| !cert.accepted);
+                if let Some(cert) = rejected {
+                    if let Some(trusted) = trusted {
+                        bail!(
+                            "The certificate presented by {} for TLS domain {} has changed!\n\
+                             It was trusted with the SHA-256 fingerprint {}\n\
+                             but it now has the fingerprint {}.\n\
+                             If this change is expected, run \
+                             `wezterm cli tls trust revoke {}` and connect again.",
+                            remote_address,
+                            self.config.name(),
+                            trusted,
+                            cert.fingerprint,
+                            self.config.name()
+                        );
+                    }
+                    if self.confirm_trust(ui, remote_address, &cert)? {
+                        return self.try_connect(tls_client, ui, remote_address, remote_host_name);
+                    }
+                }
+                return Err(err).with_context(|| {
                     format!(
                         "SslConnector for {} with host name {}",
                         remote_address, remote_host_name,
                     )
-                })?,
-        ))?);
+                });
+            }
+        };
+
+        let stream = Box::new(Async::new(AsyncSslStream::new(stream))?);
         ui.output_str("TLS Connected!\n");
         Ok(stream)
     }
+
+    /// Asks whether to trust the certificate that the server of the
+    /// domain presented, and remembers it if so
+    fn confirm_trust(
+        &self,
+        ui: &ConnectionUI,
+        remote_address: &str,
+        cert: &PresentedCert,
+    ) -> anyhow::Result<bool> {
+        let domain = self.config.name();
+        ui.output_str(&format!(
+            "The certificate presented by {} for TLS domain {} could not be verified.\n\
+             Subject: {}\n\
+             SHA-256 fingerprint: {}\n\
+             Do you want to trust this certificate for this domain?\n",
+            remote_address, domain, cert.subject, cert.fingerprint
+        ));
+        let trust = match ui.input("Enter [y/n]> ") {
+            Ok(line) => matches!(line.as_str(), "y" | "Y" | "yes" | "YES"),
+            Err(_) => false,
+        };
+        if trust {
+            let mut store = TrustStore::load()?;
+            store.trust(domain, cert, remote_address);
+            store.save()?;
+            log::info!(
+                "trusting certificate {} for TLS domain {}",
+                cert.fingerprint,
+                domain
+            );
+        }
+        Ok(trust)
+    }
 }
 
 impl Client {
     fn new(local_domain_id: Option<DomainId>, mut reconnectable: Reconnectable) -> Self {
         let is_reconnectable = reconnectable.reconnectable();
         let is_local = reconnectable.is_local();
+        let compression = reconnectable.config.mux_compression();
         let (sender, mut receiver) = unbounded();
         let client_id = ClientId::new();
+        let stats = Arc::new(CodecStats::default());
+        let thread_stats = Arc::clone(&stats);
 
         thread::spawn(move || {
             const BASE_INTERVAL: Duration = Duration::from_secs(1);
@@ -931,7 +1031,12 @@ impl Client {
 
             let mut backoff = BASE_INTERVAL;
             loop {
-                if let Err(e) = client_thread(&mut reconnectable, local_domain_id, &mut receiver) {
+                if let Err(e) = client_thread(
+                    &mut reconnectable,
+                    local_domain_id,
+                    &mut receiver,
+                    &thread_stats,
+                ) {
                     if !reconnectable.reconnectable() || local_domain_id.is_none() {
                         log::debug!("client thread ended: {}", e);
                         break;
@@ -952,6 +1057,11 @@ impl Client {
                         break;
                     }
 
+                    promise::spawn::spawn_into_main_thread(async move {
+                        ClientDomain::set_reconnecting(local_domain_id, true);
+                    })
+                    .detach();
+
                     let mut ui = ConnectionUI::new();
                     ui.title("wezterm: Reconnecting...");
 
@@ -1017,9 +1127,34 @@ impl Client {
             is_reconnectable,
             is_local,
             client_id,
+            compression,
+            stats,
         }
     }
 
+    /// Returns the traffic statistics of the connection, accumulated
+    /// across reconnects
+    pub fn connection_stats(&self) -> mux::domain::ConnectionStats {
+        self.stats.snapshot()
+    }
+
+    /// Tells the server who we are and how to compress the PDUs that
+    /// it sends to us.  The server forgets these when the connection
+    /// is lost, so they are sent again after reconnecting.
+    pub async fn send_client_settings(&self) -> anyhow::Result<()> {
+        self.set_client_id(SetClientId {
+            client_id: self.client_id.clone(),
+        })
+        .await?;
+        self.set_compression(SetCompression {
+            enabled: self.compression.enabled,
+            threshold: self.compression.threshold,
+            level: self.compression.level,
+        })
+        .await?;
+        Ok(())
+    }
+
     pub async fn verify_version_compat(
         &self,
         ui: &ConnectionUI,
@@ -1038,10 +1173,7 @@ impl Client {
                     info.version_string,
                     info.codec_vers
                 );
-                self.set_client_id(SetClientId {
-                    client_id: self.client_id.clone(),
-                })
-                .await?;
+                self.send_client_settings().await?;
                 Ok(info)
             }
             Ok(info) => {
@@ -1182,6 +1314,23 @@ impl Client {
         rx.recv().await.context("send_pdu recv")?
     }
 
+    /// Asks the server to stream the specified kinds of events to us,
+    /// returning the receiving end of that stream
+    pub async fn subscribe_events(
+        &self,
+        events: Vec<MuxEventKind>,
+    ) -> anyhow::Result<Receiver<MuxEvent>> {
+        let (tx, rx) = unbounded();
+        self.sender
+            .send(ReaderMessage::AddEventSubscriber(tx))
+            .await
+            .map_err(|_| ChannelSendError)
+            .context("subscribe_events send")?;
+        self.subscribe_events_rpc(SubscribeEvents { events })
+            .await?;
+        Ok(rx)
+    }
+
     rpc!(ping, Ping = (), Pong);
     rpc!(list_panes, ListPanes = (), ListPanesResponse);
     rpc!(spawn_v2, SpawnV2, SpawnResponse);
@@ -1212,8 +1361,39 @@ impl Client {
     );
     rpc!(kill_pane, KillPane, UnitResponse);
     rpc!(set_client_id, SetClientId, UnitResponse);
+    rpc!(set_compression, SetCompression, UnitResponse);
     rpc!(list_clients, GetClientList, GetClientListResponse);
     rpc!(set_window_workspace, SetWindowWorkspace, UnitResponse);
     rpc!(set_focused_pane_id, SetFocusedPane, UnitResponse);
     rpc!(get_image_cell, GetImageCell, GetImageCellResponse);
+    rpc!(read_file_chunk, ReadFileChunk, ReadFileChunkResponse);
+    rpc!(write_file_chunk, WriteFileChunk, UnitResponse);
+    rpc!(subscribe_events_rpc, SubscribeEvents, UnitResponse);
+    rpc!(
+        get_command_history,
+        GetCommandHistory,
+        GetCommandHistoryResponse
+    );
+    rpc!(activate_pane_direction, ActivatePaneDirection, UnitResponse);
+    rpc!(rotate_panes, RotatePanes, UnitResponse);
+    rpc!(attach_domain_group, AttachDomainGroup, UnitResponse);
+    rpc!(apply_workspace_layout, ApplyWorkspaceLayout, UnitResponse);
+    rpc!(
+        export_scrollback,
+        ExportScrollback,
+        ExportScrollbackResponse
+    );
+    rpc!(detach_domain_group, DetachDomainGroup, UnitResponse);
+    rpc!(resume_panes, ResumePanes, ResumePanesResponse);
+    rpc!(rename_workspace, RenameWorkspace, UnitResponse);
+    rpc!(merge_workspace, MergeWorkspace, UnitResponse);
+    rpc!(delete_workspace, DeleteWorkspace, UnitResponse);
+    rpc!(swap_panes, SwapPanes, UnitResponse);
+    rpc!(read_dir, ReadDir, ReadDirResponse);
+    rpc!(perform_actions, PerformActions, UnitResponse);
+    rpc!(
+        split_pane_with_layout,
+        SplitPaneWithLayout,
+        SplitPaneWithLayoutResponse
+    );
 }
@@ -393,6 +393,7 @@ pub fn discover_gui_socks() -> Vec<PathBuf> {
     socks.into_iter().map(|e| e.path).collect()
 }
 
-fn is_sock_dead(sock: &std::path::Path) -> bool {
+/// Returns true if nothing is listening on `sock`
+pub fn is_sock_dead(sock: &std::path::Path) -> bool {
     UnixStream::connect(sock).is_err()
 }
@@ -2,11 +2,17 @@ use crate::client::Client;
 use crate::pane::ClientPane;
 use anyhow::{anyhow, bail};
 use async_trait::async_trait;
-use codec::{ListPanesResponse, SpawnV2, SplitPane};
+use codec::{
+    ListPanesResponse, ReadDir, ReadFileChunk, ResumePane, ResumePanes, SpawnV2, SplitPane,
+    WriteFileChunk,
+};
 use config::keyassignment::SpawnTabDomain;
-use config::{SshDomain, TlsDomainClient, UnixDomain};
+use config::{MuxCompression, SshDomain, TlsDomainClient, UnixDomain};
 use mux::connui::{ConnectionUI, ConnectionUIParams};
-use mux::domain::{alloc_domain_id, Domain, DomainId, DomainState, SplitSource};
+use mux::domain::{
+    alloc_domain_id, ConnectionStats, DirEntry, Domain, DomainId, DomainState, FileChunk,
+    SplitSource,
+};
 use mux::pane::{Pane, PaneId};
 use mux::tab::{SplitRequest, Tab, TabId};
 use mux::window::WindowId;
@@ -28,6 +34,9 @@ pub struct ClientInner {
     remote_to_local_tab: Mutex<HashMap<TabId, TabId>>,
     remote_to_local_pane: Mutex<HashMap<PaneId, PaneId>>,
     pub focused_remote_pane_id: Mutex<Option<PaneId>>,
+    /// The `default_workspace` of the domain group that contains
+    /// this domain, if any
+    group_workspace: Option<String>,
 }
 
 impl ClientInner {
@@ -113,6 +122,29 @@ impl ClientInner {
     pub fn is_local(&self) -> bool {
         self.client.is_local
     }
+
+    /// Maps a workspace name on the remote server to the local name.
+    /// When this domain is part of a domain group with a default
+    /// workspace, the default workspace of the server is shown in
+    /// the group workspace.
+    pub fn local_workspace(&self, remote_workspace: &str) -> String {
+        match &self.group_workspace {
+            Some(group_workspace) if remote_workspace == mux::DEFAULT_WORKSPACE => {
+                group_workspace.clone()
+            }
+            _ => remote_workspace.to_string(),
+        }
+    }
+
+    /// The inverse of `local_workspace`
+    pub fn remote_workspace(&self, local_workspace: &str) -> String {
+        match &self.group_workspace {
+            Some(group_workspace) if local_workspace == group_workspace => {
+                mux::DEFAULT_WORKSPACE.to_string()
+            }
+            _ => local_workspace.to_string(),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -139,6 +171,14 @@ impl ClientDomainConfig {
         }
     }
 
+    pub fn mux_compression(&self) -> MuxCompression {
+        match self {
+            ClientDomainConfig::Unix(unix) => unix.mux_compression,
+            ClientDomainConfig::Tls(tls) => tls.mux_compression,
+            ClientDomainConfig::Ssh(ssh) => ssh.mux_compression,
+        }
+    }
+
     pub fn label(&self) -> String {
         match self {
             ClientDomainConfig::Unix(unix) => format!("unix mux {}", unix.socket_path().display()),
@@ -167,6 +207,7 @@ impl ClientInner {
         local_domain_id: DomainId,
         client: Client,
         local_echo_threshold_ms: Option<u64>,
+        group_workspace: Option<String>,
     ) -> Self {
         // Assumption: that the domain id on the other end is
         // always the first created default domain.  In the future
@@ -182,6 +223,7 @@ impl ClientInner {
             remote_to_local_tab: Mutex::new(HashMap::new()),
             remote_to_local_pane: Mutex::new(HashMap::new()),
             focused_remote_pane_id: Mutex::new(None),
+            group_workspace,
         }
     }
 }
@@ -224,7 +266,7 @@ fn mux_notify_client_domain(local_domain_id: DomainId, notif: MuxNotification) -
                 {
                     let request = codec::SetWindowWorkspace {
                         window_id: remote_window_id,
-                        workspace,
+                        workspace: domain.remote_workspace(&workspace),
                     };
                     promise::spawn::spawn_into_main_thread(async move {
                         let _ = update_remote_workspace(local_domain_id, request).await;
@@ -257,6 +299,22 @@ impl ClientDomain {
         self.inner.borrow().as_ref().map(|i| Arc::clone(i))
     }
 
+    /// Resolves a local pane id into the client and the id of the
+    /// corresponding pane on the remote mux server
+    fn remote_pane(&self, pane_id: PaneId) -> anyhow::Result<(Arc<ClientInner>, PaneId)> {
+        let inner = self
+            .inner()
+            .ok_or_else(|| anyhow!("domain is not attached"))?;
+        let mux = Mux::get().unwrap();
+        let pane = mux
+            .get_pane(pane_id)
+            .ok_or_else(|| anyhow!("pane_id {} is invalid", pane_id))?;
+        let pane = pane
+            .downcast_ref::<ClientPane>()
+            .ok_or_else(|| anyhow!("pane_id {} is not a ClientPane", pane_id))?;
+        Ok((inner, pane.remote_pane_id()))
+    }
+
     pub fn connect_automatically(&self) -> bool {
         self.config.connect_automatically()
     }
@@ -273,6 +331,20 @@ impl ClientDomain {
         inner.remote_to_local_pane_id(remote_pane_id)
     }
 
+    pub fn local_workspace(&self, remote_workspace: &str) -> String {
+        match self.inner() {
+            Some(inner) => inner.local_workspace(remote_workspace),
+            None => remote_workspace.to_string(),
+        }
+    }
+
+    pub fn remote_workspace(&self, local_workspace: &str) -> String {
+        match self.inner() {
+            Some(inner) => inner.remote_workspace(local_workspace),
+            None => local_workspace.to_string(),
+        }
+    }
+
     pub fn remote_to_local_window_id(&self, remote_window_id: WindowId) -> Option<WindowId> {
         let inner = self.inner()?;
         inner.remote_to_local_window(remote_window_id)
@@ -306,13 +378,68 @@ impl ClientDomain {
     pub async fn reattach(domain_id: DomainId, ui: ConnectionUI) -> anyhow::Result<()> {
         let inner = Self::get_client_inner_for_domain(domain_id)?;
 
+        inner.client.send_client_settings().await?;
+
+        // Resume before anything prompts the server to push changes
+        // to the panes, so that only the changes are sent
+        if let Err(err) = Self::resume_panes(&inner).await {
+            log::warn!(
+                "unable to resume panes, will resync them in full: {:#}",
+                err
+            );
+        }
+
         let panes = inner.client.list_panes().await?;
         Self::process_pane_list(inner, panes, None)?;
+        Self::set_reconnecting(domain_id, false);
 
         ui.close();
         Ok(())
     }
 
+    fn client_panes(domain_id: DomainId) -> Vec<Rc<dyn Pane>> {
+        let mux = Mux::get().expect("to be called on main thread");
+        mux.iter_panes()
+            .into_iter()
+            .filter(|pane| {
+                pane.domain_id() == domain_id && pane.downcast_ref::<ClientPane>().is_some()
+            })
+            .collect()
+    }
+
+    /// Tells the server the state that we last received for each of
+    /// our panes, so that it resends only what has changed since then
+    async fn resume_panes(inner: &Arc<ClientInner>) -> anyhow::Result<()> {
+        let panes: Vec<ResumePane> = Self::client_panes(inner.local_domain_id)
+            .iter()
+            .filter_map(|pane| pane.downcast_ref::<ClientPane>())
+            .map(|pane| ResumePane {
+                pane_id: pane.remote_pane_id(),
+                seqno: pane.get_current_seqno(),
+            })
+            .collect();
+        if panes.is_empty() {
+            return Ok(());
+        }
+        let num_panes = panes.len();
+        let response = inner.client.resume_panes(ResumePanes { panes }).await?;
+        log::debug!(
+            "resumed {} of {} panes after reconnecting",
+            response.resumed.len(),
+            num_panes
+        );
+        Ok(())
+    }
+
+    /// Shows or hides the reconnecting indicator in the panes of the domain
+    pub fn set_reconnecting(domain_id: DomainId, reconnecting: bool) {
+        for pane in Self::client_panes(domain_id) {
+            if let Some(pane) = pane.downcast_ref::<ClientPane>() {
+                pane.set_reconnecting(reconnecting);
+            }
+        }
+    }
+
     pub async fn resync(&self) -> anyhow::Result<()> {
         if let Some(inner) = self.inner.borrow().as_ref() {
             let panes = inner.client.list_panes().await?;
@@ -360,7 +487,7 @@ impl ClientDomain {
                 log::debug!("tree: {:#?}", tabroot);
                 let mut workspace = None;
                 tab.sync_with_pane_tree(root_size, tabroot, |entry| {
-                    workspace.replace(entry.workspace.clone());
+                    workspace.replace(inner.local_workspace(&entry.workspace));
                     if let Some(pane_id) = inner.remote_to_local_pane_id(entry.pane_id) {
                         match mux.get_pane(pane_id) {
                             Some(pane) => pane,
@@ -457,8 +584,16 @@ impl ClientDomain {
             .downcast_ref::<Self>()
             .ok_or_else(|| anyhow!("domain {} is not a ClientDomain", domain_id))?;
         let threshold = domain.config.local_echo_threshold_ms();
+        let group_workspace = config::configuration()
+            .domain_group_for_domain(domain.config.name())
+            .and_then(|group| group.default_workspace.clone());
 
-        let inner = Arc::new(ClientInner::new(domain_id, client, threshold));
+        let inner = Arc::new(ClientInner::new(
+            domain_id,
+            client,
+            threshold,
+            group_workspace,
+        ));
         *domain.inner.borrow_mut() = Some(Arc::clone(&inner));
 
         Self::process_pane_list(inner, panes, primary_window_id)?;
@@ -501,7 +636,7 @@ impl Domain for ClientDomain {
             .inner()
             .ok_or_else(|| anyhow!("domain is not attached"))?;
 
-        let workspace = Mux::get().unwrap().active_workspace();
+        let workspace = inner.remote_workspace(&Mux::get().unwrap().active_workspace());
 
         let result = inner
             .client
@@ -536,6 +671,66 @@ impl Domain for ClientDomain {
         Ok(tab)
     }
 
+    async fn read_file_chunk(
+        &self,
+        pane_id: PaneId,
+        path: &str,
+        offset: u64,
+        max_bytes: usize,
+    ) -> anyhow::Result<FileChunk> {
+        let (inner, remote_pane_id) = self.remote_pane(pane_id)?;
+        let result = inner
+            .client
+            .read_file_chunk(ReadFileChunk {
+                pane_id: remote_pane_id,
+                path: path.to_string(),
+                offset,
+                max_bytes,
+            })
+            .await?;
+        Ok(FileChunk {
+            data: result.data,
+            total_size: result.total_size,
+        })
+    }
+
+    async fn read_dir(&self, path: &str) -> anyhow::Result<Vec<DirEntry>> {
+        let inner = self
+            .inner()
+            .ok_or_else(|| anyhow!("domain is not attached"))?;
+        let result = inner
+            .client
+            .read_dir(ReadDir {
+                domain: SpawnTabDomain::DomainId(inner.remote_domain_id),
+                pane_id: None,
+                path: path.to_string(),
+            })
+            .await?;
+        Ok(result.entries)
+    }
+
+    async fn write_file_chunk(
+        &self,
+        pane_id: PaneId,
+        path: &str,
+        offset: u64,
+        data: Vec<u8>,
+        total_size: Option<u64>,
+    ) -> anyhow::Result<()> {
+        let (inner, remote_pane_id) = self.remote_pane(pane_id)?;
+        inner
+            .client
+            .write_file_chunk(WriteFileChunk {
+                pane_id: remote_pane_id,
+                path: path.to_string(),
+                offset,
+                data,
+                total_size,
+            })
+            .await?;
+        Ok(())
+    }
+
     async fn split_pane(
         &self,
         source: SplitSource,
@@ -688,6 +883,10 @@ impl Domain for ClientDomain {
         Ok(())
     }
 
+    fn connection_stats(&self) -> Option<ConnectionStats> {
+        self.inner().map(|inner| inner.client.connection_stats())
+    }
+
     fn state(&self) -> DomainState {
         if self.inner.borrow().is_some() {
             DomainState::Attached
@@ -5,6 +5,7 @@ use anyhow::bail;
 use async_trait::async_trait;
 use codec::*;
 use config::configuration;
+use config::keyassignment::RotationDirection;
 use mux::domain::DomainId;
 use mux::pane::{alloc_pane_id, CloseReason, Pane, PaneId, Pattern, SearchResult};
 use mux::renderable::{RenderableDimensions, StableCursorPosition};
@@ -12,7 +13,7 @@ use mux::tab::TabId;
 use mux::{Mux, MuxNotification};
 use rangeset::RangeSet;
 use ratelim::RateLimiter;
-use std::cell::{RefCell, RefMut};
+use std::cell::{Cell, RefCell, RefMut};
 use std::collections::HashMap;
 use std::ops::Range;
 use std::rc::Rc;
@@ -29,13 +30,16 @@ pub struct ClientPane {
     client: Arc<ClientInner>,
     local_pane_id: PaneId,
     pub remote_pane_id: PaneId,
-    pub remote_tab_id: TabId,
+    pub remote_tab_id: Cell<TabId>,
     pub renderable: RefCell<RenderableState>,
     palette: RefCell<ColorPalette>,
     writer: RefCell<PaneWriter>,
     mouse: Rc<RefCell<MouseState>>,
     clipboard: RefCell<Option<Arc<dyn Clipboard>>>,
     mouse_grabbed: RefCell<bool>,
+    read_only: RefCell<bool>,
+    /// Swallows the writes made while the pane is read-only
+    discard: RefCell<std::io::Sink>,
     ignore_next_kill: RefCell<bool>,
     user_vars: RefCell<HashMap<String, String>>,
 }
@@ -88,12 +92,14 @@ impl ClientPane {
             mouse,
             remote_pane_id,
             local_pane_id,
-            remote_tab_id,
+            remote_tab_id: Cell::new(remote_tab_id),
             renderable: RefCell::new(render),
             writer: RefCell::new(writer),
             palette: RefCell::new(palette),
             clipboard: RefCell::new(None),
             mouse_grabbed: RefCell::new(false),
+            read_only: RefCell::new(false),
+            discard: RefCell::new(std::io::sink()),
             ignore_next_kill: RefCell::new(false),
             user_vars: RefCell::new(HashMap::new()),
         }
@@ -149,6 +155,12 @@ impl ClientPane {
                             .borrow_mut()
                             .insert(name.clone(), value.clone());
                     }
+                    Alert::CommandStarted { command } => {
+                        mux.record_command_started(self.local_pane_id, command.clone());
+                    }
+                    Alert::CommandFinished { status } => {
+                        mux.record_command_finished(self.local_pane_id, *status);
+                    }
                     _ => {}
                 }
                 mux.notify(MuxNotification::Alert {
@@ -171,6 +183,14 @@ impl ClientPane {
         self.remote_pane_id
     }
 
+    /// Shows or hides the indicator that the connection to the
+    /// server was lost and is being re-established
+    pub fn set_reconnecting(&self, reconnecting: bool) {
+        self.renderable.borrow().inner.borrow_mut().reconnecting = reconnecting;
+        let mux = Mux::get().unwrap();
+        mux.notify(MuxNotification::PaneOutput(self.local_pane_id));
+    }
+
     /// Arrange to suppress the next Pane::kill call.
     /// This is a bit of a hack that we use when closing a window;
     /// our Domain::local_window_is_closing impl calls this for each
@@ -223,6 +243,9 @@ impl Pane for ClientPane {
     }
 
     fn send_paste(&self, text: &str) -> anyhow::Result<()> {
+        if self.is_read_only() {
+            return Ok(());
+        }
         let client = Arc::clone(&self.client);
         let remote_pane_id = self.remote_pane_id;
         self.renderable
@@ -238,6 +261,7 @@ impl Pane for ClientPane {
                 .send_paste(SendPaste {
                     pane_id: remote_pane_id,
                     data,
+                    emit_pre_paste: false,
                 })
                 .await
         })
@@ -255,15 +279,28 @@ impl Pane for ClientPane {
     }
 
     fn writer(&self) -> RefMut<dyn std::io::Write> {
+        if self.is_read_only() {
+            return self.discard.borrow_mut();
+        }
         self.writer.borrow_mut()
     }
 
+    fn set_read_only(&self, read_only: bool) {
+        *self.read_only.borrow_mut() = read_only;
+        let mux = Mux::get().unwrap();
+        mux.notify(MuxNotification::PaneOutput(self.local_pane_id));
+    }
+
+    fn is_read_only(&self) -> bool {
+        *self.read_only.borrow()
+    }
+
     fn set_zoomed(&self, zoomed: bool) {
         let render = self.renderable.borrow();
         let mut inner = render.inner.borrow_mut();
         let client = Arc::clone(&self.client);
         let remote_pane_id = self.remote_pane_id;
-        let remote_tab_id = self.remote_tab_id;
+        let remote_tab_id = self.remote_tab_id.get();
         // Invalidate any cached rows on a resize
         inner.make_all_stale();
         promise::spawn::spawn(async move {
@@ -280,6 +317,44 @@ impl Pane for ClientPane {
         inner.update_last_send();
     }
 
+    fn panes_rotated(&self, direction: RotationDirection) {
+        let client = Arc::clone(&self.client);
+        let remote_pane_id = self.remote_pane_id;
+        promise::spawn::spawn(async move {
+            client
+                .client
+                .rotate_panes(RotatePanes {
+                    pane_id: remote_pane_id,
+                    direction,
+                })
+                .await
+        })
+        .detach();
+    }
+
+    fn panes_swapped(&self, other: &dyn Pane) {
+        let other = match other.downcast_ref::<ClientPane>() {
+            Some(other) => other,
+            None => return,
+        };
+        // The panes may have moved between tabs
+        self.remote_tab_id.swap(&other.remote_tab_id);
+
+        let client = Arc::clone(&self.client);
+        let pane_id = self.remote_pane_id;
+        let other_pane_id = other.remote_pane_id;
+        promise::spawn::spawn(async move {
+            client
+                .client
+                .swap_panes(SwapPanes {
+                    pane_id,
+                    other_pane_id,
+                })
+                .await
+        })
+        .detach();
+    }
+
     fn resize(&self, size: TerminalSize) -> anyhow::Result<()> {
         let render = self.renderable.borrow();
         let mut inner = render.inner.borrow_mut();
@@ -296,7 +371,7 @@ impl Pane for ClientPane {
 
             let client = Arc::clone(&self.client);
             let remote_pane_id = self.remote_pane_id;
-            let remote_tab_id = self.remote_tab_id;
+            let remote_tab_id = self.remote_tab_id.get();
             promise::spawn::spawn(async move {
                 client
                     .client
@@ -329,6 +404,9 @@ impl Pane for ClientPane {
     }
 
     fn key_down(&self, key: KeyCode, mods: KeyModifiers) -> anyhow::Result<()> {
+        if self.is_read_only() {
+            return Ok(());
+        }
         let input_serial;
         {
             let renderable = self.renderable.borrow();
@@ -366,6 +444,25 @@ impl Pane for ClientPane {
         Ok(())
     }
 
+    fn perform_actions(&self, actions: Vec<termwiz::escape::Action>) {
+        let client = Arc::clone(&self.client);
+        let remote_pane_id = self.remote_pane_id;
+        let actions = actions.iter().map(|action| action.to_string()).collect();
+        promise::spawn::spawn(async move {
+            if let Err(err) = client
+                .client
+                .perform_actions(PerformActions {
+                    pane_id: remote_pane_id,
+                    actions,
+                })
+                .await
+            {
+                log::error!("perform_actions for pane {}: {:#}", remote_pane_id, err);
+            }
+        })
+        .detach();
+    }
+
     fn kill(&self) {
         let mut ignore = self.ignore_next_kill.borrow_mut();
         if *ignore {
@@ -436,6 +533,9 @@ impl Pane for ClientPane {
     }
 
     fn mouse_event(&self, event: MouseEvent) -> anyhow::Result<()> {
+        if self.is_read_only() {
+            return Ok(());
+        }
         self.mouse.borrow_mut().append(event);
         if MouseState::next(Rc::clone(&self.mouse)) {
             self.renderable
@@ -460,7 +560,7 @@ impl Pane for ClientPane {
     }
 
     fn is_mouse_grabbed(&self) -> bool {
-        *self.mouse_grabbed.borrow()
+        *self.mouse_grabbed.borrow() && !self.is_read_only()
     }
 
     fn is_alt_screen_active(&self) -> bool {
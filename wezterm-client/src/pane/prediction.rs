@@ -0,0 +1,257 @@
+//! Local echo prediction for panes whose round trip latency exceeds
+//! the `local_echo_threshold_ms` of their domain.
+//! In the spirit of mosh, the characters that the user types are
+//! predicted to be echoed at the cursor position.  The predictions
+//! are kept apart from the lines received from the server and are
+//! drawn over them in a subtle style until the server's echo either
+//! confirms them, in which case they are simply dropped, or
+//! contradicts them, in which case all of the outstanding predictions
+//! are discarded and prediction is suspended for a while.
+use codec::InputSerial;
+use mux::renderable::StableCursorPosition;
+use std::time::{Duration, Instant};
+use termwiz::cell::{unicode_column_width, Cell, CellAttributes, Intensity, Underline};
+use termwiz::surface::SEQ_ZERO;
+use wezterm_term::{KeyCode, KeyModifiers, Line, StableRowIndex};
+
+/// Predictions that haven't been confirmed in this time are
+/// abandoned, in case their echo was never going to arrive
+const PREDICTION_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How long to stop predicting after a misprediction
+const MISPREDICTION_BACKOFF: Duration = Duration::from_secs(2);
+
+#[derive(Debug)]
+struct Prediction {
+    row: StableRowIndex,
+    col: usize,
+    text: String,
+    width: usize,
+    /// The input that is expected to produce the echo
+    serial: InputSerial,
+    made: Instant,
+}
+
+#[derive(Debug)]
+pub struct PredictionState {
+    predictions: Vec<Prediction>,
+    /// The cursor position following the most recent prediction
+    cursor: StableCursorPosition,
+    /// The most recent input that the server has responded to
+    acknowledged: InputSerial,
+    suspended_until: Option<Instant>,
+}
+
+impl Default for PredictionState {
+    fn default() -> Self {
+        Self {
+            predictions: vec![],
+            cursor: StableCursorPosition::default(),
+            acknowledged: InputSerial::empty(),
+            suspended_until: None,
+        }
+    }
+}
+
+fn predicted_attributes() -> CellAttributes {
+    CellAttributes::default()
+        .set_underline(Underline::Single)
+        .set_intensity(Intensity::Half)
+        .clone()
+}
+
+impl PredictionState {
+    /// Returns the predicted cursor position while there are
+    /// outstanding predictions, otherwise `actual`
+    pub fn cursor_position(&self, actual: StableCursorPosition) -> StableCursorPosition {
+        if self.predictions.is_empty() {
+            actual
+        } else {
+            self.cursor
+        }
+    }
+
+    /// Returns the rows that have outstanding predictions
+    pub fn rows(&self) -> impl Iterator<Item = StableRowIndex> + '_ {
+        self.predictions.iter().map(|p| p.row)
+    }
+
+    fn is_suspended(&self) -> bool {
+        self.suspended_until
+            .map(|until| Instant::now() < until)
+            .unwrap_or(false)
+    }
+
+    fn mispredicted(&mut self) {
+        log::debug!("local echo misprediction; suspending predictions");
+        self.predictions.clear();
+        self.suspended_until = Some(Instant::now() + MISPREDICTION_BACKOFF);
+    }
+
+    fn expire(&mut self) {
+        self.predictions
+            .retain(|p| p.made.elapsed() < PREDICTION_TIMEOUT);
+    }
+
+    /// Returns the position at which the next prediction is made,
+    /// or None if it isn't safe to predict on the cursor line
+    fn prediction_cursor(
+        &mut self,
+        actual: StableCursorPosition,
+        line: &Line,
+    ) -> Option<StableCursorPosition> {
+        self.expire();
+        if self.is_suspended() {
+            return None;
+        }
+        if line.as_str().contains("sword") {
+            // This line might be a password prompt.  Don't force
+            // on local echo here, as we don't want to reveal content
+            // from their password
+            return None;
+        }
+        Some(self.cursor_position(actual))
+    }
+
+    fn predict_char(
+        &mut self,
+        c: char,
+        cursor: &mut StableCursorPosition,
+        cols: usize,
+        serial: InputSerial,
+    ) -> bool {
+        if c.is_control() {
+            return false;
+        }
+        let text = c.to_string();
+        let width = unicode_column_width(&text, None);
+        // Wrapping depends on the state of the terminal, so don't
+        // attempt to predict past the right margin
+        if width == 0 || cursor.x + width > cols {
+            return false;
+        }
+        self.predictions.push(Prediction {
+            row: cursor.y,
+            col: cursor.x,
+            text,
+            width,
+            serial,
+            made: Instant::now(),
+        });
+        cursor.x += width;
+        true
+    }
+
+    /// Predicts the echo of a key press with the cursor at `actual`
+    /// on `line`.  Returns true if the predictions changed.
+    pub fn predict_key(
+        &mut self,
+        key: KeyCode,
+        mods: KeyModifiers,
+        actual: StableCursorPosition,
+        line: &Line,
+        cols: usize,
+        serial: InputSerial,
+    ) -> bool {
+        if mods != KeyModifiers::NONE && mods != KeyModifiers::SHIFT {
+            return false;
+        }
+        let mut cursor = match self.prediction_cursor(actual, line) {
+            Some(cursor) => cursor,
+            None => return false,
+        };
+        let predicted = match key {
+            KeyCode::Char(c) => self.predict_char(c, &mut cursor, cols, serial),
+            KeyCode::Backspace => {
+                // Only retract our own predictions; what backspace
+                // does to text that the server sent is up to the
+                // application
+                match self.predictions.last() {
+                    Some(last) if last.row == cursor.y && last.col + last.width == cursor.x => {
+                        cursor.x = last.col;
+                        self.predictions.pop();
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        };
+        if predicted {
+            self.cursor = cursor;
+        }
+        predicted
+    }
+
+    /// Predicts the echo of pasted text.  Only text that fits on the
+    /// cursor line is predicted.  Returns true if a prediction was made.
+    pub fn predict_paste(
+        &mut self,
+        text: &str,
+        actual: StableCursorPosition,
+        line: &Line,
+        cols: usize,
+        serial: InputSerial,
+    ) -> bool {
+        if text.contains(|c: char| c.is_control()) {
+            return false;
+        }
+        let mut cursor = match self.prediction_cursor(actual, line) {
+            Some(cursor) => cursor,
+            None => return false,
+        };
+        if cursor.x + unicode_column_width(text, None) > cols {
+            return false;
+        }
+        let mut predicted = false;
+        for c in text.chars() {
+            predicted |= self.predict_char(c, &mut cursor, cols, serial);
+        }
+        if predicted {
+            self.cursor = cursor;
+        }
+        predicted
+    }
+
+    /// Records that the server has responded to `serial`, which
+    /// means that its echo is reflected in the lines that are
+    /// subsequently fetched
+    pub fn acknowledge(&mut self, serial: InputSerial) {
+        self.acknowledged = self.acknowledged.max(serial);
+        self.expire();
+    }
+
+    /// Compares the predictions for `row` with `line`, as received
+    /// from the server.  Predictions that it confirms are dropped.
+    /// If it contradicts a prediction whose input the server has
+    /// already responded to, all of the predictions are abandoned.
+    pub fn reconcile(&mut self, row: StableRowIndex, line: &Line) {
+        let acknowledged = self.acknowledged;
+        let mut contradicted = false;
+        self.predictions.retain(|p| {
+            if p.row != row {
+                return true;
+            }
+            let confirmed = line
+                .cells()
+                .get(p.col)
+                .map(|cell| cell.str() == p.text)
+                .unwrap_or(false);
+            if !confirmed && p.serial <= acknowledged {
+                contradicted = true;
+            }
+            !confirmed
+        });
+        if contradicted {
+            self.mispredicted();
+        }
+    }
+
+    /// Draws the outstanding predictions for `row` over `line`
+    pub fn overlay(&self, row: StableRowIndex, line: &mut Line) {
+        for p in self.predictions.iter().filter(|p| p.row == row) {
+            let cell = Cell::new_grapheme(&p.text, predicted_attributes(), None);
+            line.set_cell(p.col, cell, SEQ_ZERO);
+        }
+    }
+}
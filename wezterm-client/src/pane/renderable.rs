@@ -1,5 +1,6 @@
 use crate::domain::ClientInner;
 use crate::pane::clientpane::ClientPane;
+use crate::pane::prediction::PredictionState;
 use anyhow::anyhow;
 use codec::*;
 use config::{configuration, ConfigHandle};
@@ -16,7 +17,7 @@ use std::ops::Range;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use termwiz::cell::{Cell, CellAttributes, Underline};
+use termwiz::cell::CellAttributes;
 use termwiz::color::AnsiColor;
 use termwiz::image::{ImageCell, ImageData};
 use termwiz::surface::{SequenceNo, SEQ_ZERO};
@@ -58,6 +59,8 @@ pub struct RenderableInner {
     local_pane_id: PaneId,
     last_poll: Instant,
     pub dead: bool,
+    /// true while the connection to the server is being re-established
+    pub reconnecting: bool,
     poll_in_progress: AtomicBool,
     poll_interval: Duration,
 
@@ -77,6 +80,7 @@ pub struct RenderableInner {
     last_input_rtt: u64,
 
     pub input_serial: InputSerial,
+    predictions: PredictionState,
 }
 
 pub struct RenderableState {
@@ -100,6 +104,7 @@ impl RenderableInner {
             local_pane_id,
             last_poll: now,
             dead: false,
+            reconnecting: false,
             poll_in_progress: AtomicBool::new(false),
             poll_interval: BASE_POLL_INTERVAL,
             cursor_position: StableCursorPosition::default(),
@@ -113,6 +118,7 @@ impl RenderableInner {
             last_late_dirty: now,
             last_input_rtt: 0,
             input_serial: InputSerial::empty(),
+            predictions: PredictionState::default(),
             seqno: SEQ_ZERO,
         }
     }
@@ -139,66 +145,20 @@ impl RenderableInner {
             .unwrap_or(false)
     }
 
-    /// Compute a "prediction" and apply it to the line data that we
-    /// have available, marking it as dirty so that it gets rendered.
-    /// The prediction is basically just local echo.
-    /// Open questions:
-    /// how do we tell if the intent is to suppress local echo during eg:
-    ///  * password prompt?  One option is to look back and see if the line
-    ///                      looks like a password prompt.
-    ///  * normal mode in vim: letter presses are typically movement or
-    ///                        other editor commands
-    /// There are bound to be a number of other edge cases that we should
-    /// handle.
-    fn apply_prediction(&mut self, c: KeyCode, line: &mut Line) {
-        let text = line.as_str();
-        if text.contains("sword") {
-            // This line might be a password prompt.  Don't force
-            // on local echo here, as we don't want to reveal content
-            // from their password
-            return;
+    /// Returns our copy of the line at `row`, if we have one
+    fn cached_line(&self, row: StableRowIndex) -> Option<&Line> {
+        match self.lines.peek(&row)? {
+            LineEntry::Line(line)
+            | LineEntry::Stale(line)
+            | LineEntry::LineAndFetching(line, _) => Some(line),
+            LineEntry::Fetching(_) => None,
         }
+    }
 
-        match c {
-            KeyCode::Enter => {
-                self.cursor_position.x = 0;
-                self.cursor_position.y += 1;
-            }
-            KeyCode::UpArrow => {
-                self.cursor_position.y = self.cursor_position.y.saturating_sub(1);
-            }
-            KeyCode::DownArrow => {
-                self.cursor_position.y += 1;
-            }
-            KeyCode::RightArrow => {
-                self.cursor_position.x += 1;
-            }
-            KeyCode::LeftArrow => {
-                self.cursor_position.x = self.cursor_position.x.saturating_sub(1);
-            }
-            KeyCode::Delete => {
-                line.erase_cell(self.cursor_position.x, SEQ_ZERO);
-            }
-            KeyCode::Backspace => {
-                if self.cursor_position.x > 0 {
-                    line.erase_cell(self.cursor_position.x - 1, SEQ_ZERO);
-                    self.cursor_position.x -= 1;
-                }
-            }
-            KeyCode::Char(c) => {
-                let cell = Cell::new(
-                    c,
-                    CellAttributes::default()
-                        .set_underline(Underline::Double)
-                        .clone(),
-                );
-
-                let cell = line.set_cell(self.cursor_position.x, cell, SEQ_ZERO);
-                // Adjust the cursor to reflect the width of this new cell
-                self.cursor_position.x += cell.width();
-            }
-            _ => {}
-        }
+    fn notify_prediction_changed(&self) {
+        Mux::get()
+            .unwrap()
+            .notify(mux::MuxNotification::PaneOutput(self.local_pane_id));
     }
 
     /// Based on a keypress, apply a "prediction" of what the terminal
@@ -209,57 +169,18 @@ impl RenderableInner {
         if !self.should_predict() {
             return;
         }
-
-        let c = match key {
-            KeyCode::LeftArrow
-            | KeyCode::RightArrow
-            | KeyCode::UpArrow
-            | KeyCode::DownArrow
-            | KeyCode::Delete
-            | KeyCode::Backspace
-            | KeyCode::Enter
-            | KeyCode::Char(_) => key,
-            _ => return,
+        let cursor = self.cursor_position;
+        let line = match self.cached_line(cursor.y) {
+            Some(line) => line.clone(),
+            None => return,
         };
-        if mods != KeyModifiers::NONE && mods != KeyModifiers::SHIFT {
-            return;
-        }
-
-        let row = self.cursor_position.y;
-        match self.lines.pop(&row) {
-            Some(LineEntry::Stale(mut line)) | Some(LineEntry::Line(mut line)) => {
-                self.apply_prediction(c, &mut line);
-                self.lines.put(row, LineEntry::Line(line));
-            }
-            Some(LineEntry::LineAndFetching(mut line, instant)) => {
-                self.apply_prediction(c, &mut line);
-                self.lines
-                    .put(row, LineEntry::LineAndFetching(line, instant));
-            }
-            Some(entry) => {
-                self.lines.put(row, entry);
-            }
-            None => {}
-        }
-    }
-
-    fn apply_paste_prediction(&mut self, row: usize, text: &str, line: &mut Line) {
-        let attrs = CellAttributes::default()
-            .set_underline(Underline::Double)
-            .clone();
-
-        let text_line = Line::from_text(text, &attrs, SEQ_ZERO, None);
-
-        if row == 0 {
-            for cell in text_line.cells() {
-                line.set_cell(self.cursor_position.x, cell.clone(), SEQ_ZERO);
-                self.cursor_position.x += cell.width();
-            }
-        } else {
-            // The pasted line replaces the data for the existing line
-            line.resize_and_clear(0, SEQ_ZERO, CellAttributes::default());
-            line.append_line(text_line, SEQ_ZERO);
-            self.cursor_position.x = line.cells().len();
+        let cols = self.dimensions.cols;
+        let serial = self.input_serial;
+        if self
+            .predictions
+            .predict_key(key, mods, cursor, &line, cols, serial)
+        {
+            self.notify_prediction_changed();
         }
     }
 
@@ -267,30 +188,18 @@ impl RenderableInner {
         if !self.should_predict() {
             return;
         }
-
-        let text = textwrap::fill(text, self.dimensions.cols);
-        let lines: Vec<&str> = text.split("\n").collect();
-
-        for (idx, paste_line) in lines.iter().enumerate() {
-            let row = self.cursor_position.y + idx as StableRowIndex;
-
-            match self.lines.pop(&row) {
-                Some(LineEntry::Stale(mut line)) | Some(LineEntry::Line(mut line)) => {
-                    self.apply_paste_prediction(idx, paste_line, &mut line);
-                    self.lines.put(row, LineEntry::Line(line));
-                }
-                Some(LineEntry::LineAndFetching(mut line, instant)) => {
-                    self.apply_paste_prediction(idx, paste_line, &mut line);
-                    self.lines
-                        .put(row, LineEntry::LineAndFetching(line, instant));
-                }
-                Some(entry) => {
-                    self.lines.put(row, entry);
-                }
-                None => {}
-            }
+        let cursor = self.cursor_position;
+        let line = match self.cached_line(cursor.y) {
+            Some(line) => line.clone(),
+            None => return,
+        };
+        let cols = self.dimensions.cols;
+        if self
+            .predictions
+            .predict_paste(text, cursor, &line, cols, InputSerial::now())
+        {
+            self.notify_prediction_changed();
         }
-        self.cursor_position.y += lines.len().saturating_sub(1) as StableRowIndex;
     }
 
     pub fn update_last_send(&mut self) {
@@ -327,6 +236,7 @@ impl RenderableInner {
         // long it took for this response to come back
         if let Some(serial) = delta.input_serial {
             self.last_input_rtt = serial.elapsed_millis();
+            self.predictions.acknowledge(serial);
         }
 
         // When it comes to updating the cursor position, if the update was tagged
@@ -487,6 +397,9 @@ impl RenderableInner {
         } else {
             LineEntry::Line(line)
         };
+        if let LineEntry::Line(line) = &entry {
+            self.predictions.reconcile(stable_row, line);
+        }
         self.lines.put(stable_row, entry);
     }
 
@@ -587,6 +500,13 @@ impl RenderableInner {
             return Ok(());
         }
 
+        if self.reconnecting {
+            // Polling now would prompt the server to send the full
+            // contents of the pane before we've had a chance to tell
+            // it what we already have
+            return Ok(());
+        }
+
         if self.last_poll.elapsed() < self.poll_interval {
             return Ok(());
         }
@@ -712,7 +632,8 @@ pub(crate) async fn hydrate_lines(
 
 impl RenderableState {
     pub fn get_cursor_position(&self) -> StableCursorPosition {
-        self.inner.borrow().cursor_position
+        let inner = self.inner.borrow();
+        inner.predictions.cursor_position(inner.cursor_position)
     }
 
     pub fn get_lines(&self, lines: Range<StableRowIndex>) -> (StableRowIndex, Vec<Line>) {
@@ -751,13 +672,20 @@ impl RenderableState {
                     LineEntry::Fetching(now)
                 }
             };
+            inner.predictions.overlay(idx, result.last_mut().unwrap());
 
             if idx == inner.dimensions.physical_top {
-                if inner.is_tardy() {
-                    let status = format!(
+                let status = if inner.reconnecting {
+                    Some("wezterm: reconnecting⏳".to_string())
+                } else if inner.is_tardy() {
+                    Some(format!(
                         "wezterm: {:.0?}⏳since last response",
                         inner.last_recv_time.elapsed()
-                    );
+                    ))
+                } else {
+                    None
+                };
+                if let Some(status) = status {
                     // Right align it in the tab
                     let col = inner
                         .dimensions
@@ -811,6 +739,14 @@ impl RenderableState {
         }
 
         let mut result = RangeSet::new();
+        // Predictions are drawn over the lines as they are fetched,
+        // so their rows are considered to be changed until the
+        // predictions are resolved
+        for row in inner.predictions.rows() {
+            if lines.contains(&row) {
+                result.add(row);
+            }
+        }
         for r in lines {
             match inner.lines.get(&r) {
                 None => {
@@ -829,7 +765,7 @@ impl RenderableState {
 
         // If we're behind receiving an update, invalidate the top row so
         // that the indicator will update in a more timely fashion
-        if inner.is_tardy() {
+        if inner.reconnecting || inner.is_tardy() {
             // ... but take care to avoid always reporting it as dirty, so
             // that we don't end up busy looping just to repaint it
             if inner.last_late_dirty.elapsed() >= Duration::from_secs(1) {
@@ -0,0 +1,190 @@
+//! Remembers the server certificates of the TLS domains that use
+//! `trust_on_first_use`.  The first time that the server of such a
+//! domain presents a certificate that can't be verified, the user is
+//! asked whether to trust it, and its fingerprint is stored; subsequent
+//! connections accept only that certificate.
+//! The fingerprints are kept in a file in the data directory, and can
+//! be listed and revoked with `wezterm cli tls trust`.
+use anyhow::Context;
+use config::DATA_DIR;
+use openssl::hash::MessageDigest;
+use openssl::ssl::{SslConnectorBuilder, SslVerifyMode};
+use openssl::x509::X509Ref;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A server certificate that the user chose to trust
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustedCert {
+    /// The SHA-256 fingerprint of the certificate
+    pub fingerprint: String,
+    /// The subject of the certificate
+    pub subject: String,
+    /// The address of the server when the certificate was trusted
+    pub remote_address: String,
+    /// When the certificate was trusted, in seconds since the unix epoch
+    pub trusted_at: u64,
+}
+
+/// The trusted server certificates, keyed by the name of the domain
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrustStore {
+    domains: BTreeMap<String, TrustedCert>,
+}
+
+fn trust_store_path() -> PathBuf {
+    DATA_DIR.join("tls-trust.json")
+}
+
+impl TrustStore {
+    pub fn load() -> anyhow::Result<Self> {
+        let path = trust_store_path();
+        let data = match std::fs::read(&path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err).with_context(|| format!("reading {}", path.display())),
+        };
+        serde_json::from_slice(&data).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = trust_store_path();
+        std::fs::create_dir_all(&*DATA_DIR)
+            .with_context(|| format!("creating {}", DATA_DIR.display()))?;
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("writing {}", path.display()))
+    }
+
+    pub fn get(&self, domain: &str) -> Option<&TrustedCert> {
+        self.domains.get(domain)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &TrustedCert)> {
+        self.domains.iter()
+    }
+
+    /// Trusts `cert` for `domain`, replacing any certificate that
+    /// was previously trusted for it
+    pub fn trust(&mut self, domain: &str, cert: &PresentedCert, remote_address: &str) {
+        let trusted_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.domains.insert(
+            domain.to_string(),
+            TrustedCert {
+                fingerprint: cert.fingerprint.clone(),
+                subject: cert.subject.clone(),
+                remote_address: remote_address.to_string(),
+                trusted_at,
+            },
+        );
+    }
+
+    /// Forgets the certificate that was trusted for `domain`
+    pub fn revoke(&mut self, domain: &str) -> Option<TrustedCert> {
+        self.domains.remove(domain)
+    }
+}
+
+/// Returns the SHA-256 fingerprint of `cert` as colon separated hex
+pub fn fingerprint(cert: &X509Ref) -> anyhow::Result<String> {
+    let digest = cert.digest(MessageDigest::sha256())?;
+    Ok(digest
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":"))
+}
+
+/// Returns the subject of `cert` in the form `CN=name, O=org`
+pub fn subject(cert: &X509Ref) -> String {
+    cert.subject_name()
+        .entries()
+        .map(|entry| {
+            let name = entry.object().nid().short_name().unwrap_or("?");
+            match entry.data().as_utf8() {
+                Ok(value) => format!("{}={}", name, value),
+                Err(_) => format!("{}=?", name),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The certificate that was presented by the server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresentedCert {
+    pub fingerprint: String,
+    pub subject: String,
+    /// Whether it was accepted, either because it could be verified
+    /// or because its fingerprint was trusted
+    pub accepted: bool,
+}
+
+#[derive(Debug)]
+struct VerifyState {
+    chain_ok: bool,
+    presented: Option<PresentedCert>,
+}
+
+/// Captures the certificate that the server presented while the
+/// connection is being established
+#[derive(Debug, Clone)]
+pub struct CertCapture(Arc<Mutex<VerifyState>>);
+
+impl CertCapture {
+    pub fn presented(&self) -> Option<PresentedCert> {
+        self.0.lock().unwrap().presented.clone()
+    }
+}
+
+/// Configures `connector` to accept the certificate of the server if
+/// it can be verified as usual, or if its fingerprint is `trusted`.
+/// The certificate that the server presents is captured so that the
+/// user can be asked whether to trust it if it is rejected.
+pub fn verify_with_trusted_fingerprint(
+    connector: &mut SslConnectorBuilder,
+    trusted: Option<String>,
+) -> CertCapture {
+    let state = Arc::new(Mutex::new(VerifyState {
+        chain_ok: true,
+        presented: None,
+    }));
+    let capture = CertCapture(Arc::clone(&state));
+
+    connector.set_verify_callback(SslVerifyMode::PEER, move |preverify_ok, ctx| {
+        let mut state = state.lock().unwrap();
+        if !preverify_ok {
+            state.chain_ok = false;
+        }
+        // The decision is made for the certificate of the server itself;
+        // an untrusted issuer is acceptable if that one was trusted
+        if ctx.error_depth() > 0 {
+            return true;
+        }
+        let cert = match ctx.current_cert() {
+            Some(cert) => cert,
+            None => return false,
+        };
+        let fingerprint = match fingerprint(cert) {
+            Ok(fingerprint) => fingerprint,
+            Err(err) => {
+                log::error!("computing certificate fingerprint: {:#}", err);
+                return false;
+            }
+        };
+        let accepted = state.chain_ok || trusted.as_deref() == Some(fingerprint.as_str());
+        state.presented.replace(PresentedCert {
+            fingerprint,
+            subject: subject(cert),
+            accepted,
+        });
+        accepted
+    });
+
+    capture
+}
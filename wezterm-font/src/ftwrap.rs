@@ -630,7 +630,7 @@ impl Face {
         glyph_index: FT_UInt,
         load_flags: FT_Int32,
         render_mode: FT_Render_Mode,
-        synthesize_bold: bool,
+        synthesize_bold: Option<f64>,
     ) -> anyhow::Result<&FT_GlyphSlotRec_> {
         unsafe {
             ft_result(FT_Load_Glyph(self.face, glyph_index, load_flags), ()).with_context(
@@ -643,8 +643,30 @@ impl Face {
             )?;
             let slot = &mut *(*self.face).glyph;
 
-            if synthesize_bold {
-                FT_GlyphSlot_Embolden(slot as *mut _);
+            match synthesize_bold {
+                Some(strength)
+                    if strength != 1.
+                        && slot.format == FT_Glyph_Format::FT_GLYPH_FORMAT_OUTLINE =>
+                {
+                    // This is the same computation as FT_GlyphSlot_Embolden,
+                    // but with the strength scaled as requested
+                    let base = FT_MulFix(
+                        (*self.face).units_per_EM as FT_Long,
+                        (*(*self.face).size).metrics.y_scale,
+                    ) / 24;
+                    let amount = (base as f64 * strength) as FT_Pos;
+                    ft_result(FT_Outline_Embolden(&mut slot.outline, amount), ())
+                        .context("load_and_render_glyph: FT_Outline_Embolden")?;
+                    slot.metrics.width += amount;
+                    slot.metrics.height += amount;
+                    slot.metrics.horiAdvance += amount;
+                    slot.metrics.vertAdvance += amount;
+                    slot.metrics.horiBearingY += amount;
+                }
+                Some(_) => {
+                    FT_GlyphSlot_Embolden(slot as *mut _);
+                }
+                None => {}
             }
 
             ft_result(FT_Render_Glyph(slot, render_mode), ())
@@ -666,7 +688,7 @@ impl Face {
             anyhow::bail!("no I from which to compute cap height");
         }
         let (load_flags, render_mode) = compute_load_flags_from_config(None, None, None);
-        let ft_glyph = self.load_and_render_glyph(glyph_pos, load_flags, render_mode, false)?;
+        let ft_glyph = self.load_and_render_glyph(glyph_pos, load_flags, render_mode, None)?;
 
         let mode: FT_Pixel_Mode =
             unsafe { std::mem::transmute(u32::from(ft_glyph.bitmap.pixel_mode)) };
@@ -199,6 +199,8 @@ fn build_fallback_list_impl() -> anyhow::Result<Vec<ParsedFont>> {
         freetype_load_flags: None,
         scale: None,
         assume_emoji_presentation: None,
+        synthetic_bold_strength: None,
+        synthetic_italic_slant: None,
     };
     if let Ok(descriptors) = descriptor_from_attr(&symbols) {
         for descriptor in descriptors.iter() {
@@ -329,6 +329,8 @@ impl FontLocator for GdiFontLocator {
                         freetype_load_flags: None,
                         scale: None,
                         assume_emoji_presentation: None,
+                        synthetic_bold_strength: None,
+                        synthetic_italic_slant: None,
                     };
 
                     if !resolved.contains(&attr) {
@@ -33,6 +33,8 @@ pub struct ParsedFont {
     pub freetype_render_target: Option<FreeTypeLoadTarget>,
     pub freetype_load_flags: Option<FreeTypeLoadFlags>,
     pub scale: Option<f64>,
+    pub synthetic_bold_strength: Option<f64>,
+    pub synthetic_italic_slant: Option<f64>,
 }
 
 impl std::fmt::Debug for ParsedFont {
@@ -54,6 +56,8 @@ impl std::fmt::Debug for ParsedFont {
             .field("freetype_render_target", &self.freetype_render_target)
             .field("freetype_load_flags", &self.freetype_load_flags)
             .field("scale", &self.scale)
+            .field("synthetic_bold_strength", &self.synthetic_bold_strength)
+            .field("synthetic_italic_slant", &self.synthetic_italic_slant)
             .finish()
     }
 }
@@ -79,6 +83,8 @@ impl Clone for ParsedFont {
             freetype_load_flags: self.freetype_load_flags,
             is_built_in_fallback: self.is_built_in_fallback,
             scale: self.scale,
+            synthetic_bold_strength: self.synthetic_bold_strength,
+            synthetic_italic_slant: self.synthetic_italic_slant,
         }
     }
 }
@@ -416,6 +422,8 @@ impl ParsedFont {
             freetype_load_target: None,
             freetype_load_flags: None,
             scale: None,
+            synthetic_bold_strength: None,
+            synthetic_italic_slant: None,
         })
     }
 
@@ -657,11 +665,16 @@ impl ParsedFont {
         self.freetype_load_target = attr.freetype_load_target;
         self.freetype_load_flags = attr.freetype_load_flags;
         self.scale = attr.scale.map(|f| *f);
+        self.synthetic_bold_strength = attr.synthetic_bold_strength.map(|f| *f);
+        self.synthetic_italic_slant = attr.synthetic_italic_slant.map(|f| *f);
 
-        self.synthesize_italic = self.style == FontStyle::Normal && attr.style != FontStyle::Normal;
+        self.synthesize_italic = self.style == FontStyle::Normal
+            && attr.style != FontStyle::Normal
+            && self.synthetic_italic_slant != Some(0.);
         self.synthesize_bold = attr.weight >= FontWeight::DEMIBOLD
             && attr.weight > self.weight
-            && self.weight <= FontWeight::REGULAR;
+            && self.weight <= FontWeight::REGULAR
+            && self.synthetic_bold_strength != Some(0.);
         self.synthesize_dim = attr.weight < FontWeight::REGULAR
             && attr.weight < self.weight
             && self.weight >= FontWeight::REGULAR;
@@ -9,12 +9,18 @@ use std::cell::RefCell;
 use std::{mem, slice};
 use wezterm_color_types::linear_u8_to_srgb8;
 
+/// The horizontal skew used to synthesize italics
+/// when the font doesn't specify synthetic_italic_slant
+pub const DEFAULT_ITALIC_SLANT: f64 = 0.2;
+
 pub struct FreeTypeRasterizer {
     has_color: bool,
     face: RefCell<ftwrap::Face>,
     _lib: ftwrap::Library,
-    synthesize_bold: bool,
-    synthesize_italic: bool,
+    /// If set, synthesize bold with this multiple of the default strength
+    synthesize_bold: Option<f64>,
+    /// If set, synthesize italic with this horizontal skew factor
+    synthesize_italic: Option<f64>,
     freetype_load_target: Option<FreeTypeLoadTarget>,
     freetype_render_target: Option<FreeTypeLoadTarget>,
     freetype_load_flags: Option<FreeTypeLoadFlags>,
@@ -63,11 +69,12 @@ impl FontRasterizer for FreeTypeRasterizer {
         };
 
         let slot = unsafe { &mut *(*face.face).glyph };
-        if self.synthesize_italic && slot.format == FT_Glyph_Format::FT_GLYPH_FORMAT_BITMAP {
-            // The source was and thus the italic transform did nothing
-            Ok(glyph.skew())
-        } else {
-            Ok(glyph)
+        match self.synthesize_italic {
+            Some(slant) if slot.format == FT_Glyph_Format::FT_GLYPH_FORMAT_BITMAP => {
+                // The source was a bitmap and thus the italic transform did nothing
+                Ok(glyph.skew(slant))
+            }
+            _ => Ok(glyph),
         }
     }
 }
@@ -303,12 +310,27 @@ impl FreeTypeRasterizer {
             (((*face.face).face_flags as u32) & (ftwrap::FT_FACE_FLAG_COLOR as u32)) != 0
         };
 
-        if parsed.synthesize_italic {
+        let synthesize_italic = if parsed.synthesize_italic {
+            Some(
+                parsed
+                    .synthetic_italic_slant
+                    .unwrap_or(DEFAULT_ITALIC_SLANT),
+            )
+        } else {
+            None
+        };
+        let synthesize_bold = if parsed.synthesize_bold {
+            Some(parsed.synthetic_bold_strength.unwrap_or(1.))
+        } else {
+            None
+        };
+
+        if let Some(slant) = synthesize_italic {
             face.set_transform(Some(FT_Matrix {
-                xx: 1 * 65536,            // scale x
-                yy: 1 * 65536,            // scale y
-                xy: (0.2 * 65536.0) as _, // skew x
-                yx: 0 * 65536,            // skey y
+                xx: 1 * 65536,              // scale x
+                yy: 1 * 65536,              // scale y
+                xy: (slant * 65536.0) as _, // skew x
+                yx: 0 * 65536,              // skey y
             }));
         }
 
@@ -316,8 +338,8 @@ impl FreeTypeRasterizer {
             _lib: lib,
             face: RefCell::new(face),
             has_color,
-            synthesize_bold: parsed.synthesize_bold,
-            synthesize_italic: parsed.synthesize_italic,
+            synthesize_bold,
+            synthesize_italic,
             freetype_load_flags: parsed.freetype_load_flags,
             freetype_load_target: parsed.freetype_load_target,
             freetype_render_target: parsed.freetype_render_target,
@@ -18,11 +18,11 @@ pub struct RasterizedGlyph {
 
 impl RasterizedGlyph {
     /// Computes a skewed version of this glyph to produce a synthesized oblique variant
-    pub fn skew(&self) -> Self {
+    /// `factor` is the horizontal offset per pixel of height.
+    pub fn skew(&self, factor: f64) -> Self {
         // This function is derived from code which is subject to the terms of the
         // Mozilla Public License, v. 2.0. http://mozilla.org/MPL/2.0/
 
-        let factor = 0.2f64; // Skew factor
         let stride = self.width * 4;
 
         // Calculate the skewed horizontal offsets of the bottom and top of the glyph.
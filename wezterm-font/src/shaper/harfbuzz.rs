@@ -703,6 +703,8 @@ mod test {
                     harfbuzz_features: None,
                     scale: None,
                     assume_emoji_presentation: None,
+                    synthetic_bold_strength: None,
+                    synthetic_italic_slant: None,
                 },
                 14,
             )
@@ -331,9 +331,35 @@ pub struct LsFontsCommand {
     pub list_system: bool,
 
     /// Explain which fonts are used to render the supplied text string
-    #[clap(long = "text", conflicts_with = "list-system")]
+    #[clap(long = "text", alias = "explain", conflicts_with = "list-system")]
     pub text: Option<String>,
 }
 
 #[derive(Debug, Parser, Clone)]
-pub struct ShowKeysCommand {}
+pub struct ShowKeysCommand {
+    /// The format of the output.
+    /// The markdown and json formats list only the key assignments,
+    /// along with their descriptions, and are intended for use as
+    /// a cheatsheet.
+    #[clap(long, default_value = "text", possible_values=["text", "markdown", "json"])]
+    pub format: ShowKeysFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShowKeysFormat {
+    Text,
+    Markdown,
+    Json,
+}
+
+impl FromStr for ShowKeysFormat {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "text" => Ok(Self::Text),
+            "markdown" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            _ => anyhow::bail!("invalid show-keys format {}", s),
+        }
+    }
+}
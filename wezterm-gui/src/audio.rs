@@ -0,0 +1,76 @@
+//! Playback of sound files, used by the bell.
+//! Rather than linking in an audio stack, we defer to the sound
+//! facilities that are native to each platform.
+use std::path::{Path, PathBuf};
+
+#[cfg(windows)]
+fn play_sound_file_blocking(path: &Path) -> anyhow::Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+    use winapi::um::playsoundapi::{PlaySoundW, SND_FILENAME, SND_NODEFAULT, SND_SYNC};
+
+    let wide: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let ok = unsafe {
+        PlaySoundW(
+            wide.as_ptr(),
+            std::ptr::null_mut(),
+            SND_FILENAME | SND_NODEFAULT | SND_SYNC,
+        )
+    };
+    if ok == 0 {
+        anyhow::bail!("PlaySoundW failed for {}", path.display());
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn play_sound_file_blocking(path: &Path) -> anyhow::Result<()> {
+    let status = std::process::Command::new("afplay").arg(path).status()?;
+    if !status.success() {
+        anyhow::bail!("afplay {} failed: {}", path.display(), status);
+    }
+    Ok(())
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn play_sound_file_blocking(path: &Path) -> anyhow::Result<()> {
+    // Try the players for PulseAudio, PipeWire and ALSA, in that order
+    for player in &["paplay", "pw-play", "aplay"] {
+        match std::process::Command::new(player)
+            .arg(path)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+        {
+            Ok(status) if status.success() => return Ok(()),
+            Ok(status) => {
+                anyhow::bail!("{} {} failed: {}", player, path.display(), status);
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+    anyhow::bail!(
+        "unable to play {}: none of paplay, pw-play or aplay are installed",
+        path.display()
+    );
+}
+
+/// Plays the sound file at `path` in the background.
+/// A leading `~/` in the path is expanded to the home directory.
+/// Errors are logged rather than returned.
+pub fn play_sound_file(path: &str) {
+    let path = match path.strip_prefix("~/") {
+        Some(rest) => config::HOME_DIR.join(rest),
+        None => PathBuf::from(path),
+    };
+    std::thread::spawn(move || {
+        if let Err(err) = play_sound_file_blocking(&path) {
+            log::error!("play_sound_file: {:#}", err);
+        }
+    });
+}
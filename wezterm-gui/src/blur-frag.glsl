@@ -0,0 +1,30 @@
+// This is the backdrop blur fragment shader.
+// It applies one pass of a separable gaussian blur to `source`;
+// it is drawn once horizontally and then once vertically.
+
+precision highp float;
+
+in vec2 tex_coord;
+
+out vec4 color;
+
+uniform sampler2D source;
+// The distance between adjacent samples in texture coordinates;
+// (1 / width, 0) for the horizontal pass and (0, 1 / height)
+// for the vertical pass
+uniform vec2 direction;
+// The blur radius, in pixels
+uniform float radius;
+
+void main() {
+    int samples = int(ceil(radius));
+    float sigma = max(radius / 2.0, 1.0);
+    vec4 sum = vec4(0.0);
+    float total = 0.0;
+    for (int i = -samples; i <= samples; ++i) {
+        float weight = exp(-float(i * i) / (2.0 * sigma * sigma));
+        sum += texture(source, tex_coord + direction * float(i)) * weight;
+        total += weight;
+    }
+    color = sum / total;
+}
@@ -0,0 +1,330 @@
+//! Keeps a history of the text that was copied within wezterm,
+//! so that it can be pasted again using `PasteFromHistory`.
+//! The history is held in memory for the lifetime of the process,
+//! and can optionally be persisted to a file that is encrypted using
+//! a key derived from `clipboard_history_passphrase`.
+//! Loading and saving the file happens on a background thread, as
+//! deriving the key and encrypting the history takes a while.
+use anyhow::Context;
+use config::{configuration, DATA_DIR};
+use openssl::hash::MessageDigest;
+use openssl::symm::{decrypt_aead, encrypt_aead, Cipher};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Mutex, Once};
+
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+/// The number of PBKDF2 rounds used to derive the key from the passphrase
+const KDF_ITERATIONS: usize = 200_000;
+
+lazy_static::lazy_static! {
+    static ref HISTORY: Mutex<ClipboardHistory> = Mutex::new(ClipboardHistory::default());
+    static ref PERSIST: Mutex<Sender<PersistRequest>> = Mutex::new(spawn_persist_thread());
+}
+static LOAD: Once = Once::new();
+
+/// The most recently copied text is held at the front
+#[derive(Debug, Default, PartialEq)]
+pub struct ClipboardHistory {
+    entries: VecDeque<String>,
+}
+
+impl ClipboardHistory {
+    /// Adds `text` to the front of the history, removing any earlier
+    /// copy of the same text, and limits the history to `max_entries`
+    pub fn push(&mut self, text: &str, max_entries: usize) {
+        if text.is_empty() {
+            return;
+        }
+        self.entries.retain(|entry| entry != text);
+        self.entries.push_front(text.to_string());
+        self.entries.truncate(max_entries);
+    }
+
+    /// Adds `older` entries, most recent first, behind those that
+    /// are already present, and limits the history to `max_entries`
+    fn merge_older(&mut self, older: Vec<String>, max_entries: usize) {
+        for text in older {
+            if !text.is_empty() && !self.entries.contains(&text) {
+                self.entries.push_back(text);
+            }
+        }
+        self.entries.truncate(max_entries);
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &String> {
+        self.entries.iter()
+    }
+}
+
+/// A key derived from the passphrase, along with the
+/// salt that was used to derive it
+struct DerivedKey {
+    passphrase: String,
+    salt: Vec<u8>,
+    key: Vec<u8>,
+}
+
+impl DerivedKey {
+    fn new(passphrase: &str, salt: &[u8]) -> anyhow::Result<Self> {
+        let mut key = vec![0u8; KEY_LEN];
+        openssl::pkcs5::pbkdf2_hmac(
+            passphrase.as_bytes(),
+            salt,
+            KDF_ITERATIONS,
+            MessageDigest::sha256(),
+            &mut key,
+        )?;
+        Ok(Self {
+            passphrase: passphrase.to_string(),
+            salt: salt.to_vec(),
+            key,
+        })
+    }
+
+    fn with_random_salt(passphrase: &str) -> anyhow::Result<Self> {
+        let mut salt = [0u8; SALT_LEN];
+        openssl::rand::rand_bytes(&mut salt)?;
+        Self::new(passphrase, &salt)
+    }
+
+    /// Encrypts `plain` using AES-256-GCM.  The result is the salt,
+    /// followed by the nonce, the tag and then the ciphertext.
+    fn encrypt(&self, plain: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        openssl::rand::rand_bytes(&mut nonce)?;
+        let mut tag = [0u8; TAG_LEN];
+        let cipher = encrypt_aead(
+            Cipher::aes_256_gcm(),
+            &self.key,
+            Some(&nonce),
+            &[],
+            plain,
+            &mut tag,
+        )?;
+        let mut result = Vec::with_capacity(SALT_LEN + NONCE_LEN + TAG_LEN + cipher.len());
+        result.extend_from_slice(&self.salt);
+        result.extend_from_slice(&nonce);
+        result.extend_from_slice(&tag);
+        result.extend_from_slice(&cipher);
+        Ok(result)
+    }
+}
+
+/// Decrypts data produced by `DerivedKey::encrypt`, returning
+/// the plain text and the key that decrypted it
+fn decrypt(passphrase: &str, data: &[u8]) -> anyhow::Result<(Vec<u8>, DerivedKey)> {
+    anyhow::ensure!(
+        data.len() >= SALT_LEN + NONCE_LEN + TAG_LEN,
+        "data is truncated"
+    );
+    let (salt, rest) = data.split_at(SALT_LEN);
+    let (nonce, rest) = rest.split_at(NONCE_LEN);
+    let (tag, cipher) = rest.split_at(TAG_LEN);
+    let key = DerivedKey::new(passphrase, salt)?;
+    let plain = decrypt_aead(
+        Cipher::aes_256_gcm(),
+        &key.key,
+        Some(nonce),
+        &[],
+        cipher,
+        tag,
+    )
+    .context("decrypting; the passphrase may be wrong or the data corrupt")?;
+    Ok((plain, key))
+}
+
+fn history_file_path() -> PathBuf {
+    DATA_DIR.join("clipboard-history")
+}
+
+/// Writes `data` to `path`, readable only by the current user
+fn write_private_file(path: &PathBuf, data: &[u8]) -> anyhow::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("opening {} for write", path.display()))?;
+    std::io::Write::write_all(&mut file, data)
+        .with_context(|| format!("writing {}", path.display()))
+}
+
+fn passphrase() -> anyhow::Result<String> {
+    configuration()
+        .clipboard_history_passphrase
+        .clone()
+        .filter(|passphrase| !passphrase.is_empty())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "clipboard_history_persist requires clipboard_history_passphrase to be set"
+            )
+        })
+}
+
+enum PersistRequest {
+    Load,
+    Save,
+}
+
+/// The state of the thread that loads and saves the history file
+#[derive(Default)]
+struct Persister {
+    key: Option<DerivedKey>,
+    /// The history file is only saved once it has been loaded,
+    /// so that a file that could not be loaded isn't overwritten
+    loaded: bool,
+}
+
+impl Persister {
+    fn load(&mut self) -> anyhow::Result<()> {
+        let passphrase = passphrase()?;
+        let path = history_file_path();
+        let data = match std::fs::read(&path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                self.loaded = true;
+                return Ok(());
+            }
+            Err(err) => return Err(err).with_context(|| format!("reading {}", path.display())),
+        };
+        let (plain, key) =
+            decrypt(&passphrase, &data).with_context(|| format!("loading {}", path.display()))?;
+        let entries: Vec<String> = serde_json::from_slice(&plain)?;
+        HISTORY
+            .lock()
+            .unwrap()
+            .merge_older(entries, configuration().clipboard_history_size);
+        self.key.replace(key);
+        self.loaded = true;
+        Ok(())
+    }
+
+    fn save(&mut self) -> anyhow::Result<()> {
+        if !self.loaded {
+            return Ok(());
+        }
+        let passphrase = passphrase()?;
+        let key = match self.key.take() {
+            Some(key) if key.passphrase == passphrase => key,
+            _ => DerivedKey::with_random_salt(&passphrase)?,
+        };
+        let entries: Vec<String> = HISTORY.lock().unwrap().entries().cloned().collect();
+        let result = key
+            .encrypt(&serde_json::to_vec(&entries)?)
+            .and_then(|data| {
+                std::fs::create_dir_all(&*DATA_DIR)
+                    .with_context(|| format!("creating {}", DATA_DIR.display()))?;
+                write_private_file(&history_file_path(), &data)
+            });
+        self.key.replace(key);
+        result
+    }
+}
+
+fn spawn_persist_thread() -> Sender<PersistRequest> {
+    let (tx, rx) = channel();
+    std::thread::Builder::new()
+        .name("clipboard-history".to_string())
+        .spawn(move || persist_thread(rx))
+        .expect("failed to spawn clipboard history thread");
+    tx
+}
+
+fn persist_thread(rx: Receiver<PersistRequest>) {
+    let mut persister = Persister::default();
+    while let Ok(request) = rx.recv() {
+        let result = match request {
+            PersistRequest::Load => persister.load(),
+            PersistRequest::Save => {
+                // A save captures the whole history, so any
+                // further pending saves are redundant.
+                // The load is always the first request.
+                while rx.try_recv().is_ok() {}
+                persister.save()
+            }
+        };
+        if let Err(err) = result {
+            log::error!("clipboard history: {:#}", err);
+        }
+    }
+}
+
+fn persist(request: PersistRequest) {
+    PERSIST.lock().unwrap().send(request).ok();
+}
+
+/// Starts loading the persisted history, the first
+/// time that it is called
+fn load_persisted_history() {
+    if configuration().clipboard_history_persist {
+        LOAD.call_once(|| persist(PersistRequest::Load));
+    }
+}
+
+/// Records text that was copied within wezterm
+pub fn record_copy(text: &str) {
+    let config = configuration();
+    if config.clipboard_history_size == 0 {
+        return;
+    }
+    load_persisted_history();
+    HISTORY
+        .lock()
+        .unwrap()
+        .push(text, config.clipboard_history_size);
+    if config.clipboard_history_persist {
+        persist(PersistRequest::Save);
+    }
+}
+
+/// Returns the history, most recent first
+pub fn entries() -> Vec<String> {
+    load_persisted_history();
+    HISTORY.lock().unwrap().entries().cloned().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn push_dedups_and_truncates() {
+        let mut history = ClipboardHistory::default();
+        history.push("one", 3);
+        history.push("two", 3);
+        history.push("", 3);
+        history.push("three", 3);
+        history.push("one", 3);
+        history.push("four", 3);
+        assert_eq!(
+            history.entries().map(String::as_str).collect::<Vec<_>>(),
+            vec!["four", "one", "three"]
+        );
+
+        history.merge_older(vec!["one".to_string(), "five".to_string()], 4);
+        assert_eq!(
+            history.entries().map(String::as_str).collect::<Vec<_>>(),
+            vec!["four", "one", "three", "five"]
+        );
+    }
+
+    #[test]
+    fn encryption_round_trip() {
+        let key = DerivedKey::with_random_salt("correct horse").unwrap();
+        let data = key.encrypt(b"secret").unwrap();
+        let (plain, decrypted_with) = decrypt("correct horse", &data).unwrap();
+        assert_eq!(plain, b"secret");
+        assert_eq!(decrypted_with.key, key.key);
+
+        assert!(decrypt("battery staple", &data).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use config::keyassignment::*;
 use config::{ConfigHandle, DeferredKeyCode};
 use ordered_float::NotNan;
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use window::{KeyCode, Modifiers};
 use KeyAssignment::*;
@@ -144,6 +145,17 @@ impl CommandDef {
         }
         result
     }
+
+    /// Returns the brief descriptions of the commands, keyed by the
+    /// debug representation of their actions.  This is used to
+    /// describe key assignments that don't have a description
+    /// of their own.
+    pub fn action_briefs(config: &ConfigHandle) -> HashMap<String, String> {
+        Self::expanded_commands(config)
+            .into_iter()
+            .map(|cmd| (format!("{:?}", cmd.action), cmd.brief.to_string()))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -217,6 +229,37 @@ static DEFS: &[CommandDef] = &[
         keys: &[(Modifiers::SUPER, "v"), (Modifiers::NONE, "Paste")],
         args: &[ArgType::ActivePane],
     },
+    CommandDef {
+        brief: "Paste from clipboard history",
+        doc: "Pastes text that was recently copied within wezterm",
+        exp: |exp| exp.push(PasteFromHistory),
+        keys: &[],
+        args: &[ArgType::ActivePane],
+    },
+    CommandDef {
+        brief: "Paste from a register",
+        doc: "Lists the named registers and pastes the content of \
+              the chosen register",
+        exp: |exp| exp.push(ShowRegisters),
+        keys: &[],
+        args: &[ArgType::ActivePane],
+    },
+    CommandDef {
+        brief: "Show key binding cheatsheet",
+        doc: "Shows a searchable list of the key assignments \
+              of the current configuration",
+        exp: |exp| exp.push(ShowKeyCheatsheet),
+        keys: &[],
+        args: &[ArgType::ActiveWindow],
+    },
+    CommandDef {
+        brief: "Enter Emoji / Character selection mode",
+        doc: "Shows a picker for emoji and other symbols, and \
+              inserts the chosen text into the active pane",
+        exp: |exp| exp.push(CharSelect(CharSelectArguments::default())),
+        keys: &[(Modifiers::CTRL.union(Modifiers::SHIFT), "u")],
+        args: &[ArgType::ActivePane],
+    },
     CommandDef {
         brief: "Toggle full screen mode",
         doc: "Switch between normal and full screen mode",
@@ -302,6 +345,19 @@ static DEFS: &[CommandDef] = &[
         keys: &[(Modifiers::CTRL.union(Modifiers::SHIFT), "Space")],
         args: &[ArgType::ActivePane],
     },
+    CommandDef {
+        brief: "Open a hyperlink using the keyboard",
+        doc: "Labels the hyperlinks in the current pane; typing a label \
+            opens the corresponding link",
+        exp: |exp| {
+            exp.push(QuickSelectArgs(QuickSelectArguments {
+                hyperlinks: true,
+                ..Default::default()
+            }));
+        },
+        keys: &[],
+        args: &[ArgType::ActivePane],
+    },
     CommandDef {
         brief: "Decrease font size",
         doc: "Scales the font size smaller by 10%",
@@ -649,6 +705,22 @@ static DEFS: &[CommandDef] = &[
         keys: &[(Modifiers::CTRL.union(Modifiers::SHIFT), "z")],
         args: &[ArgType::ActivePane],
     },
+    CommandDef {
+        brief: "Toggle Pane Read-Only",
+        doc: "Blocks, or unblocks, all input to the current pane, \
+              while still allowing its output to be scrolled and selected",
+        exp: |exp| exp.push(TogglePaneReadOnly),
+        keys: &[],
+        args: &[ArgType::ActivePane],
+    },
+    CommandDef {
+        brief: "Toggle stacked layout",
+        doc: "Stacks the panes in the current tab, showing only the active pane \
+              and the titles of the others, or restores the split layout",
+        exp: |exp| exp.push(ToggleStackedLayout),
+        keys: &[],
+        args: &[ArgType::ActivePane],
+    },
     CommandDef {
         brief: "Activate the last active tab",
         doc: "If there was no prior active tab, has no effect.",
@@ -705,6 +777,57 @@ static DEFS: &[CommandDef] = &[
         keys: &[],
         args: &[ArgType::ActivePane],
     },
+    CommandDef {
+        brief: "Toggle output fold",
+        doc: "Folds or expands the most recent command output in the viewport",
+        exp: |exp| exp.push(ToggleOutputFold),
+        keys: &[],
+        args: &[ArgType::ActivePane],
+    },
+    CommandDef {
+        brief: "Fold all output",
+        doc: "Folds the output of every command into a one line summary",
+        exp: |exp| exp.push(FoldAllOutput),
+        keys: &[],
+        args: &[ArgType::ActivePane],
+    },
+    CommandDef {
+        brief: "Unfold all output",
+        doc: "Expands all folded command output",
+        exp: |exp| exp.push(UnfoldAllOutput),
+        keys: &[],
+        args: &[ArgType::ActivePane],
+    },
+    CommandDef {
+        brief: "Toggle line wrap",
+        doc: "Shows each line on a single row that can be scrolled horizontally, or wraps lines again",
+        exp: |exp| exp.push(ToggleLineWrap),
+        keys: &[],
+        args: &[ArgType::ActivePane],
+    },
+    CommandDef {
+        brief: "Compare panes",
+        doc: "Shows the differences between the text of the active \
+              pane and another pane in the current tab",
+        exp: |exp| exp.push(DiffPanes(DiffPanesArguments::default())),
+        keys: &[],
+        args: &[ArgType::ActivePane],
+    },
+    CommandDef {
+        brief: "Toggle bell mute",
+        doc: "Silences or restores the bell in the current pane",
+        exp: |exp| exp.push(ToggleBellMute),
+        keys: &[],
+        args: &[ArgType::ActivePane],
+    },
+    CommandDef {
+        brief: "Stop recording macro",
+        doc: "Stops recording the macro that was started by StartMacroRecording, \
+              and saves it so that it can be replayed by PlayMacro",
+        exp: |exp| exp.push(StopMacroRecording),
+        keys: &[],
+        args: &[ArgType::ActiveWindow],
+    },
     CommandDef {
         brief: "Show the launcher",
         doc: "Shows the launcher menu",
@@ -712,6 +835,14 @@ static DEFS: &[CommandDef] = &[
         keys: &[],
         args: &[ArgType::ActiveWindow],
     },
+    CommandDef {
+        brief: "Edit settings",
+        doc: "Shows an editor for the launch menu, default program \
+              and ssh domains",
+        exp: |exp| exp.push(ShowSettingsEditor),
+        keys: &[],
+        args: &[ArgType::ActiveWindow],
+    },
     CommandDef {
         brief: "Navigate tabs",
         doc: "Shows the tab navigator",
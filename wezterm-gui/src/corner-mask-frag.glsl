@@ -0,0 +1,25 @@
+// This is the corner mask fragment shader.
+// It is drawn over the whole frame after everything else has been
+// rendered, and the output is used to scale the color and alpha of
+// the frame, so that the area outside of the rounded corners of the
+// window becomes transparent, with an antialiased edge.
+
+precision highp float;
+
+in vec2 tex_coord;
+
+out vec4 color;
+
+// The size of the frame, in pixels
+uniform vec2 resolution;
+// The radius of the corners, in pixels
+uniform float radius;
+
+void main() {
+  vec2 pos = tex_coord * resolution;
+  // The nearest point of the rectangle that is inset by the radius;
+  // only points in the corners are further than radius from it
+  vec2 nearest = clamp(pos, vec2(radius), resolution - vec2(radius));
+  float coverage = clamp(radius - length(pos - nearest) + 0.5, 0.0, 1.0);
+  color = vec4(coverage);
+}
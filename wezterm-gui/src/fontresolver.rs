@@ -0,0 +1,211 @@
+//! Explains which fonts are used to render a string of text.
+//! This is the logic behind `wezterm ls-fonts --text` and
+//! the `wezterm.font_resolver` lua function.
+//! Also measures styled text for `wezterm.format_width` and
+//! `window:measure_text`.
+use crate::customglyph::BlockKey;
+use config::ConfigHandle;
+use luahelper::impl_lua_conversion_dynamic;
+use termwiz::cell::{CellAttributes, UnicodeVersion};
+use termwiz::surface::{Line, SEQ_ZERO};
+use wezterm_bidi::Direction;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+use wezterm_font::parser::ParsedFont;
+use wezterm_font::shaper::PresentationWidth;
+use wezterm_font::FontConfiguration;
+
+/// A glyph produced by shaping the text
+pub struct ResolvedGlyph {
+    /// The byte offset of the glyph's cluster within the text
+    pub cluster: usize,
+    /// The text that the glyph represents
+    pub text: String,
+    pub x_advance: f64,
+    pub num_cells: u8,
+    pub glyph_pos: u32,
+    pub glyph_name: Option<String>,
+    /// The font that provides the glyph
+    pub font: ParsedFont,
+    /// Set if wezterm draws the glyph itself rather than using the font
+    pub custom_block: Option<BlockKey>,
+}
+
+/// A run of text that shares the same attributes and direction
+pub struct ResolvedCluster {
+    pub direction: Direction,
+    pub glyphs: Vec<ResolvedGlyph>,
+}
+
+/// Shapes `text` with the fonts that would be selected for `attrs`
+/// and reports the font that provides each glyph
+pub fn resolve_text(
+    config: &ConfigHandle,
+    font_config: &FontConfiguration,
+    text: &str,
+    attrs: &CellAttributes,
+) -> anyhow::Result<Vec<ResolvedCluster>> {
+    let unicode_version = UnicodeVersion {
+        version: config.unicode_version,
+        ambiguous_are_wide: config.treat_east_asian_ambiguous_width_as_wide,
+    };
+
+    let line = Line::from_text(text, attrs, SEQ_ZERO, Some(unicode_version));
+    let cell_clusters = line.cluster(bidi_hint(config));
+    let ft_lib = wezterm_font::ftwrap::Library::new()?;
+    let mut result = vec![];
+
+    for cluster in cell_clusters {
+        let style = font_config.match_style(config, &cluster.attrs);
+        let font = font_config.resolve_font(style)?;
+        let presentation_width = PresentationWidth::with_cluster(&cluster);
+        let infos = font.blocking_shape(
+            &cluster.text,
+            Some(cluster.presentation),
+            cluster.direction,
+            None,
+            Some(&presentation_width),
+        )?;
+
+        // We must grab the handles after shaping, so that we get the
+        // revised list that includes system fallbacks!
+        let handles = font.clone_handles();
+        let faces: Vec<_> = handles
+            .iter()
+            .map(|p| ft_lib.face_from_locator(&p.handle).ok())
+            .collect();
+
+        let mut iter = infos.iter().peekable();
+
+        let mut byte_lens = vec![];
+        for c in cluster.text.chars() {
+            let len = c.len_utf8();
+            for _ in 0..len {
+                byte_lens.push(len);
+            }
+        }
+
+        let mut glyphs = vec![];
+        while let Some(info) = iter.next() {
+            let idx = cluster.byte_to_cell_idx(info.cluster as usize);
+
+            let text = if cluster.direction == Direction::LeftToRight {
+                if let Some(next) = iter.peek() {
+                    line.columns_as_str(idx..cluster.byte_to_cell_idx(next.cluster as usize))
+                } else {
+                    let last_idx = cluster.byte_to_cell_idx(cluster.text.len() - 1);
+                    line.columns_as_str(idx..last_idx + 1)
+                }
+            } else {
+                let info_len = byte_lens[info.cluster as usize];
+                let last_idx = cluster.byte_to_cell_idx(info.cluster as usize + info_len - 1);
+                line.columns_as_str(idx..last_idx + 1)
+            };
+
+            let custom_block = if config.custom_block_glyphs {
+                BlockKey::from_str(&text)
+            } else {
+                None
+            };
+
+            let glyph_name = faces[info.font_idx]
+                .as_ref()
+                .and_then(|face| face.get_glyph_name(info.glyph_pos));
+
+            glyphs.push(ResolvedGlyph {
+                cluster: info.cluster as usize,
+                text,
+                x_advance: info.x_advance.get(),
+                num_cells: info.num_cells,
+                glyph_pos: info.glyph_pos,
+                glyph_name,
+                font: handles[info.font_idx].clone(),
+                custom_block,
+            });
+        }
+
+        result.push(ResolvedCluster {
+            direction: cluster.direction,
+            glyphs,
+        });
+    }
+
+    Ok(result)
+}
+
+fn bidi_hint(config: &ConfigHandle) -> Option<wezterm_bidi::ParagraphDirectionHint> {
+    if config.bidi_enabled {
+        Some(config.bidi_direction)
+    } else {
+        None
+    }
+}
+
+/// The position of a glyph within measured text
+#[derive(Debug, Default, FromDynamic, ToDynamic)]
+pub struct GlyphExtent {
+    /// The text that the glyph represents
+    pub text: String,
+    /// The cell index of the start of the glyph's text
+    pub column: usize,
+    /// The number of cells that the glyph occupies
+    pub cells: usize,
+    /// The distance in pixels from the start of the text
+    pub x: f64,
+    pub x_advance: f64,
+}
+
+/// The size of styled text once it has been shaped
+#[derive(Debug, Default, FromDynamic, ToDynamic)]
+pub struct TextExtent {
+    pub cells: usize,
+    pub pixels: f64,
+    pub glyphs: Vec<GlyphExtent>,
+}
+impl_lua_conversion_dynamic!(TextExtent);
+
+/// Shapes `line` and measures the result.  The title font is used
+/// when `use_title_font` is true, as it is for the fancy tab bar;
+/// otherwise the fonts are selected by the attributes of the cells,
+/// as they are in the terminal and the retro tab bar.
+/// Ligatures and double-width characters are accounted for.
+pub fn measure_line(
+    config: &ConfigHandle,
+    font_config: &FontConfiguration,
+    line: &Line,
+    use_title_font: bool,
+) -> anyhow::Result<TextExtent> {
+    let mut extent = TextExtent::default();
+
+    for cluster in line.cluster(bidi_hint(config)) {
+        let font = if use_title_font {
+            font_config.title_font()?
+        } else {
+            font_config.resolve_font(font_config.match_style(config, &cluster.attrs))?
+        };
+        let presentation_width = PresentationWidth::with_cluster(&cluster);
+        let infos = font.blocking_shape(
+            &cluster.text,
+            Some(cluster.presentation),
+            cluster.direction,
+            None,
+            Some(&presentation_width),
+        )?;
+
+        for info in &infos {
+            let column = cluster.byte_to_cell_idx(info.cluster as usize);
+            let cells = info.num_cells as usize;
+            let x_advance = info.x_advance.get();
+            extent.glyphs.push(GlyphExtent {
+                text: line.columns_as_str(column..column + cells.max(1)),
+                column,
+                cells,
+                x: extent.pixels,
+                x_advance,
+            });
+            extent.cells += cells;
+            extent.pixels += x_advance;
+        }
+    }
+
+    Ok(extent)
+}
@@ -0,0 +1,182 @@
+//! Remembers how often and how recently the entries of the launcher
+//! were chosen, so that the entries that are used the most can be
+//! ranked ahead of the others.
+//! The usage is persisted to a file in the data directory so that
+//! it is retained when wezterm is restarted.
+use anyhow::Context;
+use config::DATA_DIR;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+
+/// Entries that haven't been chosen for this long are forgotten
+const MAX_AGE: u64 = 90 * DAY;
+
+/// The number of entries that are remembered
+const MAX_ENTRIES: usize = 500;
+
+lazy_static::lazy_static! {
+    static ref FRECENCY: Mutex<Option<Frecency>> = Mutex::new(None);
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+struct Usage {
+    count: u32,
+    /// Seconds since the unix epoch
+    last_used: u64,
+}
+
+/// The usage of launcher entries, keyed by their labels
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Frecency {
+    entries: HashMap<String, Usage>,
+}
+
+impl Frecency {
+    /// Records that the entry identified by `key` was chosen at `now`
+    pub fn record(&mut self, key: &str, now: u64) {
+        let usage = self.entries.entry(key.to_string()).or_default();
+        usage.count = usage.count.saturating_add(1);
+        usage.last_used = now;
+        self.prune(now);
+    }
+
+    /// Forgets old entries, and then the least recently used entries
+    /// if there are still more than MAX_ENTRIES
+    fn prune(&mut self, now: u64) {
+        self.entries
+            .retain(|_, usage| now.saturating_sub(usage.last_used) < MAX_AGE);
+        if self.entries.len() > MAX_ENTRIES {
+            let mut last_used: Vec<u64> = self.entries.values().map(|u| u.last_used).collect();
+            last_used.sort_unstable_by(|a, b| b.cmp(a));
+            let cutoff = last_used[MAX_ENTRIES - 1];
+            self.entries.retain(|_, usage| usage.last_used >= cutoff);
+        }
+    }
+
+    /// Returns the score for the entry identified by `key`, which is
+    /// the number of times that it was chosen, weighted by how recently
+    /// it was last chosen.  Entries that were never chosen score 0.
+    pub fn score(&self, key: &str, now: u64) -> i64 {
+        let usage = match self.entries.get(key) {
+            Some(usage) => usage,
+            None => return 0,
+        };
+        let age = now.saturating_sub(usage.last_used);
+        let weight = if age < HOUR {
+            16
+        } else if age < DAY {
+            8
+        } else if age < 7 * DAY {
+            4
+        } else if age < 30 * DAY {
+            2
+        } else {
+            1
+        };
+        usage.count as i64 * weight
+    }
+}
+
+/// Returns the current time in seconds since the unix epoch
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn frecency_file_path() -> PathBuf {
+    DATA_DIR.join("launcher-frecency.json")
+}
+
+fn load_frecency() -> anyhow::Result<Frecency> {
+    let path = frecency_file_path();
+    let data = match std::fs::read(&path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Frecency::default()),
+        Err(err) => return Err(err).with_context(|| format!("reading {}", path.display())),
+    };
+    serde_json::from_slice(&data).with_context(|| format!("parsing {}", path.display()))
+}
+
+fn save_frecency(frecency: &Frecency) -> anyhow::Result<()> {
+    let path = frecency_file_path();
+    std::fs::create_dir_all(&*DATA_DIR)
+        .with_context(|| format!("creating {}", DATA_DIR.display()))?;
+    std::fs::write(&path, serde_json::to_vec(frecency)?)
+        .with_context(|| format!("writing {}", path.display()))
+}
+
+/// Runs `func` with the usage, loading the persisted usage
+/// the first time that it is called
+fn with_frecency<F, R>(func: F) -> R
+where
+    F: FnOnce(&mut Frecency) -> R,
+{
+    let mut frecency = FRECENCY.lock().unwrap();
+    let frecency = frecency.get_or_insert_with(|| {
+        load_frecency().unwrap_or_else(|err| {
+            log::error!("launcher frecency: {:#}", err);
+            Frecency::default()
+        })
+    });
+    func(frecency)
+}
+
+/// Records that the launcher entry labelled `key` was chosen
+pub fn record_launch(key: &str) {
+    with_frecency(|frecency| {
+        frecency.record(key, now());
+        if let Err(err) = save_frecency(frecency) {
+            log::error!("launcher frecency: {:#}", err);
+        }
+    });
+}
+
+/// Returns a copy of the usage
+pub fn snapshot() -> Frecency {
+    with_frecency(|frecency| frecency.clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scores() {
+        let now = 1_000 * DAY;
+        let mut frecency = Frecency::default();
+        frecency.record("a", now - 2 * DAY);
+        frecency.record("a", now - 2 * DAY);
+        frecency.record("b", now - 10);
+        assert_eq!(frecency.score("a", now), 8);
+        assert_eq!(frecency.score("b", now), 16);
+        assert_eq!(frecency.score("c", now), 0);
+        // Ages out of the more heavily weighted periods
+        assert_eq!(frecency.score("b", now + 2 * DAY), 4);
+        assert_eq!(frecency.score("a", now + 60 * DAY), 2);
+    }
+
+    #[test]
+    fn prune() {
+        let now = 1_000 * DAY;
+        let mut frecency = Frecency::default();
+        frecency.record("old", now - MAX_AGE);
+        frecency.record("new", now);
+        assert_eq!(frecency.score("old", now), 0);
+        assert_eq!(frecency.score("new", now), 16);
+
+        for i in 0..MAX_ENTRIES + 10 {
+            frecency.record(&i.to_string(), now + i as u64);
+        }
+        assert_eq!(frecency.entries.len(), MAX_ENTRIES);
+        assert_eq!(frecency.score("new", now + DAY), 0);
+        assert_eq!(frecency.score("0", now + DAY), 0);
+    }
+}
@@ -1,26 +1,64 @@
 use crate::scripting::guiwin::GuiWin;
+use crate::scripting::pane::PaneObject;
+use crate::termwindow::clipboard::clipboard_policy_for_pane;
 use crate::termwindow::TermWindowNotif;
 use crate::TermWindow;
 use ::window::*;
 use anyhow::{Context, Error};
+use config::ClipboardPolicy;
 pub use config::FrontEndSelection;
+use luahelper::impl_lua_conversion_dynamic;
 use mux::client::ClientId;
+use mux::pane::PaneId;
 use mux::window::WindowId as MuxWindowId;
-use mux::{Mux, MuxNotification};
+use mux::{FileTransferDirection, Mux, MuxNotification};
 use promise::{Future, Promise};
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashSet};
 use std::rc::Rc;
 use std::sync::Arc;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
 use wezterm_term::{Alert, ClipboardSelection};
 use wezterm_toast_notification::*;
 
+/// The information passed to the `file-transfer-progress` event
+#[derive(Debug, Clone, FromDynamic, ToDynamic)]
+struct FileTransferProgress {
+    path: String,
+    direction: String,
+    transferred: u64,
+    total_size: Option<u64>,
+}
+impl_lua_conversion_dynamic!(FileTransferProgress);
+
+async fn emit_file_transfer_progress(
+    lua: Option<Rc<mlua::Lua>>,
+    pane_id: PaneId,
+    progress: FileTransferProgress,
+) -> anyhow::Result<()> {
+    if let Some(lua) = lua {
+        let pane = match Mux::get().and_then(|mux| mux.get_pane(pane_id)) {
+            Some(pane) => PaneObject::new(&pane),
+            None => return Ok(()),
+        };
+        let args = lua.pack_multi((pane, progress))?;
+        config::lua::emit_event(&lua, ("file-transfer-progress".to_string(), args))
+            .await
+            .map_err(|e| {
+                log::error!("while processing file-transfer-progress event: {:#}", e);
+                e
+            })?;
+    }
+    Ok(())
+}
+
 pub struct GuiFrontEnd {
     connection: Rc<Connection>,
     switching_workspaces: RefCell<bool>,
     spawned_mux_window: RefCell<HashSet<MuxWindowId>>,
     known_windows: RefCell<BTreeMap<Window, MuxWindowId>>,
     client_id: Arc<ClientId>,
+    _config_subscription: config::ConfigSubscription,
 }
 
 impl Drop for GuiFrontEnd {
@@ -36,12 +74,21 @@ impl GuiFrontEnd {
         let mux = Mux::get().expect("mux started and running on main thread");
         let client_id = mux.active_identity().expect("to have set my own id");
 
+        let config_subscription = config::subscribe_to_config_reload(move || {
+            promise::spawn::spawn_into_main_thread(async move {
+                crate::tray::update(&config::configuration());
+            })
+            .detach();
+            true
+        });
+
         let front_end = Rc::new(GuiFrontEnd {
             connection,
             switching_workspaces: RefCell::new(false),
             spawned_mux_window: RefCell::new(HashSet::new()),
             known_windows: RefCell::new(BTreeMap::new()),
             client_id: client_id.clone(),
+            _config_subscription: config_subscription,
         });
         let fe = Rc::downgrade(&front_end);
         mux.subscribe(move |n| {
@@ -60,9 +107,15 @@ impl GuiFrontEnd {
                         .detach();
                     }
                     MuxNotification::PaneRemoved(_) => {}
+                    MuxNotification::PaneFocused(_) => {}
+                    MuxNotification::PaneHibernated(_) => {}
+                    MuxNotification::PaneResized { .. } => {}
                     MuxNotification::WindowInvalidated(_) => {}
                     MuxNotification::PaneOutput(_) => {}
                     MuxNotification::PaneAdded(_) => {}
+                    MuxNotification::WorkspaceCreated(_) | MuxNotification::WorkspaceRemoved(_) => {
+                        // The lua events are emitted by the mux
+                    }
                     MuxNotification::Alert {
                         pane_id: _,
                         alert:
@@ -94,7 +147,11 @@ impl GuiFrontEnd {
                             | Alert::WindowTitleChanged(_)
                             | Alert::TabTitleChanged(_)
                             | Alert::IconTitleChanged(_)
-                            | Alert::SetUserVar { .. },
+                            | Alert::SetUserVar { .. }
+                            | Alert::CommandStarted { .. }
+                            | Alert::CommandFinished { .. }
+                            | Alert::WindowOperation(_)
+                            | Alert::ClipboardQuery(_),
                     } => {}
                     MuxNotification::Empty => {
                         if mux::activity::Activity::count() == 0 {
@@ -113,6 +170,28 @@ impl GuiFrontEnd {
                             log::error!("save_to_downloads: {:#}", err);
                         }
                     }
+                    MuxNotification::FileTransferProgress {
+                        pane_id,
+                        path,
+                        direction,
+                        transferred,
+                        total_size,
+                    } => {
+                        let progress = FileTransferProgress {
+                            path,
+                            direction: match direction {
+                                FileTransferDirection::Upload => "Upload",
+                                FileTransferDirection::Download => "Download",
+                            }
+                            .to_string(),
+                            transferred,
+                            total_size,
+                        };
+                        promise::spawn::spawn(config::with_lua_config_on_main_thread(move |lua| {
+                            emit_file_transfer_progress(lua, pane_id, progress)
+                        }))
+                        .detach();
+                    }
                     MuxNotification::AssignClipboard {
                         pane_id,
                         selection,
@@ -124,18 +203,78 @@ impl GuiFrontEnd {
                             selection,
                             clipboard
                         );
-                        if let Some(window) = fe.known_windows.borrow().keys().next() {
-                            window.set_clipboard(
-                                match selection {
-                                    ClipboardSelection::Clipboard => Clipboard::Clipboard,
-                                    ClipboardSelection::PrimarySelection => {
-                                        Clipboard::PrimarySelection
+                        let selection = match selection {
+                            ClipboardSelection::Clipboard => Clipboard::Clipboard,
+                            ClipboardSelection::PrimarySelection => Clipboard::PrimarySelection,
+                        };
+                        let mux = match Mux::get() {
+                            Some(mux) => mux,
+                            None => return true,
+                        };
+                        let policy = match mux.get_pane(pane_id) {
+                            Some(pane) => clipboard_policy_for_pane(&pane),
+                            None => ClipboardPolicy::Allow,
+                        };
+                        match policy {
+                            ClipboardPolicy::Allow => {
+                                if let Some(text) = clipboard.as_ref() {
+                                    crate::clipboardhistory::record_copy(text);
+                                }
+                                if let Some(window) = fe.known_windows.borrow().keys().next() {
+                                    window.set_clipboard(
+                                        selection,
+                                        clipboard.unwrap_or_else(String::new),
+                                    );
+                                } else {
+                                    log::error!("Cannot assign clipboard as there are no windows");
+                                }
+                            }
+                            ClipboardPolicy::Deny => {
+                                log::warn!(
+                                    "clipboard_policy denies pane {} setting the clipboard",
+                                    pane_id
+                                );
+                            }
+                            ClipboardPolicy::LocalBuffer => {
+                                if let Some(text) = clipboard.as_ref() {
+                                    crate::clipboardhistory::record_copy(text);
+                                }
+                            }
+                            ClipboardPolicy::Prompt => {
+                                match (fe.window_for_pane(&mux, pane_id), clipboard) {
+                                    (Some(window), Some(text)) => {
+                                        window.notify(TermWindowNotif::Apply(Box::new(
+                                            move |term_window| {
+                                                term_window.confirm_assign_clipboard(
+                                                    pane_id, selection, text,
+                                                );
+                                            },
+                                        )));
                                     }
-                                },
-                                clipboard.unwrap_or_else(String::new),
-                            );
-                        } else {
-                            log::error!("Cannot assign clipboard as there are no windows");
+                                    (None, _) => {
+                                        log::warn!(
+                                            "Cannot prompt to assign clipboard for pane {} \
+                                             as it is not in a window",
+                                            pane_id
+                                        );
+                                    }
+                                    (_, None) => {}
+                                }
+                            }
+                        }
+                    }
+                    MuxNotification::SshPrompt {
+                        pane_id,
+                        domain_name,
+                        prompt,
+                    } => {
+                        // If the pane isn't in one of our windows, the prompt
+                        // is dropped here and the domain will prompt in the pane
+                        let window = Mux::get().and_then(|mux| fe.window_for_pane(&mux, pane_id));
+                        if let Some(window) = window {
+                            window.notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                                term_window.show_ssh_prompt(pane_id, domain_name, prompt);
+                            })));
                         }
                     }
                 }
@@ -145,12 +284,26 @@ impl GuiFrontEnd {
             }
         });
         // Re-evaluate the config so that folks that are using
-        // `wezterm.gui.get_appearance()` can have that take effect
-        // before any windows are created
+        // `wezterm.gui.get_appearance()` or color_scheme_for_appearance
+        // can have that take effect before any windows are created
+        config::set_appearance_is_dark(front_end.connection.get_appearance().is_dark());
         config::reload();
+        crate::tray::update(&config::configuration());
+        crate::services::register();
         Ok(front_end)
     }
 
+    /// Returns the gui window that holds the mux window that contains
+    /// the specified pane
+    fn window_for_pane(&self, mux: &Mux, pane_id: PaneId) -> Option<Window> {
+        let (_domain, window_id, _tab_id) = mux.resolve_pane_id(pane_id)?;
+        self.known_windows
+            .borrow()
+            .iter()
+            .find(|(_, &mux_window_id)| mux_window_id == window_id)
+            .map(|(window, _)| window.clone())
+    }
+
     pub fn run_forever(&self) -> anyhow::Result<()> {
         self.connection
             .run_message_loop()
@@ -286,6 +439,15 @@ impl GuiFrontEnd {
         }
     }
 
+    /// Returns the gui windows and the mux windows that they display
+    pub fn known_windows(&self) -> Vec<(Window, MuxWindowId)> {
+        self.known_windows
+            .borrow()
+            .iter()
+            .map(|(window, &mux_window_id)| (window.clone(), mux_window_id))
+            .collect()
+    }
+
     pub fn is_switching_workspace(&self) -> bool {
         *self.switching_workspaces.borrow()
     }
@@ -332,6 +332,11 @@ impl GlyphCache<SrgbTexture2d> {
 }
 
 impl<T: Texture2d> GlyphCache<T> {
+    /// Returns the number of font glyphs that are held in the atlas
+    pub fn num_glyphs(&self) -> usize {
+        self.glyph_cache.len()
+    }
+
     /// Resolve a glyph from the cache, rendering the glyph on-demand if
     /// the cache doesn't already hold the desired glyph.
     pub fn cached_glyph(
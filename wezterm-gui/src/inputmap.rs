@@ -1,23 +1,41 @@
 use crate::commands::CommandDef;
 use config::keyassignment::{
-    ClipboardCopyDestination, ClipboardPasteSource, KeyAssignment, KeyTableEntry, KeyTables,
-    MouseEventTrigger, SelectionMode,
+    ClipboardCopyDestination, ClipboardPasteSource, KeyAssignment, KeyTable, KeyTableEntry,
+    KeyTables, MouseEventTrigger, MouseTable, SelectionMode,
 };
 use config::ConfigHandle;
+use serde::Serialize;
 use std::collections::{BTreeMap, HashMap};
 use std::time::Duration;
 use wezterm_term::input::MouseButton;
 use window::{KeyCode, Modifiers};
 
+/// A key assignment as listed by `wezterm show-keys --format` and
+/// the key binding cheatsheet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KeyBindingInfo {
+    /// The name of the key table, or None for the default table
+    pub table: Option<String>,
+    /// The key and its modifiers, such as `SHIFT+CTRL+c`
+    pub key: String,
+    /// The debug representation of the action
+    pub action: String,
+    /// The description from the config, falling back to the
+    /// brief description of the command that matches the action
+    pub description: Option<String>,
+}
+
 pub struct InputMap {
     pub keys: KeyTables,
     pub mouse: HashMap<(MouseEventTrigger, Modifiers), KeyAssignment>,
+    pub mouse_tables: HashMap<String, MouseTable>,
     leader: Option<(KeyCode, Modifiers, Duration)>,
 }
 
 impl InputMap {
     pub fn new(config: &ConfigHandle) -> Self {
         let mut mouse = config.mouse_bindings();
+        let mut mouse_tables = config.mouse_tables();
 
         let mut keys = config.key_bindings();
 
@@ -43,9 +61,10 @@ impl InputMap {
 
         if !config.disable_default_key_bindings {
             for (mods, code, action) in CommandDef::default_key_assignments(config) {
-                keys.default
-                    .entry((code, mods))
-                    .or_insert(KeyTableEntry { action });
+                keys.default.entry((code, mods)).or_insert(KeyTableEntry {
+                    action,
+                    description: None,
+                });
             }
         }
 
@@ -222,11 +241,15 @@ impl InputMap {
         keys.by_name
             .entry("search_mode".to_string())
             .or_insert_with(crate::overlay::copy::search_key_table);
+        mouse_tables
+            .entry("copy_mode".to_string())
+            .or_insert_with(crate::overlay::copy::copy_mouse_table);
 
         Self {
             keys,
             leader,
             mouse,
+            mouse_tables,
         }
     }
 
@@ -259,10 +282,51 @@ impl InputMap {
             .cloned()
     }
 
-    pub fn lookup_mouse(&self, event: MouseEventTrigger, mods: Modifiers) -> Option<KeyAssignment> {
-        self.mouse
-            .get(&(event, mods.remove_positional_mods()))
-            .cloned()
+    pub fn lookup_mouse(
+        &self,
+        event: MouseEventTrigger,
+        mods: Modifiers,
+        table_name: Option<&str>,
+    ) -> Option<KeyAssignment> {
+        let table = match table_name {
+            Some(name) => self.mouse_tables.get(name)?,
+            None => &self.mouse,
+        };
+        table.get(&(event, mods.remove_positional_mods())).cloned()
+    }
+
+    /// Returns the key assignments of the default key table, followed
+    /// by those of the named key tables in name order.  The entries
+    /// of each table are ordered by their key labels.
+    pub fn key_binding_infos(&self, config: &ConfigHandle) -> Vec<KeyBindingInfo> {
+        let briefs = CommandDef::action_briefs(config);
+        let table_infos = |name: Option<&String>, table: &KeyTable| {
+            let mut infos: Vec<KeyBindingInfo> = table
+                .iter()
+                .map(|((key, mods), entry)| {
+                    let action = format!("{:?}", entry.action);
+                    KeyBindingInfo {
+                        table: name.cloned(),
+                        key: key_label(key, *mods),
+                        description: entry
+                            .description
+                            .clone()
+                            .or_else(|| briefs.get(&action).cloned()),
+                        action,
+                    }
+                })
+                .collect();
+            infos.sort_by(|a, b| a.key.cmp(&b.key));
+            infos
+        };
+
+        let mut infos = table_infos(None, &self.keys.default);
+        let mut table_names = self.keys.by_name.keys().collect::<Vec<_>>();
+        table_names.sort();
+        for name in table_names {
+            infos.append(&mut table_infos(Some(name), &self.keys.by_name[name]));
+        }
+        infos
     }
 
     pub fn show_keys(&self) {
@@ -285,28 +349,38 @@ impl InputMap {
         }
 
         section_header("Mouse");
-        self.show_mouse();
+        show_mouse_table(&self.mouse);
+
+        let mut table_names = self.mouse_tables.keys().collect::<Vec<_>>();
+        table_names.sort();
+        for name in table_names {
+            if let Some(table) = self.mouse_tables.get(name) {
+                println!();
+                section_header(&format!("Mouse Table: {name}"));
+                show_mouse_table(table);
+            }
+        }
     }
+}
 
-    fn show_mouse(&self) {
-        let ordered = self.mouse.iter().collect::<BTreeMap<_, _>>();
+fn show_mouse_table(table: &MouseTable) {
+    let ordered = table.iter().collect::<BTreeMap<_, _>>();
 
-        let mut trigger_width = 0;
-        let mut mod_width = 0;
-        for (trigger, mods) in ordered.keys() {
-            mod_width = mod_width.max(format!("{mods:?}").len());
-            trigger_width = trigger_width.max(format!("{trigger:?}").len());
-        }
+    let mut trigger_width = 0;
+    let mut mod_width = 0;
+    for (trigger, mods) in ordered.keys() {
+        mod_width = mod_width.max(format!("{mods:?}").len());
+        trigger_width = trigger_width.max(format!("{trigger:?}").len());
+    }
 
-        for ((trigger, mods), action) in ordered {
-            let mods = if *mods == Modifiers::NONE {
-                String::new()
-            } else {
-                format!("{mods:?}")
-            };
-            let trigger = format!("{trigger:?}");
-            println!("\t{mods:mod_width$}   {trigger:trigger_width$}   ->   {action:?}");
-        }
+    for ((trigger, mods), action) in ordered {
+        let mods = if *mods == Modifiers::NONE {
+            String::new()
+        } else {
+            format!("{mods:?}")
+        };
+        let trigger = format!("{trigger:?}");
+        println!("\t{mods:mod_width$}   {trigger:trigger_width$}   ->   {action:?}");
     }
 }
 
@@ -317,7 +391,49 @@ fn section_header(title: &str) {
     println!();
 }
 
-fn human_key(key: &KeyCode) -> String {
+/// Renders the key assignments as markdown, with a section
+/// containing a table for each key table
+pub fn key_bindings_markdown(infos: &[KeyBindingInfo]) -> String {
+    fn cell(text: &str) -> String {
+        text.replace('|', "\\|")
+    }
+
+    let mut markdown = String::new();
+    let mut current_table = None;
+    for info in infos {
+        if current_table != Some(&info.table) {
+            if current_table.is_some() {
+                markdown.push('\n');
+            }
+            current_table = Some(&info.table);
+            match &info.table {
+                Some(name) => markdown.push_str(&format!("## Key Table: {name}\n\n")),
+                None => markdown.push_str("## Default key table\n\n"),
+            }
+            markdown.push_str("| Key | Action | Description |\n");
+            markdown.push_str("|-----|--------|-------------|\n");
+        }
+        markdown.push_str(&format!(
+            "| `{}` | `{}` | {} |\n",
+            cell(&info.key),
+            cell(&info.action),
+            cell(info.description.as_deref().unwrap_or(""))
+        ));
+    }
+    markdown
+}
+
+/// Returns the label for a key binding, such as `SHIFT+CTRL+c`
+pub fn key_label(key: &KeyCode, mods: Modifiers) -> String {
+    let key = human_key(key);
+    if mods == Modifiers::NONE {
+        key
+    } else {
+        format!("{}+{}", mods.to_string().replace('|', "+"), key)
+    }
+}
+
+pub fn human_key(key: &KeyCode) -> String {
     match key {
         KeyCode::Char('\x1b') => "Escape".to_string(),
         KeyCode::Char('\x7f') => "Escape".to_string(),
@@ -355,3 +471,38 @@ fn show_key_table(table: &config::keyassignment::KeyTable) {
         println!("\t{mods:mod_width$}   {key:key_width$}   ->   {action:?}");
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn markdown() {
+        let infos = vec![
+            KeyBindingInfo {
+                table: None,
+                key: "CTRL+|".to_string(),
+                action: "SendString(\"|\")".to_string(),
+                description: None,
+            },
+            KeyBindingInfo {
+                table: Some("resize_pane".to_string()),
+                key: "h".to_string(),
+                action: "AdjustPaneSize((Left, 1))".to_string(),
+                description: Some("Grow left".to_string()),
+            },
+        ];
+        assert_eq!(
+            key_bindings_markdown(&infos),
+            "## Default key table\n\n\
+             | Key | Action | Description |\n\
+             |-----|--------|-------------|\n\
+             | `CTRL+\\|` | `SendString(\"\\|\")` |  |\n\
+             \n\
+             ## Key Table: resize_pane\n\n\
+             | Key | Action | Description |\n\
+             |-----|--------|-------------|\n\
+             | `h` | `AdjustPaneSize((Left, 1))` | Grow left |\n"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Keyboard macros are sequences of input events that were recorded
+//! using `StartMacroRecording` and that can be sent to a pane again
+//! using `PlayMacro`.
+//! Macros are held in memory for the lifetime of the process and
+//! are shared by all windows.
+use config::keyassignment::MacroEvent;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+lazy_static::lazy_static! {
+    static ref MACROS: Mutex<HashMap<String, Vec<MacroEvent>>> = Mutex::new(HashMap::new());
+}
+
+/// A macro that is currently being recorded in a window
+#[derive(Debug)]
+pub struct MacroRecording {
+    pub name: String,
+    events: Vec<MacroEvent>,
+}
+
+impl MacroRecording {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            events: vec![],
+        }
+    }
+
+    pub fn push(&mut self, event: MacroEvent) {
+        // Merge text so that a word typed via an input method
+        // is replayed in a single write
+        if let MacroEvent::Text(text) = &event {
+            if let Some(MacroEvent::Text(prior)) = self.events.last_mut() {
+                prior.push_str(text);
+                return;
+            }
+        }
+        self.events.push(event);
+    }
+
+    /// Stores the recorded events as a macro, replacing any
+    /// prior macro with the same name
+    pub fn save(self) {
+        set_macro(&self.name, Some(self.events));
+    }
+}
+
+pub fn get_macro(name: &str) -> Option<Vec<MacroEvent>> {
+    MACROS.lock().unwrap().get(name).cloned()
+}
+
+/// Replaces the named macro, or removes it if `events` is None
+pub fn set_macro(name: &str, events: Option<Vec<MacroEvent>>) {
+    let mut macros = MACROS.lock().unwrap();
+    match events {
+        Some(events) => {
+            macros.insert(name.to_string(), events);
+        }
+        None => {
+            macros.remove(name);
+        }
+    }
+}
+
+/// Returns the names of the macros, sorted alphabetically
+pub fn macro_names() -> Vec<String> {
+    let mut names: Vec<String> = MACROS.lock().unwrap().keys().cloned().collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use config::{DeferredKeyCode, KeyNoAction};
+    use window::{KeyCode, Modifiers};
+
+    #[test]
+    fn record_merges_text() {
+        let key = MacroEvent::Key(KeyNoAction {
+            key: DeferredKeyCode::KeyCode(KeyCode::Char('a')),
+            mods: Modifiers::CTRL,
+        });
+        let mut recording = MacroRecording::new("test-merge");
+        recording.push(MacroEvent::Text("é".to_string()));
+        recording.push(MacroEvent::Text("t".to_string()));
+        recording.push(key.clone());
+        recording.push(MacroEvent::Text("é".to_string()));
+        recording.save();
+
+        assert_eq!(
+            get_macro("test-merge").unwrap(),
+            vec![
+                MacroEvent::Text("ét".to_string()),
+                key,
+                MacroEvent::Text("é".to_string())
+            ]
+        );
+        assert!(macro_names().contains(&"test-merge".to_string()));
+
+        set_macro("test-merge", None);
+        assert!(get_macro("test-merge").is_none());
+    }
+}
@@ -4,7 +4,7 @@
 use ::window::*;
 use anyhow::{anyhow, Context};
 use clap::{Parser, ValueHint};
-use config::{ConfigHandle, SshDomain, SshMultiplexing};
+use config::{ConfigHandle, ExistingGuiInstance, SshDomain, SshMultiplexing};
 use mux::activity::Activity;
 use mux::domain::{Domain, LocalDomain};
 use mux::ssh::RemoteSshDomain;
@@ -16,33 +16,38 @@ use std::ffi::OsString;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
-use termwiz::cell::{CellAttributes, UnicodeVersion};
-use termwiz::surface::{Line, SEQ_ZERO};
-use wezterm_bidi::Direction;
+use termwiz::cell::CellAttributes;
 use wezterm_client::domain::{ClientDomain, ClientDomainConfig};
-use wezterm_font::shaper::PresentationWidth;
 use wezterm_gui_subcommands::*;
 use wezterm_toast_notification::*;
 
+mod audio;
 mod cache;
+mod clipboardhistory;
 mod colorease;
 mod commands;
 mod customglyph;
 mod download;
+mod fontresolver;
+mod frecency;
 mod frontend;
 mod glyphcache;
 mod inputmap;
+mod macros;
 mod markdown;
 mod overlay;
 mod quad;
+mod registers;
 mod renderstate;
 mod scripting;
 mod scrollbar;
 mod selection;
+mod services;
 mod shapecache;
 mod stats;
 mod tabbar;
 mod termwindow;
+mod tray;
 mod update;
 mod utilsprites;
 
@@ -394,6 +399,54 @@ async fn connect_to_auto_connect_domains() -> anyhow::Result<()> {
             }
         }
     }
+
+    for group in &config::configuration().domain_groups {
+        if group.connect_automatically {
+            mux.attach_domain_group(&group.name).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Applies the `workspace_layouts` that are marked `apply_at_startup`,
+/// skipping any whose workspace already has windows, such as those
+/// from an automatically connected multiplexer domain
+async fn apply_startup_workspace_layouts() -> anyhow::Result<()> {
+    let mux = Mux::get().unwrap();
+    let config = config::configuration();
+    for layout in &config.workspace_layouts {
+        if !layout.apply_at_startup || !mux.is_workspace_empty(layout.workspace_name()) {
+            continue;
+        }
+        let workspace = mux.apply_workspace_layout(&layout.name).await?;
+        if mux.is_active_workspace_empty() {
+            mux.set_active_workspace(&workspace);
+        }
+    }
+    Ok(())
+}
+
+/// Offers to restore the session that was recorded in the crash
+/// recovery journal by a previous instance that didn't shut down cleanly
+async fn offer_session_recovery() -> anyhow::Result<()> {
+    let journal = match mux::journal::SessionJournal::load_abandoned()? {
+        Some(journal) if !journal.workspaces.is_empty() => journal,
+        _ => return Ok(()),
+    };
+    // The prompt blocks until it is answered, so it must not run on
+    // the main thread
+    let restore = smol::unblock(|| {
+        let ui = mux::connui::ConnectionUI::new_with_no_close_delay();
+        ui.title("wezterm: restore session");
+        let answer =
+            ui.input("wezterm did not shut down cleanly.\nRestore the previous session? [Y/n] ");
+        ui.close();
+        answer.map(|answer| !answer.trim().to_lowercase().starts_with('n'))
+    })
+    .await?;
+    if restore {
+        Mux::get().unwrap().restore_session(&journal).await?;
+    }
     Ok(())
 }
 
@@ -430,6 +483,25 @@ async fn async_run_terminal_gui(
         persistent_toast_notification("Error", &message);
     }
 
+    if let Err(err) = apply_startup_workspace_layouts().await {
+        let message = format!("while applying workspace layouts: {:#}", err);
+        log::error!("{}", message);
+        persistent_toast_notification("Error", &message);
+    }
+
+    // Only the primary gui process keeps a journal, as the
+    // instances would otherwise overwrite each other's journal
+    if should_publish {
+        if config::configuration().enable_crash_recovery {
+            if let Err(err) = offer_session_recovery().await {
+                let message = format!("while restoring the previous session: {:#}", err);
+                log::error!("{}", message);
+                persistent_toast_notification("Error", &message);
+            }
+        }
+        mux::journal::start_journal_task();
+    }
+
     let is_connecting = false;
     spawn_tab_in_default_domain_if_mux_is_empty(cmd, is_connecting).await
 }
@@ -448,7 +520,7 @@ impl Publish {
             return Self::NoConnectNoPublish;
         }
 
-        if always_new_process {
+        if always_new_process || config.existing_gui_instance == ExistingGuiInstance::NewProcess {
             return Self::NoConnectNoPublish;
         }
 
@@ -467,6 +539,20 @@ impl Publish {
         }
     }
 
+    /// Returns the socket of the running instance, if it is live
+    /// and the configuration asks us to attach to it
+    pub fn attach_path(&self, config: &ConfigHandle) -> Option<PathBuf> {
+        match self {
+            Self::TryPathOrPublish(gui_sock)
+                if config.existing_gui_instance == ExistingGuiInstance::Attach
+                    && !wezterm_client::discovery::is_sock_dead(gui_sock) =>
+            {
+                Some(gui_sock.clone())
+            }
+            _ => None,
+        }
+    }
+
     pub fn should_publish(&self) -> bool {
         match self {
             Self::TryPathOrPublish(_) | Self::NoConnectButPublish => true,
@@ -664,6 +750,9 @@ fn run_terminal_gui(opts: StartCommand) -> anyhow::Result<()> {
         opts.always_new_process || opts.position.is_some(),
     );
     log::trace!("{:?}", publish);
+    if let Some(gui_sock) = publish.attach_path(&config) {
+        return run_attached_to_gui(gui_sock, cmd);
+    }
     if publish.try_spawn(cmd.clone(), &config, opts.workspace.as_deref())? {
         return Ok(());
     }
@@ -679,10 +768,79 @@ fn run_terminal_gui(opts: StartCommand) -> anyhow::Result<()> {
     })
     .detach();
 
+    maybe_show_configuration_error_window();
+    let result = gui.run_forever();
+    // Leaving the journal behind is what identifies an abnormal exit
+    mux::journal::discard_journal();
+    result
+}
+
+/// Runs a GUI whose default domain is the mux of the running
+/// instance that is listening on `gui_sock`.  The windows of that
+/// instance are shown in this process too.
+fn run_attached_to_gui(gui_sock: PathBuf, cmd: Option<CommandBuilder>) -> anyhow::Result<()> {
+    log::info!(
+        "Attaching to the existing GUI instance via {}. \
+         Use --always-new-process if you do not want this behavior.",
+        gui_sock.display()
+    );
+    let gui = crate::frontend::try_new()?;
+    let activity = Activity::new();
+
+    promise::spawn::spawn(async move {
+        if let Err(err) = async_run_attached_to_gui(gui_sock, cmd).await {
+            terminate_with_error(err);
+        }
+        drop(activity);
+    })
+    .detach();
+
     maybe_show_configuration_error_window();
     gui.run_forever()
 }
 
+async fn async_run_attached_to_gui(
+    gui_sock: PathBuf,
+    cmd: Option<CommandBuilder>,
+) -> anyhow::Result<()> {
+    let mux = Mux::get().unwrap();
+    let domain: Arc<dyn Domain> = Arc::new(ClientDomain::new(ClientDomainConfig::Unix(
+        config::UnixDomain {
+            name: "gui-instance".to_string(),
+            socket_path: Some(gui_sock),
+            no_serve_automatically: true,
+            ..Default::default()
+        },
+    )));
+    mux.add_domain(&domain);
+    mux.set_default_domain(&domain);
+
+    let window_id = {
+        // Force the builder to notify the frontend early,
+        // so that the attach await below doesn't block it
+        let builder = mux.new_empty_window(None);
+        *builder
+    };
+    domain.attach(Some(window_id)).await?;
+
+    // Attaching shows the existing windows; only spawn when we were
+    // asked to run something, or when there was nothing to show
+    let have_panes_in_domain = mux
+        .iter_panes()
+        .iter()
+        .any(|p| p.domain_id() == domain.domain_id());
+    if have_panes_in_domain && cmd.is_none() {
+        return Ok(());
+    }
+
+    let config = config::configuration();
+    let dpi = config.dpi.unwrap_or_else(|| ::window::default_dpi()) as u32;
+    domain
+        .spawn(config.initial_size(dpi), cmd, None, window_id)
+        .await?;
+    Ok(())
+}
+
 fn fatal_toast_notification(title: &str, message: &str) {
     persistent_toast_notification(title, message);
     // We need a short delay otherwise the notification
@@ -729,9 +887,19 @@ fn maybe_show_configuration_error_window() {
     }
 }
 
-fn run_show_keys(config: config::ConfigHandle, _cmd: &ShowKeysCommand) -> anyhow::Result<()> {
+fn run_show_keys(config: config::ConfigHandle, cmd: &ShowKeysCommand) -> anyhow::Result<()> {
     let map = crate::inputmap::InputMap::new(&config);
-    map.show_keys();
+    match cmd.format {
+        ShowKeysFormat::Text => map.show_keys(),
+        ShowKeysFormat::Markdown => {
+            let infos = map.key_binding_infos(&config);
+            print!("{}", crate::inputmap::key_bindings_markdown(&infos));
+        }
+        ShowKeysFormat::Json => {
+            let infos = map.key_binding_infos(&config);
+            println!("{}", serde_json::to_string_pretty(&infos)?);
+        }
+    }
     Ok(())
 }
 
@@ -752,110 +920,56 @@ pub fn run_ls_fonts(config: config::ConfigHandle, cmd: &LsFontsCommand) -> anyho
         config.dpi.unwrap_or_else(|| ::window::default_dpi()) as usize,
     )?;
 
-    let bidi_hint = if config.bidi_enabled {
-        Some(config.bidi_direction)
-    } else {
-        None
-    };
-
-    let unicode_version = UnicodeVersion {
-        version: config.unicode_version,
-        ambiguous_are_wide: config.treat_east_asian_ambiguous_width_as_wide,
-    };
-
     if let Some(text) = &cmd.text {
-        let line = Line::from_text(
-            text,
-            &CellAttributes::default(),
-            SEQ_ZERO,
-            Some(unicode_version),
-        );
-        let cell_clusters = line.cluster(bidi_hint);
-        let ft_lib = wezterm_font::ftwrap::Library::new()?;
-
-        for cluster in cell_clusters {
-            let style = font_config.match_style(&config, &cluster.attrs);
-            let font = font_config.resolve_font(style)?;
-            let presentation_width = PresentationWidth::with_cluster(&cluster);
-            let infos = font
-                .blocking_shape(
-                    &cluster.text,
-                    Some(cluster.presentation),
-                    cluster.direction,
-                    None,
-                    Some(&presentation_width),
-                )
-                .unwrap();
-
-            // We must grab the handles after shaping, so that we get the
-            // revised list that includes system fallbacks!
-            let handles = font.clone_handles();
-            let faces: Vec<_> = handles
-                .iter()
-                .map(|p| ft_lib.face_from_locator(&p.handle).ok())
-                .collect();
-
-            let mut iter = infos.iter().peekable();
-
-            let mut byte_lens = vec![];
-            for c in cluster.text.chars() {
-                let len = c.len_utf8();
-                for _ in 0..len {
-                    byte_lens.push(len);
-                }
-            }
-
+        let clusters =
+            fontresolver::resolve_text(&config, &font_config, text, &CellAttributes::default())?;
+        for cluster in clusters {
             println!("{:?}", cluster.direction);
 
-            while let Some(info) = iter.next() {
-                let idx = cluster.byte_to_cell_idx(info.cluster as usize);
-
-                let text = if cluster.direction == Direction::LeftToRight {
-                    if let Some(next) = iter.peek() {
-                        line.columns_as_str(idx..cluster.byte_to_cell_idx(next.cluster as usize))
-                    } else {
-                        let last_idx = cluster.byte_to_cell_idx(cluster.text.len() - 1);
-                        line.columns_as_str(idx..last_idx + 1)
-                    }
-                } else {
-                    let info_len = byte_lens[info.cluster as usize];
-                    let last_idx = cluster.byte_to_cell_idx(info.cluster as usize + info_len - 1);
-                    line.columns_as_str(idx..last_idx + 1)
-                };
-
-                let parsed = &handles[info.font_idx];
-                let escaped = format!("{}", text.escape_unicode());
-                if config.custom_block_glyphs {
-                    if let Some(block) = customglyph::BlockKey::from_str(&text) {
-                        println!(
-                            "{:2} {:4} {:12} drawn by wezterm because custom_block_glyphs=true: {:?}",
-                            info.cluster, text, escaped, block
-                        );
-                        continue;
-                    }
+            for glyph in cluster.glyphs {
+                let escaped = format!("{}", glyph.text.escape_unicode());
+                if let Some(block) = glyph.custom_block {
+                    println!(
+                        "{:2} {:4} {:12} drawn by wezterm because custom_block_glyphs=true: {:?}",
+                        glyph.cluster, glyph.text, escaped, block
+                    );
+                    continue;
                 }
 
-                let glyph_name = faces[info.font_idx]
-                    .as_ref()
-                    .and_then(|face| {
-                        face.get_glyph_name(info.glyph_pos)
-                            .map(|name| format!("{},", name))
-                    })
+                let glyph_name = glyph
+                    .glyph_name
+                    .map(|name| format!("{},", name))
                     .unwrap_or_else(String::new);
 
                 println!(
                     "{:2} {:4} {:12} x_adv={:<2} cells={:<2} glyph={}{:<4} {}\n{:38}{}",
-                    info.cluster,
-                    text,
+                    glyph.cluster,
+                    glyph.text,
                     escaped,
-                    info.x_advance.get(),
-                    info.num_cells,
+                    glyph.x_advance,
+                    glyph.num_cells,
                     glyph_name,
-                    info.glyph_pos,
-                    parsed.lua_name(),
+                    glyph.glyph_pos,
+                    glyph.font.lua_name(),
                     "",
-                    parsed.handle.diagnostic_string()
+                    glyph.font.handle.diagnostic_string()
                 );
+                if glyph.font.synthesize_bold || glyph.font.synthesize_italic {
+                    println!(
+                        "{:38}synthesized:{}{}",
+                        "",
+                        if glyph.font.synthesize_bold {
+                            " bold"
+                        } else {
+                            ""
+                        },
+                        if glyph.font.synthesize_italic {
+                            " italic"
+                        } else {
+                            ""
+                        },
+                    );
+                }
             }
         }
         return Ok(());
@@ -0,0 +1,2196 @@
+// DO NOT EDIT THIS FILE. IT WAS AUTOMATICALLY GENERATED BY:
+//
+//   ci/generate-charselect-data.py
+//
+// Unicode version: 14.0.0
+
+use super::CharEntry;
+
+pub const EMOJI: &[CharEntry] = &[
+    CharEntry { text: "\u{a9}\u{fe0f}", name: "copyright sign", skin_tones: false },
+    CharEntry { text: "\u{ae}\u{fe0f}", name: "registered sign", skin_tones: false },
+    CharEntry { text: "\u{203c}\u{fe0f}", name: "double exclamation mark", skin_tones: false },
+    CharEntry { text: "\u{2049}\u{fe0f}", name: "exclamation question mark", skin_tones: false },
+    CharEntry { text: "\u{2122}\u{fe0f}", name: "trade mark sign", skin_tones: false },
+    CharEntry { text: "\u{2139}\u{fe0f}", name: "information source", skin_tones: false },
+    CharEntry { text: "\u{2194}\u{fe0f}", name: "left right arrow", skin_tones: false },
+    CharEntry { text: "\u{2195}\u{fe0f}", name: "up down arrow", skin_tones: false },
+    CharEntry { text: "\u{2196}\u{fe0f}", name: "north west arrow", skin_tones: false },
+    CharEntry { text: "\u{2197}\u{fe0f}", name: "north east arrow", skin_tones: false },
+    CharEntry { text: "\u{2198}\u{fe0f}", name: "south east arrow", skin_tones: false },
+    CharEntry { text: "\u{2199}\u{fe0f}", name: "south west arrow", skin_tones: false },
+    CharEntry { text: "\u{21a9}\u{fe0f}", name: "leftwards arrow with hook", skin_tones: false },
+    CharEntry { text: "\u{21aa}\u{fe0f}", name: "rightwards arrow with hook", skin_tones: false },
+    CharEntry { text: "\u{231a}", name: "watch", skin_tones: false },
+    CharEntry { text: "\u{231b}", name: "hourglass", skin_tones: false },
+    CharEntry { text: "\u{2328}\u{fe0f}", name: "keyboard", skin_tones: false },
+    CharEntry { text: "\u{23cf}\u{fe0f}", name: "eject symbol", skin_tones: false },
+    CharEntry { text: "\u{23e9}", name: "black right-pointing double triangle", skin_tones: false },
+    CharEntry { text: "\u{23ea}", name: "black left-pointing double triangle", skin_tones: false },
+    CharEntry { text: "\u{23eb}", name: "black up-pointing double triangle", skin_tones: false },
+    CharEntry { text: "\u{23ec}", name: "black down-pointing double triangle", skin_tones: false },
+    CharEntry { text: "\u{23ed}\u{fe0f}", name: "black right-pointing double triangle with vertical bar", skin_tones: false },
+    CharEntry { text: "\u{23ee}\u{fe0f}", name: "black left-pointing double triangle with vertical bar", skin_tones: false },
+    CharEntry { text: "\u{23ef}\u{fe0f}", name: "black right-pointing triangle with double vertical bar", skin_tones: false },
+    CharEntry { text: "\u{23f0}", name: "alarm clock", skin_tones: false },
+    CharEntry { text: "\u{23f1}\u{fe0f}", name: "stopwatch", skin_tones: false },
+    CharEntry { text: "\u{23f2}\u{fe0f}", name: "timer clock", skin_tones: false },
+    CharEntry { text: "\u{23f3}", name: "hourglass with flowing sand", skin_tones: false },
+    CharEntry { text: "\u{23f8}\u{fe0f}", name: "double vertical bar", skin_tones: false },
+    CharEntry { text: "\u{23f9}\u{fe0f}", name: "black square for stop", skin_tones: false },
+    CharEntry { text: "\u{23fa}\u{fe0f}", name: "black circle for record", skin_tones: false },
+    CharEntry { text: "\u{24c2}\u{fe0f}", name: "circled latin capital letter m", skin_tones: false },
+    CharEntry { text: "\u{25aa}\u{fe0f}", name: "black small square", skin_tones: false },
+    CharEntry { text: "\u{25ab}\u{fe0f}", name: "white small square", skin_tones: false },
+    CharEntry { text: "\u{25b6}\u{fe0f}", name: "black right-pointing triangle", skin_tones: false },
+    CharEntry { text: "\u{25c0}\u{fe0f}", name: "black left-pointing triangle", skin_tones: false },
+    CharEntry { text: "\u{25fb}\u{fe0f}", name: "white medium square", skin_tones: false },
+    CharEntry { text: "\u{25fc}\u{fe0f}", name: "black medium square", skin_tones: false },
+    CharEntry { text: "\u{25fd}", name: "white medium small square", skin_tones: false },
+    CharEntry { text: "\u{25fe}", name: "black medium small square", skin_tones: false },
+    CharEntry { text: "\u{2600}\u{fe0f}", name: "black sun with rays", skin_tones: false },
+    CharEntry { text: "\u{2601}\u{fe0f}", name: "cloud", skin_tones: false },
+    CharEntry { text: "\u{2602}\u{fe0f}", name: "umbrella", skin_tones: false },
+    CharEntry { text: "\u{2603}\u{fe0f}", name: "snowman", skin_tones: false },
+    CharEntry { text: "\u{2604}\u{fe0f}", name: "comet", skin_tones: false },
+    CharEntry { text: "\u{260e}\u{fe0f}", name: "black telephone", skin_tones: false },
+    CharEntry { text: "\u{2611}\u{fe0f}", name: "ballot box with check", skin_tones: false },
+    CharEntry { text: "\u{2614}", name: "umbrella with rain drops", skin_tones: false },
+    CharEntry { text: "\u{2615}", name: "hot beverage", skin_tones: false },
+    CharEntry { text: "\u{2618}\u{fe0f}", name: "shamrock", skin_tones: false },
+    CharEntry { text: "\u{261d}\u{fe0f}", name: "white up pointing index", skin_tones: true },
+    CharEntry { text: "\u{2620}\u{fe0f}", name: "skull and crossbones", skin_tones: false },
+    CharEntry { text: "\u{2622}\u{fe0f}", name: "radioactive sign", skin_tones: false },
+    CharEntry { text: "\u{2623}\u{fe0f}", name: "biohazard sign", skin_tones: false },
+    CharEntry { text: "\u{2626}\u{fe0f}", name: "orthodox cross", skin_tones: false },
+    CharEntry { text: "\u{262a}\u{fe0f}", name: "star and crescent", skin_tones: false },
+    CharEntry { text: "\u{262e}\u{fe0f}", name: "peace symbol", skin_tones: false },
+    CharEntry { text: "\u{262f}\u{fe0f}", name: "yin yang", skin_tones: false },
+    CharEntry { text: "\u{2638}\u{fe0f}", name: "wheel of dharma", skin_tones: false },
+    CharEntry { text: "\u{2639}\u{fe0f}", name: "white frowning face", skin_tones: false },
+    CharEntry { text: "\u{263a}\u{fe0f}", name: "white smiling face", skin_tones: false },
+    CharEntry { text: "\u{2640}\u{fe0f}", name: "female sign", skin_tones: false },
+    CharEntry { text: "\u{2642}\u{fe0f}", name: "male sign", skin_tones: false },
+    CharEntry { text: "\u{2648}", name: "aries", skin_tones: false },
+    CharEntry { text: "\u{2649}", name: "taurus", skin_tones: false },
+    CharEntry { text: "\u{264a}", name: "gemini", skin_tones: false },
+    CharEntry { text: "\u{264b}", name: "cancer", skin_tones: false },
+    CharEntry { text: "\u{264c}", name: "leo", skin_tones: false },
+    CharEntry { text: "\u{264d}", name: "virgo", skin_tones: false },
+    CharEntry { text: "\u{264e}", name: "libra", skin_tones: false },
+    CharEntry { text: "\u{264f}", name: "scorpius", skin_tones: false },
+    CharEntry { text: "\u{2650}", name: "sagittarius", skin_tones: false },
+    CharEntry { text: "\u{2651}", name: "capricorn", skin_tones: false },
+    CharEntry { text: "\u{2652}", name: "aquarius", skin_tones: false },
+    CharEntry { text: "\u{2653}", name: "pisces", skin_tones: false },
+    CharEntry { text: "\u{265f}\u{fe0f}", name: "black chess pawn", skin_tones: false },
+    CharEntry { text: "\u{2660}\u{fe0f}", name: "black spade suit", skin_tones: false },
+    CharEntry { text: "\u{2663}\u{fe0f}", name: "black club suit", skin_tones: false },
+    CharEntry { text: "\u{2665}\u{fe0f}", name: "black heart suit", skin_tones: false },
+    CharEntry { text: "\u{2666}\u{fe0f}", name: "black diamond suit", skin_tones: false },
+    CharEntry { text: "\u{2668}\u{fe0f}", name: "hot springs", skin_tones: false },
+    CharEntry { text: "\u{267b}\u{fe0f}", name: "black universal recycling symbol", skin_tones: false },
+    CharEntry { text: "\u{267e}\u{fe0f}", name: "permanent paper sign", skin_tones: false },
+    CharEntry { text: "\u{267f}", name: "wheelchair symbol", skin_tones: false },
+    CharEntry { text: "\u{2692}\u{fe0f}", name: "hammer and pick", skin_tones: false },
+    CharEntry { text: "\u{2693}", name: "anchor", skin_tones: false },
+    CharEntry { text: "\u{2694}\u{fe0f}", name: "crossed swords", skin_tones: false },
+    CharEntry { text: "\u{2695}\u{fe0f}", name: "staff of aesculapius", skin_tones: false },
+    CharEntry { text: "\u{2696}\u{fe0f}", name: "scales", skin_tones: false },
+    CharEntry { text: "\u{2697}\u{fe0f}", name: "alembic", skin_tones: false },
+    CharEntry { text: "\u{2699}\u{fe0f}", name: "gear", skin_tones: false },
+    CharEntry { text: "\u{269b}\u{fe0f}", name: "atom symbol", skin_tones: false },
+    CharEntry { text: "\u{269c}\u{fe0f}", name: "fleur-de-lis", skin_tones: false },
+    CharEntry { text: "\u{26a0}\u{fe0f}", name: "warning sign", skin_tones: false },
+    CharEntry { text: "\u{26a1}", name: "high voltage sign", skin_tones: false },
+    CharEntry { text: "\u{26a7}\u{fe0f}", name: "male with stroke and male and female sign", skin_tones: false },
+    CharEntry { text: "\u{26aa}", name: "medium white circle", skin_tones: false },
+    CharEntry { text: "\u{26ab}", name: "medium black circle", skin_tones: false },
+    CharEntry { text: "\u{26b0}\u{fe0f}", name: "coffin", skin_tones: false },
+    CharEntry { text: "\u{26b1}\u{fe0f}", name: "funeral urn", skin_tones: false },
+    CharEntry { text: "\u{26bd}", name: "soccer ball", skin_tones: false },
+    CharEntry { text: "\u{26be}", name: "baseball", skin_tones: false },
+    CharEntry { text: "\u{26c4}", name: "snowman without snow", skin_tones: false },
+    CharEntry { text: "\u{26c5}", name: "sun behind cloud", skin_tones: false },
+    CharEntry { text: "\u{26c8}\u{fe0f}", name: "thunder cloud and rain", skin_tones: false },
+    CharEntry { text: "\u{26ce}", name: "ophiuchus", skin_tones: false },
+    CharEntry { text: "\u{26cf}\u{fe0f}", name: "pick", skin_tones: false },
+    CharEntry { text: "\u{26d1}\u{fe0f}", name: "helmet with white cross", skin_tones: false },
+    CharEntry { text: "\u{26d3}\u{fe0f}", name: "chains", skin_tones: false },
+    CharEntry { text: "\u{26d4}", name: "no entry", skin_tones: false },
+    CharEntry { text: "\u{26e9}\u{fe0f}", name: "shinto shrine", skin_tones: false },
+    CharEntry { text: "\u{26ea}", name: "church", skin_tones: false },
+    CharEntry { text: "\u{26f0}\u{fe0f}", name: "mountain", skin_tones: false },
+    CharEntry { text: "\u{26f1}\u{fe0f}", name: "umbrella on ground", skin_tones: false },
+    CharEntry { text: "\u{26f2}", name: "fountain", skin_tones: false },
+    CharEntry { text: "\u{26f3}", name: "flag in hole", skin_tones: false },
+    CharEntry { text: "\u{26f4}\u{fe0f}", name: "ferry", skin_tones: false },
+    CharEntry { text: "\u{26f5}", name: "sailboat", skin_tones: false },
+    CharEntry { text: "\u{26f7}\u{fe0f}", name: "skier", skin_tones: false },
+    CharEntry { text: "\u{26f8}\u{fe0f}", name: "ice skate", skin_tones: false },
+    CharEntry { text: "\u{26f9}\u{fe0f}", name: "person with ball", skin_tones: true },
+    CharEntry { text: "\u{26fa}", name: "tent", skin_tones: false },
+    CharEntry { text: "\u{26fd}", name: "fuel pump", skin_tones: false },
+    CharEntry { text: "\u{2702}\u{fe0f}", name: "black scissors", skin_tones: false },
+    CharEntry { text: "\u{2705}", name: "white heavy check mark", skin_tones: false },
+    CharEntry { text: "\u{2708}\u{fe0f}", name: "airplane", skin_tones: false },
+    CharEntry { text: "\u{2709}\u{fe0f}", name: "envelope", skin_tones: false },
+    CharEntry { text: "\u{270a}", name: "raised fist", skin_tones: true },
+    CharEntry { text: "\u{270b}", name: "raised hand", skin_tones: true },
+    CharEntry { text: "\u{270c}\u{fe0f}", name: "victory hand", skin_tones: true },
+    CharEntry { text: "\u{270d}\u{fe0f}", name: "writing hand", skin_tones: true },
+    CharEntry { text: "\u{270f}\u{fe0f}", name: "pencil", skin_tones: false },
+    CharEntry { text: "\u{2712}\u{fe0f}", name: "black nib", skin_tones: false },
+    CharEntry { text: "\u{2714}\u{fe0f}", name: "heavy check mark", skin_tones: false },
+    CharEntry { text: "\u{2716}\u{fe0f}", name: "heavy multiplication x", skin_tones: false },
+    CharEntry { text: "\u{271d}\u{fe0f}", name: "latin cross", skin_tones: false },
+    CharEntry { text: "\u{2721}\u{fe0f}", name: "star of david", skin_tones: false },
+    CharEntry { text: "\u{2728}", name: "sparkles", skin_tones: false },
+    CharEntry { text: "\u{2733}\u{fe0f}", name: "eight spoked asterisk", skin_tones: false },
+    CharEntry { text: "\u{2734}\u{fe0f}", name: "eight pointed black star", skin_tones: false },
+    CharEntry { text: "\u{2744}\u{fe0f}", name: "snowflake", skin_tones: false },
+    CharEntry { text: "\u{2747}\u{fe0f}", name: "sparkle", skin_tones: false },
+    CharEntry { text: "\u{274c}", name: "cross mark", skin_tones: false },
+    CharEntry { text: "\u{274e}", name: "negative squared cross mark", skin_tones: false },
+    CharEntry { text: "\u{2753}", name: "black question mark ornament", skin_tones: false },
+    CharEntry { text: "\u{2754}", name: "white question mark ornament", skin_tones: false },
+    CharEntry { text: "\u{2755}", name: "white exclamation mark ornament", skin_tones: false },
+    CharEntry { text: "\u{2757}", name: "heavy exclamation mark symbol", skin_tones: false },
+    CharEntry { text: "\u{2763}\u{fe0f}", name: "heavy heart exclamation mark ornament", skin_tones: false },
+    CharEntry { text: "\u{2764}\u{fe0f}", name: "heavy black heart", skin_tones: false },
+    CharEntry { text: "\u{2795}", name: "heavy plus sign", skin_tones: false },
+    CharEntry { text: "\u{2796}", name: "heavy minus sign", skin_tones: false },
+    CharEntry { text: "\u{2797}", name: "heavy division sign", skin_tones: false },
+    CharEntry { text: "\u{27a1}\u{fe0f}", name: "black rightwards arrow", skin_tones: false },
+    CharEntry { text: "\u{27b0}", name: "curly loop", skin_tones: false },
+    CharEntry { text: "\u{27bf}", name: "double curly loop", skin_tones: false },
+    CharEntry { text: "\u{2934}\u{fe0f}", name: "arrow pointing rightwards then curving upwards", skin_tones: false },
+    CharEntry { text: "\u{2935}\u{fe0f}", name: "arrow pointing rightwards then curving downwards", skin_tones: false },
+    CharEntry { text: "\u{2b05}\u{fe0f}", name: "leftwards black arrow", skin_tones: false },
+    CharEntry { text: "\u{2b06}\u{fe0f}", name: "upwards black arrow", skin_tones: false },
+    CharEntry { text: "\u{2b07}\u{fe0f}", name: "downwards black arrow", skin_tones: false },
+    CharEntry { text: "\u{2b1b}", name: "black large square", skin_tones: false },
+    CharEntry { text: "\u{2b1c}", name: "white large square", skin_tones: false },
+    CharEntry { text: "\u{2b50}", name: "white medium star", skin_tones: false },
+    CharEntry { text: "\u{2b55}", name: "heavy large circle", skin_tones: false },
+    CharEntry { text: "\u{3030}\u{fe0f}", name: "wavy dash", skin_tones: false },
+    CharEntry { text: "\u{303d}\u{fe0f}", name: "part alternation mark", skin_tones: false },
+    CharEntry { text: "\u{3297}\u{fe0f}", name: "circled ideograph congratulation", skin_tones: false },
+    CharEntry { text: "\u{3299}\u{fe0f}", name: "circled ideograph secret", skin_tones: false },
+    CharEntry { text: "\u{1f004}", name: "mahjong tile red dragon", skin_tones: false },
+    CharEntry { text: "\u{1f0cf}", name: "playing card black joker", skin_tones: false },
+    CharEntry { text: "\u{1f170}\u{fe0f}", name: "negative squared latin capital letter a", skin_tones: false },
+    CharEntry { text: "\u{1f171}\u{fe0f}", name: "negative squared latin capital letter b", skin_tones: false },
+    CharEntry { text: "\u{1f17e}\u{fe0f}", name: "negative squared latin capital letter o", skin_tones: false },
+    CharEntry { text: "\u{1f17f}\u{fe0f}", name: "negative squared latin capital letter p", skin_tones: false },
+    CharEntry { text: "\u{1f18e}", name: "negative squared ab", skin_tones: false },
+    CharEntry { text: "\u{1f191}", name: "squared cl", skin_tones: false },
+    CharEntry { text: "\u{1f192}", name: "squared cool", skin_tones: false },
+    CharEntry { text: "\u{1f193}", name: "squared free", skin_tones: false },
+    CharEntry { text: "\u{1f194}", name: "squared id", skin_tones: false },
+    CharEntry { text: "\u{1f195}", name: "squared new", skin_tones: false },
+    CharEntry { text: "\u{1f196}", name: "squared ng", skin_tones: false },
+    CharEntry { text: "\u{1f197}", name: "squared ok", skin_tones: false },
+    CharEntry { text: "\u{1f198}", name: "squared sos", skin_tones: false },
+    CharEntry { text: "\u{1f199}", name: "squared up with exclamation mark", skin_tones: false },
+    CharEntry { text: "\u{1f19a}", name: "squared vs", skin_tones: false },
+    CharEntry { text: "\u{1f201}", name: "squared katakana koko", skin_tones: false },
+    CharEntry { text: "\u{1f202}\u{fe0f}", name: "squared katakana sa", skin_tones: false },
+    CharEntry { text: "\u{1f21a}", name: "squared cjk unified ideograph-7121", skin_tones: false },
+    CharEntry { text: "\u{1f22f}", name: "squared cjk unified ideograph-6307", skin_tones: false },
+    CharEntry { text: "\u{1f232}", name: "squared cjk unified ideograph-7981", skin_tones: false },
+    CharEntry { text: "\u{1f233}", name: "squared cjk unified ideograph-7a7a", skin_tones: false },
+    CharEntry { text: "\u{1f234}", name: "squared cjk unified ideograph-5408", skin_tones: false },
+    CharEntry { text: "\u{1f235}", name: "squared cjk unified ideograph-6e80", skin_tones: false },
+    CharEntry { text: "\u{1f236}", name: "squared cjk unified ideograph-6709", skin_tones: false },
+    CharEntry { text: "\u{1f237}\u{fe0f}", name: "squared cjk unified ideograph-6708", skin_tones: false },
+    CharEntry { text: "\u{1f238}", name: "squared cjk unified ideograph-7533", skin_tones: false },
+    CharEntry { text: "\u{1f239}", name: "squared cjk unified ideograph-5272", skin_tones: false },
+    CharEntry { text: "\u{1f23a}", name: "squared cjk unified ideograph-55b6", skin_tones: false },
+    CharEntry { text: "\u{1f250}", name: "circled ideograph advantage", skin_tones: false },
+    CharEntry { text: "\u{1f251}", name: "circled ideograph accept", skin_tones: false },
+    CharEntry { text: "\u{1f300}", name: "cyclone", skin_tones: false },
+    CharEntry { text: "\u{1f301}", name: "foggy", skin_tones: false },
+    CharEntry { text: "\u{1f302}", name: "closed umbrella", skin_tones: false },
+    CharEntry { text: "\u{1f303}", name: "night with stars", skin_tones: false },
+    CharEntry { text: "\u{1f304}", name: "sunrise over mountains", skin_tones: false },
+    CharEntry { text: "\u{1f305}", name: "sunrise", skin_tones: false },
+    CharEntry { text: "\u{1f306}", name: "cityscape at dusk", skin_tones: false },
+    CharEntry { text: "\u{1f307}", name: "sunset over buildings", skin_tones: false },
+    CharEntry { text: "\u{1f308}", name: "rainbow", skin_tones: false },
+    CharEntry { text: "\u{1f309}", name: "bridge at night", skin_tones: false },
+    CharEntry { text: "\u{1f30a}", name: "water wave", skin_tones: false },
+    CharEntry { text: "\u{1f30b}", name: "volcano", skin_tones: false },
+    CharEntry { text: "\u{1f30c}", name: "milky way", skin_tones: false },
+    CharEntry { text: "\u{1f30d}", name: "earth globe europe-africa", skin_tones: false },
+    CharEntry { text: "\u{1f30e}", name: "earth globe americas", skin_tones: false },
+    CharEntry { text: "\u{1f30f}", name: "earth globe asia-australia", skin_tones: false },
+    CharEntry { text: "\u{1f310}", name: "globe with meridians", skin_tones: false },
+    CharEntry { text: "\u{1f311}", name: "new moon symbol", skin_tones: false },
+    CharEntry { text: "\u{1f312}", name: "waxing crescent moon symbol", skin_tones: false },
+    CharEntry { text: "\u{1f313}", name: "first quarter moon symbol", skin_tones: false },
+    CharEntry { text: "\u{1f314}", name: "waxing gibbous moon symbol", skin_tones: false },
+    CharEntry { text: "\u{1f315}", name: "full moon symbol", skin_tones: false },
+    CharEntry { text: "\u{1f316}", name: "waning gibbous moon symbol", skin_tones: false },
+    CharEntry { text: "\u{1f317}", name: "last quarter moon symbol", skin_tones: false },
+    CharEntry { text: "\u{1f318}", name: "waning crescent moon symbol", skin_tones: false },
+    CharEntry { text: "\u{1f319}", name: "crescent moon", skin_tones: false },
+    CharEntry { text: "\u{1f31a}", name: "new moon with face", skin_tones: false },
+    CharEntry { text: "\u{1f31b}", name: "first quarter moon with face", skin_tones: false },
+    CharEntry { text: "\u{1f31c}", name: "last quarter moon with face", skin_tones: false },
+    CharEntry { text: "\u{1f31d}", name: "full moon with face", skin_tones: false },
+    CharEntry { text: "\u{1f31e}", name: "sun with face", skin_tones: false },
+    CharEntry { text: "\u{1f31f}", name: "glowing star", skin_tones: false },
+    CharEntry { text: "\u{1f320}", name: "shooting star", skin_tones: false },
+    CharEntry { text: "\u{1f321}\u{fe0f}", name: "thermometer", skin_tones: false },
+    CharEntry { text: "\u{1f324}\u{fe0f}", name: "white sun with small cloud", skin_tones: false },
+    CharEntry { text: "\u{1f325}\u{fe0f}", name: "white sun behind cloud", skin_tones: false },
+    CharEntry { text: "\u{1f326}\u{fe0f}", name: "white sun behind cloud with rain", skin_tones: false },
+    CharEntry { text: "\u{1f327}\u{fe0f}", name: "cloud with rain", skin_tones: false },
+    CharEntry { text: "\u{1f328}\u{fe0f}", name: "cloud with snow", skin_tones: false },
+    CharEntry { text: "\u{1f329}\u{fe0f}", name: "cloud with lightning", skin_tones: false },
+    CharEntry { text: "\u{1f32a}\u{fe0f}", name: "cloud with tornado", skin_tones: false },
+    CharEntry { text: "\u{1f32b}\u{fe0f}", name: "fog", skin_tones: false },
+    CharEntry { text: "\u{1f32c}\u{fe0f}", name: "wind blowing face", skin_tones: false },
+    CharEntry { text: "\u{1f32d}", name: "hot dog", skin_tones: false },
+    CharEntry { text: "\u{1f32e}", name: "taco", skin_tones: false },
+    CharEntry { text: "\u{1f32f}", name: "burrito", skin_tones: false },
+    CharEntry { text: "\u{1f330}", name: "chestnut", skin_tones: false },
+    CharEntry { text: "\u{1f331}", name: "seedling", skin_tones: false },
+    CharEntry { text: "\u{1f332}", name: "evergreen tree", skin_tones: false },
+    CharEntry { text: "\u{1f333}", name: "deciduous tree", skin_tones: false },
+    CharEntry { text: "\u{1f334}", name: "palm tree", skin_tones: false },
+    CharEntry { text: "\u{1f335}", name: "cactus", skin_tones: false },
+    CharEntry { text: "\u{1f336}\u{fe0f}", name: "hot pepper", skin_tones: false },
+    CharEntry { text: "\u{1f337}", name: "tulip", skin_tones: false },
+    CharEntry { text: "\u{1f338}", name: "cherry blossom", skin_tones: false },
+    CharEntry { text: "\u{1f339}", name: "rose", skin_tones: false },
+    CharEntry { text: "\u{1f33a}", name: "hibiscus", skin_tones: false },
+    CharEntry { text: "\u{1f33b}", name: "sunflower", skin_tones: false },
+    CharEntry { text: "\u{1f33c}", name: "blossom", skin_tones: false },
+    CharEntry { text: "\u{1f33d}", name: "ear of maize", skin_tones: false },
+    CharEntry { text: "\u{1f33e}", name: "ear of rice", skin_tones: false },
+    CharEntry { text: "\u{1f33f}", name: "herb", skin_tones: false },
+    CharEntry { text: "\u{1f340}", name: "four leaf clover", skin_tones: false },
+    CharEntry { text: "\u{1f341}", name: "maple leaf", skin_tones: false },
+    CharEntry { text: "\u{1f342}", name: "fallen leaf", skin_tones: false },
+    CharEntry { text: "\u{1f343}", name: "leaf fluttering in wind", skin_tones: false },
+    CharEntry { text: "\u{1f344}", name: "mushroom", skin_tones: false },
+    CharEntry { text: "\u{1f345}", name: "tomato", skin_tones: false },
+    CharEntry { text: "\u{1f346}", name: "aubergine", skin_tones: false },
+    CharEntry { text: "\u{1f347}", name: "grapes", skin_tones: false },
+    CharEntry { text: "\u{1f348}", name: "melon", skin_tones: false },
+    CharEntry { text: "\u{1f349}", name: "watermelon", skin_tones: false },
+    CharEntry { text: "\u{1f34a}", name: "tangerine", skin_tones: false },
+    CharEntry { text: "\u{1f34b}", name: "lemon", skin_tones: false },
+    CharEntry { text: "\u{1f34c}", name: "banana", skin_tones: false },
+    CharEntry { text: "\u{1f34d}", name: "pineapple", skin_tones: false },
+    CharEntry { text: "\u{1f34e}", name: "red apple", skin_tones: false },
+    CharEntry { text: "\u{1f34f}", name: "green apple", skin_tones: false },
+    CharEntry { text: "\u{1f350}", name: "pear", skin_tones: false },
+    CharEntry { text: "\u{1f351}", name: "peach", skin_tones: false },
+    CharEntry { text: "\u{1f352}", name: "cherries", skin_tones: false },
+    CharEntry { text: "\u{1f353}", name: "strawberry", skin_tones: false },
+    CharEntry { text: "\u{1f354}", name: "hamburger", skin_tones: false },
+    CharEntry { text: "\u{1f355}", name: "slice of pizza", skin_tones: false },
+    CharEntry { text: "\u{1f356}", name: "meat on bone", skin_tones: false },
+    CharEntry { text: "\u{1f357}", name: "poultry leg", skin_tones: false },
+    CharEntry { text: "\u{1f358}", name: "rice cracker", skin_tones: false },
+    CharEntry { text: "\u{1f359}", name: "rice ball", skin_tones: false },
+    CharEntry { text: "\u{1f35a}", name: "cooked rice", skin_tones: false },
+    CharEntry { text: "\u{1f35b}", name: "curry and rice", skin_tones: false },
+    CharEntry { text: "\u{1f35c}", name: "steaming bowl", skin_tones: false },
+    CharEntry { text: "\u{1f35d}", name: "spaghetti", skin_tones: false },
+    CharEntry { text: "\u{1f35e}", name: "bread", skin_tones: false },
+    CharEntry { text: "\u{1f35f}", name: "french fries", skin_tones: false },
+    CharEntry { text: "\u{1f360}", name: "roasted sweet potato", skin_tones: false },
+    CharEntry { text: "\u{1f361}", name: "dango", skin_tones: false },
+    CharEntry { text: "\u{1f362}", name: "oden", skin_tones: false },
+    CharEntry { text: "\u{1f363}", name: "sushi", skin_tones: false },
+    CharEntry { text: "\u{1f364}", name: "fried shrimp", skin_tones: false },
+    CharEntry { text: "\u{1f365}", name: "fish cake with swirl design", skin_tones: false },
+    CharEntry { text: "\u{1f366}", name: "soft ice cream", skin_tones: false },
+    CharEntry { text: "\u{1f367}", name: "shaved ice", skin_tones: false },
+    CharEntry { text: "\u{1f368}", name: "ice cream", skin_tones: false },
+    CharEntry { text: "\u{1f369}", name: "doughnut", skin_tones: false },
+    CharEntry { text: "\u{1f36a}", name: "cookie", skin_tones: false },
+    CharEntry { text: "\u{1f36b}", name: "chocolate bar", skin_tones: false },
+    CharEntry { text: "\u{1f36c}", name: "candy", skin_tones: false },
+    CharEntry { text: "\u{1f36d}", name: "lollipop", skin_tones: false },
+    CharEntry { text: "\u{1f36e}", name: "custard", skin_tones: false },
+    CharEntry { text: "\u{1f36f}", name: "honey pot", skin_tones: false },
+    CharEntry { text: "\u{1f370}", name: "shortcake", skin_tones: false },
+    CharEntry { text: "\u{1f371}", name: "bento box", skin_tones: false },
+    CharEntry { text: "\u{1f372}", name: "pot of food", skin_tones: false },
+    CharEntry { text: "\u{1f373}", name: "cooking", skin_tones: false },
+    CharEntry { text: "\u{1f374}", name: "fork and knife", skin_tones: false },
+    CharEntry { text: "\u{1f375}", name: "teacup without handle", skin_tones: false },
+    CharEntry { text: "\u{1f376}", name: "sake bottle and cup", skin_tones: false },
+    CharEntry { text: "\u{1f377}", name: "wine glass", skin_tones: false },
+    CharEntry { text: "\u{1f378}", name: "cocktail glass", skin_tones: false },
+    CharEntry { text: "\u{1f379}", name: "tropical drink", skin_tones: false },
+    CharEntry { text: "\u{1f37a}", name: "beer mug", skin_tones: false },
+    CharEntry { text: "\u{1f37b}", name: "clinking beer mugs", skin_tones: false },
+    CharEntry { text: "\u{1f37c}", name: "baby bottle", skin_tones: false },
+    CharEntry { text: "\u{1f37d}\u{fe0f}", name: "fork and knife with plate", skin_tones: false },
+    CharEntry { text: "\u{1f37e}", name: "bottle with popping cork", skin_tones: false },
+    CharEntry { text: "\u{1f37f}", name: "popcorn", skin_tones: false },
+    CharEntry { text: "\u{1f380}", name: "ribbon", skin_tones: false },
+    CharEntry { text: "\u{1f381}", name: "wrapped present", skin_tones: false },
+    CharEntry { text: "\u{1f382}", name: "birthday cake", skin_tones: false },
+    CharEntry { text: "\u{1f383}", name: "jack-o-lantern", skin_tones: false },
+    CharEntry { text: "\u{1f384}", name: "christmas tree", skin_tones: false },
+    CharEntry { text: "\u{1f385}", name: "father christmas", skin_tones: true },
+    CharEntry { text: "\u{1f386}", name: "fireworks", skin_tones: false },
+    CharEntry { text: "\u{1f387}", name: "firework sparkler", skin_tones: false },
+    CharEntry { text: "\u{1f388}", name: "balloon", skin_tones: false },
+    CharEntry { text: "\u{1f389}", name: "party popper", skin_tones: false },
+    CharEntry { text: "\u{1f38a}", name: "confetti ball", skin_tones: false },
+    CharEntry { text: "\u{1f38b}", name: "tanabata tree", skin_tones: false },
+    CharEntry { text: "\u{1f38c}", name: "crossed flags", skin_tones: false },
+    CharEntry { text: "\u{1f38d}", name: "pine decoration", skin_tones: false },
+    CharEntry { text: "\u{1f38e}", name: "japanese dolls", skin_tones: false },
+    CharEntry { text: "\u{1f38f}", name: "carp streamer", skin_tones: false },
+    CharEntry { text: "\u{1f390}", name: "wind chime", skin_tones: false },
+    CharEntry { text: "\u{1f391}", name: "moon viewing ceremony", skin_tones: false },
+    CharEntry { text: "\u{1f392}", name: "school satchel", skin_tones: false },
+    CharEntry { text: "\u{1f393}", name: "graduation cap", skin_tones: false },
+    CharEntry { text: "\u{1f396}\u{fe0f}", name: "military medal", skin_tones: false },
+    CharEntry { text: "\u{1f397}\u{fe0f}", name: "reminder ribbon", skin_tones: false },
+    CharEntry { text: "\u{1f399}\u{fe0f}", name: "studio microphone", skin_tones: false },
+    CharEntry { text: "\u{1f39a}\u{fe0f}", name: "level slider", skin_tones: false },
+    CharEntry { text: "\u{1f39b}\u{fe0f}", name: "control knobs", skin_tones: false },
+    CharEntry { text: "\u{1f39e}\u{fe0f}", name: "film frames", skin_tones: false },
+    CharEntry { text: "\u{1f39f}\u{fe0f}", name: "admission tickets", skin_tones: false },
+    CharEntry { text: "\u{1f3a0}", name: "carousel horse", skin_tones: false },
+    CharEntry { text: "\u{1f3a1}", name: "ferris wheel", skin_tones: false },
+    CharEntry { text: "\u{1f3a2}", name: "roller coaster", skin_tones: false },
+    CharEntry { text: "\u{1f3a3}", name: "fishing pole and fish", skin_tones: false },
+    CharEntry { text: "\u{1f3a4}", name: "microphone", skin_tones: false },
+    CharEntry { text: "\u{1f3a5}", name: "movie camera", skin_tones: false },
+    CharEntry { text: "\u{1f3a6}", name: "cinema", skin_tones: false },
+    CharEntry { text: "\u{1f3a7}", name: "headphone", skin_tones: false },
+    CharEntry { text: "\u{1f3a8}", name: "artist palette", skin_tones: false },
+    CharEntry { text: "\u{1f3a9}", name: "top hat", skin_tones: false },
+    CharEntry { text: "\u{1f3aa}", name: "circus tent", skin_tones: false },
+    CharEntry { text: "\u{1f3ab}", name: "ticket", skin_tones: false },
+    CharEntry { text: "\u{1f3ac}", name: "clapper board", skin_tones: false },
+    CharEntry { text: "\u{1f3ad}", name: "performing arts", skin_tones: false },
+    CharEntry { text: "\u{1f3ae}", name: "video game", skin_tones: false },
+    CharEntry { text: "\u{1f3af}", name: "direct hit", skin_tones: false },
+    CharEntry { text: "\u{1f3b0}", name: "slot machine", skin_tones: false },
+    CharEntry { text: "\u{1f3b1}", name: "billiards", skin_tones: false },
+    CharEntry { text: "\u{1f3b2}", name: "game die", skin_tones: false },
+    CharEntry { text: "\u{1f3b3}", name: "bowling", skin_tones: false },
+    CharEntry { text: "\u{1f3b4}", name: "flower playing cards", skin_tones: false },
+    CharEntry { text: "\u{1f3b5}", name: "musical note", skin_tones: false },
+    CharEntry { text: "\u{1f3b6}", name: "multiple musical notes", skin_tones: false },
+    CharEntry { text: "\u{1f3b7}", name: "saxophone", skin_tones: false },
+    CharEntry { text: "\u{1f3b8}", name: "guitar", skin_tones: false },
+    CharEntry { text: "\u{1f3b9}", name: "musical keyboard", skin_tones: false },
+    CharEntry { text: "\u{1f3ba}", name: "trumpet", skin_tones: false },
+    CharEntry { text: "\u{1f3bb}", name: "violin", skin_tones: false },
+    CharEntry { text: "\u{1f3bc}", name: "musical score", skin_tones: false },
+    CharEntry { text: "\u{1f3bd}", name: "running shirt with sash", skin_tones: false },
+    CharEntry { text: "\u{1f3be}", name: "tennis racquet and ball", skin_tones: false },
+    CharEntry { text: "\u{1f3bf}", name: "ski and ski boot", skin_tones: false },
+    CharEntry { text: "\u{1f3c0}", name: "basketball and hoop", skin_tones: false },
+    CharEntry { text: "\u{1f3c1}", name: "chequered flag", skin_tones: false },
+    CharEntry { text: "\u{1f3c2}", name: "snowboarder", skin_tones: true },
+    CharEntry { text: "\u{1f3c3}", name: "runner", skin_tones: true },
+    CharEntry { text: "\u{1f3c4}", name: "surfer", skin_tones: true },
+    CharEntry { text: "\u{1f3c5}", name: "sports medal", skin_tones: false },
+    CharEntry { text: "\u{1f3c6}", name: "trophy", skin_tones: false },
+    CharEntry { text: "\u{1f3c7}", name: "horse racing", skin_tones: true },
+    CharEntry { text: "\u{1f3c8}", name: "american football", skin_tones: false },
+    CharEntry { text: "\u{1f3c9}", name: "rugby football", skin_tones: false },
+    CharEntry { text: "\u{1f3ca}", name: "swimmer", skin_tones: true },
+    CharEntry { text: "\u{1f3cb}\u{fe0f}", name: "weight lifter", skin_tones: true },
+    CharEntry { text: "\u{1f3cc}\u{fe0f}", name: "golfer", skin_tones: true },
+    CharEntry { text: "\u{1f3cd}\u{fe0f}", name: "racing motorcycle", skin_tones: false },
+    CharEntry { text: "\u{1f3ce}\u{fe0f}", name: "racing car", skin_tones: false },
+    CharEntry { text: "\u{1f3cf}", name: "cricket bat and ball", skin_tones: false },
+    CharEntry { text: "\u{1f3d0}", name: "volleyball", skin_tones: false },
+    CharEntry { text: "\u{1f3d1}", name: "field hockey stick and ball", skin_tones: false },
+    CharEntry { text: "\u{1f3d2}", name: "ice hockey stick and puck", skin_tones: false },
+    CharEntry { text: "\u{1f3d3}", name: "table tennis paddle and ball", skin_tones: false },
+    CharEntry { text: "\u{1f3d4}\u{fe0f}", name: "snow capped mountain", skin_tones: false },
+    CharEntry { text: "\u{1f3d5}\u{fe0f}", name: "camping", skin_tones: false },
+    CharEntry { text: "\u{1f3d6}\u{fe0f}", name: "beach with umbrella", skin_tones: false },
+    CharEntry { text: "\u{1f3d7}\u{fe0f}", name: "building construction", skin_tones: false },
+    CharEntry { text: "\u{1f3d8}\u{fe0f}", name: "house buildings", skin_tones: false },
+    CharEntry { text: "\u{1f3d9}\u{fe0f}", name: "cityscape", skin_tones: false },
+    CharEntry { text: "\u{1f3da}\u{fe0f}", name: "derelict house building", skin_tones: false },
+    CharEntry { text: "\u{1f3db}\u{fe0f}", name: "classical building", skin_tones: false },
+    CharEntry { text: "\u{1f3dc}\u{fe0f}", name: "desert", skin_tones: false },
+    CharEntry { text: "\u{1f3dd}\u{fe0f}", name: "desert island", skin_tones: false },
+    CharEntry { text: "\u{1f3de}\u{fe0f}", name: "national park", skin_tones: false },
+    CharEntry { text: "\u{1f3df}\u{fe0f}", name: "stadium", skin_tones: false },
+    CharEntry { text: "\u{1f3e0}", name: "house building", skin_tones: false },
+    CharEntry { text: "\u{1f3e1}", name: "house with garden", skin_tones: false },
+    CharEntry { text: "\u{1f3e2}", name: "office building", skin_tones: false },
+    CharEntry { text: "\u{1f3e3}", name: "japanese post office", skin_tones: false },
+    CharEntry { text: "\u{1f3e4}", name: "european post office", skin_tones: false },
+    CharEntry { text: "\u{1f3e5}", name: "hospital", skin_tones: false },
+    CharEntry { text: "\u{1f3e6}", name: "bank", skin_tones: false },
+    CharEntry { text: "\u{1f3e7}", name: "automated teller machine", skin_tones: false },
+    CharEntry { text: "\u{1f3e8}", name: "hotel", skin_tones: false },
+    CharEntry { text: "\u{1f3e9}", name: "love hotel", skin_tones: false },
+    CharEntry { text: "\u{1f3ea}", name: "convenience store", skin_tones: false },
+    CharEntry { text: "\u{1f3eb}", name: "school", skin_tones: false },
+    CharEntry { text: "\u{1f3ec}", name: "department store", skin_tones: false },
+    CharEntry { text: "\u{1f3ed}", name: "factory", skin_tones: false },
+    CharEntry { text: "\u{1f3ee}", name: "izakaya lantern", skin_tones: false },
+    CharEntry { text: "\u{1f3ef}", name: "japanese castle", skin_tones: false },
+    CharEntry { text: "\u{1f3f0}", name: "european castle", skin_tones: false },
+    CharEntry { text: "\u{1f3f3}\u{fe0f}", name: "waving white flag", skin_tones: false },
+    CharEntry { text: "\u{1f3f4}", name: "waving black flag", skin_tones: false },
+    CharEntry { text: "\u{1f3f5}\u{fe0f}", name: "rosette", skin_tones: false },
+    CharEntry { text: "\u{1f3f7}\u{fe0f}", name: "label", skin_tones: false },
+    CharEntry { text: "\u{1f3f8}", name: "badminton racquet and shuttlecock", skin_tones: false },
+    CharEntry { text: "\u{1f3f9}", name: "bow and arrow", skin_tones: false },
+    CharEntry { text: "\u{1f3fa}", name: "amphora", skin_tones: false },
+    CharEntry { text: "\u{1f400}", name: "rat", skin_tones: false },
+    CharEntry { text: "\u{1f401}", name: "mouse", skin_tones: false },
+    CharEntry { text: "\u{1f402}", name: "ox", skin_tones: false },
+    CharEntry { text: "\u{1f403}", name: "water buffalo", skin_tones: false },
+    CharEntry { text: "\u{1f404}", name: "cow", skin_tones: false },
+    CharEntry { text: "\u{1f405}", name: "tiger", skin_tones: false },
+    CharEntry { text: "\u{1f406}", name: "leopard", skin_tones: false },
+    CharEntry { text: "\u{1f407}", name: "rabbit", skin_tones: false },
+    CharEntry { text: "\u{1f408}", name: "cat", skin_tones: false },
+    CharEntry { text: "\u{1f409}", name: "dragon", skin_tones: false },
+    CharEntry { text: "\u{1f40a}", name: "crocodile", skin_tones: false },
+    CharEntry { text: "\u{1f40b}", name: "whale", skin_tones: false },
+    CharEntry { text: "\u{1f40c}", name: "snail", skin_tones: false },
+    CharEntry { text: "\u{1f40d}", name: "snake", skin_tones: false },
+    CharEntry { text: "\u{1f40e}", name: "horse", skin_tones: false },
+    CharEntry { text: "\u{1f40f}", name: "ram", skin_tones: false },
+    CharEntry { text: "\u{1f410}", name: "goat", skin_tones: false },
+    CharEntry { text: "\u{1f411}", name: "sheep", skin_tones: false },
+    CharEntry { text: "\u{1f412}", name: "monkey", skin_tones: false },
+    CharEntry { text: "\u{1f413}", name: "rooster", skin_tones: false },
+    CharEntry { text: "\u{1f414}", name: "chicken", skin_tones: false },
+    CharEntry { text: "\u{1f415}", name: "dog", skin_tones: false },
+    CharEntry { text: "\u{1f416}", name: "pig", skin_tones: false },
+    CharEntry { text: "\u{1f417}", name: "boar", skin_tones: false },
+    CharEntry { text: "\u{1f418}", name: "elephant", skin_tones: false },
+    CharEntry { text: "\u{1f419}", name: "octopus", skin_tones: false },
+    CharEntry { text: "\u{1f41a}", name: "spiral shell", skin_tones: false },
+    CharEntry { text: "\u{1f41b}", name: "bug", skin_tones: false },
+    CharEntry { text: "\u{1f41c}", name: "ant", skin_tones: false },
+    CharEntry { text: "\u{1f41d}", name: "honeybee", skin_tones: false },
+    CharEntry { text: "\u{1f41e}", name: "lady beetle", skin_tones: false },
+    CharEntry { text: "\u{1f41f}", name: "fish", skin_tones: false },
+    CharEntry { text: "\u{1f420}", name: "tropical fish", skin_tones: false },
+    CharEntry { text: "\u{1f421}", name: "blowfish", skin_tones: false },
+    CharEntry { text: "\u{1f422}", name: "turtle", skin_tones: false },
+    CharEntry { text: "\u{1f423}", name: "hatching chick", skin_tones: false },
+    CharEntry { text: "\u{1f424}", name: "baby chick", skin_tones: false },
+    CharEntry { text: "\u{1f425}", name: "front-facing baby chick", skin_tones: false },
+    CharEntry { text: "\u{1f426}", name: "bird", skin_tones: false },
+    CharEntry { text: "\u{1f427}", name: "penguin", skin_tones: false },
+    CharEntry { text: "\u{1f428}", name: "koala", skin_tones: false },
+    CharEntry { text: "\u{1f429}", name: "poodle", skin_tones: false },
+    CharEntry { text: "\u{1f42a}", name: "dromedary camel", skin_tones: false },
+    CharEntry { text: "\u{1f42b}", name: "bactrian camel", skin_tones: false },
+    CharEntry { text: "\u{1f42c}", name: "dolphin", skin_tones: false },
+    CharEntry { text: "\u{1f42d}", name: "mouse face", skin_tones: false },
+    CharEntry { text: "\u{1f42e}", name: "cow face", skin_tones: false },
+    CharEntry { text: "\u{1f42f}", name: "tiger face", skin_tones: false },
+    CharEntry { text: "\u{1f430}", name: "rabbit face", skin_tones: false },
+    CharEntry { text: "\u{1f431}", name: "cat face", skin_tones: false },
+    CharEntry { text: "\u{1f432}", name: "dragon face", skin_tones: false },
+    CharEntry { text: "\u{1f433}", name: "spouting whale", skin_tones: false },
+    CharEntry { text: "\u{1f434}", name: "horse face", skin_tones: false },
+    CharEntry { text: "\u{1f435}", name: "monkey face", skin_tones: false },
+    CharEntry { text: "\u{1f436}", name: "dog face", skin_tones: false },
+    CharEntry { text: "\u{1f437}", name: "pig face", skin_tones: false },
+    CharEntry { text: "\u{1f438}", name: "frog face", skin_tones: false },
+    CharEntry { text: "\u{1f439}", name: "hamster face", skin_tones: false },
+    CharEntry { text: "\u{1f43a}", name: "wolf face", skin_tones: false },
+    CharEntry { text: "\u{1f43b}", name: "bear face", skin_tones: false },
+    CharEntry { text: "\u{1f43c}", name: "panda face", skin_tones: false },
+    CharEntry { text: "\u{1f43d}", name: "pig nose", skin_tones: false },
+    CharEntry { text: "\u{1f43e}", name: "paw prints", skin_tones: false },
+    CharEntry { text: "\u{1f43f}\u{fe0f}", name: "chipmunk", skin_tones: false },
+    CharEntry { text: "\u{1f440}", name: "eyes", skin_tones: false },
+    CharEntry { text: "\u{1f441}\u{fe0f}", name: "eye", skin_tones: false },
+    CharEntry { text: "\u{1f442}", name: "ear", skin_tones: true },
+    CharEntry { text: "\u{1f443}", name: "nose", skin_tones: true },
+    CharEntry { text: "\u{1f444}", name: "mouth", skin_tones: false },
+    CharEntry { text: "\u{1f445}", name: "tongue", skin_tones: false },
+    CharEntry { text: "\u{1f446}", name: "white up pointing backhand index", skin_tones: true },
+    CharEntry { text: "\u{1f447}", name: "white down pointing backhand index", skin_tones: true },
+    CharEntry { text: "\u{1f448}", name: "white left pointing backhand index", skin_tones: true },
+    CharEntry { text: "\u{1f449}", name: "white right pointing backhand index", skin_tones: true },
+    CharEntry { text: "\u{1f44a}", name: "fisted hand sign", skin_tones: true },
+    CharEntry { text: "\u{1f44b}", name: "waving hand sign", skin_tones: true },
+    CharEntry { text: "\u{1f44c}", name: "ok hand sign", skin_tones: true },
+    CharEntry { text: "\u{1f44d}", name: "thumbs up sign", skin_tones: true },
+    CharEntry { text: "\u{1f44e}", name: "thumbs down sign", skin_tones: true },
+    CharEntry { text: "\u{1f44f}", name: "clapping hands sign", skin_tones: true },
+    CharEntry { text: "\u{1f450}", name: "open hands sign", skin_tones: true },
+    CharEntry { text: "\u{1f451}", name: "crown", skin_tones: false },
+    CharEntry { text: "\u{1f452}", name: "womans hat", skin_tones: false },
+    CharEntry { text: "\u{1f453}", name: "eyeglasses", skin_tones: false },
+    CharEntry { text: "\u{1f454}", name: "necktie", skin_tones: false },
+    CharEntry { text: "\u{1f455}", name: "t-shirt", skin_tones: false },
+    CharEntry { text: "\u{1f456}", name: "jeans", skin_tones: false },
+    CharEntry { text: "\u{1f457}", name: "dress", skin_tones: false },
+    CharEntry { text: "\u{1f458}", name: "kimono", skin_tones: false },
+    CharEntry { text: "\u{1f459}", name: "bikini", skin_tones: false },
+    CharEntry { text: "\u{1f45a}", name: "womans clothes", skin_tones: false },
+    CharEntry { text: "\u{1f45b}", name: "purse", skin_tones: false },
+    CharEntry { text: "\u{1f45c}", name: "handbag", skin_tones: false },
+    CharEntry { text: "\u{1f45d}", name: "pouch", skin_tones: false },
+    CharEntry { text: "\u{1f45e}", name: "mans shoe", skin_tones: false },
+    CharEntry { text: "\u{1f45f}", name: "athletic shoe", skin_tones: false },
+    CharEntry { text: "\u{1f460}", name: "high-heeled shoe", skin_tones: false },
+    CharEntry { text: "\u{1f461}", name: "womans sandal", skin_tones: false },
+    CharEntry { text: "\u{1f462}", name: "womans boots", skin_tones: false },
+    CharEntry { text: "\u{1f463}", name: "footprints", skin_tones: false },
+    CharEntry { text: "\u{1f464}", name: "bust in silhouette", skin_tones: false },
+    CharEntry { text: "\u{1f465}", name: "busts in silhouette", skin_tones: false },
+    CharEntry { text: "\u{1f466}", name: "boy", skin_tones: true },
+    CharEntry { text: "\u{1f467}", name: "girl", skin_tones: true },
+    CharEntry { text: "\u{1f468}", name: "man", skin_tones: true },
+    CharEntry { text: "\u{1f469}", name: "woman", skin_tones: true },
+    CharEntry { text: "\u{1f46a}", name: "family", skin_tones: true },
+    CharEntry { text: "\u{1f46b}", name: "man and woman holding hands", skin_tones: true },
+    CharEntry { text: "\u{1f46c}", name: "two men holding hands", skin_tones: true },
+    CharEntry { text: "\u{1f46d}", name: "two women holding hands", skin_tones: true },
+    CharEntry { text: "\u{1f46e}", name: "police officer", skin_tones: true },
+    CharEntry { text: "\u{1f46f}", name: "woman with bunny ears", skin_tones: true },
+    CharEntry { text: "\u{1f470}", name: "bride with veil", skin_tones: true },
+    CharEntry { text: "\u{1f471}", name: "person with blond hair", skin_tones: true },
+    CharEntry { text: "\u{1f472}", name: "man with gua pi mao", skin_tones: true },
+    CharEntry { text: "\u{1f473}", name: "man with turban", skin_tones: true },
+    CharEntry { text: "\u{1f474}", name: "older man", skin_tones: true },
+    CharEntry { text: "\u{1f475}", name: "older woman", skin_tones: true },
+    CharEntry { text: "\u{1f476}", name: "baby", skin_tones: true },
+    CharEntry { text: "\u{1f477}", name: "construction worker", skin_tones: true },
+    CharEntry { text: "\u{1f478}", name: "princess", skin_tones: true },
+    CharEntry { text: "\u{1f479}", name: "japanese ogre", skin_tones: false },
+    CharEntry { text: "\u{1f47a}", name: "japanese goblin", skin_tones: false },
+    CharEntry { text: "\u{1f47b}", name: "ghost", skin_tones: false },
+    CharEntry { text: "\u{1f47c}", name: "baby angel", skin_tones: true },
+    CharEntry { text: "\u{1f47d}", name: "extraterrestrial alien", skin_tones: false },
+    CharEntry { text: "\u{1f47e}", name: "alien monster", skin_tones: false },
+    CharEntry { text: "\u{1f47f}", name: "imp", skin_tones: false },
+    CharEntry { text: "\u{1f480}", name: "skull", skin_tones: false },
+    CharEntry { text: "\u{1f481}", name: "information desk person", skin_tones: true },
+    CharEntry { text: "\u{1f482}", name: "guardsman", skin_tones: true },
+    CharEntry { text: "\u{1f483}", name: "dancer", skin_tones: true },
+    CharEntry { text: "\u{1f484}", name: "lipstick", skin_tones: false },
+    CharEntry { text: "\u{1f485}", name: "nail polish", skin_tones: true },
+    CharEntry { text: "\u{1f486}", name: "face massage", skin_tones: true },
+    CharEntry { text: "\u{1f487}", name: "haircut", skin_tones: true },
+    CharEntry { text: "\u{1f488}", name: "barber pole", skin_tones: false },
+    CharEntry { text: "\u{1f489}", name: "syringe", skin_tones: false },
+    CharEntry { text: "\u{1f48a}", name: "pill", skin_tones: false },
+    CharEntry { text: "\u{1f48b}", name: "kiss mark", skin_tones: false },
+    CharEntry { text: "\u{1f48c}", name: "love letter", skin_tones: false },
+    CharEntry { text: "\u{1f48d}", name: "ring", skin_tones: false },
+    CharEntry { text: "\u{1f48e}", name: "gem stone", skin_tones: false },
+    CharEntry { text: "\u{1f48f}", name: "kiss", skin_tones: true },
+    CharEntry { text: "\u{1f490}", name: "bouquet", skin_tones: false },
+    CharEntry { text: "\u{1f491}", name: "couple with heart", skin_tones: true },
+    CharEntry { text: "\u{1f492}", name: "wedding", skin_tones: false },
+    CharEntry { text: "\u{1f493}", name: "beating heart", skin_tones: false },
+    CharEntry { text: "\u{1f494}", name: "broken heart", skin_tones: false },
+    CharEntry { text: "\u{1f495}", name: "two hearts", skin_tones: false },
+    CharEntry { text: "\u{1f496}", name: "sparkling heart", skin_tones: false },
+    CharEntry { text: "\u{1f497}", name: "growing heart", skin_tones: false },
+    CharEntry { text: "\u{1f498}", name: "heart with arrow", skin_tones: false },
+    CharEntry { text: "\u{1f499}", name: "blue heart", skin_tones: false },
+    CharEntry { text: "\u{1f49a}", name: "green heart", skin_tones: false },
+    CharEntry { text: "\u{1f49b}", name: "yellow heart", skin_tones: false },
+    CharEntry { text: "\u{1f49c}", name: "purple heart", skin_tones: false },
+    CharEntry { text: "\u{1f49d}", name: "heart with ribbon", skin_tones: false },
+    CharEntry { text: "\u{1f49e}", name: "revolving hearts", skin_tones: false },
+    CharEntry { text: "\u{1f49f}", name: "heart decoration", skin_tones: false },
+    CharEntry { text: "\u{1f4a0}", name: "diamond shape with a dot inside", skin_tones: false },
+    CharEntry { text: "\u{1f4a1}", name: "electric light bulb", skin_tones: false },
+    CharEntry { text: "\u{1f4a2}", name: "anger symbol", skin_tones: false },
+    CharEntry { text: "\u{1f4a3}", name: "bomb", skin_tones: false },
+    CharEntry { text: "\u{1f4a4}", name: "sleeping symbol", skin_tones: false },
+    CharEntry { text: "\u{1f4a5}", name: "collision symbol", skin_tones: false },
+    CharEntry { text: "\u{1f4a6}", name: "splashing sweat symbol", skin_tones: false },
+    CharEntry { text: "\u{1f4a7}", name: "droplet", skin_tones: false },
+    CharEntry { text: "\u{1f4a8}", name: "dash symbol", skin_tones: false },
+    CharEntry { text: "\u{1f4a9}", name: "pile of poo", skin_tones: false },
+    CharEntry { text: "\u{1f4aa}", name: "flexed biceps", skin_tones: true },
+    CharEntry { text: "\u{1f4ab}", name: "dizzy symbol", skin_tones: false },
+    CharEntry { text: "\u{1f4ac}", name: "speech balloon", skin_tones: false },
+    CharEntry { text: "\u{1f4ad}", name: "thought balloon", skin_tones: false },
+    CharEntry { text: "\u{1f4ae}", name: "white flower", skin_tones: false },
+    CharEntry { text: "\u{1f4af}", name: "hundred points symbol", skin_tones: false },
+    CharEntry { text: "\u{1f4b0}", name: "money bag", skin_tones: false },
+    CharEntry { text: "\u{1f4b1}", name: "currency exchange", skin_tones: false },
+    CharEntry { text: "\u{1f4b2}", name: "heavy dollar sign", skin_tones: false },
+    CharEntry { text: "\u{1f4b3}", name: "credit card", skin_tones: false },
+    CharEntry { text: "\u{1f4b4}", name: "banknote with yen sign", skin_tones: false },
+    CharEntry { text: "\u{1f4b5}", name: "banknote with dollar sign", skin_tones: false },
+    CharEntry { text: "\u{1f4b6}", name: "banknote with euro sign", skin_tones: false },
+    CharEntry { text: "\u{1f4b7}", name: "banknote with pound sign", skin_tones: false },
+    CharEntry { text: "\u{1f4b8}", name: "money with wings", skin_tones: false },
+    CharEntry { text: "\u{1f4b9}", name: "chart with upwards trend and yen sign", skin_tones: false },
+    CharEntry { text: "\u{1f4ba}", name: "seat", skin_tones: false },
+    CharEntry { text: "\u{1f4bb}", name: "personal computer", skin_tones: false },
+    CharEntry { text: "\u{1f4bc}", name: "briefcase", skin_tones: false },
+    CharEntry { text: "\u{1f4bd}", name: "minidisc", skin_tones: false },
+    CharEntry { text: "\u{1f4be}", name: "floppy disk", skin_tones: false },
+    CharEntry { text: "\u{1f4bf}", name: "optical disc", skin_tones: false },
+    CharEntry { text: "\u{1f4c0}", name: "dvd", skin_tones: false },
+    CharEntry { text: "\u{1f4c1}", name: "file folder", skin_tones: false },
+    CharEntry { text: "\u{1f4c2}", name: "open file folder", skin_tones: false },
+    CharEntry { text: "\u{1f4c3}", name: "page with curl", skin_tones: false },
+    CharEntry { text: "\u{1f4c4}", name: "page facing up", skin_tones: false },
+    CharEntry { text: "\u{1f4c5}", name: "calendar", skin_tones: false },
+    CharEntry { text: "\u{1f4c6}", name: "tear-off calendar", skin_tones: false },
+    CharEntry { text: "\u{1f4c7}", name: "card index", skin_tones: false },
+    CharEntry { text: "\u{1f4c8}", name: "chart with upwards trend", skin_tones: false },
+    CharEntry { text: "\u{1f4c9}", name: "chart with downwards trend", skin_tones: false },
+    CharEntry { text: "\u{1f4ca}", name: "bar chart", skin_tones: false },
+    CharEntry { text: "\u{1f4cb}", name: "clipboard", skin_tones: false },
+    CharEntry { text: "\u{1f4cc}", name: "pushpin", skin_tones: false },
+    CharEntry { text: "\u{1f4cd}", name: "round pushpin", skin_tones: false },
+    CharEntry { text: "\u{1f4ce}", name: "paperclip", skin_tones: false },
+    CharEntry { text: "\u{1f4cf}", name: "straight ruler", skin_tones: false },
+    CharEntry { text: "\u{1f4d0}", name: "triangular ruler", skin_tones: false },
+    CharEntry { text: "\u{1f4d1}", name: "bookmark tabs", skin_tones: false },
+    CharEntry { text: "\u{1f4d2}", name: "ledger", skin_tones: false },
+    CharEntry { text: "\u{1f4d3}", name: "notebook", skin_tones: false },
+    CharEntry { text: "\u{1f4d4}", name: "notebook with decorative cover", skin_tones: false },
+    CharEntry { text: "\u{1f4d5}", name: "closed book", skin_tones: false },
+    CharEntry { text: "\u{1f4d6}", name: "open book", skin_tones: false },
+    CharEntry { text: "\u{1f4d7}", name: "green book", skin_tones: false },
+    CharEntry { text: "\u{1f4d8}", name: "blue book", skin_tones: false },
+    CharEntry { text: "\u{1f4d9}", name: "orange book", skin_tones: false },
+    CharEntry { text: "\u{1f4da}", name: "books", skin_tones: false },
+    CharEntry { text: "\u{1f4db}", name: "name badge", skin_tones: false },
+    CharEntry { text: "\u{1f4dc}", name: "scroll", skin_tones: false },
+    CharEntry { text: "\u{1f4dd}", name: "memo", skin_tones: false },
+    CharEntry { text: "\u{1f4de}", name: "telephone receiver", skin_tones: false },
+    CharEntry { text: "\u{1f4df}", name: "pager", skin_tones: false },
+    CharEntry { text: "\u{1f4e0}", name: "fax machine", skin_tones: false },
+    CharEntry { text: "\u{1f4e1}", name: "satellite antenna", skin_tones: false },
+    CharEntry { text: "\u{1f4e2}", name: "public address loudspeaker", skin_tones: false },
+    CharEntry { text: "\u{1f4e3}", name: "cheering megaphone", skin_tones: false },
+    CharEntry { text: "\u{1f4e4}", name: "outbox tray", skin_tones: false },
+    CharEntry { text: "\u{1f4e5}", name: "inbox tray", skin_tones: false },
+    CharEntry { text: "\u{1f4e6}", name: "package", skin_tones: false },
+    CharEntry { text: "\u{1f4e7}", name: "e-mail symbol", skin_tones: false },
+    CharEntry { text: "\u{1f4e8}", name: "incoming envelope", skin_tones: false },
+    CharEntry { text: "\u{1f4e9}", name: "envelope with downwards arrow above", skin_tones: false },
+    CharEntry { text: "\u{1f4ea}", name: "closed mailbox with lowered flag", skin_tones: false },
+    CharEntry { text: "\u{1f4eb}", name: "closed mailbox with raised flag", skin_tones: false },
+    CharEntry { text: "\u{1f4ec}", name: "open mailbox with raised flag", skin_tones: false },
+    CharEntry { text: "\u{1f4ed}", name: "open mailbox with lowered flag", skin_tones: false },
+    CharEntry { text: "\u{1f4ee}", name: "postbox", skin_tones: false },
+    CharEntry { text: "\u{1f4ef}", name: "postal horn", skin_tones: false },
+    CharEntry { text: "\u{1f4f0}", name: "newspaper", skin_tones: false },
+    CharEntry { text: "\u{1f4f1}", name: "mobile phone", skin_tones: false },
+    CharEntry { text: "\u{1f4f2}", name: "mobile phone with rightwards arrow at left", skin_tones: false },
+    CharEntry { text: "\u{1f4f3}", name: "vibration mode", skin_tones: false },
+    CharEntry { text: "\u{1f4f4}", name: "mobile phone off", skin_tones: false },
+    CharEntry { text: "\u{1f4f5}", name: "no mobile phones", skin_tones: false },
+    CharEntry { text: "\u{1f4f6}", name: "antenna with bars", skin_tones: false },
+    CharEntry { text: "\u{1f4f7}", name: "camera", skin_tones: false },
+    CharEntry { text: "\u{1f4f8}", name: "camera with flash", skin_tones: false },
+    CharEntry { text: "\u{1f4f9}", name: "video camera", skin_tones: false },
+    CharEntry { text: "\u{1f4fa}", name: "television", skin_tones: false },
+    CharEntry { text: "\u{1f4fb}", name: "radio", skin_tones: false },
+    CharEntry { text: "\u{1f4fc}", name: "videocassette", skin_tones: false },
+    CharEntry { text: "\u{1f4fd}\u{fe0f}", name: "film projector", skin_tones: false },
+    CharEntry { text: "\u{1f4ff}", name: "prayer beads", skin_tones: false },
+    CharEntry { text: "\u{1f500}", name: "twisted rightwards arrows", skin_tones: false },
+    CharEntry { text: "\u{1f501}", name: "clockwise rightwards and leftwards open circle arrows", skin_tones: false },
+    CharEntry { text: "\u{1f502}", name: "clockwise rightwards and leftwards open circle arrows with circled one overlay", skin_tones: false },
+    CharEntry { text: "\u{1f503}", name: "clockwise downwards and upwards open circle arrows", skin_tones: false },
+    CharEntry { text: "\u{1f504}", name: "anticlockwise downwards and upwards open circle arrows", skin_tones: false },
+    CharEntry { text: "\u{1f505}", name: "low brightness symbol", skin_tones: false },
+    CharEntry { text: "\u{1f506}", name: "high brightness symbol", skin_tones: false },
+    CharEntry { text: "\u{1f507}", name: "speaker with cancellation stroke", skin_tones: false },
+    CharEntry { text: "\u{1f508}", name: "speaker", skin_tones: false },
+    CharEntry { text: "\u{1f509}", name: "speaker with one sound wave", skin_tones: false },
+    CharEntry { text: "\u{1f50a}", name: "speaker with three sound waves", skin_tones: false },
+    CharEntry { text: "\u{1f50b}", name: "battery", skin_tones: false },
+    CharEntry { text: "\u{1f50c}", name: "electric plug", skin_tones: false },
+    CharEntry { text: "\u{1f50d}", name: "left-pointing magnifying glass", skin_tones: false },
+    CharEntry { text: "\u{1f50e}", name: "right-pointing magnifying glass", skin_tones: false },
+    CharEntry { text: "\u{1f50f}", name: "lock with ink pen", skin_tones: false },
+    CharEntry { text: "\u{1f510}", name: "closed lock with key", skin_tones: false },
+    CharEntry { text: "\u{1f511}", name: "key", skin_tones: false },
+    CharEntry { text: "\u{1f512}", name: "lock", skin_tones: false },
+    CharEntry { text: "\u{1f513}", name: "open lock", skin_tones: false },
+    CharEntry { text: "\u{1f514}", name: "bell", skin_tones: false },
+    CharEntry { text: "\u{1f515}", name: "bell with cancellation stroke", skin_tones: false },
+    CharEntry { text: "\u{1f516}", name: "bookmark", skin_tones: false },
+    CharEntry { text: "\u{1f517}", name: "link symbol", skin_tones: false },
+    CharEntry { text: "\u{1f518}", name: "radio button", skin_tones: false },
+    CharEntry { text: "\u{1f519}", name: "back with leftwards arrow above", skin_tones: false },
+    CharEntry { text: "\u{1f51a}", name: "end with leftwards arrow above", skin_tones: false },
+    CharEntry { text: "\u{1f51b}", name: "on with exclamation mark with left right arrow above", skin_tones: false },
+    CharEntry { text: "\u{1f51c}", name: "soon with rightwards arrow above", skin_tones: false },
+    CharEntry { text: "\u{1f51d}", name: "top with upwards arrow above", skin_tones: false },
+    CharEntry { text: "\u{1f51e}", name: "no one under eighteen symbol", skin_tones: false },
+    CharEntry { text: "\u{1f51f}", name: "keycap ten", skin_tones: false },
+    CharEntry { text: "\u{1f520}", name: "input symbol for latin capital letters", skin_tones: false },
+    CharEntry { text: "\u{1f521}", name: "input symbol for latin small letters", skin_tones: false },
+    CharEntry { text: "\u{1f522}", name: "input symbol for numbers", skin_tones: false },
+    CharEntry { text: "\u{1f523}", name: "input symbol for symbols", skin_tones: false },
+    CharEntry { text: "\u{1f524}", name: "input symbol for latin letters", skin_tones: false },
+    CharEntry { text: "\u{1f525}", name: "fire", skin_tones: false },
+    CharEntry { text: "\u{1f526}", name: "electric torch", skin_tones: false },
+    CharEntry { text: "\u{1f527}", name: "wrench", skin_tones: false },
+    CharEntry { text: "\u{1f528}", name: "hammer", skin_tones: false },
+    CharEntry { text: "\u{1f529}", name: "nut and bolt", skin_tones: false },
+    CharEntry { text: "\u{1f52a}", name: "hocho", skin_tones: false },
+    CharEntry { text: "\u{1f52b}", name: "pistol", skin_tones: false },
+    CharEntry { text: "\u{1f52c}", name: "microscope", skin_tones: false },
+    CharEntry { text: "\u{1f52d}", name: "telescope", skin_tones: false },
+    CharEntry { text: "\u{1f52e}", name: "crystal ball", skin_tones: false },
+    CharEntry { text: "\u{1f52f}", name: "six pointed star with middle dot", skin_tones: false },
+    CharEntry { text: "\u{1f530}", name: "japanese symbol for beginner", skin_tones: false },
+    CharEntry { text: "\u{1f531}", name: "trident emblem", skin_tones: false },
+    CharEntry { text: "\u{1f532}", name: "black square button", skin_tones: false },
+    CharEntry { text: "\u{1f533}", name: "white square button", skin_tones: false },
+    CharEntry { text: "\u{1f534}", name: "large red circle", skin_tones: false },
+    CharEntry { text: "\u{1f535}", name: "large blue circle", skin_tones: false },
+    CharEntry { text: "\u{1f536}", name: "large orange diamond", skin_tones: false },
+    CharEntry { text: "\u{1f537}", name: "large blue diamond", skin_tones: false },
+    CharEntry { text: "\u{1f538}", name: "small orange diamond", skin_tones: false },
+    CharEntry { text: "\u{1f539}", name: "small blue diamond", skin_tones: false },
+    CharEntry { text: "\u{1f53a}", name: "up-pointing red triangle", skin_tones: false },
+    CharEntry { text: "\u{1f53b}", name: "down-pointing red triangle", skin_tones: false },
+    CharEntry { text: "\u{1f53c}", name: "up-pointing small red triangle", skin_tones: false },
+    CharEntry { text: "\u{1f53d}", name: "down-pointing small red triangle", skin_tones: false },
+    CharEntry { text: "\u{1f549}\u{fe0f}", name: "om symbol", skin_tones: false },
+    CharEntry { text: "\u{1f54a}\u{fe0f}", name: "dove of peace", skin_tones: false },
+    CharEntry { text: "\u{1f54b}", name: "kaaba", skin_tones: false },
+    CharEntry { text: "\u{1f54c}", name: "mosque", skin_tones: false },
+    CharEntry { text: "\u{1f54d}", name: "synagogue", skin_tones: false },
+    CharEntry { text: "\u{1f54e}", name: "menorah with nine branches", skin_tones: false },
+    CharEntry { text: "\u{1f550}", name: "clock face one oclock", skin_tones: false },
+    CharEntry { text: "\u{1f551}", name: "clock face two oclock", skin_tones: false },
+    CharEntry { text: "\u{1f552}", name: "clock face three oclock", skin_tones: false },
+    CharEntry { text: "\u{1f553}", name: "clock face four oclock", skin_tones: false },
+    CharEntry { text: "\u{1f554}", name: "clock face five oclock", skin_tones: false },
+    CharEntry { text: "\u{1f555}", name: "clock face six oclock", skin_tones: false },
+    CharEntry { text: "\u{1f556}", name: "clock face seven oclock", skin_tones: false },
+    CharEntry { text: "\u{1f557}", name: "clock face eight oclock", skin_tones: false },
+    CharEntry { text: "\u{1f558}", name: "clock face nine oclock", skin_tones: false },
+    CharEntry { text: "\u{1f559}", name: "clock face ten oclock", skin_tones: false },
+    CharEntry { text: "\u{1f55a}", name: "clock face eleven oclock", skin_tones: false },
+    CharEntry { text: "\u{1f55b}", name: "clock face twelve oclock", skin_tones: false },
+    CharEntry { text: "\u{1f55c}", name: "clock face one-thirty", skin_tones: false },
+    CharEntry { text: "\u{1f55d}", name: "clock face two-thirty", skin_tones: false },
+    CharEntry { text: "\u{1f55e}", name: "clock face three-thirty", skin_tones: false },
+    CharEntry { text: "\u{1f55f}", name: "clock face four-thirty", skin_tones: false },
+    CharEntry { text: "\u{1f560}", name: "clock face five-thirty", skin_tones: false },
+    CharEntry { text: "\u{1f561}", name: "clock face six-thirty", skin_tones: false },
+    CharEntry { text: "\u{1f562}", name: "clock face seven-thirty", skin_tones: false },
+    CharEntry { text: "\u{1f563}", name: "clock face eight-thirty", skin_tones: false },
+    CharEntry { text: "\u{1f564}", name: "clock face nine-thirty", skin_tones: false },
+    CharEntry { text: "\u{1f565}", name: "clock face ten-thirty", skin_tones: false },
+    CharEntry { text: "\u{1f566}", name: "clock face eleven-thirty", skin_tones: false },
+    CharEntry { text: "\u{1f567}", name: "clock face twelve-thirty", skin_tones: false },
+    CharEntry { text: "\u{1f56f}\u{fe0f}", name: "candle", skin_tones: false },
+    CharEntry { text: "\u{1f570}\u{fe0f}", name: "mantelpiece clock", skin_tones: false },
+    CharEntry { text: "\u{1f573}\u{fe0f}", name: "hole", skin_tones: false },
+    CharEntry { text: "\u{1f574}\u{fe0f}", name: "man in business suit levitating", skin_tones: true },
+    CharEntry { text: "\u{1f575}\u{fe0f}", name: "sleuth or spy", skin_tones: true },
+    CharEntry { text: "\u{1f576}\u{fe0f}", name: "dark sunglasses", skin_tones: false },
+    CharEntry { text: "\u{1f577}\u{fe0f}", name: "spider", skin_tones: false },
+    CharEntry { text: "\u{1f578}\u{fe0f}", name: "spider web", skin_tones: false },
+    CharEntry { text: "\u{1f579}\u{fe0f}", name: "joystick", skin_tones: false },
+    CharEntry { text: "\u{1f57a}", name: "man dancing", skin_tones: true },
+    CharEntry { text: "\u{1f587}\u{fe0f}", name: "linked paperclips", skin_tones: false },
+    CharEntry { text: "\u{1f58a}\u{fe0f}", name: "lower left ballpoint pen", skin_tones: false },
+    CharEntry { text: "\u{1f58b}\u{fe0f}", name: "lower left fountain pen", skin_tones: false },
+    CharEntry { text: "\u{1f58c}\u{fe0f}", name: "lower left paintbrush", skin_tones: false },
+    CharEntry { text: "\u{1f58d}\u{fe0f}", name: "lower left crayon", skin_tones: false },
+    CharEntry { text: "\u{1f590}\u{fe0f}", name: "raised hand with fingers splayed", skin_tones: true },
+    CharEntry { text: "\u{1f595}", name: "reversed hand with middle finger extended", skin_tones: true },
+    CharEntry { text: "\u{1f596}", name: "raised hand with part between middle and ring fingers", skin_tones: true },
+    CharEntry { text: "\u{1f5a4}", name: "black heart", skin_tones: false },
+    CharEntry { text: "\u{1f5a5}\u{fe0f}", name: "desktop computer", skin_tones: false },
+    CharEntry { text: "\u{1f5a8}\u{fe0f}", name: "printer", skin_tones: false },
+    CharEntry { text: "\u{1f5b1}\u{fe0f}", name: "three button mouse", skin_tones: false },
+    CharEntry { text: "\u{1f5b2}\u{fe0f}", name: "trackball", skin_tones: false },
+    CharEntry { text: "\u{1f5bc}\u{fe0f}", name: "frame with picture", skin_tones: false },
+    CharEntry { text: "\u{1f5c2}\u{fe0f}", name: "card index dividers", skin_tones: false },
+    CharEntry { text: "\u{1f5c3}\u{fe0f}", name: "card file box", skin_tones: false },
+    CharEntry { text: "\u{1f5c4}\u{fe0f}", name: "file cabinet", skin_tones: false },
+    CharEntry { text: "\u{1f5d1}\u{fe0f}", name: "wastebasket", skin_tones: false },
+    CharEntry { text: "\u{1f5d2}\u{fe0f}", name: "spiral note pad", skin_tones: false },
+    CharEntry { text: "\u{1f5d3}\u{fe0f}", name: "spiral calendar pad", skin_tones: false },
+    CharEntry { text: "\u{1f5dc}\u{fe0f}", name: "compression", skin_tones: false },
+    CharEntry { text: "\u{1f5dd}\u{fe0f}", name: "old key", skin_tones: false },
+    CharEntry { text: "\u{1f5de}\u{fe0f}", name: "rolled-up newspaper", skin_tones: false },
+    CharEntry { text: "\u{1f5e1}\u{fe0f}", name: "dagger knife", skin_tones: false },
+    CharEntry { text: "\u{1f5e3}\u{fe0f}", name: "speaking head in silhouette", skin_tones: false },
+    CharEntry { text: "\u{1f5e8}\u{fe0f}", name: "left speech bubble", skin_tones: false },
+    CharEntry { text: "\u{1f5ef}\u{fe0f}", name: "right anger bubble", skin_tones: false },
+    CharEntry { text: "\u{1f5f3}\u{fe0f}", name: "ballot box with ballot", skin_tones: false },
+    CharEntry { text: "\u{1f5fa}\u{fe0f}", name: "world map", skin_tones: false },
+    CharEntry { text: "\u{1f5fb}", name: "mount fuji", skin_tones: false },
+    CharEntry { text: "\u{1f5fc}", name: "tokyo tower", skin_tones: false },
+    CharEntry { text: "\u{1f5fd}", name: "statue of liberty", skin_tones: false },
+    CharEntry { text: "\u{1f5fe}", name: "silhouette of japan", skin_tones: false },
+    CharEntry { text: "\u{1f5ff}", name: "moyai", skin_tones: false },
+    CharEntry { text: "\u{1f600}", name: "grinning face", skin_tones: false },
+    CharEntry { text: "\u{1f601}", name: "grinning face with smiling eyes", skin_tones: false },
+    CharEntry { text: "\u{1f602}", name: "face with tears of joy", skin_tones: false },
+    CharEntry { text: "\u{1f603}", name: "smiling face with open mouth", skin_tones: false },
+    CharEntry { text: "\u{1f604}", name: "smiling face with open mouth and smiling eyes", skin_tones: false },
+    CharEntry { text: "\u{1f605}", name: "smiling face with open mouth and cold sweat", skin_tones: false },
+    CharEntry { text: "\u{1f606}", name: "smiling face with open mouth and tightly-closed eyes", skin_tones: false },
+    CharEntry { text: "\u{1f607}", name: "smiling face with halo", skin_tones: false },
+    CharEntry { text: "\u{1f608}", name: "smiling face with horns", skin_tones: false },
+    CharEntry { text: "\u{1f609}", name: "winking face", skin_tones: false },
+    CharEntry { text: "\u{1f60a}", name: "smiling face with smiling eyes", skin_tones: false },
+    CharEntry { text: "\u{1f60b}", name: "face savouring delicious food", skin_tones: false },
+    CharEntry { text: "\u{1f60c}", name: "relieved face", skin_tones: false },
+    CharEntry { text: "\u{1f60d}", name: "smiling face with heart-shaped eyes", skin_tones: false },
+    CharEntry { text: "\u{1f60e}", name: "smiling face with sunglasses", skin_tones: false },
+    CharEntry { text: "\u{1f60f}", name: "smirking face", skin_tones: false },
+    CharEntry { text: "\u{1f610}", name: "neutral face", skin_tones: false },
+    CharEntry { text: "\u{1f611}", name: "expressionless face", skin_tones: false },
+    CharEntry { text: "\u{1f612}", name: "unamused face", skin_tones: false },
+    CharEntry { text: "\u{1f613}", name: "face with cold sweat", skin_tones: false },
+    CharEntry { text: "\u{1f614}", name: "pensive face", skin_tones: false },
+    CharEntry { text: "\u{1f615}", name: "confused face", skin_tones: false },
+    CharEntry { text: "\u{1f616}", name: "confounded face", skin_tones: false },
+    CharEntry { text: "\u{1f617}", name: "kissing face", skin_tones: false },
+    CharEntry { text: "\u{1f618}", name: "face throwing a kiss", skin_tones: false },
+    CharEntry { text: "\u{1f619}", name: "kissing face with smiling eyes", skin_tones: false },
+    CharEntry { text: "\u{1f61a}", name: "kissing face with closed eyes", skin_tones: false },
+    CharEntry { text: "\u{1f61b}", name: "face with stuck-out tongue", skin_tones: false },
+    CharEntry { text: "\u{1f61c}", name: "face with stuck-out tongue and winking eye", skin_tones: false },
+    CharEntry { text: "\u{1f61d}", name: "face with stuck-out tongue and tightly-closed eyes", skin_tones: false },
+    CharEntry { text: "\u{1f61e}", name: "disappointed face", skin_tones: false },
+    CharEntry { text: "\u{1f61f}", name: "worried face", skin_tones: false },
+    CharEntry { text: "\u{1f620}", name: "angry face", skin_tones: false },
+    CharEntry { text: "\u{1f621}", name: "pouting face", skin_tones: false },
+    CharEntry { text: "\u{1f622}", name: "crying face", skin_tones: false },
+    CharEntry { text: "\u{1f623}", name: "persevering face", skin_tones: false },
+    CharEntry { text: "\u{1f624}", name: "face with look of triumph", skin_tones: false },
+    CharEntry { text: "\u{1f625}", name: "disappointed but relieved face", skin_tones: false },
+    CharEntry { text: "\u{1f626}", name: "frowning face with open mouth", skin_tones: false },
+    CharEntry { text: "\u{1f627}", name: "anguished face", skin_tones: false },
+    CharEntry { text: "\u{1f628}", name: "fearful face", skin_tones: false },
+    CharEntry { text: "\u{1f629}", name: "weary face", skin_tones: false },
+    CharEntry { text: "\u{1f62a}", name: "sleepy face", skin_tones: false },
+    CharEntry { text: "\u{1f62b}", name: "tired face", skin_tones: false },
+    CharEntry { text: "\u{1f62c}", name: "grimacing face", skin_tones: false },
+    CharEntry { text: "\u{1f62d}", name: "loudly crying face", skin_tones: false },
+    CharEntry { text: "\u{1f62e}", name: "face with open mouth", skin_tones: false },
+    CharEntry { text: "\u{1f62f}", name: "hushed face", skin_tones: false },
+    CharEntry { text: "\u{1f630}", name: "face with open mouth and cold sweat", skin_tones: false },
+    CharEntry { text: "\u{1f631}", name: "face screaming in fear", skin_tones: false },
+    CharEntry { text: "\u{1f632}", name: "astonished face", skin_tones: false },
+    CharEntry { text: "\u{1f633}", name: "flushed face", skin_tones: false },
+    CharEntry { text: "\u{1f634}", name: "sleeping face", skin_tones: false },
+    CharEntry { text: "\u{1f635}", name: "dizzy face", skin_tones: false },
+    CharEntry { text: "\u{1f636}", name: "face without mouth", skin_tones: false },
+    CharEntry { text: "\u{1f637}", name: "face with medical mask", skin_tones: false },
+    CharEntry { text: "\u{1f638}", name: "grinning cat face with smiling eyes", skin_tones: false },
+    CharEntry { text: "\u{1f639}", name: "cat face with tears of joy", skin_tones: false },
+    CharEntry { text: "\u{1f63a}", name: "smiling cat face with open mouth", skin_tones: false },
+    CharEntry { text: "\u{1f63b}", name: "smiling cat face with heart-shaped eyes", skin_tones: false },
+    CharEntry { text: "\u{1f63c}", name: "cat face with wry smile", skin_tones: false },
+    CharEntry { text: "\u{1f63d}", name: "kissing cat face with closed eyes", skin_tones: false },
+    CharEntry { text: "\u{1f63e}", name: "pouting cat face", skin_tones: false },
+    CharEntry { text: "\u{1f63f}", name: "crying cat face", skin_tones: false },
+    CharEntry { text: "\u{1f640}", name: "weary cat face", skin_tones: false },
+    CharEntry { text: "\u{1f641}", name: "slightly frowning face", skin_tones: false },
+    CharEntry { text: "\u{1f642}", name: "slightly smiling face", skin_tones: false },
+    CharEntry { text: "\u{1f643}", name: "upside-down face", skin_tones: false },
+    CharEntry { text: "\u{1f644}", name: "face with rolling eyes", skin_tones: false },
+    CharEntry { text: "\u{1f645}", name: "face with no good gesture", skin_tones: true },
+    CharEntry { text: "\u{1f646}", name: "face with ok gesture", skin_tones: true },
+    CharEntry { text: "\u{1f647}", name: "person bowing deeply", skin_tones: true },
+    CharEntry { text: "\u{1f648}", name: "see-no-evil monkey", skin_tones: false },
+    CharEntry { text: "\u{1f649}", name: "hear-no-evil monkey", skin_tones: false },
+    CharEntry { text: "\u{1f64a}", name: "speak-no-evil monkey", skin_tones: false },
+    CharEntry { text: "\u{1f64b}", name: "happy person raising one hand", skin_tones: true },
+    CharEntry { text: "\u{1f64c}", name: "person raising both hands in celebration", skin_tones: true },
+    CharEntry { text: "\u{1f64d}", name: "person frowning", skin_tones: true },
+    CharEntry { text: "\u{1f64e}", name: "person with pouting face", skin_tones: true },
+    CharEntry { text: "\u{1f64f}", name: "person with folded hands", skin_tones: true },
+    CharEntry { text: "\u{1f680}", name: "rocket", skin_tones: false },
+    CharEntry { text: "\u{1f681}", name: "helicopter", skin_tones: false },
+    CharEntry { text: "\u{1f682}", name: "steam locomotive", skin_tones: false },
+    CharEntry { text: "\u{1f683}", name: "railway car", skin_tones: false },
+    CharEntry { text: "\u{1f684}", name: "high-speed train", skin_tones: false },
+    CharEntry { text: "\u{1f685}", name: "high-speed train with bullet nose", skin_tones: false },
+    CharEntry { text: "\u{1f686}", name: "train", skin_tones: false },
+    CharEntry { text: "\u{1f687}", name: "metro", skin_tones: false },
+    CharEntry { text: "\u{1f688}", name: "light rail", skin_tones: false },
+    CharEntry { text: "\u{1f689}", name: "station", skin_tones: false },
+    CharEntry { text: "\u{1f68a}", name: "tram", skin_tones: false },
+    CharEntry { text: "\u{1f68b}", name: "tram car", skin_tones: false },
+    CharEntry { text: "\u{1f68c}", name: "bus", skin_tones: false },
+    CharEntry { text: "\u{1f68d}", name: "oncoming bus", skin_tones: false },
+    CharEntry { text: "\u{1f68e}", name: "trolleybus", skin_tones: false },
+    CharEntry { text: "\u{1f68f}", name: "bus stop", skin_tones: false },
+    CharEntry { text: "\u{1f690}", name: "minibus", skin_tones: false },
+    CharEntry { text: "\u{1f691}", name: "ambulance", skin_tones: false },
+    CharEntry { text: "\u{1f692}", name: "fire engine", skin_tones: false },
+    CharEntry { text: "\u{1f693}", name: "police car", skin_tones: false },
+    CharEntry { text: "\u{1f694}", name: "oncoming police car", skin_tones: false },
+    CharEntry { text: "\u{1f695}", name: "taxi", skin_tones: false },
+    CharEntry { text: "\u{1f696}", name: "oncoming taxi", skin_tones: false },
+    CharEntry { text: "\u{1f697}", name: "automobile", skin_tones: false },
+    CharEntry { text: "\u{1f698}", name: "oncoming automobile", skin_tones: false },
+    CharEntry { text: "\u{1f699}", name: "recreational vehicle", skin_tones: false },
+    CharEntry { text: "\u{1f69a}", name: "delivery truck", skin_tones: false },
+    CharEntry { text: "\u{1f69b}", name: "articulated lorry", skin_tones: false },
+    CharEntry { text: "\u{1f69c}", name: "tractor", skin_tones: false },
+    CharEntry { text: "\u{1f69d}", name: "monorail", skin_tones: false },
+    CharEntry { text: "\u{1f69e}", name: "mountain railway", skin_tones: false },
+    CharEntry { text: "\u{1f69f}", name: "suspension railway", skin_tones: false },
+    CharEntry { text: "\u{1f6a0}", name: "mountain cableway", skin_tones: false },
+    CharEntry { text: "\u{1f6a1}", name: "aerial tramway", skin_tones: false },
+    CharEntry { text: "\u{1f6a2}", name: "ship", skin_tones: false },
+    CharEntry { text: "\u{1f6a3}", name: "rowboat", skin_tones: true },
+    CharEntry { text: "\u{1f6a4}", name: "speedboat", skin_tones: false },
+    CharEntry { text: "\u{1f6a5}", name: "horizontal traffic light", skin_tones: false },
+    CharEntry { text: "\u{1f6a6}", name: "vertical traffic light", skin_tones: false },
+    CharEntry { text: "\u{1f6a7}", name: "construction sign", skin_tones: false },
+    CharEntry { text: "\u{1f6a8}", name: "police cars revolving light", skin_tones: false },
+    CharEntry { text: "\u{1f6a9}", name: "triangular flag on post", skin_tones: false },
+    CharEntry { text: "\u{1f6aa}", name: "door", skin_tones: false },
+    CharEntry { text: "\u{1f6ab}", name: "no entry sign", skin_tones: false },
+    CharEntry { text: "\u{1f6ac}", name: "smoking symbol", skin_tones: false },
+    CharEntry { text: "\u{1f6ad}", name: "no smoking symbol", skin_tones: false },
+    CharEntry { text: "\u{1f6ae}", name: "put litter in its place symbol", skin_tones: false },
+    CharEntry { text: "\u{1f6af}", name: "do not litter symbol", skin_tones: false },
+    CharEntry { text: "\u{1f6b0}", name: "potable water symbol", skin_tones: false },
+    CharEntry { text: "\u{1f6b1}", name: "non-potable water symbol", skin_tones: false },
+    CharEntry { text: "\u{1f6b2}", name: "bicycle", skin_tones: false },
+    CharEntry { text: "\u{1f6b3}", name: "no bicycles", skin_tones: false },
+    CharEntry { text: "\u{1f6b4}", name: "bicyclist", skin_tones: true },
+    CharEntry { text: "\u{1f6b5}", name: "mountain bicyclist", skin_tones: true },
+    CharEntry { text: "\u{1f6b6}", name: "pedestrian", skin_tones: true },
+    CharEntry { text: "\u{1f6b7}", name: "no pedestrians", skin_tones: false },
+    CharEntry { text: "\u{1f6b8}", name: "children crossing", skin_tones: false },
+    CharEntry { text: "\u{1f6b9}", name: "mens symbol", skin_tones: false },
+    CharEntry { text: "\u{1f6ba}", name: "womens symbol", skin_tones: false },
+    CharEntry { text: "\u{1f6bb}", name: "restroom", skin_tones: false },
+    CharEntry { text: "\u{1f6bc}", name: "baby symbol", skin_tones: false },
+    CharEntry { text: "\u{1f6bd}", name: "toilet", skin_tones: false },
+    CharEntry { text: "\u{1f6be}", name: "water closet", skin_tones: false },
+    CharEntry { text: "\u{1f6bf}", name: "shower", skin_tones: false },
+    CharEntry { text: "\u{1f6c0}", name: "bath", skin_tones: true },
+    CharEntry { text: "\u{1f6c1}", name: "bathtub", skin_tones: false },
+    CharEntry { text: "\u{1f6c2}", name: "passport control", skin_tones: false },
+    CharEntry { text: "\u{1f6c3}", name: "customs", skin_tones: false },
+    CharEntry { text: "\u{1f6c4}", name: "baggage claim", skin_tones: false },
+    CharEntry { text: "\u{1f6c5}", name: "left luggage", skin_tones: false },
+    CharEntry { text: "\u{1f6cb}\u{fe0f}", name: "couch and lamp", skin_tones: false },
+    CharEntry { text: "\u{1f6cc}", name: "sleeping accommodation", skin_tones: true },
+    CharEntry { text: "\u{1f6cd}\u{fe0f}", name: "shopping bags", skin_tones: false },
+    CharEntry { text: "\u{1f6ce}\u{fe0f}", name: "bellhop bell", skin_tones: false },
+    CharEntry { text: "\u{1f6cf}\u{fe0f}", name: "bed", skin_tones: false },
+    CharEntry { text: "\u{1f6d0}", name: "place of worship", skin_tones: false },
+    CharEntry { text: "\u{1f6d1}", name: "octagonal sign", skin_tones: false },
+    CharEntry { text: "\u{1f6d2}", name: "shopping trolley", skin_tones: false },
+    CharEntry { text: "\u{1f6d5}", name: "hindu temple", skin_tones: false },
+    CharEntry { text: "\u{1f6d6}", name: "hut", skin_tones: false },
+    CharEntry { text: "\u{1f6d7}", name: "elevator", skin_tones: false },
+    CharEntry { text: "\u{1f6e0}\u{fe0f}", name: "hammer and wrench", skin_tones: false },
+    CharEntry { text: "\u{1f6e1}\u{fe0f}", name: "shield", skin_tones: false },
+    CharEntry { text: "\u{1f6e2}\u{fe0f}", name: "oil drum", skin_tones: false },
+    CharEntry { text: "\u{1f6e3}\u{fe0f}", name: "motorway", skin_tones: false },
+    CharEntry { text: "\u{1f6e4}\u{fe0f}", name: "railway track", skin_tones: false },
+    CharEntry { text: "\u{1f6e5}\u{fe0f}", name: "motor boat", skin_tones: false },
+    CharEntry { text: "\u{1f6e9}\u{fe0f}", name: "small airplane", skin_tones: false },
+    CharEntry { text: "\u{1f6eb}", name: "airplane departure", skin_tones: false },
+    CharEntry { text: "\u{1f6ec}", name: "airplane arriving", skin_tones: false },
+    CharEntry { text: "\u{1f6f0}\u{fe0f}", name: "satellite", skin_tones: false },
+    CharEntry { text: "\u{1f6f3}\u{fe0f}", name: "passenger ship", skin_tones: false },
+    CharEntry { text: "\u{1f6f4}", name: "scooter", skin_tones: false },
+    CharEntry { text: "\u{1f6f5}", name: "motor scooter", skin_tones: false },
+    CharEntry { text: "\u{1f6f6}", name: "canoe", skin_tones: false },
+    CharEntry { text: "\u{1f6f7}", name: "sled", skin_tones: false },
+    CharEntry { text: "\u{1f6f8}", name: "flying saucer", skin_tones: false },
+    CharEntry { text: "\u{1f6f9}", name: "skateboard", skin_tones: false },
+    CharEntry { text: "\u{1f6fa}", name: "auto rickshaw", skin_tones: false },
+    CharEntry { text: "\u{1f6fb}", name: "pickup truck", skin_tones: false },
+    CharEntry { text: "\u{1f6fc}", name: "roller skate", skin_tones: false },
+    CharEntry { text: "\u{1f7e0}", name: "large orange circle", skin_tones: false },
+    CharEntry { text: "\u{1f7e1}", name: "large yellow circle", skin_tones: false },
+    CharEntry { text: "\u{1f7e2}", name: "large green circle", skin_tones: false },
+    CharEntry { text: "\u{1f7e3}", name: "large purple circle", skin_tones: false },
+    CharEntry { text: "\u{1f7e4}", name: "large brown circle", skin_tones: false },
+    CharEntry { text: "\u{1f7e5}", name: "large red square", skin_tones: false },
+    CharEntry { text: "\u{1f7e6}", name: "large blue square", skin_tones: false },
+    CharEntry { text: "\u{1f7e7}", name: "large orange square", skin_tones: false },
+    CharEntry { text: "\u{1f7e8}", name: "large yellow square", skin_tones: false },
+    CharEntry { text: "\u{1f7e9}", name: "large green square", skin_tones: false },
+    CharEntry { text: "\u{1f7ea}", name: "large purple square", skin_tones: false },
+    CharEntry { text: "\u{1f7eb}", name: "large brown square", skin_tones: false },
+    CharEntry { text: "\u{1f90c}", name: "pinched fingers", skin_tones: true },
+    CharEntry { text: "\u{1f90d}", name: "white heart", skin_tones: false },
+    CharEntry { text: "\u{1f90e}", name: "brown heart", skin_tones: false },
+    CharEntry { text: "\u{1f90f}", name: "pinching hand", skin_tones: true },
+    CharEntry { text: "\u{1f910}", name: "zipper-mouth face", skin_tones: false },
+    CharEntry { text: "\u{1f911}", name: "money-mouth face", skin_tones: false },
+    CharEntry { text: "\u{1f912}", name: "face with thermometer", skin_tones: false },
+    CharEntry { text: "\u{1f913}", name: "nerd face", skin_tones: false },
+    CharEntry { text: "\u{1f914}", name: "thinking face", skin_tones: false },
+    CharEntry { text: "\u{1f915}", name: "face with head-bandage", skin_tones: false },
+    CharEntry { text: "\u{1f916}", name: "robot face", skin_tones: false },
+    CharEntry { text: "\u{1f917}", name: "hugging face", skin_tones: false },
+    CharEntry { text: "\u{1f918}", name: "sign of the horns", skin_tones: true },
+    CharEntry { text: "\u{1f919}", name: "call me hand", skin_tones: true },
+    CharEntry { text: "\u{1f91a}", name: "raised back of hand", skin_tones: true },
+    CharEntry { text: "\u{1f91b}", name: "left-facing fist", skin_tones: true },
+    CharEntry { text: "\u{1f91c}", name: "right-facing fist", skin_tones: true },
+    CharEntry { text: "\u{1f91d}", name: "handshake", skin_tones: true },
+    CharEntry { text: "\u{1f91e}", name: "hand with index and middle fingers crossed", skin_tones: true },
+    CharEntry { text: "\u{1f91f}", name: "i love you hand sign", skin_tones: true },
+    CharEntry { text: "\u{1f920}", name: "face with cowboy hat", skin_tones: false },
+    CharEntry { text: "\u{1f921}", name: "clown face", skin_tones: false },
+    CharEntry { text: "\u{1f922}", name: "nauseated face", skin_tones: false },
+    CharEntry { text: "\u{1f923}", name: "rolling on the floor laughing", skin_tones: false },
+    CharEntry { text: "\u{1f924}", name: "drooling face", skin_tones: false },
+    CharEntry { text: "\u{1f925}", name: "lying face", skin_tones: false },
+    CharEntry { text: "\u{1f926}", name: "face palm", skin_tones: true },
+    CharEntry { text: "\u{1f927}", name: "sneezing face", skin_tones: false },
+    CharEntry { text: "\u{1f928}", name: "face with one eyebrow raised", skin_tones: false },
+    CharEntry { text: "\u{1f929}", name: "grinning face with star eyes", skin_tones: false },
+    CharEntry { text: "\u{1f92a}", name: "grinning face with one large and one small eye", skin_tones: false },
+    CharEntry { text: "\u{1f92b}", name: "face with finger covering closed lips", skin_tones: false },
+    CharEntry { text: "\u{1f92c}", name: "serious face with symbols covering mouth", skin_tones: false },
+    CharEntry { text: "\u{1f92d}", name: "smiling face with smiling eyes and hand covering mouth", skin_tones: false },
+    CharEntry { text: "\u{1f92e}", name: "face with open mouth vomiting", skin_tones: false },
+    CharEntry { text: "\u{1f92f}", name: "shocked face with exploding head", skin_tones: false },
+    CharEntry { text: "\u{1f930}", name: "pregnant woman", skin_tones: true },
+    CharEntry { text: "\u{1f931}", name: "breast-feeding", skin_tones: true },
+    CharEntry { text: "\u{1f932}", name: "palms up together", skin_tones: true },
+    CharEntry { text: "\u{1f933}", name: "selfie", skin_tones: true },
+    CharEntry { text: "\u{1f934}", name: "prince", skin_tones: true },
+    CharEntry { text: "\u{1f935}", name: "man in tuxedo", skin_tones: true },
+    CharEntry { text: "\u{1f936}", name: "mother christmas", skin_tones: true },
+    CharEntry { text: "\u{1f937}", name: "shrug", skin_tones: true },
+    CharEntry { text: "\u{1f938}", name: "person doing cartwheel", skin_tones: true },
+    CharEntry { text: "\u{1f939}", name: "juggling", skin_tones: true },
+    CharEntry { text: "\u{1f93a}", name: "fencer", skin_tones: false },
+    CharEntry { text: "\u{1f93c}", name: "wrestlers", skin_tones: true },
+    CharEntry { text: "\u{1f93d}", name: "water polo", skin_tones: true },
+    CharEntry { text: "\u{1f93e}", name: "handball", skin_tones: true },
+    CharEntry { text: "\u{1f93f}", name: "diving mask", skin_tones: false },
+    CharEntry { text: "\u{1f940}", name: "wilted flower", skin_tones: false },
+    CharEntry { text: "\u{1f941}", name: "drum with drumsticks", skin_tones: false },
+    CharEntry { text: "\u{1f942}", name: "clinking glasses", skin_tones: false },
+    CharEntry { text: "\u{1f943}", name: "tumbler glass", skin_tones: false },
+    CharEntry { text: "\u{1f944}", name: "spoon", skin_tones: false },
+    CharEntry { text: "\u{1f945}", name: "goal net", skin_tones: false },
+    CharEntry { text: "\u{1f947}", name: "first place medal", skin_tones: false },
+    CharEntry { text: "\u{1f948}", name: "second place medal", skin_tones: false },
+    CharEntry { text: "\u{1f949}", name: "third place medal", skin_tones: false },
+    CharEntry { text: "\u{1f94a}", name: "boxing glove", skin_tones: false },
+    CharEntry { text: "\u{1f94b}", name: "martial arts uniform", skin_tones: false },
+    CharEntry { text: "\u{1f94c}", name: "curling stone", skin_tones: false },
+    CharEntry { text: "\u{1f94d}", name: "lacrosse stick and ball", skin_tones: false },
+    CharEntry { text: "\u{1f94e}", name: "softball", skin_tones: false },
+    CharEntry { text: "\u{1f94f}", name: "flying disc", skin_tones: false },
+    CharEntry { text: "\u{1f950}", name: "croissant", skin_tones: false },
+    CharEntry { text: "\u{1f951}", name: "avocado", skin_tones: false },
+    CharEntry { text: "\u{1f952}", name: "cucumber", skin_tones: false },
+    CharEntry { text: "\u{1f953}", name: "bacon", skin_tones: false },
+    CharEntry { text: "\u{1f954}", name: "potato", skin_tones: false },
+    CharEntry { text: "\u{1f955}", name: "carrot", skin_tones: false },
+    CharEntry { text: "\u{1f956}", name: "baguette bread", skin_tones: false },
+    CharEntry { text: "\u{1f957}", name: "green salad", skin_tones: false },
+    CharEntry { text: "\u{1f958}", name: "shallow pan of food", skin_tones: false },
+    CharEntry { text: "\u{1f959}", name: "stuffed flatbread", skin_tones: false },
+    CharEntry { text: "\u{1f95a}", name: "egg", skin_tones: false },
+    CharEntry { text: "\u{1f95b}", name: "glass of milk", skin_tones: false },
+    CharEntry { text: "\u{1f95c}", name: "peanuts", skin_tones: false },
+    CharEntry { text: "\u{1f95d}", name: "kiwifruit", skin_tones: false },
+    CharEntry { text: "\u{1f95e}", name: "pancakes", skin_tones: false },
+    CharEntry { text: "\u{1f95f}", name: "dumpling", skin_tones: false },
+    CharEntry { text: "\u{1f960}", name: "fortune cookie", skin_tones: false },
+    CharEntry { text: "\u{1f961}", name: "takeout box", skin_tones: false },
+    CharEntry { text: "\u{1f962}", name: "chopsticks", skin_tones: false },
+    CharEntry { text: "\u{1f963}", name: "bowl with spoon", skin_tones: false },
+    CharEntry { text: "\u{1f964}", name: "cup with straw", skin_tones: false },
+    CharEntry { text: "\u{1f965}", name: "coconut", skin_tones: false },
+    CharEntry { text: "\u{1f966}", name: "broccoli", skin_tones: false },
+    CharEntry { text: "\u{1f967}", name: "pie", skin_tones: false },
+    CharEntry { text: "\u{1f968}", name: "pretzel", skin_tones: false },
+    CharEntry { text: "\u{1f969}", name: "cut of meat", skin_tones: false },
+    CharEntry { text: "\u{1f96a}", name: "sandwich", skin_tones: false },
+    CharEntry { text: "\u{1f96b}", name: "canned food", skin_tones: false },
+    CharEntry { text: "\u{1f96c}", name: "leafy green", skin_tones: false },
+    CharEntry { text: "\u{1f96d}", name: "mango", skin_tones: false },
+    CharEntry { text: "\u{1f96e}", name: "moon cake", skin_tones: false },
+    CharEntry { text: "\u{1f96f}", name: "bagel", skin_tones: false },
+    CharEntry { text: "\u{1f970}", name: "smiling face with smiling eyes and three hearts", skin_tones: false },
+    CharEntry { text: "\u{1f971}", name: "yawning face", skin_tones: false },
+    CharEntry { text: "\u{1f972}", name: "smiling face with tear", skin_tones: false },
+    CharEntry { text: "\u{1f973}", name: "face with party horn and party hat", skin_tones: false },
+    CharEntry { text: "\u{1f974}", name: "face with uneven eyes and wavy mouth", skin_tones: false },
+    CharEntry { text: "\u{1f975}", name: "overheated face", skin_tones: false },
+    CharEntry { text: "\u{1f976}", name: "freezing face", skin_tones: false },
+    CharEntry { text: "\u{1f977}", name: "ninja", skin_tones: true },
+    CharEntry { text: "\u{1f978}", name: "disguised face", skin_tones: false },
+    CharEntry { text: "\u{1f97a}", name: "face with pleading eyes", skin_tones: false },
+    CharEntry { text: "\u{1f97b}", name: "sari", skin_tones: false },
+    CharEntry { text: "\u{1f97c}", name: "lab coat", skin_tones: false },
+    CharEntry { text: "\u{1f97d}", name: "goggles", skin_tones: false },
+    CharEntry { text: "\u{1f97e}", name: "hiking boot", skin_tones: false },
+    CharEntry { text: "\u{1f97f}", name: "flat shoe", skin_tones: false },
+    CharEntry { text: "\u{1f980}", name: "crab", skin_tones: false },
+    CharEntry { text: "\u{1f981}", name: "lion face", skin_tones: false },
+    CharEntry { text: "\u{1f982}", name: "scorpion", skin_tones: false },
+    CharEntry { text: "\u{1f983}", name: "turkey", skin_tones: false },
+    CharEntry { text: "\u{1f984}", name: "unicorn face", skin_tones: false },
+    CharEntry { text: "\u{1f985}", name: "eagle", skin_tones: false },
+    CharEntry { text: "\u{1f986}", name: "duck", skin_tones: false },
+    CharEntry { text: "\u{1f987}", name: "bat", skin_tones: false },
+    CharEntry { text: "\u{1f988}", name: "shark", skin_tones: false },
+    CharEntry { text: "\u{1f989}", name: "owl", skin_tones: false },
+    CharEntry { text: "\u{1f98a}", name: "fox face", skin_tones: false },
+    CharEntry { text: "\u{1f98b}", name: "butterfly", skin_tones: false },
+    CharEntry { text: "\u{1f98c}", name: "deer", skin_tones: false },
+    CharEntry { text: "\u{1f98d}", name: "gorilla", skin_tones: false },
+    CharEntry { text: "\u{1f98e}", name: "lizard", skin_tones: false },
+    CharEntry { text: "\u{1f98f}", name: "rhinoceros", skin_tones: false },
+    CharEntry { text: "\u{1f990}", name: "shrimp", skin_tones: false },
+    CharEntry { text: "\u{1f991}", name: "squid", skin_tones: false },
+    CharEntry { text: "\u{1f992}", name: "giraffe face", skin_tones: false },
+    CharEntry { text: "\u{1f993}", name: "zebra face", skin_tones: false },
+    CharEntry { text: "\u{1f994}", name: "hedgehog", skin_tones: false },
+    CharEntry { text: "\u{1f995}", name: "sauropod", skin_tones: false },
+    CharEntry { text: "\u{1f996}", name: "t-rex", skin_tones: false },
+    CharEntry { text: "\u{1f997}", name: "cricket", skin_tones: false },
+    CharEntry { text: "\u{1f998}", name: "kangaroo", skin_tones: false },
+    CharEntry { text: "\u{1f999}", name: "llama", skin_tones: false },
+    CharEntry { text: "\u{1f99a}", name: "peacock", skin_tones: false },
+    CharEntry { text: "\u{1f99b}", name: "hippopotamus", skin_tones: false },
+    CharEntry { text: "\u{1f99c}", name: "parrot", skin_tones: false },
+    CharEntry { text: "\u{1f99d}", name: "raccoon", skin_tones: false },
+    CharEntry { text: "\u{1f99e}", name: "lobster", skin_tones: false },
+    CharEntry { text: "\u{1f99f}", name: "mosquito", skin_tones: false },
+    CharEntry { text: "\u{1f9a0}", name: "microbe", skin_tones: false },
+    CharEntry { text: "\u{1f9a1}", name: "badger", skin_tones: false },
+    CharEntry { text: "\u{1f9a2}", name: "swan", skin_tones: false },
+    CharEntry { text: "\u{1f9a3}", name: "mammoth", skin_tones: false },
+    CharEntry { text: "\u{1f9a4}", name: "dodo", skin_tones: false },
+    CharEntry { text: "\u{1f9a5}", name: "sloth", skin_tones: false },
+    CharEntry { text: "\u{1f9a6}", name: "otter", skin_tones: false },
+    CharEntry { text: "\u{1f9a7}", name: "orangutan", skin_tones: false },
+    CharEntry { text: "\u{1f9a8}", name: "skunk", skin_tones: false },
+    CharEntry { text: "\u{1f9a9}", name: "flamingo", skin_tones: false },
+    CharEntry { text: "\u{1f9aa}", name: "oyster", skin_tones: false },
+    CharEntry { text: "\u{1f9ab}", name: "beaver", skin_tones: false },
+    CharEntry { text: "\u{1f9ac}", name: "bison", skin_tones: false },
+    CharEntry { text: "\u{1f9ad}", name: "seal", skin_tones: false },
+    CharEntry { text: "\u{1f9ae}", name: "guide dog", skin_tones: false },
+    CharEntry { text: "\u{1f9af}", name: "probing cane", skin_tones: false },
+    CharEntry { text: "\u{1f9b4}", name: "bone", skin_tones: false },
+    CharEntry { text: "\u{1f9b5}", name: "leg", skin_tones: true },
+    CharEntry { text: "\u{1f9b6}", name: "foot", skin_tones: true },
+    CharEntry { text: "\u{1f9b7}", name: "tooth", skin_tones: false },
+    CharEntry { text: "\u{1f9b8}", name: "superhero", skin_tones: true },
+    CharEntry { text: "\u{1f9b9}", name: "supervillain", skin_tones: true },
+    CharEntry { text: "\u{1f9ba}", name: "safety vest", skin_tones: false },
+    CharEntry { text: "\u{1f9bb}", name: "ear with hearing aid", skin_tones: true },
+    CharEntry { text: "\u{1f9bc}", name: "motorized wheelchair", skin_tones: false },
+    CharEntry { text: "\u{1f9bd}", name: "manual wheelchair", skin_tones: false },
+    CharEntry { text: "\u{1f9be}", name: "mechanical arm", skin_tones: false },
+    CharEntry { text: "\u{1f9bf}", name: "mechanical leg", skin_tones: false },
+    CharEntry { text: "\u{1f9c0}", name: "cheese wedge", skin_tones: false },
+    CharEntry { text: "\u{1f9c1}", name: "cupcake", skin_tones: false },
+    CharEntry { text: "\u{1f9c2}", name: "salt shaker", skin_tones: false },
+    CharEntry { text: "\u{1f9c3}", name: "beverage box", skin_tones: false },
+    CharEntry { text: "\u{1f9c4}", name: "garlic", skin_tones: false },
+    CharEntry { text: "\u{1f9c5}", name: "onion", skin_tones: false },
+    CharEntry { text: "\u{1f9c6}", name: "falafel", skin_tones: false },
+    CharEntry { text: "\u{1f9c7}", name: "waffle", skin_tones: false },
+    CharEntry { text: "\u{1f9c8}", name: "butter", skin_tones: false },
+    CharEntry { text: "\u{1f9c9}", name: "mate drink", skin_tones: false },
+    CharEntry { text: "\u{1f9ca}", name: "ice cube", skin_tones: false },
+    CharEntry { text: "\u{1f9cb}", name: "bubble tea", skin_tones: false },
+    CharEntry { text: "\u{1f9cd}", name: "standing person", skin_tones: true },
+    CharEntry { text: "\u{1f9ce}", name: "kneeling person", skin_tones: true },
+    CharEntry { text: "\u{1f9cf}", name: "deaf person", skin_tones: true },
+    CharEntry { text: "\u{1f9d0}", name: "face with monocle", skin_tones: false },
+    CharEntry { text: "\u{1f9d1}", name: "adult", skin_tones: true },
+    CharEntry { text: "\u{1f9d2}", name: "child", skin_tones: true },
+    CharEntry { text: "\u{1f9d3}", name: "older adult", skin_tones: true },
+    CharEntry { text: "\u{1f9d4}", name: "bearded person", skin_tones: true },
+    CharEntry { text: "\u{1f9d5}", name: "person with headscarf", skin_tones: true },
+    CharEntry { text: "\u{1f9d6}", name: "person in steamy room", skin_tones: true },
+    CharEntry { text: "\u{1f9d7}", name: "person climbing", skin_tones: true },
+    CharEntry { text: "\u{1f9d8}", name: "person in lotus position", skin_tones: true },
+    CharEntry { text: "\u{1f9d9}", name: "mage", skin_tones: true },
+    CharEntry { text: "\u{1f9da}", name: "fairy", skin_tones: true },
+    CharEntry { text: "\u{1f9db}", name: "vampire", skin_tones: true },
+    CharEntry { text: "\u{1f9dc}", name: "merperson", skin_tones: true },
+    CharEntry { text: "\u{1f9dd}", name: "elf", skin_tones: true },
+    CharEntry { text: "\u{1f9de}", name: "genie", skin_tones: false },
+    CharEntry { text: "\u{1f9df}", name: "zombie", skin_tones: false },
+    CharEntry { text: "\u{1f9e0}", name: "brain", skin_tones: false },
+    CharEntry { text: "\u{1f9e1}", name: "orange heart", skin_tones: false },
+    CharEntry { text: "\u{1f9e2}", name: "billed cap", skin_tones: false },
+    CharEntry { text: "\u{1f9e3}", name: "scarf", skin_tones: false },
+    CharEntry { text: "\u{1f9e4}", name: "gloves", skin_tones: false },
+    CharEntry { text: "\u{1f9e5}", name: "coat", skin_tones: false },
+    CharEntry { text: "\u{1f9e6}", name: "socks", skin_tones: false },
+    CharEntry { text: "\u{1f9e7}", name: "red gift envelope", skin_tones: false },
+    CharEntry { text: "\u{1f9e8}", name: "firecracker", skin_tones: false },
+    CharEntry { text: "\u{1f9e9}", name: "jigsaw puzzle piece", skin_tones: false },
+    CharEntry { text: "\u{1f9ea}", name: "test tube", skin_tones: false },
+    CharEntry { text: "\u{1f9eb}", name: "petri dish", skin_tones: false },
+    CharEntry { text: "\u{1f9ec}", name: "dna double helix", skin_tones: false },
+    CharEntry { text: "\u{1f9ed}", name: "compass", skin_tones: false },
+    CharEntry { text: "\u{1f9ee}", name: "abacus", skin_tones: false },
+    CharEntry { text: "\u{1f9ef}", name: "fire extinguisher", skin_tones: false },
+    CharEntry { text: "\u{1f9f0}", name: "toolbox", skin_tones: false },
+    CharEntry { text: "\u{1f9f1}", name: "brick", skin_tones: false },
+    CharEntry { text: "\u{1f9f2}", name: "magnet", skin_tones: false },
+    CharEntry { text: "\u{1f9f3}", name: "luggage", skin_tones: false },
+    CharEntry { text: "\u{1f9f4}", name: "lotion bottle", skin_tones: false },
+    CharEntry { text: "\u{1f9f5}", name: "spool of thread", skin_tones: false },
+    CharEntry { text: "\u{1f9f6}", name: "ball of yarn", skin_tones: false },
+    CharEntry { text: "\u{1f9f7}", name: "safety pin", skin_tones: false },
+    CharEntry { text: "\u{1f9f8}", name: "teddy bear", skin_tones: false },
+    CharEntry { text: "\u{1f9f9}", name: "broom", skin_tones: false },
+    CharEntry { text: "\u{1f9fa}", name: "basket", skin_tones: false },
+    CharEntry { text: "\u{1f9fb}", name: "roll of paper", skin_tones: false },
+    CharEntry { text: "\u{1f9fc}", name: "bar of soap", skin_tones: false },
+    CharEntry { text: "\u{1f9fd}", name: "sponge", skin_tones: false },
+    CharEntry { text: "\u{1f9fe}", name: "receipt", skin_tones: false },
+    CharEntry { text: "\u{1f9ff}", name: "nazar amulet", skin_tones: false },
+    CharEntry { text: "\u{1fa70}", name: "ballet shoes", skin_tones: false },
+    CharEntry { text: "\u{1fa71}", name: "one-piece swimsuit", skin_tones: false },
+    CharEntry { text: "\u{1fa72}", name: "briefs", skin_tones: false },
+    CharEntry { text: "\u{1fa73}", name: "shorts", skin_tones: false },
+    CharEntry { text: "\u{1fa74}", name: "thong sandal", skin_tones: false },
+    CharEntry { text: "\u{1fa78}", name: "drop of blood", skin_tones: false },
+    CharEntry { text: "\u{1fa79}", name: "adhesive bandage", skin_tones: false },
+    CharEntry { text: "\u{1fa7a}", name: "stethoscope", skin_tones: false },
+    CharEntry { text: "\u{1fa80}", name: "yo-yo", skin_tones: false },
+    CharEntry { text: "\u{1fa81}", name: "kite", skin_tones: false },
+    CharEntry { text: "\u{1fa82}", name: "parachute", skin_tones: false },
+    CharEntry { text: "\u{1fa83}", name: "boomerang", skin_tones: false },
+    CharEntry { text: "\u{1fa84}", name: "magic wand", skin_tones: false },
+    CharEntry { text: "\u{1fa85}", name: "pinata", skin_tones: false },
+    CharEntry { text: "\u{1fa86}", name: "nesting dolls", skin_tones: false },
+    CharEntry { text: "\u{1fa90}", name: "ringed planet", skin_tones: false },
+    CharEntry { text: "\u{1fa91}", name: "chair", skin_tones: false },
+    CharEntry { text: "\u{1fa92}", name: "razor", skin_tones: false },
+    CharEntry { text: "\u{1fa93}", name: "axe", skin_tones: false },
+    CharEntry { text: "\u{1fa94}", name: "diya lamp", skin_tones: false },
+    CharEntry { text: "\u{1fa95}", name: "banjo", skin_tones: false },
+    CharEntry { text: "\u{1fa96}", name: "military helmet", skin_tones: false },
+    CharEntry { text: "\u{1fa97}", name: "accordion", skin_tones: false },
+    CharEntry { text: "\u{1fa98}", name: "long drum", skin_tones: false },
+    CharEntry { text: "\u{1fa99}", name: "coin", skin_tones: false },
+    CharEntry { text: "\u{1fa9a}", name: "carpentry saw", skin_tones: false },
+    CharEntry { text: "\u{1fa9b}", name: "screwdriver", skin_tones: false },
+    CharEntry { text: "\u{1fa9c}", name: "ladder", skin_tones: false },
+    CharEntry { text: "\u{1fa9d}", name: "hook", skin_tones: false },
+    CharEntry { text: "\u{1fa9e}", name: "mirror", skin_tones: false },
+    CharEntry { text: "\u{1fa9f}", name: "window", skin_tones: false },
+    CharEntry { text: "\u{1faa0}", name: "plunger", skin_tones: false },
+    CharEntry { text: "\u{1faa1}", name: "sewing needle", skin_tones: false },
+    CharEntry { text: "\u{1faa2}", name: "knot", skin_tones: false },
+    CharEntry { text: "\u{1faa3}", name: "bucket", skin_tones: false },
+    CharEntry { text: "\u{1faa4}", name: "mouse trap", skin_tones: false },
+    CharEntry { text: "\u{1faa5}", name: "toothbrush", skin_tones: false },
+    CharEntry { text: "\u{1faa6}", name: "headstone", skin_tones: false },
+    CharEntry { text: "\u{1faa7}", name: "placard", skin_tones: false },
+    CharEntry { text: "\u{1faa8}", name: "rock", skin_tones: false },
+    CharEntry { text: "\u{1fab0}", name: "fly", skin_tones: false },
+    CharEntry { text: "\u{1fab1}", name: "worm", skin_tones: false },
+    CharEntry { text: "\u{1fab2}", name: "beetle", skin_tones: false },
+    CharEntry { text: "\u{1fab3}", name: "cockroach", skin_tones: false },
+    CharEntry { text: "\u{1fab4}", name: "potted plant", skin_tones: false },
+    CharEntry { text: "\u{1fab5}", name: "wood", skin_tones: false },
+    CharEntry { text: "\u{1fab6}", name: "feather", skin_tones: false },
+    CharEntry { text: "\u{1fac0}", name: "anatomical heart", skin_tones: false },
+    CharEntry { text: "\u{1fac1}", name: "lungs", skin_tones: false },
+    CharEntry { text: "\u{1fac2}", name: "people hugging", skin_tones: false },
+    CharEntry { text: "\u{1fad0}", name: "blueberries", skin_tones: false },
+    CharEntry { text: "\u{1fad1}", name: "bell pepper", skin_tones: false },
+    CharEntry { text: "\u{1fad2}", name: "olive", skin_tones: false },
+    CharEntry { text: "\u{1fad3}", name: "flatbread", skin_tones: false },
+    CharEntry { text: "\u{1fad4}", name: "tamale", skin_tones: false },
+    CharEntry { text: "\u{1fad5}", name: "fondue", skin_tones: false },
+    CharEntry { text: "\u{1fad6}", name: "teapot", skin_tones: false },
+];
+
+pub const ARROWS: &[CharEntry] = &[
+    CharEntry { text: "\u{2190}", name: "leftwards arrow", skin_tones: false },
+    CharEntry { text: "\u{2191}", name: "upwards arrow", skin_tones: false },
+    CharEntry { text: "\u{2192}", name: "rightwards arrow", skin_tones: false },
+    CharEntry { text: "\u{2193}", name: "downwards arrow", skin_tones: false },
+    CharEntry { text: "\u{2194}", name: "left right arrow", skin_tones: false },
+    CharEntry { text: "\u{2195}", name: "up down arrow", skin_tones: false },
+    CharEntry { text: "\u{2196}", name: "north west arrow", skin_tones: false },
+    CharEntry { text: "\u{2197}", name: "north east arrow", skin_tones: false },
+    CharEntry { text: "\u{2198}", name: "south east arrow", skin_tones: false },
+    CharEntry { text: "\u{2199}", name: "south west arrow", skin_tones: false },
+    CharEntry { text: "\u{219a}", name: "leftwards arrow with stroke", skin_tones: false },
+    CharEntry { text: "\u{219b}", name: "rightwards arrow with stroke", skin_tones: false },
+    CharEntry { text: "\u{219c}", name: "leftwards wave arrow", skin_tones: false },
+    CharEntry { text: "\u{219d}", name: "rightwards wave arrow", skin_tones: false },
+    CharEntry { text: "\u{219e}", name: "leftwards two headed arrow", skin_tones: false },
+    CharEntry { text: "\u{219f}", name: "upwards two headed arrow", skin_tones: false },
+    CharEntry { text: "\u{21a0}", name: "rightwards two headed arrow", skin_tones: false },
+    CharEntry { text: "\u{21a1}", name: "downwards two headed arrow", skin_tones: false },
+    CharEntry { text: "\u{21a2}", name: "leftwards arrow with tail", skin_tones: false },
+    CharEntry { text: "\u{21a3}", name: "rightwards arrow with tail", skin_tones: false },
+    CharEntry { text: "\u{21a4}", name: "leftwards arrow from bar", skin_tones: false },
+    CharEntry { text: "\u{21a5}", name: "upwards arrow from bar", skin_tones: false },
+    CharEntry { text: "\u{21a6}", name: "rightwards arrow from bar", skin_tones: false },
+    CharEntry { text: "\u{21a7}", name: "downwards arrow from bar", skin_tones: false },
+    CharEntry { text: "\u{21a8}", name: "up down arrow with base", skin_tones: false },
+    CharEntry { text: "\u{21a9}", name: "leftwards arrow with hook", skin_tones: false },
+    CharEntry { text: "\u{21aa}", name: "rightwards arrow with hook", skin_tones: false },
+    CharEntry { text: "\u{21ab}", name: "leftwards arrow with loop", skin_tones: false },
+    CharEntry { text: "\u{21ac}", name: "rightwards arrow with loop", skin_tones: false },
+    CharEntry { text: "\u{21ad}", name: "left right wave arrow", skin_tones: false },
+    CharEntry { text: "\u{21ae}", name: "left right arrow with stroke", skin_tones: false },
+    CharEntry { text: "\u{21af}", name: "downwards zigzag arrow", skin_tones: false },
+    CharEntry { text: "\u{21b0}", name: "upwards arrow with tip leftwards", skin_tones: false },
+    CharEntry { text: "\u{21b1}", name: "upwards arrow with tip rightwards", skin_tones: false },
+    CharEntry { text: "\u{21b2}", name: "downwards arrow with tip leftwards", skin_tones: false },
+    CharEntry { text: "\u{21b3}", name: "downwards arrow with tip rightwards", skin_tones: false },
+    CharEntry { text: "\u{21b4}", name: "rightwards arrow with corner downwards", skin_tones: false },
+    CharEntry { text: "\u{21b5}", name: "downwards arrow with corner leftwards", skin_tones: false },
+    CharEntry { text: "\u{21b6}", name: "anticlockwise top semicircle arrow", skin_tones: false },
+    CharEntry { text: "\u{21b7}", name: "clockwise top semicircle arrow", skin_tones: false },
+    CharEntry { text: "\u{21b8}", name: "north west arrow to long bar", skin_tones: false },
+    CharEntry { text: "\u{21b9}", name: "leftwards arrow to bar over rightwards arrow to bar", skin_tones: false },
+    CharEntry { text: "\u{21ba}", name: "anticlockwise open circle arrow", skin_tones: false },
+    CharEntry { text: "\u{21bb}", name: "clockwise open circle arrow", skin_tones: false },
+    CharEntry { text: "\u{21bc}", name: "leftwards harpoon with barb upwards", skin_tones: false },
+    CharEntry { text: "\u{21bd}", name: "leftwards harpoon with barb downwards", skin_tones: false },
+    CharEntry { text: "\u{21be}", name: "upwards harpoon with barb rightwards", skin_tones: false },
+    CharEntry { text: "\u{21bf}", name: "upwards harpoon with barb leftwards", skin_tones: false },
+    CharEntry { text: "\u{21c0}", name: "rightwards harpoon with barb upwards", skin_tones: false },
+    CharEntry { text: "\u{21c1}", name: "rightwards harpoon with barb downwards", skin_tones: false },
+    CharEntry { text: "\u{21c2}", name: "downwards harpoon with barb rightwards", skin_tones: false },
+    CharEntry { text: "\u{21c3}", name: "downwards harpoon with barb leftwards", skin_tones: false },
+    CharEntry { text: "\u{21c4}", name: "rightwards arrow over leftwards arrow", skin_tones: false },
+    CharEntry { text: "\u{21c5}", name: "upwards arrow leftwards of downwards arrow", skin_tones: false },
+    CharEntry { text: "\u{21c6}", name: "leftwards arrow over rightwards arrow", skin_tones: false },
+    CharEntry { text: "\u{21c7}", name: "leftwards paired arrows", skin_tones: false },
+    CharEntry { text: "\u{21c8}", name: "upwards paired arrows", skin_tones: false },
+    CharEntry { text: "\u{21c9}", name: "rightwards paired arrows", skin_tones: false },
+    CharEntry { text: "\u{21ca}", name: "downwards paired arrows", skin_tones: false },
+    CharEntry { text: "\u{21cb}", name: "leftwards harpoon over rightwards harpoon", skin_tones: false },
+    CharEntry { text: "\u{21cc}", name: "rightwards harpoon over leftwards harpoon", skin_tones: false },
+    CharEntry { text: "\u{21cd}", name: "leftwards double arrow with stroke", skin_tones: false },
+    CharEntry { text: "\u{21ce}", name: "left right double arrow with stroke", skin_tones: false },
+    CharEntry { text: "\u{21cf}", name: "rightwards double arrow with stroke", skin_tones: false },
+    CharEntry { text: "\u{21d0}", name: "leftwards double arrow", skin_tones: false },
+    CharEntry { text: "\u{21d1}", name: "upwards double arrow", skin_tones: false },
+    CharEntry { text: "\u{21d2}", name: "rightwards double arrow", skin_tones: false },
+    CharEntry { text: "\u{21d3}", name: "downwards double arrow", skin_tones: false },
+    CharEntry { text: "\u{21d4}", name: "left right double arrow", skin_tones: false },
+    CharEntry { text: "\u{21d5}", name: "up down double arrow", skin_tones: false },
+    CharEntry { text: "\u{21d6}", name: "north west double arrow", skin_tones: false },
+    CharEntry { text: "\u{21d7}", name: "north east double arrow", skin_tones: false },
+    CharEntry { text: "\u{21d8}", name: "south east double arrow", skin_tones: false },
+    CharEntry { text: "\u{21d9}", name: "south west double arrow", skin_tones: false },
+    CharEntry { text: "\u{21da}", name: "leftwards triple arrow", skin_tones: false },
+    CharEntry { text: "\u{21db}", name: "rightwards triple arrow", skin_tones: false },
+    CharEntry { text: "\u{21dc}", name: "leftwards squiggle arrow", skin_tones: false },
+    CharEntry { text: "\u{21dd}", name: "rightwards squiggle arrow", skin_tones: false },
+    CharEntry { text: "\u{21de}", name: "upwards arrow with double stroke", skin_tones: false },
+    CharEntry { text: "\u{21df}", name: "downwards arrow with double stroke", skin_tones: false },
+    CharEntry { text: "\u{21e0}", name: "leftwards dashed arrow", skin_tones: false },
+    CharEntry { text: "\u{21e1}", name: "upwards dashed arrow", skin_tones: false },
+    CharEntry { text: "\u{21e2}", name: "rightwards dashed arrow", skin_tones: false },
+    CharEntry { text: "\u{21e3}", name: "downwards dashed arrow", skin_tones: false },
+    CharEntry { text: "\u{21e4}", name: "leftwards arrow to bar", skin_tones: false },
+    CharEntry { text: "\u{21e5}", name: "rightwards arrow to bar", skin_tones: false },
+    CharEntry { text: "\u{21e6}", name: "leftwards white arrow", skin_tones: false },
+    CharEntry { text: "\u{21e7}", name: "upwards white arrow", skin_tones: false },
+    CharEntry { text: "\u{21e8}", name: "rightwards white arrow", skin_tones: false },
+    CharEntry { text: "\u{21e9}", name: "downwards white arrow", skin_tones: false },
+    CharEntry { text: "\u{21ea}", name: "upwards white arrow from bar", skin_tones: false },
+    CharEntry { text: "\u{21eb}", name: "upwards white arrow on pedestal", skin_tones: false },
+    CharEntry { text: "\u{21ec}", name: "upwards white arrow on pedestal with horizontal bar", skin_tones: false },
+    CharEntry { text: "\u{21ed}", name: "upwards white arrow on pedestal with vertical bar", skin_tones: false },
+    CharEntry { text: "\u{21ee}", name: "upwards white double arrow", skin_tones: false },
+    CharEntry { text: "\u{21ef}", name: "upwards white double arrow on pedestal", skin_tones: false },
+    CharEntry { text: "\u{21f0}", name: "rightwards white arrow from wall", skin_tones: false },
+    CharEntry { text: "\u{21f1}", name: "north west arrow to corner", skin_tones: false },
+    CharEntry { text: "\u{21f2}", name: "south east arrow to corner", skin_tones: false },
+    CharEntry { text: "\u{21f3}", name: "up down white arrow", skin_tones: false },
+    CharEntry { text: "\u{21f4}", name: "right arrow with small circle", skin_tones: false },
+    CharEntry { text: "\u{21f5}", name: "downwards arrow leftwards of upwards arrow", skin_tones: false },
+    CharEntry { text: "\u{21f6}", name: "three rightwards arrows", skin_tones: false },
+    CharEntry { text: "\u{21f7}", name: "leftwards arrow with vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{21f8}", name: "rightwards arrow with vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{21f9}", name: "left right arrow with vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{21fa}", name: "leftwards arrow with double vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{21fb}", name: "rightwards arrow with double vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{21fc}", name: "left right arrow with double vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{21fd}", name: "leftwards open-headed arrow", skin_tones: false },
+    CharEntry { text: "\u{21fe}", name: "rightwards open-headed arrow", skin_tones: false },
+    CharEntry { text: "\u{21ff}", name: "left right open-headed arrow", skin_tones: false },
+    CharEntry { text: "\u{27f0}", name: "upwards quadruple arrow", skin_tones: false },
+    CharEntry { text: "\u{27f1}", name: "downwards quadruple arrow", skin_tones: false },
+    CharEntry { text: "\u{27f2}", name: "anticlockwise gapped circle arrow", skin_tones: false },
+    CharEntry { text: "\u{27f3}", name: "clockwise gapped circle arrow", skin_tones: false },
+    CharEntry { text: "\u{27f4}", name: "right arrow with circled plus", skin_tones: false },
+    CharEntry { text: "\u{27f5}", name: "long leftwards arrow", skin_tones: false },
+    CharEntry { text: "\u{27f6}", name: "long rightwards arrow", skin_tones: false },
+    CharEntry { text: "\u{27f7}", name: "long left right arrow", skin_tones: false },
+    CharEntry { text: "\u{27f8}", name: "long leftwards double arrow", skin_tones: false },
+    CharEntry { text: "\u{27f9}", name: "long rightwards double arrow", skin_tones: false },
+    CharEntry { text: "\u{27fa}", name: "long left right double arrow", skin_tones: false },
+    CharEntry { text: "\u{27fb}", name: "long leftwards arrow from bar", skin_tones: false },
+    CharEntry { text: "\u{27fc}", name: "long rightwards arrow from bar", skin_tones: false },
+    CharEntry { text: "\u{27fd}", name: "long leftwards double arrow from bar", skin_tones: false },
+    CharEntry { text: "\u{27fe}", name: "long rightwards double arrow from bar", skin_tones: false },
+    CharEntry { text: "\u{27ff}", name: "long rightwards squiggle arrow", skin_tones: false },
+    CharEntry { text: "\u{2900}", name: "rightwards two-headed arrow with vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{2901}", name: "rightwards two-headed arrow with double vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{2902}", name: "leftwards double arrow with vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{2903}", name: "rightwards double arrow with vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{2904}", name: "left right double arrow with vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{2905}", name: "rightwards two-headed arrow from bar", skin_tones: false },
+    CharEntry { text: "\u{2906}", name: "leftwards double arrow from bar", skin_tones: false },
+    CharEntry { text: "\u{2907}", name: "rightwards double arrow from bar", skin_tones: false },
+    CharEntry { text: "\u{2908}", name: "downwards arrow with horizontal stroke", skin_tones: false },
+    CharEntry { text: "\u{2909}", name: "upwards arrow with horizontal stroke", skin_tones: false },
+    CharEntry { text: "\u{290a}", name: "upwards triple arrow", skin_tones: false },
+    CharEntry { text: "\u{290b}", name: "downwards triple arrow", skin_tones: false },
+    CharEntry { text: "\u{290c}", name: "leftwards double dash arrow", skin_tones: false },
+    CharEntry { text: "\u{290d}", name: "rightwards double dash arrow", skin_tones: false },
+    CharEntry { text: "\u{290e}", name: "leftwards triple dash arrow", skin_tones: false },
+    CharEntry { text: "\u{290f}", name: "rightwards triple dash arrow", skin_tones: false },
+    CharEntry { text: "\u{2910}", name: "rightwards two-headed triple dash arrow", skin_tones: false },
+    CharEntry { text: "\u{2911}", name: "rightwards arrow with dotted stem", skin_tones: false },
+    CharEntry { text: "\u{2912}", name: "upwards arrow to bar", skin_tones: false },
+    CharEntry { text: "\u{2913}", name: "downwards arrow to bar", skin_tones: false },
+    CharEntry { text: "\u{2914}", name: "rightwards arrow with tail with vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{2915}", name: "rightwards arrow with tail with double vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{2916}", name: "rightwards two-headed arrow with tail", skin_tones: false },
+    CharEntry { text: "\u{2917}", name: "rightwards two-headed arrow with tail with vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{2918}", name: "rightwards two-headed arrow with tail with double vertical stroke", skin_tones: false },
+    CharEntry { text: "\u{2919}", name: "leftwards arrow-tail", skin_tones: false },
+    CharEntry { text: "\u{291a}", name: "rightwards arrow-tail", skin_tones: false },
+    CharEntry { text: "\u{291b}", name: "leftwards double arrow-tail", skin_tones: false },
+    CharEntry { text: "\u{291c}", name: "rightwards double arrow-tail", skin_tones: false },
+    CharEntry { text: "\u{291d}", name: "leftwards arrow to black diamond", skin_tones: false },
+    CharEntry { text: "\u{291e}", name: "rightwards arrow to black diamond", skin_tones: false },
+    CharEntry { text: "\u{291f}", name: "leftwards arrow from bar to black diamond", skin_tones: false },
+    CharEntry { text: "\u{2920}", name: "rightwards arrow from bar to black diamond", skin_tones: false },
+    CharEntry { text: "\u{2921}", name: "north west and south east arrow", skin_tones: false },
+    CharEntry { text: "\u{2922}", name: "north east and south west arrow", skin_tones: false },
+    CharEntry { text: "\u{2923}", name: "north west arrow with hook", skin_tones: false },
+    CharEntry { text: "\u{2924}", name: "north east arrow with hook", skin_tones: false },
+    CharEntry { text: "\u{2925}", name: "south east arrow with hook", skin_tones: false },
+    CharEntry { text: "\u{2926}", name: "south west arrow with hook", skin_tones: false },
+    CharEntry { text: "\u{2927}", name: "north west arrow and north east arrow", skin_tones: false },
+    CharEntry { text: "\u{2928}", name: "north east arrow and south east arrow", skin_tones: false },
+    CharEntry { text: "\u{2929}", name: "south east arrow and south west arrow", skin_tones: false },
+    CharEntry { text: "\u{292a}", name: "south west arrow and north west arrow", skin_tones: false },
+    CharEntry { text: "\u{292b}", name: "rising diagonal crossing falling diagonal", skin_tones: false },
+    CharEntry { text: "\u{292c}", name: "falling diagonal crossing rising diagonal", skin_tones: false },
+    CharEntry { text: "\u{292d}", name: "south east arrow crossing north east arrow", skin_tones: false },
+    CharEntry { text: "\u{292e}", name: "north east arrow crossing south east arrow", skin_tones: false },
+    CharEntry { text: "\u{292f}", name: "falling diagonal crossing north east arrow", skin_tones: false },
+    CharEntry { text: "\u{2930}", name: "rising diagonal crossing south east arrow", skin_tones: false },
+    CharEntry { text: "\u{2931}", name: "north east arrow crossing north west arrow", skin_tones: false },
+    CharEntry { text: "\u{2932}", name: "north west arrow crossing north east arrow", skin_tones: false },
+    CharEntry { text: "\u{2933}", name: "wave arrow pointing directly right", skin_tones: false },
+    CharEntry { text: "\u{2934}", name: "arrow pointing rightwards then curving upwards", skin_tones: false },
+    CharEntry { text: "\u{2935}", name: "arrow pointing rightwards then curving downwards", skin_tones: false },
+    CharEntry { text: "\u{2936}", name: "arrow pointing downwards then curving leftwards", skin_tones: false },
+    CharEntry { text: "\u{2937}", name: "arrow pointing downwards then curving rightwards", skin_tones: false },
+    CharEntry { text: "\u{2938}", name: "right-side arc clockwise arrow", skin_tones: false },
+    CharEntry { text: "\u{2939}", name: "left-side arc anticlockwise arrow", skin_tones: false },
+    CharEntry { text: "\u{293a}", name: "top arc anticlockwise arrow", skin_tones: false },
+    CharEntry { text: "\u{293b}", name: "bottom arc anticlockwise arrow", skin_tones: false },
+    CharEntry { text: "\u{293c}", name: "top arc clockwise arrow with minus", skin_tones: false },
+    CharEntry { text: "\u{293d}", name: "top arc anticlockwise arrow with plus", skin_tones: false },
+    CharEntry { text: "\u{293e}", name: "lower right semicircular clockwise arrow", skin_tones: false },
+    CharEntry { text: "\u{293f}", name: "lower left semicircular anticlockwise arrow", skin_tones: false },
+    CharEntry { text: "\u{2940}", name: "anticlockwise closed circle arrow", skin_tones: false },
+    CharEntry { text: "\u{2941}", name: "clockwise closed circle arrow", skin_tones: false },
+    CharEntry { text: "\u{2942}", name: "rightwards arrow above short leftwards arrow", skin_tones: false },
+    CharEntry { text: "\u{2943}", name: "leftwards arrow above short rightwards arrow", skin_tones: false },
+    CharEntry { text: "\u{2944}", name: "short rightwards arrow above leftwards arrow", skin_tones: false },
+    CharEntry { text: "\u{2945}", name: "rightwards arrow with plus below", skin_tones: false },
+    CharEntry { text: "\u{2946}", name: "leftwards arrow with plus below", skin_tones: false },
+    CharEntry { text: "\u{2947}", name: "rightwards arrow through x", skin_tones: false },
+    CharEntry { text: "\u{2948}", name: "left right arrow through small circle", skin_tones: false },
+    CharEntry { text: "\u{2949}", name: "upwards two-headed arrow from small circle", skin_tones: false },
+    CharEntry { text: "\u{294a}", name: "left barb up right barb down harpoon", skin_tones: false },
+    CharEntry { text: "\u{294b}", name: "left barb down right barb up harpoon", skin_tones: false },
+    CharEntry { text: "\u{294c}", name: "up barb right down barb left harpoon", skin_tones: false },
+    CharEntry { text: "\u{294d}", name: "up barb left down barb right harpoon", skin_tones: false },
+    CharEntry { text: "\u{294e}", name: "left barb up right barb up harpoon", skin_tones: false },
+    CharEntry { text: "\u{294f}", name: "up barb right down barb right harpoon", skin_tones: false },
+    CharEntry { text: "\u{2950}", name: "left barb down right barb down harpoon", skin_tones: false },
+    CharEntry { text: "\u{2951}", name: "up barb left down barb left harpoon", skin_tones: false },
+    CharEntry { text: "\u{2952}", name: "leftwards harpoon with barb up to bar", skin_tones: false },
+    CharEntry { text: "\u{2953}", name: "rightwards harpoon with barb up to bar", skin_tones: false },
+    CharEntry { text: "\u{2954}", name: "upwards harpoon with barb right to bar", skin_tones: false },
+    CharEntry { text: "\u{2955}", name: "downwards harpoon with barb right to bar", skin_tones: false },
+    CharEntry { text: "\u{2956}", name: "leftwards harpoon with barb down to bar", skin_tones: false },
+    CharEntry { text: "\u{2957}", name: "rightwards harpoon with barb down to bar", skin_tones: false },
+    CharEntry { text: "\u{2958}", name: "upwards harpoon with barb left to bar", skin_tones: false },
+    CharEntry { text: "\u{2959}", name: "downwards harpoon with barb left to bar", skin_tones: false },
+    CharEntry { text: "\u{295a}", name: "leftwards harpoon with barb up from bar", skin_tones: false },
+    CharEntry { text: "\u{295b}", name: "rightwards harpoon with barb up from bar", skin_tones: false },
+    CharEntry { text: "\u{295c}", name: "upwards harpoon with barb right from bar", skin_tones: false },
+    CharEntry { text: "\u{295d}", name: "downwards harpoon with barb right from bar", skin_tones: false },
+    CharEntry { text: "\u{295e}", name: "leftwards harpoon with barb down from bar", skin_tones: false },
+    CharEntry { text: "\u{295f}", name: "rightwards harpoon with barb down from bar", skin_tones: false },
+    CharEntry { text: "\u{2960}", name: "upwards harpoon with barb left from bar", skin_tones: false },
+    CharEntry { text: "\u{2961}", name: "downwards harpoon with barb left from bar", skin_tones: false },
+    CharEntry { text: "\u{2962}", name: "leftwards harpoon with barb up above leftwards harpoon with barb down", skin_tones: false },
+    CharEntry { text: "\u{2963}", name: "upwards harpoon with barb left beside upwards harpoon with barb right", skin_tones: false },
+    CharEntry { text: "\u{2964}", name: "rightwards harpoon with barb up above rightwards harpoon with barb down", skin_tones: false },
+    CharEntry { text: "\u{2965}", name: "downwards harpoon with barb left beside downwards harpoon with barb right", skin_tones: false },
+    CharEntry { text: "\u{2966}", name: "leftwards harpoon with barb up above rightwards harpoon with barb up", skin_tones: false },
+    CharEntry { text: "\u{2967}", name: "leftwards harpoon with barb down above rightwards harpoon with barb down", skin_tones: false },
+    CharEntry { text: "\u{2968}", name: "rightwards harpoon with barb up above leftwards harpoon with barb up", skin_tones: false },
+    CharEntry { text: "\u{2969}", name: "rightwards harpoon with barb down above leftwards harpoon with barb down", skin_tones: false },
+    CharEntry { text: "\u{296a}", name: "leftwards harpoon with barb up above long dash", skin_tones: false },
+    CharEntry { text: "\u{296b}", name: "leftwards harpoon with barb down below long dash", skin_tones: false },
+    CharEntry { text: "\u{296c}", name: "rightwards harpoon with barb up above long dash", skin_tones: false },
+    CharEntry { text: "\u{296d}", name: "rightwards harpoon with barb down below long dash", skin_tones: false },
+    CharEntry { text: "\u{296e}", name: "upwards harpoon with barb left beside downwards harpoon with barb right", skin_tones: false },
+    CharEntry { text: "\u{296f}", name: "downwards harpoon with barb left beside upwards harpoon with barb right", skin_tones: false },
+    CharEntry { text: "\u{2970}", name: "right double arrow with rounded head", skin_tones: false },
+    CharEntry { text: "\u{2971}", name: "equals sign above rightwards arrow", skin_tones: false },
+    CharEntry { text: "\u{2972}", name: "tilde operator above rightwards arrow", skin_tones: false },
+    CharEntry { text: "\u{2973}", name: "leftwards arrow above tilde operator", skin_tones: false },
+    CharEntry { text: "\u{2974}", name: "rightwards arrow above tilde operator", skin_tones: false },
+    CharEntry { text: "\u{2975}", name: "rightwards arrow above almost equal to", skin_tones: false },
+    CharEntry { text: "\u{2976}", name: "less-than above leftwards arrow", skin_tones: false },
+    CharEntry { text: "\u{2977}", name: "leftwards arrow through less-than", skin_tones: false },
+    CharEntry { text: "\u{2978}", name: "greater-than above rightwards arrow", skin_tones: false },
+    CharEntry { text: "\u{2979}", name: "subset above rightwards arrow", skin_tones: false },
+    CharEntry { text: "\u{297a}", name: "leftwards arrow through subset", skin_tones: false },
+    CharEntry { text: "\u{297b}", name: "superset above leftwards arrow", skin_tones: false },
+    CharEntry { text: "\u{297c}", name: "left fish tail", skin_tones: false },
+    CharEntry { text: "\u{297d}", name: "right fish tail", skin_tones: false },
+    CharEntry { text: "\u{297e}", name: "up fish tail", skin_tones: false },
+    CharEntry { text: "\u{297f}", name: "down fish tail", skin_tones: false },
+];
+
+pub const MATH: &[CharEntry] = &[
+    CharEntry { text: "\u{2200}", name: "for all", skin_tones: false },
+    CharEntry { text: "\u{2201}", name: "complement", skin_tones: false },
+    CharEntry { text: "\u{2202}", name: "partial differential", skin_tones: false },
+    CharEntry { text: "\u{2203}", name: "there exists", skin_tones: false },
+    CharEntry { text: "\u{2204}", name: "there does not exist", skin_tones: false },
+    CharEntry { text: "\u{2205}", name: "empty set", skin_tones: false },
+    CharEntry { text: "\u{2206}", name: "increment", skin_tones: false },
+    CharEntry { text: "\u{2207}", name: "nabla", skin_tones: false },
+    CharEntry { text: "\u{2208}", name: "element of", skin_tones: false },
+    CharEntry { text: "\u{2209}", name: "not an element of", skin_tones: false },
+    CharEntry { text: "\u{220a}", name: "small element of", skin_tones: false },
+    CharEntry { text: "\u{220b}", name: "contains as member", skin_tones: false },
+    CharEntry { text: "\u{220c}", name: "does not contain as member", skin_tones: false },
+    CharEntry { text: "\u{220d}", name: "small contains as member", skin_tones: false },
+    CharEntry { text: "\u{220e}", name: "end of proof", skin_tones: false },
+    CharEntry { text: "\u{220f}", name: "n-ary product", skin_tones: false },
+    CharEntry { text: "\u{2210}", name: "n-ary coproduct", skin_tones: false },
+    CharEntry { text: "\u{2211}", name: "n-ary summation", skin_tones: false },
+    CharEntry { text: "\u{2212}", name: "minus sign", skin_tones: false },
+    CharEntry { text: "\u{2213}", name: "minus-or-plus sign", skin_tones: false },
+    CharEntry { text: "\u{2214}", name: "dot plus", skin_tones: false },
+    CharEntry { text: "\u{2215}", name: "division slash", skin_tones: false },
+    CharEntry { text: "\u{2216}", name: "set minus", skin_tones: false },
+    CharEntry { text: "\u{2217}", name: "asterisk operator", skin_tones: false },
+    CharEntry { text: "\u{2218}", name: "ring operator", skin_tones: false },
+    CharEntry { text: "\u{2219}", name: "bullet operator", skin_tones: false },
+    CharEntry { text: "\u{221a}", name: "square root", skin_tones: false },
+    CharEntry { text: "\u{221b}", name: "cube root", skin_tones: false },
+    CharEntry { text: "\u{221c}", name: "fourth root", skin_tones: false },
+    CharEntry { text: "\u{221d}", name: "proportional to", skin_tones: false },
+    CharEntry { text: "\u{221e}", name: "infinity", skin_tones: false },
+    CharEntry { text: "\u{221f}", name: "right angle", skin_tones: false },
+    CharEntry { text: "\u{2220}", name: "angle", skin_tones: false },
+    CharEntry { text: "\u{2221}", name: "measured angle", skin_tones: false },
+    CharEntry { text: "\u{2222}", name: "spherical angle", skin_tones: false },
+    CharEntry { text: "\u{2223}", name: "divides", skin_tones: false },
+    CharEntry { text: "\u{2224}", name: "does not divide", skin_tones: false },
+    CharEntry { text: "\u{2225}", name: "parallel to", skin_tones: false },
+    CharEntry { text: "\u{2226}", name: "not parallel to", skin_tones: false },
+    CharEntry { text: "\u{2227}", name: "logical and", skin_tones: false },
+    CharEntry { text: "\u{2228}", name: "logical or", skin_tones: false },
+    CharEntry { text: "\u{2229}", name: "intersection", skin_tones: false },
+    CharEntry { text: "\u{222a}", name: "union", skin_tones: false },
+    CharEntry { text: "\u{222b}", name: "integral", skin_tones: false },
+    CharEntry { text: "\u{222c}", name: "double integral", skin_tones: false },
+    CharEntry { text: "\u{222d}", name: "triple integral", skin_tones: false },
+    CharEntry { text: "\u{222e}", name: "contour integral", skin_tones: false },
+    CharEntry { text: "\u{222f}", name: "surface integral", skin_tones: false },
+    CharEntry { text: "\u{2230}", name: "volume integral", skin_tones: false },
+    CharEntry { text: "\u{2231}", name: "clockwise integral", skin_tones: false },
+    CharEntry { text: "\u{2232}", name: "clockwise contour integral", skin_tones: false },
+    CharEntry { text: "\u{2233}", name: "anticlockwise contour integral", skin_tones: false },
+    CharEntry { text: "\u{2234}", name: "therefore", skin_tones: false },
+    CharEntry { text: "\u{2235}", name: "because", skin_tones: false },
+    CharEntry { text: "\u{2236}", name: "ratio", skin_tones: false },
+    CharEntry { text: "\u{2237}", name: "proportion", skin_tones: false },
+    CharEntry { text: "\u{2238}", name: "dot minus", skin_tones: false },
+    CharEntry { text: "\u{2239}", name: "excess", skin_tones: false },
+    CharEntry { text: "\u{223a}", name: "geometric proportion", skin_tones: false },
+    CharEntry { text: "\u{223b}", name: "homothetic", skin_tones: false },
+    CharEntry { text: "\u{223c}", name: "tilde operator", skin_tones: false },
+    CharEntry { text: "\u{223d}", name: "reversed tilde", skin_tones: false },
+    CharEntry { text: "\u{223e}", name: "inverted lazy s", skin_tones: false },
+    CharEntry { text: "\u{223f}", name: "sine wave", skin_tones: false },
+    CharEntry { text: "\u{2240}", name: "wreath product", skin_tones: false },
+    CharEntry { text: "\u{2241}", name: "not tilde", skin_tones: false },
+    CharEntry { text: "\u{2242}", name: "minus tilde", skin_tones: false },
+    CharEntry { text: "\u{2243}", name: "asymptotically equal to", skin_tones: false },
+    CharEntry { text: "\u{2244}", name: "not asymptotically equal to", skin_tones: false },
+    CharEntry { text: "\u{2245}", name: "approximately equal to", skin_tones: false },
+    CharEntry { text: "\u{2246}", name: "approximately but not actually equal to", skin_tones: false },
+    CharEntry { text: "\u{2247}", name: "neither approximately nor actually equal to", skin_tones: false },
+    CharEntry { text: "\u{2248}", name: "almost equal to", skin_tones: false },
+    CharEntry { text: "\u{2249}", name: "not almost equal to", skin_tones: false },
+    CharEntry { text: "\u{224a}", name: "almost equal or equal to", skin_tones: false },
+    CharEntry { text: "\u{224b}", name: "triple tilde", skin_tones: false },
+    CharEntry { text: "\u{224c}", name: "all equal to", skin_tones: false },
+    CharEntry { text: "\u{224d}", name: "equivalent to", skin_tones: false },
+    CharEntry { text: "\u{224e}", name: "geometrically equivalent to", skin_tones: false },
+    CharEntry { text: "\u{224f}", name: "difference between", skin_tones: false },
+    CharEntry { text: "\u{2250}", name: "approaches the limit", skin_tones: false },
+    CharEntry { text: "\u{2251}", name: "geometrically equal to", skin_tones: false },
+    CharEntry { text: "\u{2252}", name: "approximately equal to or the image of", skin_tones: false },
+    CharEntry { text: "\u{2253}", name: "image of or approximately equal to", skin_tones: false },
+    CharEntry { text: "\u{2254}", name: "colon equals", skin_tones: false },
+    CharEntry { text: "\u{2255}", name: "equals colon", skin_tones: false },
+    CharEntry { text: "\u{2256}", name: "ring in equal to", skin_tones: false },
+    CharEntry { text: "\u{2257}", name: "ring equal to", skin_tones: false },
+    CharEntry { text: "\u{2258}", name: "corresponds to", skin_tones: false },
+    CharEntry { text: "\u{2259}", name: "estimates", skin_tones: false },
+    CharEntry { text: "\u{225a}", name: "equiangular to", skin_tones: false },
+    CharEntry { text: "\u{225b}", name: "star equals", skin_tones: false },
+    CharEntry { text: "\u{225c}", name: "delta equal to", skin_tones: false },
+    CharEntry { text: "\u{225d}", name: "equal to by definition", skin_tones: false },
+    CharEntry { text: "\u{225e}", name: "measured by", skin_tones: false },
+    CharEntry { text: "\u{225f}", name: "questioned equal to", skin_tones: false },
+    CharEntry { text: "\u{2260}", name: "not equal to", skin_tones: false },
+    CharEntry { text: "\u{2261}", name: "identical to", skin_tones: false },
+    CharEntry { text: "\u{2262}", name: "not identical to", skin_tones: false },
+    CharEntry { text: "\u{2263}", name: "strictly equivalent to", skin_tones: false },
+    CharEntry { text: "\u{2264}", name: "less-than or equal to", skin_tones: false },
+    CharEntry { text: "\u{2265}", name: "greater-than or equal to", skin_tones: false },
+    CharEntry { text: "\u{2266}", name: "less-than over equal to", skin_tones: false },
+    CharEntry { text: "\u{2267}", name: "greater-than over equal to", skin_tones: false },
+    CharEntry { text: "\u{2268}", name: "less-than but not equal to", skin_tones: false },
+    CharEntry { text: "\u{2269}", name: "greater-than but not equal to", skin_tones: false },
+    CharEntry { text: "\u{226a}", name: "much less-than", skin_tones: false },
+    CharEntry { text: "\u{226b}", name: "much greater-than", skin_tones: false },
+    CharEntry { text: "\u{226c}", name: "between", skin_tones: false },
+    CharEntry { text: "\u{226d}", name: "not equivalent to", skin_tones: false },
+    CharEntry { text: "\u{226e}", name: "not less-than", skin_tones: false },
+    CharEntry { text: "\u{226f}", name: "not greater-than", skin_tones: false },
+    CharEntry { text: "\u{2270}", name: "neither less-than nor equal to", skin_tones: false },
+    CharEntry { text: "\u{2271}", name: "neither greater-than nor equal to", skin_tones: false },
+    CharEntry { text: "\u{2272}", name: "less-than or equivalent to", skin_tones: false },
+    CharEntry { text: "\u{2273}", name: "greater-than or equivalent to", skin_tones: false },
+    CharEntry { text: "\u{2274}", name: "neither less-than nor equivalent to", skin_tones: false },
+    CharEntry { text: "\u{2275}", name: "neither greater-than nor equivalent to", skin_tones: false },
+    CharEntry { text: "\u{2276}", name: "less-than or greater-than", skin_tones: false },
+    CharEntry { text: "\u{2277}", name: "greater-than or less-than", skin_tones: false },
+    CharEntry { text: "\u{2278}", name: "neither less-than nor greater-than", skin_tones: false },
+    CharEntry { text: "\u{2279}", name: "neither greater-than nor less-than", skin_tones: false },
+    CharEntry { text: "\u{227a}", name: "precedes", skin_tones: false },
+    CharEntry { text: "\u{227b}", name: "succeeds", skin_tones: false },
+    CharEntry { text: "\u{227c}", name: "precedes or equal to", skin_tones: false },
+    CharEntry { text: "\u{227d}", name: "succeeds or equal to", skin_tones: false },
+    CharEntry { text: "\u{227e}", name: "precedes or equivalent to", skin_tones: false },
+    CharEntry { text: "\u{227f}", name: "succeeds or equivalent to", skin_tones: false },
+    CharEntry { text: "\u{2280}", name: "does not precede", skin_tones: false },
+    CharEntry { text: "\u{2281}", name: "does not succeed", skin_tones: false },
+    CharEntry { text: "\u{2282}", name: "subset of", skin_tones: false },
+    CharEntry { text: "\u{2283}", name: "superset of", skin_tones: false },
+    CharEntry { text: "\u{2284}", name: "not a subset of", skin_tones: false },
+    CharEntry { text: "\u{2285}", name: "not a superset of", skin_tones: false },
+    CharEntry { text: "\u{2286}", name: "subset of or equal to", skin_tones: false },
+    CharEntry { text: "\u{2287}", name: "superset of or equal to", skin_tones: false },
+    CharEntry { text: "\u{2288}", name: "neither a subset of nor equal to", skin_tones: false },
+    CharEntry { text: "\u{2289}", name: "neither a superset of nor equal to", skin_tones: false },
+    CharEntry { text: "\u{228a}", name: "subset of with not equal to", skin_tones: false },
+    CharEntry { text: "\u{228b}", name: "superset of with not equal to", skin_tones: false },
+    CharEntry { text: "\u{228c}", name: "multiset", skin_tones: false },
+    CharEntry { text: "\u{228d}", name: "multiset multiplication", skin_tones: false },
+    CharEntry { text: "\u{228e}", name: "multiset union", skin_tones: false },
+    CharEntry { text: "\u{228f}", name: "square image of", skin_tones: false },
+    CharEntry { text: "\u{2290}", name: "square original of", skin_tones: false },
+    CharEntry { text: "\u{2291}", name: "square image of or equal to", skin_tones: false },
+    CharEntry { text: "\u{2292}", name: "square original of or equal to", skin_tones: false },
+    CharEntry { text: "\u{2293}", name: "square cap", skin_tones: false },
+    CharEntry { text: "\u{2294}", name: "square cup", skin_tones: false },
+    CharEntry { text: "\u{2295}", name: "circled plus", skin_tones: false },
+    CharEntry { text: "\u{2296}", name: "circled minus", skin_tones: false },
+    CharEntry { text: "\u{2297}", name: "circled times", skin_tones: false },
+    CharEntry { text: "\u{2298}", name: "circled division slash", skin_tones: false },
+    CharEntry { text: "\u{2299}", name: "circled dot operator", skin_tones: false },
+    CharEntry { text: "\u{229a}", name: "circled ring operator", skin_tones: false },
+    CharEntry { text: "\u{229b}", name: "circled asterisk operator", skin_tones: false },
+    CharEntry { text: "\u{229c}", name: "circled equals", skin_tones: false },
+    CharEntry { text: "\u{229d}", name: "circled dash", skin_tones: false },
+    CharEntry { text: "\u{229e}", name: "squared plus", skin_tones: false },
+    CharEntry { text: "\u{229f}", name: "squared minus", skin_tones: false },
+    CharEntry { text: "\u{22a0}", name: "squared times", skin_tones: false },
+    CharEntry { text: "\u{22a1}", name: "squared dot operator", skin_tones: false },
+    CharEntry { text: "\u{22a2}", name: "right tack", skin_tones: false },
+    CharEntry { text: "\u{22a3}", name: "left tack", skin_tones: false },
+    CharEntry { text: "\u{22a4}", name: "down tack", skin_tones: false },
+    CharEntry { text: "\u{22a5}", name: "up tack", skin_tones: false },
+    CharEntry { text: "\u{22a6}", name: "assertion", skin_tones: false },
+    CharEntry { text: "\u{22a7}", name: "models", skin_tones: false },
+    CharEntry { text: "\u{22a8}", name: "true", skin_tones: false },
+    CharEntry { text: "\u{22a9}", name: "forces", skin_tones: false },
+    CharEntry { text: "\u{22aa}", name: "triple vertical bar right turnstile", skin_tones: false },
+    CharEntry { text: "\u{22ab}", name: "double vertical bar double right turnstile", skin_tones: false },
+    CharEntry { text: "\u{22ac}", name: "does not prove", skin_tones: false },
+    CharEntry { text: "\u{22ad}", name: "not true", skin_tones: false },
+    CharEntry { text: "\u{22ae}", name: "does not force", skin_tones: false },
+    CharEntry { text: "\u{22af}", name: "negated double vertical bar double right turnstile", skin_tones: false },
+    CharEntry { text: "\u{22b0}", name: "precedes under relation", skin_tones: false },
+    CharEntry { text: "\u{22b1}", name: "succeeds under relation", skin_tones: false },
+    CharEntry { text: "\u{22b2}", name: "normal subgroup of", skin_tones: false },
+    CharEntry { text: "\u{22b3}", name: "contains as normal subgroup", skin_tones: false },
+    CharEntry { text: "\u{22b4}", name: "normal subgroup of or equal to", skin_tones: false },
+    CharEntry { text: "\u{22b5}", name: "contains as normal subgroup or equal to", skin_tones: false },
+    CharEntry { text: "\u{22b6}", name: "original of", skin_tones: false },
+    CharEntry { text: "\u{22b7}", name: "image of", skin_tones: false },
+    CharEntry { text: "\u{22b8}", name: "multimap", skin_tones: false },
+    CharEntry { text: "\u{22b9}", name: "hermitian conjugate matrix", skin_tones: false },
+    CharEntry { text: "\u{22ba}", name: "intercalate", skin_tones: false },
+    CharEntry { text: "\u{22bb}", name: "xor", skin_tones: false },
+    CharEntry { text: "\u{22bc}", name: "nand", skin_tones: false },
+    CharEntry { text: "\u{22bd}", name: "nor", skin_tones: false },
+    CharEntry { text: "\u{22be}", name: "right angle with arc", skin_tones: false },
+    CharEntry { text: "\u{22bf}", name: "right triangle", skin_tones: false },
+    CharEntry { text: "\u{22c0}", name: "n-ary logical and", skin_tones: false },
+    CharEntry { text: "\u{22c1}", name: "n-ary logical or", skin_tones: false },
+    CharEntry { text: "\u{22c2}", name: "n-ary intersection", skin_tones: false },
+    CharEntry { text: "\u{22c3}", name: "n-ary union", skin_tones: false },
+    CharEntry { text: "\u{22c4}", name: "diamond operator", skin_tones: false },
+    CharEntry { text: "\u{22c5}", name: "dot operator", skin_tones: false },
+    CharEntry { text: "\u{22c6}", name: "star operator", skin_tones: false },
+    CharEntry { text: "\u{22c7}", name: "division times", skin_tones: false },
+    CharEntry { text: "\u{22c8}", name: "bowtie", skin_tones: false },
+    CharEntry { text: "\u{22c9}", name: "left normal factor semidirect product", skin_tones: false },
+    CharEntry { text: "\u{22ca}", name: "right normal factor semidirect product", skin_tones: false },
+    CharEntry { text: "\u{22cb}", name: "left semidirect product", skin_tones: false },
+    CharEntry { text: "\u{22cc}", name: "right semidirect product", skin_tones: false },
+    CharEntry { text: "\u{22cd}", name: "reversed tilde equals", skin_tones: false },
+    CharEntry { text: "\u{22ce}", name: "curly logical or", skin_tones: false },
+    CharEntry { text: "\u{22cf}", name: "curly logical and", skin_tones: false },
+    CharEntry { text: "\u{22d0}", name: "double subset", skin_tones: false },
+    CharEntry { text: "\u{22d1}", name: "double superset", skin_tones: false },
+    CharEntry { text: "\u{22d2}", name: "double intersection", skin_tones: false },
+    CharEntry { text: "\u{22d3}", name: "double union", skin_tones: false },
+    CharEntry { text: "\u{22d4}", name: "pitchfork", skin_tones: false },
+    CharEntry { text: "\u{22d5}", name: "equal and parallel to", skin_tones: false },
+    CharEntry { text: "\u{22d6}", name: "less-than with dot", skin_tones: false },
+    CharEntry { text: "\u{22d7}", name: "greater-than with dot", skin_tones: false },
+    CharEntry { text: "\u{22d8}", name: "very much less-than", skin_tones: false },
+    CharEntry { text: "\u{22d9}", name: "very much greater-than", skin_tones: false },
+    CharEntry { text: "\u{22da}", name: "less-than equal to or greater-than", skin_tones: false },
+    CharEntry { text: "\u{22db}", name: "greater-than equal to or less-than", skin_tones: false },
+    CharEntry { text: "\u{22dc}", name: "equal to or less-than", skin_tones: false },
+    CharEntry { text: "\u{22dd}", name: "equal to or greater-than", skin_tones: false },
+    CharEntry { text: "\u{22de}", name: "equal to or precedes", skin_tones: false },
+    CharEntry { text: "\u{22df}", name: "equal to or succeeds", skin_tones: false },
+    CharEntry { text: "\u{22e0}", name: "does not precede or equal", skin_tones: false },
+    CharEntry { text: "\u{22e1}", name: "does not succeed or equal", skin_tones: false },
+    CharEntry { text: "\u{22e2}", name: "not square image of or equal to", skin_tones: false },
+    CharEntry { text: "\u{22e3}", name: "not square original of or equal to", skin_tones: false },
+    CharEntry { text: "\u{22e4}", name: "square image of or not equal to", skin_tones: false },
+    CharEntry { text: "\u{22e5}", name: "square original of or not equal to", skin_tones: false },
+    CharEntry { text: "\u{22e6}", name: "less-than but not equivalent to", skin_tones: false },
+    CharEntry { text: "\u{22e7}", name: "greater-than but not equivalent to", skin_tones: false },
+    CharEntry { text: "\u{22e8}", name: "precedes but not equivalent to", skin_tones: false },
+    CharEntry { text: "\u{22e9}", name: "succeeds but not equivalent to", skin_tones: false },
+    CharEntry { text: "\u{22ea}", name: "not normal subgroup of", skin_tones: false },
+    CharEntry { text: "\u{22eb}", name: "does not contain as normal subgroup", skin_tones: false },
+    CharEntry { text: "\u{22ec}", name: "not normal subgroup of or equal to", skin_tones: false },
+    CharEntry { text: "\u{22ed}", name: "does not contain as normal subgroup or equal", skin_tones: false },
+    CharEntry { text: "\u{22ee}", name: "vertical ellipsis", skin_tones: false },
+    CharEntry { text: "\u{22ef}", name: "midline horizontal ellipsis", skin_tones: false },
+    CharEntry { text: "\u{22f0}", name: "up right diagonal ellipsis", skin_tones: false },
+    CharEntry { text: "\u{22f1}", name: "down right diagonal ellipsis", skin_tones: false },
+    CharEntry { text: "\u{22f2}", name: "element of with long horizontal stroke", skin_tones: false },
+    CharEntry { text: "\u{22f3}", name: "element of with vertical bar at end of horizontal stroke", skin_tones: false },
+    CharEntry { text: "\u{22f4}", name: "small element of with vertical bar at end of horizontal stroke", skin_tones: false },
+    CharEntry { text: "\u{22f5}", name: "element of with dot above", skin_tones: false },
+    CharEntry { text: "\u{22f6}", name: "element of with overbar", skin_tones: false },
+    CharEntry { text: "\u{22f7}", name: "small element of with overbar", skin_tones: false },
+    CharEntry { text: "\u{22f8}", name: "element of with underbar", skin_tones: false },
+    CharEntry { text: "\u{22f9}", name: "element of with two horizontal strokes", skin_tones: false },
+    CharEntry { text: "\u{22fa}", name: "contains with long horizontal stroke", skin_tones: false },
+    CharEntry { text: "\u{22fb}", name: "contains with vertical bar at end of horizontal stroke", skin_tones: false },
+    CharEntry { text: "\u{22fc}", name: "small contains with vertical bar at end of horizontal stroke", skin_tones: false },
+    CharEntry { text: "\u{22fd}", name: "contains with overbar", skin_tones: false },
+    CharEntry { text: "\u{22fe}", name: "small contains with overbar", skin_tones: false },
+    CharEntry { text: "\u{22ff}", name: "z notation bag membership", skin_tones: false },
+    CharEntry { text: "\u{b1}", name: "plus-minus sign", skin_tones: false },
+    CharEntry { text: "\u{d7}", name: "multiplication sign", skin_tones: false },
+    CharEntry { text: "\u{f7}", name: "division sign", skin_tones: false },
+];
+
+pub const GREEK: &[CharEntry] = &[
+    CharEntry { text: "\u{391}", name: "greek capital letter alpha", skin_tones: false },
+    CharEntry { text: "\u{392}", name: "greek capital letter beta", skin_tones: false },
+    CharEntry { text: "\u{393}", name: "greek capital letter gamma", skin_tones: false },
+    CharEntry { text: "\u{394}", name: "greek capital letter delta", skin_tones: false },
+    CharEntry { text: "\u{395}", name: "greek capital letter epsilon", skin_tones: false },
+    CharEntry { text: "\u{396}", name: "greek capital letter zeta", skin_tones: false },
+    CharEntry { text: "\u{397}", name: "greek capital letter eta", skin_tones: false },
+    CharEntry { text: "\u{398}", name: "greek capital letter theta", skin_tones: false },
+    CharEntry { text: "\u{399}", name: "greek capital letter iota", skin_tones: false },
+    CharEntry { text: "\u{39a}", name: "greek capital letter kappa", skin_tones: false },
+    CharEntry { text: "\u{39b}", name: "greek capital letter lamda", skin_tones: false },
+    CharEntry { text: "\u{39c}", name: "greek capital letter mu", skin_tones: false },
+    CharEntry { text: "\u{39d}", name: "greek capital letter nu", skin_tones: false },
+    CharEntry { text: "\u{39e}", name: "greek capital letter xi", skin_tones: false },
+    CharEntry { text: "\u{39f}", name: "greek capital letter omicron", skin_tones: false },
+    CharEntry { text: "\u{3a0}", name: "greek capital letter pi", skin_tones: false },
+    CharEntry { text: "\u{3a1}", name: "greek capital letter rho", skin_tones: false },
+    CharEntry { text: "\u{3a3}", name: "greek capital letter sigma", skin_tones: false },
+    CharEntry { text: "\u{3a4}", name: "greek capital letter tau", skin_tones: false },
+    CharEntry { text: "\u{3a5}", name: "greek capital letter upsilon", skin_tones: false },
+    CharEntry { text: "\u{3a6}", name: "greek capital letter phi", skin_tones: false },
+    CharEntry { text: "\u{3a7}", name: "greek capital letter chi", skin_tones: false },
+    CharEntry { text: "\u{3a8}", name: "greek capital letter psi", skin_tones: false },
+    CharEntry { text: "\u{3a9}", name: "greek capital letter omega", skin_tones: false },
+    CharEntry { text: "\u{3b1}", name: "greek small letter alpha", skin_tones: false },
+    CharEntry { text: "\u{3b2}", name: "greek small letter beta", skin_tones: false },
+    CharEntry { text: "\u{3b3}", name: "greek small letter gamma", skin_tones: false },
+    CharEntry { text: "\u{3b4}", name: "greek small letter delta", skin_tones: false },
+    CharEntry { text: "\u{3b5}", name: "greek small letter epsilon", skin_tones: false },
+    CharEntry { text: "\u{3b6}", name: "greek small letter zeta", skin_tones: false },
+    CharEntry { text: "\u{3b7}", name: "greek small letter eta", skin_tones: false },
+    CharEntry { text: "\u{3b8}", name: "greek small letter theta", skin_tones: false },
+    CharEntry { text: "\u{3b9}", name: "greek small letter iota", skin_tones: false },
+    CharEntry { text: "\u{3ba}", name: "greek small letter kappa", skin_tones: false },
+    CharEntry { text: "\u{3bb}", name: "greek small letter lamda", skin_tones: false },
+    CharEntry { text: "\u{3bc}", name: "greek small letter mu", skin_tones: false },
+    CharEntry { text: "\u{3bd}", name: "greek small letter nu", skin_tones: false },
+    CharEntry { text: "\u{3be}", name: "greek small letter xi", skin_tones: false },
+    CharEntry { text: "\u{3bf}", name: "greek small letter omicron", skin_tones: false },
+    CharEntry { text: "\u{3c0}", name: "greek small letter pi", skin_tones: false },
+    CharEntry { text: "\u{3c1}", name: "greek small letter rho", skin_tones: false },
+    CharEntry { text: "\u{3c2}", name: "greek small letter final sigma", skin_tones: false },
+    CharEntry { text: "\u{3c3}", name: "greek small letter sigma", skin_tones: false },
+    CharEntry { text: "\u{3c4}", name: "greek small letter tau", skin_tones: false },
+    CharEntry { text: "\u{3c5}", name: "greek small letter upsilon", skin_tones: false },
+    CharEntry { text: "\u{3c6}", name: "greek small letter phi", skin_tones: false },
+    CharEntry { text: "\u{3c7}", name: "greek small letter chi", skin_tones: false },
+    CharEntry { text: "\u{3c8}", name: "greek small letter psi", skin_tones: false },
+    CharEntry { text: "\u{3c9}", name: "greek small letter omega", skin_tones: false },
+];
+
+pub const CURRENCY: &[CharEntry] = &[
+    CharEntry { text: "$", name: "dollar sign", skin_tones: false },
+    CharEntry { text: "\u{a2}", name: "cent sign", skin_tones: false },
+    CharEntry { text: "\u{a3}", name: "pound sign", skin_tones: false },
+    CharEntry { text: "\u{a4}", name: "currency sign", skin_tones: false },
+    CharEntry { text: "\u{a5}", name: "yen sign", skin_tones: false },
+    CharEntry { text: "\u{20a0}", name: "euro-currency sign", skin_tones: false },
+    CharEntry { text: "\u{20a1}", name: "colon sign", skin_tones: false },
+    CharEntry { text: "\u{20a2}", name: "cruzeiro sign", skin_tones: false },
+    CharEntry { text: "\u{20a3}", name: "french franc sign", skin_tones: false },
+    CharEntry { text: "\u{20a4}", name: "lira sign", skin_tones: false },
+    CharEntry { text: "\u{20a5}", name: "mill sign", skin_tones: false },
+    CharEntry { text: "\u{20a6}", name: "naira sign", skin_tones: false },
+    CharEntry { text: "\u{20a7}", name: "peseta sign", skin_tones: false },
+    CharEntry { text: "\u{20a8}", name: "rupee sign", skin_tones: false },
+    CharEntry { text: "\u{20a9}", name: "won sign", skin_tones: false },
+    CharEntry { text: "\u{20aa}", name: "new sheqel sign", skin_tones: false },
+    CharEntry { text: "\u{20ab}", name: "dong sign", skin_tones: false },
+    CharEntry { text: "\u{20ac}", name: "euro sign", skin_tones: false },
+    CharEntry { text: "\u{20ad}", name: "kip sign", skin_tones: false },
+    CharEntry { text: "\u{20ae}", name: "tugrik sign", skin_tones: false },
+    CharEntry { text: "\u{20af}", name: "drachma sign", skin_tones: false },
+    CharEntry { text: "\u{20b0}", name: "german penny sign", skin_tones: false },
+    CharEntry { text: "\u{20b1}", name: "peso sign", skin_tones: false },
+    CharEntry { text: "\u{20b2}", name: "guarani sign", skin_tones: false },
+    CharEntry { text: "\u{20b3}", name: "austral sign", skin_tones: false },
+    CharEntry { text: "\u{20b4}", name: "hryvnia sign", skin_tones: false },
+    CharEntry { text: "\u{20b5}", name: "cedi sign", skin_tones: false },
+    CharEntry { text: "\u{20b6}", name: "livre tournois sign", skin_tones: false },
+    CharEntry { text: "\u{20b7}", name: "spesmilo sign", skin_tones: false },
+    CharEntry { text: "\u{20b8}", name: "tenge sign", skin_tones: false },
+    CharEntry { text: "\u{20b9}", name: "indian rupee sign", skin_tones: false },
+    CharEntry { text: "\u{20ba}", name: "turkish lira sign", skin_tones: false },
+    CharEntry { text: "\u{20bb}", name: "nordic mark sign", skin_tones: false },
+    CharEntry { text: "\u{20bc}", name: "manat sign", skin_tones: false },
+    CharEntry { text: "\u{20bd}", name: "ruble sign", skin_tones: false },
+    CharEntry { text: "\u{20be}", name: "lari sign", skin_tones: false },
+    CharEntry { text: "\u{20bf}", name: "bitcoin sign", skin_tones: false },
+    CharEntry { text: "\u{20c0}", name: "som sign", skin_tones: false },
+];
+
+pub const BOX_DRAWING: &[CharEntry] = &[
+    CharEntry { text: "\u{2500}", name: "box drawings light horizontal", skin_tones: false },
+    CharEntry { text: "\u{2501}", name: "box drawings heavy horizontal", skin_tones: false },
+    CharEntry { text: "\u{2502}", name: "box drawings light vertical", skin_tones: false },
+    CharEntry { text: "\u{2503}", name: "box drawings heavy vertical", skin_tones: false },
+    CharEntry { text: "\u{2504}", name: "box drawings light triple dash horizontal", skin_tones: false },
+    CharEntry { text: "\u{2505}", name: "box drawings heavy triple dash horizontal", skin_tones: false },
+    CharEntry { text: "\u{2506}", name: "box drawings light triple dash vertical", skin_tones: false },
+    CharEntry { text: "\u{2507}", name: "box drawings heavy triple dash vertical", skin_tones: false },
+    CharEntry { text: "\u{2508}", name: "box drawings light quadruple dash horizontal", skin_tones: false },
+    CharEntry { text: "\u{2509}", name: "box drawings heavy quadruple dash horizontal", skin_tones: false },
+    CharEntry { text: "\u{250a}", name: "box drawings light quadruple dash vertical", skin_tones: false },
+    CharEntry { text: "\u{250b}", name: "box drawings heavy quadruple dash vertical", skin_tones: false },
+    CharEntry { text: "\u{250c}", name: "box drawings light down and right", skin_tones: false },
+    CharEntry { text: "\u{250d}", name: "box drawings down light and right heavy", skin_tones: false },
+    CharEntry { text: "\u{250e}", name: "box drawings down heavy and right light", skin_tones: false },
+    CharEntry { text: "\u{250f}", name: "box drawings heavy down and right", skin_tones: false },
+    CharEntry { text: "\u{2510}", name: "box drawings light down and left", skin_tones: false },
+    CharEntry { text: "\u{2511}", name: "box drawings down light and left heavy", skin_tones: false },
+    CharEntry { text: "\u{2512}", name: "box drawings down heavy and left light", skin_tones: false },
+    CharEntry { text: "\u{2513}", name: "box drawings heavy down and left", skin_tones: false },
+    CharEntry { text: "\u{2514}", name: "box drawings light up and right", skin_tones: false },
+    CharEntry { text: "\u{2515}", name: "box drawings up light and right heavy", skin_tones: false },
+    CharEntry { text: "\u{2516}", name: "box drawings up heavy and right light", skin_tones: false },
+    CharEntry { text: "\u{2517}", name: "box drawings heavy up and right", skin_tones: false },
+    CharEntry { text: "\u{2518}", name: "box drawings light up and left", skin_tones: false },
+    CharEntry { text: "\u{2519}", name: "box drawings up light and left heavy", skin_tones: false },
+    CharEntry { text: "\u{251a}", name: "box drawings up heavy and left light", skin_tones: false },
+    CharEntry { text: "\u{251b}", name: "box drawings heavy up and left", skin_tones: false },
+    CharEntry { text: "\u{251c}", name: "box drawings light vertical and right", skin_tones: false },
+    CharEntry { text: "\u{251d}", name: "box drawings vertical light and right heavy", skin_tones: false },
+    CharEntry { text: "\u{251e}", name: "box drawings up heavy and right down light", skin_tones: false },
+    CharEntry { text: "\u{251f}", name: "box drawings down heavy and right up light", skin_tones: false },
+    CharEntry { text: "\u{2520}", name: "box drawings vertical heavy and right light", skin_tones: false },
+    CharEntry { text: "\u{2521}", name: "box drawings down light and right up heavy", skin_tones: false },
+    CharEntry { text: "\u{2522}", name: "box drawings up light and right down heavy", skin_tones: false },
+    CharEntry { text: "\u{2523}", name: "box drawings heavy vertical and right", skin_tones: false },
+    CharEntry { text: "\u{2524}", name: "box drawings light vertical and left", skin_tones: false },
+    CharEntry { text: "\u{2525}", name: "box drawings vertical light and left heavy", skin_tones: false },
+    CharEntry { text: "\u{2526}", name: "box drawings up heavy and left down light", skin_tones: false },
+    CharEntry { text: "\u{2527}", name: "box drawings down heavy and left up light", skin_tones: false },
+    CharEntry { text: "\u{2528}", name: "box drawings vertical heavy and left light", skin_tones: false },
+    CharEntry { text: "\u{2529}", name: "box drawings down light and left up heavy", skin_tones: false },
+    CharEntry { text: "\u{252a}", name: "box drawings up light and left down heavy", skin_tones: false },
+    CharEntry { text: "\u{252b}", name: "box drawings heavy vertical and left", skin_tones: false },
+    CharEntry { text: "\u{252c}", name: "box drawings light down and horizontal", skin_tones: false },
+    CharEntry { text: "\u{252d}", name: "box drawings left heavy and right down light", skin_tones: false },
+    CharEntry { text: "\u{252e}", name: "box drawings right heavy and left down light", skin_tones: false },
+    CharEntry { text: "\u{252f}", name: "box drawings down light and horizontal heavy", skin_tones: false },
+    CharEntry { text: "\u{2530}", name: "box drawings down heavy and horizontal light", skin_tones: false },
+    CharEntry { text: "\u{2531}", name: "box drawings right light and left down heavy", skin_tones: false },
+    CharEntry { text: "\u{2532}", name: "box drawings left light and right down heavy", skin_tones: false },
+    CharEntry { text: "\u{2533}", name: "box drawings heavy down and horizontal", skin_tones: false },
+    CharEntry { text: "\u{2534}", name: "box drawings light up and horizontal", skin_tones: false },
+    CharEntry { text: "\u{2535}", name: "box drawings left heavy and right up light", skin_tones: false },
+    CharEntry { text: "\u{2536}", name: "box drawings right heavy and left up light", skin_tones: false },
+    CharEntry { text: "\u{2537}", name: "box drawings up light and horizontal heavy", skin_tones: false },
+    CharEntry { text: "\u{2538}", name: "box drawings up heavy and horizontal light", skin_tones: false },
+    CharEntry { text: "\u{2539}", name: "box drawings right light and left up heavy", skin_tones: false },
+    CharEntry { text: "\u{253a}", name: "box drawings left light and right up heavy", skin_tones: false },
+    CharEntry { text: "\u{253b}", name: "box drawings heavy up and horizontal", skin_tones: false },
+    CharEntry { text: "\u{253c}", name: "box drawings light vertical and horizontal", skin_tones: false },
+    CharEntry { text: "\u{253d}", name: "box drawings left heavy and right vertical light", skin_tones: false },
+    CharEntry { text: "\u{253e}", name: "box drawings right heavy and left vertical light", skin_tones: false },
+    CharEntry { text: "\u{253f}", name: "box drawings vertical light and horizontal heavy", skin_tones: false },
+    CharEntry { text: "\u{2540}", name: "box drawings up heavy and down horizontal light", skin_tones: false },
+    CharEntry { text: "\u{2541}", name: "box drawings down heavy and up horizontal light", skin_tones: false },
+    CharEntry { text: "\u{2542}", name: "box drawings vertical heavy and horizontal light", skin_tones: false },
+    CharEntry { text: "\u{2543}", name: "box drawings left up heavy and right down light", skin_tones: false },
+    CharEntry { text: "\u{2544}", name: "box drawings right up heavy and left down light", skin_tones: false },
+    CharEntry { text: "\u{2545}", name: "box drawings left down heavy and right up light", skin_tones: false },
+    CharEntry { text: "\u{2546}", name: "box drawings right down heavy and left up light", skin_tones: false },
+    CharEntry { text: "\u{2547}", name: "box drawings down light and up horizontal heavy", skin_tones: false },
+    CharEntry { text: "\u{2548}", name: "box drawings up light and down horizontal heavy", skin_tones: false },
+    CharEntry { text: "\u{2549}", name: "box drawings right light and left vertical heavy", skin_tones: false },
+    CharEntry { text: "\u{254a}", name: "box drawings left light and right vertical heavy", skin_tones: false },
+    CharEntry { text: "\u{254b}", name: "box drawings heavy vertical and horizontal", skin_tones: false },
+    CharEntry { text: "\u{254c}", name: "box drawings light double dash horizontal", skin_tones: false },
+    CharEntry { text: "\u{254d}", name: "box drawings heavy double dash horizontal", skin_tones: false },
+    CharEntry { text: "\u{254e}", name: "box drawings light double dash vertical", skin_tones: false },
+    CharEntry { text: "\u{254f}", name: "box drawings heavy double dash vertical", skin_tones: false },
+    CharEntry { text: "\u{2550}", name: "box drawings double horizontal", skin_tones: false },
+    CharEntry { text: "\u{2551}", name: "box drawings double vertical", skin_tones: false },
+    CharEntry { text: "\u{2552}", name: "box drawings down single and right double", skin_tones: false },
+    CharEntry { text: "\u{2553}", name: "box drawings down double and right single", skin_tones: false },
+    CharEntry { text: "\u{2554}", name: "box drawings double down and right", skin_tones: false },
+    CharEntry { text: "\u{2555}", name: "box drawings down single and left double", skin_tones: false },
+    CharEntry { text: "\u{2556}", name: "box drawings down double and left single", skin_tones: false },
+    CharEntry { text: "\u{2557}", name: "box drawings double down and left", skin_tones: false },
+    CharEntry { text: "\u{2558}", name: "box drawings up single and right double", skin_tones: false },
+    CharEntry { text: "\u{2559}", name: "box drawings up double and right single", skin_tones: false },
+    CharEntry { text: "\u{255a}", name: "box drawings double up and right", skin_tones: false },
+    CharEntry { text: "\u{255b}", name: "box drawings up single and left double", skin_tones: false },
+    CharEntry { text: "\u{255c}", name: "box drawings up double and left single", skin_tones: false },
+    CharEntry { text: "\u{255d}", name: "box drawings double up and left", skin_tones: false },
+    CharEntry { text: "\u{255e}", name: "box drawings vertical single and right double", skin_tones: false },
+    CharEntry { text: "\u{255f}", name: "box drawings vertical double and right single", skin_tones: false },
+    CharEntry { text: "\u{2560}", name: "box drawings double vertical and right", skin_tones: false },
+    CharEntry { text: "\u{2561}", name: "box drawings vertical single and left double", skin_tones: false },
+    CharEntry { text: "\u{2562}", name: "box drawings vertical double and left single", skin_tones: false },
+    CharEntry { text: "\u{2563}", name: "box drawings double vertical and left", skin_tones: false },
+    CharEntry { text: "\u{2564}", name: "box drawings down single and horizontal double", skin_tones: false },
+    CharEntry { text: "\u{2565}", name: "box drawings down double and horizontal single", skin_tones: false },
+    CharEntry { text: "\u{2566}", name: "box drawings double down and horizontal", skin_tones: false },
+    CharEntry { text: "\u{2567}", name: "box drawings up single and horizontal double", skin_tones: false },
+    CharEntry { text: "\u{2568}", name: "box drawings up double and horizontal single", skin_tones: false },
+    CharEntry { text: "\u{2569}", name: "box drawings double up and horizontal", skin_tones: false },
+    CharEntry { text: "\u{256a}", name: "box drawings vertical single and horizontal double", skin_tones: false },
+    CharEntry { text: "\u{256b}", name: "box drawings vertical double and horizontal single", skin_tones: false },
+    CharEntry { text: "\u{256c}", name: "box drawings double vertical and horizontal", skin_tones: false },
+    CharEntry { text: "\u{256d}", name: "box drawings light arc down and right", skin_tones: false },
+    CharEntry { text: "\u{256e}", name: "box drawings light arc down and left", skin_tones: false },
+    CharEntry { text: "\u{256f}", name: "box drawings light arc up and left", skin_tones: false },
+    CharEntry { text: "\u{2570}", name: "box drawings light arc up and right", skin_tones: false },
+    CharEntry { text: "\u{2571}", name: "box drawings light diagonal upper right to lower left", skin_tones: false },
+    CharEntry { text: "\u{2572}", name: "box drawings light diagonal upper left to lower right", skin_tones: false },
+    CharEntry { text: "\u{2573}", name: "box drawings light diagonal cross", skin_tones: false },
+    CharEntry { text: "\u{2574}", name: "box drawings light left", skin_tones: false },
+    CharEntry { text: "\u{2575}", name: "box drawings light up", skin_tones: false },
+    CharEntry { text: "\u{2576}", name: "box drawings light right", skin_tones: false },
+    CharEntry { text: "\u{2577}", name: "box drawings light down", skin_tones: false },
+    CharEntry { text: "\u{2578}", name: "box drawings heavy left", skin_tones: false },
+    CharEntry { text: "\u{2579}", name: "box drawings heavy up", skin_tones: false },
+    CharEntry { text: "\u{257a}", name: "box drawings heavy right", skin_tones: false },
+    CharEntry { text: "\u{257b}", name: "box drawings heavy down", skin_tones: false },
+    CharEntry { text: "\u{257c}", name: "box drawings light left and heavy right", skin_tones: false },
+    CharEntry { text: "\u{257d}", name: "box drawings light up and heavy down", skin_tones: false },
+    CharEntry { text: "\u{257e}", name: "box drawings heavy left and light right", skin_tones: false },
+    CharEntry { text: "\u{257f}", name: "box drawings heavy up and light down", skin_tones: false },
+    CharEntry { text: "\u{2580}", name: "upper half block", skin_tones: false },
+    CharEntry { text: "\u{2581}", name: "lower one eighth block", skin_tones: false },
+    CharEntry { text: "\u{2582}", name: "lower one quarter block", skin_tones: false },
+    CharEntry { text: "\u{2583}", name: "lower three eighths block", skin_tones: false },
+    CharEntry { text: "\u{2584}", name: "lower half block", skin_tones: false },
+    CharEntry { text: "\u{2585}", name: "lower five eighths block", skin_tones: false },
+    CharEntry { text: "\u{2586}", name: "lower three quarters block", skin_tones: false },
+    CharEntry { text: "\u{2587}", name: "lower seven eighths block", skin_tones: false },
+    CharEntry { text: "\u{2588}", name: "full block", skin_tones: false },
+    CharEntry { text: "\u{2589}", name: "left seven eighths block", skin_tones: false },
+    CharEntry { text: "\u{258a}", name: "left three quarters block", skin_tones: false },
+    CharEntry { text: "\u{258b}", name: "left five eighths block", skin_tones: false },
+    CharEntry { text: "\u{258c}", name: "left half block", skin_tones: false },
+    CharEntry { text: "\u{258d}", name: "left three eighths block", skin_tones: false },
+    CharEntry { text: "\u{258e}", name: "left one quarter block", skin_tones: false },
+    CharEntry { text: "\u{258f}", name: "left one eighth block", skin_tones: false },
+    CharEntry { text: "\u{2590}", name: "right half block", skin_tones: false },
+    CharEntry { text: "\u{2591}", name: "light shade", skin_tones: false },
+    CharEntry { text: "\u{2592}", name: "medium shade", skin_tones: false },
+    CharEntry { text: "\u{2593}", name: "dark shade", skin_tones: false },
+    CharEntry { text: "\u{2594}", name: "upper one eighth block", skin_tones: false },
+    CharEntry { text: "\u{2595}", name: "right one eighth block", skin_tones: false },
+    CharEntry { text: "\u{2596}", name: "quadrant lower left", skin_tones: false },
+    CharEntry { text: "\u{2597}", name: "quadrant lower right", skin_tones: false },
+    CharEntry { text: "\u{2598}", name: "quadrant upper left", skin_tones: false },
+    CharEntry { text: "\u{2599}", name: "quadrant upper left and lower left and lower right", skin_tones: false },
+    CharEntry { text: "\u{259a}", name: "quadrant upper left and lower right", skin_tones: false },
+    CharEntry { text: "\u{259b}", name: "quadrant upper left and upper right and lower left", skin_tones: false },
+    CharEntry { text: "\u{259c}", name: "quadrant upper left and upper right and lower right", skin_tones: false },
+    CharEntry { text: "\u{259d}", name: "quadrant upper right", skin_tones: false },
+    CharEntry { text: "\u{259e}", name: "quadrant upper right and lower left", skin_tones: false },
+    CharEntry { text: "\u{259f}", name: "quadrant upper right and lower left and lower right", skin_tones: false },
+];
+
+pub const PUNCTUATION: &[CharEntry] = &[
+    CharEntry { text: "\u{2010}", name: "hyphen", skin_tones: false },
+    CharEntry { text: "\u{2011}", name: "non-breaking hyphen", skin_tones: false },
+    CharEntry { text: "\u{2012}", name: "figure dash", skin_tones: false },
+    CharEntry { text: "\u{2013}", name: "en dash", skin_tones: false },
+    CharEntry { text: "\u{2014}", name: "em dash", skin_tones: false },
+    CharEntry { text: "\u{2015}", name: "horizontal bar", skin_tones: false },
+    CharEntry { text: "\u{2016}", name: "double vertical line", skin_tones: false },
+    CharEntry { text: "\u{2017}", name: "double low line", skin_tones: false },
+    CharEntry { text: "\u{2018}", name: "left single quotation mark", skin_tones: false },
+    CharEntry { text: "\u{2019}", name: "right single quotation mark", skin_tones: false },
+    CharEntry { text: "\u{201a}", name: "single low-9 quotation mark", skin_tones: false },
+    CharEntry { text: "\u{201b}", name: "single high-reversed-9 quotation mark", skin_tones: false },
+    CharEntry { text: "\u{201c}", name: "left double quotation mark", skin_tones: false },
+    CharEntry { text: "\u{201d}", name: "right double quotation mark", skin_tones: false },
+    CharEntry { text: "\u{201e}", name: "double low-9 quotation mark", skin_tones: false },
+    CharEntry { text: "\u{201f}", name: "double high-reversed-9 quotation mark", skin_tones: false },
+    CharEntry { text: "\u{2020}", name: "dagger", skin_tones: false },
+    CharEntry { text: "\u{2021}", name: "double dagger", skin_tones: false },
+    CharEntry { text: "\u{2022}", name: "bullet", skin_tones: false },
+    CharEntry { text: "\u{2023}", name: "triangular bullet", skin_tones: false },
+    CharEntry { text: "\u{2024}", name: "one dot leader", skin_tones: false },
+    CharEntry { text: "\u{2025}", name: "two dot leader", skin_tones: false },
+    CharEntry { text: "\u{2026}", name: "horizontal ellipsis", skin_tones: false },
+    CharEntry { text: "\u{2027}", name: "hyphenation point", skin_tones: false },
+    CharEntry { text: "\u{2028}", name: "line separator", skin_tones: false },
+    CharEntry { text: "\u{2029}", name: "paragraph separator", skin_tones: false },
+    CharEntry { text: "\u{202a}", name: "left-to-right embedding", skin_tones: false },
+    CharEntry { text: "\u{202b}", name: "right-to-left embedding", skin_tones: false },
+    CharEntry { text: "\u{202c}", name: "pop directional formatting", skin_tones: false },
+    CharEntry { text: "\u{202d}", name: "left-to-right override", skin_tones: false },
+    CharEntry { text: "\u{202e}", name: "right-to-left override", skin_tones: false },
+    CharEntry { text: "\u{202f}", name: "narrow no-break space", skin_tones: false },
+    CharEntry { text: "\u{2030}", name: "per mille sign", skin_tones: false },
+    CharEntry { text: "\u{2031}", name: "per ten thousand sign", skin_tones: false },
+    CharEntry { text: "\u{2032}", name: "prime", skin_tones: false },
+    CharEntry { text: "\u{2033}", name: "double prime", skin_tones: false },
+    CharEntry { text: "\u{2034}", name: "triple prime", skin_tones: false },
+    CharEntry { text: "\u{2035}", name: "reversed prime", skin_tones: false },
+    CharEntry { text: "\u{2036}", name: "reversed double prime", skin_tones: false },
+    CharEntry { text: "\u{2037}", name: "reversed triple prime", skin_tones: false },
+    CharEntry { text: "\u{2038}", name: "caret", skin_tones: false },
+    CharEntry { text: "\u{2039}", name: "single left-pointing angle quotation mark", skin_tones: false },
+    CharEntry { text: "\u{203a}", name: "single right-pointing angle quotation mark", skin_tones: false },
+    CharEntry { text: "\u{203b}", name: "reference mark", skin_tones: false },
+    CharEntry { text: "\u{203c}", name: "double exclamation mark", skin_tones: false },
+    CharEntry { text: "\u{203d}", name: "interrobang", skin_tones: false },
+    CharEntry { text: "\u{203e}", name: "overline", skin_tones: false },
+    CharEntry { text: "\u{203f}", name: "undertie", skin_tones: false },
+    CharEntry { text: "\u{2040}", name: "character tie", skin_tones: false },
+    CharEntry { text: "\u{2041}", name: "caret insertion point", skin_tones: false },
+    CharEntry { text: "\u{2042}", name: "asterism", skin_tones: false },
+    CharEntry { text: "\u{2043}", name: "hyphen bullet", skin_tones: false },
+    CharEntry { text: "\u{2044}", name: "fraction slash", skin_tones: false },
+    CharEntry { text: "\u{2045}", name: "left square bracket with quill", skin_tones: false },
+    CharEntry { text: "\u{2046}", name: "right square bracket with quill", skin_tones: false },
+    CharEntry { text: "\u{2047}", name: "double question mark", skin_tones: false },
+    CharEntry { text: "\u{2048}", name: "question exclamation mark", skin_tones: false },
+    CharEntry { text: "\u{2049}", name: "exclamation question mark", skin_tones: false },
+    CharEntry { text: "\u{204a}", name: "tironian sign et", skin_tones: false },
+    CharEntry { text: "\u{204b}", name: "reversed pilcrow sign", skin_tones: false },
+    CharEntry { text: "\u{204c}", name: "black leftwards bullet", skin_tones: false },
+    CharEntry { text: "\u{204d}", name: "black rightwards bullet", skin_tones: false },
+    CharEntry { text: "\u{204e}", name: "low asterisk", skin_tones: false },
+    CharEntry { text: "\u{204f}", name: "reversed semicolon", skin_tones: false },
+    CharEntry { text: "\u{2050}", name: "close up", skin_tones: false },
+    CharEntry { text: "\u{2051}", name: "two asterisks aligned vertically", skin_tones: false },
+    CharEntry { text: "\u{2052}", name: "commercial minus sign", skin_tones: false },
+    CharEntry { text: "\u{2053}", name: "swung dash", skin_tones: false },
+    CharEntry { text: "\u{2054}", name: "inverted undertie", skin_tones: false },
+    CharEntry { text: "\u{2055}", name: "flower punctuation mark", skin_tones: false },
+    CharEntry { text: "\u{2056}", name: "three dot punctuation", skin_tones: false },
+    CharEntry { text: "\u{2057}", name: "quadruple prime", skin_tones: false },
+    CharEntry { text: "\u{2058}", name: "four dot punctuation", skin_tones: false },
+    CharEntry { text: "\u{2059}", name: "five dot punctuation", skin_tones: false },
+    CharEntry { text: "\u{205a}", name: "two dot punctuation", skin_tones: false },
+    CharEntry { text: "\u{205b}", name: "four dot mark", skin_tones: false },
+    CharEntry { text: "\u{205c}", name: "dotted cross", skin_tones: false },
+    CharEntry { text: "\u{205d}", name: "tricolon", skin_tones: false },
+    CharEntry { text: "\u{205e}", name: "vertical four dots", skin_tones: false },
+    CharEntry { text: "\u{a1}", name: "inverted exclamation mark", skin_tones: false },
+    CharEntry { text: "\u{a7}", name: "section sign", skin_tones: false },
+    CharEntry { text: "\u{ab}", name: "left-pointing double angle quotation mark", skin_tones: false },
+    CharEntry { text: "\u{b6}", name: "pilcrow sign", skin_tones: false },
+    CharEntry { text: "\u{b7}", name: "middle dot", skin_tones: false },
+    CharEntry { text: "\u{bb}", name: "right-pointing double angle quotation mark", skin_tones: false },
+    CharEntry { text: "\u{bf}", name: "inverted question mark", skin_tones: false },
+];
@@ -0,0 +1,227 @@
+//! CharSelect presents emoji and other symbols, along with any
+//! `char_select_custom_sets` from the config, and inserts the chosen
+//! text into the pane, or copies it to the clipboard.
+use crate::overlay::picker::{run_picker, Picker, PickerGroup, PickerItem};
+use crate::termwindow::TermWindowNotif;
+use config::configuration;
+use config::keyassignment::CharSelectArguments;
+use mux::pane::PaneId;
+use mux::termwiztermtab::TermWizTerminal;
+use mux::Mux;
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::sync::Mutex;
+use window::WindowOps;
+
+#[rustfmt::skip]
+mod data;
+
+/// How many of the recently chosen entries are remembered
+const MAX_RECENT: usize = 32;
+
+lazy_static::lazy_static! {
+    /// The most recently chosen text is held at the front
+    static ref RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+}
+
+pub struct CharEntry {
+    pub text: &'static str,
+    pub name: &'static str,
+    /// Whether the emoji accepts a skin tone modifier
+    pub skin_tones: bool,
+}
+
+/// The Fitzpatrick modifiers, U+1F3FB through U+1F3FF
+const SKIN_TONES: &[(char, &str)] = &[
+    ('\u{1f3fb}', "Light skin tone"),
+    ('\u{1f3fc}', "Medium-light skin tone"),
+    ('\u{1f3fd}', "Medium skin tone"),
+    ('\u{1f3fe}', "Medium-dark skin tone"),
+    ('\u{1f3ff}', "Dark skin tone"),
+];
+
+const BUILTIN_GROUPS: &[(&str, &[CharEntry])] = &[
+    ("Emoji", data::EMOJI),
+    ("Arrows", data::ARROWS),
+    ("Math", data::MATH),
+    ("Greek", data::GREEK),
+    ("Currency", data::CURRENCY),
+    ("Box Drawing", data::BOX_DRAWING),
+    ("Punctuation", data::PUNCTUATION),
+];
+
+const RECENTLY_USED: &str = "Recently Used";
+
+/// Applies a skin tone modifier to an emoji.  The modifier follows
+/// the base codepoint, and replaces any emoji presentation selector.
+fn with_skin_tone(text: &str, modifier: char) -> String {
+    let mut chars = text.chars();
+    let mut result = String::new();
+    if let Some(base) = chars.next() {
+        result.push(base);
+        result.push(modifier);
+    }
+    result.extend(chars.filter(|&c| c != '\u{fe0f}'));
+    result
+}
+
+fn entry_label(text: &str, name: &str) -> String {
+    if name.is_empty() {
+        text.to_string()
+    } else {
+        format!("{}  {}", text, name)
+    }
+}
+
+fn builtin_item(entry: &CharEntry) -> PickerItem {
+    let mut item = PickerItem::new(entry_label(entry.text, entry.name), entry.text.to_string());
+    if entry.skin_tones {
+        item.variants = SKIN_TONES
+            .iter()
+            .map(|&(modifier, _)| {
+                let text = with_skin_tone(entry.text, modifier);
+                PickerItem::new(entry_label(&text, entry.name), text)
+            })
+            .collect();
+    }
+    item
+}
+
+fn record_recent(text: &str) {
+    let mut recent = RECENT.lock().unwrap();
+    recent.retain(|entry| entry != text);
+    recent.push_front(text.to_string());
+    recent.truncate(MAX_RECENT);
+}
+
+/// Returns the recently used group, followed by the built-in
+/// groups and then the groups from `char_select_custom_sets`
+fn build_groups() -> Vec<PickerGroup> {
+    let config = configuration();
+    let mut groups: Vec<PickerGroup> = BUILTIN_GROUPS
+        .iter()
+        .map(|(name, entries)| PickerGroup {
+            name: name.to_string(),
+            items: entries.iter().map(builtin_item).collect(),
+            empty_message: "this group is empty".to_string(),
+        })
+        .collect();
+    for set in &config.char_select_custom_sets {
+        groups.push(PickerGroup {
+            name: set.name.clone(),
+            items: set
+                .entries
+                .iter()
+                .map(|entry| {
+                    PickerItem::new(entry_label(&entry.text, &entry.name), entry.text.clone())
+                })
+                .collect(),
+            empty_message: "this group is empty".to_string(),
+        });
+    }
+
+    // Label the recently used entries in the same way as
+    // the group that they were chosen from
+    let labels: HashMap<&str, &str> = groups
+        .iter()
+        .flat_map(|group| group.items.iter())
+        .flat_map(|item| std::iter::once(item).chain(item.variants.iter()))
+        .map(|item| (item.value.as_str(), item.label.as_str()))
+        .collect();
+    let recent = PickerGroup {
+        name: RECENTLY_USED.to_string(),
+        items: RECENT
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|text| {
+                let label = labels
+                    .get(text.as_str())
+                    .map(|label| label.to_string())
+                    .unwrap_or_else(|| text.clone());
+                PickerItem::new(label, text.clone())
+            })
+            .collect(),
+        empty_message: "nothing has been chosen yet".to_string(),
+    };
+
+    groups.insert(0, recent);
+    groups
+}
+
+pub fn char_select(
+    args: CharSelectArguments,
+    pane_id: PaneId,
+    term: TermWizTerminal,
+    window: ::window::Window,
+) -> anyhow::Result<()> {
+    let groups = build_groups();
+
+    let initial_group = match &args.group {
+        Some(name) => groups
+            .iter()
+            .position(|group| group.name.eq_ignore_ascii_case(name))
+            .unwrap_or_else(|| {
+                log::warn!("CharSelect: there is no group named {}", name);
+                0
+            }),
+        None => 0,
+    };
+    // Start with the emoji when nothing has been used yet
+    let initial_group = if initial_group == 0 && groups[0].items.is_empty() {
+        1
+    } else {
+        initial_group
+    };
+
+    let variant_names = std::iter::once("Default skin tone")
+        .chain(SKIN_TONES.iter().map(|&(_, name)| name))
+        .map(|name| name.to_string())
+        .collect();
+
+    run_picker(
+        Picker {
+            title: "Select Character".to_string(),
+            groups,
+            initial_group,
+            variant_names,
+            accept_hint: if args.copy_on_select {
+                "copy".to_string()
+            } else {
+                "insert".to_string()
+            },
+            show_preview: false,
+            on_accept: Box::new(move |text| {
+                record_recent(&text);
+                window.notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                    if args.copy_on_select {
+                        term_window.copy_to_clipboard(args.copy_to.clone(), text);
+                    } else if let Some(pane) = Mux::get().and_then(|mux| mux.get_pane(pane_id)) {
+                        pane.writer().write_all(text.as_bytes()).ok();
+                        term_window.maybe_scroll_to_bottom_for_input(&pane);
+                    }
+                })));
+            }),
+        },
+        term,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn skin_tone() {
+        // waving hand sign
+        assert_eq!(
+            with_skin_tone("\u{1f44b}", '\u{1f3fd}'),
+            "\u{1f44b}\u{1f3fd}"
+        );
+        // victory hand, with the emoji presentation selector
+        assert_eq!(
+            with_skin_tone("\u{270c}\u{fe0f}", '\u{1f3fb}'),
+            "\u{270c}\u{1f3fb}"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+//! The clipboard history picker presents the text that was recently
+//! copied within wezterm and pastes the selected entry into the pane.
+//! The lower part of the overlay shows a preview of the selected entry.
+use crate::overlay::picker::{run_picker, Picker, PickerGroup, PickerItem};
+use crate::termwindow::TermWindowNotif;
+use mux::pane::PaneId;
+use mux::termwiztermtab::TermWizTerminal;
+use mux::Mux;
+use window::WindowOps;
+
+/// Produces a single line label for an entry
+pub fn entry_label(text: &str) -> String {
+    text.trim()
+        .chars()
+        .map(|c| match c {
+            '\n' => '⏎',
+            '\r' | '\t' => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+pub fn clipboard_history_picker(
+    entries: Vec<String>,
+    pane_id: PaneId,
+    term: TermWizTerminal,
+    window: ::window::Window,
+) -> anyhow::Result<()> {
+    let items = entries
+        .into_iter()
+        .map(|text| PickerItem::new(entry_label(&text), text))
+        .collect();
+
+    run_picker(
+        Picker {
+            title: "Clipboard History".to_string(),
+            groups: vec![PickerGroup {
+                name: "Clipboard History".to_string(),
+                items,
+                empty_message: "the clipboard history is empty".to_string(),
+            }],
+            initial_group: 0,
+            variant_names: vec![],
+            accept_hint: "paste".to_string(),
+            show_preview: true,
+            on_accept: Box::new(move |text| {
+                window.notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                    if let Some(pane) = Mux::get().and_then(|mux| mux.get_pane(pane_id)) {
+                        if let Some(text) = mux_lua::apply_pre_paste_hook(pane_id, text) {
+                            pane.trickle_paste(text).ok();
+                            term_window.maybe_scroll_to_bottom_for_input(&pane);
+                        }
+                    }
+                })));
+            }),
+        },
+        term,
+    )
+}
@@ -0,0 +1,68 @@
+//! Presents the suggestion that the `command-hint` event returned for
+//! a command that failed, and performs the action that is chosen.
+use crate::overlay::picker::{run_picker, Picker, PickerGroup, PickerItem};
+use crate::termwindow::TermWindowNotif;
+use config::keyassignment::KeyAssignment;
+use mux::pane::PaneId;
+use mux::termwiztermtab::TermWizTerminal;
+use mux::Mux;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+use window::WindowOps;
+
+/// A suggestion returned by the `command-hint` event
+#[derive(Debug, Clone, FromDynamic, ToDynamic)]
+pub struct CommandHint {
+    pub message: String,
+    #[dynamic(default)]
+    pub actions: Vec<CommandHintAction>,
+}
+
+#[derive(Debug, Clone, FromDynamic, ToDynamic)]
+pub struct CommandHintAction {
+    pub label: String,
+    pub action: KeyAssignment,
+}
+
+pub fn command_hint_picker(
+    hint: CommandHint,
+    pane_id: PaneId,
+    term: TermWizTerminal,
+    window: ::window::Window,
+) -> anyhow::Result<()> {
+    let items = hint
+        .actions
+        .iter()
+        .enumerate()
+        .map(|(idx, action)| PickerItem::new(action.label.clone(), idx.to_string()))
+        .collect();
+    let actions = hint.actions;
+
+    run_picker(
+        Picker {
+            title: hint.message.clone(),
+            groups: vec![PickerGroup {
+                name: "Suggestions".to_string(),
+                items,
+                empty_message: hint.message,
+            }],
+            initial_group: 0,
+            variant_names: vec![],
+            accept_hint: "run".to_string(),
+            show_preview: false,
+            on_accept: Box::new(move |idx| {
+                let action = match idx.parse::<usize>().ok().and_then(|idx| actions.get(idx)) {
+                    Some(action) => action.action.clone(),
+                    None => return,
+                };
+                window.notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                    if let Some(pane) = Mux::get().and_then(|mux| mux.get_pane(pane_id)) {
+                        if let Err(err) = term_window.perform_key_assignment(&pane, &action) {
+                            log::error!("command hint action {:?}: {:#}", action, err);
+                        }
+                    }
+                })));
+            }),
+        },
+        term,
+    )
+}
@@ -10,7 +10,7 @@ use termwiz::input::{InputEvent, KeyCode, KeyEvent, MouseButtons, MouseEvent};
 use termwiz::surface::{Change, CursorVisibility, Position};
 use termwiz::terminal::Terminal;
 
-fn run_confirmation_app(message: &str, term: &mut TermWizTerminal) -> anyhow::Result<bool> {
+pub fn run_confirmation_app(message: &str, term: &mut TermWizTerminal) -> anyhow::Result<bool> {
     term.set_raw_mode()?;
 
     let size = term.get_screen_size()?;
@@ -248,3 +248,65 @@ pub fn confirm_quit_program(
 
     Ok(())
 }
+
+pub fn confirm_assign_clipboard(
+    pane_id: PaneId,
+    mut term: TermWizTerminal,
+    window: ::window::Window,
+    domain_name: String,
+    clipboard: ::window::Clipboard,
+    text: String,
+) -> anyhow::Result<()> {
+    // Show the start of the text on a single line
+    let mut preview: String = text
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .take(60)
+        .collect();
+    if text.chars().count() > 60 {
+        preview.push('…');
+    }
+    let message = format!(
+        "📋 A program in the {} domain wants to set the clipboard to \"{}\". Allow it?",
+        domain_name, preview
+    );
+    if run_confirmation_app(&message, &mut term)? {
+        let window = window.clone();
+        promise::spawn::spawn_into_main_thread(async move {
+            use ::window::WindowOps;
+            crate::clipboardhistory::record_copy(&text);
+            window.set_clipboard(clipboard, text);
+        })
+        .detach();
+    }
+    TermWindow::schedule_cancel_overlay_for_pane(window, pane_id);
+
+    Ok(())
+}
+
+/// Asks the user whether a program in a domain whose clipboard_policy
+/// is `Prompt` may read the clipboard
+pub fn confirm_read_clipboard(
+    pane_id: PaneId,
+    mut term: TermWizTerminal,
+    window: ::window::Window,
+    domain_name: String,
+    clipboard: ::window::Clipboard,
+) -> anyhow::Result<()> {
+    let message = format!(
+        "📋 A program in the {} domain wants to read the clipboard. Allow it?",
+        domain_name
+    );
+    if run_confirmation_app(&message, &mut term)? {
+        let window = window.clone();
+        promise::spawn::spawn_into_main_thread(async move {
+            crate::termwindow::clipboard::answer_clipboard_query_from_clipboard(
+                &window, pane_id, clipboard,
+            );
+        })
+        .detach();
+    }
+    TermWindow::schedule_cancel_overlay_for_pane(window, pane_id);
+
+    Ok(())
+}
@@ -1,10 +1,12 @@
+use crate::overlay::{OverlayLifecycle, OverlayViewState};
 use crate::selection::{SelectionCoordinate, SelectionRange, SelectionX};
 use crate::termwindow::{TermWindow, TermWindowNotif};
 use config::keyassignment::{
-    CopyModeAssignment, KeyAssignment, KeyTable, KeyTableEntry, ScrollbackEraseMode, SelectionMode,
+    CopyModeAssignment, KeyAssignment, KeyTable, KeyTableEntry, MouseEventTrigger, MouseTable,
+    ScrollbackEraseMode, SelectionMode,
 };
 use mux::domain::DomainId;
-use mux::pane::{Pane, PaneId, Pattern, SearchResult};
+use mux::pane::{Pane, PaneId, Pattern, SearchResult, WorkingDirAccuracy};
 use mux::renderable::*;
 use mux::tab::TabId;
 use rangeset::RangeSet;
@@ -19,6 +21,7 @@ use termwiz::surface::{CursorVisibility, SequenceNo, SEQ_ZERO};
 use unicode_segmentation::*;
 use url::Url;
 use wezterm_term::color::ColorPalette;
+use wezterm_term::input::MouseButton;
 use wezterm_term::{
     unicode_column_width, Clipboard, KeyCode, KeyModifiers, Line, MouseEvent, StableRowIndex,
     TerminalSize,
@@ -76,6 +79,19 @@ pub struct CopyModeParams {
     pub editing_search: bool,
 }
 
+/// The portion of the copy mode state that describes what the
+/// user is looking at and what they have selected
+#[derive(Debug, Clone)]
+pub struct CopyViewState {
+    cursor: StableCursorPosition,
+    start: Option<SelectionCoordinate>,
+    selection_mode: SelectionMode,
+    viewport: Option<StableRowIndex>,
+    pattern: Pattern,
+    editing_search: bool,
+    result_pos: Option<usize>,
+}
+
 impl CopyOverlay {
     pub fn with_pane(
         term_window: &TermWindow,
@@ -132,6 +148,16 @@ impl CopyOverlay {
         })
     }
 
+    /// Returns the rows on which the current search matches start
+    pub fn search_match_rows(&self) -> Vec<StableRowIndex> {
+        self.render
+            .borrow()
+            .results
+            .iter()
+            .map(|result| result.start_y)
+            .collect()
+    }
+
     pub fn get_params(&self) -> CopyModeParams {
         let render = self.render.borrow();
         CopyModeParams {
@@ -151,7 +177,58 @@ impl CopyOverlay {
         render.dirty_results.add(search_row);
     }
 
-    pub fn viewport_changed(&self, viewport: Option<StableRowIndex>) {
+    /// Moves the cursor to the specified cell, which is where the
+    /// mouse is pointing.  With no selection mode, any selection is
+    /// cleared; otherwise the selection is started at the cursor, if
+    /// there isn't one already, and extended to the new position.
+    pub fn move_to_mouse_cursor(&self, x: usize, y: StableRowIndex, mode: Option<SelectionMode>) {
+        self.render.borrow_mut().move_to_cell(x, y, mode);
+    }
+}
+
+impl OverlayLifecycle for CopyOverlay {
+    fn save_view_state(&self) -> OverlayViewState {
+        let render = self.render.borrow();
+        OverlayViewState::Copy(CopyViewState {
+            cursor: render.cursor,
+            start: render.start,
+            selection_mode: render.selection_mode,
+            viewport: render.viewport,
+            pattern: render.pattern.clone(),
+            editing_search: render.editing_search,
+            result_pos: render.result_pos,
+        })
+    }
+
+    fn restore_view_state(&self, state: OverlayViewState) {
+        let state = match state {
+            OverlayViewState::Copy(state) => state,
+            _ => return,
+        };
+        let mut render = self.render.borrow_mut();
+        render.editing_search = state.editing_search;
+        if render.pattern != state.pattern {
+            render.pattern = state.pattern;
+            render.update_search();
+        }
+        // Setting result_pos after update_search tells the pending
+        // search completion to leave the cursor where it is
+        render.result_pos = state.result_pos;
+        render.cursor = state.cursor;
+        render.start = state.start;
+        render.selection_mode = state.selection_mode;
+        render.viewport = state.viewport;
+
+        let search_row = render.compute_search_row();
+        render.dirty_results.add(search_row);
+        if render.start.is_some() {
+            render.select_to_cursor_pos();
+        } else {
+            render.set_viewport(state.viewport);
+        }
+    }
+
+    fn viewport_changed(&self, viewport: Option<StableRowIndex>) {
         let mut render = self.render.borrow_mut();
         if render.viewport != viewport {
             if let Some(last) = render.last_bar_pos.take() {
@@ -258,7 +335,13 @@ impl CopyRenderable {
                             let num_results = r.results.len();
 
                             if !r.results.is_empty() {
-                                r.activate_match_number(num_results - 1);
+                                match r.result_pos {
+                                    // The position was carried over from before
+                                    // a resize or a view state restore, so leave
+                                    // the cursor and selection alone
+                                    Some(n) if n < num_results => {}
+                                    _ => r.activate_match_number(num_results - 1),
+                                }
                             } else {
                                 r.set_viewport(None);
                                 r.clear_selection();
@@ -741,6 +824,36 @@ impl CopyRenderable {
             }
         }
     }
+
+    /// Switches between rectangular and cell selection, starting a
+    /// rectangular selection if there isn't one already
+    fn toggle_rectangular_selection(&mut self) {
+        let mode = if self.start.is_some() && self.selection_mode == SelectionMode::Block {
+            SelectionMode::Cell
+        } else {
+            SelectionMode::Block
+        };
+        self.set_selection_mode(&Some(mode));
+    }
+
+    fn move_to_cell(&mut self, x: usize, y: StableRowIndex, mode: Option<SelectionMode>) {
+        match mode {
+            None => {
+                self.start.take();
+                self.clear_selection();
+            }
+            Some(mode) => {
+                if self.start.is_none() {
+                    let coord = SelectionCoordinate::x_y(self.cursor.x, self.cursor.y);
+                    self.start.replace(coord);
+                }
+                self.selection_mode = mode;
+            }
+        }
+        self.cursor.x = x;
+        self.cursor.y = y;
+        self.select_to_cursor_pos();
+    }
 }
 
 impl Pane for CopyOverlay {
@@ -772,6 +885,14 @@ impl Pane for CopyOverlay {
         self.delegate.resize(size)
     }
 
+    fn set_read_only(&self, read_only: bool) {
+        self.delegate.set_read_only(read_only)
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.delegate.is_read_only()
+    }
+
     fn key_up(&self, _key: KeyCode, _mods: KeyModifiers) -> anyhow::Result<()> {
         Ok(())
     }
@@ -833,6 +954,10 @@ impl Pane for CopyOverlay {
                     EditPattern => render.edit_pattern(),
                     AcceptPattern => render.accept_pattern(),
                     SetSelectionMode(mode) => render.set_selection_mode(mode),
+                    ToggleRectangularSelection => render.toggle_rectangular_selection(),
+                    // These need to know where the mouse is, so they
+                    // are handled by the TermWindow
+                    MoveToMouseCursor | SelectToMouseCursor(_) => return false,
                 }
                 true
             }
@@ -881,6 +1006,10 @@ impl Pane for CopyOverlay {
         self.delegate.get_current_working_dir()
     }
 
+    fn get_current_working_dir_accuracy(&self) -> Option<WorkingDirAccuracy> {
+        self.delegate.get_current_working_dir_accuracy()
+    }
+
     fn get_cursor_position(&self) -> StableCursorPosition {
         let renderer = self.render.borrow();
         if renderer.editing_search {
@@ -1042,7 +1171,13 @@ pub fn search_key_table() -> KeyTable {
             KeyAssignment::CopyMode(CopyModeAssignment::ClearPattern),
         ),
     ] {
-        table.insert((key, mods), KeyTableEntry { action });
+        table.insert(
+            (key, mods),
+            KeyTableEntry {
+                action,
+                description: None,
+            },
+        );
     }
     table
 }
@@ -1220,6 +1355,11 @@ pub fn copy_key_table() -> KeyTable {
                 SelectionMode::Block,
             ))),
         ),
+        (
+            WKeyCode::Char('r'),
+            Modifiers::NONE,
+            KeyAssignment::CopyMode(CopyModeAssignment::ToggleRectangularSelection),
+        ),
         (
             WKeyCode::Char('G'),
             Modifiers::SHIFT,
@@ -1301,7 +1441,72 @@ pub fn copy_key_table() -> KeyTable {
             KeyAssignment::CopyMode(CopyModeAssignment::MoveToSelectionOtherEndHoriz),
         ),
     ] {
-        table.insert((key, mods), KeyTableEntry { action });
+        table.insert(
+            (key, mods),
+            KeyTableEntry {
+                action,
+                description: None,
+            },
+        );
+    }
+    table
+}
+
+pub fn copy_mouse_table() -> MouseTable {
+    let mut table = MouseTable::default();
+    for (event, mods, action) in [
+        (
+            MouseEventTrigger::Down {
+                streak: 1,
+                button: MouseButton::Left,
+            },
+            Modifiers::NONE,
+            KeyAssignment::CopyMode(CopyModeAssignment::MoveToMouseCursor),
+        ),
+        (
+            MouseEventTrigger::Down {
+                streak: 1,
+                button: MouseButton::Left,
+            },
+            Modifiers::ALT,
+            KeyAssignment::CopyMode(CopyModeAssignment::MoveToMouseCursor),
+        ),
+        (
+            MouseEventTrigger::Down {
+                streak: 1,
+                button: MouseButton::Left,
+            },
+            Modifiers::SHIFT,
+            KeyAssignment::CopyMode(CopyModeAssignment::SelectToMouseCursor(SelectionMode::Cell)),
+        ),
+        (
+            MouseEventTrigger::Drag {
+                streak: 1,
+                button: MouseButton::Left,
+            },
+            Modifiers::NONE,
+            KeyAssignment::CopyMode(CopyModeAssignment::SelectToMouseCursor(SelectionMode::Cell)),
+        ),
+        (
+            MouseEventTrigger::Drag {
+                streak: 1,
+                button: MouseButton::Left,
+            },
+            Modifiers::ALT,
+            KeyAssignment::CopyMode(CopyModeAssignment::SelectToMouseCursor(
+                SelectionMode::Block,
+            )),
+        ),
+        (
+            MouseEventTrigger::Up {
+                streak: 1,
+                button: MouseButton::Left,
+            },
+            Modifiers::NONE,
+            KeyAssignment::Nop,
+        ),
+    ] {
+        table.insert((event, mods), action);
     }
     table
 }
@@ -1,12 +1,13 @@
 use crate::scripting::guiwin::GuiWin;
 use chrono::prelude::*;
 use futures::FutureExt;
-use log::Level;
+use log::{Level, LevelFilter};
 use luahelper::ValuePrinter;
 use mlua::Value;
 use mux::termwiztermtab::TermWizTerminal;
 use std::io::Write;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Mutex;
 use termwiz::cell::{AttributeChange, CellAttributes, Intensity};
 use termwiz::color::AnsiColor;
@@ -19,6 +20,73 @@ lazy_static::lazy_static! {
     static ref LATEST_LOG_ENTRY: Mutex<Option<DateTime<Local>>> = Mutex::new(None);
 }
 
+/// Controls which log entries are shown in the overlay
+struct LogFilter {
+    level: LevelFilter,
+    /// Only entries whose target starts with this are shown
+    target: String,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::Trace,
+            target: String::new(),
+        }
+    }
+}
+
+impl LogFilter {
+    fn matches(&self, entry: &env_bootstrap::ringlog::Entry) -> bool {
+        entry.level <= self.level && entry.target.starts_with(&self.target)
+    }
+
+    fn describe(&self) -> String {
+        if self.target.is_empty() {
+            format!("showing {} and more severe log entries", self.level)
+        } else {
+            format!(
+                "showing {} and more severe log entries from {}",
+                self.level, self.target
+            )
+        }
+    }
+}
+
+/// Commands that start with a colon are handled by the overlay
+/// rather than being evaluated as lua
+enum OverlayCommand {
+    /// `:level LEVEL`
+    Level(LevelFilter),
+    /// `:filter [MODULE]`
+    Filter(String),
+    /// `:logs`
+    Logs,
+}
+
+impl OverlayCommand {
+    const NAMES: &'static [&'static str] = &["filter", "level", "logs"];
+
+    /// Returns None if `line` is not a command, or an error
+    /// message if it is not a valid command
+    fn parse(line: &str) -> Option<Result<Self, String>> {
+        let line = line.strip_prefix(':')?;
+        let mut words = line.split_whitespace();
+        let result = match (words.next(), words.next(), words.next()) {
+            (Some("level"), Some(level), None) => LevelFilter::from_str(level)
+                .map(Self::Level)
+                .map_err(|_| format!("{} is not a valid log level", level)),
+            (Some("filter"), target, None) => Ok(Self::Filter(target.unwrap_or("").to_string())),
+            (Some("logs"), None, None) => Ok(Self::Logs),
+            _ => Err("usage: `:level LEVEL`, `:filter [MODULE]` or `:logs`".to_string()),
+        };
+        Some(result)
+    }
+}
+
+/// The number of lines that are retained in the history file
+const MAX_HISTORY: usize = 1000;
+
 struct LuaReplHost {
     history: BasicHistory,
     lua: mlua::Lua,
@@ -32,9 +100,16 @@ impl LuaReplHost {
     fn new(lua: mlua::Lua) -> Self {
         let mut history = BasicHistory::default();
         if let Ok(data) = std::fs::read_to_string(history_file_name()) {
-            for line in data.lines() {
+            let lines: Vec<&str> = data.lines().collect();
+            let lines = &lines[lines.len().saturating_sub(MAX_HISTORY)..];
+            for line in lines {
                 history.add(line);
             }
+            if lines.len() < data.lines().count() {
+                let mut trimmed = lines.join("\n");
+                trimmed.push('\n');
+                std::fs::write(history_file_name(), trimmed).ok();
+            }
         }
         Self { history, lua }
     }
@@ -95,11 +170,125 @@ fn fragment_to_expr_or_statement(lua: &mlua::Lua, text: &str) -> Result<String,
     }
 }
 
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+/// Returns the fields of `value` that can be completed: the entries
+/// of a table, or the methods of a userdata object such as `window`
+fn completion_fields<'lua>(value: &Value<'lua>) -> Vec<(String, Value<'lua>)> {
+    let table = match value {
+        Value::Table(t) => t.clone(),
+        Value::UserData(ud) => match ud
+            .get_metatable()
+            .and_then(|mt| mt.get::<_, mlua::Table>(mlua::MetaMethod::Index))
+        {
+            Ok(methods) => methods,
+            Err(_) => return vec![],
+        },
+        _ => return vec![],
+    };
+    table
+        .pairs::<Value, Value>()
+        .filter_map(|pair| match pair {
+            Ok((Value::String(name), value)) => Some((name.to_str().ok()?.to_string(), value)),
+            _ => None,
+        })
+        .filter(|(name, _)| is_identifier(name))
+        .collect()
+}
+
+/// Resolves a dotted path such as `wezterm.mux` to its value.
+/// Only the entries of tables and the methods of userdata are
+/// considered, so that completion has no side effects.
+fn resolve_path<'lua>(lua: &'lua mlua::Lua, path: &str) -> Option<Value<'lua>> {
+    let mut value = Value::Table(lua.globals());
+    for name in path.split('.') {
+        value = completion_fields(&value)
+            .into_iter()
+            .find(|(field, _)| field == name)?
+            .1;
+    }
+    Some(value)
+}
+
+/// Returns the names that complete the lua expression that ends at the
+/// cursor, along with the byte offset at which the partial name starts
+fn complete_lua(lua: &mlua::Lua, before_cursor: &str) -> (usize, Vec<String>) {
+    let word_start = before_cursor
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | ':')))
+        .map(|(idx, c)| idx + c.len_utf8())
+        .unwrap_or(0);
+    let word = &before_cursor[word_start..];
+
+    let (value, methods_only, prefix) = match word.rfind(|c: char| c == '.' || c == ':') {
+        Some(idx) => {
+            let base = &word[..idx];
+            if base.contains(':') {
+                // We can't know what a method call will return
+                return (0, vec![]);
+            }
+            (
+                resolve_path(lua, base),
+                &word[idx..=idx] == ":",
+                &word[idx + 1..],
+            )
+        }
+        None => (Some(Value::Table(lua.globals())), false, word),
+    };
+    let value = match value {
+        Some(value) => value,
+        None => return (0, vec![]),
+    };
+
+    let mut names: Vec<String> = completion_fields(&value)
+        .into_iter()
+        .filter(|(name, value)| {
+            name.starts_with(prefix) && (!methods_only || matches!(value, Value::Function(_)))
+        })
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+    names.dedup();
+    (before_cursor.len() - prefix.len(), names)
+}
+
 impl LineEditorHost for LuaReplHost {
     fn history(&mut self) -> &mut dyn History {
         &mut self.history
     }
 
+    fn complete(&self, line: &str, cursor_position: usize) -> Vec<CompletionCandidate> {
+        let before_cursor = &line[..cursor_position];
+        let (start, names) = match before_cursor.strip_prefix(':') {
+            Some(command) if !command.contains(char::is_whitespace) => (
+                1,
+                OverlayCommand::NAMES
+                    .iter()
+                    .filter(|name| name.starts_with(command))
+                    .map(|name| name.to_string())
+                    .collect(),
+            ),
+            Some(_) => return vec![],
+            None => complete_lua(&self.lua, before_cursor),
+        };
+        names
+            .into_iter()
+            .map(|text| CompletionCandidate {
+                range: start..cursor_position,
+                text,
+            })
+            .collect()
+    }
+
     fn resolve_action(
         &mut self,
         event: &InputEvent,
@@ -124,7 +313,11 @@ impl LineEditorHost for LuaReplHost {
     fn render_preview(&self, line: &str) -> Vec<OutputElement> {
         let mut preview = vec![];
 
-        if let Err(err) = fragment_to_expr_or_statement(&self.lua, line) {
+        if let Some(Err(err)) = OverlayCommand::parse(line) {
+            preview.push(OutputElement::Text(err))
+        } else if line.starts_with(':') {
+            // A valid command
+        } else if let Err(err) = fragment_to_expr_or_statement(&self.lua, line) {
             preview.push(OutputElement::Text(err))
         }
 
@@ -135,6 +328,7 @@ impl LineEditorHost for LuaReplHost {
 pub fn show_debug_overlay(
     mut term: TermWizTerminal,
     gui_win: GuiWin,
+    pane: mux_lua::MuxPane,
     opengl_info: String,
 ) -> anyhow::Result<()> {
     term.no_grab_mouse_in_raw_mode();
@@ -152,22 +346,33 @@ pub fn show_debug_overlay(
 
     lua.load("wezterm = require 'wezterm'").exec()?;
     lua.globals().set("window", gui_win)?;
+    lua.globals().set("pane", pane)?;
 
     let mut host = Some(LuaReplHost::new(lua));
 
     term.render(&[Change::Title("Debug".to_string())])?;
 
-    fn print_new_log_entries(term: &mut TermWizTerminal) -> termwiz::Result<()> {
+    /// Prints the log entries that match `filter`.
+    /// Unless `all` is true, only entries that have not been
+    /// printed before are considered.
+    fn print_log_entries(
+        term: &mut TermWizTerminal,
+        filter: &LogFilter,
+        all: bool,
+    ) -> termwiz::Result<()> {
         let entries = env_bootstrap::ringlog::get_entries();
         let mut changes = vec![];
         for entry in entries {
             if let Some(latest) = LATEST_LOG_ENTRY.lock().unwrap().as_ref() {
-                if entry.then <= *latest {
+                if entry.then <= *latest && !all {
                     // already seen this one
                     continue;
                 }
             }
             LATEST_LOG_ENTRY.lock().unwrap().replace(entry.then);
+            if !filter.matches(&entry) {
+                continue;
+            }
 
             changes.push(Change::AllAttributes(CellAttributes::default()));
             changes.push(Change::Text(entry.then.format("%H:%M:%S%.3f ").to_string()));
@@ -200,12 +405,18 @@ pub fn show_debug_overlay(
          wezterm version: {}\r\n\
          OpenGL version: {opengl_info}\r\n\
          Enter lua statements or expressions and hit Enter.\r\n\
+         Press Tab to complete the names of functions, fields and methods,\r\n\
+         such as `wezterm.mux.` or `window:`.\r\n\
+         Use `:level LEVEL` and `:filter MODULE` to choose which log entries\r\n\
+         are shown, and `:logs` to show the matching entries again.\r\n\
          Press ESC or CTRL-D to exit\r\n",
         config::wezterm_version()
     ))])?;
 
+    let mut log_filter = LogFilter::default();
+
     loop {
-        print_new_log_entries(&mut term)?;
+        print_log_entries(&mut term, &log_filter, false)?;
         let mut editor = LineEditor::new(&mut term);
         editor.set_prompt("> ");
         if let Some(line) = editor.read_line(host.as_mut().unwrap())? {
@@ -214,6 +425,27 @@ pub fn show_debug_overlay(
             }
             host.as_mut().unwrap().add_history(&line);
 
+            if let Some(command) = OverlayCommand::parse(&line) {
+                match command {
+                    Ok(OverlayCommand::Level(level)) => {
+                        log_filter.level = level;
+                    }
+                    Ok(OverlayCommand::Filter(target)) => {
+                        log_filter.target = target;
+                    }
+                    Ok(OverlayCommand::Logs) => {
+                        print_log_entries(&mut term, &log_filter, true)?;
+                        continue;
+                    }
+                    Err(err) => {
+                        term.render(&[Change::Text(format!("{}\r\n", err))])?;
+                        continue;
+                    }
+                }
+                term.render(&[Change::Text(format!("{}\r\n", log_filter.describe()))])?;
+                continue;
+            }
+
             let passed_host = host.take().unwrap();
 
             let (host_res, text) =
@@ -281,3 +513,36 @@ async fn evaluate(host: LuaReplHost, expr: String) -> (LuaReplHost, String) {
     let result = do_it(&host, &expr).await;
     (host, result)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn completion() {
+        let lua = mlua::Lua::new();
+        lua.load(
+            "wezterm = { mux = { spawn_window = 1, set_active_workspace = 2 }, \
+             strftime = function() end }",
+        )
+        .exec()
+        .unwrap();
+
+        assert_eq!(
+            complete_lua(&lua, "print(wezterm.mux.s"),
+            (
+                18,
+                vec![
+                    "set_active_workspace".to_string(),
+                    "spawn_window".to_string()
+                ]
+            )
+        );
+        assert_eq!(
+            complete_lua(&lua, "wezterm:"),
+            (8, vec!["strftime".to_string()])
+        );
+        assert_eq!(complete_lua(&lua, "wezt"), (0, vec!["wezterm".to_string()]));
+        assert_eq!(complete_lua(&lua, "nothing.here"), (0, vec![]));
+    }
+}
@@ -0,0 +1,91 @@
+//! The key binding cheatsheet lists the key assignments of the current
+//! configuration, with a group for the default key table and for each
+//! of the named key tables.  Typing searches the keys and descriptions
+//! of all of the groups, and the lower part of the overlay shows the
+//! details of the selected assignment.
+use crate::inputmap::KeyBindingInfo;
+use crate::overlay::picker::{run_picker, Picker, PickerGroup, PickerItem};
+use mux::termwiztermtab::TermWizTerminal;
+use termwiz::cell::unicode_column_width;
+
+/// Produces the details that are shown in the preview.  These also
+/// identify the entry, as the same action may be assigned to several
+/// keys and tables.
+fn entry_details(info: &KeyBindingInfo) -> String {
+    let mut details = format!(
+        "Key table: {}\nKey: {}\nAction: {}",
+        info.table.as_deref().unwrap_or("default"),
+        info.key,
+        info.action
+    );
+    if let Some(description) = &info.description {
+        details.push_str(&format!("\nDescription: {description}"));
+    }
+    details
+}
+
+pub fn key_cheatsheet(
+    infos: Vec<KeyBindingInfo>,
+    active_table: Option<String>,
+    term: TermWizTerminal,
+) -> anyhow::Result<()> {
+    let mut groups: Vec<PickerGroup> = vec![];
+    let mut group_tables: Vec<Option<String>> = vec![];
+    let key_width = infos
+        .iter()
+        .map(|info| unicode_column_width(&info.key, None))
+        .max()
+        .unwrap_or(0);
+
+    for info in &infos {
+        if group_tables.last() != Some(&info.table) {
+            group_tables.push(info.table.clone());
+            groups.push(PickerGroup {
+                name: match &info.table {
+                    Some(name) => format!("Key Table: {name}"),
+                    None => "Default key table".to_string(),
+                },
+                items: vec![],
+                empty_message: "there are no key assignments".to_string(),
+            });
+        }
+        let padding = " ".repeat(key_width.saturating_sub(unicode_column_width(&info.key, None)));
+        let label = format!(
+            "{}{}   {}",
+            info.key,
+            padding,
+            info.description.as_deref().unwrap_or(&info.action)
+        );
+        if let Some(group) = groups.last_mut() {
+            group
+                .items
+                .push(PickerItem::new(label, entry_details(info)));
+        }
+    }
+
+    if groups.is_empty() {
+        groups.push(PickerGroup {
+            name: "Default key table".to_string(),
+            items: vec![],
+            empty_message: "there are no key assignments".to_string(),
+        });
+    }
+
+    let initial_group = group_tables
+        .iter()
+        .position(|table| *table == active_table)
+        .unwrap_or(0);
+
+    run_picker(
+        Picker {
+            title: "Key Bindings".to_string(),
+            groups,
+            initial_group,
+            variant_names: vec![],
+            accept_hint: "close".to_string(),
+            show_preview: true,
+            on_accept: Box::new(|_| {}),
+        },
+        term,
+    )
+}
@@ -5,7 +5,13 @@
 //! be rendered as a popup/context menu if the system supports it; at the
 //! time of writing our window layer doesn't provide an API for context
 //! menus.
+//!
+//! Entries are ranked by combining how well they match the filter
+//! with how frequently and recently they have been chosen.
+use crate::frecency::{self, Frecency};
 use crate::inputmap::InputMap;
+use crate::scripting::guiwin::GuiWin;
+use crate::scripting::pane::PaneObject;
 use crate::termwindow::TermWindowNotif;
 use config::configuration;
 use config::keyassignment::{KeyAssignment, SpawnCommand, SpawnTabDomain};
@@ -18,22 +24,36 @@ use mux::termwiztermtab::TermWizTerminal;
 use mux::window::WindowId;
 use mux::Mux;
 use std::collections::BTreeMap;
+use std::rc::Rc;
 use termwiz::cell::{AttributeChange, CellAttributes};
 use termwiz::color::ColorAttribute;
 use termwiz::input::{InputEvent, KeyCode, KeyEvent, Modifiers, MouseButtons, MouseEvent};
 use termwiz::surface::{Change, Position};
 use termwiz::terminal::Terminal;
 use termwiz_funcs::truncate_right;
+use wezterm_dynamic::FromDynamic;
 use window::WindowOps;
 
 pub use config::keyassignment::LauncherFlags;
 
+/// The frecency score is added to the fuzzy match score, but is
+/// capped so that a frequently chosen entry that only loosely
+/// matches the filter doesn't outrank a close match
+const MAX_FRECENCY_BONUS: i64 = 64;
+
 #[derive(Clone)]
 struct Entry {
     pub label: String,
     pub action: KeyAssignment,
 }
 
+/// An entry returned by the `augment-launcher-menu` event
+#[derive(Debug, Clone, FromDynamic)]
+pub struct LuaLauncherEntry {
+    pub label: String,
+    pub action: KeyAssignment,
+}
+
 pub struct LauncherTabEntry {
     pub title: String,
     pub tab_id: TabId,
@@ -58,6 +78,7 @@ pub struct LauncherArgs {
     title: String,
     active_workspace: String,
     workspaces: Vec<String>,
+    lua_entries: Vec<LuaLauncherEntry>,
 }
 
 impl LauncherArgs {
@@ -150,6 +171,44 @@ impl LauncherArgs {
             title: title.to_string(),
             workspaces,
             active_workspace,
+            lua_entries: vec![],
+        }
+    }
+
+    /// Adds the entries that are returned by the `augment-launcher-menu`
+    /// event to the launch menu items.
+    /// Must be called on the main thread!
+    pub async fn add_lua_entries(&mut self, window: GuiWin, pane: PaneObject) {
+        if !self.flags.contains(LauncherFlags::LAUNCH_MENU_ITEMS) {
+            return;
+        }
+
+        async fn lua_entries(
+            lua: Option<Rc<mlua::Lua>>,
+            window: GuiWin,
+            pane: PaneObject,
+        ) -> anyhow::Result<Vec<LuaLauncherEntry>> {
+            let lua = match lua {
+                Some(lua) => lua,
+                None => return Ok(vec![]),
+            };
+            let args = lua.pack_multi((window, pane))?;
+            let value = config::lua::emit_async_callback(
+                &*lua,
+                ("augment-launcher-menu".to_string(), args),
+            )
+            .await?;
+            match value {
+                mlua::Value::Nil => Ok(vec![]),
+                value => Ok(luahelper::from_lua_value_dynamic(value)?),
+            }
+        }
+
+        match config::with_lua_config_on_main_thread(move |lua| lua_entries(lua, window, pane))
+            .await
+        {
+            Ok(entries) => self.lua_entries = entries,
+            Err(err) => log::error!("while processing augment-launcher-menu event: {:#}", err),
         }
     }
 }
@@ -167,18 +226,38 @@ struct LauncherState {
     window: ::window::Window,
     filtering: bool,
     flags: LauncherFlags,
+    frecency: Frecency,
+    now: u64,
 }
 
 impl LauncherState {
+    fn frecency_score(&self, entry: &Entry) -> i64 {
+        self.frecency.score(&entry.label, self.now)
+    }
+
     fn update_filter(&mut self) {
         if self.filter_term.is_empty() {
             self.filtered_entries = self.entries.clone();
+            if self.flags.contains(LauncherFlags::FUZZY) {
+                // Put the most frequently and recently chosen entries
+                // first.  Otherwise, the order of the entries is kept
+                // so that the numeric shortcuts remain predictable.
+                let mut scored: Vec<(i64, Entry)> = self
+                    .entries
+                    .iter()
+                    .map(|entry| (self.frecency_score(entry), entry.clone()))
+                    .collect();
+                scored.sort_by(|a, b| a.0.cmp(&b.0).reverse());
+                self.filtered_entries = scored.into_iter().map(|(_, entry)| entry).collect();
+                self.active_idx = 0;
+                self.top_row = 0;
+            }
             return;
         }
 
         self.filtered_entries.clear();
 
-        let matcher = SkimMatcherV2::default();
+        let matcher = SkimMatcherV2::default().smart_case();
 
         struct MatchResult {
             row_idx: usize,
@@ -191,7 +270,11 @@ impl LauncherState {
             .enumerate()
             .filter_map(|(row_idx, entry)| {
                 let score = matcher.fuzzy_match(&entry.label, &self.filter_term)?;
-                Some(MatchResult { row_idx, score })
+                let bonus = self.frecency_score(entry).min(MAX_FRECENCY_BONUS);
+                Some(MatchResult {
+                    row_idx,
+                    score: score + bonus,
+                })
             })
             .collect();
 
@@ -223,6 +306,12 @@ impl LauncherState {
                     action: KeyAssignment::SpawnCommandInNewTab(item.clone()),
                 });
             }
+            for entry in &args.lua_entries {
+                self.entries.push(Entry {
+                    label: entry.label.clone(),
+                    action: entry.action.clone(),
+                });
+            }
         }
 
         for domain in &args.domains {
@@ -412,7 +501,12 @@ impl LauncherState {
     }
 
     fn launch(&self, active_idx: usize) {
-        let assignment = self.filtered_entries[active_idx].action.clone();
+        let entry = match self.filtered_entries.get(active_idx) {
+            Some(entry) => entry,
+            None => return,
+        };
+        frecency::record_launch(&entry.label);
+        let assignment = entry.action.clone();
         self.window.notify(TermWindowNotif::PerformAssignment {
             pane_id: self.pane_id,
             assignment,
@@ -584,6 +678,8 @@ pub fn launcher(
         window,
         filtering: args.flags.contains(LauncherFlags::FUZZY),
         flags: args.flags,
+        frecency: frecency::snapshot(),
+        now: frecency::now(),
     };
 
     term.set_raw_mode()?;
@@ -1,24 +1,84 @@
 use crate::termwindow::TermWindow;
+use config::ConfigHandle;
 use mux::pane::{Pane, PaneId};
 use mux::tab::{Tab, TabId};
 use mux::termwiztermtab::{allocate, TermWizTerminal};
 use std::pin::Pin;
 use std::rc::Rc;
-use wezterm_term::TerminalSize;
+use wezterm_term::{StableRowIndex, TerminalSize};
 
+pub mod charselect;
+pub mod clipboard_history;
+pub mod command_hint;
 pub mod confirm_close_pane;
 pub mod copy;
 pub mod debug;
+pub mod key_cheatsheet;
 pub mod launcher;
+pub mod pane_diff;
+pub mod picker;
 pub mod quickselect;
+pub mod registers;
+pub mod settings;
+pub mod ssh_prompt;
 
+pub use charselect::char_select;
+pub use clipboard_history::clipboard_history_picker;
+pub use command_hint::{command_hint_picker, CommandHint};
 pub use confirm_close_pane::{
-    confirm_close_pane, confirm_close_tab, confirm_close_window, confirm_quit_program,
+    confirm_assign_clipboard, confirm_close_pane, confirm_close_tab, confirm_close_window,
+    confirm_quit_program, confirm_read_clipboard,
 };
-pub use copy::{CopyModeParams, CopyOverlay};
+pub use copy::{CopyModeParams, CopyOverlay, CopyViewState};
 pub use debug::show_debug_overlay;
+pub use key_cheatsheet::key_cheatsheet;
 pub use launcher::{launcher, LauncherArgs, LauncherFlags};
-pub use quickselect::QuickSelectOverlay;
+pub use pane_diff::{pane_diff, PaneText};
+pub use quickselect::{QuickSelectOverlay, QuickSelectViewState};
+pub use registers::registers_picker;
+pub use settings::settings_editor;
+pub use ssh_prompt::ssh_prompt;
+
+/// A snapshot of the view state of a pane overlay.
+/// This is produced by `OverlayLifecycle::save_view_state` and
+/// is fed back to `OverlayLifecycle::restore_view_state` after
+/// an operation that may have disturbed the overlay.
+#[derive(Debug, Clone)]
+pub enum OverlayViewState {
+    Copy(CopyViewState),
+    QuickSelect(QuickSelectViewState),
+}
+
+/// Pane overlays (such as search, copy mode and quick select) implement
+/// this trait so that the TermWindow can keep them alive and consistent
+/// across a configuration reload, or a change to the window size or dpi,
+/// rather than dismissing them.
+pub trait OverlayLifecycle {
+    /// Capture the state that is needed to restore the overlay view
+    fn save_view_state(&self) -> OverlayViewState;
+
+    /// Re-apply a previously saved state.  State of a different
+    /// overlay kind is ignored.
+    fn restore_view_state(&self, state: OverlayViewState);
+
+    /// Called when the viewport of the overlaid pane has changed
+    fn viewport_changed(&self, viewport: Option<StableRowIndex>);
+
+    /// Called when the effective configuration of the window has changed
+    fn config_changed(&self, _config: &ConfigHandle) {}
+}
+
+/// If the pane is one of the pane overlays, return its lifecycle
+/// trait object
+pub fn overlay_lifecycle(pane: &Rc<dyn Pane>) -> Option<&dyn OverlayLifecycle> {
+    if let Some(copy) = pane.downcast_ref::<CopyOverlay>() {
+        Some(copy)
+    } else if let Some(qs) = pane.downcast_ref::<QuickSelectOverlay>() {
+        Some(qs)
+    } else {
+        None
+    }
+}
 
 pub fn start_overlay<T, F>(
     term_window: &TermWindow,
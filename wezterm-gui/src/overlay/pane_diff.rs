@@ -0,0 +1,504 @@
+//! Compares the text of two panes, such as the output of the same
+//! command on different hosts.  The differences are shown either as
+//! a unified diff or with the two panes side by side, and the layout
+//! can be toggled while the overlay is active.
+use config::keyassignment::DiffPanesLayout;
+use mux::pane::Pane;
+use mux::termwiztermtab::TermWizTerminal;
+use std::rc::Rc;
+use termwiz::cell::{unicode_column_width, AttributeChange, CellAttributes, Intensity};
+use termwiz::color::{AnsiColor, ColorAttribute};
+use termwiz::input::{InputEvent, KeyCode, KeyEvent, Modifiers, MouseButtons, MouseEvent};
+use termwiz::surface::{Change, Position};
+use termwiz::terminal::Terminal;
+use termwiz_funcs::truncate_right;
+
+/// The text of a pane that is being compared
+#[derive(Debug, Clone)]
+pub struct PaneText {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+impl PaneText {
+    /// Extracts the text of the visible portion of the pane, or of
+    /// its scrollback as well.  Logical lines are used so that panes
+    /// of different widths can be compared.
+    pub fn from_pane(pane: &Rc<dyn Pane>, scrollback: bool) -> Self {
+        let dims = pane.get_dimensions();
+        let bottom_row = dims.physical_top + dims.viewport_rows as isize;
+        let top_row = if scrollback {
+            dims.scrollback_top
+        } else {
+            dims.physical_top
+        };
+
+        let mut lines: Vec<String> = pane
+            .get_logical_lines(top_row..bottom_row)
+            .into_iter()
+            .map(|line| {
+                let mut text = String::new();
+                for (_, cell) in line.logical.visible_cells() {
+                    text.push_str(cell.str());
+                }
+                text.trim_end().to_string()
+            })
+            .collect();
+        while lines.last().map(|line| line.is_empty()).unwrap_or(false) {
+            lines.pop();
+        }
+
+        Self {
+            title: format!("{} (pane {})", pane.get_title(), pane.pane_id()),
+            lines,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DiffRow {
+    /// A line that is present in both panes
+    Same(String),
+    /// A line that is only present in the first pane
+    Removed(String),
+    /// A line that is only present in the second pane
+    Added(String),
+    /// A line of the first pane that was replaced by a line of
+    /// the second pane; only used by the side by side layout
+    Changed(String, String),
+}
+
+impl DiffRow {
+    fn is_difference(&self) -> bool {
+        !matches!(self, Self::Same(_))
+    }
+}
+
+/// Emits a run of removed and added lines.  In the side by side
+/// layout, the lines of the run are paired up so that a modified
+/// line appears next to its replacement.
+fn flush_run(
+    layout: DiffPanesLayout,
+    rows: &mut Vec<DiffRow>,
+    removed: &mut Vec<String>,
+    added: &mut Vec<String>,
+) {
+    match layout {
+        DiffPanesLayout::Unified => {
+            rows.extend(removed.drain(..).map(DiffRow::Removed));
+            rows.extend(added.drain(..).map(DiffRow::Added));
+        }
+        DiffPanesLayout::SideBySide => {
+            let mut removed = removed.drain(..);
+            let mut added = added.drain(..);
+            loop {
+                match (removed.next(), added.next()) {
+                    (Some(l), Some(r)) => rows.push(DiffRow::Changed(l, r)),
+                    (Some(l), None) => rows.push(DiffRow::Removed(l)),
+                    (None, Some(r)) => rows.push(DiffRow::Added(r)),
+                    (None, None) => break,
+                }
+            }
+        }
+    }
+}
+
+/// The diff takes time and memory proportional to the product of the
+/// number of lines of each side.  When the lines that differ exceed
+/// this budget, they are shown as one run of removed and added lines
+/// rather than being matched up.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+fn diff_rows(left: &[String], right: &[String], layout: DiffPanesLayout) -> Vec<DiffRow> {
+    let mut rows = vec![];
+    let mut removed = vec![];
+    let mut added = vec![];
+
+    // Lines that are common to the start or the end of both
+    // sides don't need to take part in the diff
+    let prefix = left
+        .iter()
+        .zip(right.iter())
+        .take_while(|(l, r)| l == r)
+        .count();
+    let suffix = left[prefix..]
+        .iter()
+        .rev()
+        .zip(right[prefix..].iter().rev())
+        .take_while(|(l, r)| l == r)
+        .count();
+    let left_middle = &left[prefix..left.len() - suffix];
+    let right_middle = &right[prefix..right.len() - suffix];
+
+    rows.extend(left[..prefix].iter().cloned().map(DiffRow::Same));
+
+    if left_middle.len().saturating_mul(right_middle.len()) > MAX_DIFF_CELLS {
+        removed.extend(left_middle.iter().cloned());
+        added.extend(right_middle.iter().cloned());
+    } else {
+        for result in diff::slice(left_middle, right_middle) {
+            match result {
+                diff::Result::Left(line) => removed.push(line.clone()),
+                diff::Result::Right(line) => added.push(line.clone()),
+                diff::Result::Both(line, _) => {
+                    flush_run(layout, &mut rows, &mut removed, &mut added);
+                    rows.push(DiffRow::Same(line.clone()));
+                }
+            }
+        }
+    }
+    flush_run(layout, &mut rows, &mut removed, &mut added);
+
+    rows.extend(
+        left[left.len() - suffix..]
+            .iter()
+            .cloned()
+            .map(DiffRow::Same),
+    );
+    rows
+}
+
+/// Truncates or pads the text so that it occupies exactly `width` cells
+fn fit_width(text: &str, width: usize) -> String {
+    let mut text = truncate_right(text, width);
+    let len = unicode_column_width(&text, None);
+    text.push_str(&" ".repeat(width.saturating_sub(len)));
+    text
+}
+
+fn color_for(marker: char) -> ColorAttribute {
+    match marker {
+        '-' | '<' => AnsiColor::Maroon.into(),
+        '+' | '>' => AnsiColor::Green.into(),
+        '|' => AnsiColor::Olive.into(),
+        _ => ColorAttribute::Default,
+    }
+}
+
+struct DiffState {
+    left: PaneText,
+    right: PaneText,
+    layout: DiffPanesLayout,
+    rows: Vec<DiffRow>,
+    top_row: usize,
+    list_rows: usize,
+    cols: usize,
+}
+
+impl DiffState {
+    fn set_layout(&mut self, layout: DiffPanesLayout) {
+        self.layout = layout;
+        self.rows = diff_rows(&self.left.lines, &self.right.lines, layout);
+        self.top_row = 0;
+    }
+
+    fn set_size(&mut self, cols: usize, rows: usize) {
+        self.cols = cols;
+        // Leave room for the title and the key hints
+        self.list_rows = rows.saturating_sub(2).max(1);
+        self.scroll_to(self.top_row);
+    }
+
+    fn scroll_to(&mut self, row: usize) {
+        self.top_row = row.min(self.rows.len().saturating_sub(self.list_rows));
+    }
+
+    fn scroll_up(&mut self, amount: usize) {
+        self.scroll_to(self.top_row.saturating_sub(amount));
+    }
+
+    fn scroll_down(&mut self, amount: usize) {
+        self.scroll_to(self.top_row + amount);
+    }
+
+    /// Scrolls to the start of the next group of differences
+    fn next_difference(&mut self) {
+        let mut idx = self.top_row;
+        // Skip over the differences at the top of the view
+        while self.rows.get(idx).map(DiffRow::is_difference) == Some(true) {
+            idx += 1;
+        }
+        if let Some(pos) = self.rows[idx.min(self.rows.len())..]
+            .iter()
+            .position(DiffRow::is_difference)
+        {
+            self.scroll_to(idx + pos);
+        }
+    }
+
+    /// Scrolls to the start of the previous group of differences
+    fn previous_difference(&mut self) {
+        let mut idx = match self.rows[..self.top_row]
+            .iter()
+            .rposition(DiffRow::is_difference)
+        {
+            Some(idx) => idx,
+            None => return,
+        };
+        while idx > 0 && self.rows[idx - 1].is_difference() {
+            idx -= 1;
+        }
+        self.scroll_to(idx);
+    }
+
+    fn render_row(&self, row: &DiffRow, changes: &mut Vec<Change>) {
+        let width = self.cols.saturating_sub(1);
+        match self.layout {
+            DiffPanesLayout::Unified => {
+                let (marker, text) = match row {
+                    DiffRow::Same(text) => (' ', text),
+                    DiffRow::Removed(text) => ('-', text),
+                    DiffRow::Added(text) => ('+', text),
+                    DiffRow::Changed(..) => unreachable!("not used by the unified layout"),
+                };
+                changes.push(AttributeChange::Foreground(color_for(marker)).into());
+                changes.push(Change::Text(format!(
+                    "{}\r\n",
+                    truncate_right(&format!("{marker} {text}"), width)
+                )));
+            }
+            DiffPanesLayout::SideBySide => {
+                let half = width.saturating_sub(3) / 2;
+                let (marker, left, right) = match row {
+                    DiffRow::Same(text) => (' ', text.as_str(), text.as_str()),
+                    DiffRow::Removed(text) => ('<', text.as_str(), ""),
+                    DiffRow::Added(text) => ('>', "", text.as_str()),
+                    DiffRow::Changed(left, right) => ('|', left.as_str(), right.as_str()),
+                };
+                let left_marker = if marker == '|' { '<' } else { marker };
+                let right_marker = if marker == '|' { '>' } else { marker };
+                changes.push(AttributeChange::Foreground(color_for(left_marker)).into());
+                changes.push(Change::Text(fit_width(left, half)));
+                changes.push(AttributeChange::Foreground(color_for(marker)).into());
+                changes.push(Change::Text(format!(" {marker} ")));
+                changes.push(AttributeChange::Foreground(color_for(right_marker)).into());
+                changes.push(Change::Text(format!("{}\r\n", truncate_right(right, half))));
+            }
+        }
+        changes.push(AttributeChange::Foreground(ColorAttribute::Default).into());
+    }
+
+    fn render(&self, term: &mut TermWizTerminal) -> termwiz::Result<()> {
+        let width = self.cols.saturating_sub(1);
+        let mut changes = vec![
+            Change::ClearScreen(ColorAttribute::Default),
+            Change::CursorPosition {
+                x: Position::Absolute(0),
+                y: Position::Absolute(0),
+            },
+            AttributeChange::Intensity(Intensity::Bold).into(),
+        ];
+
+        let title = match self.layout {
+            DiffPanesLayout::Unified => {
+                format!("--- {}   +++ {}", self.left.title, self.right.title)
+            }
+            DiffPanesLayout::SideBySide => {
+                let half = width.saturating_sub(3) / 2;
+                format!(
+                    "{}   {}",
+                    fit_width(&self.left.title, half),
+                    self.right.title
+                )
+            }
+        };
+        changes.push(Change::Text(format!(
+            "{}\r\n",
+            truncate_right(&title, width)
+        )));
+        changes.push(Change::AllAttributes(CellAttributes::default()));
+
+        for row in self.rows.iter().skip(self.top_row).take(self.list_rows) {
+            self.render_row(row, &mut changes);
+        }
+
+        let differences = self.rows.iter().filter(|row| row.is_difference()).count();
+        changes.push(Change::CursorPosition {
+            x: Position::Absolute(0),
+            y: Position::Absolute(self.list_rows + 1),
+        });
+        changes.push(AttributeChange::Intensity(Intensity::Half).into());
+        let summary = if differences == 0 {
+            "The panes have the same content".to_string()
+        } else {
+            format!("{differences} differing lines")
+        };
+        changes.push(Change::Text(truncate_right(
+            &format!("{summary}. n/p: next/previous difference, Tab: toggle layout, Escape: close"),
+            width,
+        )));
+        changes.push(AttributeChange::Intensity(Intensity::Normal).into());
+
+        term.render(&changes)
+    }
+
+    fn run_loop(&mut self, term: &mut TermWizTerminal) -> anyhow::Result<()> {
+        while let Ok(Some(event)) = term.poll_input(None) {
+            match event {
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Char('G'),
+                    modifiers: Modifiers::CTRL,
+                })
+                | InputEvent::Key(KeyEvent {
+                    key: KeyCode::Escape | KeyCode::Char('q'),
+                    ..
+                }) => {
+                    break;
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::UpArrow | KeyCode::Char('k'),
+                    ..
+                }) => {
+                    self.scroll_up(1);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::DownArrow | KeyCode::Char('j'),
+                    ..
+                }) => {
+                    self.scroll_down(1);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::PageUp,
+                    ..
+                }) => {
+                    self.scroll_up(self.list_rows);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::PageDown | KeyCode::Char(' '),
+                    ..
+                }) => {
+                    self.scroll_down(self.list_rows);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Home | KeyCode::Char('g'),
+                    ..
+                }) => {
+                    self.scroll_to(0);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::End | KeyCode::Char('G'),
+                    ..
+                }) => {
+                    self.scroll_to(self.rows.len());
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Char('n'),
+                    ..
+                }) => {
+                    self.next_difference();
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Char('p'),
+                    ..
+                }) => {
+                    self.previous_difference();
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Tab, ..
+                }) => {
+                    self.set_layout(match self.layout {
+                        DiffPanesLayout::Unified => DiffPanesLayout::SideBySide,
+                        DiffPanesLayout::SideBySide => DiffPanesLayout::Unified,
+                    });
+                }
+                InputEvent::Mouse(MouseEvent { mouse_buttons, .. })
+                    if mouse_buttons.contains(MouseButtons::VERT_WHEEL) =>
+                {
+                    if mouse_buttons.contains(MouseButtons::WHEEL_POSITIVE) {
+                        self.scroll_up(1);
+                    } else {
+                        self.scroll_down(1);
+                    }
+                }
+                InputEvent::Resized { cols, rows } => {
+                    self.set_size(cols, rows);
+                }
+                _ => {}
+            }
+            self.render(term)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn pane_diff(
+    left: PaneText,
+    right: PaneText,
+    layout: DiffPanesLayout,
+    mut term: TermWizTerminal,
+) -> anyhow::Result<()> {
+    let size = term.get_screen_size()?;
+    let rows = diff_rows(&left.lines, &right.lines, layout);
+    let mut state = DiffState {
+        left,
+        right,
+        layout,
+        rows,
+        top_row: 0,
+        list_rows: 0,
+        cols: 0,
+    };
+    state.set_size(size.cols, size.rows);
+
+    term.set_raw_mode()?;
+    term.render(&[Change::Title("Compare Panes".to_string())])?;
+    state.render(&mut term)?;
+    state.run_loop(&mut term)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn rows() {
+        let left = lines("a\nb\nc\nd");
+        let right = lines("a\nB\nc\nd\ne");
+
+        assert_eq!(
+            diff_rows(&left, &right, DiffPanesLayout::Unified),
+            vec![
+                DiffRow::Same("a".to_string()),
+                DiffRow::Removed("b".to_string()),
+                DiffRow::Added("B".to_string()),
+                DiffRow::Same("c".to_string()),
+                DiffRow::Same("d".to_string()),
+                DiffRow::Added("e".to_string()),
+            ]
+        );
+        assert_eq!(
+            diff_rows(&left, &right, DiffPanesLayout::SideBySide),
+            vec![
+                DiffRow::Same("a".to_string()),
+                DiffRow::Changed("b".to_string(), "B".to_string()),
+                DiffRow::Same("c".to_string()),
+                DiffRow::Same("d".to_string()),
+                DiffRow::Added("e".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn large_difference() {
+        let left: Vec<String> = (0..2001).map(|i| format!("left {}", i)).collect();
+        let right: Vec<String> = (0..2001).map(|i| format!("right {}", i)).collect();
+        let mut left_with_context = vec!["same".to_string()];
+        left_with_context.extend(left.iter().cloned());
+        let mut right_with_context = vec!["same".to_string()];
+        right_with_context.extend(right.iter().cloned());
+
+        let rows = diff_rows(
+            &left_with_context,
+            &right_with_context,
+            DiffPanesLayout::Unified,
+        );
+        assert_eq!(rows.len(), 1 + left.len() + right.len());
+        assert_eq!(rows[0], DiffRow::Same("same".to_string()));
+        assert_eq!(rows[1], DiffRow::Removed("left 0".to_string()));
+        assert_eq!(rows[1 + left.len()], DiffRow::Added("right 0".to_string()));
+    }
+}
@@ -0,0 +1,424 @@
+//! A fuzzy matching picker overlay that is shared by the overlays
+//! that present a list of text to choose from, such as the clipboard
+//! history and CharSelect.  The entries are arranged into groups that
+//! can be cycled through, and each entry may have variants, such as
+//! emoji skin tones, that are cycled through together.
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use mux::termwiztermtab::TermWizTerminal;
+use std::collections::HashSet;
+use termwiz::cell::{unicode_column_width, AttributeChange, CellAttributes, Intensity};
+use termwiz::color::ColorAttribute;
+use termwiz::input::{InputEvent, KeyCode, KeyEvent, Modifiers, MouseButtons, MouseEvent};
+use termwiz::surface::{Change, Position};
+use termwiz::terminal::Terminal;
+use termwiz_funcs::truncate_right;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PickerItem {
+    /// The text that is shown in the list and matched by the filter
+    pub label: String,
+    /// The text that is passed to `Picker::on_accept`
+    pub value: String,
+    /// Alternative forms of this item, corresponding to
+    /// `Picker::variant_names` after the first
+    pub variants: Vec<PickerItem>,
+}
+
+impl PickerItem {
+    pub fn new(label: String, value: String) -> Self {
+        Self {
+            label,
+            value,
+            variants: vec![],
+        }
+    }
+
+    fn variant(&self, variant: usize) -> &PickerItem {
+        match variant.checked_sub(1) {
+            Some(idx) => self.variants.get(idx).unwrap_or(self),
+            None => self,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PickerGroup {
+    pub name: String,
+    pub items: Vec<PickerItem>,
+    /// Shown in place of the list when the group is empty
+    pub empty_message: String,
+}
+
+pub struct Picker {
+    pub title: String,
+    pub groups: Vec<PickerGroup>,
+    pub initial_group: usize,
+    /// The names of the variants that CTRL-S cycles through;
+    /// the first name is used for the items themselves
+    pub variant_names: Vec<String>,
+    /// Describes what happens on Enter, such as "paste"
+    pub accept_hint: String,
+    /// Show the full value of the selected item below the list
+    pub show_preview: bool,
+    /// Called with the value of the chosen item
+    pub on_accept: Box<dyn FnOnce(String) + Send>,
+}
+
+/// Lines used by the header and the separator above the preview
+const ROW_OVERHEAD: usize = 2;
+
+struct PickerState {
+    picker: Picker,
+    group_idx: usize,
+    variant_idx: usize,
+    /// (group index, item index) of the items that match the filter
+    filtered: Vec<(usize, usize)>,
+    filter_term: String,
+    active_idx: usize,
+    top_row: usize,
+    list_rows: usize,
+    preview_rows: usize,
+}
+
+impl PickerState {
+    fn set_rows(&mut self, rows: usize) {
+        if self.picker.show_preview {
+            let available = rows.saturating_sub(ROW_OVERHEAD);
+            self.list_rows = (available / 2).max(1);
+            self.preview_rows = available.saturating_sub(self.list_rows);
+        } else {
+            self.list_rows = rows.saturating_sub(1).max(1);
+            self.preview_rows = 0;
+        }
+    }
+
+    fn item(&self, (group_idx, item_idx): (usize, usize)) -> &PickerItem {
+        self.picker.groups[group_idx].items[item_idx].variant(self.variant_idx)
+    }
+
+    /// When there is no filter, shows the current group.
+    /// Otherwise, shows the matches from all of the groups.
+    fn update_filter(&mut self) {
+        if self.filter_term.is_empty() {
+            self.filtered = (0..self.picker.groups[self.group_idx].items.len())
+                .map(|item_idx| (self.group_idx, item_idx))
+                .collect();
+        } else {
+            let matcher = SkimMatcherV2::default();
+            let mut seen = HashSet::new();
+            let mut scores: Vec<((usize, usize), i64)> = vec![];
+            for (group_idx, group) in self.picker.groups.iter().enumerate() {
+                for (item_idx, item) in group.items.iter().enumerate() {
+                    if !seen.insert(&item.value) {
+                        continue;
+                    }
+                    if let Some(score) = matcher.fuzzy_match(&item.label, &self.filter_term) {
+                        scores.push(((group_idx, item_idx), score));
+                    }
+                }
+            }
+            scores.sort_by(|a, b| a.1.cmp(&b.1).reverse());
+            self.filtered = scores.into_iter().map(|(idx, _)| idx).collect();
+        }
+        self.active_idx = 0;
+        self.top_row = 0;
+    }
+
+    fn header(&self) -> String {
+        if !self.filter_term.is_empty() {
+            return format!("Fuzzy matching all groups: {}", self.filter_term);
+        }
+        let mut header = String::new();
+        if self.picker.groups.len() > 1 {
+            header.push_str(&format!(
+                "{} (CTRL-R: next group)  ",
+                self.picker.groups[self.group_idx].name
+            ));
+        }
+        if let Some(name) = self.picker.variant_names.get(self.variant_idx) {
+            header.push_str(&format!("{} (CTRL-S: next variant)  ", name));
+        }
+        header.push_str(&format!(
+            "Enter={}  Esc=cancel  Type to filter",
+            self.picker.accept_hint
+        ));
+        header
+    }
+
+    fn render(&mut self, term: &mut TermWizTerminal) -> termwiz::Result<()> {
+        let size = term.get_screen_size()?;
+        let max_width = size.cols.saturating_sub(6);
+
+        let header = truncate_right(&self.header(), size.cols.saturating_sub(1));
+        let header_width = unicode_column_width(&header, None);
+
+        let mut changes = vec![
+            Change::ClearScreen(ColorAttribute::Default),
+            Change::CursorPosition {
+                x: Position::Absolute(0),
+                y: Position::Absolute(0),
+            },
+            Change::Text(format!("{}\r\n", header)),
+            Change::AllAttributes(CellAttributes::default()),
+        ];
+
+        if self.filtered.is_empty() {
+            changes.push(Change::Text(if self.filter_term.is_empty() {
+                format!(
+                    "    ({})\r\n",
+                    self.picker.groups[self.group_idx].empty_message
+                )
+            } else {
+                "    (no matches)\r\n".to_string()
+            }));
+        }
+
+        for (row_num, (idx, &entry)) in self
+            .filtered
+            .iter()
+            .enumerate()
+            .skip(self.top_row)
+            .enumerate()
+        {
+            if row_num >= self.list_rows {
+                break;
+            }
+            if idx == self.active_idx {
+                changes.push(AttributeChange::Reverse(true).into());
+            }
+            changes.push(Change::Text(format!(
+                "    {} \r\n",
+                truncate_right(&self.item(entry).label, max_width)
+            )));
+            if idx == self.active_idx {
+                changes.push(AttributeChange::Reverse(false).into());
+            }
+        }
+
+        if self.picker.show_preview {
+            if let Some(&entry) = self.filtered.get(self.active_idx) {
+                changes.push(Change::CursorPosition {
+                    x: Position::Absolute(0),
+                    y: Position::Absolute(self.list_rows + 1),
+                });
+                changes.push(AttributeChange::Intensity(Intensity::Half).into());
+                changes.push(Change::Text(format!(
+                    "{}\r\n",
+                    "─".repeat(size.cols.saturating_sub(1))
+                )));
+                changes.push(AttributeChange::Intensity(Intensity::Normal).into());
+                for line in self.item(entry).value.lines().take(self.preview_rows) {
+                    changes.push(Change::Text(format!(
+                        "{}\r\n",
+                        truncate_right(&line.replace('\t', "    "), size.cols.saturating_sub(1))
+                    )));
+                }
+            }
+        }
+
+        // Leave the cursor at the end of the filter, so that text
+        // being composed by an input method is shown where it is typed
+        changes.push(Change::CursorPosition {
+            x: Position::Absolute(header_width),
+            y: Position::Absolute(0),
+        });
+
+        term.render(&changes)
+    }
+
+    fn move_up(&mut self, amount: usize) {
+        self.active_idx = self.active_idx.saturating_sub(amount);
+        if self.active_idx < self.top_row {
+            self.top_row = self.active_idx;
+        }
+    }
+
+    fn move_down(&mut self, amount: usize) {
+        self.active_idx = (self.active_idx + amount).min(self.filtered.len().saturating_sub(1));
+        if self.active_idx >= self.top_row + self.list_rows {
+            self.top_row = self.active_idx + 1 - self.list_rows;
+        }
+    }
+
+    fn cycle_group(&mut self, delta: isize) {
+        let num_groups = self.picker.groups.len() as isize;
+        self.group_idx = (self.group_idx as isize + delta).rem_euclid(num_groups) as usize;
+        self.filter_term.clear();
+        self.update_filter();
+    }
+
+    fn cycle_variant(&mut self) {
+        if !self.picker.variant_names.is_empty() {
+            self.variant_idx = (self.variant_idx + 1) % self.picker.variant_names.len();
+        }
+    }
+
+    /// Returns the value of the active item
+    fn accept(&self) -> Option<String> {
+        let entry = *self.filtered.get(self.active_idx)?;
+        Some(self.item(entry).value.clone())
+    }
+
+    /// Returns the accepted value, or None if the picker was cancelled
+    fn run_loop(&mut self, term: &mut TermWizTerminal) -> anyhow::Result<Option<String>> {
+        while let Ok(Some(event)) = term.poll_input(None) {
+            match event {
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Char('G'),
+                    modifiers: Modifiers::CTRL,
+                })
+                | InputEvent::Key(KeyEvent {
+                    key: KeyCode::Escape,
+                    ..
+                }) => {
+                    break;
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Char('P'),
+                    modifiers: Modifiers::CTRL,
+                })
+                | InputEvent::Key(KeyEvent {
+                    key: KeyCode::UpArrow,
+                    ..
+                }) => {
+                    self.move_up(1);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Char('N'),
+                    modifiers: Modifiers::CTRL,
+                })
+                | InputEvent::Key(KeyEvent {
+                    key: KeyCode::DownArrow,
+                    ..
+                }) => {
+                    self.move_down(1);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::PageUp,
+                    ..
+                }) => {
+                    self.move_up(self.list_rows);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::PageDown,
+                    ..
+                }) => {
+                    self.move_down(self.list_rows);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Char('R'),
+                    modifiers: Modifiers::CTRL,
+                })
+                | InputEvent::Key(KeyEvent {
+                    key: KeyCode::Tab,
+                    modifiers: Modifiers::NONE,
+                }) => {
+                    self.cycle_group(1);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Tab,
+                    modifiers: Modifiers::SHIFT,
+                }) => {
+                    self.cycle_group(-1);
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Char('S'),
+                    modifiers: Modifiers::CTRL,
+                }) => {
+                    self.cycle_variant();
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Backspace,
+                    ..
+                }) => {
+                    self.filter_term.pop();
+                    self.update_filter();
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Enter,
+                    ..
+                }) => {
+                    return Ok(self.accept());
+                }
+                InputEvent::Key(KeyEvent {
+                    key: KeyCode::Char(c),
+                    ..
+                }) => {
+                    self.filter_term.push(c);
+                    self.update_filter();
+                }
+                InputEvent::Mouse(MouseEvent { mouse_buttons, .. })
+                    if mouse_buttons.contains(MouseButtons::VERT_WHEEL) =>
+                {
+                    if mouse_buttons.contains(MouseButtons::WHEEL_POSITIVE) {
+                        self.move_up(1);
+                    } else {
+                        self.move_down(1);
+                    }
+                }
+                InputEvent::Mouse(MouseEvent {
+                    y, mouse_buttons, ..
+                }) => {
+                    let row = y as usize;
+                    if row > 0 && row <= self.list_rows {
+                        let idx = self.top_row + row - 1;
+                        if idx < self.filtered.len() {
+                            self.active_idx = idx;
+                            if mouse_buttons == MouseButtons::LEFT {
+                                return Ok(self.accept());
+                            }
+                        }
+                    }
+                    if mouse_buttons != MouseButtons::NONE && mouse_buttons != MouseButtons::LEFT {
+                        // Treat any other mouse button as cancel
+                        break;
+                    }
+                }
+                InputEvent::Resized { rows, .. } => {
+                    self.set_rows(rows);
+                }
+                _ => {}
+            }
+            self.render(term)?;
+        }
+
+        Ok(None)
+    }
+}
+
+pub fn run_picker(mut picker: Picker, mut term: TermWizTerminal) -> anyhow::Result<()> {
+    let on_accept = std::mem::replace(&mut picker.on_accept, Box::new(|_| {}));
+    if let Some(value) = pick(picker, &mut term)? {
+        on_accept(value);
+    }
+    Ok(())
+}
+
+/// Runs the picker and returns the value of the chosen item, or None
+/// if it was cancelled.  `on_accept` is not called, which allows an
+/// overlay to present a sequence of pickers in the same terminal.
+pub fn pick(picker: Picker, term: &mut TermWizTerminal) -> anyhow::Result<Option<String>> {
+    anyhow::ensure!(!picker.groups.is_empty(), "picker has no groups");
+    let size = term.get_screen_size()?;
+    let group_idx = picker.initial_group.min(picker.groups.len() - 1);
+    let title = picker.title.clone();
+    let mut state = PickerState {
+        picker,
+        group_idx,
+        variant_idx: 0,
+        filtered: vec![],
+        filter_term: String::new(),
+        active_idx: 0,
+        top_row: 0,
+        list_rows: 0,
+        preview_rows: 0,
+    };
+    state.set_rows(size.rows);
+
+    term.set_raw_mode()?;
+    term.render(&[Change::Title(title)])?;
+    state.update_filter();
+    state.render(term)?;
+    state.run_loop(term)
+}
@@ -1,9 +1,10 @@
+use crate::overlay::{OverlayLifecycle, OverlayViewState};
 use crate::selection::{SelectionCoordinate, SelectionRange};
 use crate::termwindow::{TermWindow, TermWindowNotif};
 use config::keyassignment::{ClipboardCopyDestination, QuickSelectArguments, ScrollbackEraseMode};
 use config::ConfigHandle;
 use mux::domain::DomainId;
-use mux::pane::{Pane, PaneId, Pattern, SearchResult};
+use mux::pane::{Pane, PaneId, Pattern, SearchResult, WorkingDirAccuracy};
 use mux::renderable::*;
 use rangeset::RangeSet;
 use std::cell::{RefCell, RefMut};
@@ -13,6 +14,7 @@ use std::rc::Rc;
 use std::sync::Arc;
 use termwiz::cell::{Cell, CellAttributes};
 use termwiz::color::AnsiColor;
+use termwiz::hyperlink::Hyperlink;
 use termwiz::surface::{SequenceNo, SEQ_ZERO};
 use url::Url;
 use wezterm_term::color::ColorPalette;
@@ -101,6 +103,48 @@ pub fn compute_labels_for_alphabet(alphabet: &str, num_matches: usize) -> Vec<St
         .collect()
 }
 
+/// Finds the runs of cells in `lines` that have a hyperlink.
+/// Runs that link to the same URI share a match_id, which is the
+/// index of their hyperlink in the returned vec.
+fn hyperlink_results(
+    first_row: StableRowIndex,
+    lines: &[Line],
+) -> (Vec<SearchResult>, Vec<Arc<Hyperlink>>) {
+    let mut results = vec![];
+    let mut links: Vec<Arc<Hyperlink>> = vec![];
+    for (idx, line) in lines.iter().enumerate() {
+        let y = first_row + idx as StableRowIndex;
+        let mut runs: Vec<(Range<usize>, Arc<Hyperlink>)> = vec![];
+        for (x, cell) in line.visible_cells() {
+            let end = x + cell.width();
+            match (runs.last_mut(), cell.attrs().hyperlink()) {
+                (Some((range, prior)), Some(link)) if range.end == x && *prior == *link => {
+                    range.end = end;
+                }
+                (_, Some(link)) => runs.push((x..end, Arc::clone(link))),
+                (_, None) => {}
+            }
+        }
+        for (range, link) in runs {
+            let match_id = match links.iter().position(|l| l.uri() == link.uri()) {
+                Some(id) => id,
+                None => {
+                    links.push(link);
+                    links.len() - 1
+                }
+            };
+            results.push(SearchResult {
+                start_y: y,
+                start_x: range.start,
+                end_y: y,
+                end_x: range.end,
+                match_id,
+            });
+        }
+    }
+    (results, links)
+}
+
 #[cfg(test)]
 mod alphabet_test {
     use super::*;
@@ -143,6 +187,55 @@ mod alphabet_test {
             vec!["aa", "ab", "ba", "bb"]
         );
     }
+
+    #[test]
+    fn hyperlinks() {
+        let mut link = CellAttributes::default();
+        link.set_hyperlink(Some(Arc::new(Hyperlink::new("https://example.com"))));
+        let mut other = CellAttributes::default();
+        other.set_hyperlink(Some(Arc::new(Hyperlink::new("file:///tmp"))));
+
+        let mut first = Line::from_text(
+            "see here and /tmp",
+            &CellAttributes::default(),
+            SEQ_ZERO,
+            None,
+        );
+        first.overlay_text_with_attribute(4, "here", link.clone(), SEQ_ZERO);
+        first.overlay_text_with_attribute(13, "/tmp", other, SEQ_ZERO);
+        let mut second = Line::from_text("again", &CellAttributes::default(), SEQ_ZERO, None);
+        second.overlay_text_with_attribute(0, "again", link, SEQ_ZERO);
+
+        let (results, links) = hyperlink_results(10, &[first, second]);
+        let uris: Vec<&str> = links.iter().map(|l| l.uri()).collect();
+        assert_eq!(uris, vec!["https://example.com", "file:///tmp"]);
+        assert_eq!(
+            results,
+            vec![
+                SearchResult {
+                    start_y: 10,
+                    start_x: 4,
+                    end_y: 10,
+                    end_x: 8,
+                    match_id: 0
+                },
+                SearchResult {
+                    start_y: 10,
+                    start_x: 13,
+                    end_y: 10,
+                    end_x: 17,
+                    match_id: 1
+                },
+                SearchResult {
+                    start_y: 11,
+                    start_x: 0,
+                    end_y: 11,
+                    end_x: 5,
+                    match_id: 0
+                },
+            ]
+        );
+    }
 }
 
 pub struct QuickSelectOverlay {
@@ -156,12 +249,23 @@ struct MatchResult {
     label: String,
 }
 
+/// The portion of the quick select state that survives a
+/// configuration reload or a resize
+#[derive(Debug, Clone)]
+pub struct QuickSelectViewState {
+    selection: String,
+    viewport: Option<StableRowIndex>,
+    result_pos: Option<usize>,
+}
+
 struct QuickSelectRenderable {
     delegate: Rc<dyn Pane>,
     /// The text that the user entered
     pattern: Pattern,
     /// The most recently queried set of matches
     results: Vec<SearchResult>,
+    /// When labelling hyperlinks, the hyperlink for each match_id
+    links: Vec<Arc<Hyperlink>>,
     by_line: HashMap<StableRowIndex, Vec<MatchResult>>,
     by_label: HashMap<String, usize>,
     selection: String,
@@ -230,6 +334,7 @@ impl QuickSelectOverlay {
             pattern,
             selection: "".to_string(),
             results: vec![],
+            links: vec![],
             by_line: HashMap::new(),
             by_label: HashMap::new(),
             dirty_results: RangeSet::default(),
@@ -253,7 +358,68 @@ impl QuickSelectOverlay {
         })
     }
 
-    pub fn viewport_changed(&self, viewport: Option<StableRowIndex>) {
+    /// Returns the text of the active match, or the target of its
+    /// hyperlink when labelling hyperlinks
+    pub fn active_match_text(&self) -> Option<String> {
+        let r = self.renderer.borrow();
+        let result = r.results.get(r.result_pos?)?;
+        if r.args.hyperlinks {
+            return r
+                .links
+                .get(result.match_id)
+                .map(|link| link.uri().to_string());
+        }
+        let (first, lines) = self.delegate.get_lines(result.start_y..result.end_y + 1);
+        let mut text = String::new();
+        for (idx, line) in lines.iter().enumerate() {
+            let y = first + idx as StableRowIndex;
+            let start = if y == result.start_y {
+                result.start_x
+            } else {
+                0
+            };
+            let end = if y == result.end_y {
+                result.end_x
+            } else {
+                usize::MAX
+            };
+            text.push_str(&line.columns_as_str(start..end));
+        }
+        Some(text)
+    }
+
+    /// Returns the pane that is being searched
+    pub fn delegate(&self) -> &Rc<dyn Pane> {
+        &self.delegate
+    }
+}
+
+impl OverlayLifecycle for QuickSelectOverlay {
+    fn save_view_state(&self) -> OverlayViewState {
+        let render = self.renderer.borrow();
+        OverlayViewState::QuickSelect(QuickSelectViewState {
+            selection: render.selection.clone(),
+            viewport: render.viewport,
+            result_pos: render.result_pos,
+        })
+    }
+
+    fn restore_view_state(&self, state: OverlayViewState) {
+        let state = match state {
+            OverlayViewState::QuickSelect(state) => state,
+            _ => return,
+        };
+        let mut render = self.renderer.borrow_mut();
+        render.selection = state.selection;
+        render.result_pos = state.result_pos;
+        if render.viewport != state.viewport {
+            render.set_viewport(state.viewport);
+        }
+        let search_row = render.compute_search_row();
+        render.dirty_results.add(search_row);
+    }
+
+    fn viewport_changed(&self, viewport: Option<StableRowIndex>) {
         let mut render = self.renderer.borrow_mut();
         if render.viewport != viewport {
             if let Some(last) = render.last_bar_pos.take() {
@@ -265,6 +431,20 @@ impl QuickSelectOverlay {
             render.viewport = viewport;
         }
     }
+
+    fn config_changed(&self, config: &ConfigHandle) {
+        let mut render = self.renderer.borrow_mut();
+        let alphabet_changed = render.config.quick_select_alphabet != config.quick_select_alphabet;
+        render.config = config.clone();
+        if alphabet_changed && render.args.alphabet.is_empty() {
+            // Re-label the existing matches using the new alphabet
+            for idx in render.by_line.keys().copied().collect::<Vec<_>>() {
+                render.dirty_results.add(idx);
+            }
+            render.by_line.clear();
+            render.recompute_results();
+        }
+    }
 }
 
 impl Pane for QuickSelectOverlay {
@@ -293,6 +473,14 @@ impl Pane for QuickSelectOverlay {
         self.delegate.resize(size)
     }
 
+    fn set_read_only(&self, read_only: bool) {
+        self.delegate.set_read_only(read_only)
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.delegate.is_read_only()
+    }
+
     fn key_up(&self, _key: KeyCode, _mods: KeyModifiers) -> anyhow::Result<()> {
         Ok(())
     }
@@ -427,6 +615,10 @@ impl Pane for QuickSelectOverlay {
         self.delegate.get_current_working_dir()
     }
 
+    fn get_current_working_dir_accuracy(&self) -> Option<WorkingDirAccuracy> {
+        self.delegate.get_current_working_dir_accuracy()
+    }
+
     fn get_cursor_position(&self) -> StableCursorPosition {
         // move to the search box
         let renderer = self.renderer.borrow();
@@ -472,15 +664,27 @@ impl Pane for QuickSelectOverlay {
                 line.fill_range(0..dims.cols, &Cell::new(' ', rev.clone()), SEQ_ZERO);
                 line.overlay_text_with_attribute(
                     0,
-                    &format!(
-                        "Select: {}  (type highlighted prefix to {}, uppercase pastes, ESC to cancel)",
-                        renderer.selection,
-                        if renderer.args.label.is_empty() {
-                            "copy"
-                        } else {
-                            &renderer.args.label
-                        },
-                    ),
+                    &if renderer.args.hyperlinks {
+                        format!(
+                            "Select: {}  (type highlighted prefix to {}, ESC to cancel)",
+                            renderer.selection,
+                            if renderer.args.label.is_empty() {
+                                "open"
+                            } else {
+                                &renderer.args.label
+                            },
+                        )
+                    } else {
+                        format!(
+                            "Select: {}  (type highlighted prefix to {}, uppercase pastes, ESC to cancel)",
+                            renderer.selection,
+                            if renderer.args.label.is_empty() {
+                                "copy"
+                            } else {
+                                &renderer.args.label
+                            },
+                        )
+                    },
                     rev,
                     SEQ_ZERO,
                 );
@@ -648,7 +852,20 @@ impl QuickSelectRenderable {
         let bar_pos = self.compute_search_row();
         self.dirty_results.add(bar_pos);
 
-        if !self.pattern.is_empty() {
+        if self.args.hyperlinks {
+            // Label the hyperlinks in the viewport, including those
+            // created by the hyperlink_rules
+            let dims = self.delegate.get_dimensions();
+            let top = self.viewport.unwrap_or(dims.physical_top);
+            let (first_row, lines) = self.delegate.get_lines_with_hyperlinks_applied(
+                top..top + dims.viewport_rows as StableRowIndex,
+                &self.config.hyperlink_rules,
+            );
+            let (results, links) = hyperlink_results(first_row, &lines);
+            self.results = results;
+            self.links = links;
+            self.recompute_results();
+        } else if !self.pattern.is_empty() {
             let pane: Rc<dyn Pane> = self.delegate.clone();
             let window = self.window.clone();
             let pattern = self.pattern.clone();
@@ -677,6 +894,9 @@ impl QuickSelectRenderable {
                                             .iter()
                                             .position(|result| result.start_y >= *y);
                                     }
+                                    // The position was carried over from before
+                                    // a resize, so leave the viewport alone
+                                    _ if matches!(r.result_pos, Some(n) if n < num_results) => {}
                                     _ => {
                                         r.activate_match_number(num_results - 1);
                                     }
@@ -714,6 +934,19 @@ impl QuickSelectRenderable {
     fn select_and_copy_match_number(&mut self, n: usize, paste: bool) {
         let result = self.results[n].clone();
 
+        if self.args.hyperlinks {
+            if let Some(link) = self.links.get(result.match_id).cloned() {
+                let pane_id = self.delegate.pane_id();
+                self.window
+                    .notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                        if let Some(pane) = mux::Mux::get().and_then(|mux| mux.get_pane(pane_id)) {
+                            term_window.open_hyperlink(&pane, link);
+                        }
+                    })));
+            }
+            return;
+        }
+
         let pane_id = self.delegate.pane_id();
         let action = self.args.action.clone();
         self.window
@@ -741,7 +974,11 @@ impl QuickSelectRenderable {
                     let text = term_window.selection_text(&pane);
                     if !text.is_empty() {
                         if paste {
-                            let _ = pane.send_paste(&text);
+                            if let Some(text) =
+                                mux_lua::apply_pre_paste_hook(pane.pane_id(), text.clone())
+                            {
+                                let _ = pane.send_paste(&text);
+                            }
                         }
                         if let Some(action) = action {
                             let _ = term_window.perform_key_assignment(&pane, &action);
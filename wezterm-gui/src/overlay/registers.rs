@@ -0,0 +1,48 @@
+//! The register picker presents the named registers and pastes the
+//! content of the selected register into the pane.
+//! The lower part of the overlay shows a preview of its content.
+use crate::overlay::clipboard_history::entry_label;
+use crate::overlay::picker::{run_picker, Picker, PickerGroup, PickerItem};
+use crate::termwindow::TermWindowNotif;
+use mux::pane::PaneId;
+use mux::termwiztermtab::TermWizTerminal;
+use mux::Mux;
+use window::WindowOps;
+
+pub fn registers_picker(
+    registers: Vec<(String, String)>,
+    pane_id: PaneId,
+    term: TermWizTerminal,
+    window: ::window::Window,
+) -> anyhow::Result<()> {
+    let items = registers
+        .into_iter()
+        .map(|(name, text)| PickerItem::new(format!("\"{}  {}", name, entry_label(&text)), text))
+        .collect();
+
+    run_picker(
+        Picker {
+            title: "Registers".to_string(),
+            groups: vec![PickerGroup {
+                name: "Registers".to_string(),
+                items,
+                empty_message: "no registers have been set".to_string(),
+            }],
+            initial_group: 0,
+            variant_names: vec![],
+            accept_hint: "paste".to_string(),
+            show_preview: true,
+            on_accept: Box::new(move |text| {
+                window.notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                    if let Some(pane) = Mux::get().and_then(|mux| mux.get_pane(pane_id)) {
+                        if let Some(text) = mux_lua::apply_pre_paste_hook(pane_id, text) {
+                            pane.trickle_paste(text).ok();
+                            term_window.maybe_scroll_to_bottom_for_input(&pane);
+                        }
+                    }
+                })));
+            }),
+        },
+        term,
+    )
+}
@@ -0,0 +1,393 @@
+//! The settings editor lets the user browse and edit the launch menu,
+//! default program and ssh domains from within wezterm.  Changes are
+//! written to the managed settings file rather than to the lua config,
+//! and the configuration is reloaded to apply them.
+use crate::overlay::picker::{pick, Picker, PickerGroup, PickerItem};
+use config::keyassignment::{SpawnCommand, SpawnTabDomain};
+use config::{configuration, ManagedSettings, ManagedSshDomain};
+use mux::termwiztermtab::TermWizTerminal;
+use termwiz::cell::{AttributeChange, CellAttributes, Intensity};
+use termwiz::color::ColorAttribute;
+use termwiz::lineedit::{LineEditor, LineEditorHost, NopLineEditorHost};
+use termwiz::surface::{Change, Position};
+use termwiz::terminal::Terminal;
+
+const LAUNCH_MENU: &str = "launch_menu";
+const SSH_DOMAINS: &str = "ssh_domains";
+const DEFAULT_PROG: &str = "default_prog";
+const NEW_ENTRY: &str = "new";
+
+fn launch_menu_label(cmd: &SpawnCommand) -> String {
+    match (&cmd.label, &cmd.args) {
+        (Some(label), _) => label.clone(),
+        (None, Some(args)) => args.join(" "),
+        (None, None) => "(default program)".to_string(),
+    }
+}
+
+fn ssh_domain_label(dom: &ManagedSshDomain) -> String {
+    match &dom.username {
+        Some(user) => format!("{} ({}@{})", dom.name, user, dom.remote_address),
+        None => format!("{} ({})", dom.name, dom.remote_address),
+    }
+}
+
+fn settings_picker(settings: &ManagedSettings, initial_group: usize) -> Picker {
+    let mut launch_menu: Vec<PickerItem> = settings
+        .launch_menu
+        .iter()
+        .enumerate()
+        .map(|(idx, cmd)| PickerItem::new(launch_menu_label(cmd), format!("{LAUNCH_MENU}:{idx}")))
+        .collect();
+    launch_menu.push(PickerItem::new(
+        "+ Add a launch menu entry".to_string(),
+        format!("{LAUNCH_MENU}:{NEW_ENTRY}"),
+    ));
+
+    let mut ssh_domains: Vec<PickerItem> = settings
+        .ssh_domains
+        .iter()
+        .enumerate()
+        .map(|(idx, dom)| PickerItem::new(ssh_domain_label(dom), format!("{SSH_DOMAINS}:{idx}")))
+        .collect();
+    ssh_domains.push(PickerItem::new(
+        "+ Add an ssh domain".to_string(),
+        format!("{SSH_DOMAINS}:{NEW_ENTRY}"),
+    ));
+
+    let default_prog = vec![PickerItem::new(
+        match &settings.default_prog {
+            Some(prog) => prog.join(" "),
+            None => "(not set: use the default from the config file)".to_string(),
+        },
+        DEFAULT_PROG.to_string(),
+    )];
+
+    Picker {
+        title: "Settings".to_string(),
+        groups: vec![
+            PickerGroup {
+                name: "Launch Menu".to_string(),
+                items: launch_menu,
+                empty_message: String::new(),
+            },
+            PickerGroup {
+                name: "SSH Domains".to_string(),
+                items: ssh_domains,
+                empty_message: String::new(),
+            },
+            PickerGroup {
+                name: "Default Program".to_string(),
+                items: default_prog,
+                empty_message: String::new(),
+            },
+        ],
+        initial_group,
+        variant_names: vec![],
+        accept_hint: "edit".to_string(),
+        show_preview: false,
+        on_accept: Box::new(|_| {}),
+    }
+}
+
+enum EntryAction {
+    Edit,
+    Delete,
+}
+
+fn choose_entry_action(
+    term: &mut TermWizTerminal,
+    label: String,
+) -> anyhow::Result<Option<EntryAction>> {
+    let choice = pick(
+        Picker {
+            title: "Settings".to_string(),
+            groups: vec![PickerGroup {
+                name: label,
+                items: vec![
+                    PickerItem::new("Edit".to_string(), "edit".to_string()),
+                    PickerItem::new("Delete".to_string(), "delete".to_string()),
+                ],
+                empty_message: String::new(),
+            }],
+            initial_group: 0,
+            variant_names: vec![],
+            accept_hint: "select".to_string(),
+            show_preview: false,
+            on_accept: Box::new(|_| {}),
+        },
+        term,
+    )?;
+    Ok(match choice.as_deref() {
+        Some("edit") => Some(EntryAction::Edit),
+        Some("delete") => Some(EntryAction::Delete),
+        _ => None,
+    })
+}
+
+/// Clears the overlay and explains how the field prompts work
+fn show_form_header(term: &mut TermWizTerminal, title: &str) -> termwiz::Result<()> {
+    term.render(&[
+        Change::ClearScreen(ColorAttribute::Default),
+        Change::CursorPosition {
+            x: Position::Absolute(0),
+            y: Position::Absolute(0),
+        },
+        AttributeChange::Intensity(Intensity::Bold).into(),
+        Change::Text(format!("{}\r\n", title)),
+        Change::AllAttributes(CellAttributes::default()),
+        Change::Text(
+            "Press Enter to keep the value shown in brackets, UpArrow to edit it,\r\n\
+             or enter `-` to clear it.  CTRL-C cancels without saving.\r\n\r\n"
+                .to_string(),
+        ),
+    ])
+}
+
+/// Prompts for the value of a single field.
+/// Returns None if the prompt was cancelled, otherwise the new value,
+/// where an empty string means that the field was cleared.
+fn prompt_field(
+    term: &mut TermWizTerminal,
+    name: &str,
+    current: Option<&str>,
+    required: bool,
+) -> anyhow::Result<Option<String>> {
+    loop {
+        let mut host = NopLineEditorHost::default();
+        if let Some(current) = current {
+            host.history().add(current);
+        }
+        let line = {
+            let mut editor = LineEditor::new(&mut *term);
+            editor.set_prompt(&match current {
+                Some(current) => format!("{} [{}]: ", name, current),
+                None => format!("{}: ", name),
+            });
+            editor.read_line(&mut host)?
+        };
+        let value = match line {
+            Some(line) => match line.trim() {
+                "" => current.unwrap_or_default().to_string(),
+                "-" => String::new(),
+                value => value.to_string(),
+            },
+            None => return Ok(None),
+        };
+        if required && value.is_empty() {
+            term.render(&[Change::Text(format!("{} is required\r\n", name))])?;
+            continue;
+        }
+        return Ok(Some(value));
+    }
+}
+
+fn split_args(value: &str) -> Option<Vec<String>> {
+    let args: Vec<String> = value.split_whitespace().map(str::to_string).collect();
+    if args.is_empty() {
+        None
+    } else {
+        Some(args)
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Returns the edited launch menu entry, or None if editing was cancelled
+fn edit_launch_menu_entry(
+    term: &mut TermWizTerminal,
+    cmd: &SpawnCommand,
+) -> anyhow::Result<Option<SpawnCommand>> {
+    show_form_header(term, "Launch Menu Entry")?;
+
+    let args = cmd.args.as_ref().map(|args| args.join(" "));
+    let cwd = cmd.cwd.as_ref().map(|cwd| cwd.display().to_string());
+    let domain = match &cmd.domain {
+        SpawnTabDomain::DomainName(name) => Some(name.as_str()),
+        _ => None,
+    };
+
+    let label = match prompt_field(term, "Label", cmd.label.as_deref(), false)? {
+        Some(label) => label,
+        None => return Ok(None),
+    };
+    let args = match prompt_field(term, "Command", args.as_deref(), false)? {
+        Some(args) => args,
+        None => return Ok(None),
+    };
+    let cwd = match prompt_field(term, "Working directory", cwd.as_deref(), false)? {
+        Some(cwd) => cwd,
+        None => return Ok(None),
+    };
+    let domain_name = match prompt_field(term, "Domain", domain, false)? {
+        Some(domain) => domain,
+        None => return Ok(None),
+    };
+
+    let mut cmd = cmd.clone();
+    cmd.label = non_empty(label);
+    cmd.args = split_args(&args);
+    cmd.cwd = non_empty(cwd).map(Into::into);
+    if domain.map(str::to_string) != non_empty(domain_name.clone()) {
+        cmd.domain = match non_empty(domain_name) {
+            Some(name) => SpawnTabDomain::DomainName(name),
+            None => SpawnTabDomain::CurrentPaneDomain,
+        };
+    }
+    Ok(Some(cmd))
+}
+
+/// Returns the edited ssh domain, or None if editing was cancelled
+fn edit_ssh_domain(
+    term: &mut TermWizTerminal,
+    dom: &ManagedSshDomain,
+) -> anyhow::Result<Option<ManagedSshDomain>> {
+    show_form_header(term, "SSH Domain")?;
+
+    let name = match prompt_field(term, "Name", non_empty(dom.name.clone()).as_deref(), true)? {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+    let remote_address = match prompt_field(
+        term,
+        "Remote address (host:port)",
+        non_empty(dom.remote_address.clone()).as_deref(),
+        true,
+    )? {
+        Some(addr) => addr,
+        None => return Ok(None),
+    };
+    let username = match prompt_field(term, "Username", dom.username.as_deref(), false)? {
+        Some(user) => user,
+        None => return Ok(None),
+    };
+
+    Ok(Some(ManagedSshDomain {
+        name,
+        remote_address,
+        username: non_empty(username),
+    }))
+}
+
+/// Returns the edited default program, or None if editing was cancelled
+fn edit_default_prog(
+    term: &mut TermWizTerminal,
+    prog: Option<&Vec<String>>,
+) -> anyhow::Result<Option<Option<Vec<String>>>> {
+    show_form_header(term, "Default Program")?;
+    let current = prog.map(|prog| prog.join(" "));
+    Ok(prompt_field(term, "Command", current.as_deref(), false)?.map(|prog| split_args(&prog)))
+}
+
+/// Applies the chosen item from the settings picker.
+/// Returns true if the settings were changed.
+fn edit_item(
+    term: &mut TermWizTerminal,
+    settings: &mut ManagedSettings,
+    value: &str,
+) -> anyhow::Result<bool> {
+    if value == DEFAULT_PROG {
+        return Ok(
+            match edit_default_prog(term, settings.default_prog.as_ref())? {
+                Some(prog) => {
+                    settings.default_prog = prog;
+                    true
+                }
+                None => false,
+            },
+        );
+    }
+
+    let (section, entry) = value
+        .split_once(':')
+        .ok_or_else(|| anyhow::anyhow!("invalid settings item {}", value))?;
+    let idx = match entry {
+        NEW_ENTRY => None,
+        idx => Some(idx.parse::<usize>()?),
+    };
+
+    match (section, idx) {
+        (LAUNCH_MENU, None) => Ok(
+            match edit_launch_menu_entry(term, &SpawnCommand::default())? {
+                Some(cmd) => {
+                    settings.launch_menu.push(cmd);
+                    true
+                }
+                None => false,
+            },
+        ),
+        (LAUNCH_MENU, Some(idx)) => {
+            let label = launch_menu_label(&settings.launch_menu[idx]);
+            Ok(match choose_entry_action(term, label)? {
+                Some(EntryAction::Edit) => {
+                    match edit_launch_menu_entry(term, &settings.launch_menu[idx])? {
+                        Some(cmd) => {
+                            settings.launch_menu[idx] = cmd;
+                            true
+                        }
+                        None => false,
+                    }
+                }
+                Some(EntryAction::Delete) => {
+                    settings.launch_menu.remove(idx);
+                    true
+                }
+                None => false,
+            })
+        }
+        (SSH_DOMAINS, None) => Ok(match edit_ssh_domain(term, &ManagedSshDomain::default())? {
+            Some(dom) => {
+                settings.ssh_domains.push(dom);
+                true
+            }
+            None => false,
+        }),
+        (SSH_DOMAINS, Some(idx)) => {
+            let label = ssh_domain_label(&settings.ssh_domains[idx]);
+            Ok(match choose_entry_action(term, label)? {
+                Some(EntryAction::Edit) => match edit_ssh_domain(term, &settings.ssh_domains[idx])?
+                {
+                    Some(dom) => {
+                        settings.ssh_domains[idx] = dom;
+                        true
+                    }
+                    None => false,
+                },
+                Some(EntryAction::Delete) => {
+                    settings.ssh_domains.remove(idx);
+                    true
+                }
+                None => false,
+            })
+        }
+        _ => anyhow::bail!("invalid settings item {}", value),
+    }
+}
+
+pub fn settings_editor(mut term: TermWizTerminal) -> anyhow::Result<()> {
+    let path = configuration().managed_settings_path();
+    let mut settings = ManagedSettings::load(&path)?;
+    let mut group = 0;
+
+    while let Some(value) = pick(settings_picker(&settings, group), &mut term)? {
+        group = if value.starts_with(SSH_DOMAINS) {
+            1
+        } else if value == DEFAULT_PROG {
+            2
+        } else {
+            0
+        };
+        if edit_item(&mut term, &mut settings, &value)? {
+            settings.save(&path)?;
+            config::reload();
+        }
+    }
+
+    Ok(())
+}
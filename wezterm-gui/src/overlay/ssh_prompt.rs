@@ -0,0 +1,97 @@
+//! Shows the host verification and keyboard-interactive authentication
+//! prompts of an ssh domain in an overlay over the pane that is
+//! connecting, rather than as text in the pane itself.
+use crate::overlay::confirm_close_pane::run_confirmation_app;
+use crate::TermWindow;
+use mux::pane::PaneId;
+use mux::ssh::{PasswordPromptHost, SshPrompt};
+use mux::termwiztermtab::TermWizTerminal;
+use termwiz::cell::AttributeChange;
+use termwiz::lineedit::LineEditor;
+use termwiz::surface::{Change, CursorVisibility};
+use termwiz::terminal::Terminal;
+
+pub fn ssh_prompt(
+    pane_id: PaneId,
+    mut term: TermWizTerminal,
+    window: ::window::Window,
+    domain_name: String,
+    prompt: SshPrompt,
+) -> anyhow::Result<()> {
+    let result = match prompt {
+        SshPrompt::HostVerify { message, reply } => {
+            let message = format!("🔐 {}: {}", domain_name, message.replace('\n', " "));
+            let trust = run_confirmation_app(&message, &mut term).unwrap_or(false);
+            smol::block_on(reply.send(trust)).map_err(|e| anyhow::anyhow!("{:#}", e))
+        }
+        SshPrompt::Authenticate {
+            username,
+            instructions,
+            prompts,
+            reply,
+        } => {
+            let answers = authenticate(&mut term, &domain_name, &username, &instructions, &prompts)
+                .unwrap_or_else(|err| {
+                    log::error!("ssh authentication prompt: {:#}", err);
+                    None
+                });
+            smol::block_on(reply.send(answers)).map_err(|e| anyhow::anyhow!("{:#}", e))
+        }
+    };
+    TermWindow::schedule_cancel_overlay_for_pane(window, pane_id);
+
+    result
+}
+
+/// Collects the responses to the prompts.
+/// Returns None if the user cancelled any of the prompts.
+fn authenticate(
+    term: &mut TermWizTerminal,
+    domain_name: &str,
+    username: &str,
+    instructions: &str,
+    prompts: &[wezterm_ssh::AuthenticationPrompt],
+) -> anyhow::Result<Option<Vec<String>>> {
+    term.set_raw_mode()?;
+
+    let mut title = format!("🔐 Authenticating to {}", domain_name);
+    if !username.is_empty() {
+        title.push_str(&format!(" as {}", username));
+    }
+    let mut changes = vec![
+        Change::ClearScreen(Default::default()),
+        Change::CursorVisibility(CursorVisibility::Visible),
+        AttributeChange::Intensity(termwiz::cell::Intensity::Bold).into(),
+        Change::Text(format!("{}\r\n", title)),
+        AttributeChange::Intensity(termwiz::cell::Intensity::Normal).into(),
+    ];
+    if !instructions.is_empty() {
+        changes.push(Change::Text(format!(
+            "{}\r\n",
+            instructions.replace('\n', "\r\n")
+        )));
+    }
+    changes.push(Change::Text("Press CTRL-C to cancel.\r\n\r\n".to_string()));
+    term.render(&changes)?;
+
+    let mut answers = vec![];
+    for prompt in prompts {
+        let mut prompt_lines = prompt.prompt.split('\n').collect::<Vec<_>>();
+        let editor_prompt = prompt_lines.pop().unwrap_or_default();
+        for line in &prompt_lines {
+            term.render(&[Change::Text(format!("{}\r\n", line))])?;
+        }
+
+        // Responses that are not echoed are obscured as they are
+        // typed, and are never added to the history of the editor
+        let mut host = PasswordPromptHost::new(prompt.echo);
+        let mut editor = LineEditor::new(&mut *term);
+        editor.set_prompt(editor_prompt);
+        match editor.read_line(&mut host)? {
+            Some(line) => answers.push(line),
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(answers))
+}
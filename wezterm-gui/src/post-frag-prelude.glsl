@@ -0,0 +1,17 @@
+// This is prepended to the post_processing_shader from the config.
+// The shader must define main() and assign the final pixel to `color`.
+
+precision highp float;
+
+// The texture coordinate of this pixel in frame_texture
+in vec2 tex_coord;
+
+out vec4 color;
+
+// The rendered frame
+uniform sampler2D frame_texture;
+// The size of the frame in pixels
+uniform vec2 resolution;
+// The number of seconds since the shader was loaded
+uniform float time;
+
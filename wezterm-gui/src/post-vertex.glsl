@@ -0,0 +1,13 @@
+// This is the post processing vertex shader.
+// It draws a single quad that covers the whole viewport.
+
+precision highp float;
+
+in vec2 position;
+
+out vec2 tex_coord;
+
+void main() {
+    tex_coord = (position + 1.0) * 0.5;
+    gl_Position = vec4(position, 0.0, 1.0);
+}
@@ -130,4 +130,19 @@ impl<'a> Quad<'a> {
         self.vert[V_BOT_LEFT].position = (left, bottom);
         self.vert[V_BOT_RIGHT].position = (right, bottom);
     }
+
+    /// Assign the position of each corner independently, for quads
+    /// that aren't aligned with the axes, such as a sloping line
+    pub fn set_corners(
+        &mut self,
+        top_left: (f32, f32),
+        top_right: (f32, f32),
+        bot_left: (f32, f32),
+        bot_right: (f32, f32),
+    ) {
+        self.vert[V_TOP_LEFT].position = top_left;
+        self.vert[V_TOP_RIGHT].position = top_right;
+        self.vert[V_BOT_LEFT].position = bot_left;
+        self.vert[V_BOT_RIGHT].position = bot_right;
+    }
 }
@@ -0,0 +1,82 @@
+//! Named registers, in the manner of vim, that hold text copied using
+//! `CopyTo(Register(name))`, typically from copy mode, and that can be
+//! pasted using `PasteFrom(Register(name))` or picked from the overlay
+//! shown by `ShowRegisters`.
+//! Registers are held in memory for the lifetime of the process and
+//! are shared by all windows.
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+lazy_static::lazy_static! {
+    static ref REGISTERS: Mutex<Registers> = Mutex::new(Registers::default());
+}
+
+#[derive(Debug, Default)]
+struct Registers {
+    registers: BTreeMap<String, String>,
+}
+
+impl Registers {
+    /// Stores `text` in the named register.  As in vim, a single
+    /// uppercase letter appends to the register named by the
+    /// corresponding lowercase letter rather than replacing it.
+    fn set(&mut self, name: &str, text: &str) {
+        let mut chars = name.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_uppercase() => {
+                self.registers
+                    .entry(c.to_ascii_lowercase().to_string())
+                    .or_default()
+                    .push_str(text);
+            }
+            _ => {
+                self.registers.insert(name.to_string(), text.to_string());
+            }
+        }
+    }
+}
+
+pub fn get_register(name: &str) -> Option<String> {
+    REGISTERS.lock().unwrap().registers.get(name).cloned()
+}
+
+/// Stores `text` in the named register; see `Registers::set`
+pub fn set_register(name: &str, text: &str) {
+    REGISTERS.lock().unwrap().set(name, text);
+}
+
+pub fn clear_register(name: &str) {
+    REGISTERS.lock().unwrap().registers.remove(name);
+}
+
+/// Returns the names and contents of the registers,
+/// sorted by name
+pub fn registers() -> Vec<(String, String)> {
+    REGISTERS
+        .lock()
+        .unwrap()
+        .registers
+        .iter()
+        .map(|(name, text)| (name.clone(), text.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn uppercase_appends() {
+        let mut registers = Registers::default();
+        registers.set("a", "one");
+        registers.set("A", " two");
+        registers.set("B", "three");
+        registers.set("AB", "four");
+        assert_eq!(registers.registers.get("a").unwrap(), "one two");
+        assert_eq!(registers.registers.get("b").unwrap(), "three");
+        assert_eq!(registers.registers.get("AB").unwrap(), "four");
+
+        registers.set("a", "five");
+        assert_eq!(registers.registers.get("a").unwrap(), "five");
+    }
+}
@@ -8,8 +8,13 @@ use ::window::glium::texture::SrgbTexture2d;
 use ::window::glium::{CapabilitiesSource, IndexBuffer, VertexBuffer};
 use ::window::*;
 use anyhow::Context;
-use std::cell::{Ref, RefCell, RefMut};
+use config::ConfigHandle;
+use luahelper::impl_lua_conversion_dynamic;
+use std::cell::{Cell, Ref, RefCell, RefMut};
+use std::path::Path;
 use std::rc::Rc;
+use std::time::Instant;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
 use wezterm_font::FontConfiguration;
 
 const INDICES_PER_CELL: usize = 6;
@@ -186,12 +191,221 @@ impl RenderLayer {
     }
 }
 
+#[derive(Copy, Clone, Default)]
+pub struct PostVertex {
+    pub position: [f32; 2],
+}
+::window::glium::implement_vertex!(PostVertex, position);
+
+/// Returns a quad that covers the whole viewport, for drawing
+/// with post-vertex.glsl
+fn viewport_quad(context: &Rc<GliumContext>) -> anyhow::Result<VertexBuffer<PostVertex>> {
+    Ok(VertexBuffer::new(
+        context,
+        &[
+            PostVertex {
+                position: [-1.0, -1.0],
+            },
+            PostVertex {
+                position: [1.0, -1.0],
+            },
+            PostVertex {
+                position: [-1.0, 1.0],
+            },
+            PostVertex {
+                position: [1.0, 1.0],
+            },
+        ],
+    )?)
+}
+
+/// Allocates an sRGB texture of the specified size into `slot`, unless
+/// it already holds one of that size.  Content is rendered into it
+/// in the same color space as the glyph atlas and the window surface,
+/// so that colors are unchanged when it is drawn into the window.
+fn alloc_srgb_texture(
+    context: &Rc<GliumContext>,
+    slot: &mut Option<SrgbTexture2d>,
+    width: u32,
+    height: u32,
+) -> anyhow::Result<()> {
+    let needs_alloc = match slot {
+        Some(texture) => texture.width() != width || texture.height() != height,
+        None => true,
+    };
+    if needs_alloc {
+        let texture = SrgbTexture2d::empty_with_format(
+            context,
+            glium::texture::SrgbFormat::U8U8U8U8,
+            glium::texture::MipmapsOption::NoMipmap,
+            width,
+            height,
+        )?;
+        slot.replace(texture);
+    }
+    Ok(())
+}
+
+/// The `post_processing_shader` from the config, along with the
+/// texture into which the frame is rendered before the shader is
+/// applied to it
+pub struct PostProcess {
+    pub prog: glium::Program,
+    pub vertices: VertexBuffer<PostVertex>,
+    texture: RefCell<Option<SrgbTexture2d>>,
+    started: Instant,
+}
+
+impl PostProcess {
+    fn new(context: &Rc<GliumContext>, path: &Path) -> anyhow::Result<Self> {
+        let source =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let prog = RenderState::compile_prog(context, |version| {
+            (
+                format!("#version {}\n{}", version, include_str!("post-vertex.glsl")),
+                format!(
+                    "#version {}\n{}{}",
+                    version,
+                    include_str!("post-frag-prelude.glsl"),
+                    source
+                ),
+            )
+        })?;
+
+        let vertices = viewport_quad(context)?;
+
+        Ok(Self {
+            prog,
+            vertices,
+            texture: RefCell::new(None),
+            started: Instant::now(),
+        })
+    }
+
+    /// Returns the texture into which the frame should be rendered,
+    /// (re)allocating it to match the size of the window
+    pub fn texture(
+        &self,
+        context: &Rc<GliumContext>,
+        width: u32,
+        height: u32,
+    ) -> anyhow::Result<Ref<SrgbTexture2d>> {
+        alloc_srgb_texture(context, &mut self.texture.borrow_mut(), width, height)?;
+        Ok(Ref::map(self.texture.borrow(), |t| {
+            t.as_ref().expect("allocated above")
+        }))
+    }
+
+    /// The value for the `time` uniform
+    pub fn elapsed(&self) -> f32 {
+        self.started.elapsed().as_secs_f32()
+    }
+
+    /// Returns true if the shader uses the `time` uniform, in which
+    /// case it needs to be redrawn at the animation frame rate
+    pub fn is_animated(&self) -> bool {
+        self.prog.get_uniform("time").is_some()
+    }
+}
+
+/// The program used to blur the window background behind panes
+/// that have a `pane_background_blur` radius, along with the
+/// textures that hold the background and the horizontally
+/// blurred background
+pub struct BackdropBlur {
+    pub prog: glium::Program,
+    pub vertices: VertexBuffer<PostVertex>,
+    textures: RefCell<[Option<SrgbTexture2d>; 2]>,
+}
+
+impl BackdropBlur {
+    fn new(context: &Rc<GliumContext>) -> anyhow::Result<Self> {
+        let prog = RenderState::compile_prog(context, |version| {
+            (
+                format!("#version {}\n{}", version, include_str!("post-vertex.glsl")),
+                format!("#version {}\n{}", version, include_str!("blur-frag.glsl")),
+            )
+        })?;
+        Ok(Self {
+            prog,
+            vertices: viewport_quad(context)?,
+            textures: RefCell::new([None, None]),
+        })
+    }
+
+    /// Returns the textures for the background and the horizontal
+    /// pass, (re)allocating them to match the size of the window
+    pub fn textures(
+        &self,
+        context: &Rc<GliumContext>,
+        width: u32,
+        height: u32,
+    ) -> anyhow::Result<Ref<[Option<SrgbTexture2d>; 2]>> {
+        {
+            let mut textures = self.textures.borrow_mut();
+            for slot in textures.iter_mut() {
+                alloc_srgb_texture(context, slot, width, height)?;
+            }
+        }
+        Ok(self.textures.borrow())
+    }
+}
+
+/// The program used to make the area outside of the rounded corners
+/// of the window transparent, when `window_corner_radius` is set
+pub struct CornerMask {
+    pub prog: glium::Program,
+    pub vertices: VertexBuffer<PostVertex>,
+}
+
+impl CornerMask {
+    fn new(context: &Rc<GliumContext>) -> anyhow::Result<Self> {
+        let prog = RenderState::compile_prog(context, |version| {
+            (
+                format!("#version {}\n{}", version, include_str!("post-vertex.glsl")),
+                format!(
+                    "#version {}\n{}",
+                    version,
+                    include_str!("corner-mask-frag.glsl")
+                ),
+            )
+        })?;
+        Ok(Self {
+            prog,
+            vertices: viewport_quad(context)?,
+        })
+    }
+}
+
+/// Counts how often the glyph atlas of a window was cleared or grown
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, FromDynamic, ToDynamic)]
+pub struct GlyphAtlasStats {
+    /// The width and height of the atlas, in pixels
+    pub size: usize,
+    /// The number of glyphs that are held in the atlas
+    pub glyphs: usize,
+    /// The number of times that the atlas was cleared, either to
+    /// make room or because the font size changed
+    pub evictions: usize,
+    /// The total number of glyphs that were discarded by those clears
+    pub evicted_glyphs: usize,
+    /// The number of times that the atlas was grown
+    pub grows: usize,
+}
+impl_lua_conversion_dynamic!(GlyphAtlasStats);
+
 pub struct RenderState {
     pub context: Rc<GliumContext>,
     pub glyph_cache: RefCell<GlyphCache<SrgbTexture2d>>,
     pub util_sprites: UtilSprites<SrgbTexture2d>,
     pub glyph_prog: glium::Program,
     pub layers: RefCell<Vec<Rc<RenderLayer>>>,
+    pub post_process: RefCell<Option<PostProcess>>,
+    /// The config generation for which post_process was loaded
+    post_process_generation: Cell<Option<usize>>,
+    backdrop_blur: RefCell<Option<Rc<BackdropBlur>>>,
+    corner_mask: RefCell<Option<Rc<CornerMask>>>,
+    atlas_stats: GlyphAtlasStats,
 }
 
 impl RenderState {
@@ -216,6 +430,11 @@ impl RenderState {
                         util_sprites,
                         glyph_prog,
                         layers: RefCell::new(vec![main_layer]),
+                        post_process: RefCell::new(None),
+                        post_process_generation: Cell::new(None),
+                        backdrop_blur: RefCell::new(None),
+                        corner_mask: RefCell::new(None),
+                        atlas_stats: GlyphAtlasStats::default(),
                     });
                 }
                 Err(OutOfTextureSpace {
@@ -280,9 +499,61 @@ impl RenderState {
         Ok(allocated)
     }
 
+    /// Loads the `post_processing_shader` when the configuration has
+    /// changed since it was last loaded, which also happens when the
+    /// shader file is modified.  If the shader cannot be compiled, the
+    /// error is logged and the frame is rendered without it.
+    pub fn update_post_process(&self, config: &ConfigHandle) {
+        let generation = config.generation();
+        if self.post_process_generation.get() == Some(generation) {
+            return;
+        }
+        self.post_process_generation.set(Some(generation));
+        self.post_process.borrow_mut().take();
+
+        if let Some(path) = &config.post_processing_shader {
+            match PostProcess::new(&self.context, path) {
+                Ok(post_process) => {
+                    self.post_process.borrow_mut().replace(post_process);
+                }
+                Err(err) => {
+                    log::error!(
+                        "post_processing_shader {} will not be used: {:#}",
+                        path.display(),
+                        err
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns the backdrop blur program, compiling it the first
+    /// time that it is needed
+    pub fn backdrop_blur(&self) -> anyhow::Result<Rc<BackdropBlur>> {
+        let mut blur = self.backdrop_blur.borrow_mut();
+        if let Some(blur) = blur.as_ref() {
+            return Ok(Rc::clone(blur));
+        }
+        let new_blur = Rc::new(BackdropBlur::new(&self.context)?);
+        blur.replace(Rc::clone(&new_blur));
+        Ok(new_blur)
+    }
+
+    /// Returns the corner mask program, compiling it the first
+    /// time that it is needed
+    pub fn corner_mask(&self) -> anyhow::Result<Rc<CornerMask>> {
+        let mut mask = self.corner_mask.borrow_mut();
+        if let Some(mask) = mask.as_ref() {
+            return Ok(Rc::clone(mask));
+        }
+        let new_mask = Rc::new(CornerMask::new(&self.context)?);
+        mask.replace(Rc::clone(&new_mask));
+        Ok(new_mask)
+    }
+
     fn compile_prog(
         context: &Rc<GliumContext>,
-        fragment_shader: fn(&str) -> (String, String),
+        fragment_shader: impl Fn(&str) -> (String, String),
     ) -> anyhow::Result<glium::Program> {
         let mut errors = vec![];
 
@@ -323,6 +594,15 @@ impl RenderState {
         )
     }
 
+    pub fn glyph_atlas_stats(&self) -> GlyphAtlasStats {
+        let glyph_cache = self.glyph_cache.borrow();
+        GlyphAtlasStats {
+            size: glyph_cache.atlas.size(),
+            glyphs: glyph_cache.num_glyphs(),
+            ..self.atlas_stats
+        }
+    }
+
     pub fn recreate_texture_atlas(
         &mut self,
         fonts: &Rc<FontConfiguration>,
@@ -373,6 +653,13 @@ impl RenderState {
 
         let mut glyph_cache = self.glyph_cache.borrow_mut();
 
+        if size > glyph_cache.atlas.size() {
+            self.atlas_stats.grows += 1;
+        } else {
+            self.atlas_stats.evictions += 1;
+            self.atlas_stats.evicted_glyphs += glyph_cache.num_glyphs();
+        }
+
         // Steal the decoded image cache; without this, any animating gifs
         // would reset back to frame 0 each time we filled the texture
         std::mem::swap(
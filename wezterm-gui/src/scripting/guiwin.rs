@@ -1,20 +1,22 @@
 //! GuiWin represents a Gui TermWindow (as opposed to a Mux window) in lua code
 use super::luaerr;
 use super::pane::PaneObject;
+use crate::termwindow::palettecolor::PaletteSlot;
 use crate::termwindow::TermWindowNotif;
 use crate::TermWindow;
 use config::keyassignment::{ClipboardCopyDestination, KeyAssignment};
+use config::DecorationLayer;
 use luahelper::*;
-use mlua::{UserData, UserDataMethods};
+use mlua::{FromLua, UserData, UserDataMethods};
+use mux::export::lines_to_escapes;
 use mux::pane::PaneId;
 use mux::window::WindowId as MuxWindowId;
 use mux::Mux;
-use termwiz::cell::CellAttributes;
-use termwiz::surface::{Change, Line};
-use termwiz_funcs::new_wezterm_terminfo_renderer;
+use termwiz::color::SrgbaTuple;
+use termwiz_funcs::{format_as_escapes, FormatItem};
 use wezterm_dynamic::{FromDynamic, ToDynamic};
 use wezterm_toast_notification::ToastNotification;
-use window::{Connection, ConnectionOps, DeadKeyStatus, WindowOps, WindowState};
+use window::{Connection, ConnectionOps, DeadKeyStatus, InputRegion, WindowOps, WindowState};
 
 #[derive(Clone)]
 pub struct GuiWin {
@@ -33,6 +35,17 @@ impl GuiWin {
     }
 }
 
+/// A rectangle in the window that accepts mouse input,
+/// as passed to `window:set_input_region`
+#[derive(FromDynamic, ToDynamic)]
+struct InputRect {
+    x: isize,
+    y: isize,
+    width: isize,
+    height: isize,
+}
+impl_lua_conversion_dynamic!(InputRect);
+
 impl UserData for GuiWin {
     fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
         methods.add_meta_method(mlua::MetaMethod::ToString, |_, this, _: ()| {
@@ -70,6 +83,30 @@ impl UserData for GuiWin {
             this.window.toggle_fullscreen();
             Ok(())
         });
+        methods.add_method("set_click_through", |_, this, click_through: bool| {
+            this.window.set_input_region(if click_through {
+                InputRegion::Nowhere
+            } else {
+                InputRegion::Everywhere
+            });
+            Ok(())
+        });
+        methods.add_method(
+            "set_input_region",
+            |_, this, rects: Option<Vec<InputRect>>| {
+                this.window.set_input_region(match rects {
+                    None => InputRegion::Everywhere,
+                    Some(rects) if rects.is_empty() => InputRegion::Nowhere,
+                    Some(rects) => InputRegion::Rects(
+                        rects
+                            .into_iter()
+                            .map(|r| euclid::rect(r.x, r.y, r.width, r.height))
+                            .collect(),
+                    ),
+                });
+                Ok(())
+            },
+        );
         methods.add_method(
             "toast_notification",
             |_, _, (title, message, url, timeout): (String, String, Option<String>, Option<u64>)| {
@@ -85,14 +122,115 @@ impl UserData for GuiWin {
         methods.add_method("get_appearance", |_, _, _: ()| {
             Ok(Connection::get().unwrap().get_appearance().to_string())
         });
+        methods.add_method("set_font_scale", |_, this, font_scale: f64| {
+            if !font_scale.is_finite() || font_scale <= 0.0 {
+                return Err(mlua::Error::external(format!(
+                    "font_scale must be a positive number, got {font_scale}"
+                )));
+            }
+            this.window
+                .notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                    if let Some(window) = term_window.window.clone() {
+                        term_window.set_window_font_scale(font_scale, &window);
+                    }
+                })));
+            Ok(())
+        });
+        methods.add_method("apply_preset", |_, this, name: String| {
+            let config = config::configuration();
+            let preset = config
+                .window_size_preset_by_name(&name)
+                .ok_or_else(|| {
+                    mlua::Error::external(format!("no window size preset named `{name}`"))
+                })?
+                .clone();
+            preset.size().map_err(luaerr)?;
+            this.window
+                .notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                    if let Some(window) = term_window.window.clone() {
+                        if let Err(err) = term_window.apply_window_size_preset(&preset, &window) {
+                            log::error!("apply_preset `{}`: {:#}", preset.name, err);
+                        }
+                    }
+                })));
+            Ok(())
+        });
+        methods.add_method(
+            "set_palette_color",
+            |_, this, (index, color, persist): (mlua::Value, String, Option<bool>)| {
+                let slot = match &index {
+                    mlua::Value::Integer(i) => u8::try_from(*i).ok().map(PaletteSlot::Indexed),
+                    mlua::Value::String(s) => PaletteSlot::from_name(s.to_str()?),
+                    _ => None,
+                }
+                .ok_or_else(|| {
+                    mlua::Error::external(format!(
+                        "palette index must be a number in the range 0-255 or one of \
+                         foreground, background, cursor_bg, selection_fg or selection_bg, \
+                         got {index:?}"
+                    ))
+                })?;
+                let color: SrgbaTuple = color
+                    .parse()
+                    .map_err(|_| mlua::Error::external(format!("invalid color {color}")))?;
+                let persist = persist.unwrap_or(false);
+                this.window
+                    .notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                        term_window.set_palette_color(slot, color, persist);
+                    })));
+                Ok(())
+            },
+        );
+        methods.add_method(
+            "set_decorations",
+            |_, this, (name, layer): (String, Option<DecorationLayer>)| {
+                this.window
+                    .notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                        term_window.set_decorations(name, layer);
+                    })));
+                Ok(())
+            },
+        );
+        methods.add_method(
+            "call_every",
+            |lua, this, (interval, func): (f64, mlua::Function)| {
+                let interval = time_funcs::interval_from_seconds(interval)?;
+                let window = this.clone();
+                // The timer stops once the window has been closed
+                time_funcs::schedule_every(lua, interval, func, move || {
+                    let mux = Mux::get()?;
+                    let tab = mux.get_active_tab_for_window(window.mux_window_id)?;
+                    let pane = tab.get_active_pane()?;
+                    Some((window.clone(), PaneObject::new(&pane)))
+                })
+            },
+        );
         methods.add_method("set_right_status", |_, this, status: String| {
-            this.window.notify(TermWindowNotif::SetRightStatus(status));
+            this.window.notify(TermWindowNotif::SetStatus {
+                segment: "right".to_string(),
+                status,
+            });
             Ok(())
         });
+        methods.add_method(
+            "set_status",
+            |lua, this, (segment, status): (String, mlua::Value)| {
+                let status = match status {
+                    mlua::Value::Table(_) => {
+                        let items = <Vec<FormatItem>>::from_lua(status, lua)?;
+                        format_as_escapes(items).map_err(luaerr)?
+                    }
+                    _ => String::from_lua(status, lua)?,
+                };
+                this.window
+                    .notify(TermWindowNotif::SetStatus { segment, status });
+                Ok(())
+            },
+        );
         methods.add_async_method("get_dimensions", |_, this, _: ()| async move {
             let (tx, rx) = smol::channel::bounded(1);
             this.window.notify(TermWindowNotif::GetDimensions(tx));
-            let (dims, window_state) = rx
+            let (dims, window_state, font_scale) = rx
                 .recv()
                 .await
                 .map_err(|e| anyhow::anyhow!("{:#}", e))
@@ -104,6 +242,7 @@ impl UserData for GuiWin {
                 pixel_height: usize,
                 dpi: usize,
                 is_full_screen: bool,
+                font_scale: f64,
             }
             impl_lua_conversion_dynamic!(Dims);
 
@@ -112,6 +251,7 @@ impl UserData for GuiWin {
                 pixel_height: dims.pixel_height,
                 dpi: dims.dpi,
                 is_full_screen: window_state.contains(WindowState::FULL_SCREEN),
+                font_scale,
                 // FIXME: expose other states here
             };
             Ok(dims)
@@ -185,6 +325,20 @@ impl UserData for GuiWin {
 
             Ok(result)
         });
+        methods.add_async_method("is_visible", |_, this, _: ()| async move {
+            let (tx, rx) = smol::channel::bounded(1);
+            this.window
+                .notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                    tx.try_send(term_window.is_visible()).ok();
+                })));
+            let result = rx
+                .recv()
+                .await
+                .map_err(|e| anyhow::anyhow!("{:#}", e))
+                .map_err(luaerr)?;
+
+            Ok(result)
+        });
         methods.add_async_method("composition_status", |_, this, _: ()| async move {
             let (tx, rx) = smol::channel::bounded(1);
             this.window
@@ -203,6 +357,92 @@ impl UserData for GuiWin {
 
             Ok(result)
         });
+        methods.add_async_method("compose_state", |_, this, _: ()| async move {
+            #[derive(FromDynamic, ToDynamic)]
+            struct Candidate {
+                keys: Vec<String>,
+                result: String,
+            }
+            #[derive(FromDynamic, ToDynamic)]
+            struct State {
+                keys: Vec<String>,
+                candidates: Vec<Candidate>,
+            }
+            impl_lua_conversion_dynamic!(State);
+
+            let (tx, rx) = smol::channel::bounded(1);
+            this.window
+                .notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                    tx.try_send(term_window.compose_state().map(|state| {
+                        State {
+                            keys: state.keys.clone(),
+                            candidates: state
+                                .candidates
+                                .iter()
+                                .map(|c| Candidate {
+                                    keys: c.keys.clone(),
+                                    result: c.result.clone(),
+                                })
+                                .collect(),
+                        }
+                    }))
+                    .ok();
+                })));
+            let result = rx
+                .recv()
+                .await
+                .map_err(|e| anyhow::anyhow!("{:#}", e))
+                .map_err(luaerr)?;
+
+            Ok(result)
+        });
+        methods.add_async_method("measure_text", |_, this, styled: String| async move {
+            let (tx, rx) = smol::channel::bounded(1);
+            this.window
+                .notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                    tx.try_send(
+                        term_window
+                            .measure_text(&styled)
+                            .map_err(|err| format!("{:#}", err)),
+                    )
+                    .ok();
+                })));
+            let result = rx
+                .recv()
+                .await
+                .map_err(mlua::Error::external)?
+                .map_err(mlua::Error::external)?;
+
+            Ok(result)
+        });
+        methods.add_async_method("glyph_atlas_stats", |_, this, _: ()| async move {
+            let (tx, rx) = smol::channel::bounded(1);
+            this.window
+                .notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                    tx.try_send(term_window.glyph_atlas_stats()).ok();
+                })));
+            let result = rx
+                .recv()
+                .await
+                .map_err(|e| anyhow::anyhow!("{:#}", e))
+                .map_err(luaerr)?;
+
+            Ok(result)
+        });
+        methods.add_async_method("recording_macro", |_, this, _: ()| async move {
+            let (tx, rx) = smol::channel::bounded(1);
+            this.window
+                .notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                    tx.try_send(term_window.recording_macro_name()).ok();
+                })));
+            let result = rx
+                .recv()
+                .await
+                .map_err(|e| anyhow::anyhow!("{:#}", e))
+                .map_err(luaerr)?;
+
+            Ok(result)
+        });
         methods.add_async_method("active_key_table", |_, this, _: ()| async move {
             let (tx, rx) = smol::channel::bounded(1);
             this.window
@@ -263,40 +503,3 @@ impl UserData for GuiWin {
         );
     }
 }
-
-fn lines_to_escapes(lines: Vec<Line>) -> anyhow::Result<String> {
-    let mut changes = vec![];
-    let mut attr = CellAttributes::blank();
-    for line in lines {
-        changes.append(&mut line.changes(&attr));
-        changes.push(Change::Text("\r\n".to_string()));
-        if let Some(a) = line.cells().last().map(|cell| cell.attrs()) {
-            attr = a.clone();
-        }
-    }
-    changes.push(Change::AllAttributes(CellAttributes::blank()));
-    let mut renderer = new_wezterm_terminfo_renderer();
-
-    struct Target {
-        target: Vec<u8>,
-    }
-
-    impl std::io::Write for Target {
-        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-            std::io::Write::write(&mut self.target, buf)
-        }
-        fn flush(&mut self) -> std::io::Result<()> {
-            Ok(())
-        }
-    }
-
-    impl termwiz::render::RenderTty for Target {
-        fn get_size_in_cells(&mut self) -> termwiz::Result<(usize, usize)> {
-            Ok((80, 24))
-        }
-    }
-
-    let mut target = Target { target: vec![] };
-    renderer.render_to(&changes, &mut target)?;
-    Ok(String::from_utf8(target.target)?)
-}
@@ -1,7 +1,17 @@
+use crate::fontresolver::{measure_line, resolve_text, TextExtent};
 use crate::frontend::try_front_end;
-use config::lua::get_or_create_sub_module;
+use config::keyassignment::MacroEvent;
 use config::lua::mlua::{self, Lua};
+use config::lua::{get_or_create_module, get_or_create_sub_module};
+use luahelper::impl_lua_conversion_dynamic;
 use mux::window::WindowId as MuxWindowId;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use termwiz::cell::{CellAttributes, Intensity};
+use termwiz_funcs::{format_as_escapes, FormatItem};
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+use wezterm_font::FontConfiguration;
 
 pub mod guiwin;
 pub mod pane;
@@ -10,7 +20,123 @@ fn luaerr(err: anyhow::Error) -> mlua::Error {
     mlua::Error::external(err)
 }
 
+/// The attributes of the text passed to wezterm.font_resolver
+#[derive(Default, FromDynamic, ToDynamic)]
+struct FontResolverAttributes {
+    #[dynamic(default)]
+    intensity: Option<Intensity>,
+    #[dynamic(default)]
+    italic: bool,
+}
+impl_lua_conversion_dynamic!(FontResolverAttributes);
+
+/// Describes a glyph returned from wezterm.font_resolver
+#[derive(FromDynamic, ToDynamic)]
+struct ResolvedGlyphInfo {
+    cluster: usize,
+    text: String,
+    x_advance: f64,
+    num_cells: u8,
+    glyph_index: u32,
+    glyph_name: Option<String>,
+    font: String,
+    font_source: String,
+    synthesize_bold: bool,
+    synthesize_italic: bool,
+    drawn_by_wezterm: bool,
+}
+impl_lua_conversion_dynamic!(ResolvedGlyphInfo);
+
+fn font_resolver(
+    text: String,
+    attrs: Option<FontResolverAttributes>,
+) -> anyhow::Result<Vec<ResolvedGlyphInfo>> {
+    let config = config::configuration();
+    let font_config = wezterm_font::FontConfiguration::new(
+        Some(config.clone()),
+        config.dpi.unwrap_or_else(|| ::window::default_dpi()) as usize,
+    )?;
+
+    let attrs = attrs.unwrap_or_default();
+    let mut cell_attrs = CellAttributes::default();
+    if let Some(intensity) = attrs.intensity {
+        cell_attrs.set_intensity(intensity);
+    }
+    cell_attrs.set_italic(attrs.italic);
+
+    let clusters = resolve_text(&config, &font_config, &text, &cell_attrs)?;
+    Ok(clusters
+        .into_iter()
+        .flat_map(|cluster| cluster.glyphs)
+        .map(|glyph| ResolvedGlyphInfo {
+            cluster: glyph.cluster,
+            text: glyph.text,
+            x_advance: glyph.x_advance,
+            num_cells: glyph.num_cells,
+            glyph_index: glyph.glyph_pos,
+            glyph_name: glyph.glyph_name,
+            font: glyph.font.lua_name(),
+            font_source: glyph.font.handle.diagnostic_string(),
+            synthesize_bold: glyph.font.synthesize_bold,
+            synthesize_italic: glyph.font.synthesize_italic,
+            drawn_by_wezterm: glyph.custom_block.is_some(),
+        })
+        .collect())
+}
+
+thread_local! {
+    /// format_width is typically called for each tab whenever the tab bar
+    /// is updated, so the fonts are kept until the config is reloaded
+    /// rather than being loaded on every call
+    static MEASURE_FONTS: RefCell<Option<(usize, Rc<FontConfiguration>)>> = RefCell::new(None);
+}
+
+fn measure_fonts(config: &config::ConfigHandle) -> anyhow::Result<Rc<FontConfiguration>> {
+    MEASURE_FONTS.with(|fonts| {
+        let mut fonts = fonts.borrow_mut();
+        if let Some((generation, fonts)) = fonts.as_ref() {
+            if *generation == config.generation() {
+                return Ok(Rc::clone(fonts));
+            }
+        }
+        let font_config = Rc::new(FontConfiguration::new(
+            Some(config.clone()),
+            config.dpi.unwrap_or_else(|| ::window::default_dpi()) as usize,
+        )?);
+        fonts.replace((config.generation(), Rc::clone(&font_config)));
+        Ok(font_config)
+    })
+}
+
+/// Measures formatted text in the same way as the tab bar renders it
+fn format_width(items: Vec<FormatItem>) -> anyhow::Result<TextExtent> {
+    let config = config::configuration();
+    let font_config = measure_fonts(&config)?;
+    let escapes = format_as_escapes(items)?;
+    let line = crate::tabbar::parse_status_text(&escapes, CellAttributes::default());
+    measure_line(&config, &font_config, &line, config.use_fancy_tab_bar)
+}
+
 pub fn register(lua: &Lua) -> anyhow::Result<()> {
+    let wezterm_mod = get_or_create_module(lua, "wezterm")?;
+    wezterm_mod.set(
+        "font_resolver",
+        lua.create_function(
+            |_, (text, attrs): (String, Option<FontResolverAttributes>)| {
+                font_resolver(text, attrs).map_err(luaerr)
+            },
+        )?,
+    )?;
+
+    wezterm_mod.set(
+        "format_width",
+        lua.create_function(|_, items: Vec<FormatItem>| {
+            format_width(items)
+                .map(|extent| extent.cells)
+                .map_err(luaerr)
+        })?,
+    )?;
+
     let window_mod = get_or_create_sub_module(lua, "gui")?;
 
     window_mod.set(
@@ -28,5 +154,50 @@ pub fn register(lua: &Lua) -> anyhow::Result<()> {
         })?,
     )?;
 
+    window_mod.set(
+        "get_macro",
+        lua.create_function(|_, name: String| Ok(crate::macros::get_macro(&name)))?,
+    )?;
+    window_mod.set(
+        "set_macro",
+        lua.create_function(|_, (name, events): (String, Option<Vec<MacroEvent>>)| {
+            crate::macros::set_macro(&name, events);
+            Ok(())
+        })?,
+    )?;
+    window_mod.set(
+        "list_macros",
+        lua.create_function(|_, _: ()| Ok(crate::macros::macro_names()))?,
+    )?;
+    window_mod.set(
+        "get_register",
+        lua.create_function(|_, name: String| Ok(crate::registers::get_register(&name)))?,
+    )?;
+    window_mod.set(
+        "set_register",
+        lua.create_function(|_, (name, text): (String, Option<String>)| {
+            match text {
+                Some(text) => crate::registers::set_register(&name, &text),
+                None => crate::registers::clear_register(&name),
+            }
+            Ok(())
+        })?,
+    )?;
+    window_mod.set(
+        "get_registers",
+        lua.create_function(|_, _: ()| {
+            Ok(crate::registers::registers()
+                .into_iter()
+                .collect::<HashMap<String, String>>())
+        })?,
+    )?;
+    window_mod.set(
+        "prewarm_glyphs",
+        lua.create_function(|_, charset: String| {
+            crate::termwindow::prewarm::set_prewarm_charset(&charset);
+            Ok(())
+        })?,
+    )?;
+
     Ok(())
 }
@@ -1,10 +1,31 @@
 //! PaneObject represents a Mux Pane instance in lua code
 use super::luaerr;
+use crate::frontend::try_front_end;
+use crate::selection::{SelectionCoordinate, SelectionMode};
+use crate::termwindow::scrollbarmarks::ScrollbarMark;
+use crate::termwindow::TermWindowNotif;
+use crate::TermWindow;
 use anyhow::anyhow;
+use luahelper::{dynamic_to_lua_value, impl_lua_conversion_dynamic};
 use mlua::{UserData, UserDataMethods};
-use mux::pane::{Pane, PaneId};
+use mux::export::{export_scrollback, ExportFormat};
+use mux::outputrate::{set_output_rate, OutputRate};
+use mux::pane::{Pane, PaneId, WorkingDirAccuracy};
+use mux::resourceusage::get_resource_usage;
 use mux::Mux;
 use std::rc::Rc;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+use wezterm_term::StableRowIndex;
+
+/// The range of lines passed to `pane:export_scrollback`
+#[derive(FromDynamic, ToDynamic, Default)]
+struct ExportRange {
+    #[dynamic(default)]
+    start_line: Option<isize>,
+    #[dynamic(default)]
+    end_line: Option<isize>,
+}
+impl_lua_conversion_dynamic!(ExportRange);
 
 #[derive(Clone)]
 pub struct PaneObject {
@@ -26,6 +47,28 @@ impl PaneObject {
             .ok_or_else(|| anyhow!("pane id {} is not valid", self.pane))
             .map_err(luaerr)
     }
+
+    /// Runs `func` against the gui window that contains this pane
+    fn notify_term_window<F>(&self, func: F) -> mlua::Result<()>
+    where
+        F: FnOnce(&mut TermWindow) + Send + Sync + 'static,
+    {
+        let mux = Mux::get()
+            .ok_or_else(|| anyhow!("must be called on main thread"))
+            .map_err(luaerr)?;
+        let (_domain_id, mux_window_id, _tab_id) = mux
+            .resolve_pane_id(self.pane)
+            .ok_or_else(|| anyhow!("pane {} is not in a window", self.pane))
+            .map_err(luaerr)?;
+        let gui_win = try_front_end()
+            .and_then(|fe| fe.gui_window_for_mux_window(mux_window_id))
+            .ok_or_else(|| anyhow!("pane {} is not in a gui window", self.pane))
+            .map_err(luaerr)?;
+        gui_win
+            .window
+            .notify(TermWindowNotif::Apply(Box::new(func)));
+        Ok(())
+    }
 }
 
 impl UserData for PaneObject {
@@ -48,12 +91,24 @@ impl UserData for PaneObject {
                 .get_current_working_dir()
                 .map(|u| u.to_string()))
         });
+        methods.add_method("get_current_working_dir_accuracy", |_, this, _: ()| {
+            Ok(this
+                .pane()?
+                .get_current_working_dir_accuracy()
+                .map(|accuracy| match accuracy {
+                    WorkingDirAccuracy::Reported => "Reported",
+                    WorkingDirAccuracy::Inferred => "Inferred",
+                }))
+        });
         methods.add_method("get_foreground_process_name", |_, this, _: ()| {
             Ok(this.pane()?.get_foreground_process_name())
         });
         methods.add_method("get_foreground_process_info", |_, this, _: ()| {
             Ok(this.pane()?.get_foreground_process_info())
         });
+        methods.add_method("get_resource_usage", |_, this, _: ()| {
+            Ok(get_resource_usage(this.pane()?.pane_id()))
+        });
         methods.add_method("paste", |_, this, text: String| {
             this.pane()?.send_paste(&text).map_err(luaerr)?;
             Ok(())
@@ -64,15 +119,120 @@ impl UserData for PaneObject {
         methods.add_method("get_dimensions", |_, this, _: ()| {
             Ok(this.pane()?.get_dimensions())
         });
+        methods.add_method("get_terminal_state", |lua, this, _: ()| {
+            match this.pane()?.get_terminal_state() {
+                Some(state) => dynamic_to_lua_value(lua, state.to_dynamic()),
+                None => Ok(mlua::Value::Nil),
+            }
+        });
+        methods.add_method("set_font_scale", |_, this, font_scale: Option<f64>| {
+            if let Some(font_scale) = font_scale {
+                if !font_scale.is_finite() || font_scale <= 0.0 {
+                    return Err(mlua::Error::external(format!(
+                        "font_scale must be a positive number, got {font_scale}"
+                    )));
+                }
+            }
+            let pane_id = this.pane()?.pane_id();
+            this.notify_term_window(move |term_window| {
+                if let Some(window) = term_window.window.clone() {
+                    term_window.set_pane_font_scale(pane_id, font_scale, &window);
+                }
+            })
+        });
+        methods.add_method("set_background_blur", |_, this, radius: Option<u16>| {
+            let pane_id = this.pane()?.pane_id();
+            this.notify_term_window(move |term_window| {
+                term_window.set_pane_background_blur(pane_id, radius);
+            })
+        });
+        methods.add_method(
+            "set_selection_word_boundary",
+            |_, this, word_boundary: Option<String>| {
+                let pane_id = this.pane()?.pane_id();
+                this.notify_term_window(move |term_window| {
+                    term_window.set_pane_selection_word_boundary(pane_id, word_boundary);
+                })
+            },
+        );
+        methods.add_method(
+            "set_output_rate",
+            |_, this, (bytes_per_second, adaptive): (Option<u32>, Option<bool>)| {
+                let pane_id = this.pane()?.pane_id();
+                let rate = bytes_per_second.map(|bytes_per_second| OutputRate {
+                    bytes_per_second,
+                    adaptive: adaptive
+                        .unwrap_or_else(|| config::configuration().adaptive_output_ratelimit),
+                });
+                set_output_rate(pane_id, rate).map_err(luaerr)
+            },
+        );
+        methods.add_method(
+            "set_scrollbar_marks",
+            |_, this, (source, marks): (String, Option<Vec<ScrollbarMark>>)| {
+                let pane_id = this.pane()?.pane_id();
+                this.notify_term_window(move |term_window| {
+                    term_window.set_scrollbar_marks(pane_id, source, marks.unwrap_or_default());
+                })
+            },
+        );
+        methods.add_method(
+            "select_region",
+            |_,
+             this,
+             (start_x, start_y, end_x, end_y, mode): (
+                usize,
+                StableRowIndex,
+                usize,
+                StableRowIndex,
+                Option<SelectionMode>,
+            )| {
+                let pane_id = this.pane()?.pane_id();
+                let mode = mode.unwrap_or(SelectionMode::Cell);
+                this.notify_term_window(move |term_window| {
+                    let pane = match Mux::get().and_then(|mux| mux.get_pane(pane_id)) {
+                        Some(pane) => pane,
+                        None => return,
+                    };
+                    term_window.select_region(
+                        &pane,
+                        SelectionCoordinate::x_y(start_x, start_y),
+                        SelectionCoordinate::x_y(end_x, end_y),
+                        mode,
+                    );
+                })
+            },
+        );
+        methods.add_method("clear_selection", |_, this, _: ()| {
+            let pane_id = this.pane()?.pane_id();
+            this.notify_term_window(move |term_window| {
+                if let Some(pane) = Mux::get().and_then(|mux| mux.get_pane(pane_id)) {
+                    term_window.clear_selection(&pane);
+                }
+            })
+        });
+        methods.add_method("get_palette", |_, this, _: ()| {
+            Ok(config::Palette::from(this.pane()?.palette()))
+        });
         methods.add_method("get_user_vars", |_, this, _: ()| {
             Ok(this.pane()?.copy_user_vars())
         });
         methods.add_method("has_unseen_output", |_, this, _: ()| {
             Ok(this.pane()?.has_unseen_output())
         });
+        methods.add_method("unseen_bell_count", |_, this, _: ()| {
+            Ok(this.pane()?.unseen_bell_count())
+        });
         methods.add_method("is_alt_screen_active", |_, this, _: ()| {
             Ok(this.pane()?.is_alt_screen_active())
         });
+        methods.add_method("set_read_only", |_, this, read_only: bool| {
+            this.pane()?.set_read_only(read_only);
+            Ok(())
+        });
+        methods.add_method("is_read_only", |_, this, _: ()| {
+            Ok(this.pane()?.is_read_only())
+        });
 
         // When called with no arguments, returns the lines from the
         // viewport as plain text (no escape sequences).
@@ -124,6 +284,16 @@ impl UserData for PaneObject {
             },
         );
 
+        methods.add_method(
+            "export_scrollback",
+            |_, this, (format, range): (String, Option<ExportRange>)| {
+                let pane = this.pane()?;
+                let format: ExportFormat = format.parse().map_err(luaerr)?;
+                let range = range.unwrap_or_default();
+                export_scrollback(&*pane, format, range.start_line, range.end_line).map_err(luaerr)
+            },
+        );
+
         methods.add_method("get_domain_name", |_, this, _: ()| {
             let pane = this.pane()?;
             let mut name = None;
@@ -1,7 +1,7 @@
 // The range_plus_one lint can't see when the LHS is not compatible with
 // and inclusive range
 #![cfg_attr(feature = "cargo-clippy", allow(clippy::range_plus_one))]
-use mux::pane::Pane;
+use mux::pane::{LogicalLine, Pane};
 use std::cmp::Ordering;
 use std::ops::Range;
 use termwiz::surface::line::DoubleClickRange;
@@ -164,14 +164,185 @@ pub struct SelectionRange {
     pub end: SelectionCoordinate,
 }
 
-fn is_double_click_word(s: &str) -> bool {
+/// Returns true if the cell text `s` is part of a word whose
+/// boundaries are the characters in `word_boundary`
+fn is_word_char(s: &str, word_boundary: &str) -> bool {
     match s.chars().count() {
-        1 => !config::configuration().selection_word_boundary.contains(s),
+        1 => !word_boundary.contains(s),
         0 => false,
         _ => true,
     }
 }
 
+fn is_blank(s: &str) -> bool {
+    s.chars().all(char::is_whitespace)
+}
+
+/// Characters that delimit a path or URL, in addition to whitespace
+const TOKEN_DELIMITERS: &str = "\"'`()[]{}<>";
+/// Characters that are dropped from the end of a path or URL, as they
+/// are more likely to be punctuation around it
+const TOKEN_TRAILING_PUNCTUATION: &str = ".,;:!?";
+const QUOTES: &[&str] = &["\"", "'", "`"];
+const BRACKETS: &[(&str, &str)] = &[("(", ")"), ("[", "]"), ("{", "}")];
+
+/// Expands `idx` to the run of cells around it that satisfy `pred`
+fn expand_while(cells: &[&str], idx: usize, pred: impl Fn(&str) -> bool) -> Range<usize> {
+    let mut start = idx;
+    while start > 0 && pred(cells[start - 1]) {
+        start -= 1;
+    }
+    let mut end = idx + 1;
+    while end < cells.len() && pred(cells[end]) {
+        end += 1;
+    }
+    start..end
+}
+
+/// Returns the range of the innermost pair of `quote` around `idx`,
+/// including the quotes.  Quotes pair up from the start of the line.
+fn quoted_around(cells: &[&str], idx: usize, quote: &str) -> Option<Range<usize>> {
+    let positions: Vec<usize> = (0..cells.len()).filter(|&i| cells[i] == quote).collect();
+    positions
+        .chunks_exact(2)
+        .map(|pair| pair[0]..pair[1] + 1)
+        .find(|range| range.contains(&idx))
+}
+
+/// Returns the range of the balanced pair of brackets that immediately
+/// encloses `inside`, including the brackets
+fn bracketed_around(
+    cells: &[&str],
+    inside: Range<usize>,
+    (open, close): (&str, &str),
+) -> Option<Range<usize>> {
+    let mut depth = 0;
+    let mut start = None;
+    for i in (0..inside.start).rev() {
+        if cells[i] == close {
+            depth += 1;
+        } else if cells[i] == open {
+            if depth == 0 {
+                start = Some(i);
+                break;
+            }
+            depth -= 1;
+        }
+    }
+    let start = start?;
+
+    let mut depth = 0;
+    for (i, &cell) in cells.iter().enumerate().skip(inside.end) {
+        if cell == open {
+            depth += 1;
+        } else if cell == close {
+            if depth == 0 {
+                return Some(start..i + 1);
+            }
+            depth -= 1;
+        }
+    }
+    None
+}
+
+/// Returns the ranges of all of the pairs of brackets around `idx`,
+/// from the innermost outwards.  A click on a bracket is considered to
+/// be inside of the pair that it delimits.
+fn all_bracketed_around(cells: &[&str], idx: usize, pair: (&str, &str)) -> Vec<Range<usize>> {
+    let mut inside = if cells[idx] == pair.0 {
+        idx + 1..idx + 1
+    } else if cells[idx] == pair.1 {
+        idx..idx
+    } else {
+        idx..idx + 1
+    };
+    let mut ranges = vec![];
+    while let Some(range) = bracketed_around(cells, inside, pair) {
+        inside = range.clone();
+        ranges.push(range);
+    }
+    ranges
+}
+
+/// Computes the semantic units of the line of `cells` that contain the
+/// cell at `idx`, from the smallest to the largest: the word, the path
+/// or URL, the contents of quotes or brackets and the quotes or brackets
+/// themselves, and the line.
+fn smart_ranges(cells: &[&str], idx: usize, word_boundary: &str) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    if idx >= cells.len() {
+        return ranges;
+    }
+
+    if is_word_char(cells[idx], word_boundary) {
+        ranges.push(expand_while(cells, idx, |s| is_word_char(s, word_boundary)));
+    } else {
+        ranges.push(idx..idx + 1);
+    }
+
+    let is_token = |s: &str| !is_blank(s) && !TOKEN_DELIMITERS.contains(s);
+    if is_token(cells[idx]) {
+        let mut token = expand_while(cells, idx, is_token);
+        while token.end > idx + 1 && TOKEN_TRAILING_PUNCTUATION.contains(cells[token.end - 1]) {
+            token.end -= 1;
+        }
+        ranges.push(token);
+    }
+
+    let enclosing = QUOTES
+        .iter()
+        .filter_map(|quote| quoted_around(cells, idx, quote))
+        .chain(
+            BRACKETS
+                .iter()
+                .flat_map(|&pair| all_bracketed_around(cells, idx, pair)),
+        );
+    for outer in enclosing {
+        let inner = outer.start + 1..outer.end - 1;
+        if inner.contains(&idx) {
+            ranges.push(inner);
+        }
+        ranges.push(outer);
+    }
+
+    let mut line_end = cells.len();
+    while line_end > idx + 1 && is_blank(cells[line_end - 1]) {
+        line_end -= 1;
+    }
+    ranges.push(0..line_end);
+
+    ranges.sort_by_key(|r| (r.len(), r.start));
+    ranges.dedup();
+    ranges
+}
+
+/// Picks the smallest of `ranges` that is larger than `current` and
+/// contains it, or the smallest of them if there is no current range.
+/// Once the largest has been reached, it remains selected.
+fn next_smart_range(
+    ranges: &[Range<usize>],
+    current: Option<Range<usize>>,
+) -> Option<Range<usize>> {
+    match current {
+        Some(current) => ranges
+            .iter()
+            .find(|r| r.start <= current.start && r.end >= current.end && **r != current)
+            .or_else(|| ranges.last())
+            .cloned(),
+        None => ranges.first().cloned(),
+    }
+}
+
+/// The visible cells of a logical line, as pairs of the cell index and
+/// the text and width of the cell
+fn logical_cells(logical: &LogicalLine) -> Vec<(usize, &str, usize)> {
+    logical
+        .logical
+        .visible_cells()
+        .map(|(idx, cell)| (idx, cell.str(), cell.width()))
+        .collect()
+}
+
 impl SelectionRange {
     /// Create a new range that starts at the specified location
     pub fn start(start: SelectionCoordinate) -> Self {
@@ -236,8 +407,9 @@ impl SelectionRange {
         }
     }
 
-    /// Computes the selection range for the word around the specified coords
-    pub fn word_around(start: SelectionCoordinate, pane: &dyn Pane) -> Self {
+    /// Computes the selection range for the word around the specified coords.
+    /// Words are delimited by the characters in `word_boundary`.
+    pub fn word_around(start: SelectionCoordinate, pane: &dyn Pane, word_boundary: &str) -> Self {
         for logical in pane.get_logical_lines(start.y..start.y + 1) {
             if !logical.contains_y(start.y) {
                 continue;
@@ -247,7 +419,7 @@ impl SelectionRange {
                 let start_idx = logical.xy_to_logical_x(start_x, start.y);
                 return match logical
                     .logical
-                    .compute_double_click_range(start_idx, is_double_click_word)
+                    .compute_double_click_range(start_idx, |s| is_word_char(s, word_boundary))
                 {
                     DoubleClickRange::RangeWithWrap(click_range)
                     | DoubleClickRange::Range(click_range) => {
@@ -268,6 +440,69 @@ impl SelectionRange {
         Self { start, end: start }
     }
 
+    /// Computes the selection range for smart selection at the specified
+    /// coords.  If `current` is a range within the same logical line that
+    /// contains the coords, it is expanded to the next larger semantic
+    /// unit around it; otherwise the word around the coords is selected.
+    pub fn smart_around(
+        start: SelectionCoordinate,
+        current: Option<Self>,
+        pane: &dyn Pane,
+        word_boundary: &str,
+    ) -> Self {
+        let start_x = match start.x {
+            SelectionX::Cell(x) => x,
+            SelectionX::BeforeZero => 0,
+        };
+        for logical in pane.get_logical_lines(start.y..start.y + 1) {
+            if !logical.contains_y(start.y) {
+                continue;
+            }
+
+            let cells = logical_cells(&logical);
+            let element_at = |logical_x: usize| {
+                cells
+                    .iter()
+                    .position(|&(idx, _, width)| logical_x < idx + width.max(1))
+            };
+            let idx = match element_at(logical.xy_to_logical_x(start_x, start.y)) {
+                Some(idx) => idx,
+                None => return Self { start, end: start },
+            };
+
+            let current = current.map(|r| r.normalize()).and_then(|r| {
+                if !logical.contains_y(r.start.y) || !logical.contains_y(r.end.y) {
+                    return None;
+                }
+                let x_of = |x: SelectionX| match x {
+                    SelectionX::Cell(x) => x,
+                    SelectionX::BeforeZero => 0,
+                };
+                let first = element_at(logical.xy_to_logical_x(x_of(r.start.x), r.start.y))?;
+                let last = element_at(logical.xy_to_logical_x(x_of(r.end.x), r.end.y))
+                    .unwrap_or(cells.len() - 1);
+                Some(first..last + 1).filter(|range| range.contains(&idx))
+            });
+
+            let texts: Vec<&str> = cells.iter().map(|&(_, s, _)| s).collect();
+            let ranges = smart_ranges(&texts, idx, word_boundary);
+            if let Some(range) = next_smart_range(&ranges, current) {
+                let (first_idx, _, _) = cells[range.start];
+                let (last_idx, _, last_width) = cells[range.end - 1];
+                let (start_y, start_x) = logical.logical_x_to_physical_coord(first_idx);
+                let (end_y, end_x) =
+                    logical.logical_x_to_physical_coord(last_idx + last_width.max(1) - 1);
+                return Self {
+                    start: SelectionCoordinate::x_y(start_x, start_y),
+                    end: SelectionCoordinate::x_y(end_x, end_y),
+                };
+            }
+        }
+
+        // Shouldn't happen, but return a reasonable fallback
+        Self { start, end: start }
+    }
+
     /// Extends the current selection by unioning it with another selection range
     pub fn extend_with(&self, other: Self) -> Self {
         let norm = self.normalize();
@@ -355,3 +590,82 @@ impl SelectionRange {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cells(s: &str) -> Vec<&str> {
+        s.split("").filter(|c| !c.is_empty()).collect()
+    }
+
+    fn texts(line: &str, idx: usize) -> Vec<String> {
+        let cells = cells(line);
+        smart_ranges(&cells, idx, " \t\n{[}]()\"'`/")
+            .into_iter()
+            .map(|r| cells[r].concat())
+            .collect()
+    }
+
+    #[test]
+    fn word_boundary() {
+        assert!(is_word_char("a", " "));
+        assert!(!is_word_char("/", "/"));
+        assert!(is_word_char("e\u{301}", "e"));
+        assert!(!is_word_char("", ""));
+    }
+
+    #[test]
+    fn smart_path() {
+        assert_eq!(
+            texts("see /usr/local/bin. ok", 10),
+            vec!["local", "/usr/local/bin", "see /usr/local/bin. ok"]
+        );
+    }
+
+    #[test]
+    fn smart_url_in_parens() {
+        let line = "docs (https://example.com/a?b=1) here";
+        let idx = line.find("example").unwrap();
+        assert_eq!(
+            texts(line, idx),
+            vec![
+                "example.com",
+                "https://example.com/a?b=1",
+                "(https://example.com/a?b=1)",
+                line
+            ]
+        );
+    }
+
+    #[test]
+    fn smart_quotes_and_brackets() {
+        let line = r#"call(f("a b", [x]), 'y')"#;
+        let idx = line.find('b').unwrap();
+        assert_eq!(
+            texts(line, idx),
+            vec![
+                "b",
+                "a b",
+                "\"a b\"",
+                "\"a b\", [x]",
+                "(\"a b\", [x])",
+                "f(\"a b\", [x]), 'y'",
+                "(f(\"a b\", [x]), 'y')",
+                line
+            ]
+        );
+        // A click on a bracket selects the group that it delimits
+        assert_eq!(texts("a [b] c", 2), vec!["[", "[b]", "a [b] c"]);
+    }
+
+    #[test]
+    fn next_range() {
+        let ranges = vec![2..3, 1..4, 0..8];
+        assert_eq!(next_smart_range(&ranges, None), Some(2..3));
+        assert_eq!(next_smart_range(&ranges, Some(2..3)), Some(1..4));
+        assert_eq!(next_smart_range(&ranges, Some(1..3)), Some(1..4));
+        assert_eq!(next_smart_range(&ranges, Some(0..8)), Some(0..8));
+        assert_eq!(next_smart_range(&[], None), None);
+    }
+}
@@ -0,0 +1,130 @@
+//! Handles the requests that the system makes on behalf of the user,
+//! such as the "New WezTerm Tab Here" item in the Services menu of
+//! the Finder on macOS, or a `wezterm://run` URL opened by a Shortcuts
+//! action, by spawning into the most recently used window.
+use crate::frontend::front_end;
+use crate::termwindow::spawn::SpawnWhere;
+use crate::termwindow::TermWindowNotif;
+use ::window::services::{self, ServiceRequest};
+use ::window::WindowOps;
+use config::keyassignment::{SpawnCommand, SpawnTabDomain};
+use config::{configuration, TermConfig};
+use mux::activity::Activity;
+use mux::Mux;
+use portable_pty::CommandBuilder;
+use std::rc::Rc;
+use std::sync::Arc;
+
+pub fn register() {
+    services::set_callback(Some(Rc::new(|request| {
+        // Defer handling so that we're not running inside
+        // the platform's event handling
+        promise::spawn::spawn(async move {
+            if let Err(err) = handle(request).await {
+                log::error!("services: {:#}", err);
+            }
+        })
+        .detach();
+    })));
+}
+
+async fn handle(request: ServiceRequest) -> anyhow::Result<()> {
+    let (spawn, spawn_where) = match request {
+        ServiceRequest::NewTab { cwd } => (
+            SpawnCommand {
+                cwd,
+                domain: SpawnTabDomain::DefaultDomain,
+                ..Default::default()
+            },
+            SpawnWhere::NewTab,
+        ),
+        ServiceRequest::NewWindow { cwd } => (
+            SpawnCommand {
+                cwd,
+                domain: SpawnTabDomain::DefaultDomain,
+                ..Default::default()
+            },
+            SpawnWhere::NewWindow,
+        ),
+        ServiceRequest::RunCommand { args, cwd } => {
+            if !configuration().macos_allow_url_commands {
+                anyhow::bail!(
+                    "ignoring request to run {:?} because \
+                     macos_allow_url_commands is not enabled",
+                    args
+                );
+            }
+            (
+                SpawnCommand {
+                    args: Some(args),
+                    cwd,
+                    domain: SpawnTabDomain::DefaultDomain,
+                    ..Default::default()
+                },
+                SpawnWhere::NewTab,
+            )
+        }
+    };
+
+    let mux = Mux::get().unwrap();
+    let workspace = mux.active_workspace();
+    // Prefer the window that holds the most recently focused pane
+    let focused_window = mux
+        .active_identity()
+        .and_then(|ident| {
+            mux.iter_clients()
+                .into_iter()
+                .find(|info| info.client_id == ident)
+        })
+        .and_then(|info| info.focused_pane_id)
+        .and_then(|pane_id| mux.resolve_pane_id(pane_id))
+        .map(|(_domain_id, mux_window_id, _tab_id)| mux_window_id);
+    let windows: Vec<_> = front_end()
+        .known_windows()
+        .into_iter()
+        .filter(|(_, mux_window_id)| {
+            mux.get_window(*mux_window_id)
+                .map(|w| w.get_workspace() == workspace)
+                .unwrap_or(false)
+        })
+        .collect();
+    let target = windows
+        .iter()
+        .find(|(_, mux_window_id)| Some(*mux_window_id) == focused_window)
+        .or_else(|| windows.first());
+
+    match target {
+        Some((window, _)) => {
+            window.focus();
+            window.notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                term_window.spawn_command(&spawn, spawn_where);
+            })));
+        }
+        None => {
+            // There are no windows in the active workspace, so
+            // there's nowhere to add a tab; make a new window
+            let activity = Activity::new();
+            let config = configuration();
+            let cmd_builder = spawn
+                .args
+                .map(|args| CommandBuilder::from_argv(args.iter().map(Into::into).collect()));
+            let cwd = spawn
+                .cwd
+                .and_then(|cwd| cwd.to_str().map(|s| s.to_string()));
+            let (_tab, pane, _window_id) = mux
+                .spawn_tab_or_window(
+                    None,
+                    SpawnTabDomain::DefaultDomain,
+                    cmd_builder,
+                    cwd,
+                    config.initial_size(::window::default_dpi() as u32),
+                    None,
+                    workspace,
+                )
+                .await?;
+            pane.set_config(Arc::new(TermConfig::with_config(config)));
+            drop(activity);
+        }
+    }
+    Ok(())
+}
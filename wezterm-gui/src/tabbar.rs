@@ -1,5 +1,5 @@
 use crate::termwindow::{PaneInformation, TabInformation, UIItem, UIItemType};
-use config::{ConfigHandle, TabBarColors};
+use config::{ClipboardPolicy, ConfigHandle, StatusPosition, TabBarColors};
 use mlua::FromLua;
 use termwiz::cell::{unicode_column_width, Cell, CellAttributes};
 use termwiz::color::ColorSpec;
@@ -27,10 +27,21 @@ pub enum TabBarItem {
 pub struct TabEntry {
     pub item: TabBarItem,
     pub title: Line,
+    /// Which part of the status this entry shows, if any
+    pub status: Option<StatusPosition>,
     x: usize,
     width: usize,
 }
 
+/// The status text, with escapes, to show at each position
+/// of the tab bar
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StatusText {
+    pub left: String,
+    pub center: String,
+    pub right: String,
+}
+
 #[derive(Clone, Debug)]
 struct TitleText {
     items: Vec<FormatItem>,
@@ -141,10 +152,42 @@ fn compute_tab_title(
                 " no pane ".to_string()
             };
 
-            TitleText {
-                len: unicode_column_width(&title, None),
-                items: vec![FormatItem::Text(title)],
+            let mut len = unicode_column_width(&title, None);
+            let mut items = vec![FormatItem::Text(title)];
+
+            // The badges may contain escape sequences for styling,
+            // so measure them after parsing
+            let badge = if tab.unseen_bell_count > 0 {
+                format!(
+                    "{}{}",
+                    config.tab_bar_style.unseen_bell_badge, tab.unseen_bell_count
+                )
+            } else if tab.has_unseen_output {
+                config.tab_bar_style.unseen_output_badge.clone()
+            } else {
+                String::new()
+            };
+            if !badge.is_empty() {
+                len += parse_status_text(&badge, CellAttributes::default())
+                    .cells()
+                    .len();
+                items.push(FormatItem::Text(badge));
+            }
+
+            let clipboard_restricted = tab
+                .active_pane
+                .as_ref()
+                .map(|pane| pane.clipboard_policy != ClipboardPolicy::Allow)
+                .unwrap_or(false);
+            let badge = &config.tab_bar_style.clipboard_restricted_badge;
+            if clipboard_restricted && !badge.is_empty() {
+                len += parse_status_text(badge, CellAttributes::default())
+                    .cells()
+                    .len();
+                items.push(FormatItem::Text(badge.clone()));
             }
+
+            TitleText { len, items }
         }
     }
 }
@@ -162,6 +205,7 @@ impl TabBarState {
             items: vec![TabEntry {
                 item: TabBarItem::None,
                 title: Line::from_text(" ", &CellAttributes::blank(), 1, None),
+                status: None,
                 x: 1,
                 width: 1,
             }],
@@ -187,7 +231,7 @@ impl TabBarState {
         pane_info: &[PaneInformation],
         colors: Option<&TabBarColors>,
         config: &ConfigHandle,
-        right_status: &str,
+        status: &StatusText,
     ) -> Self {
         let colors = colors.cloned().unwrap_or_else(TabBarColors::default);
 
@@ -215,10 +259,24 @@ impl TabBarState {
         );
 
         // We ultimately want to produce a line looking like this:
-        // ` | tab1-title x | tab2-title x |  +      . - X `
+        // `left | tab1-title x | tab2-title x |  +    center    right`
         // Where the `+` sign will spawn a new tab (or show a context
-        // menu with tab creation options) and the other three chars
-        // are symbols representing minimize, maximize and close.
+        // menu with tab creation options) and left, center and right
+        // are the status segments at those positions.
+
+        let black_cell = Cell::blank_with_attrs(
+            CellAttributes::default()
+                .set_background(ColorSpec::TrueColor(colors.background))
+                .clone(),
+        );
+
+        // The left status is truncated from the right, so that
+        // at least its start remains visible
+        let mut left_status = parse_status_text(&status.left, black_cell.attrs().clone());
+        while left_status.cells().len() > title_width {
+            left_status.remove_cell(left_status.cells().len() - 1, SEQ_ZERO);
+        }
+        let left_status_width = left_status.cells().len();
 
         let mut active_tab_no = 0;
 
@@ -241,8 +299,9 @@ impl TabBarState {
         let titles_len: usize = tab_titles.iter().map(|s| s.len).sum();
         let number_of_tabs = tab_titles.len();
 
-        let available_cells =
-            title_width.saturating_sub(number_of_tabs.saturating_sub(1) + new_tab.cells().len());
+        let available_cells = title_width.saturating_sub(
+            left_status_width + number_of_tabs.saturating_sub(1) + new_tab.cells().len(),
+        );
         let tab_width_max = if config.use_fancy_tab_bar || available_cells >= titles_len {
             // We can render each title with its full width
             usize::max_value()
@@ -257,6 +316,18 @@ impl TabBarState {
         let mut x = 0;
         let mut items = vec![];
 
+        if left_status_width > 0 {
+            items.push(TabEntry {
+                item: TabBarItem::None,
+                title: left_status.clone(),
+                status: Some(StatusPosition::Left),
+                x,
+                width: left_status_width,
+            });
+            line.append_line(left_status, SEQ_ZERO);
+            x += left_status_width;
+        }
+
         for (tab_idx, tab_title) in tab_titles.iter().enumerate() {
             let tab_title_len = tab_title.len.min(tab_width_max);
             let active = tab_idx == active_tab_no;
@@ -303,6 +374,7 @@ impl TabBarState {
             items.push(TabEntry {
                 item: TabBarItem::Tab { tab_idx, active },
                 title,
+                status: None,
                 x: tab_start_idx,
                 width,
             });
@@ -325,6 +397,7 @@ impl TabBarState {
             items.push(TabEntry {
                 item: TabBarItem::NewTabButton,
                 title: new_tab_button.clone(),
+                status: None,
                 x: button_start,
                 width,
             });
@@ -332,29 +405,65 @@ impl TabBarState {
             x += width;
         }
 
-        let black_cell = Cell::blank_with_attrs(
-            CellAttributes::default()
-                .set_background(ColorSpec::TrueColor(colors.background))
-                .clone(),
-        );
-
         let status_space_available = title_width.saturating_sub(x);
-        let mut status_line = parse_status_text(right_status, black_cell.attrs().clone());
+        let right_title = parse_status_text(&status.right, black_cell.attrs().clone());
+        let mut right_status = right_title.clone();
+        while right_status.cells().len() > status_space_available {
+            right_status.remove_cell(0, SEQ_ZERO);
+        }
+        let right_status_width = right_status.cells().len();
+
+        // The center status is centered in the window where there
+        // is room for it, otherwise it is pushed away from the tabs
+        // or the right status, and truncated from the right
+        let center_space_available = status_space_available - right_status_width;
+        let center_title = parse_status_text(&status.center, black_cell.attrs().clone());
+        let mut center_status = center_title.clone();
+        while center_status.cells().len() > center_space_available {
+            center_status.remove_cell(center_status.cells().len() - 1, SEQ_ZERO);
+        }
+        let center_status_width = center_status.cells().len();
+        let center_x = (title_width.saturating_sub(center_status_width) / 2)
+            .min(x + center_space_available - center_status_width)
+            .max(x);
+
         items.push(TabEntry {
             item: TabBarItem::None,
-            title: status_line.clone(),
+            title: center_title,
+            status: Some(StatusPosition::Center),
             x,
-            width: status_space_available,
+            width: center_space_available,
         });
-
-        while status_line.cells().len() > status_space_available {
-            status_line.remove_cell(0, SEQ_ZERO);
+        items.push(TabEntry {
+            item: TabBarItem::None,
+            title: right_title,
+            status: Some(StatusPosition::Right),
+            x: x + center_space_available,
+            width: right_status_width,
+        });
+        if config.use_fancy_tab_bar {
+            // The fancy tab bar shows the subsequent lines of the
+            // right status in additional rows when it is tall enough
+            for row in status.right.lines().skip(1) {
+                items.push(TabEntry {
+                    item: TabBarItem::None,
+                    title: parse_status_text(row, black_cell.attrs().clone()),
+                    status: Some(StatusPosition::Right),
+                    x: x + center_space_available,
+                    width: right_status_width,
+                });
+            }
         }
 
-        line.append_line(status_line, SEQ_ZERO);
-        while line.cells().len() < title_width {
+        while line.cells().len() < center_x {
             line.insert_cell(x, black_cell.clone(), title_width, SEQ_ZERO);
         }
+        line.append_line(center_status, SEQ_ZERO);
+        let center_end = line.cells().len();
+        line.append_line(right_status, SEQ_ZERO);
+        while line.cells().len() < title_width {
+            line.insert_cell(center_end, black_cell.clone(), title_width, SEQ_ZERO);
+        }
 
         Self { line, items }
     }
@@ -0,0 +1,99 @@
+//! Ringing the bell according to the configured bell rules
+use ::window::{Connection, ConnectionOps, WindowOps};
+use config::{find_bell_rule_actions, AudibleBell, BellAction};
+use mux::pane::{Pane, PaneId};
+use mux::Mux;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+impl super::TermWindow {
+    /// Returns the actions that apply to the bell in the pane.
+    /// When no bell rule matches, this is derived from the
+    /// audible_bell and visual_bell configuration.
+    fn bell_actions_for_pane(&self, pane: &Rc<dyn Pane>) -> Vec<BellAction> {
+        if !self.config.bell_rules.is_empty() {
+            let mux = Mux::get().expect("called on main thread");
+            let domain = mux
+                .get_domain(pane.domain_id())
+                .map(|domain| domain.domain_name().to_string())
+                .unwrap_or_default();
+            let program = pane.get_foreground_process_name();
+            if let Some(actions) =
+                find_bell_rule_actions(&self.config.bell_rules, &domain, program.as_deref())
+            {
+                return actions.to_vec();
+            }
+        }
+
+        let mut actions = vec![BellAction::VisualBell(
+            self.config.visual_bell.target.clone(),
+        )];
+        if let AudibleBell::SystemBeep = self.config.audible_bell {
+            actions.push(BellAction::SystemBeep);
+        }
+        actions
+    }
+
+    pub fn ring_bell(&mut self, pane_id: PaneId) {
+        log::trace!("Ding! (this is the bell) in pane {}", pane_id);
+        self.emit_window_event("bell", Some(pane_id));
+
+        let pane = match Mux::get().and_then(|mux| mux.get_pane(pane_id)) {
+            Some(pane) => pane,
+            None => return,
+        };
+
+        {
+            let mut per_pane = self.pane_state(pane_id);
+            if per_pane.bell_muted {
+                return;
+            }
+            let now = Instant::now();
+            let limit = Duration::from_millis(self.config.bell_rate_limit_ms);
+            if let Some(last) = per_pane.last_bell {
+                if now.duration_since(last) < limit {
+                    return;
+                }
+            }
+            per_pane.last_bell.replace(now);
+        }
+
+        for action in self.bell_actions_for_pane(&pane) {
+            match action {
+                BellAction::SystemBeep => {
+                    Connection::get().expect("on main thread").beep();
+                }
+                BellAction::VisualBell(target) => {
+                    let mut per_pane = self.pane_state(pane_id);
+                    per_pane.bell_start.replace(Instant::now());
+                    per_pane.bell_target.replace(target);
+                }
+                BellAction::PlaySound(path) => {
+                    crate::audio::play_sound_file(&path);
+                }
+                BellAction::Toast => {
+                    wezterm_toast_notification::persistent_toast_notification(
+                        "Bell",
+                        &pane.get_title(),
+                    );
+                }
+                BellAction::EmitEvent(name) => {
+                    self.emit_window_event(&name, Some(pane_id));
+                }
+            }
+        }
+
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+
+    /// Silences or restores the bell in the pane
+    pub fn toggle_bell_mute(&mut self, pane: &Rc<dyn Pane>) {
+        let mut per_pane = self.pane_state(pane.pane_id());
+        per_pane.bell_muted = !per_pane.bell_muted;
+        if per_pane.bell_muted {
+            per_pane.bell_start.take();
+        }
+    }
+}
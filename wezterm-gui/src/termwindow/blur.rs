@@ -0,0 +1,203 @@
+//! Blurs the window background layers behind panes, so that the text
+//! in a pane remains legible over a busy background image.
+//! The background layers are rendered into a texture, which is then
+//! blurred in two passes of a separable gaussian blur: the horizontal
+//! pass is rendered into a second texture, and the vertical pass is
+//! drawn directly into the frame, clipped to the region of each pane.
+use crate::renderstate::BackdropBlur;
+use crate::termwindow::TermWindow;
+use ::window::glium::uniforms::{
+    MagnifySamplerFilter, MinifySamplerFilter, Sampler, SamplerWrapFunction,
+};
+use ::window::glium::{self, uniform, Surface};
+use ::window::{RectF, WindowOps};
+use mux::pane::PaneId;
+use mux::tab::PositionedPane;
+use std::rc::Rc;
+
+/// The largest radius that is honored, as the cost of the blur
+/// grows with the radius
+const MAX_RADIUS: u16 = 64;
+
+pub struct BlurRegion {
+    rect: RectF,
+    radius: u16,
+}
+
+/// Converts `rect` to the coordinate system used by glium, where the
+/// origin is the bottom left, clipping it to the frame
+fn scissor_rect(rect: &RectF, frame_width: u32, frame_height: u32) -> Option<glium::Rect> {
+    let left = rect.min_x().max(0.) as u32;
+    let top = rect.min_y().max(0.) as u32;
+    let right = (rect.max_x().max(0.) as u32).min(frame_width);
+    let bottom = (rect.max_y().max(0.) as u32).min(frame_height);
+    if left >= right || top >= bottom {
+        return None;
+    }
+    Some(glium::Rect {
+        left,
+        bottom: frame_height - bottom,
+        width: right - left,
+        height: bottom - top,
+    })
+}
+
+impl TermWindow {
+    /// Sets (or clears, when None) the blur radius for a pane
+    pub fn set_pane_background_blur(&mut self, pane_id: PaneId, radius: Option<u16>) {
+        self.pane_state(pane_id).background_blur = radius;
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+
+    /// Returns the blur radius for the pane, taking into account the
+    /// radius set via pane:set_background_blur
+    fn pane_background_blur(&self, pane_id: PaneId) -> u16 {
+        self.pane_state(pane_id)
+            .background_blur
+            .unwrap_or(self.config.pane_background_blur)
+            .min(MAX_RADIUS)
+    }
+
+    /// Records the region of the pane to be blurred, if it has a blur
+    /// radius and there is a window background to blur
+    pub fn add_blur_region(&self, pos: &PositionedPane, padding_left: f32, top_pixel_y: f32) {
+        if self.window_background.is_empty() {
+            return;
+        }
+        let radius = self.pane_background_blur(pos.pane.pane_id());
+        if radius == 0 {
+            return;
+        }
+        let cell_width = self.render_metrics.cell_size.width as f32;
+        let cell_height = self.render_metrics.cell_size.height as f32;
+        self.blur_regions.borrow_mut().push(BlurRegion {
+            rect: euclid::rect(
+                padding_left + pos.left as f32 * cell_width,
+                top_pixel_y + pos.top as f32 * cell_height,
+                pos.width as f32 * cell_width,
+                pos.height as f32 * cell_height,
+            ),
+            radius,
+        });
+    }
+
+    /// Renders the window background layers into the first of the
+    /// blur textures
+    pub fn render_backdrop_for_blur(
+        &self,
+        is_backdrop: impl Fn(i8) -> bool,
+    ) -> anyhow::Result<Rc<BackdropBlur>> {
+        let gl_state = self.render_state.as_ref().unwrap();
+        let blur = gl_state.backdrop_blur()?;
+        let textures = blur.textures(
+            &gl_state.context,
+            self.dimensions.pixel_width as u32,
+            self.dimensions.pixel_height as u32,
+        )?;
+        let backdrop = textures[0].as_ref().expect("allocated by textures()");
+        let mut target = glium::framebuffer::SimpleFrameBuffer::new(&gl_state.context, backdrop)?;
+        target.clear_color(0., 0., 0., 0.);
+        self.draw_layers(&mut target, is_backdrop, false)?;
+        Ok(Rc::clone(&blur))
+    }
+
+    /// Draws the blurred background into the blur regions of `frame`
+    pub fn draw_backdrop_blur<S: Surface>(
+        &self,
+        frame: &mut S,
+        blur: &BackdropBlur,
+    ) -> anyhow::Result<()> {
+        let gl_state = self.render_state.as_ref().unwrap();
+        let width = self.dimensions.pixel_width as u32;
+        let height = self.dimensions.pixel_height as u32;
+        let textures = blur.textures(&gl_state.context, width, height)?;
+        let backdrop = textures[0].as_ref().expect("allocated by textures()");
+        let horizontal = textures[1].as_ref().expect("allocated by textures()");
+        let sampler = |texture| {
+            Sampler::new(texture)
+                .wrap_function(SamplerWrapFunction::Clamp)
+                .magnify_filter(MagnifySamplerFilter::Linear)
+                .minify_filter(MinifySamplerFilter::Linear)
+        };
+
+        let regions = self.blur_regions.borrow();
+        let mut radii: Vec<u16> = regions.iter().map(|region| region.radius).collect();
+        radii.sort_unstable();
+        radii.dedup();
+
+        for radius in radii {
+            {
+                let mut target =
+                    glium::framebuffer::SimpleFrameBuffer::new(&gl_state.context, horizontal)?;
+                target.draw(
+                    &blur.vertices,
+                    glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip),
+                    &blur.prog,
+                    &uniform! {
+                        source: sampler(backdrop),
+                        direction: [1. / width as f32, 0.],
+                        radius: radius as f32,
+                    },
+                    &Default::default(),
+                )?;
+            }
+
+            for region in regions.iter().filter(|region| region.radius == radius) {
+                let scissor = match scissor_rect(&region.rect, width, height) {
+                    Some(scissor) => scissor,
+                    None => continue,
+                };
+                // Without blending, the blurred background replaces
+                // the background that was drawn into the region
+                frame.draw(
+                    &blur.vertices,
+                    glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip),
+                    &blur.prog,
+                    &uniform! {
+                        source: sampler(horizontal),
+                        direction: [0., 1. / height as f32],
+                        radius: radius as f32,
+                    },
+                    &glium::DrawParameters {
+                        scissor: Some(scissor),
+                        ..Default::default()
+                    },
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scissor() {
+        let rect = |x, y, w, h| euclid::rect(x, y, w, h);
+        assert_eq!(
+            scissor_rect(&rect(10., 20., 100., 50.), 200, 100),
+            Some(glium::Rect {
+                left: 10,
+                bottom: 30,
+                width: 100,
+                height: 50,
+            })
+        );
+        // Clipped to the frame
+        assert_eq!(
+            scissor_rect(&rect(-10., 80., 300., 50.), 200, 100),
+            Some(glium::Rect {
+                left: 0,
+                bottom: 0,
+                width: 200,
+                height: 20,
+            })
+        );
+        assert_eq!(scissor_rect(&rect(250., 0., 10., 10.), 200, 100), None);
+    }
+}
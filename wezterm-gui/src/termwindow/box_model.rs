@@ -13,7 +13,7 @@ use anyhow::anyhow;
 use config::{Dimension, DimensionContext};
 use std::cell::RefCell;
 use std::rc::Rc;
-use termwiz::cell::{grapheme_column_width, Presentation};
+use termwiz::cell::{grapheme_column_width, Presentation, Underline};
 use termwiz::surface::Line;
 use unicode_segmentation::UnicodeSegmentation;
 use wezterm_font::units::PixelUnit;
@@ -240,6 +240,9 @@ pub struct Element {
     pub max_width: Option<Dimension>,
     pub min_width: Option<Dimension>,
     pub min_height: Option<Dimension>,
+    pub underline: Underline,
+    /// The color of the underline; if None, the text color is used
+    pub underline_color: Option<LinearRgba>,
 }
 
 impl Element {
@@ -263,6 +266,8 @@ impl Element {
             max_width: None,
             min_width: None,
             min_height: None,
+            underline: Underline::None,
+            underline_color: None,
         }
     }
 
@@ -270,8 +275,8 @@ impl Element {
         let mut content = vec![];
 
         for cluster in line.cluster(None) {
-            let child =
-                Element::new(font, ElementContent::Text(cluster.text)).colors(ElementColors {
+            let child = Element::new(font, ElementContent::Text(cluster.text))
+                .colors(ElementColors {
                     border: BorderColor::default(),
                     bg: if cluster.attrs.background() == ColorAttribute::Default {
                         InheritableColor::Inherited
@@ -289,7 +294,14 @@ impl Element {
                             .to_linear()
                             .into()
                     },
-                });
+                })
+                .underline(
+                    cluster.attrs.underline(),
+                    match cluster.attrs.underline_color() {
+                        ColorAttribute::Default => None,
+                        col => Some(palette.resolve_fg(col).to_linear()),
+                    },
+                );
 
             content.push(child);
         }
@@ -371,6 +383,12 @@ impl Element {
         self.min_height = height;
         self
     }
+
+    pub fn underline(mut self, underline: Underline, color: Option<LinearRgba>) -> Self {
+        self.underline = underline;
+        self.underline_color = color;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -406,10 +424,17 @@ pub struct ComputedElement {
     /// The outer bounds of the content
     pub content_rect: RectF,
     pub baseline: f32,
+    pub underline: Option<ComputedUnderline>,
 
     pub content: ComputedElementContent,
 }
 
+#[derive(Debug, Clone)]
+pub struct ComputedUnderline {
+    pub sprite: Sprite<SrgbTexture2d>,
+    pub color: Option<LinearRgba>,
+}
+
 impl ComputedElement {
     pub fn translate(&mut self, delta: euclid::Vector2D<f32, PixelUnit>) {
         self.bounds = self.bounds.translate(delta);
@@ -578,6 +603,20 @@ impl super::TermWindow {
                 )?;
                 let mut computed_cells = vec![];
                 let mut glyph_cache = context.gl_state.glyph_cache.borrow_mut();
+                let underline = if element.underline == Underline::None {
+                    None
+                } else {
+                    Some(ComputedUnderline {
+                        sprite: glyph_cache.cached_line_sprite(
+                            false,
+                            false,
+                            element.underline,
+                            false,
+                            context.metrics,
+                        )?,
+                        color: element.underline_color,
+                    })
+                };
                 let mut pixel_width = 0.0;
                 let mut min_y = 0.0f32;
 
@@ -640,15 +679,23 @@ impl super::TermWindow {
                     border_rect: rects.border_rect,
                     padding: rects.padding,
                     content_rect: rects.content_rect,
+                    underline,
                     content: ComputedElementContent::Text(computed_cells),
                 })
             }
             ElementContent::Children(kids) => {
                 let mut pixel_width: f32 = 0.;
-                let mut pixel_height: f32 = 0.;
                 let mut computed_kids = vec![];
                 let mut max_x: f32 = 0.;
                 let mut float_width: f32 = 0.;
+                // Block children occupy rows of their own; the top and
+                // height of each row, and the row of each child
+                let mut row_tops = vec![0.];
+                let mut row_heights = vec![];
+                let mut row_height: f32 = 0.;
+                let mut row_is_empty = true;
+                let mut row_is_block = false;
+                let mut kid_rows = vec![];
 
                 let max_width = match element.max_width {
                     Some(w) => w
@@ -659,6 +706,17 @@ impl super::TermWindow {
                 .min(context.width.pixel_max);
 
                 for child in kids {
+                    let is_block = child.display == DisplayType::Block;
+                    if !row_is_empty && (is_block || row_is_block) {
+                        let row_top = row_tops[row_tops.len() - 1] + row_height;
+                        row_tops.push(row_top);
+                        row_heights.push(row_height);
+                        row_height = 0.;
+                        pixel_width = 0.;
+                    }
+                    row_is_empty = false;
+                    row_is_block = is_block;
+
                     let kid = self.compute_element(
                         &LayoutContext {
                             bounds: match child.float {
@@ -696,10 +754,13 @@ impl super::TermWindow {
                             max_x = max_x.max(pixel_width);
                         }
                     }
-                    pixel_height = pixel_height.max(kid.bounds.height());
+                    row_height = row_height.max(kid.bounds.height());
 
+                    kid_rows.push(row_tops.len() - 1);
                     computed_kids.push(kid);
                 }
+                row_heights.push(row_height);
+                let pixel_height = row_tops[row_tops.len() - 1] + row_height;
 
                 // Respect min-width
                 max_x = max_x.max(min_width);
@@ -707,8 +768,16 @@ impl super::TermWindow {
                 let mut float_max_x = (max_x + float_width).min(max_width);
 
                 let pixel_height = pixel_height.max(min_height);
+                if row_heights.len() == 1 {
+                    // A single row is aligned within the whole element
+                    row_heights[0] = pixel_height;
+                }
 
-                for (kid, child) in computed_kids.iter_mut().zip(kids.iter()) {
+                for ((kid, child), row) in computed_kids
+                    .iter_mut()
+                    .zip(kids.iter())
+                    .zip(kid_rows.into_iter())
+                {
                     match child.float {
                         Float::Right => {
                             max_x = max_x.max(float_max_x);
@@ -718,17 +787,24 @@ impl super::TermWindow {
                         }
                         _ => {}
                     }
+                    let row_top = row_tops[row];
+                    let row_height = row_heights[row];
                     match child.vertical_align {
                         VerticalAlign::Bottom => {
-                            kid.translate(euclid::vec2(0., pixel_height - kid.bounds.height()));
+                            kid.translate(euclid::vec2(
+                                0.,
+                                row_top + row_height - kid.bounds.height(),
+                            ));
                         }
                         VerticalAlign::Middle => {
                             kid.translate(euclid::vec2(
                                 0.,
-                                (pixel_height - kid.bounds.height()) / 2.0,
+                                row_top + (row_height - kid.bounds.height()) / 2.0,
                             ));
                         }
-                        VerticalAlign::Top => {}
+                        VerticalAlign::Top => {
+                            kid.translate(euclid::vec2(0., row_top));
+                        }
                     }
                 }
 
@@ -753,6 +829,7 @@ impl super::TermWindow {
                     border_rect: rects.border_rect,
                     padding: rects.padding,
                     content_rect: rects.content_rect,
+                    underline: None,
                     content: ComputedElementContent::Children(computed_kids),
                 })
             }
@@ -773,6 +850,7 @@ impl super::TermWindow {
                     border_rect: rects.border_rect,
                     padding: rects.padding,
                     content_rect: rects.content_rect,
+                    underline: None,
                     content: ComputedElementContent::Poly {
                         poly,
                         line_width: *line_width,
@@ -885,6 +963,33 @@ impl super::TermWindow {
                         }
                     }
                 }
+
+                if let Some(underline) = &element.underline {
+                    // Draw one per cell, otherwise curly underlines
+                    // stretch across the whole span
+                    let width = underline.sprite.coords.width() as f32;
+                    let height = underline.sprite.coords.height() as f32;
+                    let pos_y = top + element.content_rect.min_y();
+                    let end_x = pos_x.min(element.content_rect.max_x());
+                    let mut x = element.content_rect.min_x();
+                    while x < end_x {
+                        let mut quad = layers[0].allocate()?;
+                        quad.set_position(
+                            x + left,
+                            pos_y,
+                            x + left + width.min(end_x - x),
+                            pos_y + height,
+                        );
+                        match underline.color {
+                            Some(color) => ResolvedColor::from(color).apply(&mut quad),
+                            None => self.resolve_text(colors, inherited_colors).apply(&mut quad),
+                        }
+                        quad.set_texture(underline.sprite.texture_coords());
+                        quad.set_has_color(false);
+                        quad.set_hsv(None);
+                        x += width;
+                    }
+                }
             }
             ComputedElementContent::Children(kids) => {
                 drop(layers);
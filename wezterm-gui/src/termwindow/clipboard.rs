@@ -1,12 +1,147 @@
+use crate::overlay::{confirm_assign_clipboard, confirm_read_clipboard, start_overlay_pane};
+use crate::scripting::guiwin::GuiWin;
+use crate::scripting::pane::PaneObject;
 use crate::termwindow::TermWindowNotif;
 use crate::TermWindow;
 use config::keyassignment::{ClipboardCopyDestination, ClipboardPasteSource};
-use mux::pane::Pane;
+use config::{configuration, ClipboardPolicy};
+use mux::pane::{Pane, PaneId};
 use mux::Mux;
+use std::io::Write;
 use std::rc::Rc;
+use termwiz::escape::osc::Selection;
+use termwiz::escape::OperatingSystemCommand;
+use wezterm_term::ClipboardSelection;
 use window::{Clipboard, WindowOps};
 
+/// Returns the clipboard_policy of the domain that contains the pane
+pub fn clipboard_policy_for_pane(pane: &Rc<dyn Pane>) -> ClipboardPolicy {
+    match Mux::get().and_then(|mux| mux.get_domain(pane.domain_id())) {
+        Some(domain) => configuration().clipboard_policy_for_domain(domain.domain_name()),
+        None => ClipboardPolicy::Allow,
+    }
+}
+
+/// Returns the policy that applies when a program in the pane
+/// reads the clipboard
+pub fn clipboard_read_policy_for_pane(pane: &Rc<dyn Pane>) -> ClipboardPolicy {
+    match Mux::get().and_then(|mux| mux.get_domain(pane.domain_id())) {
+        Some(domain) => configuration().clipboard_read_policy_for_domain(domain.domain_name()),
+        None => ClipboardPolicy::Deny,
+    }
+}
+
+/// Answers an OSC 52 query from a program in the pane with `text`
+fn answer_clipboard_query(pane: &Rc<dyn Pane>, clipboard: Clipboard, text: String) {
+    let selection = match clipboard {
+        Clipboard::Clipboard => Selection::CLIPBOARD,
+        Clipboard::PrimarySelection => Selection::PRIMARY,
+    };
+    let response = OperatingSystemCommand::SetSelection(selection, text).to_string();
+    if let Err(err) = pane.writer().write_all(response.as_bytes()) {
+        log::error!("failed to answer clipboard query: {:#}", err);
+    }
+}
+
+/// Reads the clipboard and uses it to answer an OSC 52 query
+/// from a program in the pane
+pub fn answer_clipboard_query_from_clipboard(
+    window: &::window::Window,
+    pane_id: PaneId,
+    clipboard: Clipboard,
+) {
+    let future = window.get_clipboard(clipboard);
+    promise::spawn::spawn(async move {
+        match future.await {
+            Ok(text) => {
+                if let Some(pane) = Mux::get().and_then(|mux| mux.get_pane(pane_id)) {
+                    answer_clipboard_query(&pane, clipboard, text);
+                }
+            }
+            Err(err) => log::error!("failed to read clipboard for pane {}: {:#}", pane_id, err),
+        }
+    })
+    .detach();
+}
+
 impl TermWindow {
+    /// Asks the user whether a program in a pane whose domain has
+    /// the `Prompt` clipboard_policy may set the clipboard to `text`
+    pub fn confirm_assign_clipboard(
+        &mut self,
+        pane_id: PaneId,
+        clipboard: Clipboard,
+        text: String,
+    ) {
+        let mux = Mux::get().unwrap();
+        let pane = match mux.get_pane(pane_id) {
+            Some(pane) => pane,
+            None => return,
+        };
+        let domain_name = match mux.get_domain(pane.domain_id()) {
+            Some(domain) => domain.domain_name().to_string(),
+            None => return,
+        };
+        let window = self.window.clone().unwrap();
+        let (overlay, future) = start_overlay_pane(self, &pane, move |pane_id, term| {
+            confirm_assign_clipboard(pane_id, term, window, domain_name, clipboard, text)
+        });
+        self.assign_overlay_for_pane(pane_id, overlay);
+        promise::spawn::spawn(future).detach();
+    }
+
+    /// Called when a program in the pane asks for the contents of the
+    /// clipboard using OSC 52.  The query is answered according to the
+    /// clipboard_policy of the domain that contains the pane.
+    pub fn clipboard_query(&mut self, pane_id: PaneId, selection: ClipboardSelection) {
+        let mux = Mux::get().expect("called on main thread");
+        match mux.resolve_pane_id(pane_id) {
+            Some((_domain, window_id, _tab_id)) if window_id == self.mux_window_id => {}
+            _ => return,
+        }
+        let pane = match mux.get_pane(pane_id) {
+            Some(pane) => pane,
+            None => return,
+        };
+        let window = match self.window.clone() {
+            Some(window) => window,
+            None => return,
+        };
+        let clipboard = match selection {
+            ClipboardSelection::Clipboard => Clipboard::Clipboard,
+            ClipboardSelection::PrimarySelection => Clipboard::PrimarySelection,
+        };
+        match clipboard_read_policy_for_pane(&pane) {
+            ClipboardPolicy::Allow => {
+                answer_clipboard_query_from_clipboard(&window, pane_id, clipboard);
+            }
+            ClipboardPolicy::Deny => {
+                log::warn!(
+                    "clipboard_policy denies pane {} reading the clipboard",
+                    pane_id
+                );
+            }
+            ClipboardPolicy::LocalBuffer => {
+                let text = crate::clipboardhistory::entries()
+                    .into_iter()
+                    .next()
+                    .unwrap_or_default();
+                answer_clipboard_query(&pane, clipboard, text);
+            }
+            ClipboardPolicy::Prompt => {
+                let domain_name = match mux.get_domain(pane.domain_id()) {
+                    Some(domain) => domain.domain_name().to_string(),
+                    None => return,
+                };
+                let (overlay, future) = start_overlay_pane(self, &pane, move |pane_id, term| {
+                    confirm_read_clipboard(pane_id, term, window, domain_name, clipboard)
+                });
+                self.assign_overlay_for_pane(pane_id, overlay);
+                promise::spawn::spawn(future).detach();
+            }
+        }
+    }
+
     pub fn copy_to_clipboard(&self, clipboard: ClipboardCopyDestination, text: String) {
         let clipboard = match clipboard {
             ClipboardCopyDestination::Clipboard => [Some(Clipboard::Clipboard), None],
@@ -15,7 +150,12 @@ impl TermWindow {
                 Some(Clipboard::Clipboard),
                 Some(Clipboard::PrimarySelection),
             ],
+            ClipboardCopyDestination::Register(name) => {
+                crate::registers::set_register(&name, &text);
+                return;
+            }
         };
+        crate::clipboardhistory::record_copy(&text);
         for &c in &clipboard {
             if let Some(c) = c {
                 self.window.as_ref().unwrap().set_clipboard(c, text.clone());
@@ -34,6 +174,15 @@ impl TermWindow {
         let clipboard = match clipboard {
             ClipboardPasteSource::Clipboard => Clipboard::Clipboard,
             ClipboardPasteSource::PrimarySelection => Clipboard::PrimarySelection,
+            ClipboardPasteSource::Register(name) => {
+                if let Some(text) = crate::registers::get_register(&name) {
+                    if let Some(text) = mux_lua::apply_pre_paste_hook(pane_id, text) {
+                        pane.trickle_paste(text).ok();
+                    }
+                }
+                self.maybe_scroll_to_bottom_for_input(&pane);
+                return;
+            }
         };
         let future = window.get_clipboard(clipboard);
         promise::spawn::spawn(async move {
@@ -49,7 +198,9 @@ impl TermWindow {
                             mux.get_pane(pane_id)
                         })
                     {
-                        pane.trickle_paste(clip).ok();
+                        if let Some(clip) = mux_lua::apply_pre_paste_hook(pane.pane_id(), clip) {
+                            pane.trickle_paste(clip).ok();
+                        }
                     }
                 })));
             }
@@ -57,4 +208,69 @@ impl TermWindow {
         .detach();
         self.maybe_scroll_to_bottom_for_input(&pane);
     }
+
+    /// Called when the window layer tells us that the content of the
+    /// clipboard or primary selection was changed, either by us or by
+    /// some other application.
+    /// Emits the `clipboard-changed` event.
+    pub fn clipboard_changed(&mut self, clipboard: Clipboard) {
+        let window = match self.window.as_ref() {
+            Some(window) => window.clone(),
+            None => return,
+        };
+        let pane = match self.get_active_pane_or_overlay() {
+            Some(pane) => pane,
+            None => return,
+        };
+        let source = match clipboard {
+            Clipboard::Clipboard => "Clipboard",
+            Clipboard::PrimarySelection => "PrimarySelection",
+        }
+        .to_string();
+        let gui_win = GuiWin::new(self);
+        let pane = PaneObject::new(&pane);
+
+        async fn emit_clipboard_changed(
+            lua: Option<Rc<mlua::Lua>>,
+            window: GuiWin,
+            pane: PaneObject,
+            source: String,
+            content: Option<String>,
+        ) -> anyhow::Result<()> {
+            if let Some(lua) = lua {
+                let args = lua.pack_multi((window, pane, source, content))?;
+                config::lua::emit_event(&lua, ("clipboard-changed".to_string(), args))
+                    .await
+                    .map_err(|e| {
+                        log::error!("while processing clipboard-changed event: {:#}", e);
+                        e
+                    })?;
+            }
+            Ok(())
+        }
+
+        let content = if self.config.clipboard_changed_event_include_content {
+            Some(window.get_clipboard(clipboard))
+        } else {
+            None
+        };
+
+        promise::spawn::spawn(async move {
+            let content = match content {
+                Some(future) => match future.await {
+                    Ok(content) => Some(content),
+                    Err(err) => {
+                        log::error!("clipboard_changed: failed to read {}: {:#}", source, err);
+                        None
+                    }
+                },
+                None => None,
+            };
+            config::with_lua_config_on_main_thread(move |lua| {
+                emit_clipboard_changed(lua, gui_win, pane, source, content)
+            })
+            .await
+        })
+        .detach();
+    }
 }
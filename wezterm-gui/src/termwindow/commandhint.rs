@@ -0,0 +1,238 @@
+//! Offers hints for commands that failed, such as a "command not found"
+//! error from the shell.  When shell integration reports that a command
+//! exited with a non-zero status, the tail of its output is matched
+//! against `command_hint_rules`, and a match is passed to the
+//! `command-hint` event, whose handler may return a suggestion that is
+//! then shown in an overlay.
+use crate::overlay::{command_hint_picker, start_overlay_pane, CommandHint};
+use crate::scripting::guiwin::GuiWin;
+use crate::scripting::pane::PaneObject;
+use crate::termwindow::{TermWindow, TermWindowNotif};
+use config::CommandHintRule;
+use luahelper::impl_lua_conversion_dynamic;
+use mux::history::CommandHistoryQuery;
+use mux::pane::{Pane, PaneId};
+use mux::Mux;
+use regex::Regex;
+use std::rc::Rc;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+use wezterm_term::SemanticType;
+use window::WindowOps;
+
+/// Only the last lines of the output are examined, as that is
+/// where the shell reports the error
+const MAX_OUTPUT_LINES: usize = 50;
+
+/// Describes the failed command; passed to the `command-hint` event
+#[derive(Debug, Clone, FromDynamic, ToDynamic)]
+pub struct CommandHintInfo {
+    /// The name of the foreground process of the pane
+    pub shell: Option<String>,
+    /// The command that was entered at the prompt, if it is in the
+    /// command history
+    pub command: Option<String>,
+    pub exit_status: i32,
+    /// The line of the output that matched
+    pub line: String,
+    /// The capture groups of the regex that matched, if any
+    pub captures: Vec<String>,
+}
+impl_lua_conversion_dynamic!(CommandHintInfo);
+
+/// Returns the file name of `process`, without any extension
+fn shell_name(process: &str) -> Option<String> {
+    std::path::Path::new(process)
+        .file_stem()
+        .and_then(|name| name.to_str())
+        .map(|name| name.to_string())
+}
+
+/// Matches `lines` against the rules that apply to `shell`, the
+/// most recent line first, returning the first match
+fn match_rules(
+    rules: &[CommandHintRule],
+    shell: Option<&str>,
+    lines: &[String],
+) -> Option<(String, Vec<String>)> {
+    for rule in rules {
+        if let Some(rule_shell) = &rule.shell {
+            if Some(rule_shell.as_str()) != shell {
+                continue;
+            }
+        }
+        let re = match Regex::new(&rule.regex) {
+            Ok(re) => re,
+            Err(err) => {
+                log::error!("command_hint_rules: {:#}", err);
+                continue;
+            }
+        };
+        for line in lines.iter().rev() {
+            if let Some(captures) = re.captures(line) {
+                let captures = captures
+                    .iter()
+                    .skip(1)
+                    .map(|c| c.map(|c| c.as_str().to_string()).unwrap_or_default())
+                    .collect();
+                return Some((line.to_string(), captures));
+            }
+        }
+    }
+    None
+}
+
+/// Returns the text of the last lines of the output of the most
+/// recent command in `pane`
+fn last_command_output(pane: &Rc<dyn Pane>) -> Vec<String> {
+    let zone = match pane
+        .get_semantic_zones()
+        .unwrap_or_else(|_| vec![])
+        .into_iter()
+        .filter(|zone| zone.semantic_type == SemanticType::Output)
+        .last()
+    {
+        Some(zone) => zone,
+        None => return vec![],
+    };
+    let start = zone.start_y.max(zone.end_y + 1 - MAX_OUTPUT_LINES as isize);
+    let (_first, lines) = pane.get_lines(start..zone.end_y + 1);
+    lines
+        .iter()
+        .map(|line| line.as_str().trim_end().to_string())
+        .collect()
+}
+
+impl TermWindow {
+    /// Called when shell integration reports that a command finished
+    pub fn command_finished(&mut self, pane_id: PaneId, exit_status: i32) {
+        if !self.config.enable_command_hints || exit_status == 0 {
+            return;
+        }
+        let mux = Mux::get().unwrap();
+        match mux.resolve_pane_id(pane_id) {
+            Some((_domain, window_id, _tab_id)) if window_id == self.mux_window_id => {}
+            _ => return,
+        }
+        let pane = match mux.get_pane(pane_id) {
+            Some(pane) => pane,
+            None => return,
+        };
+
+        let shell = pane
+            .get_foreground_process_name()
+            .and_then(|process| shell_name(&process));
+        let lines = last_command_output(&pane);
+        let (line, captures) =
+            match match_rules(&self.config.command_hint_rules, shell.as_deref(), &lines) {
+                Some(found) => found,
+                None => return,
+            };
+        let command = mux
+            .query_command_history(&CommandHistoryQuery {
+                pane_id: Some(pane_id),
+                search: None,
+                limit: Some(1),
+            })
+            .into_iter()
+            .next()
+            .map(|entry| entry.command);
+        let info = CommandHintInfo {
+            shell,
+            command,
+            exit_status,
+            line,
+            captures,
+        };
+
+        let window = match self.window.clone() {
+            Some(window) => window,
+            None => return,
+        };
+        let gui_win = GuiWin::new(self);
+        let pane = PaneObject::new(&pane);
+
+        async fn emit_command_hint(
+            lua: Option<Rc<mlua::Lua>>,
+            window: GuiWin,
+            pane: PaneObject,
+            info: CommandHintInfo,
+        ) -> anyhow::Result<Option<CommandHint>> {
+            let lua = match lua {
+                Some(lua) => lua,
+                None => return Ok(None),
+            };
+            let args = lua.pack_multi((window, pane, info))?;
+            let value =
+                config::lua::emit_async_callback(&*lua, ("command-hint".to_string(), args)).await?;
+            match value {
+                mlua::Value::Nil => Ok(None),
+                value => Ok(Some(luahelper::from_lua_value_dynamic(value)?)),
+            }
+        }
+
+        promise::spawn::spawn(async move {
+            match config::with_lua_config_on_main_thread(move |lua| {
+                emit_command_hint(lua, gui_win, pane, info)
+            })
+            .await
+            {
+                Ok(Some(hint)) => {
+                    window.notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                        term_window.show_command_hint(pane_id, hint);
+                    })));
+                }
+                Ok(None) => {}
+                Err(err) => log::error!("while processing command-hint event: {:#}", err),
+            }
+        })
+        .detach();
+    }
+
+    fn show_command_hint(&mut self, pane_id: PaneId, hint: CommandHint) {
+        let pane = match Mux::get().and_then(|mux| mux.get_pane(pane_id)) {
+            Some(pane) => pane,
+            None => return,
+        };
+        let window = self.window.clone().unwrap();
+        let (overlay, future) = start_overlay_pane(self, &pane, move |pane_id, term| {
+            command_hint_picker(hint, pane_id, term, window)
+        });
+        self.assign_overlay_for_pane(pane_id, overlay);
+        promise::spawn::spawn(future).detach();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_rules() {
+        let rules = config::default_command_hint_rules();
+        let lines = vec![
+            "$ gti status".to_string(),
+            "bash: gti: command not found".to_string(),
+        ];
+        assert_eq!(
+            match_rules(&rules, Some("bash"), &lines),
+            Some((
+                "bash: gti: command not found".to_string(),
+                vec!["gti".to_string()]
+            ))
+        );
+        assert_eq!(match_rules(&rules, Some("zsh"), &lines), None);
+        assert_eq!(
+            match_rules(
+                &rules,
+                Some("zsh"),
+                &["zsh: command not found: gti".to_string()]
+            ),
+            Some((
+                "zsh: command not found: gti".to_string(),
+                vec!["gti".to_string()]
+            ))
+        );
+        assert_eq!(shell_name("/usr/bin/fish"), Some("fish".to_string()));
+        assert_eq!(shell_name("/usr/bin/pwsh.exe"), Some("pwsh".to_string()));
+    }
+}
@@ -0,0 +1,74 @@
+//! Rounds the corners of the window when `window_corner_radius` is set.
+//! The window system is asked to shape the window where it is able to,
+//! and the frame is drawn with transparent, antialiased corners so that
+//! the result is the same on the backends that can't.
+use crate::termwindow::TermWindow;
+use ::window::glium::{self, uniform, BlendingFunction, LinearBlendingFactor, Surface};
+use ::window::{Window, WindowOps, WindowState};
+use config::DimensionContext;
+
+impl TermWindow {
+    /// Returns the radius, in pixels, of the corners of the window,
+    /// or None if `window_corner_radius` is not set.  The corners are
+    /// square while the window is maximized or full screen, as they
+    /// meet the edges of the screen.
+    pub fn corner_radius(&self) -> Option<f32> {
+        let radius = self.config.window_corner_radius?;
+        if self
+            .window_state
+            .intersects(WindowState::MAXIMIZED | WindowState::FULL_SCREEN)
+        {
+            return Some(0.);
+        }
+        let pixel_max = self
+            .dimensions
+            .pixel_width
+            .min(self.dimensions.pixel_height) as f32;
+        let radius = radius.evaluate_as_pixels(DimensionContext {
+            dpi: self.dimensions.dpi as f32,
+            pixel_max,
+            pixel_cell: self.render_metrics.cell_size.width as f32,
+        });
+        Some(radius.max(0.).min(pixel_max / 2.))
+    }
+
+    /// Informs the window system of the corner radius; this needs
+    /// to be repeated when the size of the window changes, as some
+    /// backends derive the shape of the window from it
+    pub fn update_corner_radius(&self, window: &Window) {
+        window.set_corner_radius(self.corner_radius());
+    }
+
+    /// Makes the area outside of the rounded corners of the frame
+    /// transparent by scaling the color and alpha of the frame by
+    /// the coverage that is computed by the corner mask shader
+    pub fn draw_corner_mask<S: Surface>(&self, frame: &mut S, radius: f32) -> anyhow::Result<()> {
+        let gl_state = self.render_state.as_ref().unwrap();
+        let mask = gl_state.corner_mask()?;
+        let multiply = BlendingFunction::Addition {
+            source: LinearBlendingFactor::Zero,
+            destination: LinearBlendingFactor::SourceAlpha,
+        };
+        frame.draw(
+            &mask.vertices,
+            glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip),
+            &mask.prog,
+            &uniform! {
+                resolution: [
+                    self.dimensions.pixel_width as f32,
+                    self.dimensions.pixel_height as f32
+                ],
+                radius: radius,
+            },
+            &glium::DrawParameters {
+                blend: glium::Blend {
+                    color: multiply,
+                    alpha: multiply,
+                    constant_value: (0.0, 0.0, 0.0, 0.0),
+                },
+                ..Default::default()
+            },
+        )?;
+        Ok(())
+    }
+}
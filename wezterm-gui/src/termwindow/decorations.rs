@@ -0,0 +1,325 @@
+//! Draws the decoration layers that are set from lua via
+//! `window:set_decorations`.  Each layer is a list of simple
+//! primitives positioned relative to the window or to a pane; the
+//! layers are drawn over the panes, beneath the tab bar and any
+//! modal overlays, in the order of their names.
+use crate::termwindow::box_model::*;
+use crate::termwindow::image_preview::decode_thumbnail;
+use crate::termwindow::{DimensionContext, TermWindow};
+use ::window::color::LinearRgba;
+use ::window::RectF;
+use config::{DecorationAnchor, DecorationItem, DecorationLayer, Dimension};
+use mux::tab::PositionedPane;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use termwiz::image::ImageData;
+
+/// The render layer in which the decorations are drawn; above the
+/// panes and their titles, below the tab bar
+const DECORATION_ZINDEX: i8 = 5;
+
+#[derive(Default)]
+pub struct Decorations {
+    layers: BTreeMap<String, DecorationLayer>,
+    /// Decoded icons, keyed by path and by the size in pixels to
+    /// which they were scaled.  None if the file couldn't be decoded.
+    icons: HashMap<(String, u32), Option<Arc<ImageData>>>,
+}
+
+impl Decorations {
+    /// Replaces the layer `name`, or removes it if `layer` is None
+    /// or has no items
+    pub fn set_layer(&mut self, name: String, layer: Option<DecorationLayer>) {
+        match layer {
+            Some(layer) if !layer.items.is_empty() => {
+                self.layers.insert(name, layer);
+            }
+            _ => {
+                self.layers.remove(&name);
+            }
+        }
+
+        // Forget the icons that are no longer used by any layer
+        let layers = &self.layers;
+        self.icons.retain(|(path, _), _| {
+            layers.values().any(|layer| {
+                layer.items.iter().any(|item| match item {
+                    DecorationItem::Icon { path: p, .. } => p == path,
+                    _ => false,
+                })
+            })
+        });
+    }
+
+    fn load_icon(&mut self, path: &str, size: u32) -> Option<Arc<ImageData>> {
+        let key = (path.to_string(), size);
+        if let Some(icon) = self.icons.get(&key) {
+            return icon.clone();
+        }
+        let image = std::fs::read(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|data| decode_thumbnail(&data, size));
+        let icon = match image {
+            Ok(image) => Some(Arc::new(image)),
+            Err(err) => {
+                log::error!("decoration icon {}: {:#}", path, err);
+                None
+            }
+        };
+        self.icons.insert(key, icon.clone());
+        icon
+    }
+}
+
+impl TermWindow {
+    pub fn set_decorations(&mut self, name: String, layer: Option<DecorationLayer>) {
+        self.decorations.set_layer(name, layer);
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+
+    /// Returns the pixel bounds of `anchor`, or None if it isn't visible
+    fn decoration_anchor_rect(
+        &self,
+        anchor: DecorationAnchor,
+        panes: &[PositionedPane],
+    ) -> anyhow::Result<Option<RectF>> {
+        match anchor {
+            DecorationAnchor::Window => Ok(Some(euclid::rect(
+                0.,
+                0.,
+                self.dimensions.pixel_width as f32,
+                self.dimensions.pixel_height as f32,
+            ))),
+            DecorationAnchor::Pane(pane_id) => {
+                let pos = match panes
+                    .iter()
+                    .find(|pos| pos.pane.pane_id() == pane_id && !pos.is_collapsed)
+                {
+                    Some(pos) => pos,
+                    None => return Ok(None),
+                };
+                let (padding_left, padding_top) = self.padding_left_top();
+                let top_bar_height = if self.show_tab_bar && !self.config.tab_bar_at_bottom {
+                    self.tab_bar_pixel_height()?
+                } else {
+                    0.
+                };
+                let border = self.get_os_border();
+                let cell_width = self.render_metrics.cell_size.width as f32;
+                let cell_height = self.render_metrics.cell_size.height as f32;
+                Ok(Some(euclid::rect(
+                    padding_left + pos.left as f32 * cell_width,
+                    top_bar_height
+                        + padding_top
+                        + border.top.get() as f32
+                        + pos.top as f32 * cell_height,
+                    pos.width as f32 * cell_width,
+                    pos.height as f32 * cell_height,
+                )))
+            }
+        }
+    }
+
+    /// Paints the decoration layers over `panes`
+    pub fn paint_decorations(&mut self, panes: &[PositionedPane]) -> anyhow::Result<()> {
+        if self.decorations.layers.is_empty() {
+            return Ok(());
+        }
+        let layers: Vec<DecorationLayer> = self.decorations.layers.values().cloned().collect();
+        let foreground = self.palette().foreground.to_linear();
+        for layer in &layers {
+            if let Some(rect) = self.decoration_anchor_rect(layer.anchor, panes)? {
+                for item in &layer.items {
+                    self.paint_decoration_item(item, rect, foreground)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn paint_decoration_item(
+        &mut self,
+        item: &DecorationItem,
+        anchor: RectF,
+        foreground: LinearRgba,
+    ) -> anyhow::Result<()> {
+        let h_context = DimensionContext {
+            dpi: self.dimensions.dpi as f32,
+            pixel_max: anchor.width(),
+            pixel_cell: self.render_metrics.cell_size.width as f32,
+        };
+        let v_context = DimensionContext {
+            dpi: self.dimensions.dpi as f32,
+            pixel_max: anchor.height(),
+            pixel_cell: self.render_metrics.cell_size.height as f32,
+        };
+        let x = |d: &Dimension| anchor.min_x() + d.evaluate_as_pixels(h_context);
+        let y = |d: &Dimension| anchor.min_y() + d.evaluate_as_pixels(v_context);
+        let left_offset = self.dimensions.pixel_width as f32 / 2.;
+        let top_offset = self.dimensions.pixel_height as f32 / 2.;
+
+        match item {
+            DecorationItem::Rect {
+                x: left,
+                y: top,
+                width,
+                height,
+                color,
+            } => {
+                let gl_state = self.render_state.as_ref().unwrap();
+                let layer = gl_state.layer_for_zindex(DECORATION_ZINDEX)?;
+                let vbs = layer.vb.borrow();
+                let mut vb_mut0 = vbs[0].current_vb_mut();
+                let mut layer0 = vbs[0].map(&mut vb_mut0);
+                self.filled_rectangle(
+                    &mut layer0,
+                    euclid::rect(
+                        x(left),
+                        y(top),
+                        width.evaluate_as_pixels(h_context),
+                        height.evaluate_as_pixels(v_context),
+                    ),
+                    color.to_linear(),
+                )?;
+            }
+            DecorationItem::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                width,
+                color,
+            } => {
+                let (x1, y1, x2, y2) = (
+                    x(x1) - left_offset,
+                    y(y1) - top_offset,
+                    x(x2) - left_offset,
+                    y(y2) - top_offset,
+                );
+                let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
+                if length == 0. {
+                    return Ok(());
+                }
+                // Offset each end perpendicular to the line by half
+                // of its width to form the corners of the quad
+                let half_width = width.evaluate_as_pixels(h_context).max(1.) / 2.;
+                let nx = -(y2 - y1) / length * half_width;
+                let ny = (x2 - x1) / length * half_width;
+
+                let gl_state = self.render_state.as_ref().unwrap();
+                let layer = gl_state.layer_for_zindex(DECORATION_ZINDEX)?;
+                let vbs = layer.vb.borrow();
+                let mut vb_mut0 = vbs[0].current_vb_mut();
+                let mut layer0 = vbs[0].map(&mut vb_mut0);
+                let mut quad = layer0.allocate()?;
+                quad.set_corners(
+                    (x1 + nx, y1 + ny),
+                    (x2 + nx, y2 + ny),
+                    (x1 - nx, y1 - ny),
+                    (x2 - nx, y2 - ny),
+                );
+                quad.set_texture(gl_state.util_sprites.filled_box.texture_coords());
+                quad.set_is_background();
+                quad.set_fg_color(color.to_linear());
+                quad.set_hsv(None);
+            }
+            DecorationItem::Text {
+                x: left,
+                y: top,
+                text,
+                color,
+                background,
+            } => {
+                let font = self.fonts.default_font()?;
+                let colors = ElementColors {
+                    border: BorderColor::default(),
+                    bg: match background {
+                        Some(bg) => bg.to_linear().into(),
+                        None => InheritableColor::Inherited,
+                    },
+                    text: color
+                        .map(|color| color.to_linear())
+                        .unwrap_or(foreground)
+                        .into(),
+                };
+                let element =
+                    Element::new(&font, ElementContent::Text(text.to_string())).colors(colors);
+                let (left, top) = (x(left), y(top));
+                let computed = self.compute_element(
+                    &LayoutContext {
+                        height: DimensionContext {
+                            dpi: self.dimensions.dpi as f32,
+                            pixel_max: self.dimensions.pixel_height as f32,
+                            pixel_cell: self.render_metrics.cell_size.height as f32,
+                        },
+                        width: DimensionContext {
+                            dpi: self.dimensions.dpi as f32,
+                            pixel_max: self.dimensions.pixel_width as f32,
+                            pixel_cell: self.render_metrics.cell_size.width as f32,
+                        },
+                        bounds: euclid::rect(
+                            left,
+                            top,
+                            (self.dimensions.pixel_width as f32 - left).max(0.),
+                            (self.dimensions.pixel_height as f32 - top).max(0.),
+                        ),
+                        metrics: &self.render_metrics,
+                        gl_state: self.render_state.as_ref().unwrap(),
+                        zindex: DECORATION_ZINDEX,
+                    },
+                    &element,
+                )?;
+                let gl_state = self.render_state.as_ref().unwrap();
+                self.render_element(&computed, gl_state, None)?;
+            }
+            DecorationItem::Icon {
+                x: left,
+                y: top,
+                width,
+                height,
+                path,
+            } => {
+                let width = width.evaluate_as_pixels(h_context);
+                let height = height.evaluate_as_pixels(v_context);
+                let (left, top) = (x(left), y(top));
+                let size = width.max(height).max(1.) as u32;
+                let image = match self.decorations.load_icon(path, size) {
+                    Some(image) => image,
+                    None => return Ok(()),
+                };
+
+                let gl_state = self.render_state.as_ref().unwrap();
+                let (sprite, next_due) = gl_state
+                    .glyph_cache
+                    .borrow_mut()
+                    .cached_image(&image, None)?;
+                self.update_next_frame_time(next_due);
+
+                // Scale the image to fit, preserving its aspect ratio
+                let image_width = sprite.coords.size.width as f32;
+                let image_height = sprite.coords.size.height as f32;
+                let scale = (width / image_width).min(height / image_height);
+                let (width, height) = (image_width * scale, image_height * scale);
+
+                let layer = gl_state.layer_for_zindex(DECORATION_ZINDEX)?;
+                let vbs = layer.vb.borrow();
+                let mut vb_mut1 = vbs[1].current_vb_mut();
+                let mut layer1 = vbs[1].map(&mut vb_mut1);
+                let mut quad = layer1.allocate()?;
+                quad.set_position(
+                    left - left_offset,
+                    top - top_offset,
+                    left + width - left_offset,
+                    top + height - top_offset,
+                );
+                quad.set_texture(sprite.texture_coords());
+                quad.set_fg_color(LinearRgba::with_components(1., 1., 1., 1.));
+                quad.set_hsv(None);
+                quad.set_has_color(true);
+            }
+        }
+        Ok(())
+    }
+}
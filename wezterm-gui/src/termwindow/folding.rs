@@ -0,0 +1,302 @@
+//! Folding of the output of previous commands, as identified by
+//! OSC 133 semantic zones, into single summary lines.
+//! The folds are a rendering transform: the pane content is not changed.
+use ::window::WindowOps;
+use mux::pane::Pane;
+use mux::renderable::RenderableDimensions;
+use std::ops::Range;
+use std::rc::Rc;
+use termwiz::cell::{CellAttributes, Intensity};
+use termwiz::surface::{Line, SEQ_ZERO};
+use wezterm_term::{SemanticType, SemanticZone, StableRowIndex};
+
+/// A row that is displayed in the viewport of a pane with folded output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VisibleRow {
+    /// A regular line from the pane
+    Line(StableRowIndex),
+    /// A single summary line standing in for a range of folded lines
+    Fold(Range<StableRowIndex>),
+}
+
+impl VisibleRow {
+    /// Returns the stable row index that the row represents.
+    /// For a fold, this is the first row of the folded range.
+    pub fn stable_row(&self) -> StableRowIndex {
+        match self {
+            Self::Line(row) => *row,
+            Self::Fold(range) => range.start,
+        }
+    }
+}
+
+fn fold_containing(
+    folds: &[Range<StableRowIndex>],
+    row: StableRowIndex,
+) -> Option<&Range<StableRowIndex>> {
+    let idx = folds.partition_point(|fold| fold.end <= row);
+    folds.get(idx).filter(|fold| fold.start <= row)
+}
+
+/// Computes the rows to show in a viewport that is `rows` tall when
+/// each of the `folds` is collapsed to a single row.
+/// `folds` must be sorted and non-overlapping.
+/// `valid` is the range of stable rows that exist in the pane.
+/// If `top` is set the viewport is scrolled back and starts at that
+/// row; otherwise it is anchored to the bottom of the pane.
+pub fn compute_visible_rows(
+    folds: &[Range<StableRowIndex>],
+    valid: Range<StableRowIndex>,
+    top: Option<StableRowIndex>,
+    rows: usize,
+) -> Vec<VisibleRow> {
+    let mut result = vec![];
+
+    if let Some(top) = top {
+        let mut row = top.max(valid.start);
+        while result.len() < rows && row < valid.end {
+            match fold_containing(folds, row) {
+                Some(fold) => {
+                    result.push(VisibleRow::Fold(fold.clone()));
+                    row = fold.end;
+                }
+                None => {
+                    result.push(VisibleRow::Line(row));
+                    row += 1;
+                }
+            }
+        }
+        if result.len() == rows {
+            return result;
+        }
+        // The folds left us short of filling the viewport;
+        // show the bottom of the pane instead.
+        result.clear();
+    }
+
+    let mut row = valid.end - 1;
+    while result.len() < rows && row >= valid.start {
+        match fold_containing(folds, row) {
+            Some(fold) => {
+                result.push(VisibleRow::Fold(fold.clone()));
+                row = fold.start - 1;
+            }
+            None => {
+                result.push(VisibleRow::Line(row));
+                row -= 1;
+            }
+        }
+    }
+    result.reverse();
+    result
+}
+
+/// Produces the line that is rendered in place of a folded range,
+/// summarizing the first line of the output and the number of lines
+pub fn fold_summary_line(fold: &Range<StableRowIndex>, first_line: Option<&Line>) -> Line {
+    let first = first_line
+        .map(|line| line.as_str().trim().to_string())
+        .unwrap_or_default();
+    let count = fold.end - fold.start;
+    let text = format!(
+        "▸ {} … ({} line{} folded)",
+        first,
+        count,
+        if count == 1 { "" } else { "s" }
+    );
+    let mut attrs = CellAttributes::default();
+    attrs.set_intensity(Intensity::Half);
+    Line::from_text(&text, &attrs, SEQ_ZERO, None)
+}
+
+impl super::TermWindow {
+    fn output_zones(pane: &Rc<dyn Pane>) -> Vec<SemanticZone> {
+        pane.get_semantic_zones()
+            .unwrap_or_else(|_| vec![])
+            .into_iter()
+            .filter(|zone| zone.semantic_type == SemanticType::Output)
+            .collect()
+    }
+
+    /// Returns the sorted ranges of rows that are folded in the pane.
+    /// Folds are not applied to the alternate screen or while an
+    /// overlay such as copy mode is active.
+    pub fn folded_output_ranges(&self, pane: &Rc<dyn Pane>) -> Vec<Range<StableRowIndex>> {
+        if self.pane_state(pane.pane_id()).folded_output.is_empty()
+            || pane.is_alt_screen_active()
+            || crate::overlay::overlay_lifecycle(pane).is_some()
+        {
+            return vec![];
+        }
+        let state = self.pane_state(pane.pane_id());
+        let mut ranges: Vec<Range<StableRowIndex>> = Self::output_zones(pane)
+            .into_iter()
+            .filter(|zone| state.folded_output.contains(&zone.start_y))
+            .map(|zone| zone.start_y..zone.end_y + 1)
+            .collect();
+        ranges.sort_by_key(|range| range.start);
+        ranges.dedup_by(|next, prior| next.start < prior.end);
+        ranges
+    }
+
+    /// Returns the rows to render for the pane if it has folded output,
+    /// or None if the rows map directly to the viewport
+    pub fn visible_rows(
+        &self,
+        pane: &Rc<dyn Pane>,
+        dims: &RenderableDimensions,
+    ) -> Option<Vec<VisibleRow>> {
+        let folds = self.folded_output_ranges(pane);
+        if folds.is_empty() {
+            return None;
+        }
+        Some(compute_visible_rows(
+            &folds,
+            dims.scrollback_top..dims.physical_top + dims.viewport_rows as StableRowIndex,
+            self.get_viewport(pane.pane_id()),
+            dims.viewport_rows,
+        ))
+    }
+
+    /// Maps a row in the viewport to the stable row that is displayed there
+    pub fn stable_row_for_viewport_row(
+        &self,
+        pane: &Rc<dyn Pane>,
+        dims: &RenderableDimensions,
+        row: i64,
+    ) -> StableRowIndex {
+        if let Some(rows) = self.visible_rows(pane, dims) {
+            if let Some(visible) = usize::try_from(row).ok().and_then(|row| rows.get(row)) {
+                return visible.stable_row();
+            }
+        }
+        self.get_viewport(pane.pane_id())
+            .unwrap_or(dims.physical_top)
+            + row as StableRowIndex
+    }
+
+    fn set_output_folded(&mut self, pane: &Rc<dyn Pane>, start_y: StableRowIndex, folded: bool) {
+        {
+            let mut state = self.pane_state(pane.pane_id());
+            if folded {
+                state.folded_output.insert(start_y);
+            } else {
+                state.folded_output.remove(&start_y);
+            }
+        }
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+
+    fn toggle_output_zone(&mut self, pane: &Rc<dyn Pane>, zone: Option<&SemanticZone>) {
+        if let Some(zone) = zone {
+            let folded = self
+                .pane_state(pane.pane_id())
+                .folded_output
+                .contains(&zone.start_y);
+            self.set_output_folded(pane, zone.start_y, !folded);
+        }
+    }
+
+    /// Toggles the fold of the most recent command output that starts
+    /// above the bottom of the viewport
+    pub fn toggle_output_fold(&mut self, pane: &Rc<dyn Pane>) {
+        let dims = pane.get_dimensions();
+        let bottom = self
+            .get_viewport(pane.pane_id())
+            .unwrap_or(dims.physical_top)
+            + dims.viewport_rows as StableRowIndex;
+        let zones = Self::output_zones(pane);
+        let zone = zones.iter().rev().find(|zone| zone.start_y < bottom);
+        self.toggle_output_zone(pane, zone);
+    }
+
+    /// Toggles the fold of the command output under the mouse cursor
+    pub fn toggle_output_fold_at_mouse_cursor(&mut self, pane: &Rc<dyn Pane>) {
+        let row = match self.pane_state(pane.pane_id()).mouse_terminal_coords {
+            Some((_, row)) => row,
+            None => return,
+        };
+        let zones = Self::output_zones(pane);
+        let zone = zones
+            .iter()
+            .find(|zone| zone.start_y <= row && row <= zone.end_y);
+        self.toggle_output_zone(pane, zone);
+    }
+
+    /// Folds the output of every command in the pane
+    pub fn fold_all_output(&mut self, pane: &Rc<dyn Pane>) {
+        let zones = Self::output_zones(pane);
+        self.pane_state(pane.pane_id())
+            .folded_output
+            .extend(zones.iter().map(|zone| zone.start_y));
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+
+    /// Expands all of the folded output in the pane
+    pub fn unfold_all_output(&mut self, pane: &Rc<dyn Pane>) {
+        self.pane_state(pane.pane_id()).folded_output.clear();
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use VisibleRow::*;
+
+    #[test]
+    fn no_folds() {
+        assert_eq!(
+            compute_visible_rows(&[], 0..10, None, 3),
+            vec![Line(7), Line(8), Line(9)]
+        );
+        assert_eq!(
+            compute_visible_rows(&[], 0..10, Some(2), 3),
+            vec![Line(2), Line(3), Line(4)]
+        );
+    }
+
+    #[test]
+    fn folds_anchored_to_bottom() {
+        assert_eq!(
+            compute_visible_rows(&[3..8], 0..10, None, 4),
+            vec![Line(2), Fold(3..8), Line(8), Line(9)]
+        );
+        assert_eq!(
+            compute_visible_rows(&[2..4, 5..9], 0..10, None, 4),
+            vec![Fold(2..4), Line(4), Fold(5..9), Line(9)]
+        );
+    }
+
+    #[test]
+    fn folds_scrolled_back() {
+        assert_eq!(
+            compute_visible_rows(&[3..8], 0..20, Some(1), 4),
+            vec![Line(1), Line(2), Fold(3..8), Line(8)]
+        );
+        // A top row inside a fold shows the whole fold
+        assert_eq!(
+            compute_visible_rows(&[3..8], 0..20, Some(5), 3),
+            vec![Fold(3..8), Line(8), Line(9)]
+        );
+        // Not enough rows below the top falls back to the bottom
+        assert_eq!(
+            compute_visible_rows(&[3..8], 0..10, Some(4), 4),
+            vec![Line(2), Fold(3..8), Line(8), Line(9)]
+        );
+    }
+
+    #[test]
+    fn short_scrollback() {
+        assert_eq!(
+            compute_visible_rows(&[1..4], 0..5, None, 10),
+            vec![Line(0), Fold(1..4), Line(4)]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Maps the gestures that are recognized by the window layer to
+//! terminal actions when `enable_touch_gestures` is set: tapping
+//! clicks, scrolling with two fingers scrolls the viewport, pinching
+//! zooms and swiping left or right switches tabs.
+use crate::termwindow::TermWindow;
+use ::window::{
+    Gesture, Modifiers, MouseButtons, MouseEvent, MouseEventKind, MousePress, Point, ScreenPoint,
+    SwipeDirection, Window,
+};
+
+/// How far the font scale must change before the pinch is applied;
+/// applying every small change would rebuild the fonts too often
+const PINCH_THRESHOLD: f64 = 0.05;
+
+impl TermWindow {
+    pub fn gesture_event_impl(&mut self, gesture: Gesture, window: &Window) {
+        log::trace!("{:?}", gesture);
+        if !self.config.enable_touch_gestures {
+            return;
+        }
+        match gesture {
+            Gesture::Tap {
+                coords,
+                screen_coords,
+            } => {
+                for kind in [
+                    MouseEventKind::Press(MousePress::Left),
+                    MouseEventKind::Release(MousePress::Left),
+                ] {
+                    let mouse_buttons = match kind {
+                        MouseEventKind::Press(_) => MouseButtons::LEFT,
+                        _ => MouseButtons::NONE,
+                    };
+                    self.synthesize_mouse_event(kind, mouse_buttons, coords, screen_coords, window);
+                }
+            }
+            Gesture::LongPress { .. } => {
+                if let Some(pane) = self.get_active_pane_or_overlay() {
+                    let action = self.config.touch_long_press_action.clone();
+                    if let Err(err) = self.perform_key_assignment(&pane, &action) {
+                        log::error!("touch_long_press_action: {:#}", err);
+                    }
+                }
+            }
+            Gesture::Swipe(direction) => {
+                // The content follows the finger, so swiping to the
+                // left reveals the tab to the right
+                let delta = match direction {
+                    SwipeDirection::Left => 1,
+                    SwipeDirection::Right => -1,
+                    SwipeDirection::Up | SwipeDirection::Down => return,
+                };
+                self.activate_tab_relative(delta, true).ok();
+            }
+            Gesture::Scroll {
+                coords,
+                screen_coords,
+                delta_y,
+                ..
+            } => {
+                let cell_height = self.render_metrics.cell_size.height as f32;
+                self.touch_scroll_remainder += delta_y;
+                let lines = (self.touch_scroll_remainder / cell_height).trunc();
+                if lines != 0. {
+                    self.touch_scroll_remainder -= lines * cell_height;
+                    // Moving the fingers down reveals the scrollback,
+                    // which is what a positive wheel movement does
+                    self.synthesize_mouse_event(
+                        MouseEventKind::VertWheel(lines as i16),
+                        MouseButtons::NONE,
+                        coords,
+                        screen_coords,
+                        window,
+                    );
+                }
+            }
+            Gesture::Pinch { scale } => {
+                self.touch_pinch_scale *= scale as f64;
+                if (self.touch_pinch_scale - 1.).abs() >= PINCH_THRESHOLD {
+                    let font_scale = self.window_font_scale * self.touch_pinch_scale;
+                    self.touch_pinch_scale = 1.;
+                    self.set_window_font_scale(font_scale, window);
+                }
+            }
+        }
+    }
+
+    fn synthesize_mouse_event(
+        &mut self,
+        kind: MouseEventKind,
+        mouse_buttons: MouseButtons,
+        coords: Point,
+        screen_coords: ScreenPoint,
+        window: &Window,
+    ) {
+        self.mouse_event_impl(
+            MouseEvent {
+                kind,
+                coords,
+                screen_coords,
+                mouse_buttons,
+                modifiers: Modifiers::NONE,
+            },
+            window,
+        );
+    }
+}
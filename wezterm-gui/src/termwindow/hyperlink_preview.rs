@@ -0,0 +1,139 @@
+//! Shows the target of the explicit (OSC 8) hyperlink under the mouse
+//! cursor in the bottom left corner of the window, in the manner of
+//! the status bar of a web browser, so that the destination of a link
+//! can be checked before it is clicked.
+use crate::termwindow::box_model::*;
+use crate::termwindow::render::rgbcolor_to_window_color;
+use crate::termwindow::{DimensionContext, TermWindow};
+use crate::utilsprites::RenderMetrics;
+use config::Dimension;
+use termwiz::cell::unicode_column_width;
+
+/// Returns `uri`, shortened with an ellipsis in the middle if it
+/// is wider than `max_cols` cells
+fn truncate_uri(uri: &str, max_cols: usize) -> String {
+    if unicode_column_width(uri, None) <= max_cols {
+        return uri.to_string();
+    }
+    // Keep as much of the start and end as will fit around the ellipsis
+    fn take_cols(chars: impl Iterator<Item = char>, max_cols: usize) -> Vec<char> {
+        let mut cols = 0;
+        let mut result = vec![];
+        for c in chars {
+            let mut buf = [0u8; 4];
+            cols += unicode_column_width(c.encode_utf8(&mut buf), None);
+            if cols > max_cols {
+                break;
+            }
+            result.push(c);
+        }
+        result
+    }
+    let keep = max_cols.saturating_sub(1);
+    let head: String = take_cols(uri.chars(), keep - keep / 2)
+        .into_iter()
+        .collect();
+    let tail: String = take_cols(uri.chars().rev(), keep / 2)
+        .into_iter()
+        .rev()
+        .collect();
+    format!("{}…{}", head, tail)
+}
+
+impl TermWindow {
+    /// Paints the target of the highlighted hyperlink, if any
+    pub fn paint_hyperlink_preview(&mut self) -> anyhow::Result<()> {
+        if !self.config.hyperlink_hover_preview {
+            return Ok(());
+        }
+        let link = match self.current_highlight.as_ref() {
+            Some(link) if !link.is_implicit() => link.clone(),
+            _ => return Ok(()),
+        };
+
+        let border = self.get_os_border();
+        let bottom_bar_height = if self.show_tab_bar && self.config.tab_bar_at_bottom {
+            self.tab_bar_pixel_height()?
+        } else {
+            0.
+        };
+
+        let mut computed = self.compute_hover_text(link.uri())?;
+        computed.translate(euclid::vec2(
+            0.,
+            self.dimensions.pixel_height as f32
+                - (computed.bounds.height() + bottom_bar_height + border.bottom.get() as f32),
+        ));
+
+        let gl_state = self.render_state.as_ref().unwrap();
+        self.render_element(&computed, gl_state, None)?;
+
+        Ok(())
+    }
+
+    /// Lays out `text` in the style of the hyperlink preview, shortened
+    /// to fit in the window if necessary.  The element is positioned at
+    /// the top left of the window; the caller translates it into place.
+    pub fn compute_hover_text(&self, text: &str) -> anyhow::Result<ComputedElement> {
+        let font = self.fonts.title_font()?;
+        let metrics = RenderMetrics::with_font_metrics(&font.metrics());
+        let border = self.get_os_border();
+
+        let avail_width =
+            self.dimensions.pixel_width as f32 - (border.left + border.right).get() as f32;
+        let max_cols = ((avail_width / metrics.cell_size.width as f32) as usize)
+            .saturating_sub(2)
+            .max(1);
+
+        let colors = ElementColors {
+            border: BorderColor::default(),
+            bg: rgbcolor_to_window_color(self.config.window_frame.active_titlebar_bg).into(),
+            text: rgbcolor_to_window_color(self.config.window_frame.active_titlebar_fg).into(),
+        };
+        let element = Element::new(&font, ElementContent::Text(truncate_uri(text, max_cols)))
+            .colors(colors)
+            .padding(BoxDimension {
+                left: Dimension::Cells(0.5),
+                right: Dimension::Cells(0.5),
+                top: Dimension::Cells(0.25),
+                bottom: Dimension::Cells(0.25),
+            });
+
+        self.compute_element(
+            &LayoutContext {
+                height: DimensionContext {
+                    dpi: self.dimensions.dpi as f32,
+                    pixel_max: self.dimensions.pixel_height as f32,
+                    pixel_cell: metrics.cell_size.height as f32,
+                },
+                width: DimensionContext {
+                    dpi: self.dimensions.dpi as f32,
+                    pixel_max: self.dimensions.pixel_width as f32,
+                    pixel_cell: metrics.cell_size.width as f32,
+                },
+                bounds: euclid::rect(
+                    border.left.get() as f32,
+                    0.,
+                    avail_width,
+                    self.dimensions.pixel_height as f32,
+                ),
+                metrics: &metrics,
+                gl_state: self.render_state.as_ref().unwrap(),
+                zindex: 100,
+            },
+            &element,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn truncate() {
+        assert_eq!(truncate_uri("https://a.b/c", 20), "https://a.b/c");
+        assert_eq!(truncate_uri("https://example.com/path", 9), "http…path");
+        assert_eq!(truncate_uri("file:///tmp/x", 1), "…");
+    }
+}
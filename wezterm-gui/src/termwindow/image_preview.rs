@@ -0,0 +1,267 @@
+//! Shows a thumbnail of the local image file that the hovered
+//! hyperlink, or the active quick select match, refers to, when
+//! `image_preview` is enabled.  The thumbnail is drawn in the bottom
+//! right corner of the window, away from the hyperlink preview.
+//! The file is loaded and decoded on a background thread, and the
+//! thumbnail is kept until nothing refers to an image, so that the
+//! file is read again the next time that it is previewed.
+use crate::overlay::QuickSelectOverlay;
+use crate::termwindow::render::rgbcolor_to_window_color;
+use crate::termwindow::{TermWindow, TermWindowNotif};
+use ::window::color::LinearRgba;
+use ::window::WindowOps;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use termwiz::image::{ImageData, ImageDataType};
+use url::Url;
+
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "bmp", "gif", "ico", "jpeg", "jpg", "png", "tga", "tif", "tiff", "webp",
+];
+
+/// The most recently previewed image file
+pub struct ImagePreview {
+    path: PathBuf,
+    state: ImagePreviewState,
+}
+
+enum ImagePreviewState {
+    Loading,
+    /// The thumbnail, or None if the file couldn't be loaded
+    Loaded(Option<Arc<ImageData>>),
+}
+
+/// Resolves `target`, which is either a URL or a path, to a local
+/// image file.  Relative paths are resolved against `cwd`.
+fn image_path(target: &str, cwd: Option<&Url>) -> Option<PathBuf> {
+    let target = target.trim();
+    let path = if let Ok(url) = Url::parse(target) {
+        if url.scheme() != "file" {
+            return None;
+        }
+        url.to_file_path().ok()?
+    } else if let Some(rest) = target.strip_prefix("~/") {
+        config::HOME_DIR.join(rest)
+    } else {
+        let path = PathBuf::from(target);
+        if path.is_absolute() {
+            path
+        } else {
+            cwd.and_then(|url| url.to_file_path().ok())?.join(path)
+        }
+    };
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    if IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// Decodes the image file `data` and scales it down to fit within
+/// `max_size` pixels in each direction
+pub fn decode_thumbnail(data: &[u8], max_size: u32) -> anyhow::Result<ImageData> {
+    let image = image::load_from_memory(data)?;
+    let max_size = max_size.max(1);
+    let image = if image.width() > max_size || image.height() > max_size {
+        image.thumbnail(max_size, max_size)
+    } else {
+        image
+    }
+    .into_rgba8();
+    let (width, height) = image.dimensions();
+    Ok(ImageData::with_data(ImageDataType::new_single_frame(
+        width,
+        height,
+        image.into_vec(),
+    )))
+}
+
+/// Reads and decodes the image file at `path`.
+/// This is called on a background thread.
+fn load_thumbnail(path: &Path, max_file_size: u64, max_size: u32) -> Option<Arc<ImageData>> {
+    let image = std::fs::metadata(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|meta| {
+            anyhow::ensure!(meta.is_file(), "not a file");
+            anyhow::ensure!(
+                meta.len() <= max_file_size,
+                "larger than image_preview_max_file_size"
+            );
+            Ok(std::fs::read(path)?)
+        })
+        .and_then(|data| decode_thumbnail(&data, max_size));
+    match image {
+        Ok(image) => Some(Arc::new(image)),
+        Err(err) => {
+            log::debug!("image preview of {}: {:#}", path.display(), err);
+            None
+        }
+    }
+}
+
+impl TermWindow {
+    /// Returns the text that may refer to an image: the active quick
+    /// select match if quick select is active, otherwise the target
+    /// of the hovered hyperlink, along with the directory against
+    /// which relative paths are resolved
+    fn image_preview_target(&self) -> Option<(String, Option<Url>)> {
+        let pane = self.get_active_pane_or_overlay()?;
+        if let Some(quick_select) = pane.downcast_ref::<QuickSelectOverlay>() {
+            let text = quick_select.active_match_text()?;
+            return Some((text, quick_select.delegate().get_current_working_dir()));
+        }
+        let link = self.current_highlight.as_ref()?;
+        Some((link.uri().to_string(), pane.get_current_working_dir()))
+    }
+
+    /// Returns the thumbnail of `path`, if it has been loaded.
+    /// Otherwise starts loading it in the background; the window
+    /// is invalidated once it is ready.
+    fn load_image_preview(&mut self, path: &Path) -> Option<Arc<ImageData>> {
+        if let Some(preview) = &self.image_preview {
+            if preview.path == path {
+                return match &preview.state {
+                    ImagePreviewState::Loading => None,
+                    ImagePreviewState::Loaded(image) => image.clone(),
+                };
+            }
+        }
+
+        let window = self.window.clone()?;
+        self.image_preview = Some(ImagePreview {
+            path: path.to_path_buf(),
+            state: ImagePreviewState::Loading,
+        });
+        let path = path.to_path_buf();
+        let max_file_size = self.config.image_preview_max_file_size;
+        let max_size = self.config.image_preview_max_size;
+        promise::spawn::spawn(async move {
+            let image = smol::unblock({
+                let path = path.clone();
+                move || load_thumbnail(&path, max_file_size, max_size)
+            })
+            .await;
+            window.notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                term_window.image_preview_loaded(path, image);
+            })));
+        })
+        .detach();
+        None
+    }
+
+    fn image_preview_loaded(&mut self, path: PathBuf, image: Option<Arc<ImageData>>) {
+        match &mut self.image_preview {
+            // Ignore the result if the preview moved on to another
+            // file while it was loading
+            Some(preview) if preview.path == path => {
+                preview.state = ImagePreviewState::Loaded(image);
+            }
+            _ => return,
+        }
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+
+    /// Paints a thumbnail of the image that the hovered hyperlink or
+    /// the active quick select match refers to, if any
+    pub fn paint_image_preview(&mut self) -> anyhow::Result<()> {
+        if !self.config.image_preview {
+            return Ok(());
+        }
+        let path = match self
+            .image_preview_target()
+            .and_then(|(target, cwd)| image_path(&target, cwd.as_ref()))
+        {
+            Some(path) => path,
+            None => {
+                // Forget the thumbnail, so that changes to the
+                // file are picked up when it is next previewed
+                self.image_preview.take();
+                return Ok(());
+            }
+        };
+        let image = match self.load_image_preview(&path) {
+            Some(image) => image,
+            None => return Ok(()),
+        };
+
+        let gl_state = self.render_state.as_ref().unwrap();
+        let (sprite, next_due) = gl_state
+            .glyph_cache
+            .borrow_mut()
+            .cached_image(&image, None)?;
+        self.update_next_frame_time(next_due);
+        let width = sprite.coords.size.width as f32;
+        let height = sprite.coords.size.height as f32;
+
+        let border = self.get_os_border();
+        let bottom_bar_height = if self.show_tab_bar && self.config.tab_bar_at_bottom {
+            self.tab_bar_pixel_height()?
+        } else {
+            0.
+        };
+        let margin = self.render_metrics.cell_size.width as f32;
+        let frame = 2.;
+        let left = self.dimensions.pixel_width as f32
+            - (border.right.get() as f32 + margin + frame + width);
+        let top = self.dimensions.pixel_height as f32
+            - (bottom_bar_height + border.bottom.get() as f32 + margin + frame + height);
+
+        let layer = gl_state.layer_for_zindex(100)?;
+        let vbs = layer.vb.borrow();
+        let mut vb_mut0 = vbs[0].current_vb_mut();
+        let mut layer0 = vbs[0].map(&mut vb_mut0);
+        let mut vb_mut1 = vbs[1].current_vb_mut();
+        let mut layer1 = vbs[1].map(&mut vb_mut1);
+
+        self.filled_rectangle(
+            &mut layer0,
+            euclid::rect(
+                left - frame,
+                top - frame,
+                width + 2. * frame,
+                height + 2. * frame,
+            ),
+            rgbcolor_to_window_color(self.config.window_frame.active_titlebar_bg),
+        )?;
+
+        let left_offset = self.dimensions.pixel_width as f32 / 2.;
+        let top_offset = self.dimensions.pixel_height as f32 / 2.;
+        let mut quad = layer1.allocate()?;
+        quad.set_position(
+            left - left_offset,
+            top - top_offset,
+            left + width - left_offset,
+            top + height - top_offset,
+        );
+        quad.set_texture(sprite.texture_coords());
+        quad.set_fg_color(LinearRgba::with_components(1., 1., 1., 1.));
+        quad.set_hsv(None);
+        quad.set_has_color(true);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn paths() {
+        let cwd = Url::parse("file:///home/user/").unwrap();
+        assert_eq!(
+            image_path("file:///tmp/cat.PNG", None),
+            Some(PathBuf::from("/tmp/cat.PNG"))
+        );
+        assert_eq!(
+            image_path("pics/cat.jpg", Some(&cwd)),
+            Some(PathBuf::from("/home/user/pics/cat.jpg"))
+        );
+        assert_eq!(image_path("pics/cat.jpg", None), None);
+        assert_eq!(image_path("/tmp/notes.txt", None), None);
+        assert_eq!(image_path("https://example.com/cat.png", None), None);
+    }
+}
@@ -0,0 +1,113 @@
+//! Measures the time between a key press being sent to a pane and the
+//! frame that shows the output of the pane in response, which is
+//! usually the echo of the typed character.
+//! The latency is recorded in the `gui.key_echo.latency` histogram,
+//! which is logged by `periodic_stat_logging`.  When
+//! `low_latency_input` is enabled, the GPU is flushed right after such
+//! a frame is submitted, so that the driver doesn't queue it behind
+//! the frames that follow.
+use crate::termwindow::TermWindow;
+use mux::pane::PaneId;
+use std::time::{Duration, Instant};
+
+/// A key press that isn't echoed within this time is forgotten
+const MAX_ECHO_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Default)]
+pub struct KeyEcho {
+    /// The pane that the earliest key press that has not yet been
+    /// echoed was sent to, and when it was sent
+    pending: Option<(PaneId, Instant)>,
+    /// Whether the pane produced output since then
+    echoed: bool,
+}
+
+impl KeyEcho {
+    pub fn key_sent(&mut self, pane_id: PaneId, now: Instant) {
+        match self.pending {
+            Some((id, sent))
+                if id == pane_id && now.saturating_duration_since(sent) < MAX_ECHO_DELAY => {}
+            _ => {
+                self.pending.replace((pane_id, now));
+                self.echoed = false;
+            }
+        }
+    }
+
+    pub fn pane_output(&mut self, pane_id: PaneId) {
+        if matches!(self.pending, Some((id, _)) if id == pane_id) {
+            self.echoed = true;
+        }
+    }
+
+    /// Returns true if the frame that is being painted shows the echo
+    pub fn is_echo_frame(&self) -> bool {
+        self.pending.is_some() && self.echoed
+    }
+
+    /// Called once a frame has been presented; returns the latency
+    /// of the key press if the frame showed its echo
+    pub fn frame_presented(&mut self, now: Instant) -> Option<Duration> {
+        let (_, sent) = self.pending?;
+        let elapsed = now.saturating_duration_since(sent);
+        if self.echoed {
+            self.pending.take();
+            self.echoed = false;
+            Some(elapsed)
+        } else {
+            if elapsed >= MAX_ECHO_DELAY {
+                self.pending.take();
+            }
+            None
+        }
+    }
+}
+
+impl TermWindow {
+    /// Flushes the GPU if the frame that was just submitted shows the
+    /// echo of a key press and `low_latency_input` is enabled, and
+    /// records the latency of the key press
+    pub fn key_echo_frame_submitted(&mut self) {
+        if self.key_echo.is_echo_frame() && self.config.low_latency_input {
+            if let Some(gl) = self.gl.as_ref() {
+                gl.finish();
+            }
+        }
+        if let Some(latency) = self.key_echo.frame_presented(Instant::now()) {
+            metrics::histogram!("gui.key_echo.latency", latency);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn latency() {
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        let mut echo = KeyEcho::default();
+
+        // The frame painted right after the key press doesn't have
+        // the echo yet
+        echo.key_sent(1, start);
+        assert_eq!(echo.frame_presented(start + ms(1)), None);
+
+        // Output from other panes doesn't count, and a second key
+        // press is measured from the first
+        echo.pane_output(2);
+        echo.key_sent(1, start + ms(5));
+        assert!(!echo.is_echo_frame());
+        echo.pane_output(1);
+        assert!(echo.is_echo_frame());
+        assert_eq!(echo.frame_presented(start + ms(10)), Some(ms(10)));
+        assert_eq!(echo.frame_presented(start + ms(20)), None);
+
+        // A key press that produces no output is forgotten
+        echo.key_sent(1, start);
+        assert_eq!(echo.frame_presented(start + MAX_ECHO_DELAY), None);
+        echo.pane_output(1);
+        assert!(!echo.is_echo_frame());
+    }
+}
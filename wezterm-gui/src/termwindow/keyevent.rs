@@ -1,7 +1,8 @@
 use crate::termwindow::InputMap;
-use ::window::{DeadKeyStatus, KeyCode, KeyEvent, Modifiers, RawKeyEvent, WindowOps};
+use ::window::{ComposeState, DeadKeyStatus, KeyCode, KeyEvent, Modifiers, RawKeyEvent, WindowOps};
 use anyhow::Context;
-use config::keyassignment::KeyTableEntry;
+use config::keyassignment::{KeyAssignment, KeyTableEntry, MacroEvent, MouseEventTrigger};
+use config::{DeferredKeyCode, KeyNoAction};
 use mux::pane::Pane;
 use smol::Timer;
 use std::rc::Rc;
@@ -130,6 +131,23 @@ impl KeyTableState {
         result
     }
 
+    /// Resolves a mouse event against the mouse tables that share
+    /// their names with the active key tables.  Unlike key presses,
+    /// mouse events don't pop one_shot or until_unknown activations.
+    pub fn lookup_mouse(
+        &mut self,
+        input_map: &InputMap,
+        event: &MouseEventTrigger,
+        mods: Modifiers,
+    ) -> Option<KeyAssignment> {
+        while self.process_expiration() {}
+
+        self.stack
+            .iter()
+            .rev()
+            .find_map(|entry| input_map.lookup_mouse(event.clone(), mods, Some(&entry.name)))
+    }
+
     pub fn did_process_key(&mut self) {
         let should_pop = self
             .stack
@@ -348,6 +366,11 @@ impl super::TermWindow {
                             && self.pane_state(pane.pane_id()).overlay.is_none()
                         {
                             self.maybe_scroll_to_bottom_for_input(&pane);
+                            self.key_echo.key_sent(pane.pane_id(), Instant::now());
+                            self.record_macro_event(MacroEvent::Key(KeyNoAction {
+                                key: DeferredKeyCode::KeyCode(keycode.clone()),
+                                mods: raw_modifiers,
+                            }));
                         }
                         context.set_cursor(None);
                         if !keycode.is_modifier() {
@@ -364,6 +387,11 @@ impl super::TermWindow {
     }
 
     pub fn raw_key_event_impl(&mut self, key: RawKeyEvent, context: &dyn WindowOps) {
+        let key = match self.remap_raw_key_event(key) {
+            Some(key) => key,
+            None => return,
+        };
+
         // The leader key is a kind of modal modifier key.
         // It is allowed to be active for up to the leader timeout duration,
         // after which it auto-deactivates.
@@ -493,6 +521,12 @@ impl super::TermWindow {
         &self.dead_key_status
     }
 
+    /// Returns the progress of the `compose_sequences` sequence
+    /// that is being entered, if any
+    pub fn compose_state(&self) -> Option<&ComposeState> {
+        self.compose_state.as_ref()
+    }
+
     fn leader_done(&mut self) {
         self.leader_is_down.take();
         self.update_title();
@@ -502,6 +536,11 @@ impl super::TermWindow {
     }
 
     pub fn key_event_impl(&mut self, window_key: KeyEvent, context: &dyn WindowOps) {
+        let window_key = match self.remap_key_event(window_key) {
+            Some(key) => key,
+            None => return,
+        };
+
         let pane = match self.get_active_pane_or_overlay() {
             Some(pane) => pane,
             None => return,
@@ -603,6 +642,11 @@ impl super::TermWindow {
                         && self.pane_state(pane.pane_id()).overlay.is_none()
                     {
                         self.maybe_scroll_to_bottom_for_input(&pane);
+                        self.key_echo.key_sent(pane.pane_id(), Instant::now());
+                        self.record_macro_event(MacroEvent::Key(KeyNoAction {
+                            key: DeferredKeyCode::KeyCode(window_key.key.clone()),
+                            mods: window_key.modifiers,
+                        }));
                     }
                     context.set_cursor(None);
                     if !key.is_modifier() {
@@ -625,8 +669,35 @@ impl super::TermWindow {
                 if self.config.debug_key_events {
                     log::info!("send to pane string={:?}", s);
                 }
+
+                // Overlays and modals edit their own input rather than
+                // writing to a pty, so feed them the text that the IME
+                // composed one character at a time, as if it were typed
+                use ::termwiz::input::{KeyCode as KC, Modifiers as Mods};
+                if let Some(modal) = self.get_modal() {
+                    for c in s.chars() {
+                        modal.key_down(KC::Char(c), Mods::NONE, self).ok();
+                    }
+                    return;
+                }
+                let is_overlay = self
+                    .get_active_pane_no_overlay()
+                    .map(|active| active.pane_id() != pane.pane_id())
+                    .unwrap_or(false);
+                if is_overlay {
+                    for c in s.chars() {
+                        pane.key_down(KC::Char(c), Mods::NONE).ok();
+                    }
+                    context.invalidate();
+                    return;
+                }
+
                 pane.writer().write_all(s.as_bytes()).ok();
                 self.maybe_scroll_to_bottom_for_input(&pane);
+                self.key_echo.key_sent(pane.pane_id(), Instant::now());
+                if self.pane_state(pane.pane_id()).overlay.is_none() {
+                    self.record_macro_event(MacroEvent::Text(s));
+                }
                 context.invalidate();
             }
             Key::None => {}
@@ -0,0 +1,383 @@
+//! Shows the keys that can be pressed while a key table or the leader
+//! key is active, along with what they do, in the manner of which-key.
+//! The descriptions come from the `description` field of the key
+//! assignments in the config, falling back to the brief description
+//! of the command that the action corresponds to.
+use crate::commands::CommandDef;
+use crate::inputmap::key_label;
+use crate::termwindow::box_model::*;
+use crate::termwindow::render::rgbcolor_to_window_color;
+use crate::termwindow::{DimensionContext, TermWindow};
+use crate::utilsprites::RenderMetrics;
+use config::keyassignment::{KeyAssignment, KeyTable};
+use config::Dimension;
+use termwiz::cell::unicode_column_width;
+use window::Modifiers;
+
+/// Descriptions longer than this are truncated
+const MAX_DESCRIPTION_COLS: usize = 48;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyHint {
+    pub key: String,
+    pub description: String,
+}
+
+/// The hints for the current key table or leader key, which are
+/// kept until a different table becomes active or the config changes
+pub struct KeyHints {
+    title: String,
+    table_name: Option<String>,
+    hints: Vec<KeyHint>,
+}
+
+/// Returns the hints for the entries of `table` whose modifiers
+/// are accepted by `mods_filter`, which returns the modifiers to
+/// show in the label.  The hints are sorted by their labels.
+fn table_hints(
+    table: &KeyTable,
+    mods_filter: impl Fn(Modifiers) -> Option<Modifiers>,
+    describe: impl Fn(&KeyAssignment) -> String,
+) -> Vec<KeyHint> {
+    let mut hints: Vec<KeyHint> = table
+        .iter()
+        .filter_map(|((key, mods), entry)| {
+            let mods = mods_filter(*mods)?;
+            Some(KeyHint {
+                key: key_label(key, mods),
+                description: entry
+                    .description
+                    .clone()
+                    .unwrap_or_else(|| describe(&entry.action)),
+            })
+        })
+        .collect();
+    hints.sort_by(|a, b| a.key.cmp(&b.key));
+    hints.dedup_by(|a, b| a.key == b.key);
+    hints
+}
+
+/// Shortens `text` with an ellipsis if it is wider than `max_cols` cells
+fn truncate(text: &str, max_cols: usize) -> String {
+    if unicode_column_width(text, None) <= max_cols {
+        return text.to_string();
+    }
+    let mut cols = 0;
+    let mut result = String::new();
+    for c in text.chars() {
+        let mut buf = [0u8; 4];
+        cols += unicode_column_width(c.encode_utf8(&mut buf), None);
+        if cols >= max_cols {
+            break;
+        }
+        result.push(c);
+    }
+    result.push('…');
+    result
+}
+
+impl TermWindow {
+    /// Returns the title and hints for the active key table or leader key
+    fn compute_key_hints(&mut self) -> Option<KeyHints> {
+        let leader_active = self.leader_is_active();
+        let table_name = self.current_key_table_name();
+        let title = if leader_active {
+            "LEADER".to_string()
+        } else {
+            table_name.clone()?
+        };
+
+        if let Some(hints) = self.key_hints.borrow().as_ref() {
+            if hints.title == title && hints.table_name == table_name {
+                return Some(KeyHints {
+                    title,
+                    table_name,
+                    hints: hints.hints.clone(),
+                });
+            }
+        }
+
+        let briefs = CommandDef::action_briefs(&self.config);
+        let describe = |action: &KeyAssignment| {
+            let action = format!("{:?}", action);
+            briefs.get(&action).cloned().unwrap_or(action)
+        };
+
+        let mut hints = vec![];
+        if leader_active {
+            // Leader bindings in the active key table take precedence
+            // over those in the default table
+            let leader_only = |mods: Modifiers| {
+                if mods.contains(Modifiers::LEADER) {
+                    Some(mods - Modifiers::LEADER)
+                } else {
+                    None
+                }
+            };
+            if let Some(table) = table_name
+                .as_ref()
+                .and_then(|name| self.input_map.keys.by_name.get(name))
+            {
+                hints = table_hints(table, leader_only, &describe);
+            }
+            for hint in table_hints(&self.input_map.keys.default, leader_only, &describe) {
+                if !hints.iter().any(|h| h.key == hint.key) {
+                    hints.push(hint);
+                }
+            }
+        } else if let Some(table) = table_name
+            .as_ref()
+            .and_then(|name| self.input_map.keys.by_name.get(name))
+        {
+            hints = table_hints(
+                table,
+                |mods| {
+                    if mods.contains(Modifiers::LEADER) {
+                        None
+                    } else {
+                        Some(mods)
+                    }
+                },
+                &describe,
+            );
+        }
+
+        self.key_hints.borrow_mut().replace(KeyHints {
+            title: title.clone(),
+            table_name: table_name.clone(),
+            hints: hints.clone(),
+        });
+        Some(KeyHints {
+            title,
+            table_name,
+            hints,
+        })
+    }
+
+    /// Paints the hints for the active key table or leader key,
+    /// centered at the bottom of the window
+    pub fn paint_key_hints(&mut self) -> anyhow::Result<()> {
+        if !self.config.show_key_hints {
+            return Ok(());
+        }
+        let KeyHints { title, hints, .. } = match self.compute_key_hints() {
+            Some(hints) => hints,
+            None => return Ok(()),
+        };
+        if hints.is_empty() {
+            return Ok(());
+        }
+
+        let font = self.fonts.title_font()?;
+        let metrics = RenderMetrics::with_font_metrics(&font.metrics());
+        let border = self.get_os_border();
+        let bottom_bar_height = if self.show_tab_bar && self.config.tab_bar_at_bottom {
+            self.tab_bar_pixel_height()?
+        } else {
+            0.
+        };
+        let avail_width =
+            self.dimensions.pixel_width as f32 - (border.left + border.right).get() as f32;
+        let avail_height = self.dimensions.pixel_height as f32
+            - (border.top + border.bottom).get() as f32
+            - bottom_bar_height;
+
+        // Leave room for the title, the padding and the line that
+        // says how many hints were left out
+        let max_rows = ((avail_height / metrics.cell_size.height as f32) as usize)
+            .saturating_sub(4)
+            .max(1);
+        let omitted = hints.len().saturating_sub(max_rows);
+        let key_cols = hints
+            .iter()
+            .map(|hint| unicode_column_width(&hint.key, None))
+            .max()
+            .unwrap_or(0);
+
+        let colors = ElementColors {
+            border: BorderColor::default(),
+            bg: rgbcolor_to_window_color(self.config.window_frame.active_titlebar_bg).into(),
+            text: rgbcolor_to_window_color(self.config.window_frame.active_titlebar_fg).into(),
+        };
+        let key_colors = ElementColors {
+            text: rgbcolor_to_window_color(self.config.window_frame.inactive_titlebar_fg).into(),
+            ..colors.clone()
+        };
+
+        // Each line is computed as an element of its own, and the lines
+        // are stacked from the top down.  The lines are first computed
+        // to find the widest, and then again at that width so that their
+        // backgrounds form a single box.
+        let num_hints = hints.len().min(max_rows);
+        let make_lines = |width: Option<Dimension>| -> Vec<Element> {
+            let padding = |top: f32, bottom: f32| BoxDimension {
+                left: Dimension::Cells(1.),
+                right: Dimension::Cells(1.),
+                top: Dimension::Cells(top),
+                bottom: Dimension::Cells(bottom),
+            };
+            let mut lines = vec![Element::new(&font, ElementContent::Text(title.clone()))
+                .colors(colors.clone())
+                .padding(padding(0.5, 0.5))
+                .min_width(width)];
+            for (idx, hint) in hints.iter().take(num_hints).enumerate() {
+                let is_last = idx + 1 == num_hints && omitted == 0;
+                lines.push(
+                    Element::new(
+                        &font,
+                        ElementContent::Children(vec![
+                            Element::new(&font, ElementContent::Text(hint.key.clone()))
+                                .colors(key_colors.clone())
+                                .min_width(Some(Dimension::Cells(key_cols as f32 + 2.))),
+                            Element::new(
+                                &font,
+                                ElementContent::Text(truncate(
+                                    &hint.description,
+                                    MAX_DESCRIPTION_COLS,
+                                )),
+                            )
+                            .colors(colors.clone()),
+                        ]),
+                    )
+                    .colors(colors.clone())
+                    .padding(padding(0., if is_last { 0.5 } else { 0. }))
+                    .min_width(width),
+                );
+            }
+            if omitted > 0 {
+                lines.push(
+                    Element::new(
+                        &font,
+                        ElementContent::Text(format!("…and {} more", omitted)),
+                    )
+                    .colors(key_colors.clone())
+                    .padding(padding(0., 0.5))
+                    .min_width(width),
+                );
+            }
+            lines
+        };
+
+        let gl_state = self.render_state.as_ref().unwrap();
+        let context = LayoutContext {
+            height: DimensionContext {
+                dpi: self.dimensions.dpi as f32,
+                pixel_max: self.dimensions.pixel_height as f32,
+                pixel_cell: metrics.cell_size.height as f32,
+            },
+            width: DimensionContext {
+                dpi: self.dimensions.dpi as f32,
+                pixel_max: self.dimensions.pixel_width as f32,
+                pixel_cell: metrics.cell_size.width as f32,
+            },
+            bounds: euclid::rect(0., 0., avail_width, avail_height),
+            metrics: &metrics,
+            gl_state,
+            zindex: 100,
+        };
+
+        // The widest content, excluding the horizontal padding
+        let padding_width = 2. * metrics.cell_size.width as f32;
+        let mut width: f32 = 0.;
+        for line in make_lines(None) {
+            let line = self.compute_element(&context, &line)?;
+            width = width.max(line.bounds.width() - padding_width);
+        }
+        let mut computed = vec![];
+        for line in make_lines(Some(Dimension::Pixels(width))) {
+            computed.push(self.compute_element(&context, &line)?);
+        }
+        let width = computed
+            .iter()
+            .map(|line| line.bounds.width())
+            .fold(0., f32::max);
+        let height: f32 = computed.iter().map(|line| line.bounds.height()).sum();
+
+        let left = border.left.get() as f32 + ((avail_width - width) / 2.).max(0.);
+        let mut top = self.dimensions.pixel_height as f32
+            - (height + bottom_bar_height + border.bottom.get() as f32);
+        for mut line in computed {
+            line.translate(euclid::vec2(left, top));
+            top += line.bounds.height();
+            self.render_element(&line, gl_state, None)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use config::keyassignment::{KeyTableEntry, SpawnTabDomain};
+    use window::KeyCode;
+
+    #[test]
+    fn labels() {
+        assert_eq!(key_label(&KeyCode::Char('a'), Modifiers::NONE), "a");
+        assert_eq!(
+            key_label(&KeyCode::Char('c'), Modifiers::CTRL | Modifiers::SHIFT),
+            "SHIFT+CTRL+c"
+        );
+        assert_eq!(key_label(&KeyCode::Char(' '), Modifiers::ALT), "ALT+Space");
+    }
+
+    #[test]
+    fn hints() {
+        let mut table = KeyTable::new();
+        table.insert(
+            (KeyCode::Char('t'), Modifiers::LEADER),
+            KeyTableEntry {
+                action: KeyAssignment::SpawnTab(SpawnTabDomain::CurrentPaneDomain),
+                description: Some("New tab".to_string()),
+            },
+        );
+        table.insert(
+            (KeyCode::Char('q'), Modifiers::NONE),
+            KeyTableEntry {
+                action: KeyAssignment::PopKeyTable,
+                description: None,
+            },
+        );
+
+        let all = table_hints(&table, Some, |_| "described".to_string());
+        assert_eq!(
+            all,
+            vec![
+                KeyHint {
+                    key: "LEADER+t".to_string(),
+                    description: "New tab".to_string(),
+                },
+                KeyHint {
+                    key: "q".to_string(),
+                    description: "described".to_string(),
+                },
+            ]
+        );
+
+        let leader = table_hints(
+            &table,
+            |mods| {
+                if mods.contains(Modifiers::LEADER) {
+                    Some(mods - Modifiers::LEADER)
+                } else {
+                    None
+                }
+            },
+            |_| "described".to_string(),
+        );
+        assert_eq!(
+            leader,
+            vec![KeyHint {
+                key: "t".to_string(),
+                description: "New tab".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn truncation() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("a longer description", 8), "a longe…");
+    }
+}
@@ -0,0 +1,146 @@
+//! Applies the `key_remap` configuration, which makes one physical
+//! key behave as though it were another, before the key events are
+//! matched against the key assignments.
+//! Remapping a key to a modifier is emulated by holding that modifier
+//! in our own state while the key is down, and removing the modifier
+//! that the system reports for a remapped modifier key.
+use crate::termwindow::TermWindow;
+use ::window::{KeyCode, KeyEvent, Modifiers, PhysKeyCode, RawKeyEvent};
+
+#[derive(Debug, Default)]
+pub struct KeyRemapState {
+    /// Modifiers that are held down by way of remapped keys
+    held: Modifiers,
+    /// Modifiers that the system reports for modifier keys that
+    /// are held down but have been remapped to something else
+    suppressed: Modifiers,
+    /// Whether a remapped CapsLock key is down
+    caps_lock_down: bool,
+    /// The caps lock state that the system believes to be in effect
+    /// as a result of pressing a remapped CapsLock key
+    caps_lock: bool,
+}
+
+impl KeyRemapState {
+    /// Forgets the keys that are held down, as we won't see them
+    /// being released while the window doesn't have the focus
+    pub fn release_all(&mut self) {
+        self.held = Modifiers::NONE;
+        self.suppressed = Modifiers::NONE;
+        self.caps_lock_down = false;
+    }
+
+    fn adjust_modifiers(&self, mods: Modifiers) -> Modifiers {
+        (mods - self.suppressed) | self.held
+    }
+
+    /// Tracks the state of the remapped key `from`.
+    /// This may be called more than once for the same transition,
+    /// as the raw and the processed key events both report it.
+    fn update(&mut self, from: PhysKeyCode, to: PhysKeyCode, down: bool) {
+        if down {
+            self.held |= phys_modifiers(to);
+            self.suppressed |= phys_modifiers(from);
+        } else {
+            self.held -= phys_modifiers(to);
+            self.suppressed -= phys_modifiers(from);
+        }
+        if from == PhysKeyCode::CapsLock {
+            if down && !self.caps_lock_down {
+                self.caps_lock = !self.caps_lock;
+            }
+            self.caps_lock_down = down;
+        }
+    }
+
+    /// Undoes the effect of caps lock on `key` if the system toggled
+    /// it in response to a remapped CapsLock key
+    fn undo_caps_lock(&self, key: KeyCode) -> KeyCode {
+        match key {
+            KeyCode::Char(c) if self.caps_lock && c.is_uppercase() => {
+                KeyCode::Char(c.to_lowercase().next().unwrap_or(c))
+            }
+            KeyCode::Char(c) if self.caps_lock && c.is_lowercase() => {
+                KeyCode::Char(c.to_uppercase().next().unwrap_or(c))
+            }
+            key => key,
+        }
+    }
+}
+
+/// Returns the modifiers that are reported while `code` is held down
+fn phys_modifiers(code: PhysKeyCode) -> Modifiers {
+    match code {
+        PhysKeyCode::LeftShift => Modifiers::SHIFT | Modifiers::LEFT_SHIFT,
+        PhysKeyCode::RightShift => Modifiers::SHIFT | Modifiers::RIGHT_SHIFT,
+        PhysKeyCode::LeftControl => Modifiers::CTRL | Modifiers::LEFT_CTRL,
+        PhysKeyCode::RightControl => Modifiers::CTRL | Modifiers::RIGHT_CTRL,
+        PhysKeyCode::LeftAlt => Modifiers::ALT | Modifiers::LEFT_ALT,
+        PhysKeyCode::RightAlt => Modifiers::ALT | Modifiers::RIGHT_ALT,
+        PhysKeyCode::LeftWindows | PhysKeyCode::RightWindows => Modifiers::SUPER,
+        _ => Modifiers::NONE,
+    }
+}
+
+impl TermWindow {
+    fn remapped_key(&self, phys_code: Option<PhysKeyCode>) -> Option<(PhysKeyCode, PhysKeyCode)> {
+        let phys_code = phys_code?;
+        self.config
+            .key_remap
+            .iter()
+            .find(|remap| remap.from == phys_code)
+            .map(|remap| (remap.from, remap.to))
+    }
+
+    /// Applies `key_remap` to a raw key event.
+    /// Returns None if the event was consumed by the remapping.
+    pub fn remap_raw_key_event(&mut self, mut key: RawKeyEvent) -> Option<RawKeyEvent> {
+        if self.config.key_remap.is_empty() {
+            return Some(key);
+        }
+        if let Some((from, to)) = self.remapped_key(key.phys_code) {
+            self.key_remap_state.update(from, to, key.key_is_down);
+            if to.is_modifier() {
+                key.set_handled();
+                return None;
+            }
+            key.phys_code = Some(to);
+            key.key = KeyCode::Physical(to);
+        }
+        key.modifiers = self.key_remap_state.adjust_modifiers(key.modifiers);
+        Some(key)
+    }
+
+    /// Applies `key_remap` to a key event.
+    /// Returns None if the event was consumed by the remapping.
+    pub fn remap_key_event(&mut self, mut key: KeyEvent) -> Option<KeyEvent> {
+        if self.config.key_remap.is_empty() {
+            return Some(key);
+        }
+        let phys_code = key.raw.as_ref().and_then(|raw| raw.phys_code);
+        if let Some((from, to)) = self.remapped_key(phys_code) {
+            self.key_remap_state.update(from, to, key.key_is_down);
+            if to.is_modifier() {
+                return None;
+            }
+            key.key = match to.to_key_code() {
+                KeyCode::Char(c) if key.modifiers.contains(Modifiers::SHIFT) => {
+                    KeyCode::Char(c.to_ascii_uppercase())
+                }
+                code => code,
+            };
+            if let Some(raw) = key.raw.as_mut() {
+                raw.phys_code = Some(to);
+                raw.key = KeyCode::Physical(to);
+            }
+            #[cfg(windows)]
+            {
+                key.win32_uni_char = None;
+            }
+        } else {
+            key.key = self.key_remap_state.undo_caps_lock(key.key);
+        }
+        key.modifiers = self.key_remap_state.adjust_modifiers(key.modifiers);
+        Some(key)
+    }
+}
@@ -0,0 +1,332 @@
+//! Renders a miniature overview of the scrollback of the active pane
+//! along the right hand side of the window, in the manner of the
+//! minimap found in some text editors.
+//! Each line is reduced to a bar whose length is that of the text on
+//! the line; when there are more lines than there are rows in the
+//! minimap, adjacent lines are merged into a single row.
+use crate::overlay::CopyOverlay;
+use crate::renderstate::MappedQuads;
+use crate::termwindow::{TermWindow, UIItem, UIItemType};
+use config::{ConfigHandle, DimensionContext};
+use mux::pane::Pane;
+use mux::tab::PositionedPane;
+use mux::Mux;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::rc::Rc;
+use termwiz::surface::SequenceNo;
+use wezterm_term::color::ColorAttribute;
+use wezterm_term::{Line, SemanticType, StableRowIndex};
+use window::color::LinearRgba;
+
+/// The height of a row of the minimap, in pixels at 96 dpi
+const ROW_HEIGHT: usize = 2;
+
+/// Summarizes the appearance of a single line
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LineSummary {
+    /// The number of cells up to and including the last non-blank cell
+    len: usize,
+    /// The foreground color of the first non-blank cell
+    color: ColorAttribute,
+}
+
+impl LineSummary {
+    fn from_line(line: &Line) -> Self {
+        let mut len = 0;
+        let mut color = None;
+        for (idx, cell) in line.visible_cells() {
+            if cell.str().trim().is_empty() {
+                continue;
+            }
+            len = idx + cell.width();
+            if color.is_none() {
+                color.replace(cell.attrs().foreground());
+            }
+        }
+        Self {
+            len,
+            color: color.unwrap_or(ColorAttribute::Default),
+        }
+    }
+}
+
+/// Holds the line summaries for a pane, so that only the lines
+/// that have changed need to be examined when the minimap is drawn
+#[derive(Default)]
+pub struct MinimapCache {
+    seqno: SequenceNo,
+    alt_screen: bool,
+    /// The stable row index of the first entry in `lines`
+    first_row: StableRowIndex,
+    lines: VecDeque<LineSummary>,
+    prompts: Vec<StableRowIndex>,
+}
+
+impl MinimapCache {
+    fn update(&mut self, pane: &Rc<dyn Pane>) {
+        let seqno = pane.get_current_seqno();
+        let alt_screen = pane.is_alt_screen_active();
+        let dims = pane.get_dimensions();
+        let end = dims.scrollback_top + dims.scrollback_rows as StableRowIndex;
+
+        if alt_screen != self.alt_screen || self.first_row > dims.scrollback_top {
+            self.lines.clear();
+            self.alt_screen = alt_screen;
+        }
+        // Discard lines that have been scrolled out of the scrollback
+        while self.first_row < dims.scrollback_top && !self.lines.is_empty() {
+            self.lines.pop_front();
+            self.first_row += 1;
+        }
+        if self.lines.is_empty() {
+            self.first_row = dims.scrollback_top;
+        }
+        let cached_end = self.first_row + self.lines.len() as StableRowIndex;
+        if cached_end > end {
+            self.lines.truncate((end - self.first_row).max(0) as usize);
+        }
+        let cached_end = cached_end.min(end);
+
+        let mut dirty = if self.lines.is_empty() {
+            Default::default()
+        } else if seqno != self.seqno {
+            pane.get_changed_since(self.first_row..cached_end, self.seqno)
+        } else {
+            Default::default()
+        };
+        dirty.add_range(cached_end..end);
+
+        for range in dirty.iter() {
+            let (first, lines) = pane.get_lines(range.clone());
+            for (idx, line) in lines.iter().enumerate() {
+                let summary = LineSummary::from_line(line);
+                let line_idx = (first + idx as StableRowIndex - self.first_row) as usize;
+                if line_idx < self.lines.len() {
+                    self.lines[line_idx] = summary;
+                } else if line_idx == self.lines.len() {
+                    self.lines.push_back(summary);
+                }
+            }
+        }
+
+        if seqno != self.seqno || self.prompts.is_empty() {
+            self.prompts = pane
+                .get_semantic_zones()
+                .unwrap_or_else(|_| vec![])
+                .into_iter()
+                .filter(|zone| zone.semantic_type == SemanticType::Prompt)
+                .map(|zone| zone.start_y)
+                .collect();
+            self.prompts.dedup();
+        }
+        self.seqno = seqno;
+    }
+}
+
+/// Returns the row of the minimap that represents `line`, when
+/// `num_lines` lines are shown using `num_rows` rows
+fn row_for_line(line: usize, num_lines: usize, num_rows: usize) -> usize {
+    if num_lines == 0 {
+        0
+    } else {
+        line * num_rows / num_lines
+    }
+}
+
+/// Returns the range of lines that are represented by `row`; the
+/// inverse of `row_for_line`
+fn lines_for_row(row: usize, num_lines: usize, num_rows: usize) -> Range<usize> {
+    let first_line = |row: usize| (row * num_lines + num_rows - 1) / num_rows;
+    first_line(row)..first_line(row + 1)
+}
+
+/// Returns the width in pixels that is reserved for the minimap
+/// on the right hand side of the window
+pub fn minimap_width(config: &ConfigHandle, context: DimensionContext) -> usize {
+    if config.enable_scrollback_minimap {
+        config
+            .scrollback_minimap_width
+            .evaluate_as_pixels(context)
+            .max(1.) as usize
+    } else {
+        0
+    }
+}
+
+impl TermWindow {
+    /// Returns the width in pixels of the minimap, or 0 if it is disabled
+    pub fn minimap_width(&self) -> usize {
+        minimap_width(
+            &self.config,
+            DimensionContext {
+                pixel_cell: self.render_metrics.cell_size.width as f32,
+                dpi: self.dimensions.dpi as f32,
+                pixel_max: self.dimensions.pixel_width as f32,
+            },
+        )
+    }
+
+    fn minimap_row_height(&self) -> usize {
+        (ROW_HEIGHT * self.dimensions.dpi / 96).max(1)
+    }
+
+    /// Draws the minimap for the pane in the region at `x`, `y` of the
+    /// specified size, and returns the UIItem that can be used to
+    /// navigate the scrollback by clicking in the minimap
+    pub fn paint_minimap(
+        &self,
+        pos: &PositionedPane,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        layers: &mut [MappedQuads; 3],
+    ) -> anyhow::Result<UIItem> {
+        // Summarize the underlying pane, rather than any overlay
+        let pane = Mux::get()
+            .and_then(|mux| mux.get_pane(pos.pane.pane_id()))
+            .unwrap_or_else(|| Rc::clone(&pos.pane));
+        let dims = pane.get_dimensions();
+        let current_viewport = self.get_viewport(pane.pane_id());
+        let palette = self.transition_palette(pos.pane.palette());
+
+        let mut caches = self.minimap_cache.borrow_mut();
+        let cache = caches.entry(pane.pane_id()).or_default();
+        cache.update(&pane);
+
+        let row_height = self.minimap_row_height();
+        let num_lines = cache.lines.len();
+        let num_rows = num_lines.min(height / row_height).max(1);
+        let rect = |row: Range<usize>, left: f32, width: f32| {
+            euclid::rect(
+                x as f32 + left,
+                (y + row.start * row_height) as f32,
+                width,
+                ((row.end - row.start) * row_height) as f32,
+            )
+        };
+        let row_for_stable = |stable: StableRowIndex| -> Option<usize> {
+            let line = stable - cache.first_row;
+            if line < 0 || line as usize >= num_lines {
+                None
+            } else {
+                Some(row_for_line(line as usize, num_lines, num_rows))
+            }
+        };
+
+        // The region of the scrollback that is in the viewport
+        let viewport_top = current_viewport.unwrap_or(dims.physical_top);
+        let viewport_rows = (dims.viewport_rows as StableRowIndex).max(1);
+        if let (Some(first), Some(last)) = (
+            row_for_stable(viewport_top),
+            row_for_stable(viewport_top + viewport_rows - 1),
+        ) {
+            self.filled_rectangle(
+                &mut layers[0],
+                rect(first..last + 1, 0., width as f32),
+                palette.scrollbar_thumb.to_linear(),
+            )?;
+        }
+
+        let cols = dims.cols.max(1) as f32;
+        for row in 0..num_rows {
+            let mut len = 0;
+            let mut color = None;
+            for line in lines_for_row(row, num_lines, num_rows) {
+                let summary = &cache.lines[line];
+                len = len.max(summary.len);
+                if color.is_none() && summary.len > 0 {
+                    color.replace(summary.color);
+                }
+            }
+            if let Some(color) = color {
+                let bar_width = (len as f32 / cols).min(1.) * width as f32;
+                let color: LinearRgba = palette.resolve_fg(color).to_linear();
+                self.filled_rectangle(
+                    &mut layers[1],
+                    rect(row..row + 1, 0., bar_width),
+                    color.mul_alpha(0.6),
+                )?;
+            }
+        }
+
+        // Mark the prompts at the left edge of the minimap
+        let prompt_color = palette.cursor_bg.to_linear();
+        let marker_width = (width as f32 / 8.).max(1.);
+        for &prompt in &cache.prompts {
+            if let Some(row) = row_for_stable(prompt) {
+                self.filled_rectangle(
+                    &mut layers[2],
+                    rect(row..row + 1, 0., marker_width),
+                    prompt_color,
+                )?;
+            }
+        }
+
+        // and the search matches, if search mode is active
+        if let Some(copy) = pos.pane.downcast_ref::<CopyOverlay>() {
+            let match_color = palette.colors.0[3].to_linear();
+            for stable in copy.search_match_rows() {
+                if let Some(row) = row_for_stable(stable) {
+                    self.filled_rectangle(
+                        &mut layers[2],
+                        rect(row..row + 1, marker_width, width as f32 - marker_width),
+                        match_color,
+                    )?;
+                }
+            }
+        }
+
+        Ok(UIItem {
+            x,
+            y,
+            width,
+            height: num_rows * row_height,
+            item_type: UIItemType::Minimap,
+        })
+    }
+
+    /// Scrolls the active pane so that the line represented by the
+    /// row of the minimap under the mouse is in the middle of the viewport
+    pub fn minimap_jump(&mut self, item: &UIItem, mouse_y: isize) {
+        let pane = match self.get_active_pane_or_overlay() {
+            Some(pane) => pane,
+            None => return,
+        };
+        let dims = pane.get_dimensions();
+        let (first_row, num_lines) = match self.minimap_cache.borrow().get(&pane.pane_id()) {
+            Some(cache) => (cache.first_row, cache.lines.len()),
+            None => return,
+        };
+        let row_height = self.minimap_row_height();
+        let num_rows = (item.height / row_height).max(1);
+        let row = (mouse_y.saturating_sub(item.y as isize).max(0) as usize / row_height)
+            .min(num_rows - 1);
+        let line = lines_for_row(row, num_lines, num_rows).start;
+        let top = first_row + line as StableRowIndex - dims.viewport_rows as StableRowIndex / 2;
+        self.set_viewport(pane.pane_id(), Some(top), dims);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rows_and_lines_agree() {
+        for &(num_lines, num_rows) in &[(3, 2), (10, 10), (100, 7), (7, 1), (1000, 333)] {
+            let mut covered = 0;
+            for row in 0..num_rows {
+                let lines = lines_for_row(row, num_lines, num_rows);
+                assert!(!lines.is_empty(), "{} {} {}", num_lines, num_rows, row);
+                assert_eq!(lines.start, covered);
+                for line in lines.clone() {
+                    assert_eq!(row_for_line(line, num_lines, num_rows), row);
+                }
+                covered = lines.end;
+            }
+            assert_eq!(covered, num_lines);
+        }
+    }
+}
@@ -6,17 +6,19 @@ use crate::colorease::ColorEase;
 use crate::frontend::{front_end, try_front_end};
 use crate::glium::texture::SrgbTexture2d;
 use crate::inputmap::InputMap;
+use crate::macros::MacroRecording;
 use crate::overlay::{
-    confirm_close_pane, confirm_close_tab, confirm_close_window, confirm_quit_program, launcher,
-    start_overlay, start_overlay_pane, CopyModeParams, CopyOverlay, LauncherArgs, LauncherFlags,
-    QuickSelectOverlay,
+    char_select, clipboard_history_picker, confirm_close_pane, confirm_close_tab,
+    confirm_close_window, confirm_quit_program, key_cheatsheet, launcher, overlay_lifecycle,
+    pane_diff, registers_picker, ssh_prompt, start_overlay, start_overlay_pane, CopyModeParams,
+    CopyOverlay, LauncherArgs, LauncherFlags, OverlayViewState, PaneText, QuickSelectOverlay,
 };
 use crate::scripting::guiwin::GuiWin;
 use crate::scripting::pane::PaneObject;
 use crate::scrollbar::*;
-use crate::selection::Selection;
+use crate::selection::{Selection, SelectionRange};
 use crate::shapecache::*;
-use crate::tabbar::{TabBarItem, TabBarState};
+use crate::tabbar::{StatusText, TabBarItem, TabBarState};
 use crate::termwindow::background::{
     load_background_image, reload_background_image, LoadedBackgroundLayer,
 };
@@ -26,16 +28,18 @@ use ::wezterm_term::input::{ClickPosition, MouseButton as TMB};
 use ::window::*;
 use anyhow::{anyhow, ensure, Context};
 use config::keyassignment::{
-    ClipboardCopyDestination, ClipboardPasteSource, KeyAssignment, PaneDirection, Pattern,
+    CharSelectArguments, ClipboardCopyDestination, ClipboardPasteSource, CopyModeAssignment,
+    DiffPanesArguments, KeyAssignment, MacroEvent, PaneDirection, Pattern, PlayMacro,
     QuickSelectArguments, RotationDirection, SpawnCommand, SplitSize,
 };
 use config::{
-    configuration, AudibleBell, ConfigHandle, Dimension, DimensionContext, TermConfig,
-    WindowCloseConfirmation,
+    configuration, ClipboardPolicy, ConfigHandle, Dimension, DimensionContext, HsbTransform,
+    StatusPosition, StatusSegment, TermConfig, VisualBellTarget, WindowCloseConfirmation,
 };
 use mlua::{FromLua, UserData, UserDataFields};
 use mux::pane::{CloseReason, Pane, PaneId, Pattern as MuxPattern};
 use mux::renderable::RenderableDimensions;
+use mux::ssh::SshPrompt;
 use mux::tab::{
     PositionedPane, PositionedSplit, SplitDirection, SplitRequest, SplitSize as MuxSplitSize, Tab,
     TabId,
@@ -45,7 +49,7 @@ use mux::{Mux, MuxNotification};
 use smol::channel::Sender;
 use smol::Timer;
 use std::cell::{RefCell, RefMut};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::ops::Add;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -57,25 +61,44 @@ use wezterm_font::FontConfiguration;
 use wezterm_gui_subcommands::GuiPosition;
 use wezterm_term::color::ColorPalette;
 use wezterm_term::input::LastMouseClick;
-use wezterm_term::{Alert, StableRowIndex, TerminalConfiguration, TerminalSize};
+use wezterm_term::{Alert, Line, StableRowIndex, TerminalConfiguration, TerminalSize};
 
 pub mod background;
+mod bell;
+mod blur;
 pub mod box_model;
 pub mod clipboard;
+mod commandhint;
+mod corners;
+mod decorations;
+pub mod folding;
+mod gesture;
+mod hyperlink_preview;
+mod image_preview;
+mod keyecho;
 mod keyevent;
+pub mod keyhints;
+mod keyremap;
+mod minimap;
 pub mod modal;
 mod mouseevent;
+pub mod palettecolor;
+mod paneborder;
 pub mod paneselect;
 mod prevcursor;
+pub mod prewarm;
 mod render;
 pub mod resize;
+mod resizebadge;
+pub mod scrollbarmarks;
 mod selection;
 pub mod spawn;
+mod stacked;
+pub mod unwrapped;
+mod winops;
 use prevcursor::PrevCursorPos;
 use spawn::SpawnWhere;
 
-const ATLAS_SIZE: usize = 128;
-
 lazy_static::lazy_static! {
     static ref WINDOW_CLASS: Mutex<String> = Mutex::new(wezterm_gui_subcommands::DEFAULT_WINDOW_CLASS.to_owned());
     static ref POSITION: Mutex<Option<GuiPosition>> = Mutex::new(None);
@@ -109,8 +132,11 @@ pub enum TermWindowNotif {
         pane_id: PaneId,
         assignment: KeyAssignment,
     },
-    SetRightStatus(String),
-    GetDimensions(Sender<(Dimensions, WindowState)>),
+    SetStatus {
+        segment: String,
+        status: String,
+    },
+    GetDimensions(Sender<(Dimensions, WindowState, f64)>),
     GetSelectionForPane {
         pane_id: PaneId,
         tx: Sender<String>,
@@ -129,6 +155,7 @@ pub enum TermWindowNotif {
     },
     MuxNotification(MuxNotification),
     EmitStatusUpdate,
+    EmitStatusSegmentUpdate(String),
     Apply(Box<dyn FnOnce(&mut TermWindow) + Send + Sync>),
     SwitchToMuxWindow(MuxWindowId),
 }
@@ -140,7 +167,13 @@ pub enum UIItemType {
     AboveScrollThumb,
     ScrollThumb,
     BelowScrollThumb,
+    ScrollbarMark {
+        row: StableRowIndex,
+        tooltip: Option<String>,
+    },
+    Minimap,
     Split(PositionedSplit),
+    PaneBorderTitle(PaneId),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -185,7 +218,33 @@ pub struct PaneState {
     pub overlay: Option<OverlayState>,
 
     bell_start: Option<Instant>,
+    /// What the visual bell is flashing while bell_start is set
+    bell_target: Option<VisualBellTarget>,
+    /// When the bell last rang, for rate limiting
+    last_bell: Option<Instant>,
+    /// If true, the bell is silenced for this pane
+    bell_muted: bool,
+    /// Whether the pane was active when it was last rendered
+    was_active: Option<bool>,
+    /// While the pane is fading to or from inactive_pane_hsb,
+    /// when the fade started and the transform it started from
+    dim_transition: Option<(Instant, HsbTransform)>,
     pub mouse_terminal_coords: Option<(ClickPosition, StableRowIndex)>,
+    /// The font scale requested for this pane via pane:set_font_scale
+    font_scale: Option<f64>,
+    /// The blur radius requested for this pane via pane:set_background_blur
+    background_blur: Option<u16>,
+    /// The start rows of the output zones that are folded
+    folded_output: BTreeSet<StableRowIndex>,
+    /// The word boundary requested for this pane via
+    /// pane:set_selection_word_boundary
+    word_boundary: Option<String>,
+    /// The range that was last selected by smart selection, and the
+    /// seqno of the pane at that time
+    smart_selection: Option<(SelectionRange, SequenceNo)>,
+    /// Set while the pane is shown with each logical line on a single,
+    /// horizontally scrollable row
+    unwrapped: Option<unwrapped::UnwrappedView>,
 }
 
 /// Data used when synchronously formatting pane and window titles
@@ -196,6 +255,10 @@ pub struct TabInformation {
     pub is_active: bool,
     pub active_pane: Option<PaneInformation>,
     pub window_id: MuxWindowId,
+    /// True if any pane in the tab has unseen output
+    pub has_unseen_output: bool,
+    /// The total number of unseen bells from the panes in the tab
+    pub unseen_bell_count: usize,
 }
 
 impl UserData for TabInformation {
@@ -223,6 +286,8 @@ impl UserData for TabInformation {
             Ok(panes)
         });
         fields.add_field_method_get("window_id", |_, this| Ok(this.window_id));
+        fields.add_field_method_get("has_unseen_output", |_, this| Ok(this.has_unseen_output));
+        fields.add_field_method_get("unseen_bell_count", |_, this| Ok(this.unseen_bell_count));
         fields.add_field_method_get("tab_title", |_, this| {
             let mux = Mux::get().expect("event to run on main thread");
             let tab = mux
@@ -248,6 +313,7 @@ pub struct PaneInformation {
     pub is_active: bool,
     pub is_zoomed: bool,
     pub has_unseen_output: bool,
+    pub unseen_bell_count: usize,
     pub left: usize,
     pub top: usize,
     pub width: usize,
@@ -256,6 +322,7 @@ pub struct PaneInformation {
     pub pixel_height: usize,
     pub title: String,
     pub user_vars: HashMap<String, String>,
+    pub clipboard_policy: ClipboardPolicy,
 }
 
 impl UserData for PaneInformation {
@@ -265,6 +332,7 @@ impl UserData for PaneInformation {
         fields.add_field_method_get("is_active", |_, this| Ok(this.is_active));
         fields.add_field_method_get("is_zoomed", |_, this| Ok(this.is_zoomed));
         fields.add_field_method_get("has_unseen_output", |_, this| Ok(this.has_unseen_output));
+        fields.add_field_method_get("unseen_bell_count", |_, this| Ok(this.unseen_bell_count));
         fields.add_field_method_get("left", |_, this| Ok(this.left));
         fields.add_field_method_get("top", |_, this| Ok(this.top));
         fields.add_field_method_get("width", |_, this| Ok(this.width));
@@ -273,6 +341,9 @@ impl UserData for PaneInformation {
         fields.add_field_method_get("pixel_height", |_, this| Ok(this.pixel_width));
         fields.add_field_method_get("title", |_, this| Ok(this.title.clone()));
         fields.add_field_method_get("user_vars", |_, this| Ok(this.user_vars.clone()));
+        fields.add_field_method_get("clipboard_policy", |_, this| {
+            Ok(format!("{:?}", this.clipboard_policy))
+        });
         fields.add_field_method_get("foreground_process_name", |_, this| {
             let mut name = None;
             if let Some(mux) = Mux::get() {
@@ -342,9 +413,14 @@ pub struct TermWindow {
     pub window: Option<Window>,
     pub config: ConfigHandle,
     pub config_overrides: wezterm_dynamic::Value,
+    /// The workspace of the mux window, as of the last time that
+    /// the `workspace_overrides` were applied
+    workspace: String,
     os_parameters: Option<parameters::Parameters>,
     /// When we most recently received keyboard focus
     focused: Option<Instant>,
+    /// false when the window is minimized or fully occluded
+    visible: bool,
     fonts: Rc<FontConfiguration>,
     /// Window dimensions and dpi
     pub dimensions: Dimensions,
@@ -355,16 +431,31 @@ pub struct TermWindow {
     pub mux_window_id_for_subscriptions: Arc<Mutex<MuxWindowId>>,
     pub render_metrics: RenderMetrics,
     render_state: Option<RenderState>,
+    /// The per-window font scale, as adjusted by IncreaseFontSize and
+    /// friends, or window:set_font_scale
+    window_font_scale: f64,
+    font_scale_animation: Option<resize::FontScaleAnimation>,
+    /// The part of a touch scroll that didn't amount to a whole line
+    touch_scroll_remainder: f32,
+    /// The pinch scale that has yet to be applied to the font scale
+    touch_pinch_scale: f64,
+    key_remap_state: keyremap::KeyRemapState,
+    /// The resize badge is shown until this time
+    resize_badge_until: Option<Instant>,
     input_map: InputMap,
     /// If is_some, the LEADER modifier is active until the specified instant.
     leader_is_down: Option<std::time::Instant>,
     dead_key_status: DeadKeyStatus,
+    compose_state: Option<ComposeState>,
     key_table_state: KeyTableState,
     show_tab_bar: bool,
     show_scroll_bar: bool,
     tab_bar: TabBarState,
     fancy_tab_bar: Option<box_model::ComputedElement>,
-    pub right_status: String,
+    /// The status text of each segment, as set by window:set_status
+    status: HashMap<String, String>,
+    /// The titles shown above the panes when pane_border_titles is enabled
+    pane_border_titles: HashMap<PaneId, Line>,
     last_ui_item: Option<UIItem>,
     /// Tracks whether the current mouse-down event is part of click-focus.
     /// If so, we ignore mouse events until released
@@ -378,6 +469,15 @@ pub struct TermWindow {
     tab_state: RefCell<HashMap<TabId, TabState>>,
     pane_state: RefCell<HashMap<PaneId, PaneState>>,
     semantic_zones: HashMap<PaneId, SemanticZoneCache>,
+    minimap_cache: RefCell<HashMap<PaneId, minimap::MinimapCache>>,
+    scrollbar_marks: RefCell<HashMap<PaneId, scrollbarmarks::PaneScrollbarMarks>>,
+    /// The regions of the window background to blur in this frame
+    blur_regions: RefCell<Vec<blur::BlurRegion>>,
+    key_hints: RefCell<Option<keyhints::KeyHints>>,
+    /// Whether the glyphs passed to wezterm.gui.prewarm_glyphs need
+    /// to be rasterized into the glyph atlas
+    prewarm_glyphs_needed: bool,
+    key_echo: keyecho::KeyEcho,
 
     window_background: Vec<LoadedBackgroundLayer>,
 
@@ -389,18 +489,35 @@ pub struct TermWindow {
     /// Keeps track of double and triple clicks
     last_mouse_click: Option<LastMouseClick>,
 
+    /// The pane that pane_focus_follows_mouse will activate
+    /// once pane_focus_follows_mouse_delay_ms has elapsed
+    pending_focus_follows_mouse: Option<PaneId>,
+
+    /// The macro that StartMacroRecording is capturing input for
+    macro_recording: Option<MacroRecording>,
+
     /// The URL over which we are currently hovering
     current_highlight: Option<Arc<Hyperlink>>,
+    /// The most recently loaded `image_preview` thumbnail
+    image_preview: Option<image_preview::ImagePreview>,
+    /// The layers set via `window:set_decorations`
+    decorations: decorations::Decorations,
 
     shape_cache:
         RefCell<LruCache<ShapeCacheKey, anyhow::Result<Rc<Vec<ShapedInfo<SrgbTexture2d>>>>>>,
 
     last_status_call: Instant,
+    /// When the next update event of each status segment that
+    /// has its own update_interval is due
+    status_segment_calls: HashMap<String, Instant>,
     cursor_blink_state: RefCell<ColorEase>,
     blink_state: RefCell<ColorEase>,
     rapid_blink_state: RefCell<ColorEase>,
 
     palette: Option<ColorPalette>,
+    /// The palette that was in effect before the appearance changed
+    /// the color scheme, and when the transition away from it started
+    palette_transition: RefCell<Option<(ColorPalette, Instant)>>,
 
     ui_items: Vec<UIItem>,
     dragging: Option<(UIItem, MouseEvent)>,
@@ -432,6 +549,10 @@ impl TermWindow {
     }
 
     fn close_requested(&mut self, window: &Window) {
+        if self.config.enable_tray_icon && self.config.close_to_tray {
+            crate::tray::hide_window(window);
+            return;
+        }
         let mux = Mux::get().unwrap();
         match self.config.window_close_confirmation {
             WindowCloseConfirmation::NeverPrompt => {
@@ -485,6 +606,7 @@ impl TermWindow {
             self.current_mouse_buttons.clear();
             self.current_mouse_capture = None;
             self.is_click_to_focus_window = false;
+            self.key_remap_state.release_all();
 
             for state in self.pane_state.borrow_mut().values_mut() {
                 state.mouse_terminal_coords.take();
@@ -502,6 +624,24 @@ impl TermWindow {
         }
     }
 
+    fn visibility_changed(&mut self, visible: bool, window: &Window) {
+        if visible == self.visible {
+            return;
+        }
+        log::trace!("Setting visibility to {:?}", visible);
+        self.visible = visible;
+        if visible {
+            // Animations are not scheduled while the window is hidden,
+            // so repaint to pick them up again
+            window.invalidate();
+        }
+        self.emit_window_event("window-visibility-changed", None);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
     fn created(
         &mut self,
         window: &Window,
@@ -509,7 +649,12 @@ impl TermWindow {
     ) -> anyhow::Result<()> {
         self.render_state = None;
 
-        match RenderState::new(ctx, &self.fonts, &self.render_metrics, ATLAS_SIZE) {
+        match RenderState::new(
+            ctx,
+            &self.fonts,
+            &self.render_metrics,
+            self.config.glyph_atlas_size.max(1).next_power_of_two(),
+        ) {
             Ok(gl) => {
                 self.opengl_info.replace(format!(
                     "{} {}",
@@ -523,6 +668,7 @@ impl TermWindow {
                     config::wezterm_version(),
                 );
                 self.render_state.replace(gl);
+                self.prewarm_glyphs_needed = true;
             }
             Err(err) => {
                 log::error!("failed to create OpenGLRenderState: {}", err);
@@ -548,6 +694,10 @@ impl TermWindow {
         let fontconfig = Rc::new(FontConfiguration::new(Some(config.clone()), dpi)?);
 
         let mux = Mux::get().expect("to be main thread with mux running");
+        let workspace = mux
+            .get_window(mux_window_id)
+            .map(|w| w.get_workspace().to_string())
+            .unwrap_or_default();
         let size = match mux.get_active_tab_for_window(mux_window_id) {
             Some(tab) => tab.get_size(),
             None => {
@@ -607,7 +757,7 @@ impl TermWindow {
             pixel_max: terminal_size.pixel_height as f32,
             pixel_cell: render_metrics.cell_size.height as f32,
         };
-        let padding_top = config.window_padding.top.evaluate_as_pixels(v_context) as usize;
+        let padding_top = resize::effective_top_padding(&config, v_context);
         let padding_bottom = config.window_padding.bottom.evaluate_as_pixels(v_context) as usize;
 
         let dimensions = Dimensions {
@@ -638,8 +788,11 @@ impl TermWindow {
             window_background,
             config: config.clone(),
             config_overrides: wezterm_dynamic::Value::default(),
+            workspace,
             palette: None,
+            palette_transition: RefCell::new(None),
             focused: None,
+            visible: true,
             mux_window_id,
             mux_window_id_for_subscriptions: Arc::new(Mutex::new(mux_window_id)),
             fonts: Rc::clone(&fontconfig),
@@ -648,14 +801,22 @@ impl TermWindow {
             window_state: WindowState::default(),
             terminal_size,
             render_state,
+            window_font_scale: 1.0,
+            font_scale_animation: None,
+            touch_scroll_remainder: 0.,
+            touch_pinch_scale: 1.,
+            key_remap_state: keyremap::KeyRemapState::default(),
+            resize_badge_until: None,
             input_map: InputMap::new(&config),
             leader_is_down: None,
             dead_key_status: DeadKeyStatus::None,
+            compose_state: None,
             show_tab_bar,
             show_scroll_bar: config.enable_scroll_bar,
             tab_bar: TabBarState::default(),
             fancy_tab_bar: None,
-            right_status: String::new(),
+            status: HashMap::new(),
+            pane_border_titles: HashMap::new(),
             last_mouse_coords: (0, -1),
             window_drag_position: None,
             current_mouse_event: None,
@@ -666,13 +827,18 @@ impl TermWindow {
             current_mouse_buttons: vec![],
             current_mouse_capture: None,
             last_mouse_click: None,
+            pending_focus_follows_mouse: None,
+            macro_recording: None,
             current_highlight: None,
+            image_preview: None,
+            decorations: decorations::Decorations::default(),
             shape_cache: RefCell::new(LruCache::new(
                 "shape_cache.hit.rate",
                 "shape_cache.miss.rate",
                 65536,
             )),
             last_status_call: Instant::now(),
+            status_segment_calls: HashMap::new(),
             cursor_blink_state: RefCell::new(ColorEase::new(
                 config.cursor_blink_rate,
                 config.cursor_blink_ease_in,
@@ -699,6 +865,12 @@ impl TermWindow {
             scheduled_animation: RefCell::new(None),
             allow_images: true,
             semantic_zones: HashMap::new(),
+            minimap_cache: RefCell::new(HashMap::new()),
+            scrollbar_marks: RefCell::new(HashMap::new()),
+            blur_regions: RefCell::new(vec![]),
+            key_hints: RefCell::new(None),
+            prewarm_glyphs_needed: false,
+            key_echo: keyecho::KeyEcho::default(),
             ui_items: vec![],
             dragging: None,
             last_ui_item: None,
@@ -760,15 +932,14 @@ impl TermWindow {
             let mut myself = tw.borrow_mut();
             myself.config_subscription.replace(config_subscription);
             myself.gl.replace(Rc::clone(&gl));
-            if config.use_resize_increments {
-                window.set_resize_increments(
-                    myself.render_metrics.cell_size.width as u16,
-                    myself.render_metrics.cell_size.height as u16,
-                );
-            }
+            myself.update_resize_increments(&window);
+            myself.update_corner_radius(&window);
 
             myself.created(&window, Rc::clone(&gl))?;
             myself.subscribe_to_pane_updates();
+            if config.workspace_overrides.contains_key(&myself.workspace) {
+                myself.config_was_reloaded();
+            }
             myself.emit_window_event("window-config-reloaded", None);
             myself.emit_status_event();
         }
@@ -792,13 +963,22 @@ impl TermWindow {
             }
             WindowEvent::AppearanceChanged(appearance) => {
                 log::debug!("Appearance is now {:?}", appearance);
+                config::set_appearance_is_dark(appearance.is_dark());
                 self.config_was_reloaded();
                 Ok(true)
             }
+            WindowEvent::ClipboardChanged(clipboard) => {
+                self.clipboard_changed(clipboard);
+                Ok(true)
+            }
             WindowEvent::FocusChanged(focused) => {
                 self.focus_changed(focused, window);
                 Ok(true)
             }
+            WindowEvent::VisibilityChanged(visible) => {
+                self.visibility_changed(visible, window);
+                Ok(true)
+            }
             WindowEvent::MouseEvent(event) => {
                 self.mouse_event_impl(event, window);
                 Ok(true)
@@ -807,6 +987,13 @@ impl TermWindow {
                 self.mouse_leave_impl(window);
                 Ok(true)
             }
+            // Touches are only used by way of the gestures that the
+            // window layer recognizes in them
+            WindowEvent::TouchEvent(_) => Ok(true),
+            WindowEvent::Gesture(gesture) => {
+                self.gesture_event_impl(gesture, window);
+                Ok(true)
+            }
             WindowEvent::Resized {
                 dimensions,
                 window_state,
@@ -832,6 +1019,10 @@ impl TermWindow {
                 window.invalidate();
                 Ok(true)
             }
+            WindowEvent::AdviseComposeState(state) => {
+                self.compose_state = state;
+                Ok(true)
+            }
             WindowEvent::NeedRepaint => Ok(self.do_paint(window)),
             WindowEvent::Notification(item) => {
                 if let Ok(notif) = item.downcast::<TermWindowNotif>() {
@@ -854,7 +1045,9 @@ impl TermWindow {
                     })
                     .collect::<Vec<_>>()
                     .join(" ");
-                pane.trickle_paste(paths)?;
+                if let Some(paths) = mux_lua::apply_pre_paste_hook(pane.pane_id(), paths) {
+                    pane.trickle_paste(paths)?;
+                }
                 Ok(true)
             }
             WindowEvent::DraggedFile(_) => Ok(true),
@@ -874,6 +1067,18 @@ impl TermWindow {
             return false;
         }
 
+        // If the active pane changed to one with a different font
+        // scale, transition to that scale once we're done painting
+        if self.font_scale_animation.is_none()
+            && self.effective_font_scale() != self.fonts.get_font_scale()
+        {
+            window.notify(TermWindowNotif::Apply(Box::new(|tw| {
+                if let Some(window) = tw.window.clone() {
+                    tw.update_font_scale(&window);
+                }
+            })));
+        }
+
         let mut frame = glium::Frame::new(
             Rc::clone(&gl),
             (
@@ -883,7 +1088,9 @@ impl TermWindow {
         );
 
         self.paint_impl(&mut frame);
-        window.finish_frame(frame).is_ok()
+        let presented = window.finish_frame(frame).is_ok();
+        self.key_echo_frame_submitted();
+        presented
     }
 
     fn dispatch_notif(&mut self, notif: TermWindowNotif, window: &Window) -> anyhow::Result<()> {
@@ -909,18 +1116,22 @@ impl TermWindow {
                     .context("perform_key_assignment")?;
                 window.invalidate();
             }
-            TermWindowNotif::SetRightStatus(status) => {
-                if status != self.right_status {
-                    self.right_status = status;
+            TermWindowNotif::SetStatus { segment, status } => {
+                if self.status.get(&segment) != Some(&status) {
+                    self.status.insert(segment, status);
                     self.update_title_post_status();
                 } else {
                     self.schedule_next_status_update();
                 }
             }
             TermWindowNotif::GetDimensions(tx) => {
-                tx.try_send((self.dimensions, self.window_state))
-                    .map_err(chan_err)
-                    .context("send GetDimensions response")?;
+                tx.try_send((
+                    self.dimensions,
+                    self.window_state,
+                    self.effective_font_scale(),
+                ))
+                .map_err(chan_err)
+                .context("send GetDimensions response")?;
             }
             TermWindowNotif::GetEffectiveConfig(tx) => {
                 tx.try_send(self.config.clone())
@@ -970,22 +1181,30 @@ impl TermWindow {
                     alert: Alert::Bell,
                     pane_id,
                 } => {
-                    match self.config.audible_bell {
-                        AudibleBell::SystemBeep => {
-                            Connection::get().expect("on main thread").beep();
-                        }
-                        AudibleBell::Disabled => {}
-                    }
-
-                    log::trace!("Ding! (this is the bell) in pane {}", pane_id);
-                    self.emit_window_event("bell", Some(pane_id));
-
-                    let mut per_pane = self.pane_state(pane_id);
-                    per_pane.bell_start.replace(Instant::now());
-                    window.invalidate();
+                    self.ring_bell(pane_id);
+                    // Refresh the unseen bell badges in the tab bar
+                    self.update_title();
+                }
+                MuxNotification::Alert {
+                    alert: Alert::WindowOperation(op),
+                    pane_id,
+                } => {
+                    self.perform_window_operation(pane_id, op);
                 }
                 MuxNotification::Alert {
-                    alert: Alert::ToastNotification { .. },
+                    alert: Alert::ClipboardQuery(selection),
+                    pane_id,
+                } => {
+                    self.clipboard_query(pane_id, selection);
+                }
+                MuxNotification::Alert {
+                    alert: Alert::CommandFinished { status },
+                    pane_id,
+                } => {
+                    self.command_finished(pane_id, status);
+                }
+                MuxNotification::Alert {
+                    alert: Alert::ToastNotification { .. } | Alert::CommandStarted { .. },
                     ..
                 } => {}
                 MuxNotification::PaneOutput(pane_id) => {
@@ -1003,16 +1222,32 @@ impl TermWindow {
                 MuxNotification::SaveToDownloads { .. } => {
                     // Handled by frontend
                 }
+                MuxNotification::FileTransferProgress { .. } => {
+                    // Handled by frontend
+                }
+                MuxNotification::SshPrompt { .. } => {
+                    // Handled by frontend
+                }
+                MuxNotification::WindowWorkspaceChanged(_) => {
+                    self.check_workspace_changed();
+                }
                 MuxNotification::PaneAdded(_)
                 | MuxNotification::PaneRemoved(_)
-                | MuxNotification::WindowWorkspaceChanged(_)
+                | MuxNotification::PaneFocused(_)
+                | MuxNotification::PaneHibernated(_)
+                | MuxNotification::PaneResized { .. }
                 | MuxNotification::ActiveWorkspaceChanged(_)
+                | MuxNotification::WorkspaceCreated(_)
+                | MuxNotification::WorkspaceRemoved(_)
                 | MuxNotification::Empty
                 | MuxNotification::WindowCreated(_) => {}
             },
             TermWindowNotif::EmitStatusUpdate => {
                 self.emit_status_event();
             }
+            TermWindowNotif::EmitStatusSegmentUpdate(name) => {
+                self.emit_status_segment_event(&name);
+            }
             TermWindowNotif::GetSelectionForPane { pane_id, tx } => {
                 let mux = Mux::get().unwrap();
                 let pane = mux
@@ -1042,6 +1277,7 @@ impl TermWindow {
                     }
                 };
                 self.update_title();
+                self.check_workspace_changed();
                 window.invalidate();
             }
         }
@@ -1119,6 +1355,7 @@ impl TermWindow {
 
     fn mux_pane_output_event(&mut self, pane_id: PaneId) {
         metrics::histogram!("mux.pane_output_event.rate", 1.);
+        self.key_echo.pane_output(pane_id);
         if self.is_pane_visible(pane_id) {
             if let Some(ref win) = self.window {
                 win.invalidate();
@@ -1146,7 +1383,10 @@ impl TermWindow {
                     | Alert::WindowTitleChanged(_)
                     | Alert::TabTitleChanged(_)
                     | Alert::IconTitleChanged(_)
-                    | Alert::Bell,
+                    | Alert::Bell
+                    | Alert::WindowOperation(_)
+                    | Alert::ClipboardQuery(_)
+                    | Alert::CommandFinished { .. },
             }
             | MuxNotification::PaneOutput(pane_id) => {
                 // Ideally we'd check to see if pane_id is part of this window,
@@ -1174,7 +1414,8 @@ impl TermWindow {
                 return mux.get_window(mux_window_id).is_some();
             }
             MuxNotification::WindowRemoved(window_id)
-            | MuxNotification::WindowInvalidated(window_id) => {
+            | MuxNotification::WindowInvalidated(window_id)
+            | MuxNotification::WindowWorkspaceChanged(window_id) => {
                 if window_id != mux_window_id {
                     return true;
                 }
@@ -1183,16 +1424,23 @@ impl TermWindow {
                 alert:
                     Alert::SetUserVar { .. }
                     | Alert::ToastNotification { .. }
-                    | Alert::PaletteChanged { .. },
+                    | Alert::PaletteChanged { .. }
+                    | Alert::CommandStarted { .. },
                 ..
             }
             | MuxNotification::AssignClipboard { .. }
             | MuxNotification::SaveToDownloads { .. }
+            | MuxNotification::FileTransferProgress { .. }
+            | MuxNotification::SshPrompt { .. }
             | MuxNotification::PaneRemoved(_)
+            | MuxNotification::PaneFocused(_)
+            | MuxNotification::PaneHibernated(_)
+            | MuxNotification::PaneResized { .. }
             | MuxNotification::WindowCreated(_)
             | MuxNotification::ActiveWorkspaceChanged(_)
-            | MuxNotification::Empty
-            | MuxNotification::WindowWorkspaceChanged(_) => return true,
+            | MuxNotification::WorkspaceCreated(_)
+            | MuxNotification::WorkspaceRemoved(_)
+            | MuxNotification::Empty => return true,
         }
 
         window.notify(TermWindowNotif::MuxNotification(n));
@@ -1215,6 +1463,65 @@ impl TermWindow {
         self.emit_window_event("update-right-status", None);
     }
 
+    fn emit_status_segment_event(&mut self, name: &str) {
+        match self
+            .status_segments()
+            .into_iter()
+            .find(|segment| segment.name == name && segment.update_interval.is_some())
+        {
+            Some(segment) => {
+                self.emit_window_event(&segment.event_name(), None);
+                self.schedule_next_status_update();
+            }
+            None => {
+                // The segment was removed from the config, or no
+                // longer has an update_interval
+                self.status_segment_calls.remove(name);
+            }
+        }
+    }
+
+    /// Returns the configured status segments, along with those
+    /// of the built-in segments that the config doesn't redefine
+    fn status_segments(&self) -> Vec<StatusSegment> {
+        let mut segments: Vec<StatusSegment> = StatusSegment::builtin()
+            .into_iter()
+            .filter(|builtin| {
+                !self
+                    .config
+                    .status_segments
+                    .iter()
+                    .any(|segment| segment.name == builtin.name)
+            })
+            .collect();
+        segments.extend(self.config.status_segments.iter().cloned());
+        segments
+    }
+
+    /// Combines the text of the status segments at each position,
+    /// in the order that the segments are defined
+    fn status_text(&self) -> StatusText {
+        let mut text = StatusText::default();
+        for segment in self.status_segments() {
+            let status = match self.status.get(&segment.name) {
+                Some(status) if !status.is_empty() => status,
+                _ => continue,
+            };
+            let target = match segment.position {
+                StatusPosition::Left => &mut text.left,
+                StatusPosition::Center => &mut text.center,
+                StatusPosition::Right => &mut text.right,
+            };
+            if !target.is_empty() {
+                // Don't let the attributes of one segment
+                // spill into the next
+                target.push_str("\x1b[0m");
+            }
+            target.push_str(status);
+        }
+        text
+    }
+
     fn schedule_window_event(&mut self, name: &str, pane_id: Option<PaneId>) {
         let window = GuiWin::new(self);
         let pane = match pane_id {
@@ -1338,9 +1645,7 @@ impl TermWindow {
         if dirty.is_empty() {
             return;
         }
-        if pane.downcast_ref::<CopyOverlay>().is_none()
-            && pane.downcast_ref::<QuickSelectOverlay>().is_none()
-        {
+        if overlay_lifecycle(pane).is_none() {
             // If any of the changed lines intersect with the
             // selection, then we need to clear the selection, but not
             // when the search overlay is active; the search overlay
@@ -1374,23 +1679,79 @@ impl TermWindow {
         self.palette.as_ref().unwrap()
     }
 
+    /// Returns the `workspace_overrides` for the workspace of the window,
+    /// combined with the overrides from `window:set_config_overrides`,
+    /// which take precedence over those for the workspace
+    fn effective_config_overrides(&self) -> wezterm_dynamic::Value {
+        use wezterm_dynamic::Value;
+        match (
+            configuration().workspace_overrides.get(&self.workspace),
+            &self.config_overrides,
+        ) {
+            (Some(Value::Object(workspace)), Value::Object(window)) => {
+                let mut overrides = workspace.clone();
+                for (key, value) in window.iter() {
+                    overrides.insert(key.clone(), value.clone());
+                }
+                Value::Object(overrides)
+            }
+            (Some(workspace), Value::Null) => workspace.clone(),
+            _ => self.config_overrides.clone(),
+        }
+    }
+
+    /// Called when the workspace of the mux window may have changed,
+    /// either because it was moved to another workspace, or because
+    /// this window now shows a different mux window.
+    /// Applies the `workspace_overrides` of the new workspace and
+    /// emits the `workspace-changed` event.
+    fn check_workspace_changed(&mut self) {
+        let mux = Mux::get().expect("to be main thread with mux running");
+        let workspace = match mux.get_window(self.mux_window_id) {
+            Some(window) => window.get_workspace().to_string(),
+            None => return,
+        };
+        if workspace == self.workspace {
+            return;
+        }
+        let prior = std::mem::replace(&mut self.workspace, workspace);
+        log::debug!("workspace changed from {} to {}", prior, self.workspace);
+
+        let overrides = &configuration().workspace_overrides;
+        if overrides.contains_key(&prior) || overrides.contains_key(&self.workspace) {
+            self.config_was_reloaded();
+        }
+        self.emit_window_event("workspace-changed", None);
+    }
+
     pub fn config_was_reloaded(&mut self) {
-        log::debug!(
-            "config was reloaded, overrides: {:?}",
-            self.config_overrides
-        );
+        let overrides = self.effective_config_overrides();
+        log::debug!("config was reloaded, overrides: {:?}", overrides);
         self.key_table_state.clear_stack();
-        let config = match config::overridden_config(&self.config_overrides) {
+        let overlay_view_state = self.save_overlay_view_state();
+        let config = match config::overridden_config(&overrides) {
             Ok(config) => config,
             Err(err) => {
                 log::error!(
                     "Failed to apply config overrides to window: {:#}: {:?}",
                     err,
-                    self.config_overrides
+                    overrides
                 );
                 configuration()
             }
         };
+        if config.color_scheme_for_appearance.is_some()
+            && config.color_scheme_transition_duration_ms > 0
+            && config.resolved_color_scheme != self.config.resolved_color_scheme
+        {
+            // Start from the blended palette if a prior
+            // transition is still in progress
+            let palette = self.palette().clone();
+            let prior = self.transition_palette(palette);
+            self.palette_transition
+                .borrow_mut()
+                .replace((prior, Instant::now()));
+        }
         self.config = config.clone();
         self.palette.take();
 
@@ -1440,6 +1801,7 @@ impl TermWindow {
         self.invalidate_modal();
         self.input_map = InputMap::new(&config);
         self.leader_is_down = None;
+        self.key_hints.borrow_mut().take();
         let dimensions = self.dimensions;
 
         if let Err(err) = self.fonts.config_changed(&config) {
@@ -1464,6 +1826,7 @@ impl TermWindow {
             window.invalidate();
         }
 
+        self.restore_overlay_view_state(overlay_view_state);
         self.invalidate_modal();
         self.emit_window_event("window-config-reloaded", None);
     }
@@ -1515,7 +1878,7 @@ impl TermWindow {
         self.update_title_impl();
     }
 
-    /// Called by window:set_right_status after the status has
+    /// Called by window:set_status after the status has
     /// been updated; let's update the bar
     pub fn update_title_post_status(&mut self) {
         self.update_title_impl();
@@ -1537,7 +1900,7 @@ impl TermWindow {
             pixel_max: self.terminal_size.pixel_height as f32,
             pixel_cell: self.render_metrics.cell_size.height as f32,
         };
-        let padding_top = self.config.window_padding.top.evaluate_as_pixels(v_context) as u16;
+        let padding_top = resize::effective_top_padding(&self.config, v_context) as u16;
         let padding_bottom = self
             .config
             .window_padding
@@ -1578,7 +1941,7 @@ impl TermWindow {
             &panes,
             self.config.resolved_palette.tab_bar.as_ref(),
             &self.config,
-            &self.right_status,
+            &self.status_text(),
         );
         if new_tab_bar != self.tab_bar {
             self.tab_bar = new_tab_bar;
@@ -1589,6 +1952,16 @@ impl TermWindow {
             }
         }
 
+        if self.config.pane_border_titles {
+            let titles = paneborder::compute_pane_border_titles(&panes, &self.config);
+            if titles != self.pane_border_titles {
+                self.pane_border_titles = titles;
+                if let Some(window) = self.window.as_ref() {
+                    window.invalidate();
+                }
+            }
+        }
+
         let num_tabs = window.len();
         if num_tabs == 0 {
             return;
@@ -1684,13 +2057,54 @@ impl TermWindow {
                 })
                 .detach();
             }
+
+            for segment in self.status_segments() {
+                let interval = match segment.update_interval {
+                    Some(interval) => Duration::from_millis(interval),
+                    None => continue,
+                };
+                let target = match self.status_segment_calls.get(&segment.name) {
+                    Some(due) if *due > now => continue,
+                    Some(_) => now + interval,
+                    // Populate the segment right away
+                    None => now,
+                };
+                self.status_segment_calls
+                    .insert(segment.name.clone(), target);
+
+                let window = window.clone();
+                promise::spawn::spawn(async move {
+                    Timer::at(target).await;
+                    window.notify(TermWindowNotif::EmitStatusSegmentUpdate(segment.name));
+                })
+                .detach();
+            }
         }
     }
 
     fn update_text_cursor(&mut self, pos: &PositionedPane) {
+        // While a modal is active, text is typed into the modal
+        if let Some(modal) = self.get_modal() {
+            if let Some(bounds) = modal.text_input_bounds(self) {
+                if let Some(win) = self.window.as_ref() {
+                    win.set_text_cursor_position(Rect::new(
+                        Point::new(bounds.max_x() as isize, bounds.min_y() as isize),
+                        Size::new(
+                            self.render_metrics.cell_size.width,
+                            bounds.height() as isize,
+                        ),
+                    ));
+                }
+                return;
+            }
+        }
         if let Some(win) = self.window.as_ref() {
             let cursor = pos.pane.get_cursor_position();
-            let top = pos.pane.get_dimensions().physical_top;
+            // Overlays such as copy mode and quick select place the
+            // cursor in their search box at the bottom of the viewport
+            let top = self
+                .get_viewport(pos.pane.pane_id())
+                .unwrap_or_else(|| pos.pane.get_dimensions().physical_top);
             let tab_bar_height = if self.show_tab_bar && !self.config.tab_bar_at_bottom {
                 self.tab_bar_pixel_height().unwrap()
             } else {
@@ -1815,11 +2229,227 @@ impl TermWindow {
         };
 
         let gui_win = GuiWin::new(self);
+        let pane = match tab.get_active_pane() {
+            Some(pane) => mux_lua::MuxPane(pane.pane_id()),
+            None => return,
+        };
 
         let opengl_info = self.opengl_info.as_deref().unwrap_or("Unknown").to_string();
 
         let (overlay, future) = start_overlay(self, &tab, move |_tab_id, term| {
-            crate::overlay::show_debug_overlay(term, gui_win, opengl_info)
+            crate::overlay::show_debug_overlay(term, gui_win, pane, opengl_info)
+        });
+        self.assign_overlay(tab.tab_id(), overlay);
+        promise::spawn::spawn(future).detach();
+    }
+
+    /// Shows a prompt from the ssh domain of the pane in an overlay
+    /// over the pane, in place of the text prompt in the pane
+    pub fn show_ssh_prompt(&mut self, pane_id: PaneId, domain_name: String, prompt: SshPrompt) {
+        let mux = Mux::get().unwrap();
+        let pane = match mux.get_pane(pane_id) {
+            Some(pane) => pane,
+            None => return,
+        };
+        let window = self.window.clone().unwrap();
+        let (overlay, future) = start_overlay_pane(self, &pane, move |pane_id, term| {
+            ssh_prompt(pane_id, term, window, domain_name, prompt)
+        });
+        self.assign_overlay_for_pane(pane_id, overlay);
+        promise::spawn::spawn(future).detach();
+    }
+
+    fn show_settings_editor(&mut self) {
+        let mux = Mux::get().unwrap();
+        let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+            Some(tab) => tab,
+            None => return,
+        };
+
+        let (overlay, future) = start_overlay(self, &tab, move |_tab_id, term| {
+            crate::overlay::settings_editor(term)
+        });
+        self.assign_overlay(tab.tab_id(), overlay);
+        promise::spawn::spawn(future).detach();
+    }
+
+    fn show_clipboard_history(&mut self) {
+        let mux = Mux::get().unwrap();
+        let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+            Some(tab) => tab,
+            None => return,
+        };
+        let pane = match self.get_active_pane_no_overlay() {
+            Some(pane) => pane,
+            None => return,
+        };
+        let pane_id = pane.pane_id();
+        let window = self.window.as_ref().unwrap().clone();
+        let entries = crate::clipboardhistory::entries();
+
+        let (overlay, future) = start_overlay(self, &tab, move |_tab_id, term| {
+            clipboard_history_picker(entries, pane_id, term, window)
+        });
+        self.assign_overlay(tab.tab_id(), overlay);
+        promise::spawn::spawn(future).detach();
+    }
+
+    fn show_registers(&mut self) {
+        let mux = Mux::get().unwrap();
+        let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+            Some(tab) => tab,
+            None => return,
+        };
+        let pane = match self.get_active_pane_no_overlay() {
+            Some(pane) => pane,
+            None => return,
+        };
+        let pane_id = pane.pane_id();
+        let window = self.window.as_ref().unwrap().clone();
+        let registers = crate::registers::registers();
+
+        let (overlay, future) = start_overlay(self, &tab, move |_tab_id, term| {
+            registers_picker(registers, pane_id, term, window)
+        });
+        self.assign_overlay(tab.tab_id(), overlay);
+        promise::spawn::spawn(future).detach();
+    }
+
+    fn show_key_cheatsheet(&mut self) {
+        let mux = Mux::get().unwrap();
+        let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+            Some(tab) => tab,
+            None => return,
+        };
+        let infos = self.input_map.key_binding_infos(&self.config);
+        let active_table = self.current_key_table_name();
+
+        let (overlay, future) = start_overlay(self, &tab, move |_tab_id, term| {
+            key_cheatsheet(infos, active_table, term)
+        });
+        self.assign_overlay(tab.tab_id(), overlay);
+        promise::spawn::spawn(future).detach();
+    }
+
+    /// Shows the differences between the text of two panes
+    /// in an overlay over the active tab
+    pub fn show_pane_diff(
+        &mut self,
+        left: &Rc<dyn Pane>,
+        right: &Rc<dyn Pane>,
+        args: &DiffPanesArguments,
+    ) {
+        let mux = Mux::get().unwrap();
+        let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+            Some(tab) => tab,
+            None => return,
+        };
+        let left = PaneText::from_pane(left, args.scrollback);
+        let right = PaneText::from_pane(right, args.scrollback);
+        let layout = args.layout;
+
+        let (overlay, future) = start_overlay(self, &tab, move |_tab_id, term| {
+            pane_diff(left, right, layout, term)
+        });
+        self.assign_overlay(tab.tab_id(), overlay);
+        promise::spawn::spawn(future).detach();
+    }
+
+    fn diff_panes(&mut self, pane: &Rc<dyn Pane>, args: &DiffPanesArguments) {
+        let mux = Mux::get().unwrap();
+        let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+            Some(tab) => tab,
+            None => return,
+        };
+        let panes = tab.iter_panes();
+        match panes.len() {
+            0 | 1 => log::warn!("DiffPanes: there is no other pane to compare with"),
+            2 => {
+                // There is only one candidate, so compare with it directly
+                if let Some(other) = panes.iter().find(|p| p.pane.pane_id() != pane.pane_id()) {
+                    self.show_pane_diff(pane, &other.pane, args);
+                }
+            }
+            _ => {
+                let modal = crate::termwindow::paneselect::PaneSelector::for_diff(self, args);
+                self.modal.borrow_mut().replace(Rc::new(modal));
+            }
+        }
+    }
+
+    /// Measures text that may contain escape sequences, such as the
+    /// result of wezterm.format, using the fonts of this window in
+    /// the same way as the tab bar renders it
+    pub fn measure_text(&self, styled: &str) -> anyhow::Result<crate::fontresolver::TextExtent> {
+        let line =
+            crate::tabbar::parse_status_text(styled, termwiz::cell::CellAttributes::default());
+        crate::fontresolver::measure_line(
+            &self.config,
+            &self.fonts,
+            &line,
+            self.config.use_fancy_tab_bar,
+        )
+    }
+
+    pub fn recording_macro_name(&self) -> Option<String> {
+        self.macro_recording
+            .as_ref()
+            .map(|recording| recording.name.clone())
+    }
+
+    /// Captures an input event that was sent to a pane, if a
+    /// macro is being recorded
+    pub fn record_macro_event(&mut self, event: MacroEvent) {
+        if let Some(recording) = self.macro_recording.as_mut() {
+            recording.push(event);
+        }
+    }
+
+    fn play_macro(&mut self, pane: &Rc<dyn Pane>, args: &PlayMacro) -> anyhow::Result<()> {
+        let events = crate::macros::get_macro(&args.name)
+            .ok_or_else(|| anyhow!("there is no macro named `{}`", args.name))?;
+
+        if args.delay_milliseconds == 0 {
+            for event in events {
+                self.perform_key_assignment(pane, &event.to_key_assignment())?;
+            }
+            return Ok(());
+        }
+
+        let window = match self.window.clone() {
+            Some(window) => window,
+            None => return Ok(()),
+        };
+        let pane_id = pane.pane_id();
+        let delay = Duration::from_millis(args.delay_milliseconds);
+        promise::spawn::spawn(async move {
+            for event in events {
+                Timer::after(delay).await;
+                window.notify(TermWindowNotif::PerformAssignment {
+                    pane_id,
+                    assignment: event.to_key_assignment(),
+                });
+            }
+        })
+        .detach();
+        Ok(())
+    }
+
+    fn show_char_select(&mut self, args: CharSelectArguments) {
+        let mux = Mux::get().unwrap();
+        let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+            Some(tab) => tab,
+            None => return,
+        };
+        let pane = match self.get_active_pane_no_overlay() {
+            Some(pane) => pane,
+            None => return,
+        };
+        let pane_id = pane.pane_id();
+        let window = self.window.as_ref().unwrap().clone();
+
+        let (overlay, future) = start_overlay(self, &tab, move |_tab_id, term| {
+            char_select(args, pane_id, term, window)
         });
         self.assign_overlay(tab.tab_id(), overlay);
         promise::spawn::spawn(future).detach();
@@ -1862,9 +2492,11 @@ impl TermWindow {
         let pane_id = pane.pane_id();
         let tab_id = tab.tab_id();
         let title = title.to_string();
+        let gui_win = GuiWin::new(self);
+        let pane = PaneObject::new(&pane);
 
         promise::spawn::spawn(async move {
-            let args = LauncherArgs::new(
+            let mut args = LauncherArgs::new(
                 &title,
                 flags,
                 mux_window_id,
@@ -1872,6 +2504,7 @@ impl TermWindow {
                 domain_id_of_current_pane,
             )
             .await;
+            args.add_lua_entries(gui_win, pane).await;
 
             let win = window.clone();
             win.notify(TermWindowNotif::Apply(Box::new(move |term_window| {
@@ -2108,7 +2741,11 @@ impl TermWindow {
             }
             CopyTo(dest) => {
                 let text = self.selection_text(pane);
-                self.copy_to_clipboard(*dest, text);
+                // Copying completes the selection in copy mode
+                if !text.is_empty() && pane.downcast_ref::<CopyOverlay>().is_some() {
+                    self.emit_selection_changed(pane, &text);
+                }
+                self.copy_to_clipboard(dest.clone(), text);
             }
             Paste => {
                 self.paste_from_clipboard(pane, ClipboardPasteSource::Clipboard);
@@ -2117,7 +2754,7 @@ impl TermWindow {
                 self.paste_from_clipboard(pane, ClipboardPasteSource::PrimarySelection);
             }
             PasteFrom(source) => {
-                self.paste_from_clipboard(pane, *source);
+                self.paste_from_clipboard(pane, source.clone());
             }
             ActivateTabRelative(n) => {
                 self.activate_tab_relative(*n, true)?;
@@ -2177,12 +2814,37 @@ impl TermWindow {
             MoveTabRelative(n) => self.move_tab_relative(*n)?,
             ScrollByPage(n) => self.scroll_by_page(**n)?,
             ScrollByLine(n) => self.scroll_by_line(*n)?,
+            ScrollByColumn(n) => self.scroll_by_column(pane, *n),
             ScrollToPrompt(n) => self.scroll_to_prompt(*n)?,
             ScrollToTop => self.scroll_to_top(pane),
+            ToggleOutputFold => self.toggle_output_fold(pane),
+            ToggleOutputFoldAtMouseCursor => self.toggle_output_fold_at_mouse_cursor(pane),
+            FoldAllOutput => self.fold_all_output(pane),
+            UnfoldAllOutput => self.unfold_all_output(pane),
+            ToggleLineWrap => self.toggle_line_wrap(pane),
+            ToggleBellMute => self.toggle_bell_mute(pane),
+            PasteFromHistory => self.show_clipboard_history(),
+            ShowRegisters => self.show_registers(),
+            ShowKeyCheatsheet => self.show_key_cheatsheet(),
+            StartMacroRecording(name) => {
+                self.macro_recording.replace(MacroRecording::new(name));
+            }
+            StopMacroRecording => {
+                if let Some(recording) = self.macro_recording.take() {
+                    recording.save();
+                }
+            }
+            PlayMacro(args) => {
+                if let Err(err) = self.play_macro(pane, args) {
+                    log::error!("PlayMacro: {:#}", err);
+                }
+            }
+            CharSelect(args) => self.show_char_select(args.clone()),
             ScrollToBottom => self.scroll_to_bottom(pane),
             ShowTabNavigator => self.show_tab_navigator(),
             ShowDebugOverlay => self.show_debug_overlay(),
             ShowLauncher => self.show_launcher(),
+            ShowSettingsEditor => self.show_settings_editor(),
             ShowLauncherArgs(args) => {
                 self.show_launcher_impl(args.title.as_deref().unwrap_or("Launcher"), args.flags)
             }
@@ -2233,7 +2895,8 @@ impl TermWindow {
             CompleteSelectionOrOpenLinkAtMouseCursor(dest) => {
                 let text = self.selection_text(pane);
                 if !text.is_empty() {
-                    self.copy_to_clipboard(*dest, text);
+                    self.emit_selection_changed(pane, &text);
+                    self.copy_to_clipboard(dest.clone(), text);
                     let window = self.window.as_ref().unwrap();
                     window.invalidate();
                 } else {
@@ -2243,7 +2906,8 @@ impl TermWindow {
             CompleteSelection(dest) => {
                 let text = self.selection_text(pane);
                 if !text.is_empty() {
-                    self.copy_to_clipboard(*dest, text);
+                    self.emit_selection_changed(pane, &text);
+                    self.copy_to_clipboard(dest.clone(), text);
                     let window = self.window.as_ref().unwrap();
                     window.invalidate();
                 }
@@ -2297,12 +2961,14 @@ impl TermWindow {
                         &QuickSelectArguments::default(),
                     );
                     self.assign_overlay_for_pane(pane.pane_id(), qa);
+                    self.activate_quick_select_table(pane.pane_id());
                 }
             }
             QuickSelectArgs(args) => {
                 if let Some(pane) = self.get_active_pane_no_overlay() {
                     let qa = QuickSelectOverlay::with_pane(self, &pane, args);
                     self.assign_overlay_for_pane(pane.pane_id(), qa);
+                    self.activate_quick_select_table(pane.pane_id());
                 }
             }
             ActivateCopyMode => {
@@ -2348,7 +3014,7 @@ impl TermWindow {
                 let tab_id = tab.tab_id();
 
                 if self.tab_state(tab_id).overlay.is_none() {
-                    tab.adjust_pane_size(*direction, *amount);
+                    tab.animate_layout_change(|tab| tab.adjust_pane_size(*direction, *amount));
                 }
             }
             ActivatePaneByIndex(index) => {
@@ -2386,7 +3052,21 @@ impl TermWindow {
                     Some(tab) => tab,
                     None => return Ok(()),
                 };
-                tab.toggle_zoom();
+                tab.toggle_zoom_animated();
+            }
+            TogglePaneReadOnly => {
+                pane.set_read_only(!pane.is_read_only());
+            }
+            ToggleStackedLayout => {
+                let mux = Mux::get().unwrap();
+                let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+                    Some(tab) => tab,
+                    None => return Ok(()),
+                };
+                tab.toggle_stacked();
+                if let Some(window) = self.window.as_ref() {
+                    window.invalidate();
+                }
             }
             SwitchWorkspaceRelative(delta) => {
                 let mux = Mux::get().unwrap();
@@ -2474,9 +3154,37 @@ impl TermWindow {
                 })
                 .detach();
             }
+            CopyMode(CopyModeAssignment::MoveToMouseCursor) => {
+                self.copy_mode_to_mouse_cursor(pane, None);
+            }
+            CopyMode(CopyModeAssignment::SelectToMouseCursor(mode)) => {
+                self.copy_mode_to_mouse_cursor(pane, Some(*mode));
+            }
             CopyMode(_) => {
                 // NOP here; handled by the overlay directly
             }
+            SwapActivePaneWithIndex(index) => {
+                let mux = Mux::get().unwrap();
+                let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
+                    Some(tab) => tab,
+                    None => return Ok(()),
+                };
+                let active = match tab.get_active_pane() {
+                    Some(pane) => pane,
+                    None => return Ok(()),
+                };
+                let other = match tab
+                    .iter_panes_ignoring_zoom()
+                    .into_iter()
+                    .find(|p| p.index == *index)
+                {
+                    Some(pos) => pos.pane,
+                    None => return Ok(()),
+                };
+                mux.swap_panes(active.pane_id(), other.pane_id())?;
+                // The focus follows the active pane to its new position
+                tab.set_active_idx(*index);
+            }
             RotatePanes(direction) => {
                 let mux = Mux::get().unwrap();
                 let tab = match mux.get_active_tab_for_window(self.mux_window_id) {
@@ -2487,6 +3195,9 @@ impl TermWindow {
                     RotationDirection::Clockwise => tab.rotate_clockwise(),
                     RotationDirection::CounterClockwise => tab.rotate_counter_clockwise(),
                 }
+                if let Some(pane) = tab.get_active_pane() {
+                    pane.panes_rotated(direction.clone());
+                }
             }
             SplitPane(split) => {
                 log::trace!("SplitPane {:?}", split);
@@ -2523,56 +3234,71 @@ impl TermWindow {
                 let modal = crate::termwindow::paneselect::PaneSelector::new(self, args);
                 self.modal.borrow_mut().replace(Rc::new(modal));
             }
+            DiffPanes(args) => self.diff_panes(pane, args),
         };
         Ok(())
     }
 
     fn do_open_link_at_mouse_cursor(&self, pane: &Rc<dyn Pane>) {
         // They clicked on a link, so let's open it!
+        if let Some(link) = self.current_highlight.as_ref().cloned() {
+            self.open_hyperlink(pane, link);
+        }
+    }
+
+    /// Opens the link, allowing the user to define an `open-uri-<scheme>`
+    /// or `open-uri` event handler that can bypass the normal `open::that`
+    /// functionality.
+    pub fn open_hyperlink(&self, pane: &Rc<dyn Pane>, link: Arc<Hyperlink>) {
         // We need to ensure that we spawn the `open` call outside of the context
         // of our window loop; on Windows it can cause a panic due to
         // triggering our WndProc recursively.
         // We get that assurance for free as part of the async dispatch that we
-        // perform below; here we allow the user to define an `open-uri` event
-        // handler that can bypass the normal `open::that` functionality.
-        if let Some(link) = self.current_highlight.as_ref().cloned() {
-            let window = GuiWin::new(self);
-            let pane = PaneObject::new(pane);
-
-            async fn open_uri(
-                lua: Option<Rc<mlua::Lua>>,
-                window: GuiWin,
-                pane: PaneObject,
-                link: String,
-            ) -> anyhow::Result<()> {
-                let default_click = match lua {
-                    Some(lua) => {
-                        let args = lua.pack_multi((window, pane, link.clone()))?;
-                        config::lua::emit_event(&lua, ("open-uri".to_string(), args))
-                            .await
-                            .map_err(|e| {
-                                log::error!("while processing open-uri event: {:#}", e);
-                                e
-                            })?
+        // perform below.
+        let window = GuiWin::new(self);
+        let pane = PaneObject::new(pane);
+
+        async fn open_uri(
+            lua: Option<Rc<mlua::Lua>>,
+            window: GuiWin,
+            pane: PaneObject,
+            link: String,
+        ) -> anyhow::Result<()> {
+            let mut default_click = true;
+            if let Some(lua) = lua {
+                let mut events = vec!["open-uri".to_string()];
+                if let Some((scheme, _)) = link.split_once(':') {
+                    events.insert(0, format!("open-uri-{}", scheme.to_ascii_lowercase()));
+                }
+                for event in events {
+                    let args = lua.pack_multi((window.clone(), pane.clone(), link.clone()))?;
+                    default_click = config::lua::emit_event(&lua, (event.clone(), args))
+                        .await
+                        .map_err(|e| {
+                            log::error!("while processing {} event: {:#}", event, e);
+                            e
+                        })?;
+                    if !default_click {
+                        break;
                     }
-                    None => true,
-                };
-                if default_click {
-                    std::thread::spawn(move || {
-                        log::info!("clicking {}", link);
-                        if let Err(err) = open::that(&link) {
-                            log::error!("Error opening {}: {:#}", link, err);
-                        }
-                    });
                 }
-                Ok(())
             }
-
-            promise::spawn::spawn(config::with_lua_config_on_main_thread(move |lua| {
-                open_uri(lua, window, pane, link.uri().to_string())
-            }))
-            .detach();
+            if default_click {
+                std::thread::spawn(move || {
+                    log::info!("clicking {}", link);
+                    if let Err(err) = open::that(&link) {
+                        log::error!("Error opening {}: {:#}", link, err);
+                    }
+                });
+            }
+            Ok(())
         }
+
+        let link = link.uri().to_string();
+        promise::spawn::spawn(config::with_lua_config_on_main_thread(move |lua| {
+            open_uri(lua, window, pane, link)
+        }))
+        .detach();
     }
     fn close_current_pane(&mut self, confirm: bool) {
         let mux_window_id = self.mux_window_id;
@@ -2701,6 +3427,34 @@ impl TermWindow {
         }
     }
 
+    /// Capture the view state of the pane overlays, so that it can be
+    /// re-applied by restore_overlay_view_state after an operation
+    /// that may disturb them, such as a config reload or resize.
+    fn save_overlay_view_state(&self) -> HashMap<PaneId, OverlayViewState> {
+        self.pane_state
+            .borrow()
+            .iter()
+            .filter_map(|(pane_id, state)| {
+                let overlay = state.overlay.as_ref()?;
+                let lifecycle = overlay_lifecycle(&overlay.pane)?;
+                Some((*pane_id, lifecycle.save_view_state()))
+            })
+            .collect()
+    }
+
+    fn restore_overlay_view_state(&self, saved: HashMap<PaneId, OverlayViewState>) {
+        for (pane_id, view_state) in saved {
+            let overlay = match self.pane_state(pane_id).overlay.as_ref() {
+                Some(overlay) => Rc::clone(&overlay.pane),
+                None => continue,
+            };
+            if let Some(lifecycle) = overlay_lifecycle(&overlay) {
+                lifecycle.config_changed(&self.config);
+                lifecycle.restore_view_state(view_state);
+            }
+        }
+    }
+
     pub fn get_viewport(&self, pane_id: PaneId) -> Option<StableRowIndex> {
         self.pane_state(pane_id).viewport
     }
@@ -2727,21 +3481,19 @@ impl TermWindow {
         if pos != state.viewport {
             state.viewport = pos;
 
-            // This is a bit gross.  If we add other overlays that need this information,
-            // this should get extracted out into a trait
             if let Some(overlay) = state.overlay.as_ref() {
-                if let Some(copy) = overlay.pane.downcast_ref::<CopyOverlay>() {
-                    copy.viewport_changed(pos);
-                } else if let Some(qs) = overlay.pane.downcast_ref::<QuickSelectOverlay>() {
-                    qs.viewport_changed(pos);
+                if let Some(lifecycle) = overlay_lifecycle(&overlay.pane) {
+                    lifecycle.viewport_changed(pos);
                 }
             }
         }
         self.window.as_ref().unwrap().invalidate();
     }
 
-    fn maybe_scroll_to_bottom_for_input(&mut self, pane: &Rc<dyn Pane>) {
-        if self.config.scroll_to_bottom_on_input {
+    pub fn maybe_scroll_to_bottom_for_input(&mut self, pane: &Rc<dyn Pane>) {
+        // The input didn't reach a read-only pane, so leave the
+        // viewport where the user put it
+        if self.config.scroll_to_bottom_on_input && !pane.is_read_only() {
             self.scroll_to_bottom(pane);
         }
     }
@@ -2817,6 +3569,7 @@ impl TermWindow {
             is_active: pos.is_active,
             is_zoomed: pos.is_zoomed,
             has_unseen_output: pos.pane.has_unseen_output(),
+            unseen_bell_count: pos.pane.unseen_bell_count(),
             left: pos.left,
             top: pos.top,
             width: pos.width,
@@ -2825,6 +3578,7 @@ impl TermWindow {
             pixel_height: pos.pixel_height,
             title: pos.pane.get_title(),
             user_vars: pos.pane.copy_user_vars(),
+            clipboard_policy: clipboard::clipboard_policy_for_pane(&pos.pane),
         }
     }
 
@@ -2851,6 +3605,8 @@ impl TermWindow {
                         .iter()
                         .find(|p| p.is_active)
                         .map(Self::pos_pane_to_pane_info),
+                    has_unseen_output: panes.iter().any(|p| p.pane.has_unseen_output()),
+                    unseen_bell_count: panes.iter().map(|p| p.pane.unseen_bell_count()).sum(),
                 }
             })
             .collect()
@@ -2877,6 +3633,7 @@ impl TermWindow {
                 index: 0,
                 is_active: true,
                 is_zoomed: false,
+                is_collapsed: false,
                 left: 0,
                 top: 0,
                 width: size.cols as _,
@@ -2959,6 +3716,20 @@ impl TermWindow {
         self.update_title();
     }
 
+    /// Scopes the `quick_select` key and mouse tables, if any are
+    /// configured, to the quick select overlay for the pane
+    fn activate_quick_select_table(&mut self, pane_id: PaneId) {
+        if let Some(overlay) = self.pane_state(pane_id).overlay.as_mut() {
+            overlay.key_table_state.activate(KeyTableArgs {
+                name: "quick_select",
+                timeout_milliseconds: None,
+                replace_current: false,
+                one_shot: false,
+                until_unknown: false,
+            });
+        }
+    }
+
     pub fn assign_overlay(&mut self, tab_id: TabId, overlay: Rc<dyn Pane>) {
         self.cancel_overlay_for_tab(tab_id, None);
         self.tab_state(tab_id).overlay.replace(OverlayState {
@@ -1,5 +1,6 @@
 use crate::termwindow::box_model::ComputedElement;
 use crate::TermWindow;
+use ::window::RectF;
 use config::keyassignment::KeyAssignment;
 use downcast_rs::{impl_downcast, Downcast};
 use std::cell::Ref;
@@ -25,5 +26,14 @@ pub trait Modal: Downcast {
         term_window: &mut TermWindow,
     ) -> anyhow::Result<Ref<[ComputedElement]>>;
     fn reconfigure(&self, term_window: &mut TermWindow);
+
+    /// Returns the bounds, in pixels, of the place where the modal
+    /// shows the text that is typed into it.  Text that is being
+    /// composed by an input method is drawn just after it, and the
+    /// input method places its candidate window there.
+    /// Modals that don't take text input return None.
+    fn text_input_bounds(&self, _term_window: &mut TermWindow) -> Option<RectF> {
+        None
+    }
 }
 impl_downcast!(Modal);
@@ -1,14 +1,17 @@
 use crate::tabbar::TabBarItem;
 use crate::termwindow::keyevent::window_mods_to_termwiz_mods;
-use crate::termwindow::{MouseCapture, PositionedSplit, ScrollHit, UIItem, UIItemType, TMB};
+use crate::termwindow::{
+    MouseCapture, PositionedSplit, ScrollHit, TermWindowNotif, UIItem, UIItemType, TMB,
+};
 use ::window::{
     MouseButtons as WMB, MouseCursor, MouseEvent, MouseEventKind as WMEK, MousePress, WindowOps,
     WindowState,
 };
-use config::keyassignment::{MouseEventTrigger, SpawnTabDomain};
-use mux::pane::Pane;
+use config::keyassignment::{KeyAssignment, MouseEventTrigger, SpawnTabDomain};
+use mux::pane::{Pane, PaneId};
 use mux::tab::SplitDirection;
 use mux::Mux;
+use smol::Timer;
 use std::convert::TryInto;
 use std::ops::Sub;
 use std::rc::Rc;
@@ -18,6 +21,29 @@ use wezterm_term::input::MouseEventKind as TMEK;
 use wezterm_term::{ClickPosition, LastMouseClick, StableRowIndex};
 
 impl super::TermWindow {
+    fn lookup_mouse(
+        &mut self,
+        pane: &Rc<dyn Pane>,
+        event: &MouseEventTrigger,
+        mods: Modifiers,
+    ) -> Option<KeyAssignment> {
+        if let Some(overlay) = self.pane_state(pane.pane_id()).overlay.as_mut() {
+            if let Some(action) = overlay
+                .key_table_state
+                .lookup_mouse(&self.input_map, event, mods)
+            {
+                return Some(action);
+            }
+        }
+        if let Some(action) = self
+            .key_table_state
+            .lookup_mouse(&self.input_map, event, mods)
+        {
+            return Some(action);
+        }
+        self.input_map.lookup_mouse(event.clone(), mods, None)
+    }
+
     fn resolve_ui_item(&self, event: &MouseEvent) -> Option<UIItem> {
         let x = event.coords.x;
         let y = event.coords.y;
@@ -37,7 +63,10 @@ impl super::TermWindow {
             | UIItemType::AboveScrollThumb
             | UIItemType::BelowScrollThumb
             | UIItemType::ScrollThumb
-            | UIItemType::Split(_) => {}
+            | UIItemType::ScrollbarMark { .. }
+            | UIItemType::Minimap
+            | UIItemType::Split(_)
+            | UIItemType::PaneBorderTitle(_) => {}
         }
     }
 
@@ -48,7 +77,10 @@ impl super::TermWindow {
             | UIItemType::AboveScrollThumb
             | UIItemType::BelowScrollThumb
             | UIItemType::ScrollThumb
-            | UIItemType::Split(_) => {}
+            | UIItemType::ScrollbarMark { .. }
+            | UIItemType::Minimap
+            | UIItemType::Split(_)
+            | UIItemType::PaneBorderTitle(_) => {}
         }
     }
 
@@ -326,6 +358,11 @@ impl super::TermWindow {
             UIItemType::ScrollThumb => {
                 self.drag_scroll_thumb(item, start_event, event, context);
             }
+            UIItemType::Minimap => {
+                self.minimap_jump(&item, event.coords.y);
+                context.invalidate();
+                self.dragging.replace((item, start_event));
+            }
             _ => {
                 log::error!("drag not implemented for {:?}", item);
             }
@@ -354,15 +391,51 @@ impl super::TermWindow {
             UIItemType::BelowScrollThumb => {
                 self.mouse_event_below_scroll_thumb(item, pane, event, context);
             }
+            UIItemType::ScrollbarMark { row, .. } => {
+                self.mouse_event_scrollbar_mark(row, event, context);
+            }
+            UIItemType::Minimap => {
+                self.mouse_event_minimap(item, event, context);
+            }
             UIItemType::Split(split) => {
                 self.mouse_event_split(item, split, event, context);
             }
             UIItemType::CloseTab(idx) => {
                 self.mouse_event_close_tab(idx, event, context);
             }
+            UIItemType::PaneBorderTitle(pane_id) => {
+                self.mouse_event_pane_border_title(pane_id, event, context);
+            }
         }
     }
 
+    pub fn mouse_event_pane_border_title(
+        &mut self,
+        pane_id: PaneId,
+        event: MouseEvent,
+        context: &dyn WindowOps,
+    ) {
+        match event.kind {
+            WMEK::Press(MousePress::Left) => {
+                if let Some(pos) = self
+                    .get_panes_to_render()
+                    .into_iter()
+                    .find(|pos| pos.pane.pane_id() == pane_id)
+                {
+                    if !pos.is_active {
+                        let mux = Mux::get().unwrap();
+                        if let Some(tab) = mux.get_active_tab_for_window(self.mux_window_id) {
+                            tab.set_active_idx(pos.index);
+                        }
+                        context.invalidate();
+                    }
+                }
+            }
+            _ => {}
+        }
+        context.set_cursor(Some(MouseCursor::Arrow));
+    }
+
     pub fn mouse_event_close_tab(
         &mut self,
         idx: usize,
@@ -500,6 +573,33 @@ impl super::TermWindow {
         context.set_cursor(Some(MouseCursor::Arrow));
     }
 
+    pub fn mouse_event_scrollbar_mark(
+        &mut self,
+        row: StableRowIndex,
+        event: MouseEvent,
+        context: &dyn WindowOps,
+    ) {
+        if let WMEK::Press(MousePress::Left) = event.kind {
+            self.scroll_to_scrollbar_mark(row);
+            context.invalidate();
+        }
+        context.set_cursor(Some(MouseCursor::Arrow));
+    }
+
+    pub fn mouse_event_minimap(
+        &mut self,
+        item: UIItem,
+        event: MouseEvent,
+        context: &dyn WindowOps,
+    ) {
+        if let WMEK::Press(MousePress::Left) = event.kind {
+            self.minimap_jump(&item, event.coords.y);
+            context.invalidate();
+            self.dragging = Some((item, event));
+        }
+        context.set_cursor(Some(MouseCursor::Arrow));
+    }
+
     pub fn mouse_event_split(
         &mut self,
         item: UIItem,
@@ -517,6 +617,60 @@ impl super::TermWindow {
         }
     }
 
+    /// Activates the pane under the mouse once the mouse has rested
+    /// over it for pane_focus_follows_mouse_delay_ms.  With
+    /// pane_focus_follows_mouse_autoraise, the window is also raised
+    /// and focused if it doesn't already have the focus.
+    fn schedule_focus_follows_mouse(&mut self, pane_id: PaneId) {
+        if self.pending_focus_follows_mouse == Some(pane_id) {
+            return;
+        }
+        self.pending_focus_follows_mouse.replace(pane_id);
+
+        let window = match self.window.clone() {
+            Some(window) => window,
+            None => return,
+        };
+        let delay = Duration::from_millis(self.config.pane_focus_follows_mouse_delay_ms);
+        promise::spawn::spawn(async move {
+            Timer::after(delay).await;
+            window.notify(TermWindowNotif::Apply(Box::new(move |term_window| {
+                term_window.focus_follows_mouse(pane_id);
+            })));
+        })
+        .detach();
+    }
+
+    fn focus_follows_mouse(&mut self, pane_id: PaneId) {
+        // The mouse has since moved elsewhere
+        if self.pending_focus_follows_mouse != Some(pane_id) {
+            return;
+        }
+        self.pending_focus_follows_mouse.take();
+
+        let window = match self.window.clone() {
+            Some(window) => window,
+            None => return,
+        };
+        if self.config.pane_focus_follows_mouse_autoraise && self.focused.is_none() {
+            window.focus();
+        }
+
+        if let Some(pos) = self
+            .get_panes_to_render()
+            .into_iter()
+            .find(|pos| pos.pane.pane_id() == pane_id)
+        {
+            if !pos.is_active {
+                let mux = Mux::get().unwrap();
+                if let Some(tab) = mux.get_active_tab_for_window(self.mux_window_id) {
+                    tab.set_active_idx(pos.index);
+                }
+                window.invalidate();
+            }
+        }
+    }
+
     fn mouse_event_terminal(
         &mut self,
         mut pane: Rc<dyn Pane>,
@@ -539,13 +693,43 @@ impl super::TermWindow {
             Some(MouseCapture::TerminalPane(_))
         );
 
-        for pos in self.get_panes_to_render() {
+        let panes = self.get_panes_to_render();
+        // Stacked panes are not separated by a split, so each row
+        // belongs to exactly one of them
+        let is_stacked = panes.iter().any(|pos| pos.is_collapsed);
+        for pos in panes {
+            let bottom = if is_stacked {
+                (pos.top + pos.height) as i64 - 1
+            } else {
+                (pos.top + pos.height) as i64
+            };
             if !is_already_captured
                 && row >= pos.top as i64
-                && row <= (pos.top + pos.height) as i64
+                && row <= bottom
                 && column >= pos.left
                 && column <= pos.left + pos.width
             {
+                // Activating a collapsed pane changes the layout under
+                // the mouse, so only a click activates it
+                if event.kind == WMEK::Move
+                    && self.config.pane_focus_follows_mouse
+                    && !pos.is_collapsed
+                {
+                    let is_over_inactive_pane = pane.pane_id() != pos.pane.pane_id();
+                    let needs_raise =
+                        self.config.pane_focus_follows_mouse_autoraise && self.focused.is_none();
+                    if self.config.pane_focus_follows_mouse_delay_ms > 0
+                        && (is_over_inactive_pane || needs_raise)
+                    {
+                        self.schedule_focus_follows_mouse(pos.pane.pane_id());
+                    } else {
+                        self.pending_focus_follows_mouse.take();
+                        if needs_raise {
+                            context.focus();
+                        }
+                    }
+                }
+
                 if pane.pane_id() != pos.pane.pane_id() {
                     // We're over a pane that isn't active
                     match &event.kind {
@@ -558,7 +742,10 @@ impl super::TermWindow {
                             is_click_to_focus_pane = true;
                         }
                         WMEK::Move => {
-                            if self.config.pane_focus_follows_mouse {
+                            if self.config.pane_focus_follows_mouse
+                                && self.config.pane_focus_follows_mouse_delay_ms == 0
+                                && !pos.is_collapsed
+                            {
                                 let mux = Mux::get().unwrap();
                                 mux.get_active_tab_for_window(self.mux_window_id)
                                     .map(|tab| tab.set_active_idx(pos.index));
@@ -567,8 +754,8 @@ impl super::TermWindow {
                                 context.invalidate();
                             }
                         }
-                        WMEK::Release(_) | WMEK::HorzWheel(_) => {}
-                        WMEK::VertWheel(_) => {
+                        WMEK::Release(_) => {}
+                        WMEK::VertWheel(_) | WMEK::HorzWheel(_) => {
                             // Let wheel events route to the hovered pane,
                             // even if it doesn't have focus
                             pane = Rc::clone(&pos.pane);
@@ -640,16 +827,14 @@ impl super::TermWindow {
         );
 
         let dims = pane.get_dimensions();
-        let stable_row = self
-            .get_viewport(pane.pane_id())
-            .unwrap_or(dims.physical_top)
-            + row as StableRowIndex;
+        let (line_column, stable_row) =
+            self.stable_position_for_viewport_position(&pane, &dims, row, column);
 
         self.pane_state(pane.pane_id())
             .mouse_terminal_coords
             .replace((
                 ClickPosition {
-                    column,
+                    column: line_column,
                     row,
                     x_pixel_offset,
                     y_pixel_offset,
@@ -663,7 +848,7 @@ impl super::TermWindow {
         );
         let new_highlight = if top == stable_row {
             if let Some(line) = lines.get_mut(0) {
-                if let Some(cell) = line.cells().get(column) {
+                if let Some(cell) = line.cells().get(line_column) {
                     cell.attrs().hyperlink().cloned()
                 } else {
                     None
@@ -754,18 +939,24 @@ impl super::TermWindow {
                     None
                 }
             }
-            WMEK::VertWheel(amount) if !pane.is_mouse_grabbed() && !pane.is_alt_screen_active() => {
-                // adjust viewport
-                let dims = pane.get_dimensions();
-                let position = self
-                    .get_viewport(pane.pane_id())
-                    .unwrap_or(dims.physical_top)
-                    .saturating_sub((*amount).into());
-                self.set_viewport(pane.pane_id(), Some(position), dims);
-                context.invalidate();
-                return;
-            }
-            WMEK::VertWheel(_) | WMEK::HorzWheel(_) => None,
+            // Wheel bindings match a single step of the wheel in
+            // the appropriate direction, regardless of its magnitude
+            WMEK::VertWheel(amount) => Some(MouseEventTrigger::Down {
+                streak: 1,
+                button: if *amount > 0 {
+                    TMB::WheelUp(1)
+                } else {
+                    TMB::WheelDown(1)
+                },
+            }),
+            WMEK::HorzWheel(amount) => Some(MouseEventTrigger::Down {
+                streak: 1,
+                button: if *amount > 0 {
+                    TMB::WheelLeft(1)
+                } else {
+                    TMB::WheelRight(1)
+                },
+            }),
         };
 
         if allow_action
@@ -785,16 +976,34 @@ impl super::TermWindow {
                     }
                 }
 
-                if let Some(action) = self
-                    .input_map
-                    .lookup_mouse(event_trigger_type.clone(), modifiers)
-                {
+                if let Some(action) = self.lookup_mouse(&pane, &event_trigger_type, modifiers) {
                     self.perform_key_assignment(&pane, &action).ok();
                     return;
                 }
             }
         }
 
+        if let WMEK::HorzWheel(amount) = event.kind {
+            if !pane.is_mouse_grabbed() && self.is_unwrapped(&pane) {
+                self.scroll_by_column(&pane, -(amount as isize));
+                return;
+            }
+        }
+
+        if let WMEK::VertWheel(amount) = event.kind {
+            if !pane.is_mouse_grabbed() && !pane.is_alt_screen_active() {
+                // adjust viewport
+                let dims = pane.get_dimensions();
+                let position = self
+                    .get_viewport(pane.pane_id())
+                    .unwrap_or(dims.physical_top)
+                    .saturating_sub(amount.into());
+                self.set_viewport(pane.pane_id(), Some(position), dims);
+                context.invalidate();
+                return;
+            }
+        }
+
         let mouse_event = wezterm_term::MouseEvent {
             kind: match event.kind {
                 WMEK::Move => TMEK::Move,
@@ -821,7 +1030,13 @@ impl super::TermWindow {
                         TMB::WheelDown((-amount) as usize)
                     }
                 }
-                WMEK::HorzWheel(_) => TMB::None,
+                WMEK::HorzWheel(amount) => {
+                    if amount > 0 {
+                        TMB::WheelLeft(amount as usize)
+                    } else {
+                        TMB::WheelRight((-amount) as usize)
+                    }
+                }
             },
             x: column,
             y: row,
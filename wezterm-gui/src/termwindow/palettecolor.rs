@@ -0,0 +1,166 @@
+//! Runtime changes to the colors of the panes in a window, as made
+//! by `window:set_palette_color`.
+//! The changes are applied to each pane in the same way as the
+//! OSC 4, 10, 11, 12, 17 and 19 escape sequences, and can optionally
+//! be persisted to the `colors` of the window config overrides.
+use ::window::WindowOps;
+use config::{Palette, RgbaColor};
+use mux::Mux;
+use termwiz::color::SrgbaTuple;
+use termwiz::escape::osc::{ChangeColorPair, ColorOrQuery, DynamicColorNumber};
+use termwiz::escape::{Action, OperatingSystemCommand};
+use wezterm_dynamic::{ToDynamic, Value};
+use wezterm_term::color::ColorPalette;
+
+/// Identifies a color that can be changed by `window:set_palette_color`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteSlot {
+    /// One of the 256 indexed colors
+    Indexed(u8),
+    Foreground,
+    Background,
+    Cursor,
+    SelectionFg,
+    SelectionBg,
+}
+
+impl PaletteSlot {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "foreground" => Some(Self::Foreground),
+            "background" => Some(Self::Background),
+            "cursor_bg" => Some(Self::Cursor),
+            "selection_fg" => Some(Self::SelectionFg),
+            "selection_bg" => Some(Self::SelectionBg),
+            _ => None,
+        }
+    }
+
+    /// Returns the escape sequence that sets this color in a terminal
+    fn action(self, color: SrgbaTuple) -> Action {
+        let color = ColorOrQuery::Color(color);
+        let dynamic = match self {
+            Self::Indexed(palette_index) => {
+                return Action::OperatingSystemCommand(Box::new(
+                    OperatingSystemCommand::ChangeColorNumber(vec![ChangeColorPair {
+                        palette_index,
+                        color,
+                    }]),
+                ));
+            }
+            Self::Foreground => DynamicColorNumber::TextForegroundColor,
+            Self::Background => DynamicColorNumber::TextBackgroundColor,
+            Self::Cursor => DynamicColorNumber::TextCursorColor,
+            Self::SelectionFg => DynamicColorNumber::HighlightForegroundColor,
+            Self::SelectionBg => DynamicColorNumber::HighlightBackgroundColor,
+        };
+        Action::OperatingSystemCommand(Box::new(OperatingSystemCommand::ChangeDynamicColors(
+            dynamic,
+            vec![color],
+        )))
+    }
+
+    /// Records the color in `palette`.  The ansi and brights arrays
+    /// must be specified in full, so if `palette` doesn't have them
+    /// yet, they are initialized from `base`.
+    pub fn apply_to_palette(self, palette: &mut Palette, base: &ColorPalette, color: RgbaColor) {
+        match self {
+            Self::Indexed(idx) if idx < 16 => {
+                let (colors, offset) = if idx < 8 {
+                    (&mut palette.ansi, 0)
+                } else {
+                    (&mut palette.brights, 8)
+                };
+                let colors = colors.get_or_insert_with(|| {
+                    let mut colors = [RgbaColor::default(); 8];
+                    for (i, c) in colors.iter_mut().enumerate() {
+                        *c = base.colors.0[offset + i].into();
+                    }
+                    colors
+                });
+                colors[idx as usize - offset] = color;
+            }
+            Self::Indexed(idx) => {
+                palette.indexed.insert(idx, color);
+            }
+            Self::Foreground => palette.foreground = Some(color),
+            Self::Background => palette.background = Some(color),
+            Self::Cursor => {
+                // Matches OSC 12, which also sets the border
+                palette.cursor_bg = Some(color);
+                palette.cursor_border = Some(color);
+            }
+            Self::SelectionFg => palette.selection_fg = Some(color),
+            Self::SelectionBg => palette.selection_bg = Some(color),
+        }
+    }
+}
+
+impl super::TermWindow {
+    /// Changes a color in each of the panes in the window.
+    /// When `persist` is true, the color is also recorded in the
+    /// config overrides so that it applies to panes that are
+    /// created later and survives a configuration reload.
+    pub fn set_palette_color(&mut self, slot: PaletteSlot, color: SrgbaTuple, persist: bool) {
+        let mux = Mux::get().expect("called on main thread");
+        if let Some(window) = mux.get_window(self.mux_window_id) {
+            for tab in window.iter() {
+                for pos in tab.iter_panes_ignoring_zoom() {
+                    pos.pane.perform_actions(vec![slot.action(color)]);
+                }
+            }
+        }
+
+        if persist {
+            let mut colors = self.config.colors.clone().unwrap_or_default();
+            let base = self.palette().clone();
+            slot.apply_to_palette(&mut colors, &base, color.into());
+
+            let mut overrides = match &self.config_overrides {
+                Value::Object(obj) => obj.clone(),
+                _ => Default::default(),
+            };
+            overrides.insert(Value::String("colors".to_string()), colors.to_dynamic());
+            self.config_overrides = Value::Object(overrides);
+            self.config_was_reloaded();
+        }
+
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn slot_names() {
+        assert_eq!(
+            PaletteSlot::from_name("foreground"),
+            Some(PaletteSlot::Foreground)
+        );
+        assert_eq!(
+            PaletteSlot::from_name("cursor_bg"),
+            Some(PaletteSlot::Cursor)
+        );
+        assert_eq!(PaletteSlot::from_name("ansi"), None);
+    }
+
+    #[test]
+    fn apply_fills_ansi_from_base() {
+        let base = ColorPalette::default();
+        let red: RgbaColor = SrgbaTuple(1., 0., 0., 1.).into();
+
+        let mut palette = Palette::default();
+        PaletteSlot::Indexed(9).apply_to_palette(&mut palette, &base, red);
+        assert!(palette.ansi.is_none());
+        let brights = palette.brights.unwrap();
+        assert_eq!(brights[1], red);
+        assert_eq!(brights[0], RgbaColor::from(base.colors.0[8]));
+
+        PaletteSlot::Indexed(200).apply_to_palette(&mut palette, &base, red);
+        assert_eq!(palette.indexed.get(&200), Some(&red));
+    }
+}
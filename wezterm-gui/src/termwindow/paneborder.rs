@@ -0,0 +1,158 @@
+//! Shows the title of each pane in a border above it, in the manner of
+//! the pane border status of tmux, when `pane_border_titles` is enabled.
+//! The title is that of the pane, which is set by OSC 2 or derived from
+//! its foreground process, unless the `format-pane-border-title` event
+//! returns an alternative.
+//! The titles of the topmost panes are shown in a row that is reserved
+//! at the top of the window, and those of the other panes are shown in
+//! the split above them.
+use crate::tabbar::parse_status_text;
+use crate::termwindow::box_model::*;
+use crate::termwindow::render::rgbcolor_to_window_color;
+use crate::termwindow::{DimensionContext, PaneInformation, TermWindow, UIItemType};
+use crate::utilsprites::RenderMetrics;
+use config::{ConfigHandle, Dimension};
+use mlua::FromLua;
+use mux::pane::PaneId;
+use mux::tab::PositionedPane;
+use std::collections::HashMap;
+use termwiz::cell::CellAttributes;
+use termwiz_funcs::{format_as_escapes, FormatItem};
+use wezterm_term::Line;
+
+fn call_format_pane_border_title(
+    pane: &PaneInformation,
+    pane_info: &[PaneInformation],
+    config: &ConfigHandle,
+) -> Option<String> {
+    match config::run_immediate_with_lua_config(|lua| {
+        if let Some(lua) = lua {
+            let panes = lua.create_sequence_from(pane_info.iter().cloned())?;
+
+            let v = config::lua::emit_sync_callback(
+                &*lua,
+                (
+                    "format-pane-border-title".to_string(),
+                    (pane.clone(), panes, (**config).clone()),
+                ),
+            )?;
+            match &v {
+                mlua::Value::Nil => Ok(None),
+                mlua::Value::Table(_) => {
+                    let items = <Vec<FormatItem>>::from_lua(v, &*lua)?;
+                    Ok(Some(format_as_escapes(items)?))
+                }
+                _ => Ok(Some(String::from_lua(v, &*lua)?)),
+            }
+        } else {
+            Ok(None)
+        }
+    }) {
+        Ok(s) => s,
+        Err(err) => {
+            log::warn!("format-pane-border-title: {}", err);
+            None
+        }
+    }
+}
+
+/// Computes the title to show above each of the panes
+pub fn compute_pane_border_titles(
+    panes: &[PaneInformation],
+    config: &ConfigHandle,
+) -> HashMap<PaneId, Line> {
+    panes
+        .iter()
+        .map(|pane| {
+            let title = call_format_pane_border_title(pane, panes, config)
+                .unwrap_or_else(|| pane.title.clone());
+            (
+                pane.pane_id,
+                parse_status_text(&title, CellAttributes::default()),
+            )
+        })
+        .collect()
+}
+
+impl TermWindow {
+    /// Paints the title of a pane in the row above it.
+    /// Clicking the title activates the pane.
+    pub fn paint_pane_border_title(&mut self, pos: &PositionedPane) -> anyhow::Result<()> {
+        let pane_id = pos.pane.pane_id();
+        let title = match self.pane_border_titles.get(&pane_id) {
+            Some(title) => title.clone(),
+            None => parse_status_text(&pos.pane.get_title(), CellAttributes::default()),
+        };
+        let palette = self.palette().clone();
+        let font = self.fonts.default_font()?;
+        let metrics = RenderMetrics::with_font_metrics(&font.metrics());
+        let cell_width = self.render_metrics.cell_size.width as f32;
+        let cell_height = self.render_metrics.cell_size.height as f32;
+
+        let (padding_left, padding_top) = self.padding_left_top();
+        let top_bar_height = if self.show_tab_bar && !self.config.tab_bar_at_bottom {
+            self.tab_bar_pixel_height()?
+        } else {
+            0.
+        };
+        let border = self.get_os_border();
+        let left = padding_left + pos.left as f32 * cell_width;
+        let top = top_bar_height + padding_top + border.top.get() as f32 - cell_height
+            + pos.top as f32 * cell_height;
+        let width = pos.width as f32 * cell_width;
+
+        let (bg, fg) = if pos.is_active {
+            (
+                self.config.window_frame.active_titlebar_bg,
+                self.config.window_frame.active_titlebar_fg,
+            )
+        } else {
+            (
+                self.config.window_frame.inactive_titlebar_bg,
+                self.config.window_frame.inactive_titlebar_fg,
+            )
+        };
+
+        let element = Element::with_line(&font, &title, &palette)
+            .item_type(UIItemType::PaneBorderTitle(pane_id))
+            .colors(ElementColors {
+                border: BorderColor::default(),
+                bg: rgbcolor_to_window_color(bg).into(),
+                text: rgbcolor_to_window_color(fg).into(),
+            })
+            .padding(BoxDimension {
+                left: Dimension::Cells(0.5),
+                right: Dimension::Cells(0.5),
+                top: Dimension::Cells(0.),
+                bottom: Dimension::Cells(0.),
+            })
+            .max_width(Some(Dimension::Pixels((width - cell_width).max(0.))));
+
+        let mut computed = self.compute_element(
+            &LayoutContext {
+                height: DimensionContext {
+                    dpi: self.dimensions.dpi as f32,
+                    pixel_max: cell_height,
+                    pixel_cell: cell_height,
+                },
+                width: DimensionContext {
+                    dpi: self.dimensions.dpi as f32,
+                    pixel_max: width,
+                    pixel_cell: cell_width,
+                },
+                bounds: euclid::rect(0., 0., width, cell_height),
+                metrics: &metrics,
+                gl_state: self.render_state.as_ref().unwrap(),
+                zindex: 1,
+            },
+            &element,
+        )?;
+        computed.translate(euclid::vec2(left, top));
+
+        let gl_state = self.render_state.as_ref().unwrap();
+        self.render_element(&computed, gl_state, None)?;
+        self.ui_items.append(&mut computed.ui_items());
+
+        Ok(())
+    }
+}
@@ -7,7 +7,10 @@ use crate::termwindow::render::{
 use crate::termwindow::DimensionContext;
 use crate::utilsprites::RenderMetrics;
 use crate::TermWindow;
-use config::keyassignment::{KeyAssignment, PaneSelectArguments, PaneSelectMode};
+use ::window::RectF;
+use config::keyassignment::{
+    DiffPanesArguments, KeyAssignment, PaneSelectArguments, PaneSelectMode,
+};
 use config::Dimension;
 use mux::Mux;
 use std::cell::{Ref, RefCell};
@@ -19,6 +22,9 @@ pub struct PaneSelector {
     selection: RefCell<String>,
     alphabet: String,
     mode: PaneSelectMode,
+    /// When set, the selected pane is compared with the
+    /// active pane rather than applying `mode`
+    diff: Option<DiffPanesArguments>,
 }
 
 impl PaneSelector {
@@ -34,9 +40,22 @@ impl PaneSelector {
             selection: RefCell::new(String::new()),
             alphabet,
             mode: args.mode,
+            diff: None,
         }
     }
 
+    pub fn for_diff(term_window: &mut TermWindow, args: &DiffPanesArguments) -> Self {
+        let mut selector = Self::new(
+            term_window,
+            &PaneSelectArguments {
+                alphabet: args.alphabet.clone(),
+                mode: PaneSelectMode::default(),
+            },
+        );
+        selector.diff.replace(args.clone());
+        selector
+    }
+
     fn compute(
         term_window: &mut TermWindow,
         alphabet: &str,
@@ -155,6 +174,18 @@ impl PaneSelector {
         if term_window.tab_state(tab_id).overlay.is_none() {
             let panes = tab.iter_panes();
 
+            if let Some(args) = &self.diff {
+                let active = tab.get_active_pane();
+                let selected = panes.iter().find(|p| p.index == pane_index);
+                term_window.cancel_modal();
+                if let (Some(active), Some(selected)) = (active, selected) {
+                    if active.pane_id() != selected.pane.pane_id() {
+                        term_window.show_pane_diff(&active, &selected.pane, args);
+                    }
+                }
+                return Ok(());
+            }
+
             match self.mode {
                 PaneSelectMode::Activate => {
                     if panes.iter().position(|p| p.index == pane_index).is_some() {
@@ -238,4 +269,15 @@ impl Modal for PaneSelector {
     fn reconfigure(&self, _term_window: &mut TermWindow) {
         self.element.borrow_mut().take();
     }
+
+    fn text_input_bounds(&self, term_window: &mut TermWindow) -> Option<RectF> {
+        // The label is typed while looking at the active pane,
+        // so show the text being composed next to its label
+        let active = term_window
+            .get_panes_to_render()
+            .into_iter()
+            .position(|pos| pos.is_active)?;
+        let elements = self.computed_element(term_window).ok()?;
+        elements.get(active).map(|element| element.bounds)
+    }
 }
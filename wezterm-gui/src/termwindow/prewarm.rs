@@ -0,0 +1,149 @@
+//! Rasterizes the glyphs of the characters that were passed to
+//! `wezterm.gui.prewarm_glyphs` into the glyph atlas of each window
+//! ahead of time, so that the first frame that shows them doesn't stall
+//! while they are shaped and rasterized.  This is most noticeable with
+//! CJK text, where almost every character has a distinct glyph.
+//! The glyphs are rasterized again when the atlas is rebuilt for a
+//! different font size or is grown, but not when it is merely cleared
+//! to make room.
+use crate::customglyph::BlockKey;
+use crate::frontend::try_front_end;
+use crate::termwindow::{TermWindow, TermWindowNotif};
+use ::window::bitmaps::atlas::OutOfTextureSpace;
+use ::window::WindowOps;
+use std::collections::HashSet;
+use std::sync::Mutex;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The atlas is grown at most this many times while prewarming
+const MAX_GROW_ATTEMPTS: usize = 4;
+
+lazy_static::lazy_static! {
+    static ref CHARSET: Mutex<String> = Mutex::new(String::new());
+}
+
+/// Removes duplicate graphemes and whitespace from `charset`
+fn unique_graphemes(charset: &str) -> String {
+    let mut seen = HashSet::new();
+    charset
+        .graphemes(true)
+        .filter(|g| !g.chars().all(char::is_whitespace) && seen.insert(*g))
+        .collect()
+}
+
+/// Replaces the characters whose glyphs are prewarmed, and asks the
+/// existing windows to rasterize them before they next paint
+pub fn set_prewarm_charset(charset: &str) {
+    let charset = unique_graphemes(charset);
+    {
+        let mut current = CHARSET.lock().unwrap();
+        if *current == charset {
+            return;
+        }
+        *current = charset;
+    }
+
+    promise::spawn::spawn_into_main_thread(async move {
+        if let Some(fe) = try_front_end() {
+            for (window, _) in fe.known_windows() {
+                window.notify(TermWindowNotif::Apply(Box::new(|term_window| {
+                    term_window.request_prewarm_glyphs();
+                })));
+            }
+        }
+    })
+    .detach();
+}
+
+impl TermWindow {
+    fn request_prewarm_glyphs(&mut self) {
+        self.prewarm_glyphs_needed = true;
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+
+    /// Rasterizes the prewarm charset if the glyph atlas was created
+    /// or rebuilt, or the charset changed, since it was last done.
+    /// The atlas is grown if the glyphs don't fit.
+    pub fn prewarm_glyphs_if_needed(&mut self) {
+        if !self.prewarm_glyphs_needed || self.render_state.is_none() {
+            return;
+        }
+        self.prewarm_glyphs_needed = false;
+
+        let charset = CHARSET.lock().unwrap().clone();
+        if charset.is_empty() {
+            return;
+        }
+
+        for _ in 0..=MAX_GROW_ATTEMPTS {
+            let err = match self.prewarm_glyphs(&charset) {
+                Ok(()) => return,
+                Err(err) => err,
+            };
+            match err.root_cause().downcast_ref::<OutOfTextureSpace>() {
+                Some(&OutOfTextureSpace {
+                    size: Some(size), ..
+                }) => {
+                    log::trace!("grow texture atlas to {} to prewarm glyphs", size);
+                    if let Err(err) = self.recreate_texture_atlas(Some(size)) {
+                        log::error!("prewarm_glyphs: {:#}", err);
+                        return;
+                    }
+                    self.prewarm_glyphs_needed = false;
+                }
+                _ => {
+                    log::error!("prewarm_glyphs: {:#}", err);
+                    return;
+                }
+            }
+        }
+        log::warn!("prewarm_glyphs: the glyphs don't fit in the glyph atlas");
+    }
+
+    fn prewarm_glyphs(&self, charset: &str) -> anyhow::Result<()> {
+        let style = &self.config.font;
+        let font = self.fonts.resolve_font(style)?;
+        let window = self.window.as_ref().unwrap().clone();
+        let infos = font.shape(
+            charset,
+            // Rasterize the glyphs again once fallback fonts are resolved
+            move || {
+                window.notify(TermWindowNotif::Apply(Box::new(|term_window| {
+                    term_window.request_prewarm_glyphs();
+                })))
+            },
+            BlockKey::filter_out_synthetic,
+            None,
+            wezterm_bidi::Direction::LeftToRight,
+            None,
+            None,
+        )?;
+
+        let gl_state = self.render_state.as_ref().unwrap();
+        let mut glyph_cache = gl_state.glyph_cache.borrow_mut();
+        for info in &infos {
+            glyph_cache.cached_glyph(
+                info,
+                style,
+                false,
+                &font,
+                &self.render_metrics,
+                info.num_cells,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unique() {
+        assert_eq!(unique_graphemes("日本 日本語\nabca"), "日本語abc");
+        assert_eq!(unique_graphemes("e\u{301}e\u{301}"), "e\u{301}");
+    }
+}
@@ -4,11 +4,12 @@ use crate::customglyph::{BlockKey, *};
 use crate::glium::texture::SrgbTexture2d;
 use crate::glyphcache::{CachedGlyph, GlyphCache};
 use crate::quad::Quad;
+use crate::renderstate::{GlyphAtlasStats, PostProcess};
 use crate::shapecache::*;
 use crate::tabbar::{TabBarItem, TabEntry};
 use crate::termwindow::{
-    BorrowedShapeCacheKey, MappedQuads, RenderState, ScrollHit, ShapedInfo, TermWindowNotif,
-    UIItem, UIItemType,
+    folding, unwrapped, BorrowedShapeCacheKey, MappedQuads, RenderState, ScrollHit, ShapedInfo,
+    TermWindowNotif, UIItem, UIItemType,
 };
 use crate::utilsprites::RenderMetrics;
 use ::window::bitmaps::atlas::OutOfTextureSpace;
@@ -20,11 +21,11 @@ use ::window::glium::{uniform, BlendingFunction, LinearBlendingFactor, Surface};
 use ::window::{glium, DeadKeyStatus, PointF, RectF, SizeF, WindowOps};
 use anyhow::anyhow;
 use config::{
-    ConfigHandle, Dimension, DimensionContext, HsbTransform, TabBarColors, TextStyle,
-    VisualBellTarget,
+    ConfigHandle, Dimension, DimensionContext, GlyphAtlasGrowth, HsbTransform, LigatureSplitting,
+    StatusPosition, TabBarColors, TextStyle, VisualBellTarget,
 };
 use euclid::num::Zero;
-use mux::pane::Pane;
+use mux::pane::{Pane, PaneId};
 use mux::renderable::{RenderableDimensions, StableCursorPosition};
 use mux::tab::{PositionedPane, PositionedSplit, SplitDirection};
 use smol::Timer;
@@ -33,6 +34,7 @@ use std::rc::Rc;
 use std::time::Instant;
 use termwiz::cell::{unicode_column_width, Blink};
 use termwiz::cellcluster::CellCluster;
+use termwiz::color::AnsiColor;
 use termwiz::surface::{CursorShape, CursorVisibility};
 use wezterm_bidi::Direction;
 use wezterm_font::shaper::PresentationWidth;
@@ -160,6 +162,9 @@ pub struct RenderScreenLineOpenGLParams<'a> {
     pub cursor_border_color: LinearRgba,
     pub foreground: LinearRgba,
     pub is_active: bool,
+    /// The transform applied to the colors of the line,
+    /// such as inactive_pane_hsb
+    pub hsv: Option<HsbTransform>,
 
     pub selection_fg: LinearRgba,
     pub selection_bg: LinearRgba,
@@ -246,6 +251,8 @@ impl super::TermWindow {
 
         frame.clear_color(0., 0., 0., 0.);
 
+        self.prewarm_glyphs_if_needed();
+
         'pass: for pass in 0.. {
             match self.paint_opengl_pass() {
                 Ok(_) => match self.render_state.as_mut().unwrap().allocated_more_quads() {
@@ -267,7 +274,9 @@ impl super::TermWindow {
                         current_size,
                     }) = err.root_cause().downcast_ref::<OutOfTextureSpace>()
                     {
-                        let result = if pass == 0 {
+                        let clear_first =
+                            self.config.glyph_atlas_growth == GlyphAtlasGrowth::ClearThenGrow;
+                        let result = if pass == 0 && clear_first {
                             // Let's try clearing out the atlas and trying again
                             // self.clear_texture_atlas()
                             log::trace!("recreate_texture_atlas");
@@ -290,7 +299,11 @@ impl super::TermWindow {
                             } else {
                                 log::error!(
                                     "Failed to {} texture: {}",
-                                    if pass == 0 { "clear" } else { "resize" },
+                                    if pass == 0 && clear_first {
+                                        "clear"
+                                    } else {
+                                        "resize"
+                                    },
                                     err
                                 );
                                 break 'pass;
@@ -309,7 +322,37 @@ impl super::TermWindow {
         }
         log::debug!("paint_impl before call_draw elapsed={:?}", start.elapsed());
 
-        self.call_draw(frame).ok();
+        let gl_state = self.render_state.as_ref().unwrap();
+        gl_state.update_post_process(&self.config);
+        let post_process = gl_state.post_process.borrow_mut().take();
+        match post_process {
+            Some(post_process) => {
+                if let Err(err) = self.call_draw_post_process(frame, &post_process) {
+                    log::error!("post_processing_shader: {:#}", err);
+                }
+                self.render_state
+                    .as_ref()
+                    .unwrap()
+                    .post_process
+                    .borrow_mut()
+                    .replace(post_process);
+            }
+            None => {
+                self.call_draw(frame).ok();
+            }
+        }
+        let window_rounds_corners = self
+            .window
+            .as_ref()
+            .map_or(false, |window| window.window_system_rounds_corners());
+        if let Some(radius) = self
+            .corner_radius()
+            .filter(|radius| *radius >= 1. && !window_rounds_corners)
+        {
+            if let Err(err) = self.draw_corner_mask(frame, radius) {
+                log::error!("window_corner_radius: {:#}", err);
+            }
+        }
         log::debug!("paint_impl elapsed={:?}", start.elapsed());
         metrics::histogram!("gui.paint.opengl", start.elapsed());
         metrics::histogram!("gui.paint.opengl.rate", 1.);
@@ -317,8 +360,9 @@ impl super::TermWindow {
 
         // If self.has_animation is some, then the last render detected
         // image attachments with multiple frames, so we also need to
-        // invalidate the viewport when the next frame is due
-        if self.focused.is_some() {
+        // invalidate the viewport when the next frame is due.
+        // There is no point animating a window that can't be seen.
+        if self.focused.is_some() && self.visible {
             if let Some(next_due) = *self.has_animation.borrow() {
                 let prior = self.scheduled_animation.borrow_mut().take();
                 match prior {
@@ -358,6 +402,101 @@ impl super::TermWindow {
         }
     }
 
+    /// While a color scheme transition is in progress, blends `palette`
+    /// with the palette that was in effect before the transition started
+    pub fn transition_palette(&self, palette: ColorPalette) -> ColorPalette {
+        let mut transition = self.palette_transition.borrow_mut();
+        let (prior, start) = match transition.as_ref() {
+            Some((prior, start)) => (prior, *start),
+            None => return palette,
+        };
+        let mut color_ease = ColorEase::new(
+            0,
+            self.config.color_scheme_transition_function,
+            self.config.color_scheme_transition_duration_ms,
+            self.config.color_scheme_transition_function,
+            Some(start),
+        );
+
+        match color_ease.intensity_one_shot() {
+            Some((intensity, next)) => {
+                self.update_next_frame_time(Some(next));
+                palette.interpolate(prior, intensity)
+            }
+            None => {
+                transition.take();
+                palette
+            }
+        }
+    }
+
+    /// Returns the transform to apply to the colors of a pane.
+    /// When the active pane changes, the panes fade to and from
+    /// inactive_pane_hsb over inactive_pane_fade_duration_ms.
+    fn pane_hsv(&self, pane_id: PaneId, is_active: bool) -> Option<HsbTransform> {
+        let config = &self.config;
+        let target = |is_active: bool| {
+            if is_active {
+                HsbTransform::default()
+            } else {
+                config.inactive_pane_hsb
+            }
+        };
+        let fade = |start: Instant, from: HsbTransform, to: HsbTransform| {
+            let mut color_ease = ColorEase::new(
+                0,
+                config.inactive_pane_fade_function,
+                config.inactive_pane_fade_duration_ms,
+                config.inactive_pane_fade_function,
+                Some(start),
+            );
+            let (intensity, next) = color_ease.intensity_one_shot()?;
+            // intensity is the remaining proportion of `from`
+            let lerp = |a: f32, b: f32| b + (a - b) * intensity;
+            Some((
+                HsbTransform {
+                    hue: lerp(from.hue, to.hue),
+                    saturation: lerp(from.saturation, to.saturation),
+                    brightness: lerp(from.brightness, to.brightness),
+                },
+                next,
+            ))
+        };
+
+        let mut per_pane = self.pane_state(pane_id);
+        let was_active = per_pane.was_active.replace(is_active);
+        if config.inactive_pane_fade_duration_ms > 0
+            && matches!(was_active, Some(was_active) if was_active != is_active)
+        {
+            // Start from wherever a fade that is still in progress got to
+            let prior = target(!is_active);
+            let from = per_pane
+                .dim_transition
+                .and_then(|(start, from)| fade(start, from, prior))
+                .map(|(hsb, _)| hsb)
+                .unwrap_or(prior);
+            per_pane.dim_transition.replace((Instant::now(), from));
+        }
+
+        if let Some((start, from)) = per_pane.dim_transition {
+            match fade(start, from, target(is_active)) {
+                Some((hsb, next)) => {
+                    self.update_next_frame_time(Some(next));
+                    return Some(hsb);
+                }
+                None => {
+                    per_pane.dim_transition.take();
+                }
+            }
+        }
+
+        if is_active {
+            None
+        } else {
+            Some(config.inactive_pane_hsb)
+        }
+    }
+
     fn get_intensity_if_bell_target_ringing(
         &self,
         pane: &Rc<dyn Pane>,
@@ -366,7 +505,7 @@ impl super::TermWindow {
     ) -> Option<f32> {
         let mut per_pane = self.pane_state(pane.pane_id());
         if let Some(ringing) = per_pane.bell_start {
-            if config.visual_bell.target == target {
+            if per_pane.bell_target.as_ref() == Some(&target) {
                 let mut color_ease = ColorEase::new(
                     config.visual_bell.fade_in_duration_ms,
                     config.visual_bell.fade_in_function,
@@ -459,12 +598,25 @@ impl super::TermWindow {
         fontconfig: &wezterm_font::FontConfiguration,
         render_metrics: &RenderMetrics,
     ) -> anyhow::Result<f32> {
-        if config.use_fancy_tab_bar {
+        let (cell_height, default_height, min_height) = if config.use_fancy_tab_bar {
             let font = fontconfig.title_font()?;
-            Ok((font.metrics().cell_height.get() as f32 * 1.75).ceil())
+            let cell_height = font.metrics().cell_height.get() as f32;
+            // Leave room for the borders of the tabs
+            (cell_height, (cell_height * 1.75).ceil(), cell_height + 2.)
         } else {
-            Ok(render_metrics.cell_size.height as f32)
-        }
+            let cell_height = render_metrics.cell_size.height as f32;
+            (cell_height, cell_height, cell_height)
+        };
+        Ok(match config.tab_bar_height {
+            Some(height) => height
+                .evaluate_as_pixels(DimensionContext {
+                    dpi: fontconfig.get_dpi() as f32,
+                    pixel_max: cell_height,
+                    pixel_cell: cell_height,
+                })
+                .max(min_height),
+            None => default_height,
+        })
     }
 
     pub fn tab_bar_pixel_height(&self) -> anyhow::Result<f32> {
@@ -488,8 +640,67 @@ impl super::TermWindow {
             .cloned()
             .unwrap_or_else(TabBarColors::default);
 
+        let cell_height = metrics.cell_size.height as f32;
+        // The tab bar keeps its original geometry unless its height
+        // or the padding of its tabs has been configured
+        let custom_geometry =
+            self.config.tab_bar_height.is_some() || self.config.tab_bar_vertical_padding.is_some();
+        // Moves the text so that its glyphs, rather than its cells,
+        // are centered vertically
+        let glyph_offset = if custom_geometry {
+            let font_metrics = font.metrics();
+            glyph_center_offset(
+                cell_height,
+                font_metrics.descender.get() as f32,
+                font_metrics.cap_height.map(|h| h.get() as f32),
+            )
+        } else {
+            0.
+        };
+        let (tab_margin_top, tab_padding_top, tab_padding_bottom, button_padding) =
+            if custom_geometry {
+                // The tabs are attached to the bottom of the tab bar, so the
+                // space above and below their titles is limited by its height
+                let tab_padding = self
+                    .config
+                    .tab_bar_vertical_padding
+                    .unwrap_or(Dimension::Cells(0.25))
+                    .evaluate_as_pixels(DimensionContext {
+                        dpi: self.dimensions.dpi as f32,
+                        pixel_max: tab_bar_height,
+                        pixel_cell: cell_height,
+                    })
+                    .min(((tab_bar_height - (cell_height + 2.)) / 2.).max(0.));
+                let tab_offset = glyph_offset.max(-tab_padding).min(tab_padding);
+                (
+                    Dimension::Cells(0.),
+                    Dimension::Pixels(tab_padding - tab_offset),
+                    Dimension::Pixels(tab_padding + tab_offset),
+                    (
+                        Dimension::Pixels(tab_padding),
+                        Dimension::Pixels(tab_padding),
+                    ),
+                )
+            } else {
+                (
+                    Dimension::Cells(0.2),
+                    Dimension::Cells(0.2),
+                    Dimension::Cells(0.25),
+                    (Dimension::Cells(0.2), Dimension::Cells(0.25)),
+                )
+            };
+
+        // With a custom geometry, each row of the status is laid out
+        // below the previous one
+        let (status_display, status_line_height) = if custom_geometry {
+            (DisplayType::Block, None)
+        } else {
+            (DisplayType::Inline, Some(1.75))
+        };
+
         let mut left_eles = vec![];
-        let mut right_eles = vec![];
+        let mut center_items = vec![];
+        let mut status_items = vec![];
 
         let item_to_elem = |item: &TabEntry| -> Element {
             let element = Element::with_line(&font, &item.title, palette);
@@ -514,7 +725,8 @@ impl super::TermWindow {
             match item.item {
                 TabBarItem::None => element
                     .item_type(UIItemType::TabBar(TabBarItem::None))
-                    .line_height(Some(1.75))
+                    .display(status_display)
+                    .line_height(status_line_height)
                     .margin(BoxDimension {
                         left: Dimension::Cells(0.),
                         right: Dimension::Cells(0.),
@@ -549,14 +761,14 @@ impl super::TermWindow {
                 .margin(BoxDimension {
                     left: Dimension::Cells(0.5),
                     right: Dimension::Cells(0.),
-                    top: Dimension::Cells(0.2),
+                    top: tab_margin_top,
                     bottom: Dimension::Cells(0.),
                 })
                 .padding(BoxDimension {
                     left: Dimension::Cells(0.5),
                     right: Dimension::Cells(0.5),
-                    top: Dimension::Cells(0.2),
-                    bottom: Dimension::Cells(0.25),
+                    top: button_padding.0,
+                    bottom: button_padding.1,
                 })
                 .border(BoxDimension::new(Dimension::Pixels(1.)))
                 .colors(ElementColors {
@@ -574,14 +786,14 @@ impl super::TermWindow {
                     .margin(BoxDimension {
                         left: Dimension::Cells(0.),
                         right: Dimension::Cells(0.),
-                        top: Dimension::Cells(0.2),
+                        top: tab_margin_top,
                         bottom: Dimension::Cells(0.),
                     })
                     .padding(BoxDimension {
                         left: Dimension::Cells(0.5),
                         right: Dimension::Cells(0.5),
-                        top: Dimension::Cells(0.2),
-                        bottom: Dimension::Cells(0.25),
+                        top: tab_padding_top,
+                        bottom: tab_padding_bottom,
                     })
                     .border(BoxDimension::new(Dimension::Pixels(1.)))
                     .border_corners(Some(Corners {
@@ -618,14 +830,14 @@ impl super::TermWindow {
                     .margin(BoxDimension {
                         left: Dimension::Cells(0.),
                         right: Dimension::Cells(0.),
-                        top: Dimension::Cells(0.2),
+                        top: tab_margin_top,
                         bottom: Dimension::Cells(0.),
                     })
                     .padding(BoxDimension {
                         left: Dimension::Cells(0.5),
                         right: Dimension::Cells(0.5),
-                        top: Dimension::Cells(0.2),
-                        bottom: Dimension::Cells(0.25),
+                        top: tab_padding_top,
+                        bottom: tab_padding_bottom,
                     })
                     .border(BoxDimension::new(Dimension::Pixels(1.)))
                     .border_corners(Some(Corners {
@@ -698,9 +910,47 @@ impl super::TermWindow {
             - (1.5 * metrics.cell_size.width as f32))
             .max(0.);
 
+        // The status is centered vertically, and spans as many of its
+        // rows as fit in the tab bar
+        let status_rows = if custom_geometry {
+            ((tab_bar_height - glyph_offset.abs()) / cell_height)
+                .floor()
+                .max(1.) as usize
+        } else {
+            1
+        };
+        let status_height = custom_geometry.then(|| Dimension::Pixels(tab_bar_height));
+        let status_to_elem = |items: Vec<&TabEntry>| -> Element {
+            Element::new(
+                &font,
+                ElementContent::Children(
+                    items
+                        .into_iter()
+                        .take(status_rows)
+                        .map(item_to_elem)
+                        .collect(),
+                ),
+            )
+            .vertical_align(VerticalAlign::Middle)
+            .padding(BoxDimension {
+                left: Dimension::Cells(0.),
+                right: Dimension::Cells(0.),
+                top: Dimension::Pixels((-glyph_offset).max(0.)),
+                bottom: Dimension::Pixels(glyph_offset.max(0.)),
+            })
+        };
+
         for item in items {
             match item.item {
-                TabBarItem::None => right_eles.push(item_to_elem(item)),
+                TabBarItem::None => match item.status {
+                    Some(StatusPosition::Left) => left_eles.push(status_to_elem(vec![item])),
+                    Some(StatusPosition::Center) => {
+                        if !item.title.cells().is_empty() {
+                            center_items.push(item);
+                        }
+                    }
+                    _ => status_items.push(item),
+                },
                 TabBarItem::Tab { tab_idx, active } => {
                     let mut elem = item_to_elem(item);
                     elem.max_width = Some(Dimension::Pixels(max_tab_width));
@@ -786,11 +1036,39 @@ impl super::TermWindow {
                 bottom: Dimension::Cells(0.),
             })
             .zindex(1);
-        let right_ele = Element::new(&font, ElementContent::Children(right_eles))
-            .colors(bar_colors.clone())
-            .float(Float::Right);
 
-        let content = ElementContent::Children(vec![left_ele, right_ele]);
+        let status_colors = ElementColors {
+            border: BorderColor::default(),
+            bg: rgbcolor_to_window_color(colors.inactive_tab.bg_color).into(),
+            text: rgbcolor_to_window_color(colors.inactive_tab.fg_color).into(),
+        };
+        let right_ele = Element::new(
+            &font,
+            ElementContent::Children(vec![status_to_elem(status_items)]),
+        )
+        .item_type(UIItemType::TabBar(TabBarItem::None))
+        .colors(status_colors.clone())
+        .min_height(status_height)
+        .float(Float::Right);
+
+        // The center status is laid out after the tabs, and is moved
+        // to the middle of the window once the layout is computed
+        let has_center = !center_items.is_empty();
+        let mut kids = vec![left_ele];
+        if has_center {
+            kids.push(
+                Element::new(
+                    &font,
+                    ElementContent::Children(vec![status_to_elem(center_items)]),
+                )
+                .item_type(UIItemType::TabBar(TabBarItem::None))
+                .colors(status_colors)
+                .min_height(status_height),
+            );
+        }
+        kids.push(right_ele);
+
+        let content = ElementContent::Children(kids);
 
         let tabs = Element::new(&font, content)
             .display(DisplayType::Block)
@@ -827,6 +1105,19 @@ impl super::TermWindow {
             &tabs,
         )?;
 
+        if has_center {
+            if let ComputedElementContent::Children(kids) = &mut computed.content {
+                let tabs_end = kids[0].bounds.max_x();
+                let right_start = kids[2].bounds.min_x();
+                let center = &mut kids[1];
+                let width = center.bounds.width();
+                let x = ((self.dimensions.pixel_width as f32 - width) / 2.)
+                    .min(right_start - width)
+                    .max(tabs_end);
+                center.translate(euclid::vec2(x - center.bounds.min_x(), 0.));
+            }
+        }
+
         computed.translate(euclid::vec2(
             0.,
             if self.config.tab_bar_at_bottom {
@@ -850,11 +1141,69 @@ impl super::TermWindow {
 
                 self.ui_items.append(&mut ui_items);
             }
+
+            // The text being composed is drawn in the modal
+            // rather than in the pane beneath it
+            let composing = match &self.dead_key_status {
+                DeadKeyStatus::Composing(composing) => Some(composing.clone()),
+                _ => None,
+            };
+            if let Some(composing) = composing {
+                if let Some(bounds) = modal.text_input_bounds(self) {
+                    self.paint_modal_preedit(&composing, bounds)?;
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Draws the text that is being composed by an input method just
+    /// after `bounds`, the place where the modal shows its input,
+    /// in the cursor colors as is done in a pane
+    fn paint_modal_preedit(&mut self, composing: &str, bounds: RectF) -> anyhow::Result<()> {
+        let font = self.fonts.pane_select_font()?;
+        let metrics = RenderMetrics::with_font_metrics(&font.metrics());
+        let palette = self.palette().clone();
+        let element = Element::new(&font, ElementContent::Text(composing.to_string()))
+            .vertical_align(VerticalAlign::Middle)
+            .colors(ElementColors {
+                border: BorderColor::default(),
+                bg: palette.cursor_bg.to_linear().into(),
+                text: palette.cursor_fg.to_linear().into(),
+            });
+
+        let dimensions = self.dimensions;
+        let computed = self.compute_element(
+            &LayoutContext {
+                height: DimensionContext {
+                    dpi: dimensions.dpi as f32,
+                    pixel_max: dimensions.pixel_height as f32,
+                    pixel_cell: metrics.cell_size.height as f32,
+                },
+                width: DimensionContext {
+                    dpi: dimensions.dpi as f32,
+                    pixel_max: dimensions.pixel_width as f32,
+                    pixel_cell: metrics.cell_size.width as f32,
+                },
+                bounds: euclid::rect(
+                    bounds.max_x(),
+                    bounds.min_y(),
+                    (dimensions.pixel_width as f32 - bounds.max_x()).max(0.),
+                    bounds.height(),
+                ),
+                metrics: &metrics,
+                gl_state: self.render_state.as_ref().unwrap(),
+                zindex: 101,
+            },
+            &element,
+        )?;
+
+        let gl_state = self.render_state.as_ref().unwrap();
+        self.render_element(&computed, gl_state, None)?;
+        Ok(())
+    }
+
     fn paint_fancy_tab_bar(&self) -> anyhow::Result<Vec<UIItem>> {
         let computed = self
             .fancy_tab_bar
@@ -898,10 +1247,17 @@ impl super::TermWindow {
             border.top.get() as f32
         };
 
+        // The line is centered vertically when the tab bar
+        // is taller than a cell
+        let line_y = tab_bar_y
+            + ((tab_bar_height - self.render_metrics.cell_size.height as f32) / 2.)
+                .max(0.)
+                .floor();
+
         // Register the tab bar location
         self.ui_items.append(&mut self.tab_bar.compute_ui_items(
             tab_bar_y as usize,
-            self.render_metrics.cell_size.height as usize,
+            tab_bar_height as usize,
             self.render_metrics.cell_size.width as usize,
         ));
 
@@ -930,9 +1286,28 @@ impl super::TermWindow {
             vb[1].map(&mut vb_mut1),
             vb[2].map(&mut vb_mut2),
         ];
+        if line_y > tab_bar_y {
+            let background = self
+                .config
+                .resolved_palette
+                .tab_bar
+                .as_ref()
+                .map(|colors| colors.background)
+                .unwrap_or_else(|| TabBarColors::default().background);
+            self.filled_rectangle(
+                &mut layers[0],
+                euclid::rect(
+                    0.,
+                    tab_bar_y,
+                    self.dimensions.pixel_width as f32,
+                    tab_bar_height,
+                ),
+                rgbcolor_to_window_color(background),
+            )?;
+        }
         self.render_screen_line_opengl(
             RenderScreenLineOpenGLParams {
-                top_pixel_y: tab_bar_y,
+                top_pixel_y: line_y,
                 left_pixel_x: 0.,
                 pixel_width: self.dimensions.pixel_width as f32,
                 stable_line_idx: None,
@@ -954,6 +1329,7 @@ impl super::TermWindow {
                 foreground: palette.foreground.to_linear(),
                 pane: None,
                 is_active: true,
+                hsv: None,
                 selection_fg: LinearRgba::default(),
                 selection_bg: LinearRgba::default(),
                 cursor_fg: LinearRgba::default(),
@@ -1059,10 +1435,13 @@ impl super::TermWindow {
         };
         */
 
-        let global_cursor_fg = self.palette().cursor_fg;
-        let global_cursor_bg = self.palette().cursor_bg;
+        let global_palette = self.palette().clone();
+        let global_palette = self.transition_palette(global_palette);
+        let global_cursor_fg = global_palette.cursor_fg;
+        let global_cursor_bg = global_palette.cursor_bg;
+        let pane_hsv = self.pane_hsv(pos.pane.pane_id(), pos.is_active);
         let config = &self.config;
-        let palette = pos.pane.palette();
+        let palette = self.transition_palette(pos.pane.palette());
 
         let (padding_left, padding_top) = self.padding_left_top();
 
@@ -1080,16 +1459,87 @@ impl super::TermWindow {
         let border = self.get_os_border();
         let top_pixel_y = top_bar_height + padding_top + border.top.get() as f32;
 
-        let cursor = pos.pane.get_cursor_position();
+        self.add_blur_region(pos, padding_left, top_pixel_y);
+
+        let mut cursor = pos.pane.get_cursor_position();
         if pos.is_active {
             self.prev_cursor.update(&cursor);
         }
 
         let current_viewport = self.get_viewport(pos.pane.pane_id());
-        let (stable_top, lines);
+        let (stable_rows, mut lines): (Vec<StableRowIndex>, Vec<Line>);
         let dims = pos.pane.get_dimensions();
+        let unwrapped_lines = self.unwrapped_lines(&pos.pane, &dims);
+
+        if let Some((h_offset, layouts)) = &unwrapped_lines {
+            // Each row shows the visible slice of a logical line: only
+            // the physical rows that overlap the slice are fetched, so
+            // the cost doesn't depend on the length of the line
+            let slice = *h_offset..h_offset + dims.cols;
+            let mut rows = vec![];
+            let mut fetched = vec![];
+            for layout in layouts {
+                let segments = layout.segments(slice.clone());
+                let mut line = Line::from_cells(vec![], termwiz::surface::SEQ_ZERO);
+                if let (Some((first, _)), Some((last, _))) = (segments.first(), segments.last()) {
+                    let (top, phys) = pos.pane.get_lines(*first..last + 1);
+                    if top == *first {
+                        for ((_row, cols), phys) in segments.iter().zip(phys.iter()) {
+                            let seqno = phys.current_seqno();
+                            line.append_line(phys.columns_as_line(cols.clone()), seqno);
+                        }
+                    }
+                }
+                line.scan_and_create_hyperlinks(&self.config.hyperlink_rules);
+                rows.push(layout.rows().start);
+                fetched.push(line);
+            }
+            stable_rows = rows;
+            lines = fetched;
+            cursor = unwrapped::slice_cursor(layouts, *h_offset, dims.cols, cursor);
+        } else if let Some(visible_rows) = self.visible_rows(&pos.pane, &dims) {
+            // Some output is folded: fetch each contiguous run of visible
+            // lines and synthesize the summary lines for the folds
+            let mut rows = vec![];
+            let mut fetched = vec![];
+            let mut run: Option<Range<StableRowIndex>> = None;
+
+            let fetch_run = |run: Range<StableRowIndex>, fetched: &mut Vec<Line>| {
+                let (_top, run_lines) = pos
+                    .pane
+                    .get_lines_with_hyperlinks_applied(run, &self.config.hyperlink_rules);
+                fetched.extend(run_lines);
+            };
 
-        {
+            for visible in visible_rows {
+                match visible {
+                    folding::VisibleRow::Line(row) => {
+                        run = Some(match run.take() {
+                            Some(r) if r.end == row => r.start..row + 1,
+                            Some(r) => {
+                                fetch_run(r, &mut fetched);
+                                row..row + 1
+                            }
+                            None => row..row + 1,
+                        });
+                        rows.push(row);
+                    }
+                    folding::VisibleRow::Fold(fold) => {
+                        if let Some(r) = run.take() {
+                            fetch_run(r, &mut fetched);
+                        }
+                        let (_top, first) = pos.pane.get_lines(fold.start..fold.start + 1);
+                        fetched.push(folding::fold_summary_line(&fold, first.first()));
+                        rows.push(fold.start);
+                    }
+                }
+            }
+            if let Some(r) = run.take() {
+                fetch_run(r, &mut fetched);
+            }
+            stable_rows = rows;
+            lines = fetched;
+        } else {
             let stable_range = match current_viewport {
                 Some(top) => top..top + dims.viewport_rows as StableRowIndex,
                 None => dims.physical_top..dims.physical_top + dims.viewport_rows as StableRowIndex,
@@ -1104,10 +1554,18 @@ impl super::TermWindow {
                 "get_lines_with_hyperlinks_applied took {:?}",
                 start.elapsed()
             );
-            stable_top = top;
+            stable_rows = (0..vp_lines.len())
+                .map(|idx| top + idx as StableRowIndex)
+                .collect();
             lines = vp_lines;
         }
 
+        if pos.pane.is_read_only() {
+            if let Some(line) = lines.first_mut() {
+                overlay_read_only_badge(line, dims.cols);
+            }
+        }
+
         let gl_state = self.render_state.as_ref().unwrap();
         let layer = gl_state.layer_for_zindex(0)?;
         let vbs = layer.vb.borrow();
@@ -1192,11 +1650,7 @@ impl super::TermWindow {
                     .to_linear()
                     .mul_alpha(config.window_background_opacity),
             )?;
-            quad.set_hsv(if pos.is_active {
-                None
-            } else {
-                Some(config.inactive_pane_hsb)
-            });
+            quad.set_hsv(pane_hsv);
         }
 
         {
@@ -1250,11 +1704,7 @@ impl super::TermWindow {
                     background,
                 )?;
 
-                quad.set_hsv(if pos.is_active {
-                    None
-                } else {
-                    Some(config.inactive_pane_hsb)
-                });
+                quad.set_hsv(pane_hsv);
             }
         }
 
@@ -1280,9 +1730,11 @@ impl super::TermWindow {
             let thumb_size = info.height;
             let color = palette.scrollbar_thumb.to_linear();
 
-            // Adjust the scrollbar thumb position
+            // Adjust the scrollbar thumb position; the minimap, if any,
+            // occupies the left part of the padding
             let config = &self.config;
-            let padding = self.effective_right_padding(&config) as f32;
+            let padding =
+                self.effective_right_padding(&config) as f32 - self.minimap_width() as f32;
 
             let thumb_x = self.dimensions.pixel_width - padding as usize - border.right.get();
 
@@ -1322,6 +1774,36 @@ impl super::TermWindow {
                 ),
                 color,
             )?;
+
+            let mut marks = self.paint_scrollbar_marks(
+                pos,
+                thumb_x,
+                thumb_y_offset,
+                padding as usize,
+                self.dimensions.pixel_height.saturating_sub(
+                    thumb_y_offset + border.bottom.get() + bottom_bar_height as usize,
+                ),
+                &mut layers,
+            )?;
+            self.ui_items.append(&mut marks);
+        }
+
+        if pos.is_active && self.config.enable_scrollback_minimap {
+            let minimap_y = top_bar_height as usize + border.top.get();
+            let minimap_x = self.dimensions.pixel_width
+                - self.effective_right_padding(&self.config)
+                - border.right.get();
+            let item = self.paint_minimap(
+                pos,
+                minimap_x,
+                minimap_y,
+                self.minimap_width(),
+                self.dimensions
+                    .pixel_height
+                    .saturating_sub(minimap_y + border.bottom.get() + bottom_bar_height as usize),
+                &mut layers,
+            )?;
+            self.ui_items.push(item);
         }
 
         let (selrange, rectangular) = {
@@ -1337,10 +1819,16 @@ impl super::TermWindow {
         let cursor_is_default_color =
             palette.cursor_fg == global_cursor_fg && palette.cursor_bg == global_cursor_bg;
 
-        for (line_idx, line) in lines.iter().enumerate() {
-            let stable_row = stable_top + line_idx as StableRowIndex;
-
-            let selrange = selrange.map_or(0..0, |sel| sel.cols_for_row(stable_row, rectangular));
+        for (line_idx, (line, &stable_row)) in lines.iter().zip(stable_rows.iter()).enumerate() {
+            let selrange = match (&selrange, &unwrapped_lines) {
+                (Some(sel), Some((h_offset, layouts))) => {
+                    layouts[line_idx].slice_selection(*h_offset, dims.cols, |row| {
+                        sel.cols_for_row(row, rectangular)
+                    })
+                }
+                (Some(sel), None) => sel.cols_for_row(stable_row, rectangular),
+                (None, _) => 0..0,
+            };
             // Constrain to the pane width!
             let selrange = selrange.start..selrange.end.min(dims.cols);
 
@@ -1361,6 +1849,7 @@ impl super::TermWindow {
                     cursor_border_color,
                     foreground,
                     is_active: pos.is_active,
+                    hsv: pane_hsv,
                     pane: Some(&pos.pane),
                     selection_fg,
                     selection_bg,
@@ -1395,7 +1884,84 @@ impl super::TermWindow {
         Ok(())
     }
 
-    fn call_draw(&mut self, frame: &mut glium::Frame) -> anyhow::Result<()> {
+    /// Renders the frame into a texture, and then draws that texture
+    /// into the window using the `post_processing_shader`
+    fn call_draw_post_process(
+        &mut self,
+        frame: &mut glium::Frame,
+        post_process: &PostProcess,
+    ) -> anyhow::Result<()> {
+        let width = self.dimensions.pixel_width as u32;
+        let height = self.dimensions.pixel_height as u32;
+        let context = Rc::clone(&self.render_state.as_ref().unwrap().context);
+        let texture = post_process.texture(&context, width, height)?;
+        {
+            let mut target = glium::framebuffer::SimpleFrameBuffer::new(&context, &*texture)?;
+            target.clear_color(0., 0., 0., 0.);
+            self.call_draw(&mut target)?;
+        }
+
+        let frame_texture = Sampler::new(&*texture)
+            .wrap_function(SamplerWrapFunction::Clamp)
+            .magnify_filter(MagnifySamplerFilter::Linear)
+            .minify_filter(MinifySamplerFilter::Linear);
+
+        frame.draw(
+            &post_process.vertices,
+            glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip),
+            &post_process.prog,
+            &uniform! {
+                frame_texture: frame_texture,
+                resolution: [width as f32, height as f32],
+                time: post_process.elapsed(),
+            },
+            &Default::default(),
+        )?;
+
+        if post_process.is_animated() {
+            let fps = self.config.animation_fps.max(1) as u64;
+            self.update_next_frame_time(Some(
+                Instant::now() + std::time::Duration::from_millis(1000 / fps),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn call_draw<S: Surface>(&mut self, frame: &mut S) -> anyhow::Result<()> {
+        // The window background layers have negative zindex values
+        let is_backdrop = |zindex: i8| zindex < 0;
+        let blur = if self.blur_regions.borrow().is_empty() {
+            None
+        } else {
+            match self.render_backdrop_for_blur(is_backdrop) {
+                Ok(blur) => Some(blur),
+                Err(err) => {
+                    log::error!("pane_background_blur: {:#}", err);
+                    None
+                }
+            }
+        };
+
+        self.draw_layers(frame, is_backdrop, true)?;
+        if let Some(blur) = blur {
+            if let Err(err) = self.draw_backdrop_blur(frame, &blur) {
+                log::error!("pane_background_blur: {:#}", err);
+            }
+        }
+        self.draw_layers(frame, |zindex| !is_backdrop(zindex), true)
+    }
+
+    /// Draws the layers whose zindex is accepted by `filter`.
+    /// Unless `advance` is false, the vertex buffers of the layers
+    /// are advanced to the next buffer afterwards, so each layer must
+    /// be drawn with `advance` set exactly once per frame.
+    pub fn draw_layers<S: Surface>(
+        &self,
+        frame: &mut S,
+        filter: impl Fn(i8) -> bool,
+        advance: bool,
+    ) -> anyhow::Result<()> {
         let gl_state = self.render_state.as_ref().unwrap();
         let tex = gl_state.glyph_cache.borrow().atlas.texture();
         let projection = euclid::Transform3D::<f32, f32, f32>::ortho(
@@ -1460,6 +2026,9 @@ impl super::TermWindow {
         );
 
         for layer in gl_state.layers.borrow().iter() {
+            if !filter(layer.zindex) {
+                continue;
+            }
             for idx in 0..3 {
                 let vb = &layer.vb.borrow()[idx];
                 let (vertex_count, index_count) = vb.vertex_index_count();
@@ -1486,7 +2055,9 @@ impl super::TermWindow {
                     )?;
                 }
 
-                vb.next_index();
+                if advance {
+                    vb.next_index();
+                }
             }
         }
 
@@ -1510,7 +2081,7 @@ impl super::TermWindow {
             .window_padding
             .left
             .evaluate_as_pixels(h_context);
-        let padding_top = self.config.window_padding.top.evaluate_as_pixels(v_context);
+        let padding_top = super::resize::effective_top_padding(&self.config, v_context) as f32;
 
         (padding_left, padding_top)
     }
@@ -1525,7 +2096,7 @@ impl super::TermWindow {
         let vb = &layer.vb.borrow()[2];
         let mut vb_mut = vb.current_vb_mut();
         let mut quads = vb.map(&mut vb_mut);
-        let palette = pane.palette();
+        let palette = self.transition_palette(pane.palette());
         let foreground = palette.split.to_linear();
         let cell_width = self.render_metrics.cell_size.width as f32;
         let cell_height = self.render_metrics.cell_size.height as f32;
@@ -1596,6 +2167,7 @@ impl super::TermWindow {
 
         // Clear out UI item positions; we'll rebuild these as we render
         self.ui_items.clear();
+        self.blur_regions.borrow_mut().clear();
 
         let panes = self.get_panes_to_render();
         let num_panes = panes.len();
@@ -1606,7 +2178,8 @@ impl super::TermWindow {
         // Render the full window background
         match (self.window_background.is_empty(), self.allow_images) {
             (false, true) => {
-                let bg_color = self.palette().background.to_linear();
+                let palette = self.palette().clone();
+                let bg_color = self.transition_palette(palette).background.to_linear();
 
                 let top = panes
                     .iter()
@@ -1626,15 +2199,18 @@ impl super::TermWindow {
             }
             _ => {
                 // Regular window background color
-                let background = if panes.len() == 1 {
+                let palette = if panes.len() == 1 {
                     // If we're the only pane, use the pane's palette
                     // to draw the padding background
-                    panes[0].pane.palette().background
+                    panes[0].pane.palette()
                 } else {
-                    self.palette().background
-                }
-                .to_linear()
-                .mul_alpha(self.config.window_background_opacity);
+                    self.palette().clone()
+                };
+                let background = self
+                    .transition_palette(palette)
+                    .background
+                    .to_linear()
+                    .mul_alpha(self.config.window_background_opacity);
 
                 let gl_state = self.render_state.as_ref().unwrap();
                 let render_layer = gl_state.layer_for_zindex(0)?;
@@ -1655,9 +2231,13 @@ impl super::TermWindow {
             }
         }
 
-        for pos in panes {
+        for pos in &panes {
+            if pos.is_collapsed {
+                self.paint_stacked_pane_title(pos)?;
+                continue;
+            }
             if pos.is_active {
-                self.update_text_cursor(&pos);
+                self.update_text_cursor(pos);
                 if focused {
                     pos.pane.advise_focus();
                     mux::Mux::get()
@@ -1665,7 +2245,7 @@ impl super::TermWindow {
                         .record_focus_for_current_identity(pos.pane.pane_id());
                 }
             }
-            self.paint_pane_opengl(&pos, num_panes)?;
+            self.paint_pane_opengl(pos, num_panes)?;
         }
 
         if let Some(pane) = self.get_active_pane_or_overlay() {
@@ -1675,10 +2255,26 @@ impl super::TermWindow {
             }
         }
 
+        // The titles are painted over the splits, and take precedence
+        // over them for mouse clicks.  Stacked panes show their titles
+        // in their collapsed title bars instead.
+        if self.config.pane_border_titles && !panes.iter().any(|pos| pos.is_collapsed) {
+            for pos in &panes {
+                self.paint_pane_border_title(pos)?;
+            }
+        }
+
+        self.paint_decorations(&panes)?;
+
         if self.show_tab_bar {
             self.paint_tab_bar()?;
         }
 
+        self.paint_hyperlink_preview()?;
+        self.paint_image_preview()?;
+        self.paint_resize_badge()?;
+        self.paint_scrollbar_mark_tooltip()?;
+        self.paint_key_hints()?;
         self.paint_modal()?;
         self.paint_window_borders()?;
 
@@ -1835,11 +2431,7 @@ impl super::TermWindow {
 
         let num_cols = params.dims.cols;
 
-        let hsv = if params.is_active {
-            None
-        } else {
-            Some(params.config.inactive_pane_hsb)
-        };
+        let hsv = params.hsv;
 
         let width_scale = if !params.line.is_single_width() {
             2.0
@@ -1878,8 +2470,9 @@ impl super::TermWindow {
             None
         };
 
-        // Referencing the text being composed, but only if it belongs to this pane
-        let composing = if cursor_idx.is_some() {
+        // Referencing the text being composed, but only if it belongs to this pane.
+        // While a modal is active, the text is composed for the modal instead.
+        let composing = if cursor_idx.is_some() && self.modal.borrow().is_none() {
             if let DeadKeyStatus::Composing(composing) = &self.dead_key_status {
                 Some(composing)
             } else {
@@ -1935,6 +2528,32 @@ impl super::TermWindow {
         let cursor_range_pixels = params.left_pixel_x + cursor_range.start as f32 * cell_width
             ..params.left_pixel_x + cursor_range.end as f32 * cell_width;
 
+        // Break ligatures at the edges of the cursor and selection so
+        // that the characters that they cover can be drawn individually
+        let mut ligature_boundaries = vec![];
+        if params.config.ligature_splitting != LigatureSplitting::Never && !cursor_range.is_empty()
+        {
+            ligature_boundaries.push(cursor_range.start);
+            ligature_boundaries.push(cursor_range.end);
+        }
+        if params.config.ligature_splitting == LigatureSplitting::CursorAndSelection
+            && !params.selection.is_empty()
+        {
+            ligature_boundaries.push(params.selection.start);
+            ligature_boundaries.push(params.selection.end);
+        }
+        let split_clusters: Vec<CellCluster>;
+        let to_shape = if ligature_boundaries.is_empty() {
+            to_shape
+        } else {
+            split_clusters = to_shape
+                .iter()
+                .cloned()
+                .flat_map(|cluster| cluster.split_at_cells(&ligature_boundaries))
+                .collect();
+            &split_clusters
+        };
+
         let shaped = self.cluster_and_shape(&to_shape, &params)?;
 
         let bounding_rect = euclid::rect(
@@ -2858,15 +3477,43 @@ impl super::TermWindow {
         }
     }
 
+    pub fn glyph_atlas_stats(&self) -> Option<GlyphAtlasStats> {
+        self.render_state
+            .as_ref()
+            .map(|gl_state| gl_state.glyph_atlas_stats())
+    }
+
     pub fn recreate_texture_atlas(&mut self, size: Option<usize>) -> anyhow::Result<()> {
         self.shape_cache.borrow_mut().clear();
         if let Some(render_state) = self.render_state.as_mut() {
+            let current_size = render_state.glyph_cache.borrow().atlas.size();
             render_state.recreate_texture_atlas(&self.fonts, &self.render_metrics, size)?;
+            // The prewarmed glyphs are not restored when the atlas is
+            // merely cleared to make room, as they would just fill it
+            // up again
+            if size.map(|size| size > current_size).unwrap_or(true) {
+                self.prewarm_glyphs_needed = true;
+            }
         }
         Ok(())
     }
 }
 
+/// Returns the distance, in pixels, that a row of text needs to be
+/// moved up so that its glyphs appear vertically centered in the row.
+/// The glyphs of most text lie between the baseline and the cap height,
+/// which is usually lower than the middle of the cell.
+/// Returns 0 if the font doesn't specify its cap height.
+fn glyph_center_offset(cell_height: f32, descender: f32, cap_height: Option<f32>) -> f32 {
+    match cap_height {
+        Some(cap_height) => {
+            let baseline = cell_height + descender;
+            (baseline - cap_height / 2. - cell_height / 2.).round()
+        }
+        None => 0.,
+    }
+}
+
 pub fn rgbcolor_to_window_color(color: RgbColor) -> LinearRgba {
     rgbcolor_alpha_to_window_color(color, 1.0)
 }
@@ -2909,3 +3556,13 @@ fn resolve_fg_color_attr(
     }
     .to_linear()
 }
+
+/// Right aligns a badge in the top row of a read-only pane
+fn overlay_read_only_badge(line: &mut Line, cols: usize) {
+    let badge = "READ-ONLY";
+    let col = cols.saturating_sub(unicode_column_width(badge, None));
+    let mut attr = CellAttributes::default();
+    attr.set_foreground(AnsiColor::White);
+    attr.set_background(AnsiColor::Maroon);
+    line.overlay_text_with_attribute(col, badge, attr, termwiz::surface::SEQ_ZERO);
+}
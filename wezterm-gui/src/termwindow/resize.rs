@@ -1,8 +1,12 @@
+use crate::termwindow::TermWindowNotif;
 use crate::utilsprites::RenderMetrics;
-use ::window::{Dimensions, Window, WindowOps, WindowState};
-use config::{ConfigHandle, DimensionContext};
+use ::window::{Dimensions, ResizeIncrement, ScreenPoint, Window, WindowOps, WindowState};
+use config::{ConfigHandle, DimensionContext, EasingFunction, WindowPresetSize, WindowSizePreset};
+use mux::pane::PaneId;
 use mux::Mux;
+use smol::Timer;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 use wezterm_font::FontConfiguration;
 use wezterm_term::TerminalSize;
 
@@ -12,6 +16,30 @@ pub struct RowsAndCols {
     pub cols: usize,
 }
 
+/// Tracks an in-progress transition from one font scale to another
+#[derive(Debug, Clone, Copy)]
+pub struct FontScaleAnimation {
+    from: f64,
+    to: f64,
+    start: Instant,
+    duration: Duration,
+    ease: EasingFunction,
+}
+
+impl FontScaleAnimation {
+    /// Returns the scale for the current point in time, or None
+    /// if the animation has completed
+    fn current_scale(&self) -> Option<f64> {
+        let elapsed = self.start.elapsed();
+        if elapsed >= self.duration {
+            return None;
+        }
+        let position = elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        let progress = self.ease.evaluate_at_position(position) as f64;
+        Some(self.from + (self.to - self.from) * progress)
+    }
+}
+
 impl super::TermWindow {
     pub fn resize(
         &mut self,
@@ -28,6 +56,13 @@ impl super::TermWindow {
             dimensions,
             window_state,
         );
+        if window_state.contains(WindowState::HIDDEN)
+            && !self.window_state.contains(WindowState::HIDDEN)
+            && self.config.enable_tray_icon
+            && self.config.minimize_to_tray
+        {
+            crate::tray::hide_window(window);
+        }
         if dimensions.pixel_width == 0 || dimensions.pixel_height == 0 {
             // on windows, this can happen when minimizing the window.
             // NOP!
@@ -39,6 +74,9 @@ impl super::TermWindow {
             log::trace!("dimensions didn't change NOP!");
             return;
         }
+        let overlay_view_state = self.save_overlay_view_state();
+        let prior_size = self.terminal_size;
+        let prior_dpi = self.dimensions.dpi;
         let last_state = self.window_state;
         self.window_state = window_state;
         if last_state != self.window_state {
@@ -51,9 +89,19 @@ impl super::TermWindow {
         } else {
             self.scaling_changed(dimensions, self.fonts.get_font_scale(), window);
         }
+        self.restore_overlay_view_state(overlay_view_state);
         if let Some(modal) = self.get_modal() {
             modal.reconfigure(self);
         }
+        // Only the user resizing the window shows the badge, rather
+        // than changes of the font size or of the window state
+        if last_state == self.window_state
+            && prior_dpi == dimensions.dpi
+            && (prior_size.rows, prior_size.cols)
+                != (self.terminal_size.rows, self.terminal_size.cols)
+        {
+            self.show_resize_badge();
+        }
         self.emit_window_event("window-resized", None);
     }
 
@@ -95,18 +143,7 @@ impl super::TermWindow {
             }
         }
 
-        window.set_resize_increments(
-            if self.config.use_resize_increments {
-                self.render_metrics.cell_size.width as u16
-            } else {
-                1
-            },
-            if self.config.use_resize_increments {
-                self.render_metrics.cell_size.height as u16
-            } else {
-                1
-            },
-        );
+        self.update_resize_increments(window);
 
         if let Err(err) = self.recreate_texture_atlas(None) {
             log::error!("recreate_texture_atlas: {:#}", err);
@@ -182,7 +219,7 @@ impl super::TermWindow {
                 pixel_cell: self.render_metrics.cell_size.height as f32,
             };
             let padding_left = config.window_padding.left.evaluate_as_pixels(h_context) as usize;
-            let padding_top = config.window_padding.top.evaluate_as_pixels(v_context) as usize;
+            let padding_top = effective_top_padding(&config, v_context);
             let padding_bottom =
                 config.window_padding.bottom.evaluate_as_pixels(v_context) as usize;
             let padding_right = effective_right_padding(&config, h_context);
@@ -215,7 +252,7 @@ impl super::TermWindow {
                 pixel_cell: self.render_metrics.cell_size.height as f32,
             };
             let padding_left = config.window_padding.left.evaluate_as_pixels(h_context) as usize;
-            let padding_top = config.window_padding.top.evaluate_as_pixels(v_context) as usize;
+            let padding_top = effective_top_padding(&config, v_context);
             let padding_bottom =
                 config.window_padding.bottom.evaluate_as_pixels(v_context) as usize;
             let padding_right = effective_right_padding(&config, h_context);
@@ -263,6 +300,7 @@ impl super::TermWindow {
         self.resize_overlays();
         self.invalidate_fancy_tab_bar();
         self.update_title();
+        self.update_corner_radius(window);
 
         // Queue up a speculative resize in order to preserve the number of rows+cols
         if let Some(cell_dims) = scale_changed_cells {
@@ -385,23 +423,125 @@ impl super::TermWindow {
     }
 
     pub fn decrease_font_size(&mut self, window: &Window) {
-        self.adjust_font_scale(self.fonts.get_font_scale() / 1.1, window);
+        self.set_window_font_scale(self.window_font_scale / 1.1, window);
     }
 
     pub fn increase_font_size(&mut self, window: &Window) {
-        self.adjust_font_scale(self.fonts.get_font_scale() * 1.1, window);
+        self.set_window_font_scale(self.window_font_scale * 1.1, window);
     }
 
     pub fn reset_font_size(&mut self, window: &Window) {
-        self.adjust_font_scale(1.0, window);
+        self.set_window_font_scale(1.0, window);
+    }
+
+    /// Returns the font scale that the window should be using; this
+    /// is the per-window scale multiplied by the scale of the
+    /// active pane, if it has one.
+    pub fn effective_font_scale(&self) -> f64 {
+        let pane_scale = self
+            .get_active_pane_no_overlay()
+            .and_then(|pane| self.pane_state(pane.pane_id()).font_scale)
+            .unwrap_or(1.0);
+        self.window_font_scale * pane_scale
+    }
+
+    /// Sets the per-window font scale.  Unlike the font_size
+    /// configuration, this survives a configuration reload.
+    pub fn set_window_font_scale(&mut self, font_scale: f64, window: &Window) {
+        self.window_font_scale = font_scale;
+        self.update_font_scale(window);
+    }
+
+    /// Sets (or clears, when None) the font scale for a pane.
+    /// All of the panes in a window share the same font metrics,
+    /// so the pane scale is applied to the window while that
+    /// pane is the active pane.
+    pub fn set_pane_font_scale(
+        &mut self,
+        pane_id: PaneId,
+        font_scale: Option<f64>,
+        window: &Window,
+    ) {
+        self.pane_state(pane_id).font_scale = font_scale;
+        self.update_font_scale(window);
+    }
+
+    /// Transition the font scale toward the effective_font_scale,
+    /// animating the change if font_scale_animation_duration is set.
+    pub fn update_font_scale(&mut self, window: &Window) {
+        let target = self.effective_font_scale();
+        let current = match self.font_scale_animation.as_ref() {
+            Some(anim) if anim.to == target => return,
+            Some(_) => self.fonts.get_font_scale(),
+            None if self.fonts.get_font_scale() == target => return,
+            None => self.fonts.get_font_scale(),
+        };
+
+        let duration = Duration::from_millis(self.config.font_scale_animation_duration);
+        if duration.as_millis() == 0 {
+            self.font_scale_animation.take();
+            self.adjust_font_scale(target, window);
+            return;
+        }
+
+        self.font_scale_animation.replace(FontScaleAnimation {
+            from: current,
+            to: target,
+            start: Instant::now(),
+            duration,
+            ease: self.config.font_scale_animation_ease,
+        });
+        self.step_font_scale_animation(window);
+    }
+
+    fn step_font_scale_animation(&mut self, window: &Window) {
+        let anim = match self.font_scale_animation {
+            Some(anim) => anim,
+            None => return,
+        };
+
+        match anim.current_scale() {
+            Some(scale) => {
+                self.adjust_font_scale(scale, window);
+
+                let fps = self.config.animation_fps.max(1) as u64;
+                let next = Instant::now() + Duration::from_millis(1000 / fps);
+                let window = window.clone();
+                promise::spawn::spawn(async move {
+                    Timer::at(next).await;
+                    window.notify(TermWindowNotif::Apply(Box::new(|tw| {
+                        if let Some(window) = tw.window.clone() {
+                            tw.step_font_scale_animation(&window);
+                        }
+                    })));
+                })
+                .detach();
+            }
+            None => {
+                self.font_scale_animation.take();
+                self.adjust_font_scale(anim.to, window);
+            }
+        }
     }
 
     pub fn set_window_size(&mut self, size: TerminalSize, window: &Window) -> anyhow::Result<()> {
+        self.set_window_size_and_font_scale(size, 1.0, window)
+    }
+
+    /// Resizes the window to fit `size` using the font metrics
+    /// for `font_scale`
+    fn set_window_size_and_font_scale(
+        &mut self,
+        size: TerminalSize,
+        font_scale: f64,
+        window: &Window,
+    ) -> anyhow::Result<()> {
         let config = &self.config;
         let fontconfig = Rc::new(FontConfiguration::new(
             Some(config.clone()),
             self.dimensions.dpi,
         )?);
+        fontconfig.change_scaling(font_scale, self.dimensions.dpi);
         let render_metrics = RenderMetrics::new(&fontconfig)?;
 
         let terminal_size = TerminalSize {
@@ -430,7 +570,7 @@ impl super::TermWindow {
             pixel_cell: render_metrics.cell_size.height as f32,
         };
         let padding_left = config.window_padding.left.evaluate_as_pixels(h_context) as usize;
-        let padding_top = config.window_padding.top.evaluate_as_pixels(v_context) as usize;
+        let padding_top = effective_top_padding(&config, v_context);
         let padding_bottom = config.window_padding.bottom.evaluate_as_pixels(v_context) as usize;
 
         let dimensions = Dimensions {
@@ -444,7 +584,7 @@ impl super::TermWindow {
             dpi: self.dimensions.dpi,
         };
 
-        self.apply_scale_change(&dimensions, 1.0, window);
+        self.apply_scale_change(&dimensions, font_scale, window);
         self.apply_dimensions(
             &dimensions,
             Some(RowsAndCols {
@@ -456,11 +596,112 @@ impl super::TermWindow {
         Ok(())
     }
 
+    /// Applies the font scale, size and position of a window size
+    /// preset together, so that the window goes straight to its
+    /// final state rather than passing through intermediate sizes
+    pub fn apply_window_size_preset(
+        &mut self,
+        preset: &WindowSizePreset,
+        window: &Window,
+    ) -> anyhow::Result<()> {
+        let size = preset.size()?;
+        if self
+            .window_state
+            .intersects(WindowState::MAXIMIZED | WindowState::FULL_SCREEN)
+        {
+            if self.window_state.contains(WindowState::FULL_SCREEN) {
+                window.toggle_fullscreen();
+            }
+            window.restore();
+        }
+
+        self.font_scale_animation.take();
+        if let Some(font_scale) = preset.font_scale {
+            self.window_font_scale = font_scale;
+        }
+        let font_scale = self.effective_font_scale();
+
+        match size {
+            WindowPresetSize::Cells { cols, rows } => {
+                let size = TerminalSize {
+                    rows,
+                    cols,
+                    pixel_width: 0,
+                    pixel_height: 0,
+                    dpi: self.dimensions.dpi as u32,
+                };
+                self.set_window_size_and_font_scale(size, font_scale, window)?;
+            }
+            WindowPresetSize::Pixels { width, height } => {
+                let dimensions = self.dimensions;
+                self.apply_scale_change(&dimensions, font_scale, window);
+                window.set_inner_size(width, height);
+            }
+        }
+
+        if let Some(position) = preset.position {
+            window.set_window_position(ScreenPoint::new(position.x, position.y));
+        }
+        Ok(())
+    }
+
     pub fn reset_font_and_window_size(&mut self, window: &Window) -> anyhow::Result<()> {
+        self.window_font_scale = 1.0;
+        self.font_scale_animation.take();
         let size = self.config.initial_size(self.dimensions.dpi as u32);
         self.set_window_size(size, window)
     }
 
+    /// Asks the desktop environment to constrain interactive resizes of
+    /// the window to whole cells when `use_resize_increments` is enabled.
+    /// The padding, borders and tab bar make up the base size, so that
+    /// the cells fill the space between them exactly.
+    pub fn update_resize_increments(&self, window: &Window) {
+        if !self.config.use_resize_increments {
+            window.set_resize_increments(ResizeIncrement::disabled());
+            return;
+        }
+
+        let h_context = DimensionContext {
+            dpi: self.dimensions.dpi as f32,
+            pixel_max: self.terminal_size.pixel_width as f32,
+            pixel_cell: self.render_metrics.cell_size.width as f32,
+        };
+        let v_context = DimensionContext {
+            dpi: self.dimensions.dpi as f32,
+            pixel_max: self.terminal_size.pixel_height as f32,
+            pixel_cell: self.render_metrics.cell_size.height as f32,
+        };
+        let padding_left = self
+            .config
+            .window_padding
+            .left
+            .evaluate_as_pixels(h_context) as usize;
+        let padding_right = effective_right_padding(&self.config, h_context);
+        let padding_top = effective_top_padding(&self.config, v_context);
+        let padding_bottom = self
+            .config
+            .window_padding
+            .bottom
+            .evaluate_as_pixels(v_context) as usize;
+        let tab_bar_height = if self.show_tab_bar {
+            self.tab_bar_pixel_height().unwrap_or(0.) as usize
+        } else {
+            0
+        };
+        let border = self.get_os_border();
+
+        window.set_resize_increments(ResizeIncrement {
+            x: self.render_metrics.cell_size.width as u16,
+            y: self.render_metrics.cell_size.height as u16,
+            base_width: (padding_left + padding_right + (border.left + border.right).get()) as u16,
+            base_height: (padding_top
+                + padding_bottom
+                + tab_bar_height
+                + (border.top + border.bottom).get()) as u16,
+        });
+    }
+
     pub fn effective_right_padding(&self, config: &ConfigHandle) -> usize {
         effective_right_padding(
             config,
@@ -473,14 +714,28 @@ impl super::TermWindow {
     }
 }
 
+/// Computes the effective padding for the top of the window, which
+/// includes the row that holds the titles of the topmost panes when
+/// pane_border_titles is enabled.
+pub fn effective_top_padding(config: &ConfigHandle, context: DimensionContext) -> usize {
+    let padding = config.window_padding.top.evaluate_as_pixels(context) as usize;
+    if config.pane_border_titles {
+        padding + context.pixel_cell as usize
+    } else {
+        padding
+    }
+}
+
 /// Computes the effective padding for the RHS.
 /// This is needed because the default is 0, but if the user has
 /// enabled the scroll bar then they will expect it to have a reasonable
 /// size unless they've specified differently.
+/// The minimap, if enabled, is placed to the left of the scroll bar.
 pub fn effective_right_padding(config: &ConfigHandle, context: DimensionContext) -> usize {
-    if config.enable_scroll_bar && config.window_padding.right.is_zero() {
+    let padding = if config.enable_scroll_bar && config.window_padding.right.is_zero() {
         context.pixel_cell as usize
     } else {
         config.window_padding.right.evaluate_as_pixels(context) as usize
-    }
+    };
+    padding + super::minimap::minimap_width(config, context)
 }
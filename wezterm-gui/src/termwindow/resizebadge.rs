@@ -0,0 +1,46 @@
+//! Shows the size of the terminal in columns and rows in the middle of
+//! the window while the user resizes it, so that a particular size can
+//! be dragged to.  Together with `use_resize_increments`, which snaps
+//! the window to whole cells, this makes it easy to size the window
+//! precisely.  The badge is shown until a short time after the last
+//! change of size, and can be turned off with `show_resize_badge`.
+use crate::termwindow::TermWindow;
+use std::time::{Duration, Instant};
+
+/// How long the badge remains after the size last changed
+const BADGE_DURATION: Duration = Duration::from_millis(1000);
+
+impl TermWindow {
+    pub fn show_resize_badge(&mut self) {
+        if self.config.show_resize_badge {
+            self.resize_badge_until = Some(Instant::now() + BADGE_DURATION);
+        }
+    }
+
+    /// Paints the size of the terminal in the middle of the window
+    pub fn paint_resize_badge(&mut self) -> anyhow::Result<()> {
+        let until = match self.resize_badge_until {
+            Some(until) if until > Instant::now() => until,
+            Some(_) => {
+                self.resize_badge_until.take();
+                return Ok(());
+            }
+            None => return Ok(()),
+        };
+        // Repaint once it is due to disappear
+        self.update_next_frame_time(Some(until));
+
+        let text = format!("{}×{}", self.terminal_size.cols, self.terminal_size.rows);
+        let mut computed = self.compute_hover_text(&text)?;
+        let bounds = computed.bounds;
+        computed.translate(euclid::vec2(
+            (self.dimensions.pixel_width as f32 - bounds.width()) / 2. - bounds.min_x(),
+            (self.dimensions.pixel_height as f32 - bounds.height()) / 2. - bounds.min_y(),
+        ));
+
+        let gl_state = self.render_state.as_ref().unwrap();
+        self.render_element(&computed, gl_state, None)?;
+
+        Ok(())
+    }
+}
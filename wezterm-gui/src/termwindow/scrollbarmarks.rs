@@ -0,0 +1,335 @@
+//! Draws marks in the scrollbar that show where the shell prompts,
+//! the lines that match `scrollbar_error_patterns` and the search
+//! matches are in the scrollback, along with any marks that were
+//! added by lua using `pane:set_scrollbar_marks`.
+//! Hovering over a mark shows a tooltip and clicking it scrolls the
+//! marked line into view.
+use crate::overlay::CopyOverlay;
+use crate::renderstate::MappedQuads;
+use crate::termwindow::{TermWindow, UIItem, UIItemType};
+use config::RgbaColor;
+use luahelper::impl_lua_conversion_dynamic;
+use mux::pane::{Pane, PaneId};
+use mux::tab::PositionedPane;
+use mux::Mux;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::rc::Rc;
+use termwiz::color::SrgbaTuple;
+use termwiz::surface::SequenceNo;
+use wezterm_dynamic::{FromDynamic, ToDynamic};
+use wezterm_term::{SemanticType, StableRowIndex};
+use window::color::LinearRgba;
+use window::WindowOps;
+
+/// The height of a mark, in pixels at 96 dpi
+const MARK_HEIGHT: usize = 2;
+
+/// A mark that is added by lua using `pane:set_scrollbar_marks`
+#[derive(Debug, Clone, FromDynamic, ToDynamic)]
+pub struct ScrollbarMark {
+    pub row: StableRowIndex,
+    #[dynamic(default)]
+    pub color: Option<RgbaColor>,
+    #[dynamic(default)]
+    pub tooltip: Option<String>,
+}
+impl_lua_conversion_dynamic!(ScrollbarMark);
+
+/// Holds the marks for a pane.  The rows that match the error
+/// patterns are cached so that only the lines that have changed
+/// need to be examined when the scrollbar is drawn.
+#[derive(Default)]
+pub struct PaneScrollbarMarks {
+    seqno: SequenceNo,
+    alt_screen: bool,
+    prompts: Vec<StableRowIndex>,
+    patterns: Vec<String>,
+    regexes: Vec<Regex>,
+    /// The rows before this one have been matched against `regexes`
+    scanned_end: StableRowIndex,
+    errors: BTreeSet<StableRowIndex>,
+    /// The marks added by lua, keyed by the name of their source
+    lua_marks: BTreeMap<String, Vec<ScrollbarMark>>,
+}
+
+impl PaneScrollbarMarks {
+    fn update(&mut self, pane: &Rc<dyn Pane>, patterns: &[String]) {
+        let seqno = pane.get_current_seqno();
+        let alt_screen = pane.is_alt_screen_active();
+        let dims = pane.get_dimensions();
+        let end = dims.scrollback_top + dims.scrollback_rows as StableRowIndex;
+
+        if patterns != self.patterns.as_slice() {
+            self.patterns = patterns.to_vec();
+            self.regexes = patterns
+                .iter()
+                .filter_map(|pattern| match Regex::new(pattern) {
+                    Ok(re) => Some(re),
+                    Err(err) => {
+                        log::error!("scrollbar_error_patterns: {:#}", err);
+                        None
+                    }
+                })
+                .collect();
+            self.errors.clear();
+            self.scanned_end = dims.scrollback_top;
+        }
+        if alt_screen != self.alt_screen {
+            self.errors.clear();
+            self.scanned_end = dims.scrollback_top;
+            self.alt_screen = alt_screen;
+        }
+
+        // Forget the rows that are no longer in the scrollback
+        self.errors
+            .retain(|&row| row >= dims.scrollback_top && row < end);
+        self.scanned_end = self.scanned_end.max(dims.scrollback_top).min(end);
+
+        if self.regexes.is_empty() {
+            self.errors.clear();
+        } else {
+            let mut dirty = if seqno != self.seqno {
+                pane.get_changed_since(dims.scrollback_top..self.scanned_end, self.seqno)
+            } else {
+                Default::default()
+            };
+            dirty.add_range(self.scanned_end..end);
+
+            for range in dirty.iter() {
+                let (first, lines) = pane.get_lines(range.clone());
+                for (idx, line) in lines.iter().enumerate() {
+                    let row = first + idx as StableRowIndex;
+                    let text = line.as_str();
+                    if self.regexes.iter().any(|re| re.is_match(&text)) {
+                        self.errors.insert(row);
+                    } else {
+                        self.errors.remove(&row);
+                    }
+                }
+            }
+            self.scanned_end = end;
+        }
+
+        if seqno != self.seqno || self.prompts.is_empty() {
+            self.prompts = pane
+                .get_semantic_zones()
+                .unwrap_or_else(|_| vec![])
+                .into_iter()
+                .filter(|zone| zone.semantic_type == SemanticType::Prompt)
+                .map(|zone| zone.start_y)
+                .collect();
+            self.prompts.dedup();
+        }
+        self.seqno = seqno;
+    }
+}
+
+/// Returns the offset in pixels from the top of the scrollbar of the
+/// mark for `row`, when `num_rows` rows starting with `first_row` are
+/// represented by a scrollbar that is `height` pixels tall
+fn mark_offset(
+    row: StableRowIndex,
+    first_row: StableRowIndex,
+    num_rows: usize,
+    height: usize,
+    mark_height: usize,
+) -> Option<usize> {
+    let line = row - first_row;
+    if line < 0 || line as usize >= num_rows {
+        return None;
+    }
+    let offset = line as usize * height / num_rows.max(1);
+    Some(offset.min(height.saturating_sub(mark_height)))
+}
+
+impl TermWindow {
+    /// Replaces the marks from `source` for the pane.
+    /// Passing an empty list removes them.
+    pub fn set_scrollbar_marks(
+        &mut self,
+        pane_id: PaneId,
+        source: String,
+        marks: Vec<ScrollbarMark>,
+    ) {
+        let mut all_marks = self.scrollbar_marks.borrow_mut();
+        let pane_marks = all_marks.entry(pane_id).or_default();
+        if marks.is_empty() {
+            pane_marks.lua_marks.remove(&source);
+        } else {
+            pane_marks.lua_marks.insert(source, marks);
+        }
+        drop(all_marks);
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+
+    /// Draws the marks for the pane in the scrollbar at `x`, `y` of
+    /// the specified size, and returns the UIItems that show their
+    /// tooltips and scroll to them when they are clicked
+    pub fn paint_scrollbar_marks(
+        &self,
+        pos: &PositionedPane,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        layers: &mut [MappedQuads; 3],
+    ) -> anyhow::Result<Vec<UIItem>> {
+        if !self.config.show_scrollbar_marks {
+            return Ok(vec![]);
+        }
+
+        let mux = Mux::get().expect("called on main thread");
+        // Examine the underlying pane, rather than any overlay
+        let pane = mux
+            .get_pane(pos.pane.pane_id())
+            .unwrap_or_else(|| Rc::clone(&pos.pane));
+        let dims = pane.get_dimensions();
+        let palette = self.transition_palette(pos.pane.palette());
+
+        let mut all_marks = self.scrollbar_marks.borrow_mut();
+        // Forget the marks of panes that have been closed
+        all_marks.retain(|pane_id, _| mux.get_pane(*pane_id).is_some());
+        let pane_marks = all_marks.entry(pane.pane_id()).or_default();
+        pane_marks.update(&pane, &self.config.scrollbar_error_patterns);
+
+        // Later marks are drawn on top of earlier marks
+        let mut marks: Vec<(StableRowIndex, LinearRgba, Option<String>)> = vec![];
+        let prompt_color = palette.cursor_bg.to_linear();
+        for &row in &pane_marks.prompts {
+            marks.push((row, prompt_color, None));
+        }
+        let error_color = palette.colors.0[1].to_linear();
+        for &row in &pane_marks.errors {
+            marks.push((row, error_color, None));
+        }
+        let lua_color = palette.colors.0[4].to_linear();
+        for mark in pane_marks.lua_marks.values().flatten() {
+            let color = mark
+                .color
+                .map(|color| {
+                    let color: SrgbaTuple = color.into();
+                    color.to_linear()
+                })
+                .unwrap_or(lua_color);
+            marks.push((mark.row, color, mark.tooltip.clone()));
+        }
+        if let Some(copy) = pos.pane.downcast_ref::<CopyOverlay>() {
+            let match_color = palette.colors.0[3].to_linear();
+            for row in copy.search_match_rows() {
+                marks.push((row, match_color, None));
+            }
+        }
+
+        let mark_height = (MARK_HEIGHT * self.dimensions.dpi / 96).max(1);
+        // Only the topmost mark at each position is drawn
+        let mut by_offset = HashMap::new();
+        for (row, color, tooltip) in marks {
+            if let Some(offset) = mark_offset(
+                row,
+                dims.scrollback_top,
+                dims.scrollback_rows,
+                height,
+                mark_height,
+            ) {
+                by_offset.insert(offset, (row, color, tooltip));
+            }
+        }
+
+        let mut items = vec![];
+        for (offset, (row, color, tooltip)) in by_offset {
+            self.filled_rectangle(
+                &mut layers[2],
+                euclid::rect(
+                    x as f32,
+                    (y + offset) as f32,
+                    width as f32,
+                    mark_height as f32,
+                ),
+                color,
+            )?;
+            items.push(UIItem {
+                x,
+                y: y + offset,
+                width,
+                height: mark_height,
+                item_type: UIItemType::ScrollbarMark { row, tooltip },
+            });
+        }
+        Ok(items)
+    }
+
+    /// Paints the tooltip of the scrollbar mark under the mouse, if any.
+    /// Marks that don't have a tooltip show the text of the marked line.
+    pub fn paint_scrollbar_mark_tooltip(&mut self) -> anyhow::Result<()> {
+        let item = match self.last_ui_item.as_ref() {
+            Some(item) => item.clone(),
+            None => return Ok(()),
+        };
+        let (row, tooltip) = match &item.item_type {
+            UIItemType::ScrollbarMark { row, tooltip } => (*row, tooltip.clone()),
+            _ => return Ok(()),
+        };
+        let text = match tooltip {
+            Some(tooltip) => tooltip,
+            None => {
+                let pane = match self.get_active_pane_or_overlay() {
+                    Some(pane) => pane,
+                    None => return Ok(()),
+                };
+                let (_first, lines) = pane.get_lines(row..row + 1);
+                match lines.get(0) {
+                    Some(line) => line.as_str().trim().to_string(),
+                    None => return Ok(()),
+                }
+            }
+        };
+        if text.is_empty() {
+            return Ok(());
+        }
+
+        let mut computed = self.compute_hover_text(&text)?;
+        // Place it to the left of the scrollbar, centered on the mark,
+        // but keep it within the window
+        let left = (item.x as f32 - computed.bounds.max_x()).max(-computed.bounds.min_x());
+        let top = (item.y as f32 + item.height as f32 / 2. - computed.bounds.height() / 2.)
+            .max(0.)
+            .min(self.dimensions.pixel_height as f32 - computed.bounds.height());
+        computed.translate(euclid::vec2(left, top));
+
+        let gl_state = self.render_state.as_ref().unwrap();
+        self.render_element(&computed, gl_state, None)?;
+
+        Ok(())
+    }
+
+    /// Scrolls the active pane so that `row` is in the middle of the viewport
+    pub fn scroll_to_scrollbar_mark(&mut self, row: StableRowIndex) {
+        let pane = match self.get_active_pane_or_overlay() {
+            Some(pane) => pane,
+            None => return,
+        };
+        let dims = pane.get_dimensions();
+        let top = row - dims.viewport_rows as StableRowIndex / 2;
+        self.set_viewport(pane.pane_id(), Some(top), dims);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn offsets() {
+        assert_eq!(mark_offset(10, 10, 100, 200, 2), Some(0));
+        assert_eq!(mark_offset(60, 10, 100, 200, 2), Some(100));
+        // The last row is kept within the scrollbar
+        assert_eq!(mark_offset(109, 10, 100, 200, 2), Some(198));
+        assert_eq!(mark_offset(9, 10, 100, 200, 2), None);
+        assert_eq!(mark_offset(110, 10, 100, 200, 2), None);
+        // More pixels than rows
+        assert_eq!(mark_offset(1, 0, 4, 100, 2), Some(25));
+    }
+}
@@ -1,5 +1,9 @@
+use crate::overlay::CopyOverlay;
+use crate::scripting::guiwin::GuiWin;
+use crate::scripting::pane::PaneObject;
 use crate::selection::{Selection, SelectionCoordinate, SelectionMode, SelectionRange, SelectionX};
 use ::window::WindowOps;
+use config::find_selection_word_boundary;
 use mux::pane::{Pane, PaneId};
 use std::cell::RefMut;
 use std::rc::Rc;
@@ -11,6 +15,35 @@ impl super::TermWindow {
         RefMut::map(self.pane_state(pane_id), |state| &mut state.selection)
     }
 
+    /// Sets (or clears, when None) the word boundary for a pane
+    pub fn set_pane_selection_word_boundary(
+        &mut self,
+        pane_id: PaneId,
+        word_boundary: Option<String>,
+    ) {
+        self.pane_state(pane_id).word_boundary = word_boundary;
+    }
+
+    /// Returns the characters that delimit words in the pane: those set
+    /// via pane:set_selection_word_boundary, or those of the first of
+    /// the selection_word_boundary_rules that matches its foreground
+    /// process, or selection_word_boundary
+    pub fn selection_word_boundary(&self, pane: &Rc<dyn Pane>) -> String {
+        if let Some(word_boundary) = &self.pane_state(pane.pane_id()).word_boundary {
+            return word_boundary.clone();
+        }
+        if !self.config.selection_word_boundary_rules.is_empty() {
+            let program = pane.get_foreground_process_name();
+            if let Some(word_boundary) = find_selection_word_boundary(
+                &self.config.selection_word_boundary_rules,
+                program.as_deref(),
+            ) {
+                return word_boundary.to_string();
+            }
+        }
+        self.config.selection_word_boundary.clone()
+    }
+
     /// Returns the selection region as a series of Line
     pub fn selection_lines(&self, pane: &Rc<dyn Pane>) -> Vec<Line> {
         let mut result = vec![];
@@ -22,6 +55,20 @@ impl super::TermWindow {
             .as_ref()
             .map(|r| r.normalize())
         {
+            if rectangular {
+                // Each row of a rectangular selection is a line of its
+                // own, even where the rows are wrapped
+                let (first_row, lines) = pane.get_lines(sel.rows());
+                for (idx, line) in lines.iter().enumerate() {
+                    let row = first_row + idx as StableRowIndex;
+                    let mut col_span = line.columns_as_line(sel.cols_for_row(row, true));
+                    let seqno = col_span.current_seqno();
+                    col_span.prune_trailing_blanks(seqno);
+                    result.push(col_span);
+                }
+                return result;
+            }
+
             let mut last_was_wrapped = false;
             let first_row = sel.rows().start;
             let last_row = sel.rows().end;
@@ -73,6 +120,18 @@ impl super::TermWindow {
             .as_ref()
             .map(|r| r.normalize())
         {
+            if rectangular {
+                let (first_row, lines) = pane.get_lines(sel.rows());
+                for (idx, line) in lines.iter().enumerate() {
+                    let row = first_row + idx as StableRowIndex;
+                    if idx > 0 {
+                        s.push('\n');
+                    }
+                    s.push_str(line.columns_as_str(sel.cols_for_row(row, true)).trim_end());
+                }
+                return s;
+            }
+
             let mut last_was_wrapped = false;
             let first_row = sel.rows().start;
             let last_row = sel.rows().end;
@@ -118,6 +177,37 @@ impl super::TermWindow {
         self.window.as_ref().unwrap().invalidate();
     }
 
+    /// Sets the selection for the pane to the region between `start`
+    /// and `end` (inclusive), expanding the endpoints according to `mode`.
+    pub fn select_region(
+        &mut self,
+        pane: &Rc<dyn Pane>,
+        start: SelectionCoordinate,
+        end: SelectionCoordinate,
+        mode: SelectionMode,
+    ) {
+        let range = match mode {
+            SelectionMode::Cell | SelectionMode::Block => SelectionRange { start, end },
+            SelectionMode::Word | SelectionMode::Smart => {
+                let word_boundary = self.selection_word_boundary(pane);
+                SelectionRange::word_around(start, &**pane, &word_boundary)
+                    .extend_with(SelectionRange::word_around(end, &**pane, &word_boundary))
+            }
+            SelectionMode::Line => SelectionRange::line_around(start, &**pane)
+                .extend_with(SelectionRange::line_around(end, &**pane)),
+            SelectionMode::SemanticZone => SelectionRange::zone_around(start, &**pane)
+                .extend_with(SelectionRange::zone_around(end, &**pane)),
+        };
+
+        let mut selection = self.selection(pane.pane_id());
+        selection.origin = Some(range.start);
+        selection.range = Some(range);
+        selection.rectangular = mode == SelectionMode::Block;
+        selection.seqno = pane.get_current_seqno();
+        drop(selection);
+        self.window.as_ref().unwrap().invalidate();
+    }
+
     pub fn extend_selection_at_mouse_cursor(&mut self, mode: SelectionMode, pane: &Rc<dyn Pane>) {
         self.selection(pane.pane_id()).seqno = pane.get_current_seqno();
         let (position, y) = match self.pane_state(pane.pane_id()).mouse_terminal_coords {
@@ -183,15 +273,20 @@ impl super::TermWindow {
                         None
                     };
             }
-            SelectionMode::Word => {
-                let end_word = SelectionRange::word_around(SelectionCoordinate::x_y(x, y), &**pane);
+            SelectionMode::Word | SelectionMode::Smart => {
+                let word_boundary = self.selection_word_boundary(pane);
+                let end_word = SelectionRange::word_around(
+                    SelectionCoordinate::x_y(x, y),
+                    &**pane,
+                    &word_boundary,
+                );
 
                 let start_coord = self
                     .selection(pane.pane_id())
                     .origin
                     .clone()
                     .unwrap_or(end_word.start);
-                let start_word = SelectionRange::word_around(start_coord, &**pane);
+                let start_word = SelectionRange::word_around(start_coord, &**pane, &word_boundary);
 
                 let selection_range = start_word.extend_with(end_word);
                 self.selection(pane.pane_id()).range = Some(selection_range);
@@ -242,6 +337,17 @@ impl super::TermWindow {
         self.window.as_ref().unwrap().invalidate();
     }
 
+    /// Moves the copy mode cursor to the cell under the mouse
+    pub fn copy_mode_to_mouse_cursor(&mut self, pane: &Rc<dyn Pane>, mode: Option<SelectionMode>) {
+        let (x, y) = match self.pane_state(pane.pane_id()).mouse_terminal_coords {
+            Some(coords) => (coords.0.column, coords.1),
+            None => return,
+        };
+        if let Some(copy) = pane.downcast_ref::<CopyOverlay>() {
+            copy.move_to_mouse_cursor(x, y, mode);
+        }
+    }
+
     pub fn select_text_at_mouse_cursor(&mut self, mode: SelectionMode, pane: &Rc<dyn Pane>) {
         let (x, y) = match self.pane_state(pane.pane_id()).mouse_terminal_coords {
             Some(coords) => (coords.0.column, coords.1),
@@ -257,8 +363,31 @@ impl super::TermWindow {
                 self.selection(pane.pane_id()).rectangular = false;
             }
             SelectionMode::Word => {
+                let word_boundary = self.selection_word_boundary(pane);
+                let selection_range = SelectionRange::word_around(
+                    SelectionCoordinate::x_y(x, y),
+                    &**pane,
+                    &word_boundary,
+                );
+
+                self.selection(pane.pane_id()).origin = Some(selection_range.start);
+                self.selection(pane.pane_id()).range = Some(selection_range);
+                self.selection(pane.pane_id()).rectangular = false;
+            }
+            SelectionMode::Smart => {
+                let word_boundary = self.selection_word_boundary(pane);
+                let coord = SelectionCoordinate::x_y(x, y);
+                // The click that precedes a double click clears the
+                // selection, so expand the range that smart selection
+                // last selected, provided that the pane didn't change
+                let seqno = pane.get_current_seqno();
+                let previous = self
+                    .pane_state(pane.pane_id())
+                    .smart_selection
+                    .and_then(|(range, range_seqno)| (range_seqno == seqno).then(|| range));
                 let selection_range =
-                    SelectionRange::word_around(SelectionCoordinate::x_y(x, y), &**pane);
+                    SelectionRange::smart_around(coord, previous, &**pane, &word_boundary);
+                self.pane_state(pane.pane_id()).smart_selection = Some((selection_range, seqno));
 
                 self.selection(pane.pane_id()).origin = Some(selection_range.start);
                 self.selection(pane.pane_id()).range = Some(selection_range);
@@ -282,4 +411,81 @@ impl super::TermWindow {
         self.selection(pane.pane_id()).seqno = pane.get_current_seqno();
         self.window.as_ref().unwrap().invalidate();
     }
+
+    /// Emits the `selection-changed` event once the user has completed
+    /// a selection.  `text` is the selected text, which is truncated to
+    /// `selection_changed_event_max_text_len` bytes.
+    pub fn emit_selection_changed(&mut self, pane: &Rc<dyn Pane>, text: &str) {
+        let (range, rectangular) = {
+            let selection = self.selection(pane.pane_id());
+            match selection.range {
+                Some(range) => (range.normalize(), selection.rectangular),
+                None => return,
+            }
+        };
+        let mut len = text
+            .len()
+            .min(self.config.selection_changed_event_max_text_len);
+        while !text.is_char_boundary(len) {
+            len -= 1;
+        }
+        let text = text[..len].to_string();
+        let x = |x: SelectionX| match x {
+            SelectionX::Cell(x) => x,
+            SelectionX::BeforeZero => 0,
+        };
+        let range = SelectionChangedRange {
+            start: (x(range.start.x), range.start.y),
+            end: (x(range.end.x), range.end.y),
+            rectangular,
+        };
+        let gui_win = GuiWin::new(self);
+        let pane = PaneObject::new(pane);
+
+        async fn emit_selection_changed(
+            lua: Option<Rc<mlua::Lua>>,
+            window: GuiWin,
+            pane: PaneObject,
+            range: SelectionChangedRange,
+            text: String,
+        ) -> anyhow::Result<()> {
+            fn coord(
+                lua: &mlua::Lua,
+                (x, y): (usize, StableRowIndex),
+            ) -> mlua::Result<mlua::Table> {
+                let coord = lua.create_table()?;
+                coord.set("x", x)?;
+                coord.set("y", y)?;
+                Ok(coord)
+            }
+
+            if let Some(lua) = lua {
+                let table = lua.create_table()?;
+                table.set("start", coord(&lua, range.start)?)?;
+                table.set("end", coord(&lua, range.end)?)?;
+                table.set("rectangular", range.rectangular)?;
+                let args = lua.pack_multi((window, pane, table, text))?;
+                config::lua::emit_event(&lua, ("selection-changed".to_string(), args))
+                    .await
+                    .map_err(|e| {
+                        log::error!("while processing selection-changed event: {:#}", e);
+                        e
+                    })?;
+            }
+            Ok(())
+        }
+
+        promise::spawn::spawn(config::with_lua_config_on_main_thread(move |lua| {
+            emit_selection_changed(lua, gui_win, pane, range, text)
+        }))
+        .detach();
+    }
+}
+
+/// The normalized selection that is passed to the
+/// `selection-changed` event, as (x, y) coordinates
+struct SelectionChangedRange {
+    start: (usize, StableRowIndex),
+    end: (usize, StableRowIndex),
+    rectangular: bool,
 }
@@ -0,0 +1,69 @@
+//! Paints the title bars of the panes that are collapsed when the
+//! panes of a tab are stacked; only the active pane is expanded, and
+//! each of the others occupies a single row that shows its title.
+use crate::termwindow::box_model::*;
+use crate::termwindow::render::rgbcolor_to_window_color;
+use crate::termwindow::{DimensionContext, TermWindow};
+use crate::utilsprites::RenderMetrics;
+use config::Dimension;
+use mux::tab::PositionedPane;
+
+impl TermWindow {
+    /// Paints the title of a collapsed pane in the row that it occupies
+    pub fn paint_stacked_pane_title(&mut self, pos: &PositionedPane) -> anyhow::Result<()> {
+        let font = self.fonts.default_font()?;
+        let metrics = RenderMetrics::with_font_metrics(&font.metrics());
+        let cell_width = self.render_metrics.cell_size.width as f32;
+        let cell_height = self.render_metrics.cell_size.height as f32;
+
+        let (padding_left, padding_top) = self.padding_left_top();
+        let top_bar_height = if self.show_tab_bar && !self.config.tab_bar_at_bottom {
+            self.tab_bar_pixel_height()?
+        } else {
+            0.
+        };
+        let border = self.get_os_border();
+        let left = padding_left + pos.left as f32 * cell_width;
+        let top =
+            top_bar_height + padding_top + border.top.get() as f32 + pos.top as f32 * cell_height;
+        let width = pos.width as f32 * cell_width;
+
+        let element = Element::new(
+            &font,
+            ElementContent::Text(format!(" {}", pos.pane.get_title())),
+        )
+        .colors(ElementColors {
+            border: BorderColor::default(),
+            bg: rgbcolor_to_window_color(self.config.window_frame.inactive_titlebar_bg).into(),
+            text: rgbcolor_to_window_color(self.config.window_frame.inactive_titlebar_fg).into(),
+        })
+        .min_width(Some(Dimension::Pixels(width)))
+        .max_width(Some(Dimension::Pixels(width)));
+
+        let mut computed = self.compute_element(
+            &LayoutContext {
+                height: DimensionContext {
+                    dpi: self.dimensions.dpi as f32,
+                    pixel_max: cell_height,
+                    pixel_cell: cell_height,
+                },
+                width: DimensionContext {
+                    dpi: self.dimensions.dpi as f32,
+                    pixel_max: width,
+                    pixel_cell: cell_width,
+                },
+                bounds: euclid::rect(0., 0., width, cell_height),
+                metrics: &metrics,
+                gl_state: self.render_state.as_ref().unwrap(),
+                zindex: 1,
+            },
+            &element,
+        )?;
+        computed.translate(euclid::vec2(left, top));
+
+        let gl_state = self.render_state.as_ref().unwrap();
+        self.render_element(&computed, gl_state, None)?;
+
+        Ok(())
+    }
+}
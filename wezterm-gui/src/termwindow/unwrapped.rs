@@ -0,0 +1,494 @@
+//! An unwrapped view of a pane, in which each logical line is shown on
+//! a single row that can be scrolled horizontally, rather than being
+//! wrapped across several rows.
+//! Only the slice of a logical line that is visible is fetched, shaped
+//! and rendered, and the layout of the physical rows that make up each
+//! displayed logical line is cached until the pane changes them, so
+//! that extremely long lines, such as megabytes of minified JSON, don't
+//! stall rendering.
+//! Like folding, this is a rendering transform: the pane content is not
+//! changed.
+use ::window::WindowOps;
+use mux::pane::Pane;
+use mux::renderable::{RenderableDimensions, StableCursorPosition};
+use std::ops::Range;
+use std::rc::Rc;
+use termwiz::surface::{CursorVisibility, SequenceNo};
+use wezterm_term::StableRowIndex;
+
+/// The most rows that are fetched at once while finding the extent
+/// of a logical line
+const MAX_FETCH_CHUNK: StableRowIndex = 256;
+
+/// The physical rows that make up a logical line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineLayout {
+    first_row: StableRowIndex,
+    /// The offset within the logical line of the first cell of each
+    /// physical row, followed by the length of the logical line
+    offsets: Vec<usize>,
+    /// The seqno of the pane when the layout was computed
+    seqno: SequenceNo,
+}
+
+impl LineLayout {
+    pub fn new(
+        first_row: StableRowIndex,
+        lengths: impl IntoIterator<Item = usize>,
+        seqno: SequenceNo,
+    ) -> Self {
+        let mut offsets = vec![0];
+        let mut len = 0;
+        for row_len in lengths {
+            len += row_len;
+            offsets.push(len);
+        }
+        if offsets.len() == 1 {
+            offsets.push(0);
+        }
+        Self {
+            first_row,
+            offsets,
+            seqno,
+        }
+    }
+
+    /// The stable rows that make up the logical line
+    pub fn rows(&self) -> Range<StableRowIndex> {
+        self.first_row..self.first_row + (self.offsets.len() - 1) as StableRowIndex
+    }
+
+    /// The number of cells in the logical line
+    pub fn width(&self) -> usize {
+        self.offsets[self.offsets.len() - 1]
+    }
+
+    /// Returns the stable row and column of the cell at `x` in the
+    /// logical line.  Positions beyond the end of the line are mapped
+    /// to its last row.
+    pub fn physical_position(&self, x: usize) -> (StableRowIndex, usize) {
+        let idx = self
+            .offsets
+            .partition_point(|&offset| offset <= x)
+            .saturating_sub(1)
+            .min(self.offsets.len() - 2);
+        (
+            self.first_row + idx as StableRowIndex,
+            x - self.offsets[idx],
+        )
+    }
+
+    /// Returns the offset within the logical line of the column `x`
+    /// of the physical row `row`
+    pub fn logical_x(&self, row: StableRowIndex, x: usize) -> Option<usize> {
+        if self.rows().contains(&row) {
+            Some(self.offsets[(row - self.first_row) as usize] + x)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the parts of the physical rows that hold the columns
+    /// `cols` of the logical line, as each row and its columns
+    pub fn segments(&self, cols: Range<usize>) -> Vec<(StableRowIndex, Range<usize>)> {
+        self.offsets
+            .windows(2)
+            .enumerate()
+            .filter_map(|(idx, w)| {
+                let start = w[0].max(cols.start);
+                let end = w[1].min(cols.end);
+                if start < end {
+                    Some((
+                        self.first_row + idx as StableRowIndex,
+                        start - w[0]..end - w[0],
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Maps the selected columns of the physical rows, as returned
+    /// by `row_selection`, to the columns of the slice of the logical
+    /// line that starts at `start` and is `width` cells wide
+    pub fn slice_selection(
+        &self,
+        start: usize,
+        width: usize,
+        row_selection: impl Fn(StableRowIndex) -> Range<usize>,
+    ) -> Range<usize> {
+        let mut result: Option<Range<usize>> = None;
+        let mut display = 0;
+        for (row, cols) in self.segments(start..start + width) {
+            let sel = row_selection(row);
+            let sel_start = sel.start.max(cols.start);
+            let sel_end = sel.end.min(cols.end);
+            if sel_start < sel_end {
+                let sel = display + sel_start - cols.start..display + sel_end - cols.start;
+                result = Some(match result {
+                    Some(r) => r.start.min(sel.start)..r.end.max(sel.end),
+                    None => sel,
+                });
+            }
+            display += cols.end - cols.start;
+        }
+        result.unwrap_or(0..0)
+    }
+}
+
+/// Maps the cursor to the position where it is displayed in the slices
+/// of `lines` that start at `h_offset` and are `cols` wide.  The cursor
+/// is hidden while it is scrolled out of view.
+pub fn slice_cursor(
+    lines: &[LineLayout],
+    h_offset: usize,
+    cols: usize,
+    mut cursor: StableCursorPosition,
+) -> StableCursorPosition {
+    for line in lines {
+        if let Some(x) = line.logical_x(cursor.y, cursor.x) {
+            if x >= h_offset && x < h_offset + cols {
+                cursor.x = x - h_offset;
+                cursor.y = line.rows().start;
+            } else {
+                cursor.visibility = CursorVisibility::Hidden;
+            }
+            break;
+        }
+    }
+    cursor
+}
+
+/// Computes the logical lines to show in a viewport that is `rows`
+/// tall, one per row.
+/// `valid` is the range of stable rows that exist in the pane, and
+/// `layout_at` returns the layout of the logical line that includes
+/// a given row.
+/// If `top` is set the viewport is scrolled back and starts with the
+/// logical line that includes that row; otherwise it is anchored to
+/// the bottom of the pane.
+pub fn compute_visible_lines(
+    valid: Range<StableRowIndex>,
+    top: Option<StableRowIndex>,
+    rows: usize,
+    mut layout_at: impl FnMut(StableRowIndex) -> LineLayout,
+) -> Vec<LineLayout> {
+    let mut result = vec![];
+
+    if let Some(top) = top {
+        let mut row = top.max(valid.start);
+        while result.len() < rows && row < valid.end {
+            let layout = layout_at(row);
+            row = layout.rows().end;
+            result.push(layout);
+        }
+        if result.len() == rows {
+            return result;
+        }
+        // Not enough lines below the top to fill the viewport;
+        // show the bottom of the pane instead.
+        result.clear();
+    }
+
+    let mut row = valid.end - 1;
+    while result.len() < rows && row >= valid.start {
+        let layout = layout_at(row);
+        row = layout.rows().start - 1;
+        result.push(layout);
+    }
+    result.reverse();
+    result
+}
+
+/// Computes the layout of the logical line of `pane` that includes
+/// `row`.  The rows are fetched in growing chunks so that short lines
+/// are cheap while long ones don't need a fetch per row.
+fn compute_layout(
+    pane: &dyn Pane,
+    row: StableRowIndex,
+    valid: &Range<StableRowIndex>,
+) -> LineLayout {
+    let seqno = pane.get_current_seqno();
+
+    // Walk back to the first row of the logical line
+    let mut first_row = row;
+    let mut chunk = 1;
+    'back: while first_row > valid.start {
+        let start = (first_row - chunk).max(valid.start);
+        let (top, lines) = pane.get_lines(start..first_row);
+        if top != start {
+            break;
+        }
+        for line in lines.iter().rev() {
+            if !line.last_cell_was_wrapped() {
+                break 'back;
+            }
+            first_row -= 1;
+        }
+        chunk = (chunk * 2).min(MAX_FETCH_CHUNK);
+    }
+
+    let mut lengths = vec![];
+    let mut next = first_row;
+    let mut chunk = 1;
+    'forward: while next < valid.end {
+        let end = (next + chunk).min(valid.end);
+        let (top, lines) = pane.get_lines(next..end);
+        if top != next {
+            break;
+        }
+        for line in lines {
+            lengths.push(line.cells().len());
+            next += 1;
+            if !line.last_cell_was_wrapped() {
+                break 'forward;
+            }
+        }
+        chunk = (chunk * 2).min(MAX_FETCH_CHUNK);
+    }
+
+    LineLayout::new(first_row, lengths, seqno)
+}
+
+/// Returns true if `layout` still describes the rows of `pane`.
+/// The rows either side are included in the check, as wrapping
+/// either of them joins it to the line.
+fn layout_is_current(layout: &LineLayout, pane: &dyn Pane, valid: &Range<StableRowIndex>) -> bool {
+    let rows = layout.rows();
+    rows.start >= valid.start
+        && pane
+            .get_changed_since(
+                (rows.start - 1).max(valid.start)..rows.end + 1,
+                layout.seqno,
+            )
+            .is_empty()
+}
+
+#[derive(Debug, Default)]
+pub struct UnwrappedView {
+    /// The number of columns that the view is scrolled to the right
+    h_offset: usize,
+    /// The logical lines that were last displayed, whose layouts are
+    /// reused until the pane changes them
+    lines: Vec<LineLayout>,
+}
+
+impl super::TermWindow {
+    /// Returns true if the pane is shown unwrapped.
+    /// The alternate screen is always shown as-is, as is the pane
+    /// while an overlay such as copy mode is active.
+    pub fn is_unwrapped(&self, pane: &Rc<dyn Pane>) -> bool {
+        self.pane_state(pane.pane_id()).unwrapped.is_some()
+            && !pane.is_alt_screen_active()
+            && crate::overlay::overlay_lifecycle(pane).is_none()
+    }
+
+    /// Returns the horizontal scroll offset and the logical lines to
+    /// render for the pane if it is shown unwrapped, or None if the
+    /// rows map directly to the viewport
+    pub fn unwrapped_lines(
+        &self,
+        pane: &Rc<dyn Pane>,
+        dims: &RenderableDimensions,
+    ) -> Option<(usize, Vec<LineLayout>)> {
+        if !self.is_unwrapped(pane) {
+            return None;
+        }
+        let cached = self
+            .pane_state(pane.pane_id())
+            .unwrapped
+            .as_mut()
+            .map(|view| std::mem::take(&mut view.lines))
+            .unwrap_or_default();
+
+        let valid = dims.scrollback_top..dims.physical_top + dims.viewport_rows as StableRowIndex;
+        let lines = compute_visible_lines(
+            valid.clone(),
+            self.get_viewport(pane.pane_id()),
+            dims.viewport_rows,
+            |row| {
+                cached
+                    .iter()
+                    .find(|layout| {
+                        layout.rows().contains(&row) && layout_is_current(layout, &**pane, &valid)
+                    })
+                    .cloned()
+                    .unwrap_or_else(|| compute_layout(&**pane, row, &valid))
+            },
+        );
+
+        let mut state = self.pane_state(pane.pane_id());
+        let view = state.unwrapped.as_mut()?;
+        view.lines = lines.clone();
+        Some((view.h_offset, lines))
+    }
+
+    /// Maps a position in the viewport to the column and stable row of
+    /// the cell that is displayed there
+    pub fn stable_position_for_viewport_position(
+        &self,
+        pane: &Rc<dyn Pane>,
+        dims: &RenderableDimensions,
+        row: i64,
+        column: usize,
+    ) -> (usize, StableRowIndex) {
+        if self.is_unwrapped(pane) {
+            let state = self.pane_state(pane.pane_id());
+            if let Some(view) = state.unwrapped.as_ref() {
+                if let Some(line) = usize::try_from(row)
+                    .ok()
+                    .and_then(|row| view.lines.get(row))
+                {
+                    let (row, x) = line.physical_position(view.h_offset + column);
+                    return (x, row);
+                }
+            }
+        }
+        (column, self.stable_row_for_viewport_row(pane, dims, row))
+    }
+
+    /// Switches the pane between wrapped and unwrapped display
+    pub fn toggle_line_wrap(&mut self, pane: &Rc<dyn Pane>) {
+        {
+            let mut state = self.pane_state(pane.pane_id());
+            if state.unwrapped.take().is_none() {
+                state.unwrapped = Some(UnwrappedView::default());
+            }
+        }
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+
+    /// Scrolls an unwrapped pane horizontally by `amount` columns, up
+    /// to the end of the longest line that is displayed
+    pub fn scroll_by_column(&mut self, pane: &Rc<dyn Pane>, amount: isize) {
+        if !self.is_unwrapped(pane) {
+            return;
+        }
+        let cols = pane.get_dimensions().cols;
+        {
+            let mut state = self.pane_state(pane.pane_id());
+            let view = match state.unwrapped.as_mut() {
+                Some(view) => view,
+                None => return,
+            };
+            let max = view
+                .lines
+                .iter()
+                .map(LineLayout::width)
+                .max()
+                .unwrap_or(0)
+                .saturating_sub(cols);
+            view.h_offset = (view.h_offset as isize)
+                .saturating_add(amount)
+                .max(0)
+                .min(max as isize) as usize;
+        }
+        if let Some(window) = self.window.as_ref() {
+            window.invalidate();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Computes the layouts of a pane whose rows are all `width` cells
+    /// wide and where `wrapped[row]` says whether a row wraps
+    fn layout_at(wrapped: &[bool], width: usize, row: StableRowIndex) -> LineLayout {
+        let mut first = row as usize;
+        while first > 0 && wrapped[first - 1] {
+            first -= 1;
+        }
+        let mut last = row as usize;
+        while last + 1 < wrapped.len() && wrapped[last] {
+            last += 1;
+        }
+        LineLayout::new(first as StableRowIndex, vec![width; last + 1 - first], 0)
+    }
+
+    #[test]
+    fn positions() {
+        let layout = LineLayout::new(10, vec![4, 4, 2], 0);
+        assert_eq!(layout.rows(), 10..13);
+        assert_eq!(layout.width(), 10);
+        assert_eq!(layout.physical_position(0), (10, 0));
+        assert_eq!(layout.physical_position(5), (11, 1));
+        assert_eq!(layout.physical_position(8), (12, 0));
+        assert_eq!(layout.physical_position(15), (12, 7));
+
+        let empty = LineLayout::new(3, vec![], 0);
+        assert_eq!(empty.rows(), 3..4);
+        assert_eq!(empty.physical_position(2), (3, 2));
+
+        assert_eq!(layout.logical_x(11, 3), Some(7));
+        assert_eq!(layout.logical_x(13, 0), None);
+    }
+
+    #[test]
+    fn segments() {
+        let layout = LineLayout::new(10, vec![4, 4, 2], 0);
+        assert_eq!(layout.segments(0..3), vec![(10, 0..3)]);
+        assert_eq!(
+            layout.segments(3..9),
+            vec![(10, 3..4), (11, 0..4), (12, 0..1)]
+        );
+        assert_eq!(layout.segments(9..20), vec![(12, 1..2)]);
+        assert_eq!(layout.segments(12..20), vec![]);
+    }
+
+    #[test]
+    fn selection() {
+        let layout = LineLayout::new(10, vec![4, 4, 2], 0);
+        // Selected from the middle of row 10 to the middle of row 11
+        let sel = |row| match row {
+            10 => 2..4,
+            11 => 0..3,
+            _ => 0..0,
+        };
+        assert_eq!(layout.slice_selection(0, 5, sel), 2..5);
+        assert_eq!(layout.slice_selection(3, 5, sel), 0..4);
+        assert_eq!(layout.slice_selection(7, 5, sel), 0..0);
+    }
+
+    #[test]
+    fn visible_lines() {
+        let wrapped = [false, true, true, false, false, true, false];
+        let rows = |lines: Vec<LineLayout>| {
+            lines
+                .into_iter()
+                .map(|layout| layout.rows())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            rows(compute_visible_lines(0..7, None, 3, |row| layout_at(
+                &wrapped, 4, row
+            ))),
+            vec![1..4, 4..5, 5..7]
+        );
+        // A top row in the middle of a logical line shows all of it
+        assert_eq!(
+            rows(compute_visible_lines(0..7, Some(2), 2, |row| layout_at(
+                &wrapped, 4, row
+            ))),
+            vec![1..4, 4..5]
+        );
+        // Not enough lines below the top falls back to the bottom
+        assert_eq!(
+            rows(compute_visible_lines(0..7, Some(4), 3, |row| layout_at(
+                &wrapped, 4, row
+            ))),
+            vec![1..4, 4..5, 5..7]
+        );
+        assert_eq!(
+            rows(compute_visible_lines(0..7, None, 10, |row| layout_at(
+                &wrapped, 4, row
+            ))),
+            vec![0..1, 1..4, 4..5, 5..7]
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Carrying out the XTWINOPS (`CSI t`) window operations that the
+//! terminal forwards to the GUI as `Alert::WindowOperation`.
+//! Which of these are permitted is decided by the terminal, based
+//! on the `allowed_window_operations` configuration.
+use ::window::{Connection, ConnectionOps, WindowOps, WindowState};
+use mux::pane::{Pane, PaneId};
+use mux::Mux;
+use std::io::Write;
+use std::rc::Rc;
+use wezterm_term::{TerminalSize, WindowOperation};
+
+/// Writes an XTWINOPS report to the pane
+fn report(pane: &Rc<dyn Pane>, report: String) {
+    if let Err(err) = pane.writer().write_all(report.as_bytes()) {
+        log::error!("failed to send window operation report: {:#}", err);
+    }
+}
+
+impl super::TermWindow {
+    pub fn perform_window_operation(&mut self, pane_id: PaneId, op: WindowOperation) {
+        let mux = Mux::get().expect("called on main thread");
+        match mux.resolve_pane_id(pane_id) {
+            Some((_domain, window_id, _tab_id)) if window_id == self.mux_window_id => {}
+            _ => return,
+        }
+        let pane = match mux.get_pane(pane_id) {
+            Some(pane) => pane,
+            None => return,
+        };
+        let window = match self.window.clone() {
+            Some(window) => window,
+            None => return,
+        };
+
+        log::trace!("window operation {:?} from pane {}", op, pane_id);
+
+        match op {
+            WindowOperation::Iconify => window.minimize(),
+            WindowOperation::DeIconify => {
+                window.show();
+                window.focus();
+            }
+            WindowOperation::Move { x, y } => {
+                window.set_window_position(::window::ScreenPoint::new(x as isize, y as isize));
+            }
+            WindowOperation::ResizePixels { width, height } => {
+                let screen = Connection::get()
+                    .and_then(|conn| conn.screens().ok())
+                    .map(|screens| screens.active.rect.size);
+                // The requested size is that of the text area, so add
+                // back the padding and tab bar that surround it
+                let extra_width = self
+                    .dimensions
+                    .pixel_width
+                    .saturating_sub(self.terminal_size.pixel_width);
+                let extra_height = self
+                    .dimensions
+                    .pixel_height
+                    .saturating_sub(self.terminal_size.pixel_height);
+                let pick =
+                    |requested: Option<i64>, current: usize, screen: Option<isize>| match requested
+                    {
+                        None => current,
+                        Some(0) => screen.map(|s| s.max(0) as usize).unwrap_or(current),
+                        Some(n) => n.max(1) as usize,
+                    };
+                let width = pick(
+                    width,
+                    self.terminal_size.pixel_width,
+                    screen.map(|s| s.width),
+                );
+                let height = pick(
+                    height,
+                    self.terminal_size.pixel_height,
+                    screen.map(|s| s.height),
+                );
+                window.set_inner_size(width + extra_width, height + extra_height);
+            }
+            WindowOperation::ResizeCells { width, height } => {
+                let pick = |requested: Option<i64>, current: usize| match requested {
+                    None | Some(0) => current,
+                    Some(n) => n.max(1) as usize,
+                };
+                let size = TerminalSize {
+                    rows: pick(height, self.terminal_size.rows),
+                    cols: pick(width, self.terminal_size.cols),
+                    ..self.terminal_size
+                };
+                if let Err(err) = self.set_window_size(size, &window) {
+                    log::error!("failed to resize window to {:?}: {:#}", size, err);
+                }
+            }
+            WindowOperation::Raise => window.focus(),
+            WindowOperation::Lower => {
+                log::debug!("lowering the window is not supported");
+            }
+            WindowOperation::Maximize => window.maximize(),
+            WindowOperation::RestoreMaximized => window.restore(),
+            WindowOperation::FullScreen => {
+                if !self.window_state.contains(WindowState::FULL_SCREEN) {
+                    window.toggle_fullscreen();
+                }
+            }
+            WindowOperation::UndoFullScreen => {
+                if self.window_state.contains(WindowState::FULL_SCREEN) {
+                    window.toggle_fullscreen();
+                }
+            }
+            WindowOperation::ToggleFullScreen => window.toggle_fullscreen(),
+            WindowOperation::ReportState => {
+                let state = if self.window_state.contains(WindowState::HIDDEN) {
+                    2
+                } else {
+                    1
+                };
+                report(&pane, format!("\x1b[{}t", state));
+            }
+            WindowOperation::ReportWindowPosition | WindowOperation::ReportTextAreaPosition => {
+                let (offset_x, offset_y) = if op == WindowOperation::ReportTextAreaPosition {
+                    let (padding_left, padding_top) = self.padding_left_top();
+                    let border = self.get_os_border();
+                    let tab_bar_height = if self.show_tab_bar && !self.config.tab_bar_at_bottom {
+                        self.tab_bar_pixel_height().unwrap_or(0.)
+                    } else {
+                        0.
+                    };
+                    (
+                        (padding_left + border.left.get() as f32) as isize,
+                        (padding_top + tab_bar_height + border.top.get() as f32) as isize,
+                    )
+                } else {
+                    (0, 0)
+                };
+                let future = window.get_window_position();
+                promise::spawn::spawn(async move {
+                    // Backends that can't determine the position
+                    // report the origin, as xterm does when it
+                    // is unable to query the window manager
+                    let position = future.await.ok().flatten();
+                    let (x, y) = position.map(|p| (p.x, p.y)).unwrap_or((0, 0));
+                    report(&pane, format!("\x1b[3;{};{}t", x + offset_x, y + offset_y));
+                })
+                .detach();
+            }
+            WindowOperation::ReportWindowSizePixels => {
+                report(
+                    &pane,
+                    format!(
+                        "\x1b[4;{};{}t",
+                        self.dimensions.pixel_height, self.dimensions.pixel_width
+                    ),
+                );
+            }
+            WindowOperation::ReportScreenSizePixels | WindowOperation::ReportScreenSizeCells => {
+                let screen = match Connection::get().and_then(|conn| conn.screens().ok()) {
+                    Some(screens) => screens.active.rect.size,
+                    None => return,
+                };
+                let (height, width) = (screen.height.max(0), screen.width.max(0));
+                if op == WindowOperation::ReportScreenSizePixels {
+                    report(&pane, format!("\x1b[5;{};{}t", height, width));
+                } else {
+                    let cell_height = self.render_metrics.cell_size.height.max(1);
+                    let cell_width = self.render_metrics.cell_size.width.max(1);
+                    report(
+                        &pane,
+                        format!("\x1b[9;{};{}t", height / cell_height, width / cell_width),
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Manages the system tray icon that is enabled by `enable_tray_icon`,
+//! and the windows that have been hidden to the tray.
+use crate::frontend::front_end;
+use crate::termwindow::TermWindowNotif;
+use crate::ICON_DATA;
+use ::window::tray::{TrayEvent, TrayIcon, TrayMenuItem};
+use ::window::{Connection, ConnectionOps, Image, Window, WindowOps};
+use config::keyassignment::KeyAssignment;
+use config::{ConfigHandle, TrayMenuEntry};
+use mux::Mux;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+/// What to do when a menu item is selected
+#[derive(Debug, Clone)]
+enum TrayAction {
+    Perform(KeyAssignment),
+    ShowWindows,
+    Nothing,
+}
+
+thread_local! {
+    /// The entries that the current icon was built from, so that
+    /// it is only rebuilt when the tray configuration changes
+    static SHOWN: RefCell<Option<Vec<TrayMenuEntry>>> = RefCell::new(None);
+    /// The windows that are currently hidden to the tray
+    static HIDDEN: RefCell<BTreeSet<Window>> = RefCell::new(BTreeSet::new());
+}
+
+fn load_icon() -> anyhow::Result<Image> {
+    let image = image::load_from_memory(ICON_DATA)?.into_rgba8();
+    let (width, height) = image.dimensions();
+    Ok(Image::with_rgba32(
+        width as usize,
+        height as usize,
+        width as usize * 4,
+        image.as_raw(),
+    ))
+}
+
+fn build_menu(entries: &[TrayMenuEntry]) -> (Vec<TrayMenuItem>, Vec<TrayAction>) {
+    let mut items = vec![];
+    let mut actions = vec![];
+
+    for entry in entries {
+        if entry.separator {
+            items.push(TrayMenuItem::Separator);
+            actions.push(TrayAction::Nothing);
+        } else {
+            items.push(TrayMenuItem::Item {
+                label: entry.label.clone(),
+                enabled: entry.action.is_some(),
+            });
+            actions.push(
+                entry
+                    .action
+                    .clone()
+                    .map(TrayAction::Perform)
+                    .unwrap_or(TrayAction::Nothing),
+            );
+        }
+    }
+    if !entries.is_empty() {
+        items.push(TrayMenuItem::Separator);
+        actions.push(TrayAction::Nothing);
+    }
+
+    items.push(TrayMenuItem::Item {
+        label: "Show WezTerm".to_string(),
+        enabled: true,
+    });
+    actions.push(TrayAction::ShowWindows);
+    items.push(TrayMenuItem::Item {
+        label: "Quit WezTerm".to_string(),
+        enabled: true,
+    });
+    actions.push(TrayAction::Perform(KeyAssignment::QuitApplication));
+
+    (items, actions)
+}
+
+/// Shows, updates or removes the tray icon to match the configuration
+pub fn update(config: &ConfigHandle) {
+    let wanted = if config.enable_tray_icon {
+        Some(config.tray_menu_items.clone())
+    } else {
+        None
+    };
+    if SHOWN.with(|shown| *shown.borrow() == wanted) {
+        return;
+    }
+
+    let conn = match Connection::get() {
+        Some(conn) => conn,
+        None => return,
+    };
+
+    let result = match &wanted {
+        Some(entries) => load_icon().and_then(|icon| {
+            let (menu, actions) = build_menu(entries);
+            let actions = Rc::new(actions);
+            conn.set_tray_icon(Some(TrayIcon {
+                tooltip: "WezTerm".to_string(),
+                icon,
+                menu,
+                callback: Rc::new(move |event| {
+                    let action = match event {
+                        TrayEvent::Activate => None,
+                        TrayEvent::MenuItem(idx) => match actions.get(idx) {
+                            Some(action) => Some(action.clone()),
+                            None => return,
+                        },
+                    };
+                    // Defer handling so that we're not running inside
+                    // the platform's tray event handling
+                    promise::spawn::spawn(async move {
+                        match action {
+                            None => toggle_windows(),
+                            Some(action) => perform(action),
+                        }
+                    })
+                    .detach();
+                }),
+            }))
+        }),
+        None => {
+            show_windows();
+            conn.set_tray_icon(None)
+        }
+    };
+    if let Err(err) = result {
+        log::error!("tray icon: {:#}", err);
+    }
+    SHOWN.with(|shown| *shown.borrow_mut() = wanted);
+}
+
+/// Hides the window until it is restored from the tray
+pub fn hide_window(window: &Window) {
+    window.hide();
+    HIDDEN.with(|hidden| hidden.borrow_mut().insert(window.clone()));
+}
+
+/// Shows all of the windows that were hidden to the tray
+pub fn show_windows() {
+    let hidden = HIDDEN.with(|hidden| std::mem::take(&mut *hidden.borrow_mut()));
+    let known: BTreeSet<Window> = front_end()
+        .known_windows()
+        .into_iter()
+        .map(|(window, _)| window)
+        .collect();
+    for window in hidden {
+        // Skip windows that were closed while hidden
+        if known.contains(&window) {
+            window.show();
+        }
+    }
+}
+
+fn toggle_windows() {
+    if HIDDEN.with(|hidden| hidden.borrow().is_empty()) {
+        for (window, _) in front_end().known_windows() {
+            hide_window(&window);
+        }
+    } else {
+        show_windows();
+    }
+}
+
+fn perform(action: TrayAction) {
+    match action {
+        TrayAction::Nothing => {}
+        TrayAction::ShowWindows => show_windows(),
+        TrayAction::Perform(assignment) => {
+            show_windows();
+            let mux = match Mux::get() {
+                Some(mux) => mux,
+                None => return,
+            };
+            // Key assignments operate on a pane, so we use the
+            // active pane of the first window that has one
+            for (window, mux_window_id) in front_end().known_windows() {
+                if let Some(pane) = mux
+                    .get_active_tab_for_window(mux_window_id)
+                    .and_then(|tab| tab.get_active_pane())
+                {
+                    window.notify(TermWindowNotif::PerformAssignment {
+                        pane_id: pane.pane_id(),
+                        assignment,
+                    });
+                    return;
+                }
+            }
+            log::error!(
+                "tray: there is no window in which to perform {:?}",
+                assignment
+            );
+        }
+    }
+}
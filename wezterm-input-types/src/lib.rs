@@ -311,6 +311,116 @@ impl KeyCode {
             | Self::Function(_) => return None,
         })
     }
+
+    /// Returns the win32 virtual key code that corresponds to this key.
+    /// Characters are mapped according to their position on a US layout;
+    /// other characters have no virtual key code and are reported as 0,
+    /// in the same way that win32 reports characters produced by an IME.
+    pub fn win32_virtual_key_code(&self) -> Option<u32> {
+        Some(match self {
+            Self::Char(c) => match c {
+                '\u{8}' => 0x08,
+                '\t' => 0x09,
+                '\r' | '\n' => 0x0d,
+                '\u{1b}' => 0x1b,
+                ' ' => 0x20,
+                '\u{7f}' => 0x2e,
+                '0'..='9' => *c as u32,
+                'a'..='z' | 'A'..='Z' => c.to_ascii_uppercase() as u32,
+                ')' => 0x30,
+                '!' => 0x31,
+                '@' => 0x32,
+                '#' => 0x33,
+                '$' => 0x34,
+                '%' => 0x35,
+                '^' => 0x36,
+                '&' => 0x37,
+                '*' => 0x38,
+                '(' => 0x39,
+                ';' | ':' => 0xba,
+                '=' | '+' => 0xbb,
+                ',' | '<' => 0xbc,
+                '-' | '_' => 0xbd,
+                '.' | '>' => 0xbe,
+                '/' | '?' => 0xbf,
+                '`' | '~' => 0xc0,
+                '[' | '{' => 0xdb,
+                '\\' | '|' => 0xdc,
+                ']' | '}' => 0xdd,
+                '\'' | '"' => 0xde,
+                c => match is_ascii_control(*c) {
+                    Some(c) => c.to_ascii_uppercase() as u32,
+                    None => 0,
+                },
+            },
+            Self::Physical(phys) => return phys.to_key_code().win32_virtual_key_code(),
+            Self::Cancel => 0x03,
+            Self::Clear => 0x0c,
+            Self::Shift => 0x10,
+            Self::Control => 0x11,
+            Self::Alt => 0x12,
+            Self::Pause => 0x13,
+            Self::CapsLock => 0x14,
+            Self::PageUp => 0x21,
+            Self::PageDown => 0x22,
+            Self::End => 0x23,
+            Self::Home => 0x24,
+            Self::LeftArrow | Self::ApplicationLeftArrow => 0x25,
+            Self::UpArrow | Self::ApplicationUpArrow => 0x26,
+            Self::RightArrow | Self::ApplicationRightArrow => 0x27,
+            Self::DownArrow | Self::ApplicationDownArrow => 0x28,
+            Self::Select => 0x29,
+            Self::Print => 0x2a,
+            Self::Execute => 0x2b,
+            Self::PrintScreen => 0x2c,
+            Self::Insert => 0x2d,
+            Self::Help => 0x2f,
+            Self::LeftWindows | Self::Super => 0x5b,
+            Self::RightWindows => 0x5c,
+            Self::Applications => 0x5d,
+            Self::Sleep => 0x5f,
+            Self::Numpad(n) if *n <= 9 => 0x60 + *n as u32,
+            Self::Multiply => 0x6a,
+            Self::Add => 0x6b,
+            Self::Separator => 0x6c,
+            Self::Subtract => 0x6d,
+            Self::Decimal => 0x6e,
+            Self::Divide => 0x6f,
+            Self::Function(n) if (1..=24).contains(n) => 0x70 + (*n as u32 - 1),
+            Self::NumLock => 0x90,
+            Self::ScrollLock => 0x91,
+            Self::LeftShift => 0xa0,
+            Self::RightShift => 0xa1,
+            Self::LeftControl => 0xa2,
+            Self::RightControl => 0xa3,
+            Self::LeftAlt => 0xa4,
+            Self::RightAlt => 0xa5,
+            Self::BrowserBack => 0xa6,
+            Self::BrowserForward => 0xa7,
+            Self::BrowserRefresh => 0xa8,
+            Self::BrowserStop => 0xa9,
+            Self::BrowserSearch => 0xaa,
+            Self::BrowserFavorites => 0xab,
+            Self::BrowserHome => 0xac,
+            Self::VolumeMute => 0xad,
+            Self::VolumeDown => 0xae,
+            Self::VolumeUp => 0xaf,
+            Self::MediaNextTrack => 0xb0,
+            Self::MediaPrevTrack => 0xb1,
+            Self::MediaStop => 0xb2,
+            Self::MediaPlayPause => 0xb3,
+            Self::Hyper
+            | Self::Meta
+            | Self::VoidSymbol
+            | Self::Copy
+            | Self::Cut
+            | Self::Paste
+            | Self::Numpad(_)
+            | Self::Function(_)
+            | Self::RawCode(_)
+            | Self::Composed(_) => return None,
+        })
+    }
 }
 
 impl TryFrom<&str> for KeyCode {
@@ -824,6 +934,127 @@ impl PhysKeyCode {
         }
     }
 
+    /// Returns the set 1 scan code for the key, along with whether
+    /// it is an enhanced key that is prefixed by 0xe0 on the wire.
+    /// These are the values that win32 reports in KEY_EVENT_RECORD.
+    pub fn win32_scan_code(&self) -> Option<(u32, bool)> {
+        Some(match self {
+            Self::Escape => (0x01, false),
+            Self::K1 => (0x02, false),
+            Self::K2 => (0x03, false),
+            Self::K3 => (0x04, false),
+            Self::K4 => (0x05, false),
+            Self::K5 => (0x06, false),
+            Self::K6 => (0x07, false),
+            Self::K7 => (0x08, false),
+            Self::K8 => (0x09, false),
+            Self::K9 => (0x0a, false),
+            Self::K0 => (0x0b, false),
+            Self::Minus => (0x0c, false),
+            Self::Equal => (0x0d, false),
+            Self::Backspace => (0x0e, false),
+            Self::Tab => (0x0f, false),
+            Self::Q => (0x10, false),
+            Self::W => (0x11, false),
+            Self::E => (0x12, false),
+            Self::R => (0x13, false),
+            Self::T => (0x14, false),
+            Self::Y => (0x15, false),
+            Self::U => (0x16, false),
+            Self::I => (0x17, false),
+            Self::O => (0x18, false),
+            Self::P => (0x19, false),
+            Self::LeftBracket => (0x1a, false),
+            Self::RightBracket => (0x1b, false),
+            Self::Return => (0x1c, false),
+            Self::LeftControl => (0x1d, false),
+            Self::A => (0x1e, false),
+            Self::S => (0x1f, false),
+            Self::D => (0x20, false),
+            Self::F => (0x21, false),
+            Self::G => (0x22, false),
+            Self::H => (0x23, false),
+            Self::J => (0x24, false),
+            Self::K => (0x25, false),
+            Self::L => (0x26, false),
+            Self::Semicolon => (0x27, false),
+            Self::Quote => (0x28, false),
+            Self::Grave => (0x29, false),
+            Self::LeftShift => (0x2a, false),
+            Self::Backslash => (0x2b, false),
+            Self::Z => (0x2c, false),
+            Self::X => (0x2d, false),
+            Self::C => (0x2e, false),
+            Self::V => (0x2f, false),
+            Self::B => (0x30, false),
+            Self::N => (0x31, false),
+            Self::M => (0x32, false),
+            Self::Comma => (0x33, false),
+            Self::Period => (0x34, false),
+            Self::Slash => (0x35, false),
+            Self::RightShift => (0x36, false),
+            Self::KeypadMultiply => (0x37, false),
+            Self::LeftAlt => (0x38, false),
+            Self::Space => (0x39, false),
+            Self::CapsLock => (0x3a, false),
+            Self::F1 => (0x3b, false),
+            Self::F2 => (0x3c, false),
+            Self::F3 => (0x3d, false),
+            Self::F4 => (0x3e, false),
+            Self::F5 => (0x3f, false),
+            Self::F6 => (0x40, false),
+            Self::F7 => (0x41, false),
+            Self::F8 => (0x42, false),
+            Self::F9 => (0x43, false),
+            Self::F10 => (0x44, false),
+            Self::NumLock => (0x45, false),
+            Self::Keypad7 => (0x47, false),
+            Self::Keypad8 => (0x48, false),
+            Self::Keypad9 => (0x49, false),
+            Self::KeypadSubtract => (0x4a, false),
+            Self::Keypad4 => (0x4b, false),
+            Self::Keypad5 | Self::KeypadClear => (0x4c, false),
+            Self::Keypad6 => (0x4d, false),
+            Self::KeypadAdd => (0x4e, false),
+            Self::Keypad1 => (0x4f, false),
+            Self::Keypad2 => (0x50, false),
+            Self::Keypad3 => (0x51, false),
+            Self::Keypad0 => (0x52, false),
+            Self::KeypadDecimal | Self::KeypadDelete => (0x53, false),
+            Self::F11 => (0x57, false),
+            Self::F12 => (0x58, false),
+            Self::KeypadEquals => (0x59, false),
+            Self::F13 => (0x64, false),
+            Self::F14 => (0x65, false),
+            Self::F15 => (0x66, false),
+            Self::F16 => (0x67, false),
+            Self::F17 => (0x68, false),
+            Self::F18 => (0x69, false),
+            Self::F19 => (0x6a, false),
+            Self::F20 => (0x6b, false),
+            Self::KeypadEnter => (0x1c, true),
+            Self::RightControl => (0x1d, true),
+            Self::VolumeMute => (0x20, true),
+            Self::VolumeDown => (0x2e, true),
+            Self::VolumeUp => (0x30, true),
+            Self::KeypadDivide => (0x35, true),
+            Self::RightAlt => (0x38, true),
+            Self::Home => (0x47, true),
+            Self::UpArrow => (0x48, true),
+            Self::PageUp => (0x49, true),
+            Self::LeftArrow => (0x4b, true),
+            Self::RightArrow => (0x4d, true),
+            Self::End => (0x4f, true),
+            Self::DownArrow => (0x50, true),
+            Self::PageDown => (0x51, true),
+            Self::Insert => (0x52, true),
+            Self::Delete => (0x53, true),
+            Self::LeftWindows => (0x5b, true),
+            Self::RightWindows => (0x5c, true),
+            Self::Help | Self::Function => return None,
+        })
+    }
+
     fn make_map() -> HashMap<String, Self> {
         let mut map = HashMap::new();
 
@@ -1022,7 +1253,9 @@ pub enum MouseEventKind {
     Move,
     Press(MousePress),
     Release(MousePress),
+    /// Positive values scroll up, negative values scroll down
     VertWheel(i16),
+    /// Positive values scroll left, negative values scroll right
     HorzWheel(i16),
 }
 
@@ -1037,6 +1270,29 @@ pub struct MouseEvent {
     pub modifiers: Modifiers,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TouchPhase {
+    Began,
+    Moved,
+    Ended,
+    /// The system took over the touch, for example, to perform
+    /// a gesture of its own
+    Cancelled,
+}
+
+/// Describes a finger that touched, moved across or was lifted
+/// from a touchscreen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TouchEvent {
+    /// Identifies the finger for the duration of the touch
+    pub id: u64,
+    pub phase: TouchPhase,
+    /// Coordinates of the touch relative to the top left of the window
+    pub coords: Point,
+    /// The touch position in screen coordinates
+    pub screen_coords: crate::ScreenPoint,
+}
+
 #[derive(Debug, Clone)]
 pub struct Handled(Arc<AtomicBool>);
 
@@ -1175,18 +1431,59 @@ impl KeyEvent {
         self
     }
 
+    /// Returns the virtual key code, scan code and whether the key is an
+    /// enhanced key, as they would be reported in a win32 KEY_EVENT_RECORD.
+    #[cfg(windows)]
+    fn win32_key_codes(&self) -> Option<(u32, u32, bool)> {
+        let phys = self.raw.as_ref()?;
+        Some((
+            phys.raw_code,
+            phys.scan_code,
+            self.modifiers.contains(Modifiers::ENHANCED_KEY),
+        ))
+    }
+
+    /// Returns the virtual key code, scan code and whether the key is an
+    /// enhanced key, as they would be reported in a win32 KEY_EVENT_RECORD.
+    /// Outside of Windows there is no native equivalent, so these are
+    /// synthesized from the key and its physical position on a US layout.
     #[cfg(not(windows))]
-    pub fn encode_win32_input_mode(&self) -> Option<String> {
-        None
+    fn win32_key_codes(&self) -> Option<(u32, u32, bool)> {
+        let vkey = self.key.win32_virtual_key_code()?;
+        let phys = self
+            .raw
+            .as_ref()
+            .and_then(|raw| raw.phys_code)
+            .or_else(|| self.key.to_phys());
+        let (scan_code, enhanced) = phys
+            .and_then(|phys| phys.win32_scan_code())
+            .unwrap_or((0, false));
+        Some((vkey, scan_code, enhanced))
     }
 
-    /// <https://github.com/microsoft/terminal/blob/main/doc/specs/%234999%20-%20Improved%20keyboard%20handling%20in%20Conpty.md>
     #[cfg(windows)]
+    fn win32_uni_char(&self, c: char) -> u32 {
+        self.win32_uni_char.unwrap_or(c) as u32
+    }
+
+    #[cfg(not(windows))]
+    fn win32_uni_char(&self, c: char) -> u32 {
+        // Windows reports the control character that results from
+        // pressing CTRL with a letter, rather than the letter itself
+        if self.modifiers.contains(Modifiers::CTRL)
+            && !self.modifiers.contains(Modifiers::RIGHT_ALT)
+            && c.is_ascii_alphabetic()
+        {
+            (c as u32) & 0x1f
+        } else {
+            c as u32
+        }
+    }
+
+    /// <https://github.com/microsoft/terminal/blob/main/doc/specs/%234999%20-%20Improved%20keyboard%20handling%20in%20Conpty.md>
     pub fn encode_win32_input_mode(&self) -> Option<String> {
-        let phys = self.raw.as_ref()?;
+        let (vkey, scan_code, enhanced) = self.win32_key_codes()?;
 
-        let vkey = phys.raw_code;
-        let scan_code = phys.scan_code;
         // <https://docs.microsoft.com/en-us/windows/console/key-event-record-str>
         // defines the dwControlKeyState values
         let mut control_key_state = 0;
@@ -1223,27 +1520,28 @@ impl KeyEvent {
         if self.modifiers.contains(Modifiers::LEFT_CTRL) {
             control_key_state |= LEFT_CTRL_PRESSED;
         }
-        if self.modifiers.contains(Modifiers::ENHANCED_KEY) {
+        if enhanced {
             control_key_state |= ENHANCED_KEY;
         }
 
         let key_down = if self.key_is_down { 1 } else { 0 };
+        let repeat_count = self.repeat_count.max(1);
 
         match &self.key {
             KeyCode::Composed(_) => None,
             KeyCode::Char(c) => {
-                let uni = self.win32_uni_char.unwrap_or(*c) as u32;
+                let uni = self.win32_uni_char(*c);
 
                 Some(format!(
                     "\u{1b}[{};{};{};{};{};{}_",
-                    vkey, scan_code, uni, key_down, control_key_state, self.repeat_count
+                    vkey, scan_code, uni, key_down, control_key_state, repeat_count
                 ))
             }
             _ => {
                 let uni = 0;
                 Some(format!(
                     "\u{1b}[{};{};{};{};{};{}_",
-                    vkey, scan_code, uni, key_down, control_key_state, self.repeat_count
+                    vkey, scan_code, uni, key_down, control_key_state, repeat_count
                 ))
             }
         }
@@ -1286,3 +1584,59 @@ impl Default for WindowDecorations {
         WindowDecorations::TITLE | WindowDecorations::RESIZE
     }
 }
+
+#[cfg(all(test, not(windows)))]
+mod test {
+    use super::*;
+
+    fn key_event(key: KeyCode, modifiers: Modifiers, key_is_down: bool) -> KeyEvent {
+        KeyEvent {
+            key,
+            modifiers,
+            repeat_count: 1,
+            key_is_down,
+            raw: None,
+        }
+    }
+
+    #[test]
+    fn win32_input_mode_chars() {
+        assert_eq!(
+            key_event(KeyCode::Char('a'), Modifiers::NONE, true).encode_win32_input_mode(),
+            Some("\u{1b}[65;30;97;1;0;1_".to_string())
+        );
+        assert_eq!(
+            key_event(KeyCode::Char('a'), Modifiers::NONE, false).encode_win32_input_mode(),
+            Some("\u{1b}[65;30;97;0;0;1_".to_string())
+        );
+        assert_eq!(
+            key_event(KeyCode::Char('A'), Modifiers::SHIFT, true).encode_win32_input_mode(),
+            Some("\u{1b}[65;30;65;1;16;1_".to_string())
+        );
+        assert_eq!(
+            key_event(KeyCode::Char('c'), Modifiers::CTRL, true).encode_win32_input_mode(),
+            Some("\u{1b}[67;46;3;1;8;1_".to_string())
+        );
+        assert_eq!(
+            key_event(KeyCode::Char('\r'), Modifiers::NONE, true).encode_win32_input_mode(),
+            Some("\u{1b}[13;28;13;1;0;1_".to_string())
+        );
+    }
+
+    #[test]
+    fn win32_input_mode_keys() {
+        assert_eq!(
+            key_event(KeyCode::UpArrow, Modifiers::NONE, true).encode_win32_input_mode(),
+            Some("\u{1b}[38;72;0;1;256;1_".to_string())
+        );
+        assert_eq!(
+            key_event(KeyCode::LeftShift, Modifiers::SHIFT, false).encode_win32_input_mode(),
+            Some("\u{1b}[160;42;0;0;16;1_".to_string())
+        );
+        assert_eq!(
+            key_event(KeyCode::Composed("foo".to_string()), Modifiers::NONE, true)
+                .encode_win32_input_mode(),
+            None
+        );
+    }
+}
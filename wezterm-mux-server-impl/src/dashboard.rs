@@ -0,0 +1,445 @@
+//! A small web UI that shows the clients, workspaces and panes of the
+//! mux server, and offers a few administrative actions: killing a pane,
+//! spawning a window in a workspace and renaming a workspace.
+//! It is enabled by setting `web_dashboard_bind_address` on a TLS server,
+//! and is served over TLS with the same certificate and client
+//! certificate verification as that server, so only clients that may
+//! connect to the mux can use it.
+//! The requests are handled on the thread that accepted the connection;
+//! the mux itself is only accessed on the main thread.
+use anyhow::{anyhow, bail, Context};
+use config::keyassignment::SpawnTabDomain;
+use mux::pane::PaneId;
+use mux::Mux;
+use promise::spawn::spawn_into_main_thread;
+use std::future::Future;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::time::Duration;
+
+/// Requests with more headers or a larger body than this are rejected
+const MAX_HEADERS: usize = 64;
+const MAX_BODY: usize = 64 * 1024;
+
+/// A connection that doesn't make progress reading or writing for
+/// this long, including during the TLS handshake, is closed
+pub const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connections beyond this number are closed immediately, so that
+/// idle connections can't exhaust the threads of the server
+pub const MAX_CONNECTIONS: usize = 16;
+
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the value of the named field of a form that was posted
+    fn form_value(&self, name: &str) -> anyhow::Result<String> {
+        url::form_urlencoded::parse(&self.body)
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.into_owned())
+            .ok_or_else(|| anyhow!("missing form field {}", name))
+    }
+}
+
+fn read_request<R: Read>(stream: R) -> anyhow::Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut fields = line.split_whitespace();
+    let method = fields.next().ok_or_else(|| anyhow!("empty request"))?;
+    let path = fields
+        .next()
+        .ok_or_else(|| anyhow!("request has no path"))?;
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut headers = vec![];
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            bail!("connection closed while reading headers");
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if headers.len() == MAX_HEADERS {
+            bail!("too many headers");
+        }
+        let (name, value) = header
+            .split_once(':')
+            .ok_or_else(|| anyhow!("malformed header {:?}", header))?;
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+
+    let mut request = Request {
+        method,
+        path,
+        headers,
+        body: vec![],
+    };
+    if let Some(len) = request.header("Content-Length") {
+        let len: usize = len.parse().context("parsing Content-Length")?;
+        if len > MAX_BODY {
+            bail!("request body of {} bytes is too large", len);
+        }
+        request.body.resize(len, 0);
+        reader.read_exact(&mut request.body)?;
+    }
+    Ok(request)
+}
+
+fn write_response<W: Write>(
+    mut stream: W,
+    status: &str,
+    extra_headers: &[(&str, &str)],
+    body: &str,
+) -> anyhow::Result<()> {
+    write!(stream, "HTTP/1.1 {}\r\n", status)?;
+    write!(stream, "Content-Type: text/html; charset=utf-8\r\n")?;
+    write!(stream, "Content-Length: {}\r\n", body.len())?;
+    write!(stream, "Cache-Control: no-store\r\n")?;
+    write!(stream, "Connection: close\r\n")?;
+    for (name, value) in extra_headers {
+        write!(stream, "{}: {}\r\n", name, value)?;
+    }
+    write!(stream, "\r\n{}", body)?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Runs `f` on the main thread, where the mux lives, and waits for it
+/// to complete
+fn on_main_thread<F, Fut, R>(f: F) -> R
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = R> + 'static,
+    R: Send + 'static,
+{
+    smol::block_on(spawn_into_main_thread(async move {
+        let task = promise::spawn::spawn(f());
+        task.await
+    }))
+}
+
+/// Serves a single request on `stream`, which has already been
+/// authenticated, and then closes it
+pub fn serve<S: Read + Write>(mut stream: S) -> anyhow::Result<()> {
+    let request = read_request(&mut stream)?;
+    log::trace!("dashboard: {} {}", request.method, request.path);
+
+    let result = match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/") => {
+            let snapshot = on_main_thread(|| async { Snapshot::capture() });
+            return write_response(stream, "200 OK", &[], &snapshot.render());
+        }
+        ("POST", path) => check_origin(&request).and_then(|_| perform_action(path, &request)),
+        ("GET", _) | ("HEAD", _) => {
+            return write_response(stream, "404 Not Found", &[], &error_page("Not found"));
+        }
+        _ => {
+            return write_response(
+                stream,
+                "405 Method Not Allowed",
+                &[],
+                &error_page("Method not allowed"),
+            );
+        }
+    };
+
+    match result {
+        Ok(()) => write_response(stream, "303 See Other", &[("Location", "/")], ""),
+        Err(err) => {
+            log::error!("dashboard: {} failed: {:#}", request.path, err);
+            write_response(
+                stream,
+                "400 Bad Request",
+                &[],
+                &error_page(&format!("{:#}", err)),
+            )
+        }
+    }
+}
+
+/// The client certificate of a browser is presented to any site that
+/// asks for it, so reject forms that were posted from other origins
+fn check_origin(request: &Request) -> anyhow::Result<()> {
+    let origin = match request.header("Origin") {
+        Some(origin) => origin,
+        None => return Ok(()),
+    };
+    let host = request.header("Host").unwrap_or_default();
+    if origin != format!("https://{}", host) {
+        bail!("refusing a form posted from {}", origin);
+    }
+    Ok(())
+}
+
+fn perform_action(path: &str, request: &Request) -> anyhow::Result<()> {
+    match path {
+        "/pane/kill" => {
+            let pane_id: PaneId = request
+                .form_value("pane_id")?
+                .parse()
+                .context("parsing pane_id")?;
+            on_main_thread(move || async move {
+                let mux = Mux::get().unwrap();
+                let pane = mux
+                    .get_pane(pane_id)
+                    .ok_or_else(|| anyhow!("no such pane {}", pane_id))?;
+                pane.kill();
+                mux.remove_pane(pane_id);
+                Ok(())
+            })
+        }
+        "/spawn" => {
+            let workspace = request.form_value("workspace")?;
+            if workspace.is_empty() {
+                bail!("the workspace name must not be empty");
+            }
+            on_main_thread(move || async move {
+                let mux = Mux::get().unwrap();
+                let config = config::configuration();
+                mux.spawn_tab_or_window(
+                    None,
+                    SpawnTabDomain::DefaultDomain,
+                    None,
+                    None,
+                    config.initial_size(0),
+                    None,
+                    workspace,
+                )
+                .await?;
+                Ok(())
+            })
+        }
+        "/workspace/rename" => {
+            let old_workspace = request.form_value("old_workspace")?;
+            let new_workspace = request.form_value("new_workspace")?;
+            if new_workspace.is_empty() {
+                bail!("the workspace name must not be empty");
+            }
+            on_main_thread(move || async move {
+                let mux = Mux::get().unwrap();
+                mux.rename_workspace(&old_workspace, &new_workspace)
+            })
+        }
+        _ => bail!("unknown action {}", path),
+    }
+}
+
+struct ClientRow {
+    username: String,
+    hostname: String,
+    pid: u32,
+    connected_at: String,
+    last_input: String,
+    active_workspace: String,
+    focused_pane_id: Option<PaneId>,
+}
+
+struct PaneRow {
+    pane_id: PaneId,
+    tab_id: usize,
+    window_id: usize,
+    title: String,
+    cwd: String,
+    cols: usize,
+    rows: usize,
+    is_dead: bool,
+}
+
+struct WorkspaceRow {
+    name: String,
+    panes: Vec<PaneRow>,
+}
+
+/// The state of the mux, captured on the main thread so that it can
+/// be rendered on the thread that serves the request
+struct Snapshot {
+    clients: Vec<ClientRow>,
+    workspaces: Vec<WorkspaceRow>,
+}
+
+impl Snapshot {
+    fn capture() -> Self {
+        let mux = Mux::get().unwrap();
+
+        let clients = mux
+            .iter_clients()
+            .into_iter()
+            .map(|info| ClientRow {
+                username: info.client_id.username.clone(),
+                hostname: info.client_id.hostname.clone(),
+                pid: info.client_id.pid,
+                connected_at: info.connected_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                last_input: info.last_input.format("%Y-%m-%d %H:%M:%S").to_string(),
+                active_workspace: info.active_workspace.unwrap_or_default(),
+                focused_pane_id: info.focused_pane_id,
+            })
+            .collect();
+
+        let workspaces = mux
+            .iter_workspaces()
+            .into_iter()
+            .map(|name| {
+                let mut panes = vec![];
+                for window_id in mux.iter_windows_in_workspace(&name) {
+                    let window = match mux.get_window(window_id) {
+                        Some(window) => window,
+                        None => continue,
+                    };
+                    for tab in window.iter() {
+                        for pos in tab.iter_panes() {
+                            let dims = pos.pane.get_dimensions();
+                            panes.push(PaneRow {
+                                pane_id: pos.pane.pane_id(),
+                                tab_id: tab.tab_id(),
+                                window_id,
+                                title: pos.pane.get_title(),
+                                cwd: pos
+                                    .pane
+                                    .get_current_working_dir()
+                                    .map(|url| url.to_string())
+                                    .unwrap_or_default(),
+                                cols: dims.cols,
+                                rows: dims.viewport_rows,
+                                is_dead: pos.pane.is_dead(),
+                            });
+                        }
+                    }
+                }
+                WorkspaceRow { name, panes }
+            })
+            .collect();
+
+        Self {
+            clients,
+            workspaces,
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut html = String::new();
+        html.push_str(&page_start("wezterm mux server"));
+
+        html.push_str("<h2>Clients</h2>\n");
+        if self.clients.is_empty() {
+            html.push_str("<p>No clients are connected.</p>\n");
+        } else {
+            html.push_str(
+                "<table><tr><th>User</th><th>Host</th><th>PID</th><th>Connected</th>\
+                 <th>Last input</th><th>Workspace</th><th>Focused pane</th></tr>\n",
+            );
+            for client in &self.clients {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+                     <td>{}</td><td>{}</td></tr>\n",
+                    escape(&client.username),
+                    escape(&client.hostname),
+                    client.pid,
+                    escape(&client.connected_at),
+                    escape(&client.last_input),
+                    escape(&client.active_workspace),
+                    client
+                        .focused_pane_id
+                        .map(|id| id.to_string())
+                        .unwrap_or_default(),
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str("<h2>Workspaces</h2>\n");
+        for workspace in &self.workspaces {
+            let name = escape(&workspace.name);
+            html.push_str(&format!("<h3>{}</h3>\n", name));
+            html.push_str(&format!(
+                "<form method=\"post\" action=\"/workspace/rename\">\
+                 <input type=\"hidden\" name=\"old_workspace\" value=\"{name}\">\
+                 <input name=\"new_workspace\" value=\"{name}\" required>\
+                 <button>Rename</button></form>\n\
+                 <form method=\"post\" action=\"/spawn\">\
+                 <input type=\"hidden\" name=\"workspace\" value=\"{name}\">\
+                 <button>Spawn window</button></form>\n",
+                name = name
+            ));
+            html.push_str(
+                "<table><tr><th>Pane</th><th>Tab</th><th>Window</th><th>Title</th>\
+                 <th>Working directory</th><th>Size</th><th></th></tr>\n",
+            );
+            for pane in &workspace.panes {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}{}</td><td>{}</td>\
+                     <td>{}&times;{}</td><td><form method=\"post\" action=\"/pane/kill\">\
+                     <input type=\"hidden\" name=\"pane_id\" value=\"{}\">\
+                     <button>Kill</button></form></td></tr>\n",
+                    pane.pane_id,
+                    pane.tab_id,
+                    pane.window_id,
+                    escape(&pane.title),
+                    if pane.is_dead { " (dead)" } else { "" },
+                    escape(&pane.cwd),
+                    pane.cols,
+                    pane.rows,
+                    pane.pane_id,
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str(
+            "<h2>New workspace</h2>\n<form method=\"post\" action=\"/spawn\">\
+             <input name=\"workspace\" placeholder=\"name\" required>\
+             <button>Spawn window</button></form>\n",
+        );
+        html.push_str("</body></html>\n");
+        html
+    }
+}
+
+fn page_start(title: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{}</title>\
+         <style>body {{ font-family: sans-serif; }} \
+         table {{ border-collapse: collapse; margin-bottom: 1em; }} \
+         th, td {{ border: 1px solid #ccc; padding: 0.2em 0.5em; text-align: left; }} \
+         form {{ display: inline; margin-right: 0.5em; }}</style></head><body>\n\
+         <h1>{}</h1>\n",
+        escape(title),
+        escape(title)
+    )
+}
+
+fn error_page(message: &str) -> String {
+    format!(
+        "{}<p>{}</p><p><a href=\"/\">Back</a></p></body></html>\n",
+        page_start("Error"),
+        escape(message)
+    )
+}
+
+fn escape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            c => result.push(c),
+        }
+    }
+    result
+}
@@ -66,7 +66,20 @@ where
         let rx_msg = item_rx.recv();
         let wait_for_read = stream.readable().map(|_| Ok(Item::Readable));
 
-        match smol::future::or(rx_msg, wait_for_read).await {
+        let item = smol::future::or(rx_msg, wait_for_read).await;
+        // The client may have asked for a different compression
+        // while handling the previous item
+        let compression = handler.compression();
+        if let Ok(Item::Notif(notif)) = &item {
+            if let Some(event) = handler.mux_event_for(notif) {
+                Pdu::MuxEvent(event)
+                    .encode_async_with(&mut stream, 0, &compression, None)
+                    .await?;
+                stream.flush().await.context("flushing PDU to client")?;
+            }
+        }
+
+        match item {
             Ok(Item::Readable) => {
                 let decoded = match Pdu::decode_async(&mut stream, None).await {
                     Ok(data) => data,
@@ -83,7 +96,11 @@ where
                 handler.process_one(decoded);
             }
             Ok(Item::WritePdu(decoded)) => {
-                match decoded.pdu.encode_async(&mut stream, decoded.serial).await {
+                match decoded
+                    .pdu
+                    .encode_async_with(&mut stream, decoded.serial, &compression, None)
+                    .await
+                {
                     Ok(()) => {}
                     Err(err) => {
                         if let Some(err) = err.root_cause().downcast_ref::<std::io::Error>() {
@@ -110,9 +127,14 @@ where
                 handler.schedule_pane_push(pane_id);
             }
             Ok(Item::Notif(MuxNotification::PaneAdded(_pane_id))) => {}
+            Ok(Item::Notif(MuxNotification::PaneFocused(_pane_id))) => {}
+            Ok(Item::Notif(MuxNotification::PaneHibernated(pane_id))) => {
+                handler.forget_pane(pane_id);
+            }
+            Ok(Item::Notif(MuxNotification::PaneResized { .. })) => {}
             Ok(Item::Notif(MuxNotification::PaneRemoved(pane_id))) => {
                 Pdu::PaneRemoved(codec::PaneRemoved { pane_id })
-                    .encode_async(&mut stream, 0)
+                    .encode_async_with(&mut stream, 0, &compression, None)
                     .await?;
                 stream.flush().await.context("flushing PDU to client")?;
             }
@@ -125,6 +147,8 @@ where
                 handler.schedule_pane_push(pane_id);
             }
             Ok(Item::Notif(MuxNotification::SaveToDownloads { .. })) => {}
+            Ok(Item::Notif(MuxNotification::FileTransferProgress { .. })) => {}
+            Ok(Item::Notif(MuxNotification::SshPrompt { .. })) => {}
             Ok(Item::Notif(MuxNotification::AssignClipboard {
                 pane_id,
                 selection,
@@ -135,7 +159,7 @@ where
                     clipboard,
                     selection,
                 })
-                .encode_async(&mut stream, 0)
+                .encode_async_with(&mut stream, 0, &compression, None)
                 .await?;
                 stream.flush().await.context("flushing PDU to client")?;
             }
@@ -153,12 +177,14 @@ where
                         window_id,
                         workspace,
                     })
-                    .encode_async(&mut stream, 0)
+                    .encode_async_with(&mut stream, 0, &compression, None)
                     .await?;
                     stream.flush().await.context("flushing PDU to client")?;
                 }
             }
             Ok(Item::Notif(MuxNotification::ActiveWorkspaceChanged(_))) => {}
+            Ok(Item::Notif(MuxNotification::WorkspaceCreated(_))) => {}
+            Ok(Item::Notif(MuxNotification::WorkspaceRemoved(_))) => {}
             Ok(Item::Notif(MuxNotification::Empty)) => {}
             Err(err) => {
                 log::error!("process_async Err {}", err);
@@ -3,6 +3,7 @@ use std::os::unix::net::{UnixListener, UnixStream};
 #[cfg(windows)]
 use uds_windows::{UnixListener, UnixStream};
 
+pub mod dashboard;
 pub mod dispatch;
 pub mod local;
 pub mod pki;
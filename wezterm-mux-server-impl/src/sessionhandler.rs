@@ -1,14 +1,17 @@
 use crate::PKI;
 use anyhow::{anyhow, Context};
 use codec::*;
+use config::keyassignment::RotationDirection;
+use config::MuxCompression;
 use mux::client::ClientId;
 use mux::domain::SplitSource;
 use mux::pane::{Pane, PaneId};
 use mux::renderable::{RenderableDimensions, StableCursorPosition};
-use mux::tab::TabId;
-use mux::Mux;
+use mux::tab::{Tab, TabId};
+use mux::window::WindowId;
+use mux::{Mux, MuxNotification};
 use promise::spawn::spawn_into_main_thread;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
@@ -133,6 +136,21 @@ impl PerPane {
             seqno: self.seqno,
         })
     }
+
+    /// Adopts `seqno`, the last state of the pane that a reconnecting
+    /// client received over its previous connection, so that only the
+    /// lines that changed since then are reported as dirty.
+    /// Returns false if the pane has already been synchronized over
+    /// this connection, or if `seqno` is ahead of the pane, which means
+    /// that it refers to a different pane that had the same id, such as
+    /// one in a server that has since been restarted.
+    fn resume(&mut self, pane: &Rc<dyn Pane>, seqno: SequenceNo) -> bool {
+        if self.seqno != 0 || seqno > pane.get_current_seqno() {
+            return false;
+        }
+        self.seqno = seqno;
+        true
+    }
 }
 
 fn maybe_push_pane_changes(
@@ -192,6 +210,8 @@ pub struct SessionHandler {
     to_write_tx: PduSender,
     per_pane: HashMap<TabId, Arc<Mutex<PerPane>>>,
     client_id: Option<Arc<ClientId>>,
+    subscribed_events: HashSet<MuxEventKind>,
+    compression: MuxCompression,
 }
 
 impl Drop for SessionHandler {
@@ -209,6 +229,38 @@ impl SessionHandler {
             to_write_tx,
             per_pane: HashMap::new(),
             client_id: None,
+            subscribed_events: HashSet::new(),
+            compression: MuxCompression::default(),
+        }
+    }
+
+    /// How the PDUs sent to the client are to be compressed,
+    /// as requested by the client
+    pub fn compression(&self) -> MuxCompression {
+        self.compression
+    }
+
+    /// Returns the event that should be streamed to the client
+    /// for the notification, if the client subscribed to it
+    pub fn mux_event_for(&self, notif: &MuxNotification) -> Option<MuxEvent> {
+        let (kind, pane_id, size) = match notif {
+            MuxNotification::PaneOutput(pane_id) => (MuxEventKind::PaneOutput, *pane_id, None),
+            MuxNotification::PaneFocused(pane_id) => (MuxEventKind::PaneFocused, *pane_id, None),
+            MuxNotification::PaneResized { pane_id, size } => {
+                (MuxEventKind::PaneResized, *pane_id, Some(*size))
+            }
+            MuxNotification::PaneAdded(pane_id) => (MuxEventKind::PaneAdded, *pane_id, None),
+            MuxNotification::PaneRemoved(pane_id) => (MuxEventKind::PaneRemoved, *pane_id, None),
+            _ => return None,
+        };
+        if self.subscribed_events.contains(&kind) {
+            Some(MuxEvent {
+                kind,
+                pane_id,
+                size,
+            })
+        } else {
+            None
         }
     }
 
@@ -220,6 +272,13 @@ impl SessionHandler {
         )
     }
 
+    /// Drops the state that was retained for pushing changes in the
+    /// pane to the client, so that the pane is sent in full when it
+    /// next changes
+    pub fn forget_pane(&mut self, pane_id: PaneId) {
+        self.per_pane.remove(&pane_id);
+    }
+
     pub fn schedule_pane_push(&mut self, pane_id: PaneId) {
         let sender = self.to_write_tx.clone();
         let per_pane = self.per_pane(pane_id);
@@ -303,6 +362,155 @@ impl SessionHandler {
                 .detach();
                 send_response(Ok(Pdu::UnitResponse(UnitResponse {})))
             }
+            Pdu::SetCompression(request) => {
+                // The level comes from the client; zstd rejects
+                // levels outside of its range
+                self.compression = MuxCompression {
+                    enabled: request.enabled,
+                    threshold: request.threshold,
+                    level: request.clamped_level(),
+                };
+                send_response(Ok(Pdu::UnitResponse(UnitResponse {})))
+            }
+            Pdu::SubscribeEvents(SubscribeEvents { events }) => {
+                self.subscribed_events = events.into_iter().collect();
+                send_response(Ok(Pdu::UnitResponse(UnitResponse {})))
+            }
+            Pdu::GetCommandHistory(GetCommandHistory { query }) => {
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            let entries = mux.query_command_history(&query);
+                            Ok(Pdu::GetCommandHistoryResponse(GetCommandHistoryResponse {
+                                entries,
+                            }))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
+            Pdu::AttachDomainGroup(request) => {
+                spawn_into_main_thread(async move {
+                    schedule_attach_domain_group(request, send_response);
+                })
+                .detach();
+            }
+            Pdu::ApplyWorkspaceLayout(request) => {
+                let client_id = self.client_id.clone();
+                spawn_into_main_thread(async move {
+                    schedule_apply_workspace_layout(request, send_response, client_id);
+                })
+                .detach();
+            }
+            Pdu::ExportScrollback(ExportScrollback {
+                pane_id,
+                format,
+                start_line,
+                end_line,
+            }) => {
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            let pane = mux
+                                .get_pane(pane_id)
+                                .ok_or_else(|| anyhow!("no such pane {}", pane_id))?;
+                            let text = mux::export::export_scrollback(
+                                &*pane, format, start_line, end_line,
+                            )?;
+                            Ok(Pdu::ExportScrollbackResponse(ExportScrollbackResponse {
+                                text,
+                            }))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
+            Pdu::ResumePanes(ResumePanes { panes }) => {
+                let panes: Vec<_> = panes
+                    .into_iter()
+                    .map(|ResumePane { pane_id, seqno }| (pane_id, seqno, self.per_pane(pane_id)))
+                    .collect();
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            let mut resumed = vec![];
+                            for (pane_id, seqno, per_pane) in panes {
+                                if let Some(pane) = mux.get_pane(pane_id) {
+                                    if per_pane.lock().unwrap().resume(&pane, seqno) {
+                                        resumed.push(pane_id);
+                                    }
+                                }
+                            }
+                            Ok(Pdu::ResumePanesResponse(ResumePanesResponse { resumed }))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
+            Pdu::RenameWorkspace(RenameWorkspace {
+                old_workspace,
+                new_workspace,
+            }) => {
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            mux.rename_workspace(&old_workspace, &new_workspace)?;
+                            Ok(Pdu::UnitResponse(UnitResponse {}))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
+            Pdu::MergeWorkspace(MergeWorkspace {
+                from_workspace,
+                into_workspace,
+            }) => {
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            mux.merge_workspace(&from_workspace, &into_workspace)?;
+                            Ok(Pdu::UnitResponse(UnitResponse {}))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
+            Pdu::DeleteWorkspace(DeleteWorkspace { workspace }) => {
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            mux.delete_workspace(&workspace)?;
+                            Ok(Pdu::UnitResponse(UnitResponse {}))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
+            Pdu::DetachDomainGroup(DetachDomainGroup { name }) => {
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            mux.detach_domain_group(&name)?;
+                            Ok(Pdu::UnitResponse(UnitResponse {}))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
             Pdu::GetClientList(GetClientList) => {
                 spawn_into_main_thread(async move {
                     catch(
@@ -378,7 +586,11 @@ impl SessionHandler {
                 })
                 .detach();
             }
-            Pdu::SendPaste(SendPaste { pane_id, data }) => {
+            Pdu::SendPaste(SendPaste {
+                pane_id,
+                data,
+                emit_pre_paste,
+            }) => {
                 let sender = self.to_write_tx.clone();
                 let per_pane = self.per_pane(pane_id);
                 spawn_into_main_thread(async move {
@@ -388,7 +600,14 @@ impl SessionHandler {
                             let pane = mux
                                 .get_pane(pane_id)
                                 .ok_or_else(|| anyhow!("no such pane {}", pane_id))?;
-                            pane.send_paste(&data)?;
+                            let data = if emit_pre_paste {
+                                mux_lua::apply_pre_paste_hook(pane_id, data)
+                            } else {
+                                Some(data)
+                            };
+                            if let Some(data) = data {
+                                pane.send_paste(&data)?;
+                            }
                             maybe_push_pane_changes(&pane, sender, per_pane)?;
                             Ok(Pdu::UnitResponse(UnitResponse {}))
                         },
@@ -542,6 +761,91 @@ impl SessionHandler {
                 .detach();
             }
 
+            Pdu::SplitPaneWithLayout(request) => {
+                let client_id = self.client_id.clone();
+                spawn_into_main_thread(async move {
+                    promise::spawn::spawn(async move {
+                        send_response(split_pane_with_layout(request, client_id).await)
+                    })
+                    .detach();
+                })
+                .detach();
+            }
+
+            Pdu::ActivatePaneDirection(ActivatePaneDirection { pane_id, direction }) => {
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            let (tab, window_id) = resolve_tab_for_pane(&mux, pane_id)?;
+                            tab.activate_pane_direction(direction);
+                            mux.notify(MuxNotification::WindowInvalidated(window_id));
+                            Ok(Pdu::UnitResponse(UnitResponse {}))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
+
+            Pdu::RotatePanes(RotatePanes { pane_id, direction }) => {
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            let (tab, window_id) = resolve_tab_for_pane(&mux, pane_id)?;
+                            match direction {
+                                RotationDirection::Clockwise => tab.rotate_clockwise(),
+                                RotationDirection::CounterClockwise => {
+                                    tab.rotate_counter_clockwise()
+                                }
+                            }
+                            mux.notify(MuxNotification::WindowInvalidated(window_id));
+                            Ok(Pdu::UnitResponse(UnitResponse {}))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
+
+            Pdu::SwapPanes(SwapPanes {
+                pane_id,
+                other_pane_id,
+            }) => {
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            mux.swap_panes(pane_id, other_pane_id)?;
+                            Ok(Pdu::UnitResponse(UnitResponse {}))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
+
+            Pdu::PerformActions(PerformActions { pane_id, actions }) => {
+                spawn_into_main_thread(async move {
+                    catch(
+                        move || {
+                            let mux = Mux::get().unwrap();
+                            let pane = mux
+                                .get_pane(pane_id)
+                                .ok_or_else(|| anyhow!("no such pane {}", pane_id))?;
+                            pane.perform_actions(
+                                termwiz::escape::parser::Parser::new()
+                                    .parse_as_vec(actions.as_bytes()),
+                            );
+                            Ok(Pdu::UnitResponse(UnitResponse {}))
+                        },
+                        send_response,
+                    )
+                })
+                .detach();
+            }
+
             Pdu::MovePaneToNewTab(request) => {
                 let client_id = self.client_id.clone();
                 spawn_into_main_thread(async move {
@@ -550,6 +854,27 @@ impl SessionHandler {
                 .detach();
             }
 
+            Pdu::ReadFileChunk(request) => {
+                spawn_into_main_thread(async move {
+                    schedule_read_file_chunk(request, send_response);
+                })
+                .detach();
+            }
+
+            Pdu::ReadDir(request) => {
+                spawn_into_main_thread(async move {
+                    schedule_read_dir(request, send_response);
+                })
+                .detach();
+            }
+
+            Pdu::WriteFileChunk(request) => {
+                spawn_into_main_thread(async move {
+                    schedule_write_file_chunk(request, send_response);
+                })
+                .detach();
+            }
+
             Pdu::GetPaneRenderChanges(GetPaneRenderChanges { pane_id, .. }) => {
                 let sender = self.to_write_tx.clone();
                 let per_pane = self.per_pane(pane_id);
@@ -691,6 +1016,13 @@ impl SessionHandler {
             | Pdu::PaneRemoved { .. }
             | Pdu::GetImageCellResponse { .. }
             | Pdu::MovePaneToNewTabResponse { .. }
+            | Pdu::ReadFileChunkResponse { .. }
+            | Pdu::ReadDirResponse { .. }
+            | Pdu::SplitPaneWithLayoutResponse { .. }
+            | Pdu::MuxEvent { .. }
+            | Pdu::GetCommandHistoryResponse { .. }
+            | Pdu::ExportScrollbackResponse { .. }
+            | Pdu::ResumePanesResponse { .. }
             | Pdu::ErrorResponse { .. } => {
                 send_response(Err(anyhow!("expected a request, got {:?}", decoded.pdu)))
             }
@@ -750,6 +1082,27 @@ async fn split_pane(split: SplitPane, client_id: Option<Arc<ClientId>>) -> anyho
     }))
 }
 
+async fn split_pane_with_layout(
+    request: SplitPaneWithLayout,
+    client_id: Option<Arc<ClientId>>,
+) -> anyhow::Result<Pdu> {
+    let mux = Mux::get().unwrap();
+    let _identity = mux.with_identity(client_id);
+
+    let splits = mux::layout::splits_from_json(&request.splits)?;
+    let mut pane_ids = vec![];
+    let result = mux
+        .apply_pane_splits(request.pane_id, &splits, &mut pane_ids)
+        .await;
+
+    Ok(Pdu::SplitPaneWithLayoutResponse(
+        SplitPaneWithLayoutResponse {
+            pane_ids,
+            error: result.err().map(|err| format!("{:#}", err)),
+        },
+    ))
+}
+
 async fn domain_spawn_v2(spawn: SpawnV2, client_id: Option<Arc<ClientId>>) -> anyhow::Result<Pdu> {
     let mux = Mux::get().unwrap();
     let _identity = mux.with_identity(client_id);
@@ -774,6 +1127,108 @@ async fn domain_spawn_v2(spawn: SpawnV2, client_id: Option<Arc<ClientId>>) -> an
     }))
 }
 
+fn schedule_read_file_chunk<SND>(request: ReadFileChunk, send_response: SND)
+where
+    SND: Fn(anyhow::Result<Pdu>) + 'static,
+{
+    promise::spawn::spawn(async move { send_response(read_file_chunk(request).await) }).detach();
+}
+
+async fn read_file_chunk(request: ReadFileChunk) -> anyhow::Result<Pdu> {
+    let mux = Mux::get().unwrap();
+    let chunk = mux
+        .read_file_chunk(
+            request.pane_id,
+            &request.path,
+            request.offset,
+            request.max_bytes,
+        )
+        .await?;
+    Ok::<Pdu, anyhow::Error>(Pdu::ReadFileChunkResponse(ReadFileChunkResponse {
+        data: chunk.data,
+        total_size: chunk.total_size,
+    }))
+}
+
+fn schedule_read_dir<SND>(request: ReadDir, send_response: SND)
+where
+    SND: Fn(anyhow::Result<Pdu>) + 'static,
+{
+    promise::spawn::spawn(async move { send_response(read_dir(request).await) }).detach();
+}
+
+async fn read_dir(request: ReadDir) -> anyhow::Result<Pdu> {
+    let mux = Mux::get().unwrap();
+    let domain = mux.resolve_spawn_tab_domain(request.pane_id, &request.domain)?;
+    let entries = domain.read_dir(&request.path).await?;
+    Ok::<Pdu, anyhow::Error>(Pdu::ReadDirResponse(ReadDirResponse { entries }))
+}
+
+/// Resolves the tab and window that contain the specified pane
+fn resolve_tab_for_pane(mux: &Mux, pane_id: PaneId) -> anyhow::Result<(Rc<Tab>, WindowId)> {
+    let (_domain_id, window_id, tab_id) = mux
+        .resolve_pane_id(pane_id)
+        .ok_or_else(|| anyhow!("pane_id {} invalid", pane_id))?;
+    let tab = mux
+        .get_tab(tab_id)
+        .ok_or_else(|| anyhow!("tab_id {} invalid", tab_id))?;
+    Ok((tab, window_id))
+}
+
+fn schedule_attach_domain_group<SND>(request: AttachDomainGroup, send_response: SND)
+where
+    SND: Fn(anyhow::Result<Pdu>) + 'static,
+{
+    promise::spawn::spawn(async move {
+        let mux = Mux::get().unwrap();
+        send_response(
+            mux.attach_domain_group(&request.name)
+                .await
+                .map(|_| Pdu::UnitResponse(UnitResponse {})),
+        )
+    })
+    .detach();
+}
+
+fn schedule_apply_workspace_layout<SND>(
+    request: ApplyWorkspaceLayout,
+    send_response: SND,
+    client_id: Option<Arc<ClientId>>,
+) where
+    SND: Fn(anyhow::Result<Pdu>) + 'static,
+{
+    promise::spawn::spawn(async move {
+        let mux = Mux::get().unwrap();
+        let _identity = mux.with_identity(client_id);
+        send_response(
+            mux.apply_workspace_layout(&request.name)
+                .await
+                .map(|_| Pdu::UnitResponse(UnitResponse {})),
+        )
+    })
+    .detach();
+}
+
+fn schedule_write_file_chunk<SND>(request: WriteFileChunk, send_response: SND)
+where
+    SND: Fn(anyhow::Result<Pdu>) + 'static,
+{
+    promise::spawn::spawn(async move { send_response(write_file_chunk(request).await) }).detach();
+}
+
+async fn write_file_chunk(request: WriteFileChunk) -> anyhow::Result<Pdu> {
+    let mux = Mux::get().unwrap();
+    mux.write_file_chunk(
+        request.pane_id,
+        &request.path,
+        request.offset,
+        request.data,
+        request.total_size,
+    )
+    .await?;
+    Ok::<Pdu, anyhow::Error>(Pdu::UnitResponse(UnitResponse {}))
+}
+
 fn schedule_move_pane<SND>(
     request: MovePaneToNewTab,
     send_response: SND,
@@ -186,6 +186,8 @@ fn run() -> anyhow::Result<()> {
         e
     })?;
 
+    mux::hibernate::start_hibernation_task();
+
     let activity = Activity::new();
 
     promise::spawn::spawn(async move {
@@ -6,8 +6,9 @@ use openssl::x509::X509;
 use promise::spawn::spawn_into_main_thread;
 use std::net::TcpListener;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use wezterm_mux_server_impl::PKI;
+use wezterm_mux_server_impl::{dashboard, PKI};
 
 struct OpenSSLNetListener {
     acceptor: Arc<SslAcceptor>,
@@ -69,6 +70,56 @@ impl OpenSSLNetListener {
         }
     }
 
+    /// Serves the web dashboard to authenticated peers.
+    /// Each connection is handled on a thread of its own, so that a
+    /// slow browser can't hold up the others.  The number of those
+    /// threads is limited, and a connection that stalls is closed.
+    fn run_web_dashboard(&mut self) {
+        let connections = Arc::new(AtomicUsize::new(0));
+        for stream in self.listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if connections.fetch_add(1, Ordering::SeqCst) >= dashboard::MAX_CONNECTIONS {
+                        connections.fetch_sub(1, Ordering::SeqCst);
+                        log::error!("web dashboard: too many connections, closing a new one");
+                        continue;
+                    }
+                    let connections = Arc::clone(&connections);
+                    let acceptor = self.acceptor.clone();
+                    std::thread::spawn(move || {
+                        if let Err(err) = stream
+                            .set_read_timeout(Some(dashboard::IO_TIMEOUT))
+                            .and_then(|_| stream.set_write_timeout(Some(dashboard::IO_TIMEOUT)))
+                        {
+                            log::error!("web dashboard: failed to set timeouts: {}", err);
+                        } else {
+                            match acceptor.accept(stream) {
+                                Ok(stream) => {
+                                    if let Err(err) = Self::verify_peer_cert(&stream) {
+                                        log::error!(
+                                            "web dashboard: problem with peer cert: {}",
+                                            err
+                                        );
+                                    } else if let Err(err) = dashboard::serve(stream) {
+                                        log::error!("web dashboard: {:#}", err);
+                                    }
+                                }
+                                Err(e) => {
+                                    log::error!("web dashboard: failed TlsAcceptor: {}", e);
+                                }
+                            }
+                        }
+                        connections.fetch_sub(1, Ordering::SeqCst);
+                    });
+                }
+                Err(err) => {
+                    log::error!("web dashboard: accept failed: {}", err);
+                    return;
+                }
+            }
+        }
+    }
+
     fn run(&mut self) {
         for stream in self.listener.incoming() {
             match stream {
@@ -109,9 +160,9 @@ impl OpenSSLNetListener {
     }
 }
 
-pub fn spawn_tls_listener(tls_server: &TlsDomainServer) -> Result<(), Error> {
-    openssl::init();
-
+/// Builds the acceptor for the TLS domain, which requires that peers
+/// present a client certificate that is signed by a trusted CA
+fn build_acceptor(tls_server: &TlsDomainServer) -> Result<SslAcceptor, Error> {
     let mut acceptor = SslAcceptor::mozilla_modern(SslMethod::tls())?;
 
     let cert_file = tls_server
@@ -168,7 +219,29 @@ pub fn spawn_tls_listener(tls_server: &TlsDomainServer) -> Result<(), Error> {
 
     acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
 
-    let acceptor = acceptor.build();
+    Ok(acceptor.build())
+}
+
+pub fn spawn_tls_listener(tls_server: &TlsDomainServer) -> Result<(), Error> {
+    openssl::init();
+
+    let acceptor = build_acceptor(tls_server)?;
+
+    if let Some(dashboard_address) = &tls_server.web_dashboard_bind_address {
+        log::error!("serving web dashboard on {:?}", dashboard_address);
+        let mut dashboard_listener = OpenSSLNetListener::new(
+            TcpListener::bind(dashboard_address).with_context(|| {
+                format!(
+                    "error binding to web_dashboard_bind_address {}",
+                    dashboard_address,
+                )
+            })?,
+            acceptor.clone(),
+        );
+        std::thread::spawn(move || {
+            dashboard_listener.run_web_dashboard();
+        });
+    }
 
     log::error!("listening with TLS on {:?}", tls_server.bind_address);
 
@@ -2,7 +2,7 @@ use crate::session::SessionEvent;
 use anyhow::Context;
 use smol::channel::{bounded, Sender};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AuthenticationPrompt {
     pub prompt: String,
     pub echo: bool,
@@ -71,4 +71,20 @@ impl FileWrap {
             }
         }
     }
+
+    pub fn seek(&mut self, pos: u64) -> SftpChannelResult<()> {
+        use std::io::{Seek, SeekFrom};
+        match self {
+            #[cfg(feature = "ssh2")]
+            Self::Ssh2(file) => {
+                file.seek(SeekFrom::Start(pos))?;
+            }
+
+            #[cfg(feature = "libssh-rs")]
+            Self::LibSsh(file) => {
+                file.seek(SeekFrom::Start(pos))?;
+            }
+        }
+        Ok(())
+    }
 }
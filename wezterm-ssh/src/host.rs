@@ -5,6 +5,11 @@ use smol::channel::{bounded, Sender};
 #[derive(Debug)]
 pub struct HostVerificationEvent {
     pub message: String,
+    /// The name of the host, as it is recorded in the known_hosts file
+    pub hostname: String,
+    pub port: u16,
+    /// The fingerprint of the host key
+    pub fingerprint: String,
     pub(crate) reply: Sender<bool>,
 }
 
@@ -41,6 +46,9 @@ impl crate::sessioninner::SessionInner {
                                     Trust and continue connecting?",
                             hostname, port, key
                         ),
+                        hostname: hostname.to_string(),
+                        port,
+                        fingerprint: key.clone(),
                         reply,
                     }))
                     .context("sending HostVerify request to user")?;
@@ -149,6 +157,9 @@ impl crate::sessioninner::SessionInner {
                                 Trust and continue connecting?",
                                 remote_address, key_type, fingerprint
                             ),
+                            hostname: remote_host_name.to_string(),
+                            port,
+                            fingerprint: fingerprint.clone(),
                             reply,
                         }))
                         .context("sending HostVerify request to user")?;
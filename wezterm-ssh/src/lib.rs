@@ -7,6 +7,7 @@ mod config;
 mod dirwrap;
 mod filewrap;
 mod host;
+mod proxyjump;
 mod pty;
 mod session;
 mod sessioninner;
@@ -0,0 +1,367 @@
+//! Support for the `ProxyJump` option, which connects to the target
+//! host through a chain of intermediate "jump" hosts.
+//!
+//! Each jump host is connected to using its own `Session`, which is
+//! authenticated independently of the others; its events are relayed
+//! to the consumer of the session that is being established.
+//! Once a jump host is authenticated, a `direct-tcpip` channel is opened
+//! through it to the next host in the chain, and the ssh protocol for
+//! that next host is carried over that channel.
+//!
+//! Authenticated sessions are shared: connecting through a host that
+//! already has an established session, whether that is another jump
+//! or a session made by the embedding application, reuses it rather
+//! than connecting and authenticating again.  The shared sessions are
+//! held weakly, so that a session ends once all of the sessions that
+//! use it, and its own `Session` handles, have been dropped.
+use crate::config::{Config, ConfigMap};
+use crate::session::{Session, SessionEvent, SessionOwner};
+use anyhow::Context;
+use filedescriptor::FileDescriptor;
+use smol::channel::Sender;
+use std::collections::HashMap;
+use std::sync::{Mutex, Weak};
+
+/// Guards against a ProxyJump configuration that loops back on itself
+const MAX_JUMP_DEPTH: usize = 16;
+
+/// Tracks the number of hops taken to reach a given session
+const JUMP_DEPTH_KEY: &str = "wezterm_ssh_jump_depth";
+
+lazy_static::lazy_static! {
+    static ref CONNECTIONS: Mutex<HashMap<String, Weak<SessionOwner>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// One of the hosts listed in a `ProxyJump` option
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpHost {
+    pub user: Option<String>,
+    pub host: String,
+    pub port: Option<u16>,
+}
+
+impl JumpHost {
+    /// Parses a `[user@]host[:port]` or `ssh://[user@]host[:port]` spec
+    fn parse(spec: &str) -> anyhow::Result<Self> {
+        let spec = spec.trim();
+        let spec = spec.strip_prefix("ssh://").unwrap_or(spec);
+        let (user, host_port) = match spec.rsplit_once('@') {
+            Some((user, host_port)) => (Some(user.to_string()), host_port),
+            None => (None, spec),
+        };
+
+        let (host, port) = if let Some(rest) = host_port.strip_prefix('[') {
+            // An IPv6 address, such as `[::1]:2222`
+            let (host, rest) = rest
+                .split_once(']')
+                .with_context(|| format!("missing `]` in ProxyJump host {}", spec))?;
+            match rest.strip_prefix(':') {
+                Some(port) => (host, Some(port)),
+                None if rest.is_empty() => (host, None),
+                None => anyhow::bail!("invalid ProxyJump host {}", spec),
+            }
+        } else {
+            match host_port.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (host_port, None),
+            }
+        };
+
+        if host.is_empty() {
+            anyhow::bail!("ProxyJump host {} has no hostname", spec);
+        }
+        let port = match port {
+            Some(port) => Some(
+                port.parse::<u16>()
+                    .with_context(|| format!("invalid port in ProxyJump host {}", spec))?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            user,
+            host: host.to_string(),
+            port,
+        })
+    }
+}
+
+/// Parses the value of the `ProxyJump` option into the list of hosts,
+/// in the order that they are connected to.
+/// Returns an empty list if the option is `none`.
+pub fn parse_proxy_jump(value: &str) -> anyhow::Result<Vec<JumpHost>> {
+    let value = value.trim();
+    if value.is_empty() || value.eq_ignore_ascii_case("none") {
+        return Ok(vec![]);
+    }
+    value.split(',').map(JumpHost::parse).collect()
+}
+
+/// Returns the key that identifies the connection made for `config`.
+/// Sessions with the same key are interchangeable for the purpose of
+/// reaching the next hop.
+fn connection_key(config: &ConfigMap) -> Option<String> {
+    let mut key = format!(
+        "{}@{}:{}",
+        config.get("user")?,
+        config.get("hostname")?,
+        config.get("port")?
+    );
+    for option in &["proxyjump", "proxycommand"] {
+        if let Some(value) = config.get(*option) {
+            if !value.is_empty() && value != "none" {
+                key.push_str(&format!(" {}={}", option, value));
+            }
+        }
+    }
+    Some(key)
+}
+
+/// Removes the sessions that have ended, or that are no longer referenced
+fn prune_connections(connections: &mut HashMap<String, Weak<SessionOwner>>) {
+    connections.retain(|_, owner| {
+        Session::from_owner(owner)
+            .map(|session| !session.is_closed())
+            .unwrap_or(false)
+    });
+}
+
+/// Makes an authenticated session available for reuse as a jump host
+pub(crate) fn share_connection(config: &ConfigMap, owner: &Weak<SessionOwner>) {
+    if let Some(key) = connection_key(config) {
+        let mut connections = CONNECTIONS.lock().unwrap();
+        prune_connections(&mut connections);
+        connections.insert(key, owner.clone());
+    }
+}
+
+fn shared_connection(key: &str) -> Option<Session> {
+    let mut connections = CONNECTIONS.lock().unwrap();
+    prune_connections(&mut connections);
+    connections.get(key).and_then(Session::from_owner)
+}
+
+fn forget_connection(key: &str) {
+    CONNECTIONS.lock().unwrap().remove(key);
+}
+
+/// Resolves the configuration for the last of the `jumps` using
+/// `ssh_config`; the hops that precede it become its own ProxyJump option
+fn jump_host_config(
+    ssh_config: &Config,
+    config: &ConfigMap,
+    jumps: &[JumpHost],
+) -> anyhow::Result<ConfigMap> {
+    let (jump, preceding) = jumps.split_last().context("no jump hosts")?;
+
+    let depth = config
+        .get(JUMP_DEPTH_KEY)
+        .and_then(|depth| depth.parse::<usize>().ok())
+        .unwrap_or(0)
+        + 1;
+    if depth > MAX_JUMP_DEPTH {
+        anyhow::bail!(
+            "more than {} ProxyJump hops were needed to reach {}; \
+             check your ssh config for a ProxyJump loop",
+            MAX_JUMP_DEPTH,
+            jump.host
+        );
+    }
+
+    let mut hop = ssh_config.for_host(&jump.host);
+
+    if let Some(user) = &jump.user {
+        hop.insert("user".to_string(), user.to_string());
+    }
+    if let Some(port) = jump.port {
+        hop.insert("port".to_string(), port.to_string());
+    }
+    if !preceding.is_empty() {
+        let preceding = preceding
+            .iter()
+            .map(|jump| {
+                let mut spec = String::new();
+                if let Some(user) = &jump.user {
+                    spec.push_str(&format!("{}@", user));
+                }
+                if jump.host.contains(':') {
+                    spec.push_str(&format!("[{}]", jump.host));
+                } else {
+                    spec.push_str(&jump.host);
+                }
+                if let Some(port) = jump.port {
+                    spec.push_str(&format!(":{}", port));
+                }
+                spec
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        hop.insert("proxyjump".to_string(), preceding);
+        hop.remove("proxycommand");
+    }
+
+    // Use the same backend and diagnostics for every hop
+    for key in &["wezterm_ssh_backend", "wezterm_ssh_verbose"] {
+        if let Some(value) = config.get(*key) {
+            hop.insert(key.to_string(), value.to_string());
+        }
+    }
+    hop.insert(JUMP_DEPTH_KEY.to_string(), depth.to_string());
+
+    Ok(hop)
+}
+
+/// Establishes a session with the jump host that is configured by `hop`,
+/// relaying its banners, host verification and authentication requests
+/// via `tx_event`
+fn connect_jump_host(hop: ConfigMap, tx_event: &Sender<SessionEvent>) -> anyhow::Result<Session> {
+    let destination = format!(
+        "{}@{}:{}",
+        hop.get("user").map(|s| s.as_str()).unwrap_or(""),
+        hop.get("hostname").map(|s| s.as_str()).unwrap_or(""),
+        hop.get("port").map(|s| s.as_str()).unwrap_or("")
+    );
+
+    smol::block_on(tx_event.send(SessionEvent::Banner(Some(format!(
+        "Connecting to jump host {}",
+        destination
+    )))))
+    .context("notifying user of banner")?;
+
+    let (session, events) = Session::connect(hop.clone())?;
+    loop {
+        let event = smol::block_on(events.recv())
+            .with_context(|| format!("connecting to jump host {}", destination))?;
+        match event {
+            SessionEvent::Authenticated => break,
+            SessionEvent::Error(err) => {
+                anyhow::bail!("jump host {}: {}", destination, err);
+            }
+            event => {
+                smol::block_on(tx_event.send(event)).context("relaying jump host event to user")?
+            }
+        }
+    }
+
+    Ok(session)
+}
+
+/// Connects to `host` and `port` through the jump hosts listed in
+/// the `proxyjump` option of `config`.
+/// Returns the jump session, which must be kept alive for as long as
+/// the connection is in use, and the connection itself.
+pub(crate) fn connect_via_proxy_jump(
+    config: &ConfigMap,
+    host: &str,
+    port: u16,
+    tx_event: &Sender<SessionEvent>,
+) -> anyhow::Result<Option<(Session, FileDescriptor)>> {
+    let jumps = match config.get("proxyjump") {
+        Some(value) => parse_proxy_jump(value)?,
+        None => return Ok(None),
+    };
+    if jumps.is_empty() {
+        return Ok(None);
+    }
+
+    // The jump hosts are configured from the same files as `ssh` would use
+    let mut ssh_config = Config::new();
+    ssh_config.add_default_config_files();
+    let hop = jump_host_config(&ssh_config, config, &jumps)?;
+    if let Some(key) = connection_key(&hop) {
+        if let Some(session) = shared_connection(&key) {
+            match smol::block_on(session.direct_tcpip(host, port)) {
+                Ok(fd) => {
+                    log::debug!("reusing connection {} to reach {}:{}", key, host, port);
+                    return Ok(Some((session, fd)));
+                }
+                Err(err) => {
+                    // It may have been disconnected since it was shared;
+                    // fall back to making a new connection
+                    log::debug!("not reusing connection {}: {:#}", key, err);
+                    forget_connection(&key);
+                }
+            }
+        }
+    }
+
+    let session = connect_jump_host(hop.clone(), tx_event)?;
+    let fd = smol::block_on(session.direct_tcpip(host, port)).with_context(|| {
+        format!(
+            "opening a connection to {}:{} via jump host {}",
+            host,
+            port,
+            hop.get("hostname").map(|s| s.as_str()).unwrap_or("")
+        )
+    })?;
+    Ok(Some((session, fd)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn jump(user: Option<&str>, host: &str, port: Option<u16>) -> JumpHost {
+        JumpHost {
+            user: user.map(|s| s.to_string()),
+            host: host.to_string(),
+            port,
+        }
+    }
+
+    #[test]
+    fn parse_jumps() {
+        assert_eq!(parse_proxy_jump("none").unwrap(), vec![]);
+        assert_eq!(
+            parse_proxy_jump("bastion").unwrap(),
+            vec![jump(None, "bastion", None)]
+        );
+        assert_eq!(
+            parse_proxy_jump("me@bastion:2222,ssh://other@inner, [::1]:22").unwrap(),
+            vec![
+                jump(Some("me"), "bastion", Some(2222)),
+                jump(Some("other"), "inner", None),
+                jump(None, "::1", Some(22)),
+            ]
+        );
+        assert!(parse_proxy_jump("bastion:notaport").is_err());
+        assert!(parse_proxy_jump("me@").is_err());
+        assert!(parse_proxy_jump("[::1").is_err());
+    }
+
+    #[test]
+    fn hop_config() {
+        let mut ssh_config = Config::new();
+        let mut fake_env = ConfigMap::new();
+        fake_env.insert("HOME".to_string(), "/home/me".to_string());
+        fake_env.insert("USER".to_string(), "me".to_string());
+        ssh_config.assign_environment(fake_env);
+        ssh_config.add_config_string(
+            r#"
+        Host second
+            HostName 10.0.0.2
+            User second
+            "#,
+        );
+
+        let mut config = ConfigMap::new();
+        config.insert("wezterm_ssh_backend".to_string(), "ssh2".to_string());
+        let jumps = parse_proxy_jump("a@first,second:2222,b@[::1]").unwrap();
+
+        let hop = jump_host_config(&ssh_config, &config, &jumps).unwrap();
+        assert_eq!(hop.get("user").unwrap(), "b");
+        assert_eq!(hop.get("hostname").unwrap(), "::1");
+        assert_eq!(hop.get("proxyjump").unwrap(), "a@first,second:2222");
+        assert_eq!(hop.get("wezterm_ssh_backend").unwrap(), "ssh2");
+        assert_eq!(hop.get(JUMP_DEPTH_KEY).unwrap(), "1");
+
+        let jumps = parse_proxy_jump(&hop["proxyjump"]).unwrap();
+        let hop = jump_host_config(&ssh_config, &hop, &jumps).unwrap();
+        assert_eq!(hop.get("user").unwrap(), "second");
+        assert_eq!(hop.get("hostname").unwrap(), "10.0.0.2");
+        assert_eq!(hop.get("port").unwrap(), "2222");
+        assert_eq!(hop.get("proxyjump").unwrap(), "a@first");
+        assert_eq!(hop.get(JUMP_DEPTH_KEY).unwrap(), "2");
+    }
+}
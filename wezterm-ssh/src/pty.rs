@@ -290,6 +290,7 @@ impl crate::sessioninner::SessionInner {
                     buf: VecDeque::with_capacity(8192),
                 },
             ],
+            forwarded: false,
         };
 
         self.channels.insert(channel_id, info);
@@ -9,7 +9,7 @@ use portable_pty::PtySize;
 use smol::channel::{bounded, Receiver, Sender};
 use std::collections::HashMap;
 use std::io::Write;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, Weak};
 
 #[derive(Debug)]
 pub enum SessionEvent {
@@ -50,6 +50,7 @@ pub(crate) enum SessionRequest {
     NewPty(NewPty, Sender<anyhow::Result<(SshPty, SshChildProcess)>>),
     ResizePty(ResizePty, Option<Sender<anyhow::Result<()>>>),
     Exec(Exec, Sender<anyhow::Result<ExecResult>>),
+    DirectTcpip(DirectTcpip, Sender<anyhow::Result<FileDescriptor>>),
     Sftp(SftpRequest),
     SignalChannel(SignalChannel),
     SessionDropped,
@@ -67,18 +68,31 @@ pub(crate) struct Exec {
     pub env: Option<HashMap<String, String>>,
 }
 
-#[derive(Clone)]
-pub struct Session {
+#[derive(Debug)]
+pub(crate) struct DirectTcpip {
+    pub host: String,
+    pub port: u16,
+}
+
+/// Shared by the clones of a `Session`; the session is told that it
+/// is no longer in use once the last of them has been dropped
+pub(crate) struct SessionOwner {
     tx: SessionSender,
 }
 
-impl Drop for Session {
+impl Drop for SessionOwner {
     fn drop(&mut self) {
         self.tx.try_send(SessionRequest::SessionDropped).ok();
         log::trace!("Drop Session");
     }
 }
 
+#[derive(Clone)]
+pub struct Session {
+    tx: SessionSender,
+    owner: Arc<SessionOwner>,
+}
+
 impl Session {
     pub fn connect(config: ConfigMap) -> anyhow::Result<(Self, Receiver<SessionEvent>)> {
         let (tx_event, rx_event) = bounded(8);
@@ -91,9 +105,14 @@ impl Session {
             tx: tx_req,
             pipe: Arc::new(Mutex::new(sender_write)),
         };
+        let owner = Arc::new(SessionOwner {
+            tx: session_sender.clone(),
+        });
 
         let mut inner = SessionInner {
             config,
+            owner: Arc::downgrade(&owner),
+            jump_session: None,
             tx_event,
             rx_req,
             channels: HashMap::new(),
@@ -105,7 +124,13 @@ impl Session {
             session_was_dropped: false,
         };
         std::thread::spawn(move || inner.run());
-        Ok((Self { tx: session_sender }, rx_event))
+        Ok((
+            Self {
+                tx: session_sender,
+                owner,
+            },
+            rx_event,
+        ))
     }
 
     pub async fn request_pty(
@@ -153,6 +178,37 @@ impl Session {
         Ok(exec)
     }
 
+    /// Opens a connection to `host` and `port`, as seen from the remote
+    /// host, that is tunnelled through this session.
+    /// This is used to connect to the next host of a ProxyJump chain.
+    pub async fn direct_tcpip(&self, host: &str, port: u16) -> anyhow::Result<FileDescriptor> {
+        let (reply, rx) = bounded(1);
+        self.tx
+            .send(SessionRequest::DirectTcpip(
+                DirectTcpip {
+                    host: host.to_string(),
+                    port,
+                },
+                reply,
+            ))
+            .await?;
+        rx.recv().await?
+    }
+
+    /// Returns true if the session has ended
+    pub(crate) fn is_closed(&self) -> bool {
+        self.tx.tx.is_closed()
+    }
+
+    /// Returns a new reference to a session that is still in use
+    pub(crate) fn from_owner(owner: &Weak<SessionOwner>) -> Option<Self> {
+        let owner = owner.upgrade()?;
+        Some(Self {
+            tx: owner.tx.clone(),
+            owner,
+        })
+    }
+
     /// Creates a new reference to the sftp channel for filesystem operations
     ///
     /// ### Note
@@ -2,8 +2,12 @@ use crate::channelwrap::ChannelWrap;
 use crate::config::ConfigMap;
 use crate::dirwrap::DirWrap;
 use crate::filewrap::FileWrap;
+use crate::proxyjump::{connect_via_proxy_jump, share_connection};
 use crate::pty::*;
-use crate::session::{Exec, ExecResult, SessionEvent, SessionRequest, SignalChannel};
+use crate::session::{
+    DirectTcpip, Exec, ExecResult, Session, SessionEvent, SessionOwner, SessionRequest,
+    SignalChannel,
+};
 use crate::sessionwrap::SessionWrap;
 use crate::sftp::dir::{Dir, DirId, DirRequest};
 use crate::sftp::file::{File, FileId, FileRequest};
@@ -18,6 +22,7 @@ use portable_pty::ExitStatus;
 use smol::channel::{bounded, Receiver, Sender, TryRecvError};
 use std::collections::{HashMap, VecDeque};
 use std::io::{Read, Write};
+use std::sync::Weak;
 use std::time::Duration;
 
 #[derive(Debug)]
@@ -31,12 +36,21 @@ pub(crate) struct ChannelInfo {
     pub channel: ChannelWrap,
     pub exit: Option<Sender<ExitStatus>>,
     pub descriptors: [DescriptorState; 3],
+    /// True for a direct-tcpip channel, whose stdin and stdout
+    /// descriptors are the same socket
+    pub forwarded: bool,
 }
 
 pub(crate) type ChannelId = usize;
 
 pub(crate) struct SessionInner {
     pub config: ConfigMap,
+    /// Used to share this session with other sessions that jump
+    /// through it; it doesn't keep the session alive
+    pub owner: Weak<SessionOwner>,
+    /// The session with the jump host through which this
+    /// session is connected, if any
+    pub jump_session: Option<Session>,
     pub tx_event: Sender<SessionEvent>,
     pub rx_req: Receiver<SessionRequest>,
     pub channels: HashMap<ChannelId, ChannelInfo>,
@@ -189,7 +203,25 @@ impl SessionInner {
                 break;
             }
         }
-        if let Some(cmd) = self.config.get("proxycommand") {
+        if let Some((jump_session, sock)) =
+            connect_via_proxy_jump(&self.config, &hostname, port, &self.tx_event)?
+        {
+            #[cfg(unix)]
+            let sock = {
+                use std::os::unix::io::IntoRawFd;
+                sock.into_raw_fd()
+            };
+            #[cfg(windows)]
+            let sock = {
+                use std::os::windows::io::IntoRawSocket;
+                sock.into_raw_socket()
+            };
+            // libssh takes ownership of the socket.  Clear any ProxyCommand
+            // that libssh derived from ProxyJump when it parsed the config.
+            sess.set_option(libssh_rs::SshOption::ProxyCommand(None))?;
+            sess.set_option(libssh_rs::SshOption::Socket(sock))?;
+            self.jump_session.replace(jump_session);
+        } else if let Some(cmd) = self.config.get("proxycommand") {
             sess.set_option(libssh_rs::SshOption::ProxyCommand(Some(cmd.to_string())))?;
         }
         if let Some(types) = self.config.get("pubkeyacceptedtypes") {
@@ -217,6 +249,7 @@ impl SessionInner {
                 .context("notifying user of banner")?;
         }
 
+        share_connection(&self.config, &self.owner);
         self.tx_event
             .try_send(SessionEvent::Authenticated)
             .context("notifying user that session is authenticated")?;
@@ -254,14 +287,18 @@ impl SessionInner {
             ))))
             .context("notifying user of banner")?;
 
-        let sock: Socket = if let Some(proxy_command) =
-            self.config.get("proxycommand").and_then(|c| {
-                if !c.is_empty() && c != "none" {
-                    Some(c)
-                } else {
-                    None
-                }
-            }) {
+        let sock: Socket = if let Some((jump_session, sock)) =
+            connect_via_proxy_jump(&self.config, &hostname, port, &self.tx_event)?
+        {
+            self.jump_session.replace(jump_session);
+            socket_from_descriptor(sock)
+        } else if let Some(proxy_command) = self.config.get("proxycommand").and_then(|c| {
+            if !c.is_empty() && c != "none" {
+                Some(c)
+            } else {
+                None
+            }
+        }) {
             let mut cmd;
             if cfg!(windows) {
                 let comspec = std::env::var("COMSPEC").unwrap_or_else(|_| "cmd".to_string());
@@ -281,16 +318,7 @@ impl SessionInner {
                 .spawn()
                 .with_context(|| format!("spawning ProxyCommand {}", proxy_command))?;
 
-            #[cfg(unix)]
-            unsafe {
-                use std::os::unix::io::{FromRawFd, IntoRawFd};
-                Socket::from_raw_fd(a.into_raw_fd())
-            }
-            #[cfg(windows)]
-            unsafe {
-                use std::os::windows::io::{FromRawSocket, IntoRawSocket};
-                Socket::from_raw_socket(a.into_raw_socket())
-            }
+            socket_from_descriptor(a)
         } else {
             let addr = (hostname.as_str(), port)
                 .to_socket_addrs()?
@@ -336,6 +364,7 @@ impl SessionInner {
         self.authenticate(&sess, &user, &hostname)
             .context("authentication")?;
 
+        share_connection(&self.config, &self.owner);
         self.tx_event
             .try_send(SessionEvent::Authenticated)
             .context("notifying user that session is authenticated")?;
@@ -498,6 +527,12 @@ impl SessionInner {
                 }
             }
 
+            if chan.forwarded && chan.descriptors[1].fd.is_none() {
+                // The remote end closed the connection; close our end
+                // of the socket so that its peer sees EOF
+                chan.descriptors[0].fd.take();
+            }
+
             if chan
                 .descriptors
                 .iter()
@@ -549,6 +584,9 @@ impl SessionInner {
                     SessionRequest::Exec(exec, reply) => {
                         dispatch(reply, || self.exec(sess, exec), "exec")
                     }
+                    SessionRequest::DirectTcpip(direct, reply) => {
+                        dispatch(reply, || self.direct_tcpip(sess, direct), "direct_tcpip")
+                    }
                     SessionRequest::SignalChannel(info) => {
                         if let Err(err) = self.signal_channel(&info) {
                             log::error!("{:?} -> error: {:#}", info, err);
@@ -683,6 +721,19 @@ impl SessionInner {
                             "fsync",
                         )
                     }
+                    SessionRequest::Sftp(SftpRequest::File(FileRequest::Seek(msg, reply))) => {
+                        dispatch(
+                            reply,
+                            || {
+                                let file = self
+                                    .files
+                                    .get_mut(&msg.file_id)
+                                    .ok_or_else(|| anyhow!("invalid file_id"))?;
+                                file.seek(msg.pos)
+                            },
+                            "seek_file",
+                        )
+                    }
 
                     SessionRequest::Sftp(SftpRequest::ReadDir(path, reply)) => {
                         dispatch(reply, || self.init_sftp(sess)?.read_dir(&path), "read_dir")
@@ -819,6 +870,7 @@ impl SessionInner {
                     buf: VecDeque::with_capacity(8192),
                 },
             ],
+            forwarded: false,
         };
 
         self.channels.insert(channel_id, info);
@@ -826,6 +878,49 @@ impl SessionInner {
         Ok(result)
     }
 
+    /// Opens a direct-tcpip channel to the requested host and port,
+    /// returning one end of a socketpair that is bridged to it
+    pub fn direct_tcpip(
+        &mut self,
+        sess: &mut SessionWrap,
+        direct: DirectTcpip,
+    ) -> anyhow::Result<FileDescriptor> {
+        let channel = sess
+            .open_direct_tcpip(&direct.host, direct.port)
+            .with_context(|| format!("opening channel to {}:{}", direct.host, direct.port))?;
+
+        let channel_id = self.next_channel_id;
+        self.next_channel_id += 1;
+
+        let (mut ours, theirs) = socketpair()?;
+        ours.set_non_blocking(true)?;
+
+        let info = ChannelInfo {
+            channel_id,
+            channel,
+            exit: None,
+            descriptors: [
+                DescriptorState {
+                    fd: Some(ours.try_clone()?),
+                    buf: VecDeque::with_capacity(8192),
+                },
+                DescriptorState {
+                    fd: Some(ours),
+                    buf: VecDeque::with_capacity(8192),
+                },
+                DescriptorState {
+                    fd: None,
+                    buf: VecDeque::new(),
+                },
+            ],
+            forwarded: true,
+        };
+
+        self.channels.insert(channel_id, info);
+
+        Ok(theirs)
+    }
+
     /// Open a handle to a file.
     pub fn open_with_mode(
         &mut self,
@@ -882,6 +977,20 @@ impl SessionInner {
     }
 }
 
+#[cfg(feature = "ssh2")]
+fn socket_from_descriptor(fd: FileDescriptor) -> socket2::Socket {
+    #[cfg(unix)]
+    unsafe {
+        use std::os::unix::io::{FromRawFd, IntoRawFd};
+        socket2::Socket::from_raw_fd(fd.into_raw_fd())
+    }
+    #[cfg(windows)]
+    unsafe {
+        use std::os::windows::io::{FromRawSocket, IntoRawSocket};
+        socket2::Socket::from_raw_socket(fd.into_raw_socket())
+    }
+}
+
 fn write_from_buf<W: Write>(w: &mut W, buf: &mut VecDeque<u8>) -> std::io::Result<()> {
     match w.write(buf.make_contiguous()) {
         Ok(len) => {
@@ -92,4 +92,22 @@ impl SessionWrap {
             }
         }
     }
+
+    pub fn open_direct_tcpip(&self, host: &str, port: u16) -> anyhow::Result<ChannelWrap> {
+        match self {
+            #[cfg(feature = "ssh2")]
+            Self::Ssh2(sess) => {
+                let channel = sess.sess.channel_direct_tcpip(host, port, None)?;
+                Ok(ChannelWrap::Ssh2(channel))
+            }
+
+            #[cfg(feature = "libssh-rs")]
+            Self::LibSsh(sess) => {
+                let channel = sess.sess.new_channel()?;
+                // The originator address is informational only
+                channel.open_forward(host, port, "127.0.0.1", 0)?;
+                Ok(ChannelWrap::LibSsh(channel))
+            }
+        }
+    }
 }
@@ -32,6 +32,7 @@ pub(crate) enum FileRequest {
     SetMetadata(SetMetadataFile, Sender<SftpChannelResult<()>>),
     Metadata(FileId, Sender<SftpChannelResult<Metadata>>),
     Fsync(FileId, Sender<SftpChannelResult<()>>),
+    Seek(SeekFile, Sender<SftpChannelResult<()>>),
 }
 
 #[derive(Debug)]
@@ -46,6 +47,12 @@ pub(crate) struct ReadFile {
     pub max_bytes: usize,
 }
 
+#[derive(Debug)]
+pub(crate) struct SeekFile {
+    pub file_id: FileId,
+    pub pos: u64,
+}
+
 #[derive(Debug)]
 pub(crate) struct SetMetadataFile {
     pub file_id: FileId,
@@ -124,6 +131,27 @@ impl File {
         Ok(result)
     }
 
+    /// Moves the read/write position of this handle to `pos` bytes from
+    /// the start of the file.
+    ///
+    /// See [`std::io::Seek::seek`] for more information.
+    pub async fn seek(&self, pos: u64) -> SftpChannelResult<()> {
+        let (reply, rx) = bounded(1);
+        self.tx
+            .as_ref()
+            .unwrap()
+            .send(SessionRequest::Sftp(SftpRequest::File(FileRequest::Seek(
+                SeekFile {
+                    file_id: self.file_id,
+                    pos,
+                },
+                reply,
+            ))))
+            .await?;
+        let result = rx.recv().await??;
+        Ok(result)
+    }
+
     /// This function causes the remote server to synchronize the file data and metadata to disk
     /// (like fsync(2)).
     ///
@@ -1,6 +1,6 @@
 use super::{SessionRequest, SessionSender};
 use crate::sftp::dir::{Dir, DirRequest};
-use crate::sftp::file::{File, FileRequest};
+use crate::sftp::file::FileRequest;
 use crate::sftp::types::{Metadata, OpenFileType, OpenOptions, RenameOptions, WriteMode};
 use camino::Utf8PathBuf;
 use error::SftpError;
@@ -14,6 +14,8 @@ pub(crate) mod error;
 pub(crate) mod file;
 pub(crate) mod types;
 
+pub use file::File;
+
 fn into_invalid_data<E>(err: E) -> io::Error
 where
     E: Into<Box<dyn std::error::Error + Send + Sync>>,
@@ -1,8 +1,8 @@
 use anyhow::{anyhow, Context};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use clap::{Parser, ValueHint};
 use clap_complete::{generate as generate_completion, Shell};
-use config::keyassignment::SpawnTabDomain;
+use config::keyassignment::{PaneDirection, RotationDirection, SpawnTabDomain};
 use config::wezterm_version;
 use mux::activity::Activity;
 use mux::pane::PaneId;
@@ -11,8 +11,10 @@ use mux::window::WindowId;
 use mux::Mux;
 use portable_pty::cmdbuilder::CommandBuilder;
 use serde::Serializer as _;
+use std::convert::TryFrom;
 use std::ffi::OsString;
 use std::io::{Read, Write};
+use std::path::PathBuf;
 use std::rc::Rc;
 use tabout::{tabulate_output, Alignment, Column};
 use umask::UmaskSaver;
@@ -157,6 +159,43 @@ struct CliOutputFormat {
     format: CliOutputFormatKind,
 }
 
+#[derive(Debug, Parser, Clone)]
+struct TlsCommand {
+    #[clap(subcommand)]
+    sub: TlsSubCommand,
+}
+
+#[derive(Debug, Parser, Clone)]
+enum TlsSubCommand {
+    #[clap(
+        name = "trust",
+        about = "manage the server certificates that were trusted on first use"
+    )]
+    Trust(TlsTrustCommand),
+}
+
+#[derive(Debug, Parser, Clone)]
+struct TlsTrustCommand {
+    #[clap(subcommand)]
+    sub: TlsTrustSubCommand,
+}
+
+#[derive(Debug, Parser, Clone)]
+enum TlsTrustSubCommand {
+    /// List the trusted server certificates of TLS domains
+    #[clap(name = "list")]
+    List(CliOutputFormat),
+
+    /// Forget the trusted server certificate of a TLS domain, so that
+    /// the next connection asks whether to trust the certificate that
+    /// the server presents
+    #[clap(name = "revoke")]
+    Revoke {
+        /// The name of the TLS domain
+        domain: String,
+    },
+}
+
 #[derive(Debug, Parser, Clone)]
 enum CliSubCommand {
     #[clap(name = "list", about = "list windows, tabs and panes")]
@@ -171,6 +210,9 @@ enum CliSubCommand {
     #[clap(name = "tlscreds", about = "obtain tls credentials")]
     TlsCreds,
 
+    #[clap(name = "tls", about = "manage the certificates of TLS domains")]
+    Tls(TlsCommand),
+
     #[clap(
         name = "move-pane-to-new-tab",
         rename_all = "kebab",
@@ -206,7 +248,8 @@ enum CliSubCommand {
         rename_all = "kebab",
         trailing_var_arg = true,
         about = "split the current pane.
-Outputs the pane-id for the newly created pane on success"
+Outputs the pane-id for the newly created pane on success.
+With --layout, outputs the pane-id of each created pane, one per line"
     )]
     SplitPane {
         /// Specify the pane that should be split.
@@ -261,6 +304,22 @@ Outputs the pane-id for the newly created pane on success"
         #[clap(long, conflicts_with_all=&["cwd", "prog"])]
         move_pane_id: Option<PaneId>,
 
+        /// Apply the nested splits described by the JSON in FILE
+        /// to the pane, rather than making a single split.
+        /// FILE holds a list of splits in the same form as the
+        /// `splits` of a pane in `workspace_layouts`.
+        /// Use `-` to read the layout from stdin.
+        #[clap(
+            long,
+            parse(from_os_str),
+            value_hint=ValueHint::FilePath,
+            conflicts_with_all=&[
+                "horizontal", "left", "right", "top", "bottom", "top-level",
+                "cells", "percent", "cwd", "move-pane-id", "prog"
+            ]
+        )]
+        layout: Option<PathBuf>,
+
         /// Instead of executing your shell, run PROG.
         /// For example: `wezterm cli split-pane -- bash -l` will spawn bash
         /// as if it were a login shell.
@@ -297,7 +356,10 @@ Outputs the pane-id for the newly created pane on success"
         new_window: bool,
 
         /// Specify the current working directory for the initially
-        /// spawned program
+        /// spawned program.
+        /// When used together with --domain-name, an absolute path is
+        /// checked on the filesystem of that domain, such as the remote
+        /// host of an ssh domain, rather than locally.
         #[clap(long = "cwd", parse(from_os_str), value_hint=ValueHint::DirPath)]
         cwd: Option<OsString>,
 
@@ -331,8 +393,299 @@ Outputs the pane-id for the newly created pane on success"
         /// The text to send. If omitted, will read the text from stdin.
         text: Option<String>,
     },
+
+    /// Copy a local file to the filesystem that hosts a pane.
+    /// For panes in a remote mux server or ssh domain, the file is
+    /// copied to the remote host over the existing connection.
+    #[clap(name = "upload", rename_all = "kebab")]
+    Upload {
+        /// Specify the target pane.
+        /// The default is to use the current pane based on the
+        /// environment variable WEZTERM_PANE.
+        #[clap(long)]
+        pane_id: Option<PaneId>,
+
+        /// The local file to upload
+        #[clap(parse(from_os_str), value_hint=ValueHint::FilePath)]
+        local: PathBuf,
+
+        /// The destination path.  Relative paths are resolved against
+        /// the current working directory of the pane.
+        /// If omitted, the file name of the local file is used.
+        remote: Option<String>,
+    },
+
+    /// Copy a file from the filesystem that hosts a pane to the
+    /// local filesystem.
+    /// For panes in a remote mux server or ssh domain, the file is
+    /// copied from the remote host over the existing connection.
+    #[clap(name = "download", rename_all = "kebab")]
+    Download {
+        /// Specify the source pane.
+        /// The default is to use the current pane based on the
+        /// environment variable WEZTERM_PANE.
+        #[clap(long)]
+        pane_id: Option<PaneId>,
+
+        /// The path of the file to download.  Relative paths are resolved
+        /// against the current working directory of the pane.
+        remote: String,
+
+        /// The local destination path.
+        /// If omitted, the file is saved to the current directory
+        /// using the file name of the remote file.
+        #[clap(parse(from_os_str), value_hint=ValueHint::FilePath)]
+        local: Option<PathBuf>,
+    },
+
+    /// Stream events from the mux as JSON, one object per line.
+    /// This allows external tools to react to changes without
+    /// having to poll `wezterm cli list`.
+    #[clap(name = "subscribe")]
+    Subscribe {
+        /// A comma separated list of the events to receive.
+        /// Possible events are "pane-output", "focus", "resize",
+        /// "pane-added" and "pane-removed".
+        /// The default is to receive all of them.
+        #[clap(long, parse(try_from_str = parse_event_kinds))]
+        events: Option<MuxEventKinds>,
+    },
+
+    /// Show the commands that were run in panes with shell
+    /// integration enabled, most recent first.
+    #[clap(name = "history", rename_all = "kebab")]
+    History {
+        /// Only show the commands that were run in this pane
+        #[clap(long)]
+        pane_id: Option<PaneId>,
+
+        /// Only show the commands that contain this text
+        #[clap(long)]
+        search: Option<String>,
+
+        /// Show at most this many commands
+        #[clap(long)]
+        limit: Option<usize>,
+
+        /// Controls the output format.
+        /// "table" and "json" are possible formats.
+        #[clap(long, default_value = "table")]
+        format: CliOutputFormatKind,
+    },
+
+    /// Activate an adjacent pane in the specified direction,
+    /// in the same way as the `ActivatePaneDirection` key assignment.
+    #[clap(name = "activate-pane-direction", rename_all = "kebab")]
+    ActivatePaneDirection {
+        /// Specify the current pane.
+        /// The default is to use the current pane based on the
+        /// environment variable WEZTERM_PANE.
+        #[clap(long)]
+        pane_id: Option<PaneId>,
+
+        /// The direction to move: one of "up", "down", "left",
+        /// "right", "next" or "prev"
+        #[clap(parse(try_from_str = parse_pane_direction))]
+        direction: PaneDirection,
+    },
+
+    /// Rotate the panes in the tab that contains the current pane,
+    /// in the same way as the `RotatePanes` key assignment.
+    #[clap(name = "rotate-panes", rename_all = "kebab")]
+    RotatePanes {
+        /// Specify the current pane.
+        /// The default is to use the current pane based on the
+        /// environment variable WEZTERM_PANE.
+        #[clap(long)]
+        pane_id: Option<PaneId>,
+
+        /// The direction to rotate: either "clockwise" or
+        /// "counter-clockwise"
+        #[clap(
+            parse(try_from_str = parse_rotation_direction),
+            default_value = "clockwise"
+        )]
+        direction: RotationDirection,
+    },
+
+    /// Exchange the positions of two panes, which may be in different
+    /// tabs, preserving the sizes of the positions.
+    #[clap(name = "swap-panes", rename_all = "kebab")]
+    SwapPanes {
+        /// Specify the current pane.
+        /// The default is to use the current pane based on the
+        /// environment variable WEZTERM_PANE.
+        #[clap(long)]
+        pane_id: Option<PaneId>,
+
+        /// The pane to exchange positions with
+        other_pane_id: PaneId,
+    },
+
+    /// Complete a partially typed path on the filesystem of a domain,
+    /// printing each of the candidates on its own line.
+    /// This can be used from shell completion functions to complete
+    /// paths on the remote host of an ssh domain.
+    #[clap(name = "complete-path", rename_all = "kebab")]
+    CompletePath {
+        /// Specify the current pane.
+        /// The default is to use the current pane based on the
+        /// environment variable WEZTERM_PANE.
+        /// The domain of the pane is used unless --domain-name is given.
+        #[clap(long)]
+        pane_id: Option<PaneId>,
+
+        /// The name of the domain whose filesystem is used
+        #[clap(long)]
+        domain_name: Option<String>,
+
+        /// Only complete directories, such as for --cwd
+        #[clap(long)]
+        dirs_only: bool,
+
+        /// The path to complete.  Relative paths are relative to the
+        /// home directory of the user.
+        #[clap(default_value = "")]
+        partial: String,
+    },
+
+    /// Attach all of the domains in one of the `domain_groups`
+    /// from the configuration of the wezterm GUI.
+    #[clap(name = "attach-group")]
+    AttachGroup {
+        /// The name of the domain group
+        name: String,
+    },
+
+    /// Detach all of the domains in one of the `domain_groups`
+    /// from the configuration of the wezterm GUI.
+    #[clap(name = "detach-group")]
+    DetachGroup {
+        /// The name of the domain group
+        name: String,
+    },
+
+    /// Create the windows, tabs and panes described by one of the
+    /// `workspace_layouts` from the configuration of the wezterm GUI.
+    #[clap(name = "apply-layout")]
+    ApplyLayout {
+        /// The name of the workspace layout
+        name: String,
+    },
+
+    /// Rename a workspace
+    #[clap(name = "rename-workspace")]
+    RenameWorkspace {
+        /// The workspace to rename.
+        /// The default is the workspace of the current pane based on
+        /// the environment variable WEZTERM_PANE.
+        #[clap(long)]
+        workspace: Option<String>,
+
+        /// The new name for the workspace
+        new_workspace: String,
+    },
+
+    /// Move the windows of a workspace into another workspace
+    #[clap(name = "merge-workspace")]
+    MergeWorkspace {
+        /// The workspace whose windows are moved.
+        /// The default is the workspace of the current pane based on
+        /// the environment variable WEZTERM_PANE.
+        #[clap(long)]
+        workspace: Option<String>,
+
+        /// The workspace that receives the windows
+        into_workspace: String,
+    },
+
+    /// Close all of the windows in a workspace, killing their panes
+    #[clap(name = "delete-workspace")]
+    DeleteWorkspace {
+        /// The workspace to delete.
+        /// The default is the workspace of the current pane based on
+        /// the environment variable WEZTERM_PANE.
+        #[clap(long)]
+        workspace: Option<String>,
+    },
+
+    /// Print the scrollback of a pane as text, as text with
+    /// escape sequences, or as a standalone HTML document
+    #[clap(name = "export-scrollback", rename_all = "kebab")]
+    ExportScrollback {
+        /// Specify the pane to export.
+        /// The default is to use the current pane based on the
+        /// environment variable WEZTERM_PANE.
+        #[clap(long)]
+        pane_id: Option<PaneId>,
+
+        /// The output format: one of "text", "ansi" or "html"
+        #[clap(long, default_value = "text")]
+        format: mux::export::ExportFormat,
+
+        /// The first line to export.
+        /// 0 is the first line of the screen and negative numbers
+        /// refer to lines in the scrollback.
+        /// The default is the start of the scrollback.
+        #[clap(long, allow_hyphen_values = true)]
+        start_line: Option<isize>,
+
+        /// The last line to export, using the same numbering as
+        /// --start-line.  The default is the bottom of the screen.
+        #[clap(long, allow_hyphen_values = true)]
+        end_line: Option<isize>,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct MuxEventKinds(Vec<codec::MuxEventKind>);
+
+const MUX_EVENT_NAMES: &[(&str, codec::MuxEventKind)] = &[
+    ("pane-output", codec::MuxEventKind::PaneOutput),
+    ("focus", codec::MuxEventKind::PaneFocused),
+    ("resize", codec::MuxEventKind::PaneResized),
+    ("pane-added", codec::MuxEventKind::PaneAdded),
+    ("pane-removed", codec::MuxEventKind::PaneRemoved),
+];
+
+fn parse_event_kinds(s: &str) -> anyhow::Result<MuxEventKinds> {
+    let mut kinds = vec![];
+    for name in s.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let kind = MUX_EVENT_NAMES
+            .iter()
+            .find(|(candidate, _)| *candidate == name)
+            .map(|(_, kind)| *kind)
+            .ok_or_else(|| anyhow!("unknown event {}", name))?;
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+    Ok(MuxEventKinds(kinds))
+}
+
+fn parse_pane_direction(s: &str) -> anyhow::Result<PaneDirection> {
+    match s.to_ascii_lowercase().as_str() {
+        "up" => Ok(PaneDirection::Up),
+        "down" => Ok(PaneDirection::Down),
+        "left" => Ok(PaneDirection::Left),
+        "right" => Ok(PaneDirection::Right),
+        "next" => Ok(PaneDirection::Next),
+        "prev" => Ok(PaneDirection::Prev),
+        _ => Err(anyhow!("unknown direction {}", s)),
+    }
 }
 
+fn parse_rotation_direction(s: &str) -> anyhow::Result<RotationDirection> {
+    match s.to_ascii_lowercase().as_str() {
+        "clockwise" => Ok(RotationDirection::Clockwise),
+        "counter-clockwise" | "counterclockwise" => Ok(RotationDirection::CounterClockwise),
+        _ => Err(anyhow!("unknown rotation direction {}", s)),
+    }
+}
+
+/// The size of each chunk sent by `wezterm cli upload` and `download`
+const FILE_TRANSFER_CHUNK_SIZE: usize = 256 * 1024;
+
 use termwiz::escape::osc::{
     ITermDimension, ITermFileData, ITermProprietary, OperatingSystemCommand,
 };
@@ -426,6 +779,13 @@ impl SetCwdCommand {
     }
 }
 
+/// Returns true if `path` is absolute, either locally or as a path
+/// on a unix host, which is how paths on the remote host of an ssh
+/// domain are written
+fn is_absolute_path(path: &OsString) -> bool {
+    std::path::Path::new(path).is_absolute() || path.to_string_lossy().starts_with('/')
+}
+
 fn canon_cwd(cwd: Option<OsString>) -> anyhow::Result<Option<String>> {
     match cwd {
         None => Ok(None),
@@ -569,6 +929,67 @@ async fn resolve_pane_id(client: &Client, pane_id: Option<PaneId>) -> anyhow::Re
     Ok(pane_id)
 }
 
+/// Applies the splits that are described by the JSON layout in
+/// `path` to `pane_id`, printing the id of each pane that was created,
+/// in the order that they were created.  The splits are made by the
+/// server, each targeting a pane by its id, so the result doesn't
+/// depend on which pane is active in the meantime.
+async fn split_pane_with_layout(
+    client: &Client,
+    pane_id: PaneId,
+    path: PathBuf,
+) -> anyhow::Result<()> {
+    let splits = if path.as_os_str() == "-" {
+        let mut json = String::new();
+        std::io::stdin().read_to_string(&mut json)?;
+        json
+    } else {
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("reading layout from {}", path.display()))?
+    };
+    // Report a malformed layout without involving the server
+    mux::layout::splits_from_json(&splits)?;
+
+    let response = client
+        .split_pane_with_layout(codec::SplitPaneWithLayout { pane_id, splits })
+        .await?;
+    // Print the panes that were created even if a later split
+    // failed, so that they can be cleaned up
+    for pane_id in response.pane_ids {
+        println!("{}", pane_id);
+    }
+    match response.error {
+        Some(err) => anyhow::bail!("{}", err),
+        None => Ok(()),
+    }
+}
+
+/// Returns `workspace` if it was specified, or else the workspace
+/// that contains the current pane
+async fn resolve_workspace(client: &Client, workspace: Option<String>) -> anyhow::Result<String> {
+    if let Some(workspace) = workspace {
+        return Ok(workspace);
+    }
+    let pane_id = resolve_pane_id(client, None).await?;
+    let panes = client.list_panes().await?;
+    for tabroot in panes.tabs {
+        let mut cursor = tabroot.into_tree().cursor();
+
+        loop {
+            if let Some(entry) = cursor.leaf_mut() {
+                if entry.pane_id == pane_id {
+                    return Ok(entry.workspace.clone());
+                }
+            }
+            match cursor.preorder_next() {
+                Ok(c) => cursor = c,
+                Err(_) => break,
+            }
+        }
+    }
+    anyhow::bail!("unable to determine the workspace of pane {}", pane_id);
+}
+
 #[derive(serde::Serialize)]
 struct CliListResultPtySize {
     rows: usize,
@@ -587,6 +1008,14 @@ struct CliListResultItem {
     size: CliListResultPtySize,
     title: String,
     cwd: String,
+    resource_usage: Option<CliListResultResourceUsage>,
+}
+
+#[derive(serde::Serialize)]
+struct CliListResultResourceUsage {
+    cpu_percent: f64,
+    memory_bytes: u64,
+    process_count: usize,
 }
 
 impl From<mux::tab::PaneEntry> for CliListResultItem {
@@ -599,6 +1028,7 @@ impl From<mux::tab::PaneEntry> for CliListResultItem {
             title,
             working_dir,
             size: TerminalSize { rows, cols, .. },
+            resource_usage,
             ..
         } = pane;
 
@@ -614,6 +1044,40 @@ impl From<mux::tab::PaneEntry> for CliListResultItem {
                 .map(|url| url.url.as_str())
                 .unwrap_or("")
                 .to_string(),
+            resource_usage: resource_usage.map(|usage| CliListResultResourceUsage {
+                cpu_percent: usage.cpu_percent,
+                memory_bytes: usage.memory_bytes,
+                process_count: usage.process_count,
+            }),
+        }
+    }
+}
+
+// This will be serialized to JSON via the 'Subscribe' command.
+// As such it is intended to be a stable output format,
+// Thus we need to be careful about the stability of the fields and types
+// herein as they are directly reflected in the output.
+#[derive(serde::Serialize)]
+struct CliSubscribeEvent {
+    event: &'static str,
+    pane_id: mux::pane::PaneId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<CliListResultPtySize>,
+}
+
+impl From<codec::MuxEvent> for CliSubscribeEvent {
+    fn from(event: codec::MuxEvent) -> CliSubscribeEvent {
+        let name = MUX_EVENT_NAMES
+            .iter()
+            .find(|(_, kind)| *kind == event.kind)
+            .map(|(name, _)| *name)
+            .expect("all event kinds to be named");
+        CliSubscribeEvent {
+            event: name,
+            pane_id: event.pane_id,
+            size: event
+                .size
+                .map(|TerminalSize { rows, cols, .. }| CliListResultPtySize { rows, cols }),
         }
     }
 }
@@ -892,9 +1356,14 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
             cells,
             percent,
             move_pane_id,
+            layout,
         } => {
             let pane_id = resolve_pane_id(&client, pane_id).await?;
 
+            if let Some(layout) = layout {
+                return split_pane_with_layout(&client, pane_id, layout).await;
+            }
+
             let direction = if left || right || horizontal {
                 SplitDirection::Horizontal
             } else if top || bottom {
@@ -962,7 +1431,11 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
                     .await?;
             } else {
                 client
-                    .send_paste(codec::SendPaste { pane_id, data })
+                    .send_paste(codec::SendPaste {
+                        pane_id,
+                        data,
+                        emit_pre_paste: true,
+                    })
                     .await?;
             }
         }
@@ -1010,6 +1483,26 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
 
             let size = config.initial_size(0);
 
+            let command_dir = match (&domain_name, cwd) {
+                (Some(name), Some(cwd)) if is_absolute_path(&cwd) => {
+                    let cwd = cwd
+                        .into_string()
+                        .map_err(|_| anyhow!("path is not representable as String"))?;
+                    // The directory is in the named domain, which may be
+                    // on another host, so check it there rather than locally
+                    client
+                        .read_dir(codec::ReadDir {
+                            domain: SpawnTabDomain::DomainName(name.clone()),
+                            pane_id: None,
+                            path: cwd.clone(),
+                        })
+                        .await
+                        .with_context(|| format!("checking --cwd {} in domain {}", cwd, name))?;
+                    Some(cwd)
+                }
+                (_, cwd) => canon_cwd(cwd)?,
+            };
+
             let spawned = client
                 .spawn_v2(codec::SpawnV2 {
                     domain: domain_name.map_or(SpawnTabDomain::DefaultDomain, |name| {
@@ -1022,7 +1515,7 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
                         let builder = CommandBuilder::from_argv(prog);
                         Some(builder)
                     },
-                    command_dir: canon_cwd(cwd)?,
+                    command_dir,
                     size,
                     workspace,
                 })
@@ -1031,6 +1524,88 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
             log::debug!("{:?}", spawned);
             println!("{}", spawned.pane_id);
         }
+        CliSubCommand::Upload {
+            pane_id,
+            local,
+            remote,
+        } => {
+            let pane_id = resolve_pane_id(&client, pane_id).await?;
+            let remote = match remote {
+                Some(remote) => remote,
+                None => local
+                    .file_name()
+                    .ok_or_else(|| anyhow!("{} has no file name", local.display()))?
+                    .to_string_lossy()
+                    .to_string(),
+            };
+
+            let mut file = std::fs::File::open(&local)
+                .with_context(|| format!("opening {}", local.display()))?;
+            let total_size = file.metadata().ok().map(|m| m.len());
+            let mut offset = 0;
+            let mut buf = vec![0u8; FILE_TRANSFER_CHUNK_SIZE];
+            loop {
+                let len = file
+                    .read(&mut buf)
+                    .with_context(|| format!("reading {}", local.display()))?;
+                // Always send at least one chunk, so that an empty
+                // file is created at the destination
+                if len == 0 && offset > 0 {
+                    break;
+                }
+                client
+                    .write_file_chunk(codec::WriteFileChunk {
+                        pane_id,
+                        path: remote.clone(),
+                        offset,
+                        data: buf[..len].to_vec(),
+                        total_size,
+                    })
+                    .await?;
+                if len == 0 {
+                    break;
+                }
+                offset += len as u64;
+            }
+        }
+        CliSubCommand::Download {
+            pane_id,
+            remote,
+            local,
+        } => {
+            let pane_id = resolve_pane_id(&client, pane_id).await?;
+            let local = match local {
+                Some(local) => local,
+                None => {
+                    let name = remote
+                        .rsplit('/')
+                        .next()
+                        .filter(|name| !name.is_empty())
+                        .ok_or_else(|| anyhow!("{} has no file name", remote))?;
+                    PathBuf::from(name)
+                }
+            };
+
+            let mut file = std::fs::File::create(&local)
+                .with_context(|| format!("creating {}", local.display()))?;
+            let mut offset = 0;
+            loop {
+                let chunk = client
+                    .read_file_chunk(codec::ReadFileChunk {
+                        pane_id,
+                        path: remote.clone(),
+                        offset,
+                        max_bytes: FILE_TRANSFER_CHUNK_SIZE,
+                    })
+                    .await?;
+                if chunk.data.is_empty() {
+                    break;
+                }
+                file.write_all(&chunk.data)
+                    .with_context(|| format!("writing {}", local.display()))?;
+                offset += chunk.data.len() as u64;
+            }
+        }
         CliSubCommand::Proxy => {
             // The client object we created above will have spawned
             // the server if needed, so now all we need to do is turn
@@ -1062,15 +1637,308 @@ async fn run_cli_async(config: config::ConfigHandle, cli: CliCommand) -> anyhow:
             // Wait forever; the stdio threads will terminate on EOF
             smol::future::pending().await
         }
+        CliSubCommand::Subscribe { events } => {
+            let events = match events {
+                Some(MuxEventKinds(events)) => events,
+                None => MUX_EVENT_NAMES.iter().map(|(_, kind)| *kind).collect(),
+            };
+            let rx = client.subscribe_events(events).await?;
+            let out = std::io::stdout();
+            while let Ok(event) = rx.recv().await {
+                let mut out = out.lock();
+                serde_json::to_writer(&mut out, &CliSubscribeEvent::from(event))?;
+                writeln!(out)?;
+                out.flush()?;
+            }
+        }
+        CliSubCommand::ActivatePaneDirection { pane_id, direction } => {
+            let pane_id = resolve_pane_id(&client, pane_id).await?;
+            client
+                .activate_pane_direction(codec::ActivatePaneDirection { pane_id, direction })
+                .await?;
+        }
+        CliSubCommand::RotatePanes { pane_id, direction } => {
+            let pane_id = resolve_pane_id(&client, pane_id).await?;
+            client
+                .rotate_panes(codec::RotatePanes { pane_id, direction })
+                .await?;
+        }
+        CliSubCommand::SwapPanes {
+            pane_id,
+            other_pane_id,
+        } => {
+            let pane_id = resolve_pane_id(&client, pane_id).await?;
+            client
+                .swap_panes(codec::SwapPanes {
+                    pane_id,
+                    other_pane_id,
+                })
+                .await?;
+        }
+        CliSubCommand::CompletePath {
+            pane_id,
+            domain_name,
+            dirs_only,
+            partial,
+        } => {
+            let (domain, pane_id) = match domain_name {
+                Some(name) => (SpawnTabDomain::DomainName(name), None),
+                None => (
+                    SpawnTabDomain::CurrentPaneDomain,
+                    Some(resolve_pane_id(&client, pane_id).await?),
+                ),
+            };
+            let (dir, _prefix) = mux::domain::split_partial_path(&partial);
+            let listing = client
+                .read_dir(codec::ReadDir {
+                    domain,
+                    pane_id,
+                    path: if dir.is_empty() { "." } else { dir }.to_string(),
+                })
+                .await?;
+            for candidate in mux::domain::complete_path(&partial, &listing.entries, dirs_only) {
+                println!("{}", candidate);
+            }
+        }
+        CliSubCommand::AttachGroup { name } => {
+            client
+                .attach_domain_group(codec::AttachDomainGroup { name })
+                .await?;
+        }
+        CliSubCommand::DetachGroup { name } => {
+            client
+                .detach_domain_group(codec::DetachDomainGroup { name })
+                .await?;
+        }
+        CliSubCommand::ApplyLayout { name } => {
+            client
+                .apply_workspace_layout(codec::ApplyWorkspaceLayout { name })
+                .await?;
+        }
+        CliSubCommand::RenameWorkspace {
+            workspace,
+            new_workspace,
+        } => {
+            let old_workspace = resolve_workspace(&client, workspace).await?;
+            client
+                .rename_workspace(codec::RenameWorkspace {
+                    old_workspace,
+                    new_workspace,
+                })
+                .await?;
+        }
+        CliSubCommand::MergeWorkspace {
+            workspace,
+            into_workspace,
+        } => {
+            let from_workspace = resolve_workspace(&client, workspace).await?;
+            client
+                .merge_workspace(codec::MergeWorkspace {
+                    from_workspace,
+                    into_workspace,
+                })
+                .await?;
+        }
+        CliSubCommand::DeleteWorkspace { workspace } => {
+            let workspace = resolve_workspace(&client, workspace).await?;
+            client
+                .delete_workspace(codec::DeleteWorkspace { workspace })
+                .await?;
+        }
+        CliSubCommand::ExportScrollback {
+            pane_id,
+            format,
+            start_line,
+            end_line,
+        } => {
+            let pane_id = resolve_pane_id(&client, pane_id).await?;
+            let exported = client
+                .export_scrollback(codec::ExportScrollback {
+                    pane_id,
+                    format,
+                    start_line,
+                    end_line,
+                })
+                .await?;
+            let mut out = std::io::stdout();
+            out.write_all(exported.text.as_bytes())?;
+            if format == mux::export::ExportFormat::Text {
+                writeln!(out)?;
+            }
+        }
+        CliSubCommand::History {
+            pane_id,
+            search,
+            limit,
+            format,
+        } => {
+            let out = std::io::stdout();
+            let history = client
+                .get_command_history(codec::GetCommandHistory {
+                    query: mux::history::CommandHistoryQuery {
+                        pane_id,
+                        search,
+                        limit,
+                    },
+                })
+                .await?;
+            match format {
+                CliOutputFormatKind::Json => {
+                    let mut writer = serde_json::Serializer::pretty(out.lock());
+                    writer.collect_seq(history.entries)?;
+                }
+                CliOutputFormatKind::Table => {
+                    let cols = vec![
+                        Column {
+                            name: "PANE".to_string(),
+                            alignment: Alignment::Right,
+                        },
+                        Column {
+                            name: "STARTED".to_string(),
+                            alignment: Alignment::Left,
+                        },
+                        Column {
+                            name: "STATUS".to_string(),
+                            alignment: Alignment::Right,
+                        },
+                        Column {
+                            name: "COMMAND".to_string(),
+                            alignment: Alignment::Left,
+                        },
+                    ];
+                    let data: Vec<Vec<String>> = history
+                        .entries
+                        .into_iter()
+                        .map(|entry| {
+                            vec![
+                                entry.pane_id.to_string(),
+                                // The timestamp comes from the server, so
+                                // show it as-is if it is out of range
+                                chrono::Local
+                                    .timestamp_opt(entry.started, 0)
+                                    .single()
+                                    .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+                                    .unwrap_or_else(|| entry.started.to_string()),
+                                entry
+                                    .exit_status
+                                    .map(|status| status.to_string())
+                                    .unwrap_or_else(String::new),
+                                entry.command.replace('\n', " "),
+                            ]
+                        })
+                        .collect();
+                    tabulate_output(&cols, &data, &mut out.lock())?;
+                }
+            }
+        }
         CliSubCommand::TlsCreds => {
             let creds = client.get_tls_creds().await?;
             codec::Pdu::GetTlsCredsResponse(creds).encode(std::io::stdout().lock(), 0)?;
         }
+        CliSubCommand::Tls(_) => {
+            anyhow::bail!("tls subcommands don't use the mux server");
+        }
+    }
+    Ok(())
+}
+
+// This is the JSON-serializable version of a trusted TLS certificate,
+// which is the output of `wezterm cli tls trust list --format json`.
+#[derive(serde::Serialize)]
+struct CliTlsTrustResultItem {
+    domain: String,
+    fingerprint: String,
+    subject: String,
+    remote_address: String,
+    trusted_at: u64,
+}
+
+fn run_tls_trust(sub: TlsTrustSubCommand) -> anyhow::Result<()> {
+    use wezterm_client::tlstrust::TrustStore;
+
+    let mut store = TrustStore::load()?;
+    match sub {
+        TlsTrustSubCommand::List(CliOutputFormat { format }) => {
+            let out = std::io::stdout();
+            match format {
+                CliOutputFormatKind::Json => {
+                    let certs = store.iter().map(|(domain, cert)| CliTlsTrustResultItem {
+                        domain: domain.clone(),
+                        fingerprint: cert.fingerprint.clone(),
+                        subject: cert.subject.clone(),
+                        remote_address: cert.remote_address.clone(),
+                        trusted_at: cert.trusted_at,
+                    });
+                    let mut writer = serde_json::Serializer::pretty(out.lock());
+                    writer.collect_seq(certs)?;
+                }
+                CliOutputFormatKind::Table => {
+                    let cols = vec![
+                        Column {
+                            name: "DOMAIN".to_string(),
+                            alignment: Alignment::Left,
+                        },
+                        Column {
+                            name: "ADDRESS".to_string(),
+                            alignment: Alignment::Left,
+                        },
+                        Column {
+                            name: "SUBJECT".to_string(),
+                            alignment: Alignment::Left,
+                        },
+                        Column {
+                            name: "TRUSTED".to_string(),
+                            alignment: Alignment::Left,
+                        },
+                        Column {
+                            name: "SHA-256 FINGERPRINT".to_string(),
+                            alignment: Alignment::Left,
+                        },
+                    ];
+                    let data = store
+                        .iter()
+                        .map(|(domain, cert)| {
+                            vec![
+                                domain.clone(),
+                                cert.remote_address.clone(),
+                                cert.subject.clone(),
+                                // The store may have been edited by hand, so
+                                // the time may be out of range
+                                i64::try_from(cert.trusted_at)
+                                    .ok()
+                                    .and_then(|secs| chrono::Local.timestamp_opt(secs, 0).single())
+                                    .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+                                    .unwrap_or_else(|| cert.trusted_at.to_string()),
+                                cert.fingerprint.clone(),
+                            ]
+                        })
+                        .collect::<Vec<_>>();
+                    tabulate_output(&cols, &data, &mut out.lock())?;
+                }
+            }
+        }
+        TlsTrustSubCommand::Revoke { domain } => {
+            if store.revoke(&domain).is_none() {
+                anyhow::bail!("no certificate is trusted for TLS domain {}", domain);
+            }
+            store.save()?;
+        }
     }
     Ok(())
 }
 
 fn run_cli(config: config::ConfigHandle, cli: CliCommand) -> anyhow::Result<()> {
+    // Managing trusted certificates doesn't involve the mux server
+    if let CliSubCommand::Tls(TlsCommand {
+        sub: TlsSubCommand::Trust(TlsTrustCommand { sub }),
+    }) = &cli.sub
+    {
+        return match run_tls_trust(sub.clone()) {
+            Ok(_) => Ok(()),
+            Err(err) => terminate_with_error(err),
+        };
+    }
+
     let executor = promise::spawn::ScopedExecutor::new();
     match promise::spawn::block_on(executor.run(async move { run_cli_async(config, cli).await })) {
         Ok(_) => Ok(()),
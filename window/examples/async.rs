@@ -79,11 +79,15 @@ impl MyWindow {
                 }
             }
             WindowEvent::AppearanceChanged(_)
+            | WindowEvent::ClipboardChanged(_)
             | WindowEvent::AdviseDeadKeyStatus(_)
+            | WindowEvent::AdviseComposeState(_)
             | WindowEvent::Notification(_)
             | WindowEvent::FocusChanged(_)
             | WindowEvent::DraggedFile(_)
             | WindowEvent::DroppedFile(_)
+            | WindowEvent::TouchEvent(_)
+            | WindowEvent::Gesture(_)
             | WindowEvent::MouseLeave => {}
         }
     }
@@ -0,0 +1,255 @@
+//! Processes the `compose_key` and `compose_sequences` configuration,
+//! which define XCompose-like sequences that behave the same way on
+//! all platforms.  Key events pass through the `Composer` before they
+//! are dispatched to the window, so that the window only receives the
+//! composed text.
+use crate::{DeadKeyStatus, KeyCode, KeyEvent, Modifiers, WindowEvent};
+use config::{ComposeSequence, KeyMapPreference, KeyNoAction};
+
+/// Shown as the composition text until the first key of a
+/// sequence has been pressed
+const COMPOSE_PLACEHOLDER: &str = "⎄";
+
+/// A compose sequence that can complete the keys entered so far
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposeCandidate {
+    pub keys: Vec<String>,
+    pub result: String,
+}
+
+/// The progress of a compose sequence that is being entered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComposeState {
+    /// The text of the keys that were pressed after the compose key
+    pub keys: Vec<String>,
+    /// The sequences that start with `keys`
+    pub candidates: Vec<ComposeCandidate>,
+}
+
+#[derive(Default)]
+pub(crate) struct Composer {
+    /// The keys entered since the compose key was pressed,
+    /// or None when not composing
+    pending: Option<Vec<String>>,
+}
+
+fn is_compose_key(event: &KeyEvent, compose_key: &KeyNoAction) -> bool {
+    let key = compose_key.key.resolve(KeyMapPreference::Mapped);
+    if key.is_modifier() {
+        // The modifier flags for the key itself vary by platform
+        return event.key == key;
+    }
+    let (key, mods) = key.normalize_shift(compose_key.mods);
+    let (event_key, event_mods) = event
+        .key
+        .normalize_shift(event.modifiers.remove_positional_mods());
+    key == event_key && mods == event_mods
+}
+
+fn candidates(keys: &[String], sequences: &[ComposeSequence]) -> Vec<ComposeCandidate> {
+    sequences
+        .iter()
+        .filter(|seq| !seq.keys.is_empty() && seq.keys.starts_with(keys))
+        .map(|seq| ComposeCandidate {
+            keys: seq.keys.clone(),
+            result: seq.result.clone(),
+        })
+        .collect()
+}
+
+fn composed_key_event(result: &str) -> KeyEvent {
+    let mut chars = result.chars();
+    let key = match (chars.next(), chars.next()) {
+        (Some(c), None) => KeyCode::Char(c),
+        _ => KeyCode::Composed(result.to_string()),
+    };
+    KeyEvent {
+        #[cfg(windows)]
+        win32_uni_char: match &key {
+            KeyCode::Char(c) => Some(*c),
+            _ => None,
+        },
+        key,
+        modifiers: Modifiers::NONE,
+        repeat_count: 1,
+        key_is_down: true,
+        raw: None,
+    }
+}
+
+/// The events that advise the window that composition has finished
+fn idle_events() -> Vec<WindowEvent> {
+    vec![
+        WindowEvent::AdviseDeadKeyStatus(DeadKeyStatus::None),
+        WindowEvent::AdviseComposeState(None),
+    ]
+}
+
+impl Composer {
+    pub fn process(&mut self, event: KeyEvent) -> Vec<WindowEvent> {
+        let config = config::configuration();
+        self.process_with(
+            event,
+            config.compose_key.as_ref(),
+            &config.compose_sequences,
+        )
+    }
+
+    /// Abandons any composition that is in progress
+    pub fn cancel(&mut self) -> Vec<WindowEvent> {
+        match self.pending.take() {
+            Some(_) => idle_events(),
+            None => vec![],
+        }
+    }
+
+    fn process_with(
+        &mut self,
+        event: KeyEvent,
+        compose_key: Option<&KeyNoAction>,
+        sequences: &[ComposeSequence],
+    ) -> Vec<WindowEvent> {
+        let mut keys = match self.pending.take() {
+            Some(keys) => keys,
+            None => {
+                if event.key_is_down
+                    && compose_key.map_or(false, |compose| is_compose_key(&event, compose))
+                {
+                    return self.composing(vec![], sequences);
+                }
+                return vec![WindowEvent::KeyEvent(event)];
+            }
+        };
+
+        if !event.key_is_down || event.key.is_modifier() {
+            self.pending.replace(keys);
+            return vec![];
+        }
+
+        match &event.key {
+            // Escape abandons the sequence
+            KeyCode::Char('\x1b') => return idle_events(),
+            // Backspace removes the most recently entered key
+            KeyCode::Char('\x08') | KeyCode::Char('\x7f') => {
+                keys.pop();
+                return self.composing(keys, sequences);
+            }
+            KeyCode::Char(c) => keys.push(c.to_string()),
+            KeyCode::Composed(s) => keys.push(s.clone()),
+            _ => {
+                // Keys that don't produce text, such as the arrow keys,
+                // abandon the sequence and then take effect as usual
+                let mut events = idle_events();
+                events.push(WindowEvent::KeyEvent(event));
+                return events;
+            }
+        }
+
+        let candidates = candidates(&keys, sequences);
+        if let Some(complete) = candidates.iter().find(|c| c.keys == keys) {
+            let mut events = idle_events();
+            events.push(WindowEvent::KeyEvent(composed_key_event(&complete.result)));
+            return events;
+        }
+        if candidates.is_empty() {
+            // As with XCompose, an invalid sequence is discarded
+            log::debug!("no compose sequence matches {:?}", keys);
+            return idle_events();
+        }
+        self.composing(keys, sequences)
+    }
+
+    /// Records the keys entered so far and returns the events that
+    /// advise the window of the progress of the sequence
+    fn composing(&mut self, keys: Vec<String>, sequences: &[ComposeSequence]) -> Vec<WindowEvent> {
+        let text = if keys.is_empty() {
+            COMPOSE_PLACEHOLDER.to_string()
+        } else {
+            keys.concat()
+        };
+        let state = ComposeState {
+            candidates: candidates(&keys, sequences),
+            keys: keys.clone(),
+        };
+        self.pending.replace(keys);
+        vec![
+            WindowEvent::AdviseDeadKeyStatus(DeadKeyStatus::Composing(text)),
+            WindowEvent::AdviseComposeState(Some(state)),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use config::DeferredKeyCode;
+
+    fn key(c: char, key_is_down: bool) -> KeyEvent {
+        KeyEvent {
+            key: KeyCode::Char(c),
+            modifiers: Modifiers::NONE,
+            repeat_count: 1,
+            key_is_down,
+            #[cfg(windows)]
+            win32_uni_char: None,
+            raw: None,
+        }
+    }
+
+    /// Returns the text of the key events and the composition
+    /// text of the dead key events
+    fn summarize(events: Vec<WindowEvent>) -> Vec<String> {
+        events
+            .into_iter()
+            .filter_map(|event| match event {
+                WindowEvent::KeyEvent(event) => Some(format!("key {:?}", event.key)),
+                WindowEvent::AdviseDeadKeyStatus(DeadKeyStatus::Composing(text)) => {
+                    Some(format!("composing {text}"))
+                }
+                WindowEvent::AdviseDeadKeyStatus(DeadKeyStatus::None) => Some("idle".to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sequences() {
+        let compose_key = KeyNoAction {
+            key: DeferredKeyCode::KeyCode(KeyCode::Function(12)),
+            mods: Modifiers::NONE,
+        };
+        let sequences = vec![
+            ComposeSequence {
+                keys: vec!["o".to_string(), "e".to_string()],
+                result: "œ".to_string(),
+            },
+            ComposeSequence {
+                keys: vec!["-".to_string(), ">".to_string()],
+                result: "→".to_string(),
+            },
+        ];
+        let mut composer = Composer::default();
+        let mut feed = |event: KeyEvent| {
+            summarize(composer.process_with(event, Some(&compose_key), &sequences))
+        };
+        let mut compose = key('x', true);
+        compose.key = KeyCode::Function(12);
+
+        assert_eq!(feed(key('a', true)), vec!["key Char('a')"]);
+        assert_eq!(feed(compose.clone()), vec!["composing ⎄"]);
+        assert_eq!(feed(key('o', true)), vec!["composing o"]);
+        assert!(feed(key('o', false)).is_empty());
+        assert_eq!(feed(key('e', true)), vec!["idle", "key Char('œ')"]);
+
+        // An invalid sequence is discarded
+        feed(compose.clone());
+        assert_eq!(feed(key('q', true)), vec!["idle"]);
+        assert_eq!(feed(key('q', true)), vec!["key Char('q')"]);
+
+        // Backspace and Escape
+        feed(compose);
+        feed(key('o', true));
+        assert_eq!(feed(key('\x08', true)), vec!["composing ⎄"]);
+        assert_eq!(feed(key('\x1b', true)), vec!["idle"]);
+    }
+}
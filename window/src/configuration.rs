@@ -10,3 +10,16 @@ pub(crate) fn prefer_swrast() -> bool {
     }
     config::configuration().front_end == config::FrontEndSelection::Software
 }
+
+/// Returns the swap interval that corresponds to the `present_mode`
+/// configuration; without a vsync, we throttle frames at the
+/// application level.
+/// Returns None for the default mode, in which case each backend
+/// keeps the swap interval that it has always used.
+pub(crate) fn swap_interval() -> Option<i32> {
+    match config::configuration().present_mode {
+        config::PresentMode::VSync => Some(1),
+        config::PresentMode::Immediate => Some(0),
+        config::PresentMode::Mailbox => None,
+    }
+}
@@ -1,4 +1,5 @@
 use crate::screen::Screens;
+use crate::tray::TrayIcon;
 use crate::{Appearance, Connection, GeometryOrigin, RequestedWindowGeometry, ResolvedGeometry};
 use anyhow::Result as Fallible;
 use config::DimensionContext;
@@ -51,6 +52,12 @@ pub trait ConnectionOps {
     /// Perform the system beep/notification sound
     fn beep(&self) {}
 
+    /// Show an icon in the system tray, replacing any icon that
+    /// was previously shown.  Passing `None` removes the icon.
+    fn set_tray_icon(&self, _tray: Option<TrayIcon>) -> anyhow::Result<()> {
+        anyhow::bail!("tray icons are not supported on this system");
+    }
+
     /// Returns information about the screens
     fn screens(&self) -> anyhow::Result<Screens> {
         anyhow::bail!("Unable to query screen information");
@@ -643,10 +643,13 @@ impl GlState {
             log::trace!("Successfully created a surface using this configuration");
             connection.egl.log_config_info(connection.display, config);
 
-            // Request non-blocking buffer swaps; we'll manage throttling
-            // frames at the application level.
+            // Unless vsync was requested, use non-blocking buffer swaps;
+            // we'll manage throttling frames at the application level.
             unsafe {
-                connection.egl.egl.SwapInterval(connection.display, 0);
+                connection.egl.egl.SwapInterval(
+                    connection.display,
+                    crate::configuration::swap_interval().unwrap_or(0),
+                );
             }
 
             return Ok(Self {
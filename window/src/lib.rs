@@ -8,11 +8,15 @@ use std::rc::Rc;
 use thiserror::Error;
 pub mod bitmaps;
 pub use wezterm_color_types as color;
+mod compose;
 mod configuration;
 pub mod connection;
 pub mod os;
 pub mod screen;
+pub mod services;
 mod spawn;
+mod touch;
+pub mod tray;
 
 #[cfg(target_os = "macos")]
 pub(crate) const DEFAULT_DPI: f64 = 72.0;
@@ -29,9 +33,11 @@ pub fn default_dpi() -> f64 {
 mod egl;
 
 pub use bitmaps::{BitmapImage, Image};
+pub use compose::{ComposeCandidate, ComposeState};
 pub use connection::*;
 pub use glium;
 pub use os::*;
+pub use touch::{Gesture, SwipeDirection};
 pub use wezterm_input_types::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -53,6 +59,45 @@ pub struct Dimensions {
     pub dpi: usize,
 }
 
+/// The steps in which the desktop environment sizes the window when it
+/// is resized interactively: the width of the content of the window is
+/// `base_width` plus a multiple of `x` pixels, and likewise its height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResizeIncrement {
+    pub x: u16,
+    pub y: u16,
+    pub base_width: u16,
+    pub base_height: u16,
+}
+
+impl ResizeIncrement {
+    /// Allows the window to be sized to any number of pixels
+    pub fn disabled() -> Self {
+        Self {
+            x: 1,
+            y: 1,
+            base_width: 0,
+            base_height: 0,
+        }
+    }
+
+    /// Returns the largest size that is no larger than `width` by
+    /// `height` pixels and that is a whole number of increments
+    pub fn snap(&self, width: usize, height: usize) -> (usize, usize) {
+        fn snap_dim(size: usize, base: u16, incr: u16) -> usize {
+            let base = base as usize;
+            if size <= base {
+                return size;
+            }
+            size - (size - base) % incr.max(1) as usize
+        }
+        (
+            snap_dim(width, self.base_width, self.x),
+            snap_dim(height, self.base_height, self.y),
+        )
+    }
+}
+
 pub type ULength = euclid::Length<usize, PixelUnit>;
 pub type Rect = euclid::Rect<isize, PixelUnit>;
 pub type RectF = euclid::Rect<f32, PixelUnit>;
@@ -85,6 +130,12 @@ pub enum Appearance {
     DarkHighContrast,
 }
 
+impl Appearance {
+    pub fn is_dark(&self) -> bool {
+        matches!(self, Self::Dark | Self::DarkHighContrast)
+    }
+}
+
 impl std::string::ToString for Appearance {
     fn to_string(&self) -> String {
         match self {
@@ -161,8 +212,18 @@ pub enum WindowEvent {
     /// Called when the window gains/loses focus
     FocusChanged(bool),
 
+    /// Called when the window becomes hidden, because it was
+    /// minimized or is fully covered by other windows, and
+    /// when it becomes visible again
+    VisibilityChanged(bool),
+
     AdviseDeadKeyStatus(DeadKeyStatus),
 
+    /// Called when a sequence from `compose_sequences` is started,
+    /// progresses or finishes.  This is accompanied by a corresponding
+    /// `AdviseDeadKeyStatus` event.
+    AdviseComposeState(Option<ComposeState>),
+
     /// Called to handle a raw key event, prior to any dead key,
     /// keymap composition or other higher level treatment.
     /// If you handle this key event, you must call
@@ -175,8 +236,23 @@ pub enum WindowEvent {
     MouseEvent(MouseEvent),
     MouseLeave,
 
+    /// Called when a finger touches, moves across or is lifted
+    /// from a touchscreen
+    TouchEvent(TouchEvent),
+
+    /// Called when a gesture is recognized in the touch events,
+    /// or is reported by the system, as is the case for trackpad
+    /// gestures on macOS
+    Gesture(Gesture),
+
     AppearanceChanged(Appearance),
 
+    /// Called when the content of the clipboard or primary selection
+    /// has been changed, either by this application or another one.
+    /// This is delivered to a single window; the focused window
+    /// is preferred.
+    ClipboardChanged(Clipboard),
+
     Notification(Box<dyn Any + Send + Sync>),
 
     // Called when the files are being dragged into the window
@@ -189,6 +265,8 @@ pub enum WindowEvent {
 pub struct WindowEventSender {
     handler: Box<dyn FnMut(WindowEvent, &Window)>,
     window: Option<Window>,
+    composer: compose::Composer,
+    gestures: touch::GestureRecognizer,
 }
 
 impl WindowEventSender {
@@ -196,6 +274,8 @@ impl WindowEventSender {
         Self {
             handler: Box::new(handler),
             window: None,
+            composer: compose::Composer::default(),
+            gestures: touch::GestureRecognizer::default(),
         }
     }
 
@@ -206,7 +286,44 @@ impl WindowEventSender {
     pub fn dispatch(&mut self, event: WindowEvent) {
         if let Some(window) = self.window.as_ref() {
             log::trace!("{:?}", event);
-            (self.handler)(event, window);
+            let events = match event {
+                // Key events are subject to the compose_sequences
+                // before the application sees them
+                WindowEvent::KeyEvent(event) => self.composer.process(event),
+                WindowEvent::FocusChanged(false) => {
+                    let mut events = self.composer.cancel();
+                    events.push(event);
+                    events
+                }
+                // Gestures are recognized in the touch events
+                WindowEvent::TouchEvent(event) => {
+                    let gestures = self.gestures.process(&event);
+                    if let Some(timer) = self.gestures.take_long_press_timer() {
+                        let window = window.clone();
+                        promise::spawn::spawn(async move {
+                            async_io::Timer::after(timer.delay()).await;
+                            window.notify(timer);
+                        })
+                        .detach();
+                    }
+                    std::iter::once(WindowEvent::TouchEvent(event))
+                        .chain(gestures.into_iter().map(WindowEvent::Gesture))
+                        .collect()
+                }
+                WindowEvent::Notification(item) => match item.downcast::<touch::LongPressTimer>() {
+                    Ok(timer) => self
+                        .gestures
+                        .long_press_elapsed(*timer)
+                        .map(WindowEvent::Gesture)
+                        .into_iter()
+                        .collect(),
+                    Err(item) => vec![WindowEvent::Notification(item)],
+                },
+                event => vec![event],
+            };
+            for event in events {
+                (self.handler)(event, window);
+            }
         }
     }
 }
@@ -235,6 +352,18 @@ pub trait WindowOps {
     /// Hide a visible window
     fn hide(&self);
 
+    /// Minimize the window, leaving it in the taskbar or dock.
+    /// On most backends this is the same as hide().
+    fn minimize(&self) {
+        self.hide();
+    }
+
+    /// Raise the window and give it the keyboard focus.
+    ///
+    /// This is not implemented on Wayland, where clients
+    /// are not permitted to focus themselves.
+    fn focus(&self) {}
+
     /// Schedule the window to be closed
     fn close(&self);
 
@@ -273,6 +402,16 @@ pub trait WindowOps {
     /// windows to move themselves (not Wayland).
     fn set_window_position(&self, _coords: ScreenPoint) {}
 
+    /// Returns the location of the top left pixel of the client
+    /// area of the window on the screen.
+    ///
+    /// This is only implemented on backends that allow windows
+    /// to learn their position (not Wayland); elsewhere it
+    /// resolves to None.
+    fn get_window_position(&self) -> Future<Option<ScreenPoint>> {
+        Future::ok(None)
+    }
+
     /// inform the windowing system of the current textual
     /// cursor input location.  This is used primarily for
     /// the platform specific input method editor
@@ -297,11 +436,34 @@ pub trait WindowOps {
     fn config_did_change(&self, _config: &config::ConfigHandle) {}
 
     /// Configure the Window so that the desktop environment
-    /// will constrain resizes so that they are multiples of
-    /// the x and y values specified.
+    /// will constrain resizes so that they are whole multiples of
+    /// the increments specified, on top of the base size.
     /// This may not be supported or respected by the desktop
     /// environment.
-    fn set_resize_increments(&self, _x: u16, _y: u16) {}
+    fn set_resize_increments(&self, _incr: ResizeIncrement) {}
+
+    /// Set the region of the window that accepts mouse input.
+    /// Mouse events outside of that region pass through to
+    /// whatever is beneath the window.
+    /// Windows and macOS can only make the whole window click-through,
+    /// so they treat `InputRegion::Rects` as `InputRegion::Everywhere`.
+    fn set_input_region(&self, _region: InputRegion) {}
+
+    /// Round the corners of the window with the specified radius,
+    /// in pixels; None leaves them to the desktop environment.
+    /// Where the window system can shape the window, it is shaped
+    /// to match, so that the shadow, border and the areas that accept
+    /// input follow the corners.  The application is responsible for
+    /// rendering transparent corners on other backends.
+    fn set_corner_radius(&self, _radius: Option<f32>) {}
+
+    /// Returns true if the window system rounds the corners of the
+    /// window itself, in which case the application should not render
+    /// transparent corners; they would show up black when the surface
+    /// is not composited with its alpha channel.
+    fn window_system_rounds_corners(&self) -> bool {
+        false
+    }
 
     fn get_os_parameters(
         &self,
@@ -312,6 +474,55 @@ pub trait WindowOps {
     }
 }
 
+/// Describes which parts of a window accept mouse input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputRegion {
+    /// The whole window accepts input. This is the default.
+    Everywhere,
+    /// The whole window is click-through
+    Nowhere,
+    /// Only these rectangles, in pixels relative to the top left
+    /// of the client area, accept input
+    Rects(Vec<Rect>),
+}
+
+impl Default for InputRegion {
+    fn default() -> Self {
+        Self::Everywhere
+    }
+}
+
+impl InputRegion {
+    /// Returns the region covered by a window of the specified size
+    /// whose corners are rounded with `radius`.  The rounded portions
+    /// are approximated by a rectangle for each row of pixels.
+    pub fn rounded(width: usize, height: usize, radius: f32) -> Self {
+        let radius = radius.min(width.min(height) as f32 / 2.);
+        if radius < 1. {
+            return Self::Everywhere;
+        }
+        let rows = radius.ceil() as usize;
+        let mut rects = vec![];
+        for y in 0..rows {
+            // How far the edge of the circle is from the side of
+            // the window, measured at the center of the row
+            let dy = radius - (y as f32 + 0.5);
+            let inset = (radius - (radius * radius - dy * dy).max(0.).sqrt()).round() as usize;
+            let size = Size::new(width.saturating_sub(2 * inset) as isize, 1);
+            rects.push(Rect::new(Point::new(inset as isize, y as isize), size));
+            rects.push(Rect::new(
+                Point::new(inset as isize, (height - 1 - y) as isize),
+                size,
+            ));
+        }
+        rects.push(Rect::new(
+            Point::new(0, rows as isize),
+            Size::new(width as isize, height.saturating_sub(2 * rows) as isize),
+        ));
+        Self::Rects(rects)
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct RequestedWindowGeometry {
     pub width: Dimension,
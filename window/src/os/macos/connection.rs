@@ -6,10 +6,13 @@ use super::window::WindowInner;
 use crate::connection::ConnectionOps;
 use crate::screen::{ScreenInfo, Screens};
 use crate::spawn::*;
-use crate::Appearance;
-use cocoa::appkit::{NSApp, NSApplication, NSApplicationActivationPolicyRegular, NSScreen};
+use crate::tray::TrayIcon;
+use crate::{Appearance, Clipboard};
+use cocoa::appkit::{
+    NSApp, NSApplication, NSApplicationActivationPolicyRegular, NSPasteboard, NSScreen,
+};
 use cocoa::base::{id, nil};
-use cocoa::foundation::{NSArray, NSRect};
+use cocoa::foundation::{NSArray, NSInteger, NSRect};
 use objc::runtime::Object;
 use objc::*;
 use std::cell::RefCell;
@@ -39,10 +42,50 @@ impl Connection {
                 next_window_id: AtomicUsize::new(1),
                 gl_connection: RefCell::new(None),
             };
+            Self::spawn_pasteboard_monitor();
+            super::services::register();
             Ok(conn)
         }
     }
 
+    /// macOS doesn't notify applications about changes to the
+    /// pasteboard, so we poll its change count instead
+    fn spawn_pasteboard_monitor() {
+        promise::spawn::spawn(async move {
+            let change_count = || -> NSInteger {
+                unsafe {
+                    let pasteboard = NSPasteboard::generalPasteboard(nil);
+                    msg_send![pasteboard, changeCount]
+                }
+            };
+            let mut last_count = change_count();
+            loop {
+                async_io::Timer::after(std::time::Duration::from_millis(500)).await;
+                let count = change_count();
+                if count != last_count {
+                    last_count = count;
+                    if let Some(conn) = Connection::get() {
+                        conn.advise_of_clipboard_change(Clipboard::Clipboard);
+                    }
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Delivers a clipboard change notification to the key window,
+    /// or to an arbitrary window if none of ours is the key window
+    fn advise_of_clipboard_change(&self, clipboard: Clipboard) {
+        let windows = self.windows.borrow();
+        let target = windows
+            .values()
+            .find(|win| win.borrow().is_key_window())
+            .or_else(|| windows.values().next());
+        if let Some(win) = target {
+            win.borrow_mut().clipboard_changed(clipboard);
+        }
+    }
+
     pub(crate) fn next_window_id(&self) -> usize {
         self.next_window_id
             .fetch_add(1, ::std::sync::atomic::Ordering::Relaxed)
@@ -122,6 +165,10 @@ impl ConnectionOps for Connection {
         }
     }
 
+    fn set_tray_icon(&self, tray: Option<TrayIcon>) -> anyhow::Result<()> {
+        super::tray::set_tray_icon(tray)
+    }
+
     fn screens(&self) -> anyhow::Result<Screens> {
         let mut by_name = HashMap::new();
         let mut virtual_rect = euclid::rect(0, 0, 0, 0);
@@ -6,6 +6,8 @@ use objc::*;
 
 pub mod bitmap;
 pub mod connection;
+mod services;
+mod tray;
 pub mod window;
 
 mod keycodes;
@@ -0,0 +1,112 @@
+//! Provides the items that wezterm adds to the Services menu, which
+//! are declared by `NSServices` in our Info.plist, and handles the
+//! `wezterm://` URLs that are declared by `CFBundleURLTypes`.
+use super::nsstring_to_str;
+use crate::services::{self, ServiceRequest};
+use cocoa::appkit::NSApp;
+use cocoa::base::{id, nil, YES};
+use cocoa::foundation::NSArray;
+use objc::declare::ClassDecl;
+use objc::runtime::{Class, Object, Sel, BOOL};
+use objc::*;
+use std::path::{Path, PathBuf};
+
+const PROVIDER_CLS_NAME: &str = "WezTermServicesProvider";
+/// kInternetEventClass and kAEGetURL are both 'GURL'
+const GET_URL_EVENT: u32 = u32::from_be_bytes(*b"GURL");
+/// keyDirectObject
+const DIRECT_OBJECT: u32 = u32::from_be_bytes(*b"----");
+
+extern "C" {
+    fn NSUpdateDynamicServices();
+}
+
+/// Returns the directory that is selected on the pasteboard.
+/// If a file is selected, returns the directory that contains it.
+unsafe fn selected_directory(pboard: id) -> Option<PathBuf> {
+    let classes = NSArray::arrayWithObject(nil, class!(NSURL) as *const Class as id);
+    let urls: id = msg_send![pboard, readObjectsForClasses: classes options: nil];
+    if urls == nil {
+        return None;
+    }
+    for idx in 0..NSArray::count(urls) {
+        let url = NSArray::objectAtIndex(urls, idx);
+        let is_file: BOOL = msg_send![url, isFileURL];
+        if is_file != YES {
+            continue;
+        }
+        let path = PathBuf::from(nsstring_to_str(msg_send![url, path]));
+        if path.is_dir() {
+            return Some(path);
+        }
+        return path.parent().map(Path::to_path_buf);
+    }
+    None
+}
+
+extern "C" fn new_tab(_this: &mut Object, _sel: Sel, pboard: id, _user_data: id, _error: id) {
+    let cwd = unsafe { selected_directory(pboard) };
+    services::dispatch(ServiceRequest::NewTab { cwd });
+}
+
+extern "C" fn new_window(_this: &mut Object, _sel: Sel, pboard: id, _user_data: id, _error: id) {
+    let cwd = unsafe { selected_directory(pboard) };
+    services::dispatch(ServiceRequest::NewWindow { cwd });
+}
+
+extern "C" fn get_url(_this: &mut Object, _sel: Sel, event: id, _reply: id) {
+    let url = unsafe {
+        let descriptor: id = msg_send![event, paramDescriptorForKeyword: DIRECT_OBJECT];
+        if descriptor == nil {
+            return;
+        }
+        nsstring_to_str(msg_send![descriptor, stringValue]).to_string()
+    };
+    match ServiceRequest::from_url(&url) {
+        Ok(request) => services::dispatch(request),
+        Err(err) => log::error!("{:#}", err),
+    }
+}
+
+fn get_provider_class() -> &'static Class {
+    Class::get(PROVIDER_CLS_NAME).unwrap_or_else(|| {
+        let mut cls = ClassDecl::new(PROVIDER_CLS_NAME, class!(NSObject))
+            .expect("Unable to register services provider class");
+        unsafe {
+            cls.add_method(
+                sel!(newTabHere:userData:error:),
+                new_tab as extern "C" fn(&mut Object, Sel, id, id, id),
+            );
+            cls.add_method(
+                sel!(newWindowHere:userData:error:),
+                new_window as extern "C" fn(&mut Object, Sel, id, id, id),
+            );
+            cls.add_method(
+                sel!(handleGetURLEvent:withReplyEvent:),
+                get_url as extern "C" fn(&mut Object, Sel, id, id),
+            );
+        }
+        cls.register()
+    })
+}
+
+/// Registers the services provider and the handler for `wezterm://`
+/// URLs.  Requests are passed to the callback that was set using
+/// `window::services::set_callback`.
+pub fn register() {
+    unsafe {
+        // The provider lives for the life of the process
+        let provider: id = msg_send![get_provider_class(), new];
+        let () = msg_send![NSApp(), setServicesProvider: provider];
+
+        let event_manager: id = msg_send![class!(NSAppleEventManager), sharedAppleEventManager];
+        let () = msg_send![event_manager,
+            setEventHandler: provider
+            andSelector: sel!(handleGetURLEvent:withReplyEvent:)
+            forEventClass: GET_URL_EVENT
+            andEventID: GET_URL_EVENT
+        ];
+
+        NSUpdateDynamicServices();
+    }
+}
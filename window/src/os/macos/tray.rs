@@ -0,0 +1,196 @@
+//! The status item that is shown in the menu bar
+use super::nsstring;
+use crate::bitmaps::BitmapImage;
+use crate::tray::{self, TrayEvent, TrayIcon, TrayMenuItem};
+use cocoa::appkit::NSApp;
+use cocoa::base::{id, nil, NO, YES};
+use cocoa::foundation::{NSInteger, NSSize};
+use core_graphics::base::{
+    kCGBitmapByteOrderDefault, kCGImageAlphaLast, kCGRenderingIntentDefault,
+};
+use core_graphics::color_space::CGColorSpace;
+use core_graphics::data_provider::CGDataProvider;
+use core_graphics::image::{CGImage, CGImageRef};
+use objc::declare::ClassDecl;
+use objc::runtime::{Class, Object, Sel};
+use objc::*;
+use std::cell::RefCell;
+use std::sync::Arc;
+
+const TARGET_CLS_NAME: &str = "WezTermTrayTarget";
+/// NSVariableStatusItemLength
+const VARIABLE_LENGTH: f64 = -1.0;
+/// The height of the menu bar icon, in points
+const ICON_SIZE: f64 = 18.0;
+const NS_EVENT_TYPE_RIGHT_MOUSE_UP: u64 = 4;
+const NS_EVENT_MASK_LEFT_MOUSE_UP: u64 = 1 << 2;
+const NS_EVENT_MASK_RIGHT_MOUSE_UP: u64 = 1 << 4;
+
+struct TrayState {
+    status_item: id,
+    target: id,
+    menu: id,
+}
+
+impl Drop for TrayState {
+    fn drop(&mut self) {
+        unsafe {
+            let status_bar: id = msg_send![class!(NSStatusBar), systemStatusBar];
+            let () = msg_send![status_bar, removeStatusItem: self.status_item];
+            let () = msg_send![self.status_item, release];
+            let () = msg_send![self.menu, release];
+            let () = msg_send![self.target, release];
+        }
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<Option<TrayState>> = RefCell::new(None);
+}
+
+extern "C" fn activate(_this: &mut Object, _sel: Sel, _sender: id) {
+    let is_right_click = unsafe {
+        let event: id = msg_send![NSApp(), currentEvent];
+        let event_type: u64 = if event.is_null() {
+            0
+        } else {
+            msg_send![event, type]
+        };
+        event_type == NS_EVENT_TYPE_RIGHT_MOUSE_UP
+    };
+
+    if is_right_click {
+        // Don't hold the borrow while the menu is tracking, as a
+        // selected item may replace the tray icon
+        let item_and_menu = STATE.with(|s| {
+            s.borrow()
+                .as_ref()
+                .map(|state| (state.status_item, state.menu))
+        });
+        if let Some((status_item, menu)) = item_and_menu {
+            unsafe {
+                let () = msg_send![status_item, popUpStatusItemMenu: menu];
+            }
+        }
+    } else {
+        tray::dispatch(TrayEvent::Activate);
+    }
+}
+
+extern "C" fn menu_item_selected(_this: &mut Object, _sel: Sel, sender: id) {
+    let tag: NSInteger = unsafe { msg_send![sender, tag] };
+    tray::dispatch(TrayEvent::MenuItem(tag as usize));
+}
+
+fn get_target_class() -> &'static Class {
+    Class::get(TARGET_CLS_NAME).unwrap_or_else(|| {
+        let mut cls = ClassDecl::new(TARGET_CLS_NAME, class!(NSObject))
+            .expect("Unable to register tray target class");
+        unsafe {
+            cls.add_method(
+                sel!(trayActivated:),
+                activate as extern "C" fn(&mut Object, Sel, id),
+            );
+            cls.add_method(
+                sel!(trayMenuItemSelected:),
+                menu_item_selected as extern "C" fn(&mut Object, Sel, id),
+            );
+        }
+        cls.register()
+    })
+}
+
+/// Builds an NSImage from the rgba32 data in `image`.
+/// The result is autoreleased.
+unsafe fn ns_image(image: &crate::Image) -> id {
+    let (width, height) = image.image_dimensions();
+    let data = std::slice::from_raw_parts(image.pixel_data(), width * height * 4).to_vec();
+    let provider = CGDataProvider::from_buffer(Arc::new(data));
+    let cg_image = CGImage::new(
+        width,
+        height,
+        8,
+        32,
+        width * 4,
+        &CGColorSpace::create_device_rgb(),
+        kCGImageAlphaLast | kCGBitmapByteOrderDefault,
+        &provider,
+        true,
+        kCGRenderingIntentDefault,
+    );
+    // A reference to a foreign type has the address of the
+    // underlying CGImageRef, so we can pass it to objc directly
+    let cg_image: &CGImageRef = &cg_image;
+    let ns_image: id = msg_send![class!(NSImage), alloc];
+    let ns_image: id = msg_send![ns_image,
+        initWithCGImage: cg_image as *const CGImageRef as id
+        size: NSSize::new(ICON_SIZE, ICON_SIZE)
+    ];
+    msg_send![ns_image, autorelease]
+}
+
+unsafe fn build_menu(target: id, items: &[TrayMenuItem]) -> id {
+    let menu: id = msg_send![class!(NSMenu), alloc];
+    let menu: id = msg_send![menu, init];
+    let () = msg_send![menu, setAutoenablesItems: NO];
+    for (idx, item) in items.iter().enumerate() {
+        let menu_item: id = match item {
+            TrayMenuItem::Item { label, enabled } => {
+                let menu_item: id = msg_send![class!(NSMenuItem), alloc];
+                let menu_item: id = msg_send![menu_item,
+                    initWithTitle: *nsstring(label)
+                    action: sel!(trayMenuItemSelected:)
+                    keyEquivalent: *nsstring("")
+                ];
+                let () = msg_send![menu_item, setTarget: target];
+                let () = msg_send![menu_item, setTag: idx as NSInteger];
+                let () = msg_send![menu_item, setEnabled: if *enabled { YES } else { NO }];
+                msg_send![menu_item, autorelease]
+            }
+            TrayMenuItem::Separator => msg_send![class!(NSMenuItem), separatorItem],
+        };
+        let () = msg_send![menu, addItem: menu_item];
+    }
+    menu
+}
+
+pub fn set_tray_icon(icon: Option<TrayIcon>) -> anyhow::Result<()> {
+    // Dropping the prior state removes its status item
+    STATE.with(|s| s.borrow_mut().take());
+    let icon = match icon {
+        Some(icon) => icon,
+        None => {
+            tray::set_callback(None);
+            return Ok(());
+        }
+    };
+
+    unsafe {
+        let target: id = msg_send![get_target_class(), new];
+        let menu = build_menu(target, &icon.menu);
+
+        let status_bar: id = msg_send![class!(NSStatusBar), systemStatusBar];
+        let status_item: id = msg_send![status_bar, statusItemWithLength: VARIABLE_LENGTH];
+        let () = msg_send![status_item, retain];
+
+        let button: id = msg_send![status_item, button];
+        if button != nil {
+            let () = msg_send![button, setImage: ns_image(&icon.icon)];
+            let () = msg_send![button, setToolTip: *nsstring(&icon.tooltip)];
+            let () = msg_send![button, setTarget: target];
+            let () = msg_send![button, setAction: sel!(trayActivated:)];
+            let () = msg_send![button,
+                sendActionOn: NS_EVENT_MASK_LEFT_MOUSE_UP | NS_EVENT_MASK_RIGHT_MOUSE_UP];
+        }
+
+        tray::set_callback(Some(icon.callback));
+        STATE.with(|s| {
+            s.borrow_mut().replace(TrayState {
+                status_item,
+                target,
+                menu,
+            })
+        });
+    }
+    Ok(())
+}
@@ -6,10 +6,11 @@ use super::{nsstring, nsstring_to_str};
 use crate::connection::ConnectionOps;
 use crate::parameters::{Border, Parameters, TitleBar};
 use crate::{
-    Clipboard, Connection, DeadKeyStatus, Dimensions, Handled, KeyCode, KeyEvent, Modifiers,
-    MouseButtons, MouseCursor, MouseEvent, MouseEventKind, MousePress, Point, RawKeyEvent, Rect,
-    RequestedWindowGeometry, ResolvedGeometry, ScreenPoint, Size, ULength, WindowDecorations,
-    WindowEvent, WindowEventSender, WindowOps, WindowState,
+    Clipboard, Connection, DeadKeyStatus, Dimensions, Gesture, Handled, InputRegion, KeyCode,
+    KeyEvent, Modifiers, MouseButtons, MouseCursor, MouseEvent, MouseEventKind, MousePress, Point,
+    RawKeyEvent, Rect, RequestedWindowGeometry, ResizeIncrement, ResolvedGeometry, ScreenPoint,
+    Size, SwipeDirection, ULength, WindowDecorations, WindowEvent, WindowEventSender, WindowOps,
+    WindowState,
 };
 use anyhow::{anyhow, bail, ensure};
 use async_trait::async_trait;
@@ -270,6 +271,13 @@ mod cglbits {
                     &opaque,
                     cocoa::appkit::NSOpenGLContextParameter::NSOpenGLCPSurfaceOpacity,
                 );
+                if let Some(swap_interval) = crate::configuration::swap_interval() {
+                    let swap_interval: cgl::GLint = swap_interval;
+                    gl_context.setValues_forParameter_(
+                        &swap_interval,
+                        cocoa::appkit::NSOpenGLContextParameter::NSOpenGLCPSwapInterval,
+                    );
+                }
 
                 gl_context.setView_(view);
             }
@@ -345,6 +353,8 @@ pub(crate) struct WindowInner {
     view: StrongPtr,
     window: StrongPtr,
     config: ConfigHandle,
+    /// The radius of the rounded corners, in pixels
+    corner_radius: Option<f32>,
 }
 
 fn function_key_to_keycode(function_key: char) -> KeyCode {
@@ -445,6 +455,7 @@ impl Window {
                 ime_state: ImeDisposition::None,
                 ime_last_event: None,
                 live_resizing: false,
+                resize_increments: None,
                 ime_text: String::new(),
             }));
 
@@ -529,6 +540,7 @@ impl Window {
                 window,
                 view,
                 config: config.clone(),
+                corner_radius: None,
             }));
             inner.borrow_mut().window.replace(weak_window);
             conn.windows
@@ -659,6 +671,29 @@ impl WindowOps for Window {
         });
     }
 
+    fn get_window_position(&self) -> Future<Option<ScreenPoint>> {
+        Connection::with_window_inner(self.id, |inner| {
+            let content_frame = unsafe {
+                let frame = NSWindow::frame(*inner.window);
+                NSWindow::contentRectForFrameRect_(*inner.window, frame)
+            };
+            // The origin of the content frame is its bottom left corner
+            Ok(Some(cartesian_to_screen_point(NSPoint::new(
+                content_frame.origin.x,
+                content_frame.origin.y + content_frame.size.height,
+            ))))
+        })
+    }
+
+    fn focus(&self) {
+        // Showing the window activates the application
+        // and makes the window key
+        Connection::with_window_inner(self.id, |inner| {
+            inner.show();
+            Ok(())
+        });
+    }
+
     fn set_text_cursor_position(&self, cursor: Rect) {
         Connection::with_window_inner(self.id, move |inner| {
             inner.set_text_cursor_position(cursor);
@@ -701,9 +736,23 @@ impl WindowOps for Window {
         });
     }
 
-    fn set_resize_increments(&self, x: u16, y: u16) {
+    fn set_resize_increments(&self, incr: ResizeIncrement) {
         Connection::with_window_inner(self.id, move |inner| {
-            inner.set_resize_increments(x, y);
+            inner.set_resize_increments(incr);
+            Ok(())
+        });
+    }
+
+    fn set_input_region(&self, region: InputRegion) {
+        Connection::with_window_inner(self.id, move |inner| {
+            inner.set_input_region(&region);
+            Ok(())
+        });
+    }
+
+    fn set_corner_radius(&self, radius: Option<f32>) {
+        Connection::with_window_inner(self.id, move |inner| {
+            inner.set_corner_radius(radius);
             Ok(())
         });
     }
@@ -925,8 +974,12 @@ impl WindowInner {
         } else {
             NO
         };
+        // The transparent corners require a non-opaque window,
+        // but the shadow can follow the rounded shape
+        let is_rounded = self.corner_radius.map_or(false, |radius| radius >= 1.);
         unsafe {
-            self.window.setOpaque_(is_opaque);
+            self.window
+                .setOpaque_(if is_rounded { NO } else { is_opaque });
             // when transparent, also turn off the window shadow,
             // because having the shadow enabled seems to correlate
             // with ghostly remnants see:
@@ -987,6 +1040,23 @@ impl WindowInner {
             let () = msg_send![*self.view, setNeedsDisplay: YES];
         }
     }
+
+    pub(crate) fn is_key_window(&self) -> bool {
+        unsafe {
+            let key: BOOL = msg_send![*self.window, isKeyWindow];
+            key == YES
+        }
+    }
+
+    pub(crate) fn clipboard_changed(&mut self, clipboard: Clipboard) {
+        if let Some(window_view) = WindowView::get_this(unsafe { &**self.view }) {
+            window_view
+                .inner
+                .borrow_mut()
+                .events
+                .dispatch(WindowEvent::ClipboardChanged(clipboard));
+        }
+    }
     fn set_title(&mut self, title: &str) {
         let title = nsstring(title);
         unsafe {
@@ -1082,10 +1152,58 @@ impl WindowInner {
         }
     }
 
-    fn set_resize_increments(&self, x: u16, y: u16) {
+    fn set_resize_increments(&self, incr: ResizeIncrement) {
+        // The increments are applied by `windowWillResize:toSize:`,
+        // as NSWindow has no notion of a base size
+        if let Some(window_view) = WindowView::get_this(unsafe { &**self.view }) {
+            window_view.inner.borrow_mut().resize_increments = Some(incr);
+        }
+    }
+
+    fn set_corner_radius(&mut self, radius: Option<f32>) {
+        if radius.is_none() && self.corner_radius.is_none() {
+            return;
+        }
+        self.corner_radius = radius;
+        unsafe {
+            let view = *self.view;
+            let layer: id = msg_send![view, layer];
+            if layer.is_null() {
+                if radius.is_none() {
+                    return;
+                }
+                let _: () = msg_send![view, setWantsLayer: YES];
+            }
+            let layer: id = msg_send![view, layer];
+            if layer.is_null() {
+                return;
+            }
+
+            // The layer is measured in points rather than pixels
+            let frame = NSView::frame(view);
+            let backing_frame = NSView::convertRectToBacking(view, frame);
+            let scale = if frame.size.width > 0. {
+                backing_frame.size.width / frame.size.width
+            } else {
+                1.0
+            };
+            let radius = radius.unwrap_or(0.) as f64 / scale;
+            let _: () = msg_send![layer, setCornerRadius: radius];
+            let _: () = msg_send![layer, setMasksToBounds: if radius > 0. { YES } else { NO }];
+        }
+        self.update_window_shadow();
+        unsafe {
+            // The shadow follows the shape of the rounded content
+            let () = msg_send![*self.window, invalidateShadow];
+        }
+    }
+
+    fn set_input_region(&self, region: &InputRegion) {
+        // NSWindow can only ignore mouse events for the whole window
+        let ignore = *region == InputRegion::Nowhere;
         unsafe {
             self.window
-                .setResizeIncrements_(NSSize::new(x.into(), y.into()));
+                .setIgnoresMouseEvents_(if ignore { YES } else { NO });
         }
     }
 
@@ -1198,6 +1316,8 @@ struct Inner {
 
     /// Whether we're in live resize
     live_resizing: bool,
+    /// Constrains interactive resizes of the window
+    resize_increments: Option<ResizeIncrement>,
 
     ime_text: String,
 }
@@ -1875,6 +1995,26 @@ impl WindowView {
         }
     }
 
+    extern "C" fn did_change_occlusion_state(this: &mut Object, _sel: Sel, _id: id) {
+        /// NSWindowOcclusionStateVisible
+        const OCCLUSION_STATE_VISIBLE: NSUInteger = 1 << 1;
+
+        if let Some(this) = Self::get_this(this) {
+            let window = match this.inner.borrow().window.as_ref() {
+                Some(window) => window.load(),
+                None => return,
+            };
+            let state: NSUInteger = unsafe { msg_send![*window, occlusionState] };
+            // The window is occluded when it is minimized, on another
+            // space, or completely covered by other windows
+            let visible = state & OCCLUSION_STATE_VISIBLE != 0;
+            this.inner
+                .borrow_mut()
+                .events
+                .dispatch(WindowEvent::VisibilityChanged(visible));
+        }
+    }
+
     // Switch the coordinate system to have 0,0 in the top left
     extern "C" fn is_flipped(_this: &Object, _sel: Sel) -> BOOL {
         YES
@@ -2032,6 +2172,48 @@ impl WindowView {
         Self::mouse_common(this, nsevent, kind);
     }
 
+    /// Trackpad gestures are recognized by the system, rather
+    /// than reported as touch events.  They are only dispatched
+    /// when the touch gestures are enabled.
+    fn gesture_common(this: &mut Object, gesture: Gesture) {
+        if let Some(myself) = Self::get_this(this) {
+            let mut inner = myself.inner.borrow_mut();
+            if !inner.config.enable_touch_gestures {
+                return;
+            }
+            inner.events.dispatch(WindowEvent::Gesture(gesture));
+        }
+    }
+
+    extern "C" fn magnify_with_event(this: &mut Object, _sel: Sel, nsevent: id) {
+        let magnification: CGFloat = unsafe { msg_send![nsevent, magnification] };
+        Self::gesture_common(
+            this,
+            Gesture::Pinch {
+                scale: 1. + magnification as f32,
+            },
+        );
+    }
+
+    extern "C" fn swipe_with_event(this: &mut Object, _sel: Sel, nsevent: id) {
+        let dx = unsafe { nsevent.deltaX() };
+        let dy = unsafe { nsevent.deltaY() };
+        // deltaX is positive for a swipe to the left, and deltaY
+        // is positive for a swipe up
+        let direction = if dx > 0. {
+            SwipeDirection::Left
+        } else if dx < 0. {
+            SwipeDirection::Right
+        } else if dy > 0. {
+            SwipeDirection::Up
+        } else if dy < 0. {
+            SwipeDirection::Down
+        } else {
+            return;
+        };
+        Self::gesture_common(this, Gesture::Swipe(direction));
+    }
+
     extern "C" fn right_mouse_down(this: &mut Object, _sel: Sel, nsevent: id) {
         Self::mouse_common(this, nsevent, MouseEventKind::Press(MousePress::Right));
     }
@@ -2470,6 +2652,51 @@ impl WindowView {
         }
     }
 
+    /// Snaps the size of the content of the window, in pixels, to the
+    /// resize increments.  The size that is proposed and returned is
+    /// that of the frame of the window, in points.
+    extern "C" fn will_resize(
+        this: &mut Object,
+        _sel: Sel,
+        _window: id,
+        frame_size: NSSize,
+    ) -> NSSize {
+        let incr = match Self::get_this(this).and_then(|this| {
+            this.inner
+                .try_borrow()
+                .ok()
+                .and_then(|i| i.resize_increments)
+        }) {
+            Some(incr) => incr,
+            None => return frame_size,
+        };
+
+        unsafe {
+            let window: id = msg_send![this as *mut _, window];
+            if window == nil {
+                return frame_size;
+            }
+            let window_frame = NSWindow::frame(window);
+            let content_frame = NSView::frame(this as *mut _);
+            let backing_frame = NSView::convertRectToBacking(this as *mut _, content_frame);
+            if content_frame.size.width <= 0. {
+                return frame_size;
+            }
+            let scale = backing_frame.size.width / content_frame.size.width;
+            let decoration_width = window_frame.size.width - content_frame.size.width;
+            let decoration_height = window_frame.size.height - content_frame.size.height;
+
+            let (width, height) = incr.snap(
+                ((frame_size.width - decoration_width) * scale).max(0.) as usize,
+                ((frame_size.height - decoration_height) * scale).max(0.) as usize,
+            );
+            NSSize::new(
+                width as f64 / scale + decoration_width,
+                height as f64 / scale + decoration_height,
+            )
+        }
+    }
+
     extern "C" fn did_resize(this: &mut Object, _sel: Sel, _notification: id) {
         if let Some(this) = Self::get_this(this) {
             let inner = this.inner.borrow_mut();
@@ -2660,6 +2887,10 @@ impl WindowView {
                 sel!(windowDidEndLiveResize:),
                 Self::did_end_live_resize as extern "C" fn(&mut Object, Sel, id),
             );
+            cls.add_method(
+                sel!(windowWillResize:toSize:),
+                Self::will_resize as extern "C" fn(&mut Object, Sel, id, NSSize) -> NSSize,
+            );
             cls.add_method(
                 sel!(windowDidResize:),
                 Self::did_resize as extern "C" fn(&mut Object, Sel, id),
@@ -2677,6 +2908,10 @@ impl WindowView {
                 sel!(windowDidResignKey:),
                 Self::did_resign_key as extern "C" fn(&mut Object, Sel, id),
             );
+            cls.add_method(
+                sel!(windowDidChangeOcclusionState:),
+                Self::did_change_occlusion_state as extern "C" fn(&mut Object, Sel, id),
+            );
 
             cls.add_method(
                 sel!(mouseMoved:),
@@ -2718,6 +2953,14 @@ impl WindowView {
                 sel!(scrollWheel:),
                 Self::scroll_wheel as extern "C" fn(&mut Object, Sel, id),
             );
+            cls.add_method(
+                sel!(magnifyWithEvent:),
+                Self::magnify_with_event as extern "C" fn(&mut Object, Sel, id),
+            );
+            cls.add_method(
+                sel!(swipeWithEvent:),
+                Self::swipe_with_event as extern "C" fn(&mut Object, Sel, id),
+            );
             cls.add_method(
                 sel!(mouseExited:),
                 Self::mouse_exited as extern "C" fn(&mut Object, Sel, id),
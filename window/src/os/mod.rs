@@ -3,6 +3,7 @@ pub mod windows;
 #[cfg(windows)]
 pub use self::windows::*;
 
+pub mod status_notifier;
 #[cfg(feature = "wayland")]
 pub mod wayland;
 pub mod x11;
@@ -0,0 +1,338 @@
+#![cfg(all(unix, not(target_os = "macos")))]
+
+//! <https://www.freedesktop.org/wiki/Specifications/StatusNotifierItem/StatusNotifierItem/>
+//! <https://github.com/AyatanaIndicators/libdbusmenu/blob/master/libdbusmenu-glib/dbus-menu.xml>
+
+use crate::bitmaps::BitmapImage;
+use crate::tray::{self, TrayEvent, TrayIcon, TrayMenuItem};
+use crate::Image;
+use anyhow::Context;
+use serde::Serialize;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use zbus::{dbus_interface, dbus_proxy};
+use zvariant::{OwnedObjectPath, OwnedValue, StructureBuilder, Type, Value};
+
+const ITEM_PATH: &str = "/StatusNotifierItem";
+const MENU_PATH: &str = "/MenuBar";
+
+#[dbus_proxy(
+    interface = "org.kde.StatusNotifierWatcher",
+    default_service = "org.kde.StatusNotifierWatcher",
+    default_path = "/StatusNotifierWatcher"
+)]
+trait StatusNotifierWatcher {
+    fn register_status_notifier_item(&self, service: &str) -> zbus::Result<()>;
+}
+
+/// The icon data, as width, height and ARGB32 pixels in network byte order
+type Pixmap = (i32, i32, Vec<u8>);
+
+fn image_to_pixmap(image: &Image) -> Pixmap {
+    let (width, height) = image.image_dimensions();
+    let mut data = Vec::with_capacity(width * height * 4);
+    // `Image` is rgba32, so we need to munge to get argb32
+    for pixel in image.pixels() {
+        let [r, g, b, a] = pixel.to_ne_bytes();
+        data.extend_from_slice(&[a, r, g, b]);
+    }
+    (width as i32, height as i32, data)
+}
+
+/// Delivers an event that was received on the dbus executor
+/// thread to the tray callback on the main thread
+fn send_event(event: TrayEvent) {
+    promise::spawn::spawn_into_main_thread(async move {
+        tray::dispatch(event);
+    })
+    .detach();
+}
+
+struct StatusNotifierItem {
+    tooltip: String,
+    pixmap: Pixmap,
+}
+
+#[dbus_interface(name = "org.kde.StatusNotifierItem")]
+impl StatusNotifierItem {
+    fn activate(&self, _x: i32, _y: i32) {
+        send_event(TrayEvent::Activate);
+    }
+
+    fn secondary_activate(&self, _x: i32, _y: i32) {}
+
+    fn scroll(&self, _delta: i32, _orientation: &str) {}
+
+    #[dbus_interface(property)]
+    fn category(&self) -> String {
+        "ApplicationStatus".to_string()
+    }
+
+    #[dbus_interface(property)]
+    fn id(&self) -> String {
+        "wezterm".to_string()
+    }
+
+    #[dbus_interface(property)]
+    fn title(&self) -> String {
+        self.tooltip.clone()
+    }
+
+    #[dbus_interface(property)]
+    fn status(&self) -> String {
+        "Active".to_string()
+    }
+
+    #[dbus_interface(property)]
+    fn icon_name(&self) -> String {
+        String::new()
+    }
+
+    #[dbus_interface(property)]
+    fn icon_pixmap(&self) -> Vec<Pixmap> {
+        vec![self.pixmap.clone()]
+    }
+
+    #[dbus_interface(property)]
+    fn tool_tip(&self) -> (String, Vec<Pixmap>, String, String) {
+        (String::new(), vec![], self.tooltip.clone(), String::new())
+    }
+
+    #[dbus_interface(property)]
+    fn item_is_menu(&self) -> bool {
+        false
+    }
+
+    #[dbus_interface(property)]
+    fn menu(&self) -> OwnedObjectPath {
+        OwnedObjectPath::try_from(MENU_PATH).expect("valid object path")
+    }
+}
+
+/// The root of the menu layout; each of the children is a
+/// variant holding a structure of the same shape
+#[derive(Serialize, Type)]
+struct MenuLayout {
+    id: i32,
+    properties: HashMap<String, OwnedValue>,
+    children: Vec<OwnedValue>,
+}
+
+/// Implements the dbusmenu protocol that is used by the tray host
+/// to render the menu.  The menu is flat; id 0 is the root and the
+/// item at index N in the menu has id N+1.
+struct DBusMenu {
+    items: Vec<TrayMenuItem>,
+}
+
+impl DBusMenu {
+    fn item_properties(&self, id: i32) -> HashMap<String, OwnedValue> {
+        let mut props = HashMap::new();
+        if id == 0 {
+            props.insert(
+                "children-display".to_string(),
+                Value::from("submenu").into(),
+            );
+            return props;
+        }
+        match self.items.get(id as usize - 1) {
+            Some(TrayMenuItem::Item { label, enabled }) => {
+                props.insert("label".to_string(), Value::from(label.as_str()).into());
+                props.insert("enabled".to_string(), Value::from(*enabled).into());
+            }
+            Some(TrayMenuItem::Separator) => {
+                props.insert("type".to_string(), Value::from("separator").into());
+            }
+            None => {}
+        }
+        props
+    }
+
+    fn item_value(&self, id: i32) -> OwnedValue {
+        let children: Vec<Value> = vec![];
+        let props: HashMap<String, Value> = self
+            .item_properties(id)
+            .into_iter()
+            .map(|(name, value)| (name, Value::from(value)))
+            .collect();
+        Value::from(
+            StructureBuilder::new()
+                .add_field(id)
+                .add_field(props)
+                .add_field(children)
+                .build(),
+        )
+        .into()
+    }
+
+    fn is_valid_id(&self, id: i32) -> bool {
+        id >= 0 && id as usize <= self.items.len()
+    }
+}
+
+#[dbus_interface(name = "com.canonical.dbusmenu")]
+impl DBusMenu {
+    fn get_layout(
+        &self,
+        parent_id: i32,
+        _recursion_depth: i32,
+        _property_names: Vec<String>,
+    ) -> (u32, MenuLayout) {
+        let children = if parent_id == 0 {
+            (1..=self.items.len() as i32)
+                .map(|id| self.item_value(id))
+                .collect()
+        } else {
+            vec![]
+        };
+        (
+            0,
+            MenuLayout {
+                id: parent_id,
+                properties: self.item_properties(parent_id),
+                children,
+            },
+        )
+    }
+
+    fn get_group_properties(
+        &self,
+        ids: Vec<i32>,
+        _property_names: Vec<String>,
+    ) -> Vec<(i32, HashMap<String, OwnedValue>)> {
+        ids.into_iter()
+            .filter(|&id| self.is_valid_id(id))
+            .map(|id| (id, self.item_properties(id)))
+            .collect()
+    }
+
+    fn get_property(&self, id: i32, name: &str) -> zbus::fdo::Result<OwnedValue> {
+        self.item_properties(id)
+            .remove(name)
+            .ok_or_else(|| zbus::fdo::Error::InvalidArgs(format!("no property {name}")))
+    }
+
+    fn event(&self, id: i32, event_id: &str, _data: OwnedValue, _timestamp: u32) {
+        if event_id == "clicked" && id > 0 && self.is_valid_id(id) {
+            send_event(TrayEvent::MenuItem(id as usize - 1));
+        }
+    }
+
+    fn event_group(&self, events: Vec<(i32, String, OwnedValue, u32)>) -> Vec<i32> {
+        let mut errors = vec![];
+        for (id, event_id, data, timestamp) in events {
+            if self.is_valid_id(id) {
+                self.event(id, &event_id, data, timestamp);
+            } else {
+                errors.push(id);
+            }
+        }
+        errors
+    }
+
+    fn about_to_show(&self, _id: i32) -> bool {
+        false
+    }
+
+    fn about_to_show_group(&self, ids: Vec<i32>) -> (Vec<i32>, Vec<i32>) {
+        let errors = ids
+            .into_iter()
+            .filter(|&id| !self.is_valid_id(id))
+            .collect();
+        (vec![], errors)
+    }
+
+    #[dbus_interface(property)]
+    fn version(&self) -> u32 {
+        3
+    }
+
+    #[dbus_interface(property)]
+    fn text_direction(&self) -> String {
+        "ltr".to_string()
+    }
+
+    #[dbus_interface(property)]
+    fn status(&self) -> String {
+        "normal".to_string()
+    }
+
+    #[dbus_interface(property)]
+    fn icon_theme_path(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+thread_local! {
+    /// Keeps the bus name and objects alive for as long as the icon is shown
+    static CONNECTION: RefCell<Option<zbus::Connection>> = RefCell::new(None);
+    /// Incremented each time the icon is changed, so that a registration
+    /// that completes after a later change can be discarded
+    static GENERATION: Cell<usize> = Cell::new(0);
+}
+
+async fn register(
+    generation: usize,
+    item: StatusNotifierItem,
+    menu: DBusMenu,
+) -> anyhow::Result<zbus::Connection> {
+    let name = format!(
+        "org.kde.StatusNotifierItem-{}-{}",
+        std::process::id(),
+        generation
+    );
+    let connection = zbus::ConnectionBuilder::session()?
+        .name(name.as_str())?
+        .serve_at(ITEM_PATH, item)?
+        .serve_at(MENU_PATH, menu)?
+        .build()
+        .await
+        .context("serving StatusNotifierItem")?;
+    let watcher = StatusNotifierWatcherProxy::new(&connection)
+        .await
+        .context("make proxy")?;
+    watcher
+        .register_status_notifier_item(&name)
+        .await
+        .context("RegisterStatusNotifierItem; is there a system tray running?")?;
+    Ok(connection)
+}
+
+pub fn set_tray_icon(icon: Option<TrayIcon>) -> anyhow::Result<()> {
+    let generation = GENERATION.with(|g| {
+        g.set(g.get() + 1);
+        g.get()
+    });
+    // Dropping the connection releases the name, which causes
+    // the tray host to remove the prior icon
+    CONNECTION.with(|c| c.borrow_mut().take());
+
+    let icon = match icon {
+        Some(icon) => icon,
+        None => {
+            tray::set_callback(None);
+            return Ok(());
+        }
+    };
+
+    let item = StatusNotifierItem {
+        tooltip: icon.tooltip,
+        pixmap: image_to_pixmap(&icon.icon),
+    };
+    let menu = DBusMenu { items: icon.menu };
+    tray::set_callback(Some(icon.callback));
+
+    promise::spawn::spawn(async move {
+        match register(generation, item, menu).await {
+            Ok(connection) => {
+                if GENERATION.with(|g| g.get()) == generation {
+                    CONNECTION.with(|c| c.borrow_mut().replace(connection));
+                }
+            }
+            Err(err) => log::error!("tray icon: {:#}", err),
+        }
+    })
+    .detach();
+    Ok(())
+}
@@ -7,7 +7,7 @@ use crate::os::wayland::output::OutputHandler;
 use crate::os::x11::keyboard::Keyboard;
 use crate::screen::{ScreenInfo, Screens};
 use crate::spawn::*;
-use crate::{Appearance, Connection, ScreenRect, WindowEvent};
+use crate::{Appearance, Connection, ScreenRect, TouchPhase, WindowEvent};
 use anyhow::{bail, Context};
 use mio::unix::SourceFd;
 use mio::{Events, Interest, Poll, Token};
@@ -23,6 +23,8 @@ use toolkit::reexports::client::Display;
 use toolkit::seat::SeatListener;
 use toolkit::shm::AutoMemPool;
 use wayland_client::protocol::wl_keyboard::{Event as WlKeyboardEvent, KeymapFormat, WlKeyboard};
+use wayland_client::protocol::wl_seat::WlSeat;
+use wayland_client::protocol::wl_touch::{Event as WlTouchEvent, WlTouch};
 use wayland_client::{EventQueue, Main};
 
 toolkit::default_environment!(MyEnvironment, desktop,
@@ -59,6 +61,9 @@ pub struct WaylandConnection {
     pub(crate) keyboard_mapper: RefCell<Option<Keyboard>>,
     pub(crate) keyboard_window_id: RefCell<Option<usize>>,
     pub(crate) surface_to_window_id: RefCell<HashMap<u32, usize>>,
+    /// The window that each finger that is touching the screen
+    /// touched, as only the touch down event identifies the surface
+    touch_window_ids: RefCell<HashMap<i32, usize>>,
     pub(crate) active_surface_id: RefCell<u32>,
 
     /// Repeats per second
@@ -95,17 +100,22 @@ impl WaylandConnection {
 
         let mut pointer = None;
         let mut seat_keyboards = HashMap::new();
+        let mut seat_touches = HashMap::new();
 
         for seat in environment.get_all_seats() {
-            if let Some((has_kbd, has_ptr, name)) =
+            if let Some((has_kbd, has_ptr, has_touch, name)) =
                 toolkit::seat::with_seat_data(&seat, |seat_data| {
                     (
                         seat_data.has_keyboard && !seat_data.defunct,
                         seat_data.has_pointer && !seat_data.defunct,
+                        seat_data.has_touch && !seat_data.defunct,
                         seat_data.name.clone(),
                     )
                 })
             {
+                if has_touch {
+                    seat_touches.insert(name.clone(), Self::get_touch(&seat));
+                }
                 if has_kbd {
                     let keyboard = seat.get_keyboard();
                     keyboard.quick_assign(|keyboard, event, _| {
@@ -172,6 +182,15 @@ impl WaylandConnection {
                     // fires for this seat with has_keyboard = true.
                     seat_keyboards.remove(&seat_data.name);
                 }
+                if seat_data.has_touch && !seat_data.defunct {
+                    // As with the keyboard, avoid assigning a second
+                    // touch object to the same seat
+                    if !seat_touches.contains_key(&seat_data.name) {
+                        seat_touches.insert(seat_data.name.clone(), Self::get_touch(&seat));
+                    }
+                } else {
+                    seat_touches.remove(&seat_data.name);
+                }
                 if seat_data.has_pointer && !seat_data.defunct {
                     let conn = Connection::get().unwrap().wayland();
                     conn.pointer.borrow_mut().seat_changed(&seat);
@@ -198,10 +217,76 @@ impl WaylandConnection {
             keyboard_window_id: RefCell::new(None),
             last_serial: RefCell::new(0),
             surface_to_window_id: RefCell::new(HashMap::new()),
+            touch_window_ids: RefCell::new(HashMap::new()),
             active_surface_id: RefCell::new(0),
         })
     }
 
+    fn get_touch(seat: &WlSeat) -> Main<WlTouch> {
+        let touch = seat.get_touch();
+        touch.quick_assign(|_touch, event, _| {
+            let conn = Connection::get().unwrap().wayland();
+            conn.touch_event(event);
+        });
+        touch
+    }
+
+    fn touch_event(&self, event: WlTouchEvent) {
+        let (id, phase, position) = match event {
+            WlTouchEvent::Down {
+                serial,
+                surface,
+                id,
+                x,
+                y,
+                ..
+            } => {
+                *self.last_serial.borrow_mut() = serial;
+                match self
+                    .surface_to_window_id
+                    .borrow()
+                    .get(&surface.as_ref().id())
+                {
+                    Some(&window_id) => {
+                        self.touch_window_ids.borrow_mut().insert(id, window_id);
+                    }
+                    None => return,
+                }
+                (id, TouchPhase::Began, Some((x, y)))
+            }
+            WlTouchEvent::Motion { id, x, y, .. } => (id, TouchPhase::Moved, Some((x, y))),
+            WlTouchEvent::Up { serial, id, .. } => {
+                *self.last_serial.borrow_mut() = serial;
+                (id, TouchPhase::Ended, None)
+            }
+            WlTouchEvent::Cancel => {
+                let mut window_ids: Vec<usize> = self
+                    .touch_window_ids
+                    .borrow_mut()
+                    .drain()
+                    .map(|(_, window_id)| window_id)
+                    .collect();
+                window_ids.sort_unstable();
+                window_ids.dedup();
+                for window_id in window_ids {
+                    if let Some(win) = self.window_by_id(window_id) {
+                        win.borrow_mut().cancel_touches();
+                    }
+                }
+                return;
+            }
+            _ => return,
+        };
+        let window_id = if phase == TouchPhase::Ended {
+            self.touch_window_ids.borrow_mut().remove(&id)
+        } else {
+            self.touch_window_ids.borrow().get(&id).copied()
+        };
+        if let Some(win) = window_id.and_then(|window_id| self.window_by_id(window_id)) {
+            win.borrow_mut().touch_event(id, phase, position);
+        }
+    }
+
     fn keyboard_event(
         &self,
         keyboard: Main<WlKeyboard>,
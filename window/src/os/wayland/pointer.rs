@@ -1,7 +1,7 @@
 use super::copy_and_paste::*;
 use super::drag_and_drop::*;
 use crate::os::wayland::connection::WaylandConnection;
-use crate::ConnectionOps;
+use crate::{Clipboard, ConnectionOps};
 use smithay_client_toolkit as toolkit;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
@@ -62,6 +62,18 @@ impl Inner {
         }
     }
 
+    fn advise_of_clipboard_change(&self, clipboard: Clipboard) {
+        let conn = WaylandConnection::get().unwrap().wayland();
+        let active_surface_id = conn.active_surface_id.borrow();
+        if let Some(pending) = self.surface_to_pending.get(&active_surface_id) {
+            let window_id = pending.lock().unwrap().window_id;
+            WaylandConnection::with_window_inner(window_id, move |inner| {
+                inner.clipboard_changed(clipboard);
+                Ok(())
+            });
+        }
+    }
+
     fn route_data_offer(&mut self, event: DataOfferEvent, offer: WlDataOffer) {
         if let Some(copy_and_paste) = self.resolve_copy_and_paste() {
             copy_and_paste
@@ -96,6 +108,10 @@ impl Inner {
                         copy_and_paste.lock().unwrap().confirm_selection(offer);
                     }
                 }
+                // The compositor only sends the selection to the focused
+                // client, so this is also how we learn about changes that
+                // were made while we were not focused
+                self.advise_of_clipboard_change(Clipboard::Clipboard);
             }
             _ => {}
         }
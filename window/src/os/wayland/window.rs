@@ -6,9 +6,9 @@ use crate::os::wayland::connection::WaylandConnection;
 use crate::os::wayland::wl_id;
 use crate::os::x11::keyboard::Keyboard;
 use crate::{
-    Appearance, Clipboard, Connection, Dimensions, MouseCursor, Point, Rect,
-    RequestedWindowGeometry, ResolvedGeometry, ScreenPoint, Window, WindowEvent, WindowEventSender,
-    WindowKeyEvent, WindowOps, WindowState,
+    Appearance, Clipboard, Connection, Dimensions, InputRegion, MouseCursor, Point, Rect,
+    RequestedWindowGeometry, ResizeIncrement, ResolvedGeometry, ScreenPoint, Window, WindowEvent,
+    WindowEventSender, WindowKeyEvent, WindowOps, WindowState,
 };
 use anyhow::{anyhow, bail, Context};
 use async_io::Timer;
@@ -21,6 +21,7 @@ use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
 use smithay_client_toolkit as toolkit;
 use std::any::Any;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::io::Read;
 use std::os::unix::io::AsRawFd;
@@ -33,6 +34,7 @@ use toolkit::reexports::client::protocol::wl_pointer::ButtonState;
 use toolkit::reexports::client::protocol::wl_surface::WlSurface;
 use toolkit::window::{Decorations, Event as SCTKWindowEvent, State};
 use wayland_client::protocol::wl_callback::WlCallback;
+use wayland_client::protocol::wl_compositor::WlCompositor;
 use wayland_client::protocol::wl_keyboard::{Event as WlKeyboardEvent, KeyState};
 use wayland_client::{Attached, Main};
 use wayland_egl::{is_available as egl_is_available, WlEglSurface};
@@ -131,9 +133,14 @@ pub struct WaylandWindowInner {
     copy_and_paste: Arc<Mutex<CopyAndPaste>>,
     window: Option<toolkit::window::Window<ConceptFrame>>,
     dimensions: Dimensions,
-    resize_increments: Option<(u16, u16)>,
+    resize_increments: Option<ResizeIncrement>,
+    input_region: InputRegion,
+    corner_radius: Option<f32>,
     window_state: WindowState,
     last_mouse_coords: Point,
+    /// The most recent position of each finger that is touching
+    /// the window, as the wayland touch up event has no position
+    touch_coords: HashMap<i32, Point>,
     mouse_buttons: MouseButtons,
     hscroll_remainder: f64,
     vscroll_remainder: f64,
@@ -143,6 +150,15 @@ pub struct WaylandWindowInner {
     pending_mouse: Arc<Mutex<PendingMouse>>,
     pending_first_configure: Option<async_channel::Sender<()>>,
     frame_callback: Option<Main<WlCallback>>,
+    // Counts the frame callbacks that have been requested, so that
+    // a callback that remains pending can be recognized
+    frame_serial: usize,
+    // Whether a timer is already watching for a frame callback
+    // that stays pending
+    visibility_check_pending: bool,
+    // Wayland doesn't tell us whether the window can be seen, but
+    // compositors don't call back for frames that won't be shown
+    visible: bool,
     invalidated: bool,
     font_config: Rc<FontConfiguration>,
     text_cursor: Option<Rect>,
@@ -359,8 +375,11 @@ impl WaylandWindow {
             window: Some(window),
             dimensions,
             resize_increments: None,
+            input_region: InputRegion::Everywhere,
+            corner_radius: None,
             window_state: WindowState::default(),
             last_mouse_coords: Point::new(0, 0),
+            touch_coords: HashMap::new(),
             mouse_buttons: MouseButtons::NONE,
             hscroll_remainder: 0.0,
             vscroll_remainder: 0.0,
@@ -369,6 +388,9 @@ impl WaylandWindow {
             pending_mouse,
             pending_first_configure: Some(pending_first_configure),
             frame_callback: None,
+            frame_serial: 0,
+            visibility_check_pending: false,
+            visible: true,
             title: None,
             gl_state: None,
             wegl_surface: None,
@@ -411,6 +433,11 @@ impl WaylandWindowInner {
         }
     }
 
+    pub(crate) fn clipboard_changed(&mut self, clipboard: Clipboard) {
+        self.events
+            .dispatch(WindowEvent::ClipboardChanged(clipboard));
+    }
+
     pub(crate) fn keyboard_event(&mut self, event: WlKeyboardEvent) {
         let conn = WaylandConnection::get().unwrap().wayland();
         let mut mapper = conn.keyboard_mapper.borrow_mut();
@@ -477,6 +504,46 @@ impl WaylandWindowInner {
         self.events.dispatch(WindowEvent::DroppedFile(paths));
     }
 
+    /// Processes a touch event.  `position` is in surface coordinates
+    /// and is None for the touch up event.
+    pub(crate) fn touch_event(&mut self, id: i32, phase: TouchPhase, position: Option<(f64, f64)>) {
+        let coords = match position {
+            Some((x, y)) => {
+                let coords = Point::new(
+                    self.surface_to_pixels(x as i32) as isize,
+                    self.surface_to_pixels(y as i32) as isize,
+                );
+                self.touch_coords.insert(id, coords);
+                coords
+            }
+            None => match self.touch_coords.get(&id) {
+                Some(&coords) => coords,
+                None => return,
+            },
+        };
+        if matches!(phase, TouchPhase::Ended | TouchPhase::Cancelled) {
+            self.touch_coords.remove(&id);
+        }
+        self.events.dispatch(WindowEvent::TouchEvent(TouchEvent {
+            id: id as u64,
+            phase,
+            coords,
+            screen_coords: ScreenPoint::new(
+                coords.x + self.dimensions.pixel_width as isize,
+                coords.y + self.dimensions.pixel_height as isize,
+            ),
+        }));
+    }
+
+    /// Cancels all of the touches on the window, as happens when
+    /// the compositor takes over the touch sequence
+    pub(crate) fn cancel_touches(&mut self) {
+        let ids: Vec<i32> = self.touch_coords.keys().copied().collect();
+        for id in ids {
+            self.touch_event(id, TouchPhase::Cancelled, None);
+        }
+    }
+
     pub(crate) fn dispatch_pending_mouse(&mut self) {
         // Dancing around the borrow checker and the call to self.refresh_frame()
         let pending_mouse = Arc::clone(&self.pending_mouse);
@@ -639,11 +706,11 @@ impl WaylandWindowInner {
                 let mut pixel_height = self.surface_to_pixels(h.try_into().unwrap());
 
                 if self.window_state.can_resize() {
-                    if let Some((x, y)) = self.resize_increments {
-                        let desired_pixel_width = pixel_width - (pixel_width % x as i32);
-                        let desired_pixel_height = pixel_height - (pixel_height % y as i32);
-                        w = self.pixels_to_surface(desired_pixel_width) as u32;
-                        h = self.pixels_to_surface(desired_pixel_height) as u32;
+                    if let Some(incr) = self.resize_increments {
+                        let (desired_pixel_width, desired_pixel_height) =
+                            incr.snap(pixel_width as usize, pixel_height as usize);
+                        w = self.pixels_to_surface(desired_pixel_width as i32) as u32;
+                        h = self.pixels_to_surface(desired_pixel_height as i32) as u32;
                         pixel_width = self.surface_to_pixels(w.try_into().unwrap());
                         pixel_height = self.surface_to_pixels(h.try_into().unwrap());
                     }
@@ -775,6 +842,10 @@ impl WaylandWindowInner {
 
     fn next_frame_is_ready(&mut self) {
         self.frame_callback.take();
+        if !self.visible {
+            self.visible = true;
+            self.events.dispatch(WindowEvent::VisibilityChanged(true));
+        }
         if self.invalidated {
             self.do_paint().ok();
         }
@@ -804,6 +875,39 @@ impl WaylandWindowInner {
         });
         self.frame_callback.replace(callback);
 
+        // If the compositor hasn't asked for the next frame after a
+        // while, then the window is most likely hidden: minimized,
+        // fully covered or on another workspace
+        self.frame_serial += 1;
+        if self.visible && !self.visibility_check_pending {
+            self.visibility_check_pending = true;
+            let mut frame_serial = self.frame_serial;
+            promise::spawn::spawn(async move {
+                loop {
+                    Timer::after(Duration::from_secs(1)).await;
+                    let serial = WaylandConnection::with_window_inner(window_id, move |inner| {
+                        if inner.frame_callback.is_none() || !inner.visible {
+                            inner.visibility_check_pending = false;
+                            return Ok(None);
+                        }
+                        if inner.frame_serial == frame_serial {
+                            inner.visibility_check_pending = false;
+                            inner.visible = false;
+                            inner.events.dispatch(WindowEvent::VisibilityChanged(false));
+                            return Ok(None);
+                        }
+                        Ok(Some(inner.frame_serial))
+                    })
+                    .await;
+                    match serial {
+                        Ok(Some(serial)) => frame_serial = serial,
+                        _ => break,
+                    }
+                }
+            })
+            .detach();
+        }
+
         Ok(())
     }
 }
@@ -943,9 +1047,21 @@ impl WindowOps for WaylandWindow {
         });
     }
 
-    fn set_resize_increments(&self, x: u16, y: u16) {
+    fn set_resize_increments(&self, incr: ResizeIncrement) {
+        WaylandConnection::with_window_inner(self.0, move |inner| {
+            Ok(inner.set_resize_increments(incr))
+        });
+    }
+
+    fn set_input_region(&self, region: InputRegion) {
+        WaylandConnection::with_window_inner(self.0, move |inner| {
+            Ok(inner.set_input_region(&region))
+        });
+    }
+
+    fn set_corner_radius(&self, radius: Option<f32>) {
         WaylandConnection::with_window_inner(self.0, move |inner| {
-            Ok(inner.set_resize_increments(x, y))
+            Ok(inner.set_corner_radius(radius))
         });
     }
 
@@ -1170,8 +1286,62 @@ impl WaylandWindowInner {
         self.title = Some(title);
     }
 
-    fn set_resize_increments(&mut self, x: u16, y: u16) {
-        self.resize_increments = Some((x, y));
+    fn set_resize_increments(&mut self, incr: ResizeIncrement) {
+        self.resize_increments = Some(incr);
+    }
+
+    fn set_input_region(&mut self, region: &InputRegion) {
+        self.input_region = region.clone();
+        self.apply_input_region();
+    }
+
+    fn set_corner_radius(&mut self, radius: Option<f32>) {
+        if radius.is_none() && self.corner_radius.is_none() {
+            return;
+        }
+        self.corner_radius = radius;
+        if self.input_region == InputRegion::Everywhere {
+            self.apply_input_region();
+        }
+    }
+
+    /// Restricts input to the input region, or to the rounded shape
+    /// of the window if no input region has been set, so that clicks
+    /// on the transparent corners go to whatever is beneath them
+    fn apply_input_region(&self) {
+        let region = match (&self.input_region, self.corner_radius) {
+            (InputRegion::Everywhere, Some(radius)) => InputRegion::rounded(
+                self.dimensions.pixel_width,
+                self.dimensions.pixel_height,
+                radius,
+            ),
+            (region, _) => region.clone(),
+        };
+        let rects = match &region {
+            InputRegion::Everywhere => {
+                // A null region means that the whole surface accepts input
+                self.surface.set_input_region(None);
+                self.surface.commit();
+                return;
+            }
+            InputRegion::Nowhere => &[][..],
+            InputRegion::Rects(rects) => rects.as_slice(),
+        };
+
+        let conn = Connection::get().unwrap().wayland();
+        let compositor = conn.environment.require_global::<WlCompositor>();
+        let wl_region = compositor.create_region();
+        for rect in rects {
+            wl_region.add(
+                self.pixels_to_surface(rect.min_x() as i32),
+                self.pixels_to_surface(rect.min_y() as i32),
+                self.pixels_to_surface(rect.width() as i32),
+                self.pixels_to_surface(rect.height() as i32),
+            );
+        }
+        self.surface.set_input_region(Some(&wl_region));
+        wl_region.destroy();
+        self.surface.commit();
     }
 
     fn config_did_change(&mut self, config: &ConfigHandle) {
@@ -3,6 +3,7 @@ use super::{HWindow, WindowInner};
 use crate::connection::ConnectionOps;
 use crate::screen::{ScreenInfo, Screens};
 use crate::spawn::*;
+use crate::tray::TrayIcon;
 use crate::{Appearance, ScreenRect};
 use anyhow::Context;
 use std::cell::RefCell;
@@ -92,6 +93,10 @@ impl ConnectionOps for Connection {
         }
     }
 
+    fn set_tray_icon(&self, tray: Option<TrayIcon>) -> anyhow::Result<()> {
+        super::tray::set_tray_icon(tray)
+    }
+
     fn screens(&self) -> anyhow::Result<Screens> {
         struct Info {
             primary: Option<ScreenInfo>,
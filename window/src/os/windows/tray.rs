@@ -0,0 +1,222 @@
+//! The notification area icon, managed via Shell_NotifyIcon
+use super::wide_string;
+use crate::tray::{self, TrayEvent, TrayIcon, TrayMenuItem};
+use std::cell::RefCell;
+use std::io::Error as IoError;
+use std::ptr::{null, null_mut};
+use winapi::shared::minwindef::*;
+use winapi::shared::windef::*;
+use winapi::um::libloaderapi::GetModuleHandleW;
+use winapi::um::shellapi::{
+    Shell_NotifyIconW, NIF_ICON, NIF_MESSAGE, NIF_TIP, NIM_ADD, NIM_DELETE, NIM_MODIFY,
+    NOTIFYICONDATAW,
+};
+use winapi::um::winuser::*;
+
+const CLASS_NAME: &str = "wezterm_tray";
+const WM_TRAY_CALLBACK: UINT = WM_APP + 1;
+const ICON_ID: UINT = 1;
+
+struct TrayState {
+    hwnd: HWND,
+    tooltip: String,
+    menu: Vec<TrayMenuItem>,
+}
+
+thread_local! {
+    static STATE: RefCell<Option<TrayState>> = RefCell::new(None);
+}
+
+/// Returns the message that explorer broadcasts when the taskbar
+/// is re-created, at which point our icon needs to be re-added
+fn taskbar_created_message() -> UINT {
+    let name = wide_string("TaskbarCreated");
+    unsafe { RegisterWindowMessageW(name.as_ptr()) }
+}
+
+fn notify_icon_data(hwnd: HWND, tooltip: &str) -> NOTIFYICONDATAW {
+    let mut data: NOTIFYICONDATAW = unsafe { std::mem::zeroed() };
+    data.cbSize = std::mem::size_of::<NOTIFYICONDATAW>() as u32;
+    data.hWnd = hwnd;
+    data.uID = ICON_ID;
+    data.uFlags = NIF_MESSAGE | NIF_ICON | NIF_TIP;
+    data.uCallbackMessage = WM_TRAY_CALLBACK;
+    // The ID is defined in assets/windows/resource.rc
+    data.hIcon = unsafe { LoadIconW(GetModuleHandleW(null()), MAKEINTRESOURCEW(0x101)) };
+    let tip = wide_string(tooltip);
+    // Leave room for the terminating nul
+    let len = tip.len().min(data.szTip.len()) - 1;
+    data.szTip[..len].copy_from_slice(&tip[..len]);
+    data
+}
+
+fn create_message_window() -> anyhow::Result<HWND> {
+    let class_name = wide_string(CLASS_NAME);
+    let h_inst = unsafe { GetModuleHandleW(null()) };
+    let class = WNDCLASSW {
+        style: 0,
+        lpfnWndProc: Some(tray_wnd_proc),
+        cbClsExtra: 0,
+        cbWndExtra: 0,
+        hInstance: h_inst,
+        hIcon: null_mut(),
+        hCursor: null_mut(),
+        hbrBackground: null_mut(),
+        lpszMenuName: null(),
+        lpszClassName: class_name.as_ptr(),
+    };
+
+    if unsafe { RegisterClassW(&class) } == 0 {
+        let err = IoError::last_os_error();
+        match err.raw_os_error() {
+            Some(code) if code == winapi::shared::winerror::ERROR_CLASS_ALREADY_EXISTS as i32 => {}
+            _ => return Err(err.into()),
+        }
+    }
+
+    // This is a top level window rather than a message-only window,
+    // as message-only windows don't receive the TaskbarCreated broadcast
+    let hwnd = unsafe {
+        CreateWindowExW(
+            0,
+            class_name.as_ptr(),
+            class_name.as_ptr(),
+            WS_OVERLAPPED,
+            0,
+            0,
+            0,
+            0,
+            null_mut(),
+            null_mut(),
+            h_inst,
+            null_mut(),
+        )
+    };
+    if hwnd.is_null() {
+        return Err(IoError::last_os_error().into());
+    }
+    Ok(hwnd)
+}
+
+pub fn set_tray_icon(icon: Option<TrayIcon>) -> anyhow::Result<()> {
+    let icon = match icon {
+        Some(icon) => icon,
+        None => {
+            tray::set_callback(None);
+            if let Some(state) = STATE.with(|s| s.borrow_mut().take()) {
+                unsafe {
+                    let mut data = notify_icon_data(state.hwnd, "");
+                    Shell_NotifyIconW(NIM_DELETE, &mut data);
+                    DestroyWindow(state.hwnd);
+                }
+            }
+            return Ok(());
+        }
+    };
+
+    let existing = STATE.with(|s| s.borrow().as_ref().map(|state| state.hwnd));
+    let (hwnd, op) = match existing {
+        Some(hwnd) => (hwnd, NIM_MODIFY),
+        None => (create_message_window()?, NIM_ADD),
+    };
+
+    let mut data = notify_icon_data(hwnd, &icon.tooltip);
+    if unsafe { Shell_NotifyIconW(op, &mut data) } == 0 {
+        if op == NIM_ADD {
+            unsafe {
+                DestroyWindow(hwnd);
+            }
+        }
+        anyhow::bail!("Shell_NotifyIconW failed");
+    }
+
+    tray::set_callback(Some(icon.callback));
+    STATE.with(|s| {
+        s.borrow_mut().replace(TrayState {
+            hwnd,
+            tooltip: icon.tooltip,
+            menu: icon.menu,
+        })
+    });
+    Ok(())
+}
+
+/// Shows the menu at the mouse position and returns the index
+/// of the selected item
+unsafe fn track_menu(hwnd: HWND, items: &[TrayMenuItem]) -> Option<usize> {
+    let menu = CreatePopupMenu();
+    if menu.is_null() {
+        return None;
+    }
+    for (idx, item) in items.iter().enumerate() {
+        match item {
+            TrayMenuItem::Item { label, enabled } => {
+                let label = wide_string(label);
+                let flags = if *enabled {
+                    MF_STRING
+                } else {
+                    MF_STRING | MF_GRAYED
+                };
+                AppendMenuW(menu, flags, idx + 1, label.as_ptr());
+            }
+            TrayMenuItem::Separator => {
+                AppendMenuW(menu, MF_SEPARATOR, 0, null());
+            }
+        }
+    }
+
+    let mut point = POINT { x: 0, y: 0 };
+    GetCursorPos(&mut point);
+    // Without this, the menu won't go away when clicking elsewhere.
+    // See the remarks in the TrackPopupMenu documentation.
+    SetForegroundWindow(hwnd);
+    let cmd = TrackPopupMenu(
+        menu,
+        TPM_RETURNCMD | TPM_RIGHTBUTTON | TPM_NONOTIFY,
+        point.x,
+        point.y,
+        0,
+        hwnd,
+        null(),
+    );
+    PostMessageW(hwnd, WM_NULL, 0, 0);
+    DestroyMenu(menu);
+
+    if cmd > 0 {
+        Some(cmd as usize - 1)
+    } else {
+        None
+    }
+}
+
+unsafe extern "system" fn tray_wnd_proc(
+    hwnd: HWND,
+    msg: UINT,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if msg == WM_TRAY_CALLBACK {
+        match LOWORD(lparam as u32) as UINT {
+            WM_LBUTTONUP => tray::dispatch(TrayEvent::Activate),
+            WM_RBUTTONUP | WM_CONTEXTMENU => {
+                let items = STATE.with(|s| s.borrow().as_ref().map(|state| state.menu.clone()));
+                if let Some(idx) = items.and_then(|items| track_menu(hwnd, &items)) {
+                    tray::dispatch(TrayEvent::MenuItem(idx));
+                }
+            }
+            _ => {}
+        }
+        return 0;
+    }
+
+    if msg == taskbar_created_message() {
+        let tooltip = STATE.with(|s| s.borrow().as_ref().map(|state| state.tooltip.clone()));
+        if let Some(tooltip) = tooltip {
+            let mut data = notify_icon_data(hwnd, &tooltip);
+            Shell_NotifyIconW(NIM_ADD, &mut data);
+        }
+        return 0;
+    }
+
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
@@ -322,6 +322,17 @@ impl GlState {
             wgl.wgl.MakeCurrent(hdc as *mut _, rc);
         }
 
+        // By default, leave the swap interval to the driver,
+        // which normally waits for the vertical blank
+        if let Some(interval) = crate::configuration::swap_interval() {
+            if has_extension(&extensions, "WGL_EXT_swap_control") {
+                log::trace!("setting swap interval to {}", interval);
+                unsafe {
+                    wgl.ext.as_ref().unwrap().SwapIntervalEXT(interval);
+                }
+            }
+        }
+
         Ok(Self {
             wgl: Some(wgl),
             rc,
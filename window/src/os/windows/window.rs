@@ -2,10 +2,11 @@ use super::*;
 use crate::connection::ConnectionOps;
 use crate::parameters::{self, Parameters};
 use crate::{
-    Appearance, Clipboard, DeadKeyStatus, Dimensions, Handled, KeyCode, KeyEvent, Modifiers,
-    MouseButtons, MouseCursor, MouseEvent, MouseEventKind, MousePress, Point, RawKeyEvent, Rect,
-    RequestedWindowGeometry, ResolvedGeometry, ScreenPoint, ULength, WindowDecorations,
-    WindowEvent, WindowEventSender, WindowOps, WindowState,
+    Appearance, Clipboard, DeadKeyStatus, Dimensions, Handled, InputRegion, KeyCode, KeyEvent,
+    Modifiers, MouseButtons, MouseCursor, MouseEvent, MouseEventKind, MousePress, Point,
+    RawKeyEvent, Rect, RequestedWindowGeometry, ResizeIncrement, ResolvedGeometry, ScreenPoint,
+    TouchEvent, TouchPhase, ULength, WindowDecorations, WindowEvent, WindowEventSender, WindowOps,
+    WindowState,
 };
 use anyhow::{bail, Context};
 use async_trait::async_trait;
@@ -98,10 +99,16 @@ pub(crate) struct WindowInner {
 
     last_size: Option<Dimensions>,
     in_size_move: bool,
+    /// Constrains interactive resizes of the window; applied by WM_SIZING
+    resize_increments: Option<ResizeIncrement>,
     dead_pending: Option<(Modifiers, u32)>,
     saved_placement: Option<WINDOWPLACEMENT>,
     track_mouse_leave: bool,
     window_drag_position: Option<ScreenPoint>,
+    /// The radius of the rounded corners, in pixels
+    corner_radius: Option<f32>,
+    /// Whether the window is shown, not minimized and not cloaked
+    visible: bool,
 
     keyboard_info: KeyboardLayoutInfo,
     appearance: Appearance,
@@ -227,6 +234,31 @@ impl WindowInner {
         Ok(gl_state)
     }
 
+    /// Dispatches VisibilityChanged if the window has been minimized,
+    /// hidden or cloaked by the DWM (which happens when it is on another
+    /// virtual desktop), or has become visible again
+    fn check_visibility(&mut self) {
+        use winapi::um::dwmapi::DwmGetWindowAttribute;
+        const DWMWA_CLOAKED: DWORD = 14;
+
+        let hwnd = self.hwnd.0;
+        let mut cloaked: DWORD = 0;
+        let visible = unsafe {
+            let res = DwmGetWindowAttribute(
+                hwnd,
+                DWMWA_CLOAKED,
+                &mut cloaked as *mut DWORD as *mut _,
+                std::mem::size_of::<DWORD>() as u32,
+            );
+            IsWindowVisible(hwnd) != 0 && IsIconic(hwnd) == 0 && (res != S_OK || cloaked == 0)
+        };
+        if visible != self.visible {
+            self.visible = visible;
+            self.events
+                .dispatch(WindowEvent::VisibilityChanged(visible));
+        }
+    }
+
     /// Check if we need to generate a resize callback.
     /// Calls resize if needed.
     /// Returns true if we did.
@@ -457,10 +489,13 @@ impl Window {
             keyboard_info: KeyboardLayoutInfo::new(),
             last_size: None,
             in_size_move: false,
+            resize_increments: None,
             dead_pending: None,
             saved_placement: None,
             track_mouse_leave: false,
             window_drag_position: None,
+            corner_radius: None,
+            visible: true,
             config: config.clone(),
         }));
 
@@ -493,6 +528,11 @@ impl Window {
             DragAcceptFiles(hwnd.0, winapi::shared::minwindef::TRUE);
         }
 
+        // Ask to be told when the clipboard changes
+        unsafe {
+            AddClipboardFormatListener(hwnd.0);
+        }
+
         conn.windows
             .borrow_mut()
             .insert(hwnd.clone(), Rc::clone(&inner));
@@ -543,6 +583,60 @@ impl WindowInner {
         apply_mouse_cursor(cursor);
     }
 
+    fn set_corner_radius(&mut self, radius: Option<f32>) {
+        // Windows 11 offers a choice of corner styles rather than an
+        // arbitrary radius, so pick the closest.  Earlier versions
+        // don't know the attribute and ignore it.
+        use winapi::um::dwmapi::DwmSetWindowAttribute;
+        const DWMWA_WINDOW_CORNER_PREFERENCE: DWORD = 33;
+        const DWMWCP_DEFAULT: DWORD = 0;
+        const DWMWCP_DONOTROUND: DWORD = 1;
+        const DWMWCP_ROUND: DWORD = 2;
+        const DWMWCP_ROUNDSMALL: DWORD = 3;
+
+        self.corner_radius = radius;
+        let hwnd = self.hwnd.0;
+        unsafe {
+            // The small style has a radius of 4 pixels at 96 dpi,
+            // and the regular style 8 pixels
+            let scale = GetDpiForWindow(hwnd) as f32 / crate::DEFAULT_DPI as f32;
+            let preference = match radius {
+                None => DWMWCP_DEFAULT,
+                Some(radius) if radius < 1. => DWMWCP_DONOTROUND,
+                Some(radius) if radius <= 6. * scale => DWMWCP_ROUNDSMALL,
+                Some(_) => DWMWCP_ROUND,
+            };
+            DwmSetWindowAttribute(
+                hwnd as _,
+                DWMWA_WINDOW_CORNER_PREFERENCE,
+                &preference as *const _ as *const _,
+                std::mem::size_of_val(&preference) as u32,
+            );
+        }
+    }
+
+    fn set_input_region(&mut self, region: &InputRegion) {
+        // Windows can only make the whole window click-through,
+        // which requires it to be a layered window
+        let click_through = *region == InputRegion::Nowhere;
+        let hwnd = self.hwnd.0;
+        let flags = WS_EX_LAYERED | WS_EX_TRANSPARENT;
+        unsafe {
+            let style = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
+            let new_style = if click_through {
+                style | flags
+            } else {
+                style & !flags
+            };
+            if new_style != style {
+                SetWindowLongW(hwnd, GWL_EXSTYLE, new_style as _);
+                if click_through {
+                    SetLayeredWindowAttributes(hwnd, 0, 255, LWA_ALPHA);
+                }
+            }
+        }
+    }
+
     fn set_window_position(&self, coords: ScreenPoint) {
         let hwnd = self.hwnd.0;
         log::trace!("set_window_position wants {coords:?}");
@@ -754,6 +848,13 @@ impl WindowOps for Window {
         });
     }
 
+    fn set_resize_increments(&self, incr: ResizeIncrement) {
+        Connection::with_window_inner(self.0, move |inner| {
+            inner.resize_increments = Some(incr);
+            Ok(())
+        });
+    }
+
     fn set_inner_size(&self, width: usize, height: usize) {
         Connection::with_window_inner(self.0, move |inner| {
             let hwnd = inner.hwnd;
@@ -792,6 +893,26 @@ impl WindowOps for Window {
         });
     }
 
+    fn set_input_region(&self, region: InputRegion) {
+        Connection::with_window_inner(self.0, move |inner| {
+            inner.set_input_region(&region);
+            Ok(())
+        });
+    }
+
+    fn set_corner_radius(&self, radius: Option<f32>) {
+        Connection::with_window_inner(self.0, move |inner| {
+            inner.set_corner_radius(radius);
+            Ok(())
+        });
+    }
+
+    fn window_system_rounds_corners(&self) -> bool {
+        // The DWM rounds the corners, and the WGL surface
+        // may not be composited with its alpha channel
+        true
+    }
+
     fn set_window_position(&self, coords: ScreenPoint) {
         Connection::with_window_inner(self.0, move |inner| {
             inner.set_window_position(coords);
@@ -799,6 +920,21 @@ impl WindowOps for Window {
         });
     }
 
+    fn get_window_position(&self) -> Future<Option<ScreenPoint>> {
+        Connection::with_window_inner(self.0, |inner| {
+            Ok(Some(client_to_screen(inner.hwnd.0, Point::new(0, 0))))
+        })
+    }
+
+    fn focus(&self) {
+        Connection::with_window_inner(self.0, |inner| {
+            unsafe {
+                SetForegroundWindow(inner.hwnd.0);
+            }
+            Ok(())
+        });
+    }
+
     fn get_clipboard(&self, _clipboard: Clipboard) -> Future<String> {
         Future::result(
             clipboard_win::get_clipboard_string()
@@ -952,6 +1088,7 @@ unsafe fn wm_ncdestroy(
     _wparam: WPARAM,
     _lparam: LPARAM,
 ) -> Option<LRESULT> {
+    RemoveClipboardFormatListener(hwnd);
     let raw = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as LPVOID;
     if !raw.is_null() {
         let inner = take_rc_from_pointer(raw);
@@ -1052,6 +1189,16 @@ unsafe fn wm_nchittest(hwnd: HWND, msg: UINT, wparam: WPARAM, lparam: LPARAM) ->
     let client_rect_is_valid =
         GetClientRect(hwnd, &mut client_rect) == winapi::shared::minwindef::TRUE;
 
+    // The transparent area outside of a rounded corner resizes
+    // the window diagonally, as the corner of a frame would
+    if let Some(radius) = inner.corner_radius.filter(|r| *r >= 1.) {
+        if !is_maximized && client_rect_is_valid {
+            if let Some(hit) = rounded_corner_hit(&client_rect, cursor_point, radius) {
+                return Some(hit);
+            }
+        }
+    }
+
     // Since we are eating the bottom window frame to deal with a Windows 10 bug,
     // we detect resizing in the window client area as a workaround
     if !is_maximized
@@ -1087,6 +1234,37 @@ unsafe fn wm_nchittest(hwnd: HWND, msg: UINT, wparam: WPARAM, lparam: LPARAM) ->
     Some(HTCLIENT)
 }
 
+/// Returns the resize area for a point that is outside of the
+/// rounded shape of a window whose corners have the specified radius
+fn rounded_corner_hit(client_rect: &RECT, point: Point, radius: f32) -> Option<LRESULT> {
+    let width = rect_width(client_rect) as f32;
+    let height = rect_height(client_rect) as f32;
+    let (x, y) = (point.x as f32 + 0.5, point.y as f32 + 0.5);
+    let (center_x, left) = if x < radius {
+        (radius, true)
+    } else if x > width - radius {
+        (width - radius, false)
+    } else {
+        return None;
+    };
+    let (center_y, top) = if y < radius {
+        (radius, true)
+    } else if y > height - radius {
+        (height - radius, false)
+    } else {
+        return None;
+    };
+    if (x - center_x).hypot(y - center_y) <= radius {
+        return None;
+    }
+    Some(match (left, top) {
+        (true, true) => HTTOPLEFT,
+        (false, true) => HTTOPRIGHT,
+        (true, false) => HTBOTTOMLEFT,
+        (false, false) => HTBOTTOMRIGHT,
+    })
+}
+
 fn get_window_state(hwnd: HWND) -> WindowState {
     let mut placement = WINDOWPLACEMENT {
         length: std::mem::size_of::<WINDOWPLACEMENT>() as _,
@@ -1222,6 +1400,35 @@ fn apply_theme(hwnd: HWND) -> Option<LRESULT> {
     None
 }
 
+unsafe fn clipboard_update(
+    hwnd: HWND,
+    _msg: UINT,
+    _wparam: WPARAM,
+    _lparam: LPARAM,
+) -> Option<LRESULT> {
+    // Each of our windows is registered as a clipboard listener, but the
+    // notification should only be delivered once; prefer the foreground
+    // window and otherwise pick one of the others.
+    let target = {
+        let conn = Connection::get()?;
+        let windows = conn.windows.borrow();
+        let foreground = GetForegroundWindow();
+        if windows.keys().any(|w| w.0 == foreground) {
+            foreground
+        } else {
+            windows.keys().map(|w| w.0).min()?
+        }
+    };
+    if target == hwnd {
+        let inner = rc_from_hwnd(hwnd)?;
+        let mut inner = inner.borrow_mut();
+        inner
+            .events
+            .dispatch(WindowEvent::ClipboardChanged(Clipboard::Clipboard));
+    }
+    Some(0)
+}
+
 unsafe fn wm_enter_exit_size_move(
     hwnd: HWND,
     msg: UINT,
@@ -1242,6 +1449,45 @@ unsafe fn wm_enter_exit_size_move(
     Some(0)
 }
 
+/// Adjusts the rectangle that the user is dragging the window to, so
+/// that the client area is a whole number of resize increments.
+/// The edges that are being dragged are moved, and the others are
+/// left in place.
+unsafe fn wm_sizing(hwnd: HWND, _msg: UINT, wparam: WPARAM, lparam: LPARAM) -> Option<LRESULT> {
+    let incr = rc_from_hwnd(hwnd)?.borrow().resize_increments?;
+
+    let mut window_rect = RECT {
+        left: 0,
+        top: 0,
+        right: 0,
+        bottom: 0,
+    };
+    let mut client_rect = window_rect;
+    if GetWindowRect(hwnd, &mut window_rect) == 0 || GetClientRect(hwnd, &mut client_rect) == 0 {
+        return None;
+    }
+    let decoration_width = rect_width(&window_rect) - rect_width(&client_rect);
+    let decoration_height = rect_height(&window_rect) - rect_height(&client_rect);
+
+    let rect = &mut *(lparam as *mut RECT);
+    let (width, height) = incr.snap(
+        (rect_width(rect) - decoration_width).max(0) as usize,
+        (rect_height(rect) - decoration_height).max(0) as usize,
+    );
+    let width = width as i32 + decoration_width;
+    let height = height as i32 + decoration_height;
+
+    match wparam as u32 {
+        WMSZ_LEFT | WMSZ_TOPLEFT | WMSZ_BOTTOMLEFT => rect.left = rect.right - width,
+        _ => rect.right = rect.left + width,
+    }
+    match wparam as u32 {
+        WMSZ_TOP | WMSZ_TOPLEFT | WMSZ_TOPRIGHT => rect.top = rect.bottom - height,
+        _ => rect.bottom = rect.top + height,
+    }
+    Some(1)
+}
+
 /// We handle WM_WINDOWPOSCHANGED and dispatch directly to our wm_size as it
 /// is a bit more efficient than letting DefWindowProcW parse this and
 /// trigger WM_SIZE.
@@ -1262,6 +1508,7 @@ unsafe fn wm_size(hwnd: HWND, _msg: UINT, _wparam: WPARAM, _lparam: LPARAM) -> O
 
     if let Some(inner) = rc_from_hwnd(hwnd) {
         let mut inner = inner.borrow_mut();
+        inner.check_visibility();
         should_paint = inner.check_and_call_resize_if_needed();
         should_pump = inner.in_size_move;
     }
@@ -1282,10 +1529,10 @@ unsafe fn wm_set_focus(
     _wparam: WPARAM,
     _lparam: LPARAM,
 ) -> Option<LRESULT> {
-    rc_from_hwnd(hwnd)?
-        .borrow_mut()
-        .events
-        .dispatch(WindowEvent::FocusChanged(true));
+    let inner = rc_from_hwnd(hwnd)?;
+    let mut inner = inner.borrow_mut();
+    inner.check_visibility();
+    inner.events.dispatch(WindowEvent::FocusChanged(true));
     None
 }
 
@@ -1295,10 +1542,12 @@ unsafe fn wm_kill_focus(
     _wparam: WPARAM,
     _lparam: LPARAM,
 ) -> Option<LRESULT> {
-    rc_from_hwnd(hwnd)?
-        .borrow_mut()
-        .events
-        .dispatch(WindowEvent::FocusChanged(false));
+    // Switching to another virtual desktop cloaks the window
+    // and takes the focus away from it
+    let inner = rc_from_hwnd(hwnd)?;
+    let mut inner = inner.borrow_mut();
+    inner.check_visibility();
+    inner.events.dispatch(WindowEvent::FocusChanged(false));
     None
 }
 
@@ -1460,6 +1709,51 @@ unsafe fn mouse_move(hwnd: HWND, _msg: UINT, wparam: WPARAM, lparam: LPARAM) ->
     Some(0)
 }
 
+/// Touch input is reported by the pointer messages.  Returning None
+/// for the other kinds of pointer, or for touches when the touch
+/// gestures are disabled, allows DefWindowProc to translate them
+/// into the mouse messages.
+unsafe fn pointer_event(hwnd: HWND, msg: UINT, wparam: WPARAM, _lparam: LPARAM) -> Option<LRESULT> {
+    let inner = rc_from_hwnd(hwnd)?;
+    if !inner.borrow().config.enable_touch_gestures {
+        return None;
+    }
+    // GET_POINTERID_WPARAM
+    let pointer_id = (wparam & 0xffff) as u32;
+    let mut pointer_type = 0;
+    if GetPointerType(pointer_id, &mut pointer_type) == 0 || pointer_type != PT_TOUCH {
+        return None;
+    }
+    let mut info: POINTER_INFO = std::mem::zeroed();
+    if GetPointerInfo(pointer_id, &mut info) == 0 {
+        return None;
+    }
+    let phase = if info.pointerFlags & POINTER_FLAG_CANCELED != 0 {
+        TouchPhase::Cancelled
+    } else {
+        match msg {
+            WM_POINTERDOWN => TouchPhase::Began,
+            WM_POINTERUP => TouchPhase::Ended,
+            _ => TouchPhase::Moved,
+        }
+    };
+    let screen_coords = ScreenPoint::new(
+        info.ptPixelLocation.x as isize,
+        info.ptPixelLocation.y as isize,
+    );
+    let event = TouchEvent {
+        id: pointer_id as u64,
+        phase,
+        coords: screen_to_client(hwnd, screen_coords),
+        screen_coords,
+    };
+    inner
+        .borrow_mut()
+        .events
+        .dispatch(WindowEvent::TouchEvent(event));
+    Some(0)
+}
+
 unsafe fn mouse_leave(hwnd: HWND, _msg: UINT, _wparam: WPARAM, _lparam: LPARAM) -> Option<LRESULT> {
     let inner = rc_from_hwnd(hwnd)?;
     let mut inner = inner.borrow_mut();
@@ -1518,7 +1812,8 @@ unsafe fn mouse_wheel(hwnd: HWND, msg: UINT, wparam: WPARAM, lparam: LPARAM) ->
             if position == 0 {
                 return Some(0);
             }
-            MouseEventKind::HorzWheel(position)
+            // WM_MOUSEHWHEEL reports positive values for scrolling right
+            MouseEventKind::HorzWheel(-position)
         } else {
             let mut inner = inner.borrow_mut();
             if inner.vscroll_remainder.signum() != remainder.signum() {
@@ -2526,13 +2821,17 @@ unsafe fn do_wnd_proc(hwnd: HWND, msg: UINT, wparam: WPARAM, lparam: LPARAM) ->
         WM_SIZING => {
             // Allow events to be processed during live resize
             crate::spawn::SPAWN_QUEUE.run();
-            None
+            wm_sizing(hwnd, msg, wparam, lparam)
         }
         WM_SETTINGCHANGE => apply_theme(hwnd),
+        WM_CLIPBOARDUPDATE => clipboard_update(hwnd, msg, wparam, lparam),
         WM_IME_SETCONTEXT => ime_set_context(hwnd, msg, wparam, lparam),
         WM_IME_COMPOSITION => ime_composition(hwnd, msg, wparam, lparam),
         WM_IME_ENDCOMPOSITION => ime_end_composition(hwnd, msg, wparam, lparam),
         WM_MOUSEMOVE => mouse_move(hwnd, msg, wparam, lparam),
+        WM_POINTERDOWN | WM_POINTERUPDATE | WM_POINTERUP => {
+            pointer_event(hwnd, msg, wparam, lparam)
+        }
         WM_MOUSELEAVE => mouse_leave(hwnd, msg, wparam, lparam),
         WM_MOUSEHWHEEL | WM_MOUSEWHEEL => mouse_wheel(hwnd, msg, wparam, lparam),
         WM_LBUTTONDBLCLK | WM_RBUTTONDBLCLK | WM_MBUTTONDBLCLK | WM_LBUTTONDOWN | WM_LBUTTONUP
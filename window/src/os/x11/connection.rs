@@ -5,7 +5,7 @@ use crate::os::x11::xsettings::*;
 use crate::os::Connection;
 use crate::screen::{ScreenInfo, Screens};
 use crate::spawn::*;
-use crate::{Appearance, DeadKeyStatus, ScreenRect};
+use crate::{Appearance, Clipboard, DeadKeyStatus, ScreenRect};
 use anyhow::{anyhow, bail, Context as _};
 use mio::event::Source;
 use mio::unix::SourceFd;
@@ -48,6 +48,8 @@ pub struct XConnection {
     pub atom_net_wm_name: Atom,
     pub atom_net_wm_icon: Atom,
     pub atom_net_move_resize_window: Atom,
+    pub atom_net_active_window: Atom,
+    pub atom_wm_change_state: Atom,
     pub(crate) xrm: RefCell<HashMap<String, String>>,
     pub(crate) windows: RefCell<HashMap<xcb::x::Window, Arc<Mutex<XWindowInner>>>>,
     should_terminate: RefCell<bool>,
@@ -57,6 +59,9 @@ pub struct XConnection {
     pub(crate) ime: RefCell<std::pin::Pin<Box<xcb_imdkit::ImeClient>>>,
     pub(crate) ime_process_event_result: RefCell<anyhow::Result<()>>,
     pub(crate) has_randr: bool,
+    /// Whether the server supports XInput 2.2, which
+    /// reports touch events
+    pub(crate) has_xinput_touch: bool,
     pub(crate) atom_names: RefCell<HashMap<Atom, String>>,
 }
 
@@ -111,6 +116,9 @@ fn window_id_from_event(event: &xcb::Event) -> Option<xcb::x::Window> {
         xcb::Event::X(xcb::x::Event::FocusIn(e)) => Some(e.event()),
         xcb::Event::X(xcb::x::Event::FocusOut(e)) => Some(e.event()),
         xcb::Event::X(xcb::x::Event::LeaveNotify(e)) => Some(e.event()),
+        xcb::Event::Input(xcb::xinput::Event::TouchBegin(e)) => Some(e.event()),
+        xcb::Event::Input(xcb::xinput::Event::TouchUpdate(e)) => Some(e.event()),
+        xcb::Event::Input(xcb::xinput::Event::TouchEnd(e)) => Some(e.event()),
         _ => None,
     }
 }
@@ -325,6 +333,44 @@ impl XConnection {
         }
     }
 
+    /// Delivers a clipboard change notification to the focused window,
+    /// or to an arbitrary window if none of ours have the focus
+    fn advise_of_clipboard_change(&self, clipboard: Clipboard) {
+        let windows = self.windows.borrow();
+        let target = windows
+            .values()
+            .find(|win| win.lock().unwrap().has_focus())
+            .or_else(|| windows.values().next());
+        if let Some(win) = target {
+            win.lock().unwrap().clipboard_changed(clipboard);
+        }
+    }
+
+    /// Ask the X server to notify us when the owner of the
+    /// clipboard or primary selection changes
+    fn select_selection_input(&self) -> anyhow::Result<()> {
+        if !self
+            .conn
+            .active_extensions()
+            .any(|e| e == xcb::Extension::XFixes)
+        {
+            log::debug!("XFixes is not available; clipboard changes will not be reported");
+            return Ok(());
+        }
+        self.send_and_wait_request(&xcb::xfixes::QueryVersion {
+            client_major_version: 5,
+            client_minor_version: 0,
+        })?;
+        for selection in [xcb::x::ATOM_PRIMARY, self.atom_clipboard] {
+            self.send_request_no_reply(&xcb::xfixes::SelectSelectionInput {
+                window: self.root,
+                selection,
+                event_mask: xcb::xfixes::SelectionEventMask::SET_SELECTION_OWNER,
+            })?;
+        }
+        Ok(())
+    }
+
     fn process_queued_xcb(&self) -> anyhow::Result<()> {
         if let Some(event) = self
             .conn
@@ -400,7 +446,14 @@ impl XConnection {
             _ => {}
         }
 
-        if let Some(window_id) = window_id_from_event(event) {
+        if let xcb::Event::XFixes(xcb::xfixes::Event::SelectionNotify(e)) = event {
+            let selection = e.selection();
+            if selection == xcb::x::ATOM_PRIMARY {
+                self.advise_of_clipboard_change(Clipboard::PrimarySelection);
+            } else if selection == self.atom_clipboard {
+                self.advise_of_clipboard_change(Clipboard::Clipboard);
+            }
+        } else if let Some(window_id) = window_id_from_event(event) {
             self.process_window_event(window_id, event)?;
         } else if matches!(event, xcb::Event::Xkb(_)) {
             // key press/release are not processed here.
@@ -456,6 +509,9 @@ impl XConnection {
                 xcb::Extension::Present,
                 xcb::Extension::RandR,
                 xcb::Extension::Render,
+                xcb::Extension::Shape,
+                xcb::Extension::XFixes,
+                xcb::Extension::Input,
             ],
         )?;
         conn.set_event_queue_owner(xcb::EventQueueOwner::Xcb);
@@ -481,8 +537,18 @@ impl XConnection {
         let atom_net_wm_name = Self::intern_atom(&conn, "_NET_WM_NAME")?;
         let atom_net_wm_icon = Self::intern_atom(&conn, "_NET_WM_ICON")?;
         let atom_net_move_resize_window = Self::intern_atom(&conn, "_NET_MOVERESIZE_WINDOW")?;
+        let atom_net_active_window = Self::intern_atom(&conn, "_NET_ACTIVE_WINDOW")?;
+        let atom_wm_change_state = Self::intern_atom(&conn, "WM_CHANGE_STATE")?;
 
         let has_randr = conn.active_extensions().any(|e| e == xcb::Extension::RandR);
+        let has_xinput_touch = conn.active_extensions().any(|e| e == xcb::Extension::Input)
+            && conn
+                .wait_for_reply(conn.send_request(&xcb::xinput::XiQueryVersion {
+                    major_version: 2,
+                    minor_version: 2,
+                }))
+                .map(|reply| (reply.major_version(), reply.minor_version()) >= (2, 2))
+                .unwrap_or(false);
 
         let screen = conn
             .get_setup()
@@ -586,6 +652,8 @@ impl XConnection {
             atom_net_wm_pid,
             atom_net_wm_name,
             atom_net_move_resize_window,
+            atom_net_active_window,
+            atom_wm_change_state,
             atom_net_wm_icon,
             keyboard,
             kbd_ev,
@@ -600,6 +668,7 @@ impl XConnection {
             ime: RefCell::new(ime),
             ime_process_event_result: RefCell::new(Ok(())),
             has_randr,
+            has_xinput_touch,
             atom_names: RefCell::new(HashMap::new()),
         });
 
@@ -656,6 +725,10 @@ impl XConnection {
                 });
         }
 
+        if let Err(err) = conn.select_selection_input() {
+            log::error!("Unable to monitor clipboard changes: {:#}", err);
+        }
+
         Ok(conn)
     }
 
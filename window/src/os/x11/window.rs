@@ -3,9 +3,10 @@ use crate::bitmaps::*;
 use crate::connection::ConnectionOps;
 use crate::os::{xkeysyms, Connection, Window};
 use crate::{
-    Appearance, Clipboard, DeadKeyStatus, Dimensions, MouseButtons, MouseCursor, MouseEvent,
-    MouseEventKind, MousePress, Point, Rect, RequestedWindowGeometry, ResolvedGeometry,
-    ScreenPoint, WindowDecorations, WindowEvent, WindowEventSender, WindowOps, WindowState,
+    Appearance, Clipboard, DeadKeyStatus, Dimensions, InputRegion, MouseButtons, MouseCursor,
+    MouseEvent, MouseEventKind, MousePress, Point, Rect, RequestedWindowGeometry, ResizeIncrement,
+    ResolvedGeometry, ScreenPoint, TouchEvent, TouchPhase, WindowDecorations, WindowEvent,
+    WindowEventSender, WindowOps, WindowState,
 };
 use anyhow::{anyhow, Context as _};
 use async_trait::async_trait;
@@ -69,11 +70,17 @@ pub(crate) struct XWindowInner {
     title: String,
     has_focus: Option<bool>,
     verify_focus: bool,
+    /// Whether the window is mapped
+    mapped: bool,
+    /// Whether the window is fully covered by other windows
+    obscured: bool,
     last_cursor_position: Rect,
     invalidated: bool,
     paint_throttled: bool,
     pending: Vec<WindowEvent>,
     sure_about_geometry: bool,
+    input_region: InputRegion,
+    corner_radius: Option<f32>,
 }
 
 impl Drop for XWindowInner {
@@ -160,6 +167,25 @@ impl XWindowInner {
         Ok(())
     }
 
+    /// The coordinates of XInput events are 16.16 fixed point values
+    fn touch_event(
+        &mut self,
+        phase: TouchPhase,
+        id: u32,
+        event_x: i32,
+        event_y: i32,
+        root_x: i32,
+        root_y: i32,
+    ) {
+        let fixed = |value: i32| (value >> 16) as isize;
+        self.events.dispatch(WindowEvent::TouchEvent(TouchEvent {
+            id: id as u64,
+            phase,
+            coords: Point::new(fixed(event_x), fixed(event_y)),
+            screen_coords: ScreenPoint::new(fixed(root_x), fixed(root_y)),
+        }));
+    }
+
     fn set_cursor(&mut self, cursor: Option<MouseCursor>) -> anyhow::Result<()> {
         self.cursors.set_cursor(self.window_id, cursor)
     }
@@ -292,6 +318,10 @@ impl XWindowInner {
 
                 self.events.dispatch(WindowEvent::NeedRepaint);
 
+                if self.config.present_mode == config::PresentMode::Immediate {
+                    return Ok(());
+                }
+
                 self.paint_throttled = true;
                 let window_id = self.window_id;
                 let max_fps = self.config.max_fps;
@@ -356,6 +386,13 @@ impl XWindowInner {
                     -LINES_PER_TICK
                 })
             }
+            b @ 6..=7 => {
+                if !pressed {
+                    return Ok(());
+                }
+                // Horizontal scrolling and wheel tilt
+                MouseEventKind::HorzWheel(if b == 6 { 1 } else { -1 })
+            }
             _ => {
                 eprintln!("button {} is not implemented", detail);
                 return Ok(());
@@ -463,6 +500,36 @@ impl XWindowInner {
                 };
                 self.do_mouse_event(event)?;
             }
+            Event::Input(xcb::xinput::Event::TouchBegin(e)) => {
+                self.touch_event(
+                    TouchPhase::Began,
+                    e.detail(),
+                    e.event_x(),
+                    e.event_y(),
+                    e.root_x(),
+                    e.root_y(),
+                );
+            }
+            Event::Input(xcb::xinput::Event::TouchUpdate(e)) => {
+                self.touch_event(
+                    TouchPhase::Moved,
+                    e.detail(),
+                    e.event_x(),
+                    e.event_y(),
+                    e.root_x(),
+                    e.root_y(),
+                );
+            }
+            Event::Input(xcb::xinput::Event::TouchEnd(e)) => {
+                self.touch_event(
+                    TouchPhase::Ended,
+                    e.detail(),
+                    e.event_x(),
+                    e.event_y(),
+                    e.root_x(),
+                    e.root_y(),
+                );
+            }
             Event::X(xcb::x::Event::ButtonPress(e)) => {
                 self.button_event(
                     true,
@@ -543,6 +610,18 @@ impl XWindowInner {
             Event::X(xcb::x::Event::LeaveNotify(_)) => {
                 self.events.dispatch(WindowEvent::MouseLeave);
             }
+            Event::X(xcb::x::Event::MapNotify(_)) => {
+                self.visibility_changed(true, self.obscured);
+            }
+            Event::X(xcb::x::Event::UnmapNotify(_)) => {
+                self.visibility_changed(false, self.obscured);
+            }
+            Event::X(xcb::x::Event::VisibilityNotify(e)) => {
+                self.visibility_changed(
+                    self.mapped,
+                    e.state() == xcb::x::Visibility::FullyObscured,
+                );
+            }
             _ => {
                 eprintln!("unhandled: {:?}", event);
             }
@@ -559,6 +638,15 @@ impl XWindowInner {
         }
     }
 
+    pub(crate) fn clipboard_changed(&mut self, clipboard: Clipboard) {
+        self.events
+            .dispatch(WindowEvent::ClipboardChanged(clipboard));
+    }
+
+    pub(crate) fn has_focus(&self) -> bool {
+        self.has_focus == Some(true)
+    }
+
     fn focus_changed(&mut self, focused: bool) {
         log::trace!("focus_changed {focused}, flagging geometry as unsure");
         self.sure_about_geometry = false;
@@ -570,6 +658,20 @@ impl XWindowInner {
         }
     }
 
+    /// Records whether the window is mapped and whether it is fully
+    /// obscured, dispatching VisibilityChanged if that changes whether
+    /// any of it can be seen.  Minimizing the window unmaps it.
+    fn visibility_changed(&mut self, mapped: bool, obscured: bool) {
+        let was_visible = self.mapped && !self.obscured;
+        self.mapped = mapped;
+        self.obscured = obscured;
+        let visible = mapped && !obscured;
+        if visible != was_visible {
+            self.events
+                .dispatch(WindowEvent::VisibilityChanged(visible));
+        }
+    }
+
     pub fn dispatch_ime_compose_status(&mut self, status: DeadKeyStatus) {
         self.events
             .dispatch(WindowEvent::AdviseDeadKeyStatus(status));
@@ -1035,7 +1137,8 @@ impl XWindow {
                             | xcb::x::EventMask::BUTTON_MOTION
                             | xcb::x::EventMask::KEY_RELEASE
                             | xcb::x::EventMask::PROPERTY_CHANGE
-                            | xcb::x::EventMask::STRUCTURE_NOTIFY,
+                            | xcb::x::EventMask::STRUCTURE_NOTIFY
+                            | xcb::x::EventMask::VISIBILITY_CHANGE,
                     ),
                     xcb::x::Cw::Colormap(color_map_id),
                 ],
@@ -1060,12 +1163,16 @@ impl XWindow {
                 config: config.clone(),
                 has_focus: None,
                 verify_focus: true,
+                mapped: false,
+                obscured: false,
                 last_cursor_position: Rect::default(),
                 paint_throttled: false,
                 last_wm_state: WindowState::default(),
                 invalidated: false,
                 pending: vec![],
                 sure_about_geometry: false,
+                input_region: InputRegion::Everywhere,
+                corner_radius: None,
             }))
         };
 
@@ -1104,6 +1211,7 @@ impl XWindow {
             .lock()
             .unwrap()
             .adjust_decorations(config.window_decorations)?;
+        window.lock().unwrap().select_touch_events()?;
 
         let window_handle = Window::X11(XWindow::from_id(window_id));
 
@@ -1179,7 +1287,42 @@ impl XWindowInner {
         log::trace!("clear out self.window_id");
         self.window_id = xcb::x::Window::none();
     }
-    fn hide(&mut self) {}
+    fn hide(&mut self) {
+        // Withdraw the window, as described by ICCCM section 4.1.4:
+        // unmap it and let the window manager know via a synthetic
+        // UnmapNotify, so that it is also removed from the taskbar.
+        // show() maps it again.
+        let conn = self.conn();
+        conn.send_request_no_reply_log(&xcb::x::UnmapWindow {
+            window: self.window_id,
+        });
+        conn.send_request_no_reply_log(&xcb::x::SendEvent {
+            propagate: false,
+            destination: xcb::x::SendEventDest::Window(conn.root),
+            event_mask: xcb::x::EventMask::SUBSTRUCTURE_REDIRECT
+                | xcb::x::EventMask::SUBSTRUCTURE_NOTIFY,
+            event: &xcb::x::UnmapNotifyEvent::new(conn.root, self.window_id, false),
+        });
+        conn.flush().ok();
+    }
+    fn minimize(&mut self) {
+        // Ask the window manager to iconify the window,
+        // as described by ICCCM section 4.1.4
+        const ICONIC_STATE: u32 = 3;
+        let conn = self.conn();
+        conn.send_request_no_reply_log(&xcb::x::SendEvent {
+            propagate: false,
+            destination: xcb::x::SendEventDest::Window(conn.root),
+            event_mask: xcb::x::EventMask::SUBSTRUCTURE_REDIRECT
+                | xcb::x::EventMask::SUBSTRUCTURE_NOTIFY,
+            event: &xcb::x::ClientMessageEvent::new(
+                self.window_id,
+                conn.atom_wm_change_state,
+                xcb::x::ClientMessageData::Data32([ICONIC_STATE, 0, 0, 0, 0]),
+            ),
+        });
+        conn.flush().ok();
+    }
     fn show(&mut self) {
         self.conn().send_request_no_reply_log(&xcb::x::MapWindow {
             window: self.window_id,
@@ -1215,8 +1358,40 @@ impl XWindowInner {
     }
 
     fn config_did_change(&mut self, config: &ConfigHandle) {
+        let touch_changed = self.config.enable_touch_gestures != config.enable_touch_gestures;
         self.config = config.clone();
         let _ = self.adjust_decorations(config.window_decorations);
+        if touch_changed {
+            if let Err(err) = self.select_touch_events() {
+                log::error!("selecting touch events: {:#}", err);
+            }
+        }
+    }
+
+    /// Touches that we select are no longer emulated as pointer
+    /// events by the X server, so we only select them when the
+    /// touch gestures are enabled
+    fn select_touch_events(&self) -> anyhow::Result<()> {
+        let conn = self.conn();
+        if !conn.has_xinput_touch {
+            return Ok(());
+        }
+        let mask = if self.config.enable_touch_gestures {
+            xcb::xinput::XiEventMask::TOUCH_BEGIN
+                | xcb::xinput::XiEventMask::TOUCH_UPDATE
+                | xcb::xinput::XiEventMask::TOUCH_END
+        } else {
+            xcb::xinput::XiEventMask::empty()
+        };
+        conn.send_request_no_reply(&xcb::xinput::XiSelectEvents {
+            window: self.window_id,
+            masks: &[xcb::xinput::EventMaskBuf::new(
+                xcb::xinput::Device::AllMaster,
+                &[mask],
+            )],
+        })
+        .context("XInput::SelectEvents")?;
+        Ok(())
     }
 
     fn set_window_position(&self, coords: ScreenPoint) {
@@ -1250,6 +1425,27 @@ impl XWindowInner {
         });
     }
 
+    /// Ask the window manager to activate the window
+    fn focus(&mut self) {
+        let conn = self.conn();
+
+        conn.send_request_no_reply_log(&xcb::x::SendEvent {
+            propagate: false,
+            destination: xcb::x::SendEventDest::Window(conn.root),
+            event_mask: xcb::x::EventMask::SUBSTRUCTURE_REDIRECT
+                | xcb::x::EventMask::SUBSTRUCTURE_NOTIFY,
+            event: &xcb::x::ClientMessageEvent::new(
+                self.window_id,
+                conn.atom_net_active_window,
+                xcb::x::ClientMessageData::Data32([
+                    1, // source indication: normal application
+                    0, // CurrentTime
+                    0, 0, 0,
+                ]),
+            ),
+        });
+    }
+
     /// Change the title for the window manager
     fn set_title(&mut self, title: &str) {
         if title == self.title {
@@ -1324,10 +1520,85 @@ impl XWindowInner {
             });
     }
 
-    fn set_resize_increments(&mut self, x: u16, y: u16) -> anyhow::Result<()> {
+    fn set_input_region(&mut self, region: &InputRegion) -> anyhow::Result<()> {
+        self.input_region = region.clone();
+        self.apply_shape()
+    }
+
+    fn set_corner_radius(&mut self, radius: Option<f32>) -> anyhow::Result<()> {
+        if radius.is_none() && self.corner_radius.is_none() {
+            return Ok(());
+        }
+        self.corner_radius = radius;
+        self.apply_shape()
+    }
+
+    /// Shapes the window to match the corner radius, and restricts
+    /// input to the input region, or to the rounded shape if no
+    /// input region has been set.  The bounding shape is aliased,
+    /// but keeps the corners clear when there is no compositor
+    /// to make use of the transparent corners that are rendered.
+    fn apply_shape(&self) -> anyhow::Result<()> {
+        use xcb::shape;
+        let conn = self.conn();
+        if !conn.active_extensions().any(|e| e == xcb::Extension::Shape) {
+            anyhow::bail!("the X server does not support the SHAPE extension");
+        }
+
+        let rounded = match self.corner_radius {
+            Some(radius) => InputRegion::rounded(self.width as usize, self.height as usize, radius),
+            None => InputRegion::Everywhere,
+        };
+        self.set_shape(shape::Sk::Bounding, &rounded)?;
+        if self.input_region == InputRegion::Everywhere {
+            self.set_shape(shape::Sk::Input, &rounded)
+        } else {
+            self.set_shape(shape::Sk::Input, &self.input_region)
+        }
+    }
+
+    fn set_shape(&self, kind: xcb::shape::Sk, region: &InputRegion) -> anyhow::Result<()> {
+        use xcb::shape;
+        let conn = self.conn();
+        let rects: Vec<xcb::x::Rectangle> = match region {
+            InputRegion::Everywhere => {
+                // Setting the mask to None restores the default shape
+                return conn.send_request_no_reply(&shape::Mask {
+                    operation: shape::So::Set,
+                    destination_kind: kind,
+                    destination_window: self.window_id,
+                    x_offset: 0,
+                    y_offset: 0,
+                    source_bitmap: xcb::x::Pixmap::none(),
+                });
+            }
+            InputRegion::Nowhere => vec![],
+            InputRegion::Rects(rects) => rects
+                .iter()
+                .map(|r| xcb::x::Rectangle {
+                    x: r.origin.x as i16,
+                    y: r.origin.y as i16,
+                    width: r.size.width as u16,
+                    height: r.size.height as u16,
+                })
+                .collect(),
+        };
+
+        conn.send_request_no_reply(&shape::Rectangles {
+            operation: shape::So::Set,
+            destination_kind: kind,
+            ordering: xcb::x::ClipOrdering::Unsorted,
+            destination_window: self.window_id,
+            x_offset: 0,
+            y_offset: 0,
+            rectangles: &rects,
+        })
+    }
+
+    fn set_resize_increments(&mut self, incr: ResizeIncrement) -> anyhow::Result<()> {
         use xcb_util::*;
         let hints = xcb_size_hints_t {
-            flags: XCB_ICCCM_SIZE_HINT_P_RESIZE_INC,
+            flags: XCB_ICCCM_SIZE_HINT_P_RESIZE_INC | XCB_ICCCM_SIZE_HINT_BASE_SIZE,
             x: 0,
             y: 0,
             width: 0,
@@ -1336,14 +1607,14 @@ impl XWindowInner {
             min_height: 0,
             max_width: 0,
             max_height: 0,
-            width_inc: x.into(),
-            height_inc: y.into(),
+            width_inc: incr.x.into(),
+            height_inc: incr.y.into(),
             min_aspect_num: 0,
             min_aspect_den: 0,
             max_aspect_num: 0,
             max_aspect_den: 0,
-            base_width: 0,
-            base_height: 0,
+            base_width: incr.base_width.into(),
+            base_height: incr.base_height.into(),
             win_gravity: 0,
         };
 
@@ -1420,6 +1691,13 @@ impl WindowOps for XWindow {
         });
     }
 
+    fn minimize(&self) {
+        XConnection::with_window_inner(self.0, |inner| {
+            inner.minimize();
+            Ok(())
+        });
+    }
+
     fn toggle_fullscreen(&self) {
         XConnection::with_window_inner(self.0, |inner| {
             inner.toggle_fullscreen();
@@ -1500,6 +1778,29 @@ impl WindowOps for XWindow {
         });
     }
 
+    fn get_window_position(&self) -> Future<Option<ScreenPoint>> {
+        XConnection::with_window_inner(self.0, |inner| {
+            let conn = inner.conn();
+            let reply = conn.send_and_wait_request(&xcb::x::TranslateCoordinates {
+                src_window: inner.window_id,
+                dst_window: conn.root,
+                src_x: 0,
+                src_y: 0,
+            })?;
+            Ok(Some(ScreenPoint::new(
+                reply.dst_x() as isize,
+                reply.dst_y() as isize,
+            )))
+        })
+    }
+
+    fn focus(&self) {
+        XConnection::with_window_inner(self.0, |inner| {
+            inner.focus();
+            Ok(())
+        });
+    }
+
     fn set_text_cursor_position(&self, cursor: Rect) {
         XConnection::with_window_inner(self.0, move |inner| {
             inner.set_text_cursor_position(cursor);
@@ -1514,9 +1815,27 @@ impl WindowOps for XWindow {
         });
     }
 
-    fn set_resize_increments(&self, x: u16, y: u16) {
+    fn set_input_region(&self, region: InputRegion) {
+        XConnection::with_window_inner(self.0, move |inner| {
+            if let Err(err) = inner.set_input_region(&region) {
+                log::error!("set_input_region failed: {:#}", err);
+            }
+            Ok(())
+        });
+    }
+
+    fn set_corner_radius(&self, radius: Option<f32>) {
+        XConnection::with_window_inner(self.0, move |inner| {
+            if let Err(err) = inner.set_corner_radius(radius) {
+                log::error!("set_corner_radius failed: {:#}", err);
+            }
+            Ok(())
+        });
+    }
+
+    fn set_resize_increments(&self, incr: ResizeIncrement) {
         XConnection::with_window_inner(self.0, move |inner| {
-            if let Err(err) = inner.set_resize_increments(x, y) {
+            if let Err(err) = inner.set_resize_increments(incr) {
                 log::error!("set_resize_increments failed: {:#}", err);
             }
             Ok(())
@@ -8,9 +8,10 @@ use crate::os::wayland::window::WaylandWindow;
 use crate::os::x11::connection::XConnection;
 use crate::os::x11::window::XWindow;
 use crate::screen::Screens;
+use crate::tray::TrayIcon;
 use crate::{
-    Appearance, Clipboard, MouseCursor, Rect, RequestedWindowGeometry, ScreenPoint, WindowEvent,
-    WindowOps,
+    Appearance, Clipboard, MouseCursor, Rect, RequestedWindowGeometry, ResizeIncrement,
+    ScreenPoint, WindowEvent, WindowOps,
 };
 use async_trait::async_trait;
 use config::ConfigHandle;
@@ -157,6 +158,10 @@ impl ConnectionOps for Connection {
         }
     }
 
+    fn set_tray_icon(&self, tray: Option<TrayIcon>) -> anyhow::Result<()> {
+        crate::os::status_notifier::set_tray_icon(tray)
+    }
+
     fn screens(&self) -> anyhow::Result<Screens> {
         match self {
             Self::X11(x) => x.screens(),
@@ -246,6 +251,14 @@ impl WindowOps for Window {
         }
     }
 
+    fn minimize(&self) {
+        match self {
+            Self::X11(x) => x.minimize(),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(w) => w.minimize(),
+        }
+    }
+
     fn toggle_fullscreen(&self) {
         match self {
             Self::X11(x) => x.toggle_fullscreen(),
@@ -286,11 +299,27 @@ impl WindowOps for Window {
         }
     }
 
-    fn set_resize_increments(&self, x: u16, y: u16) {
+    fn set_resize_increments(&self, incr: ResizeIncrement) {
         match self {
-            Self::X11(x11) => x11.set_resize_increments(x, y),
+            Self::X11(x11) => x11.set_resize_increments(incr),
             #[cfg(feature = "wayland")]
-            Self::Wayland(w) => w.set_resize_increments(x, y),
+            Self::Wayland(w) => w.set_resize_increments(incr),
+        }
+    }
+
+    fn set_input_region(&self, region: crate::InputRegion) {
+        match self {
+            Self::X11(x) => x.set_input_region(region),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(w) => w.set_input_region(region),
+        }
+    }
+
+    fn set_corner_radius(&self, radius: Option<f32>) {
+        match self {
+            Self::X11(x) => x.set_corner_radius(radius),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(w) => w.set_corner_radius(radius),
         }
     }
 
@@ -350,6 +379,22 @@ impl WindowOps for Window {
         }
     }
 
+    fn get_window_position(&self) -> Future<Option<ScreenPoint>> {
+        match self {
+            Self::X11(x) => x.get_window_position(),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(w) => w.get_window_position(),
+        }
+    }
+
+    fn focus(&self) {
+        match self {
+            Self::X11(x) => x.focus(),
+            #[cfg(feature = "wayland")]
+            Self::Wayland(w) => w.focus(),
+        }
+    }
+
     fn set_text_cursor_position(&self, cursor: Rect) {
         match self {
             Self::X11(x) => x.set_text_cursor_position(cursor),
@@ -1,13 +1,16 @@
 #![cfg(all(unix, not(target_os = "macos")))]
 
 //! <https://github.com/flatpak/xdg-desktop-portal/blob/main/data/org.freedesktop.portal.Settings.xml>
+//! <https://github.com/flatpak/xdg-desktop-portal/blob/main/data/org.freedesktop.portal.FileChooser.xml>
 
 use crate::{Appearance, Connection, ConnectionOps};
 use anyhow::Context;
 use futures_util::stream::StreamExt;
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use zbus::dbus_proxy;
-use zvariant::OwnedValue;
+use zvariant::{OwnedObjectPath, OwnedValue, Value};
 
 #[dbus_proxy(
     interface = "org.freedesktop.portal.Settings",
@@ -73,3 +76,177 @@ pub fn subscribe() {
     })
     .detach();
 }
+
+#[dbus_proxy(
+    interface = "org.freedesktop.portal.FileChooser",
+    default_service = "org.freedesktop.portal.Desktop",
+    default_path = "/org/freedesktop/portal/desktop"
+)]
+trait PortalFileChooser {
+    fn OpenFile(
+        &self,
+        parent_window: &str,
+        title: &str,
+        options: HashMap<&str, Value<'_>>,
+    ) -> zbus::Result<OwnedObjectPath>;
+
+    fn SaveFile(
+        &self,
+        parent_window: &str,
+        title: &str,
+        options: HashMap<&str, Value<'_>>,
+    ) -> zbus::Result<OwnedObjectPath>;
+}
+
+#[dbus_proxy(
+    interface = "org.freedesktop.portal.Request",
+    default_service = "org.freedesktop.portal.Desktop"
+)]
+trait PortalRequest {
+    #[dbus_proxy(signal)]
+    fn Response(&self, response: u32, results: HashMap<String, OwnedValue>) -> Result<()>;
+}
+
+/// A named set of glob patterns used to filter the files
+/// offered by the file chooser
+#[derive(Debug, Clone, Default)]
+pub struct FileFilter {
+    pub name: String,
+    pub patterns: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FilePickerOptions {
+    pub title: String,
+    pub accept_label: Option<String>,
+    pub multiple: bool,
+    pub directory: bool,
+    /// Show a save dialog rather than an open dialog
+    pub save: bool,
+    /// The suggested file name for a save dialog
+    pub current_name: Option<String>,
+    pub current_folder: Option<PathBuf>,
+    pub filters: Vec<FileFilter>,
+}
+
+/// Show the portal file chooser and wait for the user to make
+/// their choice.  Returns None if the user cancelled the dialog.
+pub async fn pick_file(options: FilePickerOptions) -> anyhow::Result<Option<Vec<PathBuf>>> {
+    let connection = zbus::ConnectionBuilder::session()?.build().await?;
+    let proxy = PortalFileChooserProxy::new(&connection)
+        .await
+        .context("make proxy")?;
+
+    // The portal returns the path of a Request object that will
+    // emit a Response signal once the user is done.  In order to
+    // avoid racing with that signal, we compute the path that the
+    // portal will use ahead of time and subscribe before making
+    // the call, as recommended by the portal documentation.
+    // The token must be unique to this request, so that concurrent
+    // requests don't pick up each other's responses.
+    static NEXT_TOKEN: AtomicUsize = AtomicUsize::new(0);
+    let token = format!(
+        "wezterm_{}_{}",
+        std::process::id(),
+        NEXT_TOKEN.fetch_add(1, Ordering::Relaxed)
+    );
+    let sender = connection
+        .unique_name()
+        .ok_or_else(|| anyhow::anyhow!("dbus connection has no unique name"))?
+        .trim_start_matches(':')
+        .replace('.', "_");
+    let handle = format!("/org/freedesktop/portal/desktop/request/{sender}/{token}");
+    let request = PortalRequestProxy::builder(&connection)
+        .path(handle.as_str())?
+        .build()
+        .await
+        .context("make request proxy")?;
+    let mut responses = request.receive_Response().await?;
+
+    let mut opts: HashMap<&str, Value<'_>> = HashMap::new();
+    opts.insert("handle_token", Value::from(token.as_str()));
+    opts.insert("modal", Value::from(true));
+    if let Some(label) = &options.accept_label {
+        opts.insert("accept_label", Value::from(label.as_str()));
+    }
+    if !options.save {
+        opts.insert("multiple", Value::from(options.multiple));
+        opts.insert("directory", Value::from(options.directory));
+    }
+    if let Some(name) = &options.current_name {
+        opts.insert("current_name", Value::from(name.as_str()));
+    }
+    if let Some(folder) = &options.current_folder {
+        // The portal wants a nul terminated byte string
+        let mut bytes = folder.to_string_lossy().as_bytes().to_vec();
+        bytes.push(0);
+        opts.insert("current_folder", Value::from(bytes));
+    }
+    if !options.filters.is_empty() {
+        let filters: Vec<(String, Vec<(u32, String)>)> = options
+            .filters
+            .iter()
+            .map(|filter| {
+                (
+                    filter.name.clone(),
+                    filter
+                        .patterns
+                        .iter()
+                        .map(|pattern| (0u32, pattern.clone()))
+                        .collect(),
+                )
+            })
+            .collect();
+        opts.insert("filters", Value::from(filters));
+    }
+
+    if options.save {
+        proxy
+            .SaveFile("", &options.title, opts)
+            .await
+            .context("SaveFile")?;
+    } else {
+        proxy
+            .OpenFile("", &options.title, opts)
+            .await
+            .context("OpenFile")?;
+    }
+
+    let signal = responses
+        .next()
+        .await
+        .ok_or_else(|| anyhow::anyhow!("portal did not respond to file chooser request"))?;
+    let args = signal.args()?;
+
+    // 0 is success, 1 is user cancelled, 2 is some other failure
+    match args.response {
+        0 => {}
+        1 => return Ok(None),
+        n => anyhow::bail!("file chooser request failed with response code {n}"),
+    }
+
+    let mut uris = vec![];
+    if let Some(value) = args.results.get("uris") {
+        match &**value {
+            Value::Array(array) => {
+                for item in array.get() {
+                    match item {
+                        Value::Str(uri) => uris.push(uri.as_str().to_string()),
+                        _ => anyhow::bail!("unexpected uri in response: {item:?}"),
+                    }
+                }
+            }
+            _ => anyhow::bail!("unexpected uris in response: {value:?}"),
+        }
+    }
+
+    let mut paths = vec![];
+    for uri in uris {
+        let url = url::Url::parse(&uri).with_context(|| format!("parsing uri {uri}"))?;
+        let path = url
+            .to_file_path()
+            .map_err(|_| anyhow::anyhow!("{uri} is not a local file"))?;
+        paths.push(path);
+    }
+    Ok(Some(paths))
+}
@@ -0,0 +1,77 @@
+//! Requests that the system makes of the application on behalf of
+//! the user, such as selecting "New WezTerm Tab Here" from the
+//! Services menu of the Finder on macOS, or a Shortcuts action that
+//! opens a `wezterm://` URL.
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+use url::Url;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceRequest {
+    /// Open a new tab whose working directory is `cwd`
+    NewTab { cwd: Option<PathBuf> },
+    /// Open a new window whose working directory is `cwd`
+    NewWindow { cwd: Option<PathBuf> },
+    /// Run `args` in a new pane
+    RunCommand {
+        args: Vec<String>,
+        cwd: Option<PathBuf>,
+    },
+}
+
+impl ServiceRequest {
+    /// Parses a `wezterm://` URL.  The supported forms are:
+    ///
+    /// * `wezterm://new-tab?cwd=/some/dir`
+    /// * `wezterm://new-window?cwd=/some/dir`
+    /// * `wezterm://run?args=htop&args=-d&args=10&cwd=/some/dir`
+    ///
+    /// The `cwd` parameter is optional in each case.
+    pub fn from_url(url: &str) -> anyhow::Result<Self> {
+        let url = Url::parse(url)?;
+        if url.scheme() != "wezterm" {
+            anyhow::bail!("unsupported scheme {}", url.scheme());
+        }
+        let mut cwd = None;
+        let mut args = vec![];
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "cwd" => cwd = Some(PathBuf::from(value.into_owned())),
+                "args" => args.push(value.into_owned()),
+                _ => anyhow::bail!("unsupported parameter {} in {}", key, url),
+            }
+        }
+        match url.host_str() {
+            Some("new-tab") => Ok(Self::NewTab { cwd }),
+            Some("new-window") => Ok(Self::NewWindow { cwd }),
+            Some("run") if !args.is_empty() => Ok(Self::RunCommand { args, cwd }),
+            Some("run") => anyhow::bail!("{} does not specify any args", url),
+            _ => anyhow::bail!("unsupported request {}", url),
+        }
+    }
+}
+
+pub type ServiceCallback = Rc<dyn Fn(ServiceRequest)>;
+
+thread_local! {
+    static CALLBACK: RefCell<Option<ServiceCallback>> = RefCell::new(None);
+}
+
+/// Sets the function that is called on the main thread to
+/// handle requests.  Requests that arrive while there is no
+/// callback are ignored.
+pub fn set_callback(callback: Option<ServiceCallback>) {
+    CALLBACK.with(|cb| *cb.borrow_mut() = callback);
+}
+
+/// Passes `request` to the callback.
+/// Must be called on the main thread.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn dispatch(request: ServiceRequest) {
+    let callback = CALLBACK.with(|cb| cb.borrow().clone());
+    match callback {
+        Some(callback) => callback(request),
+        None => log::warn!("ignoring {:?} as there is no handler", request),
+    }
+}
@@ -0,0 +1,392 @@
+//! Recognizes gestures in the touch events that are reported by
+//! the window backends.  Touch events pass through the
+//! `GestureRecognizer` before they are dispatched to the window, and
+//! the recognized gestures are dispatched after the touch event that
+//! completed them.  A long press is recognized while the finger is
+//! still held, by way of a `LongPressTimer` that the window is
+//! notified with once it has elapsed.
+use crate::{Point, ScreenPoint, TouchEvent, TouchPhase};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// How far, in pixels, a finger can move and still be considered
+/// to be held in place
+const TAP_SLOP: f32 = 16.;
+
+/// How long a finger must be held in place for a long press
+const LONG_PRESS: Duration = Duration::from_millis(500);
+
+/// How far, in pixels, a finger must travel for a swipe
+const SWIPE_DISTANCE: f32 = 120.;
+
+/// A swipe must be completed within this time; slower movements
+/// are not treated as gestures
+const SWIPE_DURATION: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Gesture {
+    /// A single finger briefly touched the window without moving
+    Tap {
+        coords: Point,
+        screen_coords: ScreenPoint,
+    },
+    /// A single finger was held in place
+    LongPress {
+        coords: Point,
+        screen_coords: ScreenPoint,
+    },
+    /// A single finger quickly moved across the window
+    Swipe(SwipeDirection),
+    /// Two fingers moved together.  The deltas are in pixels and
+    /// are positive when the fingers move right or down.
+    Scroll {
+        coords: Point,
+        screen_coords: ScreenPoint,
+        delta_x: f32,
+        delta_y: f32,
+    },
+    /// Two fingers moved apart or together.  The scale is relative
+    /// to the distance between the fingers at the previous event.
+    Pinch { scale: f32 },
+}
+
+struct Touch {
+    start: Point,
+    started: Instant,
+    coords: Point,
+    screen_coords: ScreenPoint,
+    /// Whether the finger has moved away from where it started
+    strayed: bool,
+}
+
+/// Checks whether a finger has been held in place for a long press.
+/// The window is notified with the timer once it elapses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LongPressTimer {
+    id: u64,
+    started: Instant,
+}
+
+impl LongPressTimer {
+    pub fn delay(&self) -> Duration {
+        LONG_PRESS
+    }
+}
+
+/// The centroid of two fingers and the distance between them
+#[derive(Debug, Clone, Copy)]
+struct Pair {
+    x: f32,
+    y: f32,
+    distance: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PairMode {
+    /// The fingers have yet to move far enough to tell whether
+    /// they are scrolling or pinching
+    Undecided,
+    Scroll,
+    Pinch,
+}
+
+#[derive(Default)]
+pub(crate) struct GestureRecognizer {
+    touches: BTreeMap<u64, Touch>,
+    /// The most fingers that have touched the window at the same
+    /// time since the first finger touched it
+    max_touches: usize,
+    /// The position of two fingers when they both touched the
+    /// window, and as of the previous gesture
+    pair: Option<(Pair, Pair, PairMode)>,
+    /// A long press timer that has yet to be started
+    pending_timer: Option<LongPressTimer>,
+    /// Whether a long press has been recognized for the finger
+    /// that is currently held
+    long_pressed: bool,
+}
+
+fn distance(a: Point, b: Point) -> f32 {
+    let dx = (a.x - b.x) as f32;
+    let dy = (a.y - b.y) as f32;
+    (dx * dx + dy * dy).sqrt()
+}
+
+impl GestureRecognizer {
+    pub fn process(&mut self, event: &TouchEvent) -> Vec<Gesture> {
+        self.process_at(event, Instant::now())
+    }
+
+    /// Returns the long press timer that should be started as a
+    /// result of the most recently processed touch event
+    pub fn take_long_press_timer(&mut self) -> Option<LongPressTimer> {
+        self.pending_timer.take()
+    }
+
+    /// Called when a long press timer has elapsed; recognizes a long
+    /// press if the finger is still held in place
+    pub fn long_press_elapsed(&mut self, timer: LongPressTimer) -> Option<Gesture> {
+        if self.max_touches != 1 || self.long_pressed {
+            return None;
+        }
+        let touch = self.touches.get(&timer.id)?;
+        if touch.started != timer.started || touch.strayed {
+            return None;
+        }
+        self.long_pressed = true;
+        Some(Gesture::LongPress {
+            coords: touch.coords,
+            screen_coords: touch.screen_coords,
+        })
+    }
+
+    fn current_pair(&self) -> Option<Pair> {
+        let mut touches = self.touches.values();
+        match (touches.next(), touches.next(), touches.next()) {
+            (Some(a), Some(b), None) => Some(Pair {
+                x: (a.coords.x + b.coords.x) as f32 / 2.,
+                y: (a.coords.y + b.coords.y) as f32 / 2.,
+                distance: distance(a.coords, b.coords),
+            }),
+            _ => None,
+        }
+    }
+
+    fn process_at(&mut self, event: &TouchEvent, now: Instant) -> Vec<Gesture> {
+        match event.phase {
+            TouchPhase::Began => {
+                self.touches.insert(
+                    event.id,
+                    Touch {
+                        start: event.coords,
+                        started: now,
+                        coords: event.coords,
+                        screen_coords: event.screen_coords,
+                        strayed: false,
+                    },
+                );
+                self.max_touches = self.max_touches.max(self.touches.len());
+                self.pending_timer = match self.max_touches {
+                    1 => Some(LongPressTimer {
+                        id: event.id,
+                        started: now,
+                    }),
+                    _ => None,
+                };
+                self.pair = match (self.max_touches, self.current_pair()) {
+                    (2, Some(pair)) => Some((pair, pair, PairMode::Undecided)),
+                    _ => None,
+                };
+                vec![]
+            }
+            TouchPhase::Moved => {
+                match self.touches.get_mut(&event.id) {
+                    Some(touch) => {
+                        touch.coords = event.coords;
+                        touch.screen_coords = event.screen_coords;
+                        if distance(touch.coords, touch.start) >= TAP_SLOP {
+                            touch.strayed = true;
+                        }
+                    }
+                    None => return vec![],
+                }
+                self.two_finger_gesture(event)
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                let touch = match self.touches.remove(&event.id) {
+                    Some(touch) => touch,
+                    None => return vec![],
+                };
+                // Lifting one of two fingers ends the gesture
+                self.pair.take();
+                if !self.touches.is_empty() {
+                    return vec![];
+                }
+                let max_touches = std::mem::take(&mut self.max_touches);
+                let long_pressed = std::mem::take(&mut self.long_pressed);
+                if max_touches != 1 || long_pressed || event.phase == TouchPhase::Cancelled {
+                    return vec![];
+                }
+                Self::single_finger_gesture(touch, event, now)
+                    .into_iter()
+                    .collect()
+            }
+        }
+    }
+
+    fn two_finger_gesture(&mut self, event: &TouchEvent) -> Vec<Gesture> {
+        let current = match self.current_pair() {
+            Some(pair) => pair,
+            None => return vec![],
+        };
+        let (start, previous, mode) = match self.pair.as_mut() {
+            Some((start, previous, mode)) => (*start, previous, mode),
+            None => return vec![],
+        };
+        if *mode == PairMode::Undecided {
+            let moved = ((current.x - start.x).powi(2) + (current.y - start.y).powi(2)).sqrt();
+            let spread = (current.distance - start.distance).abs();
+            if moved.max(spread) < TAP_SLOP {
+                return vec![];
+            }
+            *mode = if spread > moved {
+                PairMode::Pinch
+            } else {
+                PairMode::Scroll
+            };
+        }
+        let gesture = match *mode {
+            PairMode::Scroll => Gesture::Scroll {
+                coords: event.coords,
+                screen_coords: event.screen_coords,
+                delta_x: current.x - previous.x,
+                delta_y: current.y - previous.y,
+            },
+            PairMode::Pinch if previous.distance > 0. => Gesture::Pinch {
+                scale: current.distance / previous.distance,
+            },
+            _ => return vec![],
+        };
+        *previous = current;
+        vec![gesture]
+    }
+
+    fn single_finger_gesture(touch: Touch, event: &TouchEvent, now: Instant) -> Option<Gesture> {
+        let elapsed = now.saturating_duration_since(touch.started);
+        let dx = (event.coords.x - touch.start.x) as f32;
+        let dy = (event.coords.y - touch.start.y) as f32;
+        if distance(event.coords, touch.start) < TAP_SLOP {
+            // The long press is normally recognized by the timer
+            // while the finger is held, but the timer may be late
+            return Some(if elapsed >= LONG_PRESS {
+                Gesture::LongPress {
+                    coords: event.coords,
+                    screen_coords: event.screen_coords,
+                }
+            } else {
+                Gesture::Tap {
+                    coords: event.coords,
+                    screen_coords: event.screen_coords,
+                }
+            });
+        }
+        if elapsed > SWIPE_DURATION {
+            return None;
+        }
+        // The movement must be mostly along one axis
+        let direction = if dx.abs() >= SWIPE_DISTANCE && dx.abs() > dy.abs() * 2. {
+            if dx < 0. {
+                SwipeDirection::Left
+            } else {
+                SwipeDirection::Right
+            }
+        } else if dy.abs() >= SWIPE_DISTANCE && dy.abs() > dx.abs() * 2. {
+            if dy < 0. {
+                SwipeDirection::Up
+            } else {
+                SwipeDirection::Down
+            }
+        } else {
+            return None;
+        };
+        Some(Gesture::Swipe(direction))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn touch(id: u64, phase: TouchPhase, x: isize, y: isize) -> TouchEvent {
+        TouchEvent {
+            id,
+            phase,
+            coords: Point::new(x, y),
+            screen_coords: ScreenPoint::new(x, y),
+        }
+    }
+
+    #[test]
+    fn gestures() {
+        let mut recognizer = GestureRecognizer::default();
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+
+        recognizer.process_at(&touch(1, TouchPhase::Began, 10, 10), start);
+        assert_eq!(
+            recognizer.process_at(&touch(1, TouchPhase::Ended, 12, 10), ms(100)),
+            vec![Gesture::Tap {
+                coords: Point::new(12, 10),
+                screen_coords: ScreenPoint::new(12, 10),
+            }]
+        );
+
+        // A long press is recognized while the finger is held
+        recognizer.process_at(&touch(1, TouchPhase::Began, 10, 10), start);
+        let timer = recognizer.take_long_press_timer().unwrap();
+        assert_eq!(
+            recognizer.long_press_elapsed(timer),
+            Some(Gesture::LongPress {
+                coords: Point::new(10, 10),
+                screen_coords: ScreenPoint::new(10, 10),
+            })
+        );
+        assert!(recognizer.long_press_elapsed(timer).is_none());
+        assert!(recognizer
+            .process_at(&touch(1, TouchPhase::Ended, 10, 10), ms(800))
+            .is_empty());
+
+        // or when it is lifted, if the timer is late
+        recognizer.process_at(&touch(1, TouchPhase::Began, 10, 10), start);
+        assert!(matches!(
+            recognizer.process_at(&touch(1, TouchPhase::Ended, 10, 10), ms(800))[..],
+            [Gesture::LongPress { .. }]
+        ));
+
+        // but not once the finger has moved away
+        recognizer.process_at(&touch(1, TouchPhase::Began, 10, 10), start);
+        let timer = recognizer.take_long_press_timer().unwrap();
+        recognizer.process_at(&touch(1, TouchPhase::Moved, 50, 10), ms(100));
+        recognizer.process_at(&touch(1, TouchPhase::Moved, 10, 10), ms(200));
+        assert!(recognizer.long_press_elapsed(timer).is_none());
+        recognizer.process_at(&touch(1, TouchPhase::Cancelled, 10, 10), ms(600));
+
+        recognizer.process_at(&touch(1, TouchPhase::Began, 300, 10), start);
+        recognizer.process_at(&touch(1, TouchPhase::Moved, 200, 20), ms(50));
+        assert_eq!(
+            recognizer.process_at(&touch(1, TouchPhase::Ended, 100, 20), ms(100)),
+            vec![Gesture::Swipe(SwipeDirection::Left)]
+        );
+
+        // Two fingers moving down together scroll
+        recognizer.process_at(&touch(1, TouchPhase::Began, 100, 100), start);
+        recognizer.process_at(&touch(2, TouchPhase::Began, 200, 100), start);
+        assert!(recognizer
+            .process_at(&touch(1, TouchPhase::Moved, 100, 110), ms(10))
+            .is_empty());
+        assert!(matches!(
+            recognizer.process_at(&touch(2, TouchPhase::Moved, 200, 130), ms(20))[..],
+            [Gesture::Scroll { delta_x, delta_y, .. }] if delta_x == 0. && delta_y == 20.
+        ));
+        recognizer.process_at(&touch(1, TouchPhase::Ended, 100, 110), ms(30));
+        assert!(recognizer
+            .process_at(&touch(2, TouchPhase::Ended, 200, 130), ms(40))
+            .is_empty());
+
+        // Two fingers moving apart pinch
+        recognizer.process_at(&touch(1, TouchPhase::Began, 100, 100), start);
+        recognizer.process_at(&touch(2, TouchPhase::Began, 200, 100), start);
+        assert_eq!(
+            recognizer.process_at(&touch(2, TouchPhase::Moved, 300, 100), ms(10)),
+            vec![Gesture::Pinch { scale: 2. }]
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! An icon in the system tray; this is known as the notification
+//! area on Windows, a status item on macOS and a StatusNotifierItem
+//! on freedesktop systems.
+use crate::Image;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayMenuItem {
+    Item { label: String, enabled: bool },
+    Separator,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayEvent {
+    /// The primary action; typically a left click on the icon
+    Activate,
+    /// The menu item at this index was selected
+    MenuItem(usize),
+}
+
+pub type TrayCallback = Rc<dyn Fn(TrayEvent)>;
+
+#[derive(Clone)]
+pub struct TrayIcon {
+    pub tooltip: String,
+    pub icon: Image,
+    pub menu: Vec<TrayMenuItem>,
+    /// Called on the main thread when the user interacts with the icon
+    pub callback: TrayCallback,
+}
+
+thread_local! {
+    static CALLBACK: RefCell<Option<TrayCallback>> = RefCell::new(None);
+}
+
+pub(crate) fn set_callback(callback: Option<TrayCallback>) {
+    CALLBACK.with(|cb| *cb.borrow_mut() = callback);
+}
+
+/// Passes `event` to the callback of the current tray icon.
+/// Must be called on the main thread.
+pub(crate) fn dispatch(event: TrayEvent) {
+    // Take a copy so that the callback can replace the tray icon
+    let callback = CALLBACK.with(|cb| cb.borrow().clone());
+    if let Some(callback) = callback {
+        callback(event);
+    }
+}